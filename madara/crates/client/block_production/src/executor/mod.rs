@@ -1,4 +1,5 @@
 use crate::util::{BatchToExecute, BlockExecutionContext, ExecutionStats};
+use crate::{BlockProductionConfigUpdate, BlockProductionStatus};
 use anyhow::Context;
 use blockifier::{
     blockifier::transaction_executor::{TransactionExecutionOutput, TransactionExecutorResult},
@@ -30,12 +31,21 @@ pub struct ExecutorThreadHandle {
 pub enum ExecutorCommandError {
     #[error("Executor not running")]
     ChannelClosed,
+    #[error("Invalid block production config: {0}")]
+    InvalidConfig(&'static str),
 }
 
+pub type StatusCallback = oneshot::Sender<Result<BlockProductionStatus, ExecutorCommandError>>;
+
 #[derive(Debug)]
 pub enum ExecutorCommand {
     /// Force close the current block.
     CloseBlock(oneshot::Sender<Result<(), ExecutorCommandError>>),
+    /// Stop executing transactions and closing blocks, until resumed.
+    Pause(StatusCallback),
+    Resume(StatusCallback),
+    UpdateConfig(BlockProductionConfigUpdate, StatusCallback),
+    GetStatus(StatusCallback),
 }
 
 #[derive(Debug)]
@@ -49,6 +59,8 @@ pub enum ExecutorMessage {
     },
     BatchExecuted(BatchExecutionResult),
     EndBlock,
+    /// Block production was paused, resumed, or its config was updated.
+    StatusUpdated(BlockProductionStatus),
 }
 
 #[derive(Default, Debug)]
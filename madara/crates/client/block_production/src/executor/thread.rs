@@ -20,7 +20,8 @@ use mc_exec::{execution::TxInfo, LayeredStateAdaptor, MadaraBackendExecutionExt}
 use mc_mempool::L1DataProvider;
 use mp_convert::{Felt, ToFelt};
 
-use crate::util::{create_execution_context, BatchToExecute, BlockExecutionContext, ExecutionStats};
+use crate::util::{create_execution_context, next_l2_gas_price, BatchToExecute, BlockExecutionContext, ExecutionStats};
+use crate::BlockProductionStatus;
 
 struct ExecutorStateExecuting {
     exec_ctx: BlockExecutionContext,
@@ -34,6 +35,8 @@ struct ExecutorStateExecuting {
 struct ExecutorStateNewBlock {
     /// Keep the cached adaptor around to keep the cache around.
     state_adaptor: LayeredStateAdaptor,
+    /// L2 gas price of the new block. It depends on the utilization of the previous block.
+    l2_gas_price: u128,
 }
 
 /// Note: The reason this exists is because we want to create the new block execution context (meaning, the block header) as late as possible, as to have
@@ -61,6 +64,8 @@ pub struct ExecutorThread {
     incoming_batches: mpsc::Receiver<super::BatchToExecute>,
     replies_sender: mpsc::Sender<super::ExecutorMessage>,
     commands: mpsc::UnboundedReceiver<super::ExecutorCommand>,
+    /// Runtime block production settings, see [`crate::BlockProductionHandle`].
+    status: BlockProductionStatus,
 
     /// See `take_tx_batch`. When the mempool is empty, we will not be getting transactions.
     /// We still potentially want to emit empty blocks based on the block_time deadline.
@@ -85,6 +90,7 @@ impl ExecutorThread {
         commands: mpsc::UnboundedReceiver<super::ExecutorCommand>,
     ) -> anyhow::Result<Self> {
         Ok(Self {
+            status: BlockProductionStatus::from_chain_config(backend.chain_config()),
             backend,
             l1_data_provider,
             incoming_batches,
@@ -142,6 +148,66 @@ impl ExecutorThread {
         })
     }
 
+    /// While paused, we only wait for commands. Returns None when the block production task has stopped.
+    fn wait_command_while_paused(&mut self) -> Option<super::ExecutorCommand> {
+        tracing::debug!("Block production paused, waiting for command.");
+        self.wait_rt.block_on(async {
+            tokio::select! {
+                Some(cmd) = self.commands.recv() => Some(cmd),
+                _ = self.replies_sender.closed() => None,
+            }
+        })
+    }
+
+    /// Handles a command sent through the [`crate::BlockProductionHandle`]. Returns false when the block production
+    /// task has stopped.
+    fn handle_command(&mut self, command: super::ExecutorCommand, force_close: &mut bool) -> bool {
+        use super::ExecutorCommand;
+
+        let (callback, new_status) = match command {
+            ExecutorCommand::CloseBlock(callback) => {
+                *force_close = true;
+                let _ = callback.send(Ok(()));
+                return true;
+            }
+            ExecutorCommand::GetStatus(callback) => {
+                let _ = callback.send(Ok(self.status.clone()));
+                return true;
+            }
+            ExecutorCommand::Pause(callback) => {
+                (callback, Ok(BlockProductionStatus { paused: true, ..self.status.clone() }))
+            }
+            ExecutorCommand::Resume(callback) => {
+                (callback, Ok(BlockProductionStatus { paused: false, ..self.status.clone() }))
+            }
+            ExecutorCommand::UpdateConfig(update, callback) => (callback, update.apply(self.status.clone())),
+        };
+
+        let new_status = match new_status {
+            Ok(new_status) => new_status,
+            Err(err) => {
+                let _ = callback.send(Err(err));
+                return true;
+            }
+        };
+        let _ = callback.send(Ok(new_status.clone()));
+        if new_status == self.status {
+            return true;
+        }
+
+        if new_status.paused != self.status.paused {
+            if new_status.paused {
+                tracing::info!("⏸️  Block production paused");
+            } else {
+                tracing::info!("▶️  Block production resumed");
+            }
+        } else {
+            tracing::info!("⚙️  Block production config updated: {new_status:?}");
+        }
+        self.status = new_status.clone();
+        self.replies_sender.blocking_send(super::ExecutorMessage::StatusUpdated(new_status)).is_ok()
+    }
+
     /// We are making a new block - we need to put the hash of current_block_n-10 into the state diff.
     /// current_block_n-10 however might not be saved into the database yet. In that case, we have to wait.
     /// This shouldn't create a deadlock (cyclic wait) unless the database is in a weird state (?)
@@ -186,7 +252,12 @@ impl ExecutorThread {
         let mut cached_adaptor = cached_state.state;
         cached_adaptor.finish_block(state_diff, mem::take(&mut state.declared_classes))?;
 
-        Ok(ExecutorThreadState::NewBlock(ExecutorStateNewBlock { state_adaptor: cached_adaptor }))
+        let weights = *state.executor.bouncer.lock().expect("Bouncer lock poisoned").get_accumulated_weights();
+        let l2_gas_price =
+            next_l2_gas_price(&self.backend, state.exec_ctx.l1_gas_price.strk_l2_gas_price, Some(&weights));
+        tracing::debug!("L2 gas price of the next block: {l2_gas_price}");
+
+        Ok(ExecutorThreadState::NewBlock(ExecutorStateNewBlock { state_adaptor: cached_adaptor, l2_gas_price }))
     }
 
     /// Returns the initial state diff storage too. It is used to create the StartNewBlock message and transition to ExecutorState::Executing.
@@ -194,7 +265,12 @@ impl ExecutorThread {
         &mut self,
         state: ExecutorStateNewBlock,
     ) -> anyhow::Result<(ExecutorStateExecuting, HashMap<StorageEntry, Felt>)> {
-        let exec_ctx = create_execution_context(&self.l1_data_provider, &self.backend, state.state_adaptor.block_n());
+        let exec_ctx = create_execution_context(
+            &self.l1_data_provider,
+            &self.backend,
+            state.state_adaptor.block_n(),
+            state.l2_gas_price,
+        );
 
         // Create the TransactionExecution, but reuse the layered_state_adaptor.
         let mut executor =
@@ -224,15 +300,16 @@ impl ExecutorThread {
     }
 
     fn initial_state(&self) -> anyhow::Result<ExecutorThreadState> {
+        // The pending block has been closed at this point, its header has the gas prices of the latest block.
+        let parent_price = self.backend.latest_pending_block().header.l1_gas_price.strk_l2_gas_price;
         Ok(ExecutorThreadState::NewBlock(ExecutorStateNewBlock {
             state_adaptor: LayeredStateAdaptor::new(Arc::clone(&self.backend))?,
+            l2_gas_price: next_l2_gas_price(&self.backend, parent_price, None),
         }))
     }
 
     pub fn run(mut self) -> anyhow::Result<()> {
         let batch_size = self.backend.chain_config().block_production_concurrency.batch_size;
-        let block_time = self.backend.chain_config().block_time;
-        let no_empty_blocks = self.backend.chain_config().no_empty_blocks;
 
         // Initial state is ExecutorState::NewBlock, we don't yet have an execution state.
         let mut state = self.initial_state().context("Creating executor initial state")?;
@@ -240,7 +317,7 @@ impl ExecutorThread {
         // The batch of transactions to execute.
         let mut to_exec = BatchToExecute::with_capacity(batch_size);
 
-        let mut next_block_deadline = Instant::now() + block_time;
+        let mut block_start = Instant::now();
         let mut force_close = false;
         let mut block_empty = true;
 
@@ -251,22 +328,36 @@ impl ExecutorThread {
         // `blockifier` isn't really well optimized in this regard, but since we can't easily change its code (maybe we should?) we're
         //  still optimizing everything we have a hand on here in madara.
         loop {
+            if self.status.paused {
+                match self.wait_command_while_paused() {
+                    Some(command) if self.handle_command(command, &mut force_close) => continue,
+                    // Receiver closed
+                    _ => break Ok(()),
+                }
+            }
+
             // Take transactions to execute.
             if to_exec.len() < batch_size {
-                let wait_deadline = if block_empty && no_empty_blocks { None } else { Some(next_block_deadline) };
+                let next_block_deadline = block_start + self.status.block_time;
+                let wait_deadline =
+                    if block_empty && self.status.no_empty_blocks { None } else { Some(next_block_deadline) };
                 // should_wait: We don't want to wait if we already have transactions to process - but we would still like to fill up our batch if possible.
 
                 let taken = match self.wait_take_tx_batch(wait_deadline, /* should_wait */ to_exec.is_empty()) {
                     // Got a batch
                     WaitTxBatchOutcome::Batch(batch_to_execute) => batch_to_execute,
                     // Got a command
-                    WaitTxBatchOutcome::Command(executor_command) => match executor_command {
-                        super::ExecutorCommand::CloseBlock(callback) => {
-                            force_close = true;
-                            let _ = callback.send(Ok(()));
-                            Default::default()
+                    WaitTxBatchOutcome::Command(executor_command) => {
+                        if !self.handle_command(executor_command, &mut force_close) {
+                            // Receiver closed
+                            break Ok(());
+                        }
+                        if !force_close {
+                            // Re-evaluate the new status before executing anything.
+                            continue;
                         }
-                    },
+                        Default::default()
+                    }
                     // Channel closed. Exit gracefully.
                     WaitTxBatchOutcome::Exit => return Ok(()),
                 };
@@ -373,7 +464,7 @@ impl ExecutorThread {
             // This transitions the state machine from ExecutorState::Executing to ExecutorState::NewBlock.
 
             let now = Instant::now();
-            let block_time_deadline_reached = now >= next_block_deadline;
+            let block_time_deadline_reached = now >= block_start + self.status.block_time;
             if force_close || block_full || block_time_deadline_reached {
                 tracing::debug!(
                     "Ending block block_n={} (force_close={force_close}, block_full={block_full}, block_time_deadline_reached={block_time_deadline_reached})",
@@ -384,7 +475,7 @@ impl ExecutorThread {
                     // Receiver closed
                     break Ok(());
                 }
-                next_block_deadline = Instant::now() + block_time;
+                block_start = Instant::now();
                 state = self.end_block(execution_state).context("Ending block")?;
                 block_empty = true;
                 force_close = false;
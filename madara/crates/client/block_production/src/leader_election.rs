@@ -0,0 +1,579 @@
+//! Leader election for sequencer high availability.
+//!
+//! Several sequencer nodes can share a lease, stored in a [`LeaseBackend`]. Only the node holding the lease produces
+//! blocks, while the other nodes stay in standby and follow the leader through L2 sync. When the leader stops renewing
+//! its lease, a standby node acquires it and continues block production on top of the last closed block.
+//!
+//! To never produce conflicting blocks:
+//! - A node only considers itself leader for the lease ttl counted from the moment it _sent_ its last renewal, so it
+//!   always stops before the lease can be acquired by another node (assuming bounded clock drift between nodes).
+//! - Before closing a block, the leader renews its lease and records the number of the block it is about to close,
+//!   and it only closes the block if it is still the leader. A new leader waits until it has synced up to the highest
+//!   recorded block before producing anything, so a block saved by a leader that died right after is never produced
+//!   again by another node.
+//! - Every new holder of the lease gets a new term. A leader that sees the term change has lost the lease.
+
+use anyhow::Context;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::{server::conn::http1, service::service_fn, Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use mp_utils::service::ServiceContext;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::net::TcpListener;
+use url::Url;
+
+/// Returned by block production when this node lost the leader lease. The node should go back to standby.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("This node is not the sequencer leader")]
+pub struct NotLeader;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaseRequest {
+    pub node_id: String,
+    pub ttl_ms: u64,
+    /// Highest block the node is about to close or has closed, if any.
+    pub head_block_n: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseRequest {
+    pub node_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lease {
+    pub holder: String,
+    /// Incremented every time the lease changes holder.
+    pub term: u64,
+    /// Unix timestamp in milliseconds.
+    pub expires_at_ms: u64,
+    /// Highest block number recorded by the leaders before closing it. The block may not exist if its leader stopped
+    /// before saving it.
+    pub head_block_n: Option<u64>,
+}
+
+impl Lease {
+    pub fn is_held_by(&self, node_id: &str, now_ms: u64) -> bool {
+        self.holder == node_id && now_ms < self.expires_at_ms
+    }
+
+    /// The lease after an acquire or renew request. This is the same for every backend.
+    pub fn acquire(current: Option<Lease>, request: &LeaseRequest, now_ms: u64) -> Lease {
+        let head_block_n = |lease: &Lease| lease.head_block_n.max(request.head_block_n);
+        let expires_at_ms = now_ms.saturating_add(request.ttl_ms);
+        match current {
+            // Held by another node.
+            Some(lease) if now_ms < lease.expires_at_ms && lease.holder != request.node_id => lease,
+            // Renewal.
+            Some(lease) if now_ms < lease.expires_at_ms => {
+                Lease { head_block_n: head_block_n(&lease), expires_at_ms, ..lease }
+            }
+            // Expired or released: the lease changes term, even when the holder does not change.
+            Some(lease) => Lease {
+                holder: request.node_id.clone(),
+                term: lease.term + 1,
+                expires_at_ms,
+                head_block_n: head_block_n(&lease),
+            },
+            None => {
+                Lease { holder: request.node_id.clone(), term: 1, expires_at_ms, head_block_n: request.head_block_n }
+            }
+        }
+    }
+
+    /// The lease after a release request. Releasing a lease held by another node does nothing.
+    pub fn release(current: Option<Lease>, request: &ReleaseRequest) -> Option<Lease> {
+        current.map(|lease| if lease.holder == request.node_id { Lease { expires_at_ms: 0, ..lease } } else { lease })
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).expect("System time before unix epoch").as_millis() as u64
+}
+
+/// Storage for the leader lease shared by all the sequencer nodes. Implementations must apply every request
+/// atomically.
+#[async_trait::async_trait]
+pub trait LeaseBackend: Send + Sync {
+    /// Acquires or renews the lease, see [`Lease::acquire`]. Returns the lease after the request, which is held by
+    /// another node if it could not be acquired.
+    async fn acquire(&self, request: LeaseRequest) -> anyhow::Result<Lease>;
+    /// Releases the lease so that another node can acquire it without waiting for it to expire.
+    async fn release(&self, request: ReleaseRequest) -> anyhow::Result<()>;
+}
+
+/// Lease stored in memory. Useful for tests, and as the state of the lease server.
+#[derive(Debug, Default)]
+pub struct InMemoryLeaseBackend(Mutex<Option<Lease>>);
+
+#[async_trait::async_trait]
+impl LeaseBackend for InMemoryLeaseBackend {
+    async fn acquire(&self, request: LeaseRequest) -> anyhow::Result<Lease> {
+        let mut lease = self.0.lock().expect("Poisoned lock");
+        let new = Lease::acquire(lease.take(), &request, now_ms());
+        *lease = Some(new.clone());
+        Ok(new)
+    }
+
+    async fn release(&self, request: ReleaseRequest) -> anyhow::Result<()> {
+        let mut lease = self.0.lock().expect("Poisoned lock");
+        *lease = Lease::release(lease.take(), &request);
+        Ok(())
+    }
+}
+
+/// Lease stored as a json file, for sequencer nodes sharing a filesystem. Access to the file is serialized using a
+/// lock file next to it.
+#[derive(Debug, Clone)]
+pub struct FileLeaseBackend {
+    path: PathBuf,
+}
+
+/// A lock file older than this was left behind by a crashed process. Locks are only held while reading and writing
+/// the lease file, and waiting nodes take over a stale lock before [`LOCK_FILE_TIMEOUT`].
+const STALE_LOCK_FILE: Duration = Duration::from_secs(1);
+const LOCK_FILE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A lock file holding a token unique to its holder, so that a node never removes a lock it does not hold.
+struct LockFile {
+    path: PathBuf,
+    token: Vec<u8>,
+}
+
+impl LockFile {
+    fn acquire(path: PathBuf) -> anyhow::Result<Self> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).expect("System time before unix epoch").as_nanos();
+        let token = format!("{}.{nanos}.{}", std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed));
+
+        // The lock file is created with its content by hard linking a file only this node knows about, which fails
+        // if the lock file already exists.
+        let tmp_path = path.with_extension(format!("lock.{token}"));
+        std::fs::write(&tmp_path, &token)
+            .with_context(|| format!("Creating lease lock file {}", tmp_path.display()))?;
+        let res = Self::acquire_with(&path, &tmp_path, token.as_bytes());
+        let _ = std::fs::remove_file(&tmp_path);
+        res?;
+
+        Ok(Self { path, token: token.into_bytes() })
+    }
+
+    fn acquire_with(path: &Path, tmp_path: &Path, token: &[u8]) -> anyhow::Result<()> {
+        let start = Instant::now();
+        loop {
+            match std::fs::hard_link(tmp_path, path) {
+                Ok(()) => return Ok(()),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    if let Some(stale_token) = Self::stale_token(path) {
+                        tracing::warn!("Removing stale lease lock file {}", path.display());
+                        Self::take_over(path, &stale_token, token);
+                        continue;
+                    }
+                    anyhow::ensure!(
+                        start.elapsed() < LOCK_FILE_TIMEOUT,
+                        "Timed out waiting for lease lock file {}",
+                        path.display()
+                    );
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                Err(err) => return Err(err).with_context(|| format!("Creating lease lock file {}", path.display())),
+            }
+        }
+    }
+
+    /// The token of the lock file if it was left behind by a crashed process.
+    fn stale_token(path: &Path) -> Option<Vec<u8>> {
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+        if !modified.elapsed().is_ok_and(|age| age > STALE_LOCK_FILE) {
+            return None;
+        }
+        std::fs::read(path).ok()
+    }
+
+    /// Moves the stale lock file out of the way. The rename is atomic, so among the nodes seeing the same stale lock
+    /// only one moves it. If the moved file turns out not to be the stale lock, another node took the lock over in
+    /// between, and it is put back.
+    fn take_over(path: &Path, stale_token: &[u8], token: &[u8]) {
+        let moved_path = path.with_extension(format!("lock.stale.{}", String::from_utf8_lossy(token)));
+        if std::fs::rename(path, &moved_path).is_err() {
+            return;
+        }
+        if std::fs::read(&moved_path).is_ok_and(|moved| moved != stale_token) {
+            let _ = std::fs::hard_link(&moved_path, path);
+        }
+        let _ = std::fs::remove_file(&moved_path);
+    }
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        if std::fs::read(&self.path).is_ok_and(|token| token == self.token) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+impl FileLeaseBackend {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn update<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(Option<Lease>) -> (Option<Lease>, R) + Send + 'static,
+    ) -> anyhow::Result<R> {
+        let _lock = LockFile::acquire(self.path.with_extension("lock"))?;
+
+        let current = match std::fs::read(&self.path) {
+            Ok(content) => Some(serde_json::from_slice(&content).context("Parsing lease file")?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err).with_context(|| format!("Reading lease file {}", self.path.display())),
+        };
+        let (new, res) = f(current);
+
+        if let Some(new) = new {
+            // Write then rename, so that the lease file is never partially written.
+            let tmp_path = self.path.with_extension("tmp");
+            let mut file = std::fs::File::create(&tmp_path).context("Creating lease file")?;
+            file.write_all(&serde_json::to_vec(&new)?).context("Writing lease file")?;
+            file.sync_all().context("Writing lease file")?;
+            std::fs::rename(&tmp_path, &self.path).context("Writing lease file")?;
+        }
+        Ok(res)
+    }
+}
+
+#[async_trait::async_trait]
+impl LeaseBackend for FileLeaseBackend {
+    async fn acquire(&self, request: LeaseRequest) -> anyhow::Result<Lease> {
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || {
+            this.update(move |current| {
+                let new = Lease::acquire(current, &request, now_ms());
+                (Some(new.clone()), new)
+            })
+        })
+        .await?
+    }
+
+    async fn release(&self, request: ReleaseRequest) -> anyhow::Result<()> {
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || this.update(move |current| (Lease::release(current, &request), ()))).await?
+    }
+}
+
+/// Client for a lease served over http, see [`serve_lease_backend`].
+#[derive(Debug, Clone)]
+pub struct HttpLeaseBackend {
+    client: reqwest::Client,
+    url: Url,
+}
+
+impl HttpLeaseBackend {
+    pub fn new(url: Url, timeout: Duration) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder().timeout(timeout).build().context("Building http client")?;
+        Ok(Self { client, url })
+    }
+
+    async fn post<T: Serialize>(&self, path: &str, body: &T) -> anyhow::Result<reqwest::Response> {
+        let url = self.url.join(path).context("Building lease url")?;
+        let res = self.client.post(url).json(body).send().await.context("Sending lease request")?;
+        res.error_for_status().context("Lease request failed")
+    }
+}
+
+#[async_trait::async_trait]
+impl LeaseBackend for HttpLeaseBackend {
+    async fn acquire(&self, request: LeaseRequest) -> anyhow::Result<Lease> {
+        self.post("acquire", &request).await?.json().await.context("Parsing lease response")
+    }
+
+    async fn release(&self, request: ReleaseRequest) -> anyhow::Result<()> {
+        self.post("release", &request).await?;
+        Ok(())
+    }
+}
+
+/// A simple http server for a lease backend, with `POST /acquire` and `POST /release` endpoints taking json
+/// requests. Serving an [`InMemoryLeaseBackend`] is the easiest way to test leader election across nodes.
+pub async fn serve_lease_backend(
+    backend: Arc<dyn LeaseBackend>,
+    listener: TcpListener,
+    mut ctx: ServiceContext,
+) -> anyhow::Result<()> {
+    tracing::info!("🌐 Lease server started at {}", listener.local_addr().context("Getting the bound-to address")?);
+
+    while let Some(res) = ctx.run_until_cancelled(listener.accept()).await {
+        let Ok((stream, _)) = res else { continue };
+        let backend = Arc::clone(&backend);
+        tokio::task::spawn(async move {
+            let service = service_fn(move |req| lease_router(Arc::clone(&backend), req));
+            if let Err(err) = http1::Builder::new().serve_connection(TokioIo::new(stream), service).await {
+                tracing::error!("Error serving connection: {:#}", err);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+async fn lease_router(
+    backend: Arc<dyn LeaseBackend>,
+    req: Request<hyper::body::Incoming>,
+) -> Result<Response<Full<Bytes>>, std::convert::Infallible> {
+    let response = |status, body: Vec<u8>| {
+        let mut res = Response::new(Full::new(Bytes::from(body)));
+        *res.status_mut() = status;
+        Ok(res)
+    };
+
+    let (method, path) = (req.method().clone(), req.uri().path().to_owned());
+    let body = match req.into_body().collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(err) => return response(StatusCode::BAD_REQUEST, format!("{err:#}").into_bytes()),
+    };
+
+    let res = match (method, path.trim_end_matches('/')) {
+        (Method::POST, "/acquire") => match serde_json::from_slice(&body) {
+            Ok(request) => backend.acquire(request).await.and_then(|lease| Ok(serde_json::to_vec(&lease)?)),
+            Err(err) => return response(StatusCode::BAD_REQUEST, format!("{err:#}").into_bytes()),
+        },
+        (Method::POST, "/release") => match serde_json::from_slice(&body) {
+            Ok(request) => backend.release(request).await.map(|_| vec![]),
+            Err(err) => return response(StatusCode::BAD_REQUEST, format!("{err:#}").into_bytes()),
+        },
+        _ => return response(StatusCode::NOT_FOUND, vec![]),
+    };
+
+    match res {
+        Ok(body) => response(StatusCode::OK, body),
+        Err(err) => response(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}").into_bytes()),
+    }
+}
+
+#[derive(Debug, Default)]
+struct LeaderState {
+    term: u64,
+    /// This node considers itself leader until this instant.
+    valid_until: Option<Instant>,
+    /// Last block this node is about to close or has closed, sent with every renewal.
+    head_block_n: Option<u64>,
+}
+
+/// The view of the leader lease from one sequencer node. This is cheap to clone, and clones share their state.
+#[derive(Clone)]
+pub struct LeaderLease {
+    backend: Arc<dyn LeaseBackend>,
+    node_id: String,
+    ttl: Duration,
+    state: Arc<Mutex<LeaderState>>,
+}
+
+impl LeaderLease {
+    pub fn new(backend: Arc<dyn LeaseBackend>, node_id: impl Into<String>, ttl: Duration) -> Self {
+        Self { backend, node_id: node_id.into(), ttl, state: Default::default() }
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// The leader renews its lease at this interval, so that a few renewals can fail before it expires.
+    pub fn renew_interval(&self) -> Duration {
+        self.ttl / 3
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.state.lock().expect("Poisoned lock").valid_until.is_some_and(|t| Instant::now() < t)
+    }
+
+    /// Acquires or renews the lease. Returns the lease as stored in the backend, which tells whether this node is
+    /// now the leader, and the highest block recorded by the previous leaders.
+    ///
+    /// A leader that fails to renew its lease stays leader until its last renewal expires.
+    pub async fn try_acquire(&self, head_block_n: Option<u64>) -> anyhow::Result<Lease> {
+        let sent_at = Instant::now();
+        let request = LeaseRequest { node_id: self.node_id.clone(), ttl_ms: self.ttl.as_millis() as u64, head_block_n };
+        let lease = self.backend.acquire(request).await?;
+
+        let mut state = self.state.lock().expect("Poisoned lock");
+        let was_leader = state.valid_until.is_some_and(|t| Instant::now() < t);
+        state.valid_until = if !lease.is_held_by(&self.node_id, now_ms()) {
+            None
+        } else if was_leader && lease.term != state.term {
+            // The lease expired in between and might have been held by another node.
+            tracing::warn!("The sequencer leader lease changed term from {} to {}", state.term, lease.term);
+            None
+        } else {
+            state.term = lease.term;
+            Some(sent_at + self.ttl)
+        };
+        Ok(lease)
+    }
+
+    fn recorded_head(&self) -> Option<u64> {
+        self.state.lock().expect("Poisoned lock").head_block_n
+    }
+
+    /// Renews the lease before closing `block_n`, recording it as the head of the chain. Returns [`NotLeader`] if
+    /// this node is not the leader anymore, or if the lease could not be renewed, in which case it must not close the
+    /// block.
+    pub async fn ensure_leader(&self, block_n: u64) -> anyhow::Result<()> {
+        let head_block_n = {
+            let mut state = self.state.lock().expect("Poisoned lock");
+            state.head_block_n = state.head_block_n.max(Some(block_n));
+            state.head_block_n
+        };
+        if let Err(err) = self.try_acquire(head_block_n).await {
+            tracing::warn!("Failed to renew the sequencer leader lease: {err:#}");
+            return Err(NotLeader.into());
+        }
+        if !self.is_leader() {
+            return Err(NotLeader.into());
+        }
+        Ok(())
+    }
+
+    /// Renews the lease if this node is the leader. Returns [`NotLeader`] once this node is not the leader anymore.
+    pub async fn renew(&self) -> anyhow::Result<()> {
+        if let Err(err) = self.try_acquire(self.recorded_head()).await {
+            tracing::warn!("Failed to renew the sequencer leader lease: {err:#}");
+        }
+        if !self.is_leader() {
+            return Err(NotLeader.into());
+        }
+        Ok(())
+    }
+
+    /// Gives up the lease, so that a standby node can take over without waiting for it to expire.
+    pub async fn release(&self) -> anyhow::Result<()> {
+        self.state.lock().expect("Poisoned lock").valid_until = None;
+        self.backend.release(ReleaseRequest { node_id: self.node_id.clone() }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(node_id: &str, head_block_n: Option<u64>) -> LeaseRequest {
+        LeaseRequest { node_id: node_id.into(), ttl_ms: 1000, head_block_n }
+    }
+
+    #[test]
+    fn test_lease_acquire() {
+        let lease = Lease::acquire(None, &request("a", None), 0);
+        assert_eq!(lease, Lease { holder: "a".into(), term: 1, expires_at_ms: 1000, head_block_n: None });
+
+        // Renewal keeps the term and records the highest head.
+        let lease = Lease::acquire(Some(lease), &request("a", Some(5)), 500);
+        assert_eq!(lease, Lease { holder: "a".into(), term: 1, expires_at_ms: 1500, head_block_n: Some(5) });
+        let lease = Lease::acquire(Some(lease), &request("a", None), 600);
+        assert_eq!(lease.head_block_n, Some(5));
+
+        // Held by another node.
+        assert_eq!(Lease::acquire(Some(lease.clone()), &request("b", Some(8)), 1499), lease);
+
+        // Expired: the other node gets a new term, and the head is kept.
+        let lease = Lease::acquire(Some(lease), &request("b", None), 1500);
+        assert_eq!(lease, Lease { holder: "b".into(), term: 2, expires_at_ms: 2500, head_block_n: Some(5) });
+
+        // Release.
+        let lease = Lease::release(Some(lease), &ReleaseRequest { node_id: "a".into() }).unwrap();
+        assert_eq!(lease.expires_at_ms, 2500);
+        let lease = Lease::release(Some(lease), &ReleaseRequest { node_id: "b".into() }).unwrap();
+        assert!(!lease.is_held_by("b", 1600));
+        assert_eq!(Lease::acquire(Some(lease), &request("a", None), 1600).term, 3);
+    }
+
+    async fn test_leader_election(backend: Arc<dyn LeaseBackend>) {
+        let a = LeaderLease::new(Arc::clone(&backend), "a", Duration::from_secs(60));
+        let b = LeaderLease::new(Arc::clone(&backend), "b", Duration::from_secs(60));
+
+        assert_eq!(a.try_acquire(None).await.unwrap().holder, "a");
+        assert!(a.is_leader());
+        let lease = b.try_acquire(None).await.unwrap();
+        assert_eq!(lease.holder, "a");
+        assert!(!b.is_leader());
+        assert_eq!(b.ensure_leader(0).await.unwrap_err().downcast_ref(), Some(&NotLeader));
+        assert_eq!(a.try_acquire(None).await.unwrap().head_block_n, None);
+
+        // The block is recorded before it is closed.
+        a.ensure_leader(3).await.unwrap();
+        assert_eq!(b.try_acquire(None).await.unwrap().head_block_n, Some(3));
+        a.renew().await.unwrap();
+
+        // Hand over the lease.
+        a.release().await.unwrap();
+        assert!(!a.is_leader());
+        let lease = b.try_acquire(None).await.unwrap();
+        assert_eq!((lease.holder.as_str(), lease.term, lease.head_block_n), ("b", 2, Some(3)));
+        assert!(b.is_leader());
+        assert_eq!(a.renew().await.unwrap_err().downcast_ref(), Some(&NotLeader));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_lease_backend() {
+        test_leader_election(Arc::new(InMemoryLeaseBackend::default())).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_lease_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lease.json");
+        test_leader_election(Arc::new(FileLeaseBackend::new(&path))).await;
+
+        let lease: Lease = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(lease.holder, "b");
+        assert!(!path.with_extension("lock").exists());
+    }
+
+    #[tokio::test]
+    async fn test_file_lease_backend_stale_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lease.json");
+        let lock_path = path.with_extension("lock");
+
+        // Left behind by a crashed node.
+        let lock = std::fs::File::create(&lock_path).unwrap();
+        lock.set_modified(SystemTime::now() - STALE_LOCK_FILE * 2).unwrap();
+        drop(lock);
+
+        let lease = FileLeaseBackend::new(&path).acquire(request("a", None)).await.unwrap();
+        assert_eq!(lease.holder, "a");
+        assert!(!lock_path.exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_file_lease_backend_live_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lease.json");
+
+        let lock = LockFile::acquire(path.with_extension("lock")).unwrap();
+        let backend = FileLeaseBackend::new(&path);
+        let waiting = tokio::spawn(async move { backend.acquire(request("a", None)).await });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!waiting.is_finished());
+
+        drop(lock);
+        assert_eq!(waiting.await.unwrap().unwrap().holder, "a");
+    }
+
+    #[tokio::test]
+    async fn test_http_lease_backend() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let ctx = ServiceContext::new_for_testing();
+        let _server =
+            mp_utils::AbortOnDrop::spawn(serve_lease_backend(Arc::new(InMemoryLeaseBackend::default()), listener, ctx));
+
+        test_leader_election(Arc::new(HttpLeaseBackend::new(url, Duration::from_secs(5)).unwrap())).await;
+    }
+}
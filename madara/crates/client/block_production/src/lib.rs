@@ -3,31 +3,41 @@
 use crate::metrics::BlockProductionMetrics;
 use anyhow::Context;
 use blockifier::state::cached_state::{StateMaps, StorageEntry};
-use executor::{BatchExecutionResult, ExecutorCommand, ExecutorCommandError, ExecutorMessage};
+use executor::{BatchExecutionResult, ExecutorCommand, ExecutorMessage, StatusCallback};
 use futures::future::OptionFuture;
+use leader_election::LeaderLease;
 use mc_db::db_block_id::DbBlockId;
 use mc_db::MadaraBackend;
 use mc_exec::execution::TxInfo;
+use mc_exec::{execution_result_to_tx_trace, state_map_to_state_diff, ExecutionResult};
 use mc_mempool::{L1DataProvider, Mempool};
 use mp_block::header::PendingHeader;
 use mp_block::{BlockId, BlockTag, PendingFullBlock, TransactionWithReceipt};
+use mp_chain_config::ChainConfig;
 use mp_class::ConvertedClass;
 use mp_convert::ToFelt;
 use mp_receipt::{from_blockifier_execution_info, EventWithTransactionHash};
+use mp_rpc::TraceBlockTransactionsResult;
 use mp_state_update::DeclaredClassItem;
 use mp_transactions::TransactionWithHash;
+use mp_utils::serde::{
+    deserialize_duration, deserialize_optional_duration, serialize_duration, serialize_optional_duration,
+};
 use mp_utils::service::ServiceContext;
 use mp_utils::AbortOnDrop;
 use opentelemetry::KeyValue;
+use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt;
 use std::collections::HashMap;
 use std::mem;
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, oneshot};
-use util::{state_map_to_state_diff, AdditionalTxInfo, BatchToExecute, BlockExecutionContext, ExecutionStats};
+use util::{AdditionalTxInfo, BatchToExecute, BlockExecutionContext, ExecutionStats};
 
 mod executor;
+pub use executor::ExecutorCommandError;
+pub mod leader_election;
 pub mod metrics;
 mod util;
 
@@ -125,11 +135,20 @@ pub(crate) struct CurrentPendingState {
     // These are reset every pending tick.
     pub tx_executed_for_tick: Vec<Felt>,
     pub stats_for_tick: ExecutionStats,
+    /// Traces of the transactions added to the block, only collected when trace storage is enabled.
+    pub traces: Vec<TraceBlockTransactionsResult>,
 }
 
 impl CurrentPendingState {
     pub fn new(backend: Arc<MadaraBackend>, block: PendingBlockState, block_n: u64) -> Self {
-        Self { backend, block, block_n, tx_executed_for_tick: Default::default(), stats_for_tick: Default::default() }
+        Self {
+            backend,
+            block,
+            block_n,
+            tx_executed_for_tick: Default::default(),
+            stats_for_tick: Default::default(),
+            traces: Default::default(),
+        }
     }
     /// Process the execution result, merging it with the current pending state
     pub fn append_batch(&mut self, batch: BatchExecutionResult) {
@@ -148,6 +167,24 @@ impl CurrentPendingState {
                 let receipt = from_blockifier_execution_info(&execution_info, &blockifier_tx);
                 let converted_tx = TransactionWithHash::from(blockifier_tx.clone());
 
+                if self.backend.trace_storage_enabled() {
+                    let result = ExecutionResult {
+                        hash: blockifier_tx.tx_hash(),
+                        tx_type: TxInfo::tx_type(&blockifier_tx),
+                        fee_type: TxInfo::fee_type(&blockifier_tx),
+                        minimal_l1_gas: None,
+                        execution_info,
+                        state_diff: state_diff.clone().into(),
+                    };
+                    match execution_result_to_tx_trace(&result) {
+                        Ok(trace_root) => self
+                            .traces
+                            .push(TraceBlockTransactionsResult { trace_root, transaction_hash: converted_tx.hash }),
+                        // The block will be saved without traces, the rpc falls back to re-execution.
+                        Err(err) => tracing::warn!("Failed to make trace for tx {:#x}: {err:#}", converted_tx.hash),
+                    }
+                }
+
                 self.block.events.extend(
                     receipt
                         .events()
@@ -171,6 +208,8 @@ impl CurrentPendingState {
 pub enum BlockProductionStateNotification {
     ClosedBlock,
     UpdatedPendingBlock,
+    /// Block production was paused, resumed, or its config was updated through the [`BlockProductionHandle`].
+    StatusUpdated(BlockProductionStatus),
 }
 
 /// Little state machine that helps us following the state transitions the executor thread sends us.
@@ -184,21 +223,122 @@ pub(crate) enum TaskState {
     Executing(Box<CurrentPendingState>),
 }
 
+/// Block production settings that can be changed at runtime through the [`BlockProductionHandle`]. They are reset
+/// to the chain config values every time block production starts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockProductionStatus {
+    /// No transaction is executed and no block is closed while block production is paused. Transactions are still
+    /// accepted into the mempool.
+    pub paused: bool,
+    #[serde(serialize_with = "serialize_duration", deserialize_with = "deserialize_duration")]
+    pub block_time: Duration,
+    #[serde(serialize_with = "serialize_optional_duration", deserialize_with = "deserialize_optional_duration")]
+    pub pending_block_update_time: Option<Duration>,
+    pub no_empty_blocks: bool,
+}
+
+impl BlockProductionStatus {
+    pub(crate) fn from_chain_config(chain_config: &ChainConfig) -> Self {
+        Self {
+            paused: false,
+            block_time: chain_config.block_time,
+            pending_block_update_time: chain_config.pending_block_update_time,
+            no_empty_blocks: chain_config.no_empty_blocks,
+        }
+    }
+}
+
+/// Changes to the block production settings. Fields left to [`None`] are not changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockProductionConfigUpdate {
+    #[serde(default, deserialize_with = "deserialize_optional_duration")]
+    pub block_time: Option<Duration>,
+    #[serde(default, deserialize_with = "deserialize_optional_duration")]
+    pub pending_block_update_time: Option<Duration>,
+    #[serde(default)]
+    pub no_empty_blocks: Option<bool>,
+}
+
+impl BlockProductionConfigUpdate {
+    pub(crate) fn apply(self, status: BlockProductionStatus) -> Result<BlockProductionStatus, ExecutorCommandError> {
+        if self.block_time.is_some_and(|t| t.is_zero()) {
+            return Err(ExecutorCommandError::InvalidConfig("Block time cannot be zero"));
+        }
+        if self.pending_block_update_time.is_some_and(|t| t.is_zero()) {
+            return Err(ExecutorCommandError::InvalidConfig("Pending block update time cannot be zero"));
+        }
+        Ok(BlockProductionStatus {
+            block_time: self.block_time.unwrap_or(status.block_time),
+            pending_block_update_time: self.pending_block_update_time.or(status.pending_block_update_time),
+            no_empty_blocks: self.no_empty_blocks.unwrap_or(status.no_empty_blocks),
+            ..status
+        })
+    }
+}
+
+/// Remotely control block production. A handle stays valid when block production is restarted, see
+/// [`BlockProductionTask::with_handle`]. Commands fail with [`ExecutorCommandError::ChannelClosed`] while block
+/// production is not running.
 #[derive(Clone, Debug)]
-/// Remotely control block production.
 pub struct BlockProductionHandle {
     /// Commands to executor task.
-    executor_commands: mpsc::UnboundedSender<executor::ExecutorCommand>,
+    executor_commands: Arc<RwLock<mpsc::UnboundedSender<executor::ExecutorCommand>>>,
+}
+
+impl Default for BlockProductionHandle {
+    /// A handle that is not connected to any block production task yet.
+    fn default() -> Self {
+        let (sender, _) = mpsc::unbounded_channel();
+        Self { executor_commands: Arc::new(RwLock::new(sender)) }
+    }
 }
 
 impl BlockProductionHandle {
+    fn send_command(&self, command: ExecutorCommand) -> Result<(), ExecutorCommandError> {
+        self.executor_commands
+            .read()
+            .expect("Poisoned lock")
+            .send(command)
+            .map_err(|_| ExecutorCommandError::ChannelClosed)
+    }
+
+    async fn status_command(
+        &self,
+        command: impl FnOnce(StatusCallback) -> ExecutorCommand,
+    ) -> Result<BlockProductionStatus, ExecutorCommandError> {
+        let (sender, recv) = oneshot::channel();
+        self.send_command(command(sender))?;
+        recv.await.map_err(|_| ExecutorCommandError::ChannelClosed)?
+    }
+
     pub async fn close_block(&self) -> Result<(), ExecutorCommandError> {
         let (sender, recv) = oneshot::channel();
-        self.executor_commands
-            .send(ExecutorCommand::CloseBlock(sender))
-            .map_err(|_| ExecutorCommandError::ChannelClosed)?;
+        self.send_command(ExecutorCommand::CloseBlock(sender))?;
         recv.await.map_err(|_| ExecutorCommandError::ChannelClosed)?
     }
+
+    /// Stops executing transactions and closing blocks. The current pending block is kept open.
+    pub async fn pause(&self) -> Result<BlockProductionStatus, ExecutorCommandError> {
+        self.status_command(ExecutorCommand::Pause).await
+    }
+
+    pub async fn resume(&self) -> Result<BlockProductionStatus, ExecutorCommandError> {
+        self.status_command(ExecutorCommand::Resume).await
+    }
+
+    /// Changes the block time, pending block update time or empty block production. A new block time applies to
+    /// the current block.
+    pub async fn update_config(
+        &self,
+        update: BlockProductionConfigUpdate,
+    ) -> Result<BlockProductionStatus, ExecutorCommandError> {
+        self.status_command(|callback| ExecutorCommand::UpdateConfig(update, callback)).await
+    }
+
+    pub async fn status(&self) -> Result<BlockProductionStatus, ExecutorCommandError> {
+        self.status_command(ExecutorCommand::GetStatus).await
+    }
 }
 
 /// The block production task consumes transactions from the mempool in batches.
@@ -217,6 +357,7 @@ pub struct BlockProductionTask {
     state_notifications: Option<mpsc::UnboundedSender<BlockProductionStateNotification>>,
     handle: BlockProductionHandle,
     executor_commands_recv: Option<mpsc::UnboundedReceiver<executor::ExecutorCommand>>,
+    leader_lease: Option<LeaderLease>,
 }
 
 impl BlockProductionTask {
@@ -233,12 +374,28 @@ impl BlockProductionTask {
             mempool,
             current_state: None,
             metrics,
-            handle: BlockProductionHandle { executor_commands: sender },
+            handle: BlockProductionHandle { executor_commands: Arc::new(RwLock::new(sender)) },
             state_notifications: None,
             executor_commands_recv: Some(recv),
+            leader_lease: None,
         }
     }
 
+    /// Only produce blocks while holding this leader lease. The task renews the lease while it runs, and stops with a
+    /// [`leader_election::NotLeader`] error when it loses it. See [`leader_election`].
+    pub fn with_leader_lease(mut self, leader_lease: LeaderLease) -> Self {
+        self.leader_lease = Some(leader_lease);
+        self
+    }
+
+    /// Control this task with an existing handle, which is disconnected from its previous task.
+    pub fn with_handle(mut self, handle: BlockProductionHandle) -> Self {
+        let sender = self.handle.executor_commands.read().expect("Poisoned lock").clone();
+        *handle.executor_commands.write().expect("Poisoned lock") = sender;
+        self.handle = handle;
+        self
+    }
+
     pub fn handle(&self) -> BlockProductionHandle {
         self.handle.clone()
     }
@@ -274,7 +431,24 @@ impl BlockProductionTask {
 
         self.backend.clear_pending_block().context("Error clearing pending block")?;
 
-        let block_n = self.backend.get_latest_block_n().context("Getting latest block n")?.map(|n| n + 1).unwrap_or(0);
+        let latest_block_n = self.backend.get_latest_block_n().context("Getting latest block n")?;
+        if self.leader_lease.is_some() {
+            // This pending block was synced from the previous leader, and may be outdated.
+            let latest_block_hash = match latest_block_n {
+                Some(block_n) => {
+                    self.backend.get_block_hash(&DbBlockId::Number(block_n))?.context("Block not found")?
+                }
+                None => Felt::ZERO,
+            };
+            if block.header.parent_block_hash != latest_block_hash {
+                tracing::debug!(
+                    "Dropping the pending block synced from the previous leader, it is not on top of the latest block."
+                );
+                return Ok(());
+            }
+        }
+
+        let block_n = latest_block_n.map(|n| n + 1).unwrap_or(0);
         self.close_and_save_block(block_n, block, declared_classes, vec![]).await?;
 
         Ok(())
@@ -290,6 +464,11 @@ impl BlockProductionTask {
         _txs_executed: Vec<Felt>,
     ) -> anyhow::Result<Felt> {
         tracing::debug!("Close and save block block_n={block_n}");
+        // The block is recorded in the lease before it is saved: a standby node taking over if this node stops right
+        // after saving it waits until it has synced it, instead of producing a conflicting block.
+        if let Some(leader_lease) = &self.leader_lease {
+            leader_lease.ensure_leader(block_n).await?;
+        }
         let start_time = Instant::now();
 
         let n_txs = block.transactions.len();
@@ -370,17 +549,29 @@ impl BlockProductionTask {
                     anyhow::bail!("Invalid executor state transition: expected current state to be Executing")
                 };
 
+                let n_txs = state.block.transactions.len();
                 let (block, classes) = state.block.into_full_block_with_classes(&self.backend, state.block_n)?;
                 let block_hash = self
                     .close_and_save_block(state.block_n, block, classes, state.tx_executed_for_tick)
                     .await
                     .context("Closing and saving block")?;
 
+                // Only save complete traces: a missing trace would make the rpc serve a partial block trace.
+                if self.backend.trace_storage_enabled() && state.traces.len() == n_txs {
+                    self.backend
+                        .store_block_traces(state.block_n, &state.traces)
+                        .context("Saving transaction traces")?;
+                }
+
                 self.current_state = Some(TaskState::NotExecuting {
                     latest_block_n: Some(state.block_n),
                     latest_block_hash: block_hash,
                 });
             }
+            ExecutorMessage::StatusUpdated(status) => {
+                tracing::debug!("Received ExecutorMessage::StatusUpdated status={status:?}");
+                self.send_state_notification(BlockProductionStateNotification::StatusUpdated(status));
+            }
         }
 
         Ok(())
@@ -443,9 +634,19 @@ impl BlockProductionTask {
         )
         .context("Starting executor thread")?;
 
-        let mut interval_pending_block_update = self.backend.chain_config().pending_block_update_time.map(|t| {
-            let mut int = tokio::time::interval(t);
-            int.reset(); // Skip the immediate first tick.
+        let pending_block_update_interval = |pending_block_update_time: Option<Duration>| {
+            pending_block_update_time.map(|t| {
+                let mut int = tokio::time::interval(t);
+                int.reset(); // Skip the immediate first tick.
+                int.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                int
+            })
+        };
+        let mut interval_pending_block_update =
+            pending_block_update_interval(self.backend.chain_config().pending_block_update_time);
+
+        let mut interval_lease_renew = self.leader_lease.as_ref().map(|lease| {
+            let mut int = tokio::time::interval(lease.renew_interval());
             int.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
             int
         });
@@ -503,6 +704,9 @@ impl BlockProductionTask {
 
                 // Process results from the execution
                 Some(reply) = executor.replies.recv() => {
+                    if let ExecutorMessage::StatusUpdated(status) = &reply {
+                        interval_pending_block_update = pending_block_update_interval(status.pending_block_update_time);
+                    }
                     self.process_reply(reply).await.context("Processing reply from executor thread")?;
                 }
 
@@ -511,6 +715,13 @@ impl BlockProductionTask {
                     self.store_pending_block().context("Storing pending block")?;
                 }
 
+                // Stop producing blocks once the leader lease is lost.
+                Some(_) = OptionFuture::from(interval_lease_renew.as_mut().map(|int| int.tick())) => {
+                    if let Some(leader_lease) = &self.leader_lease {
+                        leader_lease.renew().await?;
+                    }
+                }
+
                 // Bubble up errors from the executor thread, or graceful shutdown.
                 // We do this after processing all the replies to ensure we don't lose some of the state by accident.
                 res = executor.stop.recv() => return res.context("In executor thread"),
@@ -521,14 +732,21 @@ impl BlockProductionTask {
 
 #[cfg(test)]
 pub(crate) mod tests {
-    use crate::BlockProductionStateNotification;
-    use crate::{metrics::BlockProductionMetrics, util::state_map_to_state_diff, BlockProductionTask};
+    use crate::leader_election::{InMemoryLeaseBackend, LeaderLease, NotLeader};
+    use crate::{metrics::BlockProductionMetrics, BlockProductionTask};
+    use crate::{
+        BlockProductionConfigUpdate, BlockProductionHandle, BlockProductionStateNotification, BlockProductionStatus,
+        ExecutorCommandError,
+    };
+    use assert_matches::assert_matches;
     use blockifier::{
         bouncer::{BouncerConfig, BouncerWeights},
         state::cached_state::StateMaps,
     };
-    use mc_db::{db_block_id::DbBlockId, MadaraBackend};
+    use mc_db::{db_block_id::DbBlockId, MadaraBackend, TrieLogConfig};
     use mc_devnet::{Call, ChainGenesisDescription, DevnetKeys, DevnetPredeployedContract, Multicall, Selector};
+    use mc_exec::state_map_to_state_diff;
+    use mc_exec::verify::{verify_block_execution, BlockVerification};
     use mc_mempool::{Mempool, MempoolConfig, MockL1DataProvider};
     use mc_submit_tx::{SubmitTransaction, TransactionValidator, TransactionValidatorConfig};
     use mp_block::header::GasPrices;
@@ -583,6 +801,7 @@ pub(crate) mod tests {
         #[default(Duration::from_secs(30))] block_time: Duration,
         #[default(Some(Duration::from_secs(2)))] pending_block_update_time: Option<Duration>,
         #[default(false)] use_bouncer_weights: bool,
+        #[default(TrieLogConfig::default())] trie_log: TrieLogConfig,
     ) -> (
         Arc<MadaraBackend>,
         Arc<BlockProductionMetrics>,
@@ -595,9 +814,6 @@ pub(crate) mod tests {
             .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
             .with_test_writer()
             .try_init();
-        let mut genesis = ChainGenesisDescription::base_config().unwrap();
-        let contracts = genesis.add_devnet_contracts(10).unwrap();
-
         let chain_config: Arc<ChainConfig> = if use_bouncer_weights {
             let bouncer_weights = bouncer_weights();
 
@@ -611,7 +827,10 @@ pub(crate) mod tests {
             Arc::new(ChainConfig { block_time, pending_block_update_time, ..ChainConfig::madara_devnet() })
         };
 
-        let backend = MadaraBackend::open_for_testing(Arc::clone(&chain_config));
+        let mut genesis = ChainGenesisDescription::base_config(&chain_config).unwrap();
+        let contracts = genesis.add_devnet_contracts(10).unwrap();
+
+        let backend = MadaraBackend::open_for_testing_with_trie_log(Arc::clone(&chain_config), trie_log);
         genesis.build_and_store(&backend).await.unwrap();
 
         let mut l1_data_provider = MockL1DataProvider::new();
@@ -620,6 +839,7 @@ pub(crate) mod tests {
             strk_l1_gas_price: 128,
             eth_l1_data_gas_price: 128,
             strk_l1_data_gas_price: 128,
+            ..Default::default()
         });
         let l1_data_provider = Arc::new(l1_data_provider);
 
@@ -1872,6 +2092,52 @@ pub(crate) mod tests {
         assert_eq!(backend.get_latest_block_n().unwrap().unwrap(), 1);
     }
 
+    // Re-executing a produced block must give back the stored receipts,
+    // state diff and state root
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn test_block_prod_re_execution_matches_produced_block(
+        #[future]
+        #[with(
+            Duration::from_secs(30),
+            Some(Duration::from_secs(2)),
+            false,
+            TrieLogConfig { max_saved_trie_logs: 2, ..Default::default() }
+        )]
+        devnet_setup: (
+            Arc<MadaraBackend>,
+            Arc<BlockProductionMetrics>,
+            Arc<MockL1DataProvider>,
+            Arc<Mempool>,
+            Arc<TransactionValidator>,
+            DevnetKeys,
+        ),
+    ) {
+        let (backend, metrics, l1_data_provider, mempool, tx_validator, contracts) = devnet_setup.await;
+
+        sign_and_add_declare_tx(&contracts.0[0], &backend, &tx_validator, Felt::ZERO).await;
+
+        let mut block_production_task =
+            BlockProductionTask::new(Arc::clone(&backend), Arc::clone(&mempool), metrics, l1_data_provider);
+        let mut notifications = block_production_task.subscribe_state_notifications();
+        let _task =
+            AbortOnDrop::spawn(
+                async move { block_production_task.run(ServiceContext::new_for_testing()).await.unwrap() },
+            );
+        loop {
+            if notifications.recv().await.unwrap() == BlockProductionStateNotification::ClosedBlock {
+                break;
+            }
+        }
+
+        let block = backend.get_block(&DbBlockId::Number(1)).unwrap().unwrap();
+        assert_eq!(block.inner.transactions.len(), 1);
+        assert_matches!(
+            verify_block_execution(&backend, 1).unwrap(),
+            BlockVerification::Matches { state_root_checked: true }
+        );
+    }
+
     // This test checks when the block production task starts on
     // normal behaviour, it updates properly
     #[rstest::rstest]
@@ -2060,4 +2326,120 @@ pub(crate) mod tests {
             }
         );
     }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn test_block_prod_stops_when_leader_lease_is_lost(
+        #[future]
+        #[with(Duration::from_secs(3000000000), None, false)]
+        devnet_setup: (
+            Arc<MadaraBackend>,
+            Arc<BlockProductionMetrics>,
+            Arc<MockL1DataProvider>,
+            Arc<Mempool>,
+            Arc<TransactionValidator>,
+            DevnetKeys,
+        ),
+    ) {
+        let (backend, metrics, l1_data_provider, mempool, _tx_validator, _contracts) = devnet_setup.await;
+        let lease_backend = Arc::new(InMemoryLeaseBackend::default());
+        let leader = LeaderLease::new(lease_backend.clone(), "leader", Duration::from_secs(60));
+        let standby = LeaderLease::new(lease_backend, "standby", Duration::from_secs(60));
+        leader.try_acquire(None).await.unwrap();
+
+        let mut block_production_task =
+            BlockProductionTask::new(Arc::clone(&backend), Arc::clone(&mempool), metrics, l1_data_provider)
+                .with_leader_lease(leader.clone());
+        let mut notifications = block_production_task.subscribe_state_notifications();
+        let control = block_production_task.handle();
+        let task = tokio::spawn(block_production_task.run(ServiceContext::new_for_testing()));
+
+        control.close_block().await.unwrap();
+        assert_eq!(notifications.recv().await.unwrap(), BlockProductionStateNotification::ClosedBlock);
+        assert_eq!(backend.get_latest_block_n().unwrap(), Some(1));
+
+        // The standby node takes over, and sees the block closed by the leader.
+        leader.release().await.unwrap();
+        let lease = standby.try_acquire(None).await.unwrap();
+        assert!(standby.is_leader());
+        assert_eq!(lease.head_block_n, Some(1));
+
+        // The previous leader stops without closing its block.
+        let _ = control.close_block().await;
+        let err = task.await.unwrap().unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&NotLeader));
+        assert_eq!(backend.get_latest_block_n().unwrap(), Some(1));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn test_block_prod_pause_resume_and_update_config(
+        #[future]
+        #[with(Duration::from_secs(3000000000), None, false)]
+        devnet_setup: (
+            Arc<MadaraBackend>,
+            Arc<BlockProductionMetrics>,
+            Arc<MockL1DataProvider>,
+            Arc<Mempool>,
+            Arc<TransactionValidator>,
+            DevnetKeys,
+        ),
+    ) {
+        let (backend, metrics, l1_data_provider, mempool, _tx_validator, _contracts) = devnet_setup.await;
+        let handle = BlockProductionHandle::default();
+        assert_matches!(handle.status().await, Err(ExecutorCommandError::ChannelClosed));
+
+        let mut block_production_task =
+            BlockProductionTask::new(Arc::clone(&backend), Arc::clone(&mempool), metrics, l1_data_provider)
+                .with_handle(handle.clone());
+        let mut notifications = block_production_task.subscribe_state_notifications();
+        let _task =
+            AbortOnDrop::spawn(
+                async move { block_production_task.run(ServiceContext::new_for_testing()).await.unwrap() },
+            );
+
+        let status = handle.pause().await.unwrap();
+        assert!(status.paused);
+        assert_eq!(notifications.recv().await.unwrap(), BlockProductionStateNotification::StatusUpdated(status));
+
+        // Blocks are not closed while paused.
+        handle.close_block().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(notifications.try_recv().is_err());
+        assert_eq!(backend.get_latest_block_n().unwrap(), Some(0));
+
+        let status = handle
+            .update_config(BlockProductionConfigUpdate {
+                block_time: Some(Duration::from_millis(200)),
+                no_empty_blocks: Some(false),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            BlockProductionStatus {
+                paused: true,
+                block_time: Duration::from_millis(200),
+                pending_block_update_time: None,
+                no_empty_blocks: false
+            }
+        );
+        assert_eq!(notifications.recv().await.unwrap(), BlockProductionStateNotification::StatusUpdated(status));
+        assert_matches!(
+            handle
+                .update_config(BlockProductionConfigUpdate { block_time: Some(Duration::ZERO), ..Default::default() })
+                .await,
+            Err(ExecutorCommandError::InvalidConfig(_))
+        );
+
+        // The block is closed once resumed, and the next blocks use the new block time.
+        let status = handle.resume().await.unwrap();
+        assert!(!status.paused);
+        assert_eq!(handle.status().await.unwrap(), status);
+        assert_eq!(notifications.recv().await.unwrap(), BlockProductionStateNotification::StatusUpdated(status));
+        assert_eq!(notifications.recv().await.unwrap(), BlockProductionStateNotification::ClosedBlock);
+        assert_eq!(notifications.recv().await.unwrap(), BlockProductionStateNotification::ClosedBlock);
+        assert!(backend.get_latest_block_n().unwrap() >= Some(2));
+    }
 }
@@ -1,17 +1,13 @@
-use blockifier::{state::cached_state::StateMaps, transaction::transaction_execution::Transaction};
-use mc_db::{db_block_id::DbBlockId, MadaraBackend};
+use blockifier::{bouncer::BouncerWeights, transaction::transaction_execution::Transaction};
+use mc_db::MadaraBackend;
 use mc_mempool::L1DataProvider;
 use mp_block::header::{BlockTimestamp, GasPrices, PendingHeader};
 use mp_chain_config::{L1DataAvailabilityMode, StarknetVersion};
 use mp_class::ConvertedClass;
-use mp_convert::{Felt, ToFelt};
-use mp_state_update::{
-    ContractStorageDiffItem, DeclaredClassItem, DeployedContractItem, NonceUpdate, ReplacedClassItem, StateDiff,
-    StorageEntry,
-};
-use starknet_api::{core::ContractAddress, StarknetApiError};
+use mp_convert::Felt;
+use starknet_api::StarknetApiError;
 use std::{
-    collections::{hash_map, HashMap, VecDeque},
+    collections::VecDeque,
     ops::{Add, AddAssign},
     sync::Arc,
     time::{Duration, SystemTime},
@@ -137,113 +133,75 @@ impl BlockExecutionContext {
             block_timestamp: starknet_api::block::BlockTimestamp(BlockTimestamp::from(self.block_timestamp).0),
             sequencer_address: self.sequencer_address.try_into()?,
             gas_prices: (&self.l1_gas_price).into(),
-            use_kzg_da: self.l1_da_mode == L1DataAvailabilityMode::Blob,
+            use_kzg_da: self.l1_da_mode.use_kzg_da(),
         })
     }
 }
 
+/// `strk_l2_gas_price` is the L2 gas price of the new block, see [`next_l2_gas_price`].
 pub(crate) fn create_execution_context(
     l1_data_provider: &Arc<dyn L1DataProvider>,
     backend: &Arc<MadaraBackend>,
     block_n: u64,
+    strk_l2_gas_price: u128,
 ) -> BlockExecutionContext {
+    let mut l1_gas_price = l1_data_provider.get_gas_prices();
+    l1_gas_price.strk_l2_gas_price = strk_l2_gas_price;
+    // The L2 gas price is set in fri, use the STRK/ETH rate of the L1 gas price to get the price in wei.
+    l1_gas_price.eth_l2_gas_price = match l1_gas_price.strk_l1_gas_price {
+        0 => strk_l2_gas_price,
+        strk_l1_gas_price => strk_l2_gas_price.saturating_mul(l1_gas_price.eth_l1_gas_price) / strk_l1_gas_price,
+    };
+    // Nothing is posted on L1 in validium mode, the state diffs are not charged for data availability.
+    let l1_da_mode = backend.chain_config().l1_da_mode;
+    if !l1_da_mode.charges_l1_data_gas() {
+        l1_gas_price.eth_l1_data_gas_price = 0;
+        l1_gas_price.strk_l1_data_gas_price = 0;
+    }
+    // The header only carries the prices of the fee tokens of the chain. When the native token is the only one, the
+    // `strk` prices are in its base unit, and there is no price in wei.
+    if !backend.chain_config().supports_legacy_fee_payment() {
+        l1_gas_price.eth_l1_gas_price = 0;
+        l1_gas_price.eth_l1_data_gas_price = 0;
+        l1_gas_price.eth_l2_gas_price = 0;
+    }
+
     BlockExecutionContext {
         sequencer_address: **backend.chain_config().sequencer_address,
         block_timestamp: SystemTime::now(),
         protocol_version: backend.chain_config().latest_protocol_version,
-        l1_gas_price: l1_data_provider.get_gas_prices(),
-        l1_da_mode: backend.chain_config().l1_da_mode,
+        l1_gas_price,
+        l1_da_mode,
         block_n,
     }
 }
 
-pub(crate) fn state_map_to_state_diff(
+/// Computes the L2 gas price in fri of a new block, using the L2 gas price controller of the chain config. This returns
+/// zero when the chain does not use the controller.
+/// The bouncer weights of the parent block are not known when it was produced before a restart, in which case the L2
+/// gas price of the parent block is kept.
+pub(crate) fn next_l2_gas_price(
     backend: &MadaraBackend,
-    on_top_of: &Option<DbBlockId>,
-    diff: StateMaps,
-) -> anyhow::Result<StateDiff> {
-    let mut backing_map = HashMap::<ContractAddress, usize>::default();
-    let mut storage_diffs = Vec::<ContractStorageDiffItem>::default();
-    for ((address, key), value) in diff.storage {
-        match backing_map.entry(address) {
-            hash_map::Entry::Vacant(e) => {
-                e.insert(storage_diffs.len());
-                storage_diffs.push(ContractStorageDiffItem {
-                    address: address.to_felt(),
-                    storage_entries: vec![StorageEntry { key: key.to_felt(), value }],
-                });
-            }
-            hash_map::Entry::Occupied(e) => {
-                storage_diffs[*e.get()].storage_entries.push(StorageEntry { key: key.to_felt(), value });
-            }
-        }
-    }
-
-    let mut deprecated_declared_classes = Vec::default();
-    for (class_hash, _) in diff.declared_contracts {
-        if !diff.compiled_class_hashes.contains_key(&class_hash) {
-            deprecated_declared_classes.push(class_hash.to_felt());
-        }
-    }
-
-    let declared_classes = diff
-        .compiled_class_hashes
-        .iter()
-        .map(|(class_hash, compiled_class_hash)| DeclaredClassItem {
-            class_hash: class_hash.to_felt(),
-            compiled_class_hash: compiled_class_hash.to_felt(),
-        })
-        .collect();
-
-    let nonces = diff
-        .nonces
-        .into_iter()
-        .map(|(contract_address, nonce)| NonceUpdate {
-            contract_address: contract_address.to_felt(),
-            nonce: nonce.to_felt(),
-        })
-        .collect();
-
-    let mut deployed_contracts = Vec::new();
-    let mut replaced_classes = Vec::new();
-    for (contract_address, new_class_hash) in diff.class_hashes {
-        let replaced = if let Some(on_top_of) = on_top_of {
-            match backend.get_contract_class_hash_at(on_top_of, &contract_address.to_felt())? {
-                Some(class_hash) => class_hash != new_class_hash.to_felt(),
-                None => false,
-            }
-        } else {
-            // Executing genesis block: nothing being redefined here
-            false
-        };
-        if replaced {
-            replaced_classes.push(ReplacedClassItem {
-                contract_address: contract_address.to_felt(),
-                class_hash: new_class_hash.to_felt(),
-            })
-        } else {
-            deployed_contracts.push(DeployedContractItem {
-                address: contract_address.to_felt(),
-                class_hash: new_class_hash.to_felt(),
-            })
+    parent_price: u128,
+    parent_weights: Option<&BouncerWeights>,
+) -> u128 {
+    let Some(config) = &backend.chain_config().l2_gas_price else { return 0 };
+    match parent_weights {
+        Some(weights) => {
+            config.next_price(parent_price, weights, &backend.chain_config().bouncer_config.block_max_capacity)
         }
+        None if parent_price == 0 => config.initial_price,
+        None => parent_price.clamp(config.min_price, config.max_price),
     }
-
-    Ok(StateDiff {
-        storage_diffs,
-        deprecated_declared_classes,
-        declared_classes,
-        nonces,
-        deployed_contracts,
-        replaced_classes,
-    })
 }
 
 #[cfg(test)]
 mod test {
     use blockifier::state::cached_state::StateMaps;
     use mc_db::MadaraBackend;
-    use mp_chain_config::ChainConfig;
+    use mc_mempool::MockL1DataProvider;
+    use mp_block::header::GasPrices;
+    use mp_chain_config::{ChainConfig, L1DataAvailabilityMode};
     use mp_state_update::{
         ContractStorageDiffItem, DeclaredClassItem, DeployedContractItem, NonceUpdate, StateDiff, StorageEntry,
     };
@@ -399,7 +357,7 @@ mod test {
             replaced_classes,
         };
 
-        let mut actual = super::state_map_to_state_diff(&backend, &Option::<_>::None, state_map).unwrap();
+        let mut actual = mc_exec::state_map_to_state_diff(&backend, &Option::<_>::None, state_map).unwrap();
 
         actual.storage_diffs.sort_by(|a, b| a.address.cmp(&b.address));
         actual.storage_diffs.iter_mut().for_each(|s| s.storage_entries.sort_by(|a, b| a.key.cmp(&b.key)));
@@ -417,4 +375,60 @@ mod test {
             serde_json::to_string_pretty(&expected).unwrap_or_default()
         );
     }
+
+    #[rstest::rstest]
+    #[case::blob(L1DataAvailabilityMode::Blob, 128)]
+    #[case::validium(L1DataAvailabilityMode::Validium, 0)]
+    fn execution_context_l1_data_gas_price(
+        #[case] l1_da_mode: L1DataAvailabilityMode,
+        #[case] expected_l1_data_gas_price: u128,
+    ) {
+        let backend =
+            MadaraBackend::open_for_testing(Arc::new(ChainConfig { l1_da_mode, ..ChainConfig::madara_test() }));
+        let mut l1_data_provider = MockL1DataProvider::new();
+        l1_data_provider.expect_get_gas_prices().return_const(GasPrices {
+            eth_l1_gas_price: 128,
+            strk_l1_gas_price: 128,
+            eth_l1_data_gas_price: 128,
+            strk_l1_data_gas_price: 128,
+            ..Default::default()
+        });
+        let l1_data_provider: Arc<dyn mc_mempool::L1DataProvider> = Arc::new(l1_data_provider);
+
+        let context = super::create_execution_context(&l1_data_provider, &backend, 1, 0);
+        assert_eq!(context.l1_gas_price.eth_l1_gas_price, 128);
+        assert_eq!(context.l1_gas_price.eth_l1_data_gas_price, expected_l1_data_gas_price);
+        assert_eq!(context.l1_gas_price.strk_l1_data_gas_price, expected_l1_data_gas_price);
+        assert!(context.to_blockifier().unwrap().use_kzg_da);
+    }
+
+    #[test]
+    fn execution_context_single_fee_token() {
+        let backend = MadaraBackend::open_for_testing(Arc::new(ChainConfig {
+            parent_fee_token_address: None,
+            ..ChainConfig::madara_test()
+        }));
+        let mut l1_data_provider = MockL1DataProvider::new();
+        l1_data_provider.expect_get_gas_prices().return_const(GasPrices {
+            eth_l1_gas_price: 128,
+            strk_l1_gas_price: 64,
+            eth_l1_data_gas_price: 32,
+            strk_l1_data_gas_price: 16,
+            ..Default::default()
+        });
+        let l1_data_provider: Arc<dyn mc_mempool::L1DataProvider> = Arc::new(l1_data_provider);
+
+        let context = super::create_execution_context(&l1_data_provider, &backend, 1, 8);
+        assert_eq!(
+            context.l1_gas_price,
+            GasPrices {
+                eth_l1_gas_price: 0,
+                strk_l1_gas_price: 64,
+                eth_l1_data_gas_price: 0,
+                strk_l1_data_gas_price: 16,
+                eth_l2_gas_price: 0,
+                strk_l2_gas_price: 8,
+            }
+        );
+    }
 }
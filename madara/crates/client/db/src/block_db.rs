@@ -149,6 +149,8 @@ impl MadaraBackend {
                             strk_l1_gas_price: 1,
                             eth_l1_data_gas_price: 1,
                             strk_l1_data_gas_price: 1,
+                            eth_l2_gas_price: 1,
+                            strk_l2_gas_price: 1,
                         },
                         l1_da_mode: self.chain_config.l1_da_mode,
                     },
@@ -275,6 +277,7 @@ impl MadaraBackend {
         tx.put_cf(&block_hash_to_block_n, block_hash_encoded, &block_n_encoded);
         tx.put_cf(&block_n_to_block_inner, &block_n_encoded, bincode::serialize(&block.inner)?);
         tx.put_cf(&block_n_to_state_diff, &block_n_encoded, bincode::serialize(state_diff)?);
+        self.message_to_l1_db_store_block(&block.inner.receipts, &mut tx)?;
 
         // susbcribers
         self.watch_blocks.on_new_block(block.info.clone().into());
@@ -314,6 +317,56 @@ impl MadaraBackend {
         Ok(())
     }
 
+    /// Delete the header, transactions, receipts, state diff and event bloom filter of a closed block.
+    #[tracing::instrument(skip(self, block_info, batch), fields(module = "BlockDB"))]
+    pub(crate) fn block_db_revert_block(
+        &self,
+        block_info: &MadaraBlockInfo,
+        batch: &mut WriteBatchWithTransaction,
+    ) -> Result<()> {
+        let tx_hash_to_block_n = self.db.get_column(Column::TxHashToBlockN);
+        let block_hash_to_block_n = self.db.get_column(Column::BlockHashToBlockN);
+        let block_n_to_block = self.db.get_column(Column::BlockNToBlockInfo);
+        let block_n_to_block_inner = self.db.get_column(Column::BlockNToBlockInner);
+        let block_n_to_state_diff = self.db.get_column(Column::BlockNToStateDiff);
+        let block_n_to_bloom = self.db.get_column(Column::EventBloom);
+        let block_n_to_traces = self.db.get_column(Column::BlockNToTxTraces);
+
+        let block_n = block_info.header.block_number;
+        let block_n_encoded = bincode::serialize(&block_n)?;
+
+        for hash in &block_info.tx_hashes {
+            batch.delete_cf(&tx_hash_to_block_n, bincode::serialize(hash)?);
+        }
+        batch.delete_cf(&block_hash_to_block_n, bincode::serialize(&block_info.block_hash)?);
+        batch.delete_cf(&block_n_to_block, block_n.to_be_bytes());
+        batch.delete_cf(&block_n_to_block_inner, &block_n_encoded);
+        batch.delete_cf(&block_n_to_state_diff, &block_n_encoded);
+        batch.delete_cf(&block_n_to_bloom, &block_n_encoded);
+        batch.delete_cf(&block_n_to_traces, block_n.to_be_bytes());
+        self.message_to_l1_db_revert_block(&block_info.tx_hashes, batch)?;
+        Ok(())
+    }
+
+    /// Block numbers above `block_n` that have a header in the database, including the blocks that the sync process
+    /// saved past the latest full block.
+    #[tracing::instrument(skip(self), fields(module = "BlockDB"))]
+    pub(crate) fn block_db_stored_blocks_above(&self, block_n: u64) -> Result<Vec<u64>> {
+        let col = self.db.get_column(Column::BlockNToBlockInfo);
+        let Some(start) = block_n.checked_add(1) else { return Ok(vec![]) };
+        let start = start.to_be_bytes();
+        self.db
+            .iterator_cf(&col, IteratorMode::From(&start, Direction::Forward))
+            .map(|kv| {
+                let (key, _) = kv?;
+                let key: [u8; 8] = (*key).try_into().map_err(|_| {
+                    MadaraStorageError::InconsistentStorage("Malformed block_n key in block info column".into())
+                })?;
+                Ok(u64::from_be_bytes(key))
+            })
+            .collect()
+    }
+
     // Convenience functions
 
     fn storage_to_info(&self, id: &RawDbBlockId) -> Result<Option<MadaraMaybePendingBlockInfo>> {
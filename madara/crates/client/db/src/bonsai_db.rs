@@ -6,7 +6,7 @@ use bonsai_trie::{BonsaiDatabase, BonsaiPersistentDatabase, BonsaiStorage, ByteV
 use rocksdb::{Direction, IteratorMode, WriteOptions};
 use std::collections::BTreeMap;
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 pub type GlobalTrie<H> = BonsaiStorage<BasicId, BonsaiDb, H>;
 
@@ -18,6 +18,16 @@ pub(crate) struct DatabaseKeyMapping {
 }
 
 impl DatabaseKeyMapping {
+    pub(crate) const CONTRACTS: Self =
+        Self { flat: Column::BonsaiContractsFlat, trie: Column::BonsaiContractsTrie, log: Column::BonsaiContractsLog };
+    pub(crate) const CONTRACTS_STORAGE: Self = Self {
+        flat: Column::BonsaiContractsStorageFlat,
+        trie: Column::BonsaiContractsStorageTrie,
+        log: Column::BonsaiContractsStorageLog,
+    };
+    pub(crate) const CLASSES: Self =
+        Self { flat: Column::BonsaiClassesFlat, trie: Column::BonsaiClassesTrie, log: Column::BonsaiClassesLog };
+
     pub(crate) fn map(&self, key: &DatabaseKey) -> Column {
         match key {
             DatabaseKey::Trie(_) => self.trie,
@@ -33,13 +43,20 @@ pub struct BonsaiDb {
     column_mapping: DatabaseKeyMapping,
     snapshots: Arc<Snapshots>,
     write_opt: WriteOptions,
+    /// When set, writes are appended to this batch instead of being applied to the database, so that the caller can
+    /// commit them atomically with its own changes.
+    captured_batch: Option<Arc<Mutex<WriteBatchWithTransaction>>>,
 }
 
 impl BonsaiDb {
     pub(crate) fn new(db: Arc<DB>, snapshots: Arc<Snapshots>, column_mapping: DatabaseKeyMapping) -> Self {
         let mut write_opt = WriteOptions::default();
         write_opt.disable_wal(true);
-        Self { db, column_mapping, write_opt, snapshots }
+        Self { db, column_mapping, write_opt, snapshots, captured_batch: None }
+    }
+
+    pub(crate) fn capture_writes(self, batch: Arc<Mutex<WriteBatchWithTransaction>>) -> Self {
+        Self { captured_batch: Some(batch), ..self }
     }
 }
 
@@ -103,7 +120,9 @@ impl BonsaiDatabase for BonsaiDb {
         let handle = self.db.get_column(self.column_mapping.map(key));
 
         let old_value = self.db.get_cf(&handle, key.as_slice())?;
-        if let Some(batch) = batch {
+        if let Some(captured) = &self.captured_batch {
+            captured.lock().expect("Poisoned lock").put_cf(&handle, key.as_slice(), value);
+        } else if let Some(batch) = batch {
             batch.put_cf(&handle, key.as_slice(), value);
         } else {
             self.db.put_cf_opt(&handle, key.as_slice(), value, &self.write_opt)?;
@@ -120,7 +139,9 @@ impl BonsaiDatabase for BonsaiDb {
         tracing::trace!("Removing from RocksDB: {:?}", key);
         let handle = self.db.get_column(self.column_mapping.map(key));
         let old_value = self.db.get_cf(&handle, key.as_slice())?;
-        if let Some(batch) = batch {
+        if let Some(captured) = &self.captured_batch {
+            captured.lock().expect("Poisoned lock").delete_cf(&handle, key.as_slice());
+        } else if let Some(batch) = batch {
             batch.delete_cf(&handle, key.as_slice());
         } else {
             self.db.delete_cf_opt(&handle, key.as_slice(), &self.write_opt)?;
@@ -133,18 +154,15 @@ impl BonsaiDatabase for BonsaiDb {
         tracing::trace!("Getting from RocksDB: {:?}", prefix);
         let handle = self.db.get_column(self.column_mapping.map(prefix));
         let iter = self.db.iterator_cf(&handle, IteratorMode::From(prefix.as_slice(), Direction::Forward));
-        let mut batch = self.create_batch();
-        for kv in iter {
-            if let Ok((key, _)) = kv {
-                if key.starts_with(prefix.as_slice()) {
-                    batch.delete_cf(&handle, &key);
-                } else {
-                    break;
-                }
-            } else {
-                break;
-            }
+        let keys: Vec<Box<[u8]>> =
+            iter.map_while(|kv| kv.ok().map(|(key, _)| key).filter(|key| key.starts_with(prefix.as_slice()))).collect();
+        if let Some(captured) = &self.captured_batch {
+            let mut captured = captured.lock().expect("Poisoned lock");
+            keys.iter().for_each(|key| captured.delete_cf(&handle, key));
+            return Ok(());
         }
+        let mut batch = self.create_batch();
+        keys.iter().for_each(|key| batch.delete_cf(&handle, key));
         drop(handle);
         self.write_batch(batch)?;
         Ok(())
@@ -152,6 +170,11 @@ impl BonsaiDatabase for BonsaiDb {
 
     #[tracing::instrument(skip(self, batch), fields(module = "BonsaiDB"))]
     fn write_batch(&mut self, batch: Self::Batch) -> Result<(), Self::DatabaseError> {
+        if self.captured_batch.is_some() {
+            // Inserts and removals already went to the captured batch, nothing was added to this one.
+            debug_assert!(batch.is_empty());
+            return Ok(());
+        }
         Ok(self.db.write_opt(batch, &self.write_opt)?)
     }
 }
@@ -1,4 +1,4 @@
-use crate::{Column, MadaraBackend, MadaraStorageError};
+use crate::{Column, MadaraBackend, MadaraStorageError, WriteBatchWithTransaction};
 use crate::{DatabaseExt, DB};
 use std::fmt;
 use std::sync::atomic::{AtomicU64, Ordering::SeqCst};
@@ -89,4 +89,13 @@ impl MadaraBackend {
         self.db.put_cf_opt(&col, ROW_HEAD_STATUS, bincode::serialize(&self.head_status)?, &self.writeopts_no_wal)?;
         Ok(())
     }
+    /// Same as [`Self::save_head_status_to_db`], but the head status is written when `batch` is.
+    pub(crate) fn save_head_status_to_batch(
+        &self,
+        batch: &mut WriteBatchWithTransaction,
+    ) -> Result<(), MadaraStorageError> {
+        let col = self.db.get_column(Column::BlockStorageMeta);
+        batch.put_cf(&col, ROW_HEAD_STATUS, bincode::serialize(&self.head_status)?);
+        Ok(())
+    }
 }
@@ -3,6 +3,7 @@ use crate::{
     Column, DatabaseExt, MadaraBackend, MadaraStorageError, WriteBatchWithTransaction, DB_UPDATES_BATCH_SIZE,
 };
 use mp_class::{ClassInfo, CompiledSierra, ConvertedClass, LegacyConvertedClass, SierraConvertedClass};
+use mp_state_update::StateDiff;
 use rayon::{iter::ParallelIterator, slice::ParallelSlice};
 use starknet_types_core::felt::Felt;
 use std::sync::Arc;
@@ -248,6 +249,38 @@ impl MadaraBackend {
         )
     }
 
+    /// Delete the classes declared in this block. Classes that were first declared in an earlier block are kept.
+    #[tracing::instrument(skip(self, state_diff, batch), fields(module = "ClassDB"))]
+    pub(crate) fn class_db_revert_block(
+        &self,
+        block_n: u64,
+        state_diff: &StateDiff,
+        batch: &mut WriteBatchWithTransaction,
+    ) -> Result<(), MadaraStorageError> {
+        let col_info = self.db.get_column(Column::ClassInfo);
+        let col_compiled = self.db.get_column(Column::ClassCompiled);
+
+        let class_hashes = state_diff
+            .declared_classes
+            .iter()
+            .map(|item| item.class_hash)
+            .chain(state_diff.deprecated_declared_classes.iter().copied());
+        for class_hash in class_hashes {
+            let key_bin = bincode::serialize(&class_hash)?;
+            let Some(info) = self.db.get_pinned_cf(&col_info, &key_bin)? else { continue };
+            let info: ClassInfoWithBlockNumber = bincode::deserialize(&info)?;
+            if info.block_id != RawDbBlockId::Number(block_n) {
+                continue;
+            }
+            batch.delete_cf(&col_info, &key_bin);
+            if let ClassInfo::Sierra(sierra) = info.class_info {
+                batch.delete_cf(&col_compiled, bincode::serialize(&sierra.compiled_class_hash)?);
+            }
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(fields(module = "ClassDB"))]
     pub(crate) fn class_db_clear_pending(&self) -> Result<(), MadaraStorageError> {
         self.db.delete_range_cf_opt(
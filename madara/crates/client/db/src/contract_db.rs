@@ -8,7 +8,7 @@ use mp_state_update::{
     ContractStorageDiffItem, DeployedContractItem, NonceUpdate, ReplacedClassItem, StateDiff, StorageEntry,
 };
 use rayon::{iter::ParallelIterator, slice::ParallelSlice};
-use rocksdb::{BoundColumnFamily, IteratorMode, ReadOptions, WriteOptions};
+use rocksdb::{BoundColumnFamily, Direction, IteratorMode, ReadOptions, WriteOptions};
 use serde::Serialize;
 use starknet_types_core::felt::Felt;
 use std::{collections::HashMap, sync::Arc};
@@ -96,6 +96,8 @@ impl MadaraBackend {
             RawDbBlockId::Number(block_n) => block_n,
         };
 
+        self.check_state_not_pruned(block_n)?;
+
         // We try to find history values.
 
         let block_n = u32::try_from(block_n).map_err(|_| MadaraStorageError::InvalidBlockNumber)?;
@@ -251,6 +253,111 @@ impl MadaraBackend {
         Ok(())
     }
 
+    /// Delete the history entries written by [`Self::contract_db_store_block`] for this block.
+    #[tracing::instrument(skip(self, state_diff, batch), fields(module = "ContractDB"))]
+    pub(crate) fn contract_db_revert_block(
+        &self,
+        block_number: u64,
+        state_diff: &StateDiff,
+        batch: &mut WriteBatchWithTransaction,
+    ) -> Result<(), MadaraStorageError> {
+        let block_number = u32::try_from(block_number).map_err(|_| MadaraStorageError::InvalidBlockNumber)?;
+        let block_number = block_number.to_be_bytes();
+
+        let class_hashes_col = self.db.get_column(Column::ContractToClassHashes);
+        let contract_addresses = state_diff
+            .deployed_contracts
+            .iter()
+            .map(|item| item.address)
+            .chain(state_diff.replaced_classes.iter().map(|item| item.contract_address));
+        for contract_address in contract_addresses {
+            batch.delete_cf(&class_hashes_col, [&contract_address.to_bytes_be() as &[u8], &block_number].concat());
+        }
+
+        let nonces_col = self.db.get_column(Column::ContractToNonces);
+        for NonceUpdate { contract_address, .. } in &state_diff.nonces {
+            batch.delete_cf(&nonces_col, [&contract_address.to_bytes_be() as &[u8], &block_number].concat());
+        }
+
+        let storage_col = self.db.get_column(Column::ContractStorage);
+        for ContractStorageDiffItem { address, storage_entries } in &state_diff.storage_diffs {
+            for StorageEntry { key, .. } in storage_entries {
+                batch.delete_cf(
+                    &storage_col,
+                    [&make_storage_key_prefix(*address, *key) as &[u8], &block_number].concat(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Delete the history entries of `bin_prefix` older than `block_number`.
+    fn contract_db_prune_key(
+        &self,
+        col: &Arc<BoundColumnFamily>,
+        block_number: u32,
+        bin_prefix: &[u8],
+        batch: &mut WriteBatchWithTransaction,
+    ) -> Result<(), MadaraStorageError> {
+        let Some(previous_block) = block_number.checked_sub(1) else { return Ok(()) };
+        let start_at = [bin_prefix, &previous_block.to_be_bytes() as &[u8]].concat();
+
+        let mut options = ReadOptions::default();
+        options.set_prefix_same_as_start(true);
+        for res in self.db.iterator_cf_opt(col, options, IteratorMode::From(&start_at, Direction::Reverse)) {
+            let (key, _) = res?;
+            // Don't rely on the prefix extractor of the column alone, deleting the entries of a neighbouring key would
+            // corrupt its history.
+            if !key.starts_with(bin_prefix) {
+                break;
+            }
+            batch.delete_cf(col, key);
+        }
+        Ok(())
+    }
+
+    /// For every key updated in this block, delete the history entries that are shadowed by the entry of this block.
+    /// The state at this block and at every later block is left unchanged.
+    #[tracing::instrument(skip(self, state_diff, batch), fields(module = "ContractDB"))]
+    pub(crate) fn contract_db_prune_block(
+        &self,
+        block_number: u64,
+        state_diff: &StateDiff,
+        batch: &mut WriteBatchWithTransaction,
+    ) -> Result<(), MadaraStorageError> {
+        let block_number = u32::try_from(block_number).map_err(|_| MadaraStorageError::InvalidBlockNumber)?;
+
+        let class_hashes_col = self.db.get_column(Column::ContractToClassHashes);
+        let contract_addresses = state_diff
+            .deployed_contracts
+            .iter()
+            .map(|item| item.address)
+            .chain(state_diff.replaced_classes.iter().map(|item| item.contract_address));
+        for contract_address in contract_addresses {
+            self.contract_db_prune_key(&class_hashes_col, block_number, &contract_address.to_bytes_be(), batch)?;
+        }
+
+        let nonces_col = self.db.get_column(Column::ContractToNonces);
+        for NonceUpdate { contract_address, .. } in &state_diff.nonces {
+            self.contract_db_prune_key(&nonces_col, block_number, &contract_address.to_bytes_be(), batch)?;
+        }
+
+        let storage_col = self.db.get_column(Column::ContractStorage);
+        for ContractStorageDiffItem { address, storage_entries } in &state_diff.storage_diffs {
+            for StorageEntry { key, .. } in storage_entries {
+                self.contract_db_prune_key(
+                    &storage_col,
+                    block_number,
+                    &make_storage_key_prefix(*address, *key),
+                    batch,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// NB: This functions needs to run on the rayon thread pool
     #[tracing::instrument(skip(self, value), fields(module = "ContractDB"))]
     pub(crate) fn contract_db_store_pending(&self, value: ContractDbBlockUpdate) -> Result<(), MadaraStorageError> {
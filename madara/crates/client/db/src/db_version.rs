@@ -8,7 +8,12 @@
 //! The version is stored in a `.db-version` file in the database directory.
 //! This file contains a single number representing the database version.
 //!
+//! # Migrations
+//! Databases from an older version that can still be migrated (see [`crate::migrations`]) are accepted: the caller
+//! runs the migrations and then records the new version with [`write_db_version`].
+//!
 
+use crate::migrations::OLDEST_MIGRATABLE_DB_VERSION;
 use std::fs;
 use std::path::Path;
 
@@ -45,7 +50,8 @@ pub enum DbVersionError {
 ///
 /// # Returns
 /// * `Ok(None)` - New database created with current version
-/// * `Ok(Some(version))` - Existing database with compatible version
+/// * `Ok(Some(version))` - Existing database with compatible version, which needs to be migrated if it is older than
+///   [`required_db_version`]
 /// * `Err(DbVersionError)` - Version mismatch or IO error
 ///
 /// # Examples
@@ -62,8 +68,7 @@ pub enum DbVersionError {
 /// ```
 ///
 pub fn check_db_version(path: &Path) -> Result<Option<u32>, DbVersionError> {
-    let required_db_version =
-        REQUIRED_DB_VERSION.parse::<u32>().expect("REQUIRED_DB_VERSION is checked at compile time");
+    let required_db_version = required_db_version();
 
     // Create directory if it doesn't exist
     if !path.exists() {
@@ -80,7 +85,7 @@ pub fn check_db_version(path: &Path) -> Result<Option<u32>, DbVersionError> {
         let version = fs::read_to_string(&file_path).map_err(|e| DbVersionError::VersionReadError(e.to_string()))?;
         let version = version.trim().parse::<u32>().map_err(|_| DbVersionError::VersionReadError(version))?;
 
-        if version != required_db_version {
+        if !(OLDEST_MIGRATABLE_DB_VERSION..=required_db_version).contains(&version) {
             return Err(DbVersionError::IncompatibleVersion {
                 db_version: version,
                 required_version: required_db_version,
@@ -90,6 +95,17 @@ pub fn check_db_version(path: &Path) -> Result<Option<u32>, DbVersionError> {
     }
 }
 
+/// Database version of the current binary.
+pub fn required_db_version() -> u32 {
+    REQUIRED_DB_VERSION.parse::<u32>().expect("REQUIRED_DB_VERSION is checked at compile time")
+}
+
+/// Records the current version in the database directory, once the database has been migrated to it.
+pub fn write_db_version(path: &Path) -> Result<(), DbVersionError> {
+    fs::write(path.join(DB_VERSION_FILE), REQUIRED_DB_VERSION)
+        .map_err(|e| DbVersionError::VersionReadError(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,6 +156,34 @@ mod tests {
         assert!(matches!(err, DbVersionError::IncompatibleVersion { .. }));
     }
 
+    #[test]
+    fn test_migratable_version() {
+        let temp_dir = setup_test_db();
+        let version_file = temp_dir.path().join(DB_VERSION_FILE);
+
+        // Create version file with the oldest version that can be migrated
+        fs::write(&version_file, OLDEST_MIGRATABLE_DB_VERSION.to_string()).unwrap();
+
+        let result = check_db_version(temp_dir.path()).unwrap();
+        assert_eq!(result, Some(OLDEST_MIGRATABLE_DB_VERSION));
+
+        write_db_version(temp_dir.path()).unwrap();
+        let result = check_db_version(temp_dir.path()).unwrap();
+        assert_eq!(result, Some(required_db_version()));
+    }
+
+    #[test]
+    fn test_too_old_version() {
+        let temp_dir = setup_test_db();
+        let version_file = temp_dir.path().join(DB_VERSION_FILE);
+
+        // Create version file with a version that can no longer be migrated
+        fs::write(version_file, (OLDEST_MIGRATABLE_DB_VERSION - 1).to_string()).unwrap();
+
+        let err = check_db_version(temp_dir.path()).unwrap_err();
+        assert!(matches!(err, DbVersionError::IncompatibleVersion { .. }));
+    }
+
     #[test]
     fn test_invalid_version_format() {
         let temp_dir = setup_test_db();
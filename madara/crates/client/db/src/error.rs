@@ -11,6 +11,10 @@ pub enum MadaraStorageError {
     RocksDB(#[from] rocksdb::Error),
     #[error("Bincode error: {0}")]
     Bincode(#[from] bincode::Error),
+    #[error("Json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Compression error: {0}")]
+    Compression(#[from] std::io::Error),
     #[error("Failed to compile class: {0}")]
     CompilationClassError(String),
     #[error("Invalid block number")]
@@ -27,6 +31,10 @@ pub enum MadaraStorageError {
     MissingCompiledClass { class_hash: Felt, compiled_class_hash: Felt },
     #[error("Batch is empty")]
     EmptyBatch,
+    #[error("Cannot revert the chain to block #{target}: {reason}")]
+    InvalidRevertTarget { target: u64, reason: Cow<'static, str> },
+    #[error("The state of block #{block_n} has been pruned, the oldest available state is #{oldest_available}")]
+    BlockPruned { block_n: u64, oldest_available: u64 },
 }
 
 pub type BonsaiStorageError = bonsai_trie::BonsaiStorageError<DbError>;
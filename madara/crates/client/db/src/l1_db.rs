@@ -1,5 +1,5 @@
 use crate::error::DbError;
-use crate::{Column, DatabaseExt, MadaraBackend, MadaraStorageError};
+use crate::{Column, DatabaseExt, MadaraBackend, MadaraStorageError, WriteBatchWithTransaction};
 use rocksdb::IteratorMode;
 use serde::{Deserialize, Serialize};
 use starknet_api::core::Nonce;
@@ -129,6 +129,64 @@ impl MadaraBackend {
         Ok(())
     }
 
+    /// Marks the message with this nonce as processed, remembering the L1 block and event index it was emitted at so
+    /// that the message can be processed again if the block including it is reverted.
+    #[tracing::instrument(skip(self, nonce), fields(module = "L1DB"))]
+    pub fn set_l1_messaging_nonce_from_event(
+        &self,
+        nonce: Nonce,
+        event_block: &LastSyncedEventBlock,
+    ) -> Result<(), DbError> {
+        let nonce_column = self.db.get_column(Column::L1MessagingNonce);
+        self.db.put_cf_opt(
+            &nonce_column,
+            bincode::serialize(&nonce)?,
+            bincode::serialize(event_block)?,
+            &self.writeopts_no_wal,
+        )?;
+        Ok(())
+    }
+
+    /// Unmarks the messages of the reverted L1 handler transactions and rewinds the last synced L1 event block to the
+    /// earliest of them, so that the messaging sync processes them again.
+    pub(crate) fn l1_db_revert_messages(
+        &self,
+        nonces: impl IntoIterator<Item = Nonce>,
+        batch: &mut WriteBatchWithTransaction,
+    ) -> Result<()> {
+        let nonce_column = self.db.get_column(Column::L1MessagingNonce);
+        let mut earliest: Option<LastSyncedEventBlock> = None;
+        for nonce in nonces {
+            let key = bincode::serialize(&nonce)?;
+            let Some(value) = self.db.get_pinned_cf(&nonce_column, &key)? else { continue };
+            // Nonces marked without their event, such as cancelled messages, have an empty value.
+            if !value.is_empty() {
+                let event_block: LastSyncedEventBlock = bincode::deserialize(&value)?;
+                if earliest.as_ref().is_none_or(|earliest| {
+                    (event_block.block_number, event_block.event_index) < (earliest.block_number, earliest.event_index)
+                }) {
+                    earliest = Some(event_block);
+                }
+            } else {
+                tracing::warn!(
+                    "L1 message with nonce {:#x} has no recorded event, the messaging sync isn't rewound for it",
+                    nonce.0
+                );
+            }
+            batch.delete_cf(&nonce_column, key);
+        }
+
+        if let Some(earliest) = earliest {
+            let last_synced =
+                self.messaging_last_synced_l1_block_with_event()?.unwrap_or(LastSyncedEventBlock::new(0, 0));
+            if earliest.block_number <= last_synced.block_number {
+                let messaging_column = self.db.get_column(Column::L1Messaging);
+                batch.put_cf(&messaging_column, LAST_SYNCED_L1_EVENT_BLOCK, bincode::serialize(&earliest)?);
+            }
+        }
+        Ok(())
+    }
+
     /// Retrieve the latest L1 messaging [Nonce] if one is available, otherwise
     /// returns [None].
     pub fn get_l1_messaging_nonce_latest(&self) -> Result<Option<Nonce>, MadaraStorageError> {
@@ -38,12 +38,12 @@
 use anyhow::Context;
 use bonsai_db::{BonsaiDb, DatabaseKeyMapping};
 use bonsai_trie::{BonsaiStorage, BonsaiStorageConfig};
-use chain_head::ChainHead;
+use chain_head::{BlockNStatus, ChainHead};
 use db_metrics::DbMetrics;
 use events::EventChannels;
 use mp_block::EventWithInfo;
 use mp_block::MadaraBlockInfo;
-use mp_chain_config::ChainConfig;
+use mp_chain_config::{ChainConfig, TransactionPolicy};
 use mp_convert::Felt;
 use mp_receipt::EventWithTransactionHash;
 use mp_utils::service::{MadaraServiceId, PowerOfTwo, Service, ServiceId};
@@ -55,7 +55,7 @@ use rocksdb_options::rocksdb_global_options;
 use snapshots::Snapshots;
 use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::{fmt, fs};
 use tokio::sync::{mpsc, oneshot, RwLock};
 use watch::BlockWatch;
@@ -65,6 +65,7 @@ mod db_version;
 mod error;
 mod events;
 mod events_bloom_filter;
+mod migrations;
 mod rocksdb_options;
 mod rocksdb_snapshot;
 mod snapshots;
@@ -79,16 +80,22 @@ pub mod db_metrics;
 pub mod devnet_db;
 pub mod l1_db;
 pub mod mempool_db;
+pub mod message_to_l1_db;
+pub mod prune_db;
+pub mod revert_db;
 pub mod storage_updates;
 pub mod stream;
 #[cfg(any(test, feature = "testing"))]
 pub mod tests;
+pub mod trace_db;
 mod update_global_trie;
 
 pub use bonsai_db::GlobalTrie;
 pub use bonsai_trie::{id::BasicId, MultiProof, ProofNode};
 pub use error::{BonsaiStorageError, MadaraStorageError, TrieType};
+pub use prune_db::StorageMode;
 pub use rocksdb_options::{RocksDBConfig, StatsLevel};
+pub use trace_db::TraceStorageConfig;
 pub use watch::{ClosedBlocksReceiver, LastBlockOnL1Receiver, PendingBlockReceiver, PendingTxsReceiver};
 pub type DB = DBWithThreadMode<MultiThreaded>;
 pub use rocksdb;
@@ -200,10 +207,20 @@ pub enum Column {
     L1Messaging,
     L1MessagingNonce,
 
+    /// tx_hash => hashes of the L2 to L1 messages sent by the transaction
+    TxHashToMessagesToL1,
+    /// message_hash => what the settlement layer did with the L2 to L1 message
+    MessagesToL1Settlement,
+    /// (message_hash, tx_hash) => () for the transactions of closed blocks that sent the L2 to L1 message
+    MessageToL1HashToTxHashes,
+
     /// Devnet: stores the private keys for the devnet predeployed contracts
     Devnet,
 
     MempoolTransactions,
+
+    /// block_n => compressed transaction traces of the block
+    BlockNToTxTraces,
 }
 
 impl fmt::Debug for Column {
@@ -247,11 +264,15 @@ impl Column {
             BonsaiClassesLog,
             L1Messaging,
             L1MessagingNonce,
+            TxHashToMessagesToL1,
+            MessagesToL1Settlement,
+            MessageToL1HashToTxHashes,
             PendingContractToClassHashes,
             PendingContractToNonces,
             PendingContractStorage,
             Devnet,
             MempoolTransactions,
+            BlockNToTxTraces,
         ]
     };
     pub const NUM_COLUMNS: usize = Self::ALL.len();
@@ -284,11 +305,15 @@ impl Column {
             ContractStorage => "contract_storage",
             L1Messaging => "l1_messaging",
             L1MessagingNonce => "l1_messaging_nonce",
+            TxHashToMessagesToL1 => "tx_hash_to_messages_to_l1",
+            MessagesToL1Settlement => "messages_to_l1_settlement",
+            MessageToL1HashToTxHashes => "message_to_l1_hash_to_tx_hashes",
             PendingContractToClassHashes => "pending_contract_to_class_hashes",
             PendingContractToNonces => "pending_contract_to_nonces",
             PendingContractStorage => "pending_contract_storage",
             Devnet => "devnet",
             MempoolTransactions => "mempool_transactions",
+            BlockNToTxTraces => "block_n_to_tx_traces",
         }
     }
 }
@@ -357,6 +382,7 @@ impl SyncStatusCell {
 /// Madara client database backend singleton.
 pub struct MadaraBackend {
     backup_handle: Option<mpsc::Sender<BackupRequest>>,
+    prune_handle: Option<mpsc::Sender<()>>,
     db: Arc<DB>,
     chain_config: Arc<ChainConfig>,
     db_metrics: DbMetrics,
@@ -372,6 +398,11 @@ pub struct MadaraBackend {
     _temp_dir: Option<tempfile::TempDir>,
     sync_status: SyncStatusCell,
     starting_block: Option<u64>,
+    /// Oldest block whose state history has not been pruned.
+    oldest_state_block: BlockNStatus,
+    /// Held while pruning or reverting the state history.
+    prune_lock: Mutex<()>,
+    transaction_policy: std::sync::RwLock<Arc<TransactionPolicy>>,
 }
 
 impl fmt::Debug for MadaraBackend {
@@ -457,6 +488,8 @@ pub struct MadaraBackendConfig {
     pub backup_every_n_blocks: Option<u64>,
     pub flush_every_n_blocks: Option<u64>,
     pub rocksdb: RocksDBConfig,
+    pub trace_storage: TraceStorageConfig,
+    pub storage_mode: StorageMode,
 }
 
 impl MadaraBackendConfig {
@@ -469,6 +502,8 @@ impl MadaraBackendConfig {
             backup_every_n_blocks: None,
             flush_every_n_blocks: None,
             rocksdb: Default::default(),
+            trace_storage: Default::default(),
+            storage_mode: Default::default(),
         }
     }
     pub fn backup_dir(self, backup_dir: Option<PathBuf>) -> Self {
@@ -486,6 +521,12 @@ impl MadaraBackendConfig {
     pub fn trie_log(self, trie_log: TrieLogConfig) -> Self {
         Self { trie_log, ..self }
     }
+    pub fn trace_storage(self, trace_storage: TraceStorageConfig) -> Self {
+        Self { trace_storage, ..self }
+    }
+    pub fn storage_mode(self, storage_mode: StorageMode) -> Self {
+        Self { storage_mode, ..self }
+    }
 }
 
 impl MadaraBackend {
@@ -493,6 +534,17 @@ impl MadaraBackend {
         &self.chain_config
     }
 
+    /// The policy enforced when validating new transactions. It is loaded from the chain config at startup, and can
+    /// be replaced at runtime with [`Self::set_transaction_policy`].
+    pub fn transaction_policy(&self) -> Arc<TransactionPolicy> {
+        Arc::clone(&self.transaction_policy.read().expect("Poisoned lock"))
+    }
+
+    /// The policy is not persisted in the database: the chain config policy is enforced again after a restart.
+    pub fn set_transaction_policy(&self, policy: TransactionPolicy) {
+        *self.transaction_policy.write().expect("Poisoned lock") = Arc::new(policy);
+    }
+
     fn new(
         backup_handle: Option<mpsc::Sender<BackupRequest>>,
         db: Arc<DB>,
@@ -505,10 +557,12 @@ impl MadaraBackend {
             Some(config.trie_log.max_kept_snapshots),
             config.trie_log.snapshot_interval,
         ));
+        let transaction_policy = Arc::new(chain_config.transaction_policy.clone()).into();
         let backend = Self {
             writeopts_no_wal: make_write_opt_no_wal(),
             db_metrics: DbMetrics::register().context("Registering db metrics")?,
             backup_handle,
+            prune_handle: None,
             db,
             chain_config,
             watch_events: EventChannels::new(100),
@@ -518,6 +572,9 @@ impl MadaraBackend {
             head_status: ChainHead::default(),
             snapshots,
             watch_blocks: BlockWatch::new(),
+            oldest_state_block: BlockNStatus::default(),
+            prune_lock: Mutex::new(()),
+            transaction_policy,
             #[cfg(any(test, feature = "testing"))]
             _temp_dir: None,
         };
@@ -527,8 +584,16 @@ impl MadaraBackend {
 
     #[cfg(any(test, feature = "testing"))]
     pub fn open_for_testing(chain_config: Arc<ChainConfig>) -> Arc<MadaraBackend> {
+        Self::open_for_testing_with_trie_log(chain_config, TrieLogConfig::default())
+    }
+
+    /// Same as [`Self::open_for_testing`], keeping trie logs as configured by `trie_log`.
+    pub fn open_for_testing_with_trie_log(
+        chain_config: Arc<ChainConfig>,
+        trie_log: TrieLogConfig,
+    ) -> Arc<MadaraBackend> {
         let temp_dir = tempfile::TempDir::with_prefix("madara-test").unwrap();
-        let config = MadaraBackendConfig::new(&temp_dir);
+        let config = MadaraBackendConfig::new(&temp_dir).trie_log(trie_log);
         let db = open_rocksdb(temp_dir.as_ref(), &config.rocksdb).unwrap();
         let mut backend = Self::new(None, db, chain_config, config).unwrap();
         backend._temp_dir = Some(temp_dir);
@@ -542,9 +607,8 @@ impl MadaraBackend {
     ) -> anyhow::Result<Arc<MadaraBackend>> {
         // check if the db version is compatible with the current binary
         tracing::debug!("checking db version");
-        if let Some(db_version) =
-            db_version::check_db_version(&config.base_path).context("Checking database version")?
-        {
+        let db_version = db_version::check_db_version(&config.base_path).context("Checking database version")?;
+        if let Some(db_version) = db_version {
             tracing::debug!("version of existing db is {db_version}");
         }
 
@@ -579,12 +643,32 @@ impl MadaraBackend {
 
         let db = open_rocksdb(&db_path, &config.rocksdb)?;
 
+        let required_db_version = db_version::required_db_version();
+        if let Some(db_version) = db_version.filter(|version| *version < required_db_version) {
+            migrations::migrate(&db, db_version, required_db_version).context("Migrating the database")?;
+            db_version::write_db_version(&config.base_path).context("Updating database version")?;
+        }
+
+        // in pruned mode, a thread is spawned that compacts the state history in the background. It is woken up by
+        // new blocks using a mpsc channel.
+        let prune_channel = matches!(config.storage_mode, StorageMode::Pruned { .. }).then(|| mpsc::channel(1));
+        let (prune_handle, prune_receiver) = prune_channel.unzip();
+
         let mut backend = Self::new(backup_handle, db, chain_config, config)?;
+        backend.prune_handle = prune_handle;
         backend.check_configuration()?;
         backend.load_head_status_from_db()?;
+        backend.load_oldest_state_block_from_db()?;
         backend.update_metrics();
         backend.set_starting_block(backend.head_status.latest_full_block_n());
-        Ok(Arc::new(backend))
+
+        let backend = Arc::new(backend);
+        if let Some(receiver) = prune_receiver {
+            prune_db::spawn_prune_history_task(Arc::downgrade(&backend), receiver);
+            // catch up with the blocks imported while the node was stopped or in archive mode.
+            backend.request_history_pruning();
+        }
+        Ok(backend)
     }
 
     /// This function needs to be called by the downstream block importer consumer service to mark a
@@ -612,6 +696,7 @@ impl MadaraBackend {
         self.watch_blocks.on_new_block(block_info);
 
         self.save_head_status_to_db()?;
+        self.request_history_pruning();
 
         if self
             .config
@@ -662,6 +747,20 @@ impl MadaraBackend {
         &self,
         map: DatabaseKeyMapping,
     ) -> BonsaiStorage<BasicId, BonsaiDb, H> {
+        self.make_bonsai(BonsaiDb::new(Arc::clone(&self.db), Arc::clone(&self.snapshots), map))
+    }
+
+    /// Same as [`Self::get_bonsai`], but the writes to the trie are appended to `batch` instead of being applied to the
+    /// database.
+    pub(crate) fn get_bonsai_capturing_writes<H: StarkHash + Send + Sync>(
+        &self,
+        map: DatabaseKeyMapping,
+        batch: Arc<Mutex<WriteBatchWithTransaction>>,
+    ) -> BonsaiStorage<BasicId, BonsaiDb, H> {
+        self.make_bonsai(BonsaiDb::new(Arc::clone(&self.db), Arc::clone(&self.snapshots), map).capture_writes(batch))
+    }
+
+    fn make_bonsai<H: StarkHash + Send + Sync>(&self, db: BonsaiDb) -> BonsaiStorage<BasicId, BonsaiDb, H> {
         let config = BonsaiStorageConfig {
             max_saved_trie_logs: Some(self.config.trie_log.max_saved_trie_logs),
             max_saved_snapshots: Some(self.config.trie_log.max_kept_snapshots),
@@ -669,35 +768,21 @@ impl MadaraBackend {
         };
 
         BonsaiStorage::new(
-            BonsaiDb::new(Arc::clone(&self.db), Arc::clone(&self.snapshots), map),
-            config,
-            // Every global tree has keys of 251 bits.
+            db, config, // Every global tree has keys of 251 bits.
             251,
         )
     }
 
     pub fn contract_trie(&self) -> GlobalTrie<Pedersen> {
-        self.get_bonsai(DatabaseKeyMapping {
-            flat: Column::BonsaiContractsFlat,
-            trie: Column::BonsaiContractsTrie,
-            log: Column::BonsaiContractsLog,
-        })
+        self.get_bonsai(DatabaseKeyMapping::CONTRACTS)
     }
 
     pub fn contract_storage_trie(&self) -> GlobalTrie<Pedersen> {
-        self.get_bonsai(DatabaseKeyMapping {
-            flat: Column::BonsaiContractsStorageFlat,
-            trie: Column::BonsaiContractsStorageTrie,
-            log: Column::BonsaiContractsStorageLog,
-        })
+        self.get_bonsai(DatabaseKeyMapping::CONTRACTS_STORAGE)
     }
 
     pub fn class_trie(&self) -> GlobalTrie<Poseidon> {
-        self.get_bonsai(DatabaseKeyMapping {
-            flat: Column::BonsaiClassesFlat,
-            trie: Column::BonsaiClassesTrie,
-            log: Column::BonsaiClassesLog,
-        })
+        self.get_bonsai(DatabaseKeyMapping::CLASSES)
     }
 
     /// Returns the total storage size
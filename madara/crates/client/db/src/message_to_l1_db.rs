@@ -0,0 +1,143 @@
+use crate::{Column, DatabaseExt, MadaraBackend, MadaraStorageError, WriteBatchWithTransaction, DB};
+use mp_receipt::{Hash256, TransactionReceipt};
+use rocksdb::{Direction, IteratorMode};
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
+use std::collections::hash_map::{Entry, HashMap};
+
+type Result<T, E = MadaraStorageError> = std::result::Result<T, E>;
+
+pub const LAST_INDEXED_L1_BLOCK: &[u8] = b"LAST_INDEXED_L1_BLOCK";
+
+/// What the settlement layer core contract did with an L2 to L1 message. The same message can be sent several times,
+/// the core contract keeps a counter per message hash.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageToL1Settlement {
+    /// Number of times the message was registered by a state update (`LogMessageToL1`), and could be consumed.
+    pub logged: u64,
+    /// Number of times the message was consumed (`ConsumedMessageToL1`).
+    pub consumed: u64,
+}
+
+/// An event of the settlement layer core contract about an L2 to L1 message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MessageToL1Event {
+    Logged(Hash256),
+    Consumed(Hash256),
+}
+
+/// Key of the [`Column::MessageToL1HashToTxHashes`] column: the message hash followed by the transaction hash, so that
+/// the transactions that sent a message can be found by prefix.
+pub(crate) fn message_to_l1_tx_key(message_hash: &[u8; 32], tx_hash: &Felt) -> [u8; 64] {
+    let mut key = [0u8; 64];
+    key[..32].copy_from_slice(message_hash);
+    key[32..].copy_from_slice(&tx_hash.to_bytes_be());
+    key
+}
+
+pub(crate) fn index_messages_to_l1(
+    db: &DB,
+    receipts: &[TransactionReceipt],
+    batch: &mut WriteBatchWithTransaction,
+) -> Result<()> {
+    let tx_col = db.get_column(Column::TxHashToMessagesToL1);
+    let message_col = db.get_column(Column::MessageToL1HashToTxHashes);
+    for receipt in receipts.iter().filter(|receipt| !receipt.messages_sent().is_empty()) {
+        let tx_hash = receipt.transaction_hash();
+        let hashes: Vec<[u8; 32]> = receipt.messages_sent().iter().map(|msg| *msg.compute_hash().as_bytes()).collect();
+        for hash in &hashes {
+            batch.put_cf(&message_col, message_to_l1_tx_key(hash, &tx_hash), b"");
+        }
+        batch.put_cf(&tx_col, bincode::serialize(&tx_hash)?, bincode::serialize(&hashes)?);
+    }
+    Ok(())
+}
+
+impl MadaraBackend {
+    /// Index the hashes of the L2 to L1 messages sent by the transactions of a closed block.
+    pub(crate) fn message_to_l1_db_store_block(
+        &self,
+        receipts: &[TransactionReceipt],
+        batch: &mut WriteBatchWithTransaction,
+    ) -> Result<()> {
+        index_messages_to_l1(&self.db, receipts, batch)
+    }
+
+    pub(crate) fn message_to_l1_db_revert_block(
+        &self,
+        tx_hashes: &[Felt],
+        batch: &mut WriteBatchWithTransaction,
+    ) -> Result<()> {
+        let tx_col = self.db.get_column(Column::TxHashToMessagesToL1);
+        let message_col = self.db.get_column(Column::MessageToL1HashToTxHashes);
+        for tx_hash in tx_hashes {
+            for hash in self.get_messages_to_l1_of_tx(tx_hash)? {
+                batch.delete_cf(&message_col, message_to_l1_tx_key(hash.as_bytes(), tx_hash));
+            }
+            batch.delete_cf(&tx_col, bincode::serialize(tx_hash)?);
+        }
+        Ok(())
+    }
+
+    /// Whether a transaction of a closed block sent this L2 to L1 message.
+    #[tracing::instrument(skip(self), fields(module = "MessageToL1DB"))]
+    pub fn is_message_to_l1_sent(&self, message_hash: &Hash256) -> Result<bool> {
+        let col = self.db.get_column(Column::MessageToL1HashToTxHashes);
+        let prefix = message_hash.as_bytes();
+        match self.db.iterator_cf(&col, IteratorMode::From(prefix, Direction::Forward)).next() {
+            Some(kv) => Ok(kv?.0.starts_with(prefix)),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns the hashes of the L2 to L1 messages sent by a transaction of a closed block, in order.
+    #[tracing::instrument(skip(self), fields(module = "MessageToL1DB"))]
+    pub fn get_messages_to_l1_of_tx(&self, tx_hash: &Felt) -> Result<Vec<Hash256>> {
+        let col = self.db.get_column(Column::TxHashToMessagesToL1);
+        let Some(res) = self.db.get_pinned_cf(&col, bincode::serialize(tx_hash)?)? else { return Ok(vec![]) };
+        let hashes: Vec<[u8; 32]> = bincode::deserialize(&res)?;
+        Ok(hashes.into_iter().map(Hash256::from_bytes).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(module = "MessageToL1DB"))]
+    pub fn get_message_to_l1_settlement(&self, message_hash: &Hash256) -> Result<MessageToL1Settlement> {
+        let col = self.db.get_column(Column::MessagesToL1Settlement);
+        let Some(res) = self.db.get_pinned_cf(&col, message_hash.as_bytes())? else { return Ok(Default::default()) };
+        Ok(bincode::deserialize(&res)?)
+    }
+
+    /// The last settlement layer block whose L2 to L1 message events were indexed.
+    #[tracing::instrument(skip(self), fields(module = "MessageToL1DB"))]
+    pub fn get_messages_to_l1_last_indexed_l1_block(&self) -> Result<Option<u64>> {
+        let col = self.db.get_column(Column::MessagesToL1Settlement);
+        let Some(res) = self.db.get_pinned_cf(&col, LAST_INDEXED_L1_BLOCK)? else { return Ok(None) };
+        Ok(Some(bincode::deserialize(&res)?))
+    }
+
+    /// Apply the L2 to L1 message events found in the settlement layer blocks up to `l1_block_n`, and mark these blocks
+    /// as indexed. Both are written atomically, so that events are never counted twice.
+    #[tracing::instrument(skip(self, events), fields(module = "MessageToL1DB"))]
+    pub fn store_messages_to_l1_events(&self, events: &[MessageToL1Event], l1_block_n: u64) -> Result<()> {
+        let col = self.db.get_column(Column::MessagesToL1Settlement);
+        let mut updated = HashMap::<[u8; 32], MessageToL1Settlement>::new();
+        for event in events {
+            let (MessageToL1Event::Logged(hash) | MessageToL1Event::Consumed(hash)) = event;
+            let settlement = match updated.entry(*hash.as_bytes()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.get_message_to_l1_settlement(hash)?),
+            };
+            match event {
+                MessageToL1Event::Logged(_) => settlement.logged += 1,
+                MessageToL1Event::Consumed(_) => settlement.consumed += 1,
+            }
+        }
+
+        let mut batch = WriteBatchWithTransaction::default();
+        for (hash, settlement) in updated {
+            batch.put_cf(&col, hash, bincode::serialize(&settlement)?);
+        }
+        batch.put_cf(&col, LAST_INDEXED_L1_BLOCK, bincode::serialize(&l1_block_n)?);
+        self.db.write_opt(batch, &self.writeopts_no_wal)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+//! Migrations of databases created by older versions of madara.
+//!
+//! When the encoding of a column changes, the database version in `.db-versions.yml` is bumped and a migration step
+//! rewriting the affected rows is added here. Steps are run in order by [`migrate`] when the node opens a database
+//! whose version is older than the current one, before anything else reads from it.
+//!
+//! Rows are rewritten in batches of [`MIGRATION_BATCH_SIZE`]. Each batch also saves the progress of the step, so that
+//! a migration interrupted by a crash resumes where it stopped instead of decoding already migrated rows with the old
+//! layout.
+
+use crate::message_to_l1_db::index_messages_to_l1;
+use crate::{Column, DatabaseExt, WriteBatchWithTransaction, DB};
+use anyhow::Context;
+use mp_block::header::{BlockTimestamp, GasPrices, Header, PendingHeader};
+use mp_block::{MadaraBlockInfo, MadaraBlockInner, MadaraPendingBlockInfo};
+use mp_chain_config::{L1DataAvailabilityMode, StarknetVersion};
+use rocksdb::{Direction, IteratorMode};
+use starknet_types_core::felt::Felt;
+
+/// Oldest database version that can still be migrated to the current one.
+pub(crate) const OLDEST_MIGRATABLE_DB_VERSION: u32 = 4;
+
+const MIGRATION_BATCH_SIZE: usize = 10_000;
+
+const ROW_MIGRATION_CURSOR: &[u8] = b"migration_cursor";
+// Same row as in block_db.rs.
+const ROW_PENDING_INFO: &[u8] = b"pending_info";
+
+/// Progress of a migration step, identified by the version it migrates to.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct MigrationCursor {
+    to_version: u32,
+    /// Column being rewritten and the last key rewritten in it.
+    last_key: Option<(String, Vec<u8>)>,
+    done: bool,
+}
+
+impl MigrationCursor {
+    fn load(db: &DB, to_version: u32) -> anyhow::Result<Self> {
+        let cursor = match db.get_cf(&db.get_column(Column::BlockStorageMeta), ROW_MIGRATION_CURSOR)? {
+            Some(bytes) => bincode::deserialize::<MigrationCursor>(&bytes)?,
+            None => Self::default(),
+        };
+        Ok(if cursor.to_version == to_version { cursor } else { Self { to_version, ..Default::default() } })
+    }
+
+    fn save_to_batch(&self, db: &DB, batch: &mut WriteBatchWithTransaction) -> anyhow::Result<()> {
+        batch.put_cf(&db.get_column(Column::BlockStorageMeta), ROW_MIGRATION_CURSOR, bincode::serialize(self)?);
+        Ok(())
+    }
+}
+
+/// Migrates the database from `from_version` to `to_version`, one version at a time.
+///
+/// The cursor of the last step is kept once it is done: it is only the `.db-version` file written by the caller
+/// afterwards that tells the step does not have to run again.
+pub(crate) fn migrate(db: &DB, from_version: u32, to_version: u32) -> anyhow::Result<()> {
+    for version in from_version..to_version {
+        let mut cursor = MigrationCursor::load(db, version + 1)?;
+        if cursor.done {
+            continue;
+        }
+        tracing::info!("🔄 Migrating the database from version {version} to version {}", version + 1);
+        match version {
+            4 => v4_to_v5(db, &mut cursor).context("Migrating the database from version 4 to version 5")?,
+            5 => v5_to_v6(db, &mut cursor).context("Migrating the database from version 5 to version 6")?,
+            _ => anyhow::bail!("No migration from database version {version}"),
+        }
+    }
+    Ok(())
+}
+
+/// Calls `f` on every row of `col`, resuming after the last key saved in the cursor. `f` adds the writes migrating
+/// the row to the batch.
+fn migrate_column(
+    db: &DB,
+    cursor: &mut MigrationCursor,
+    col: Column,
+    mut f: impl FnMut(&[u8], &[u8], &mut WriteBatchWithTransaction) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let handle = db.get_column(col);
+    let resume_key = cursor.last_key.take().filter(|(name, _)| name == col.rocksdb_name()).map(|(_, key)| key);
+    let mode = match &resume_key {
+        Some(key) => IteratorMode::From(key, Direction::Forward),
+        None => IteratorMode::Start,
+    };
+
+    let mut batch = WriteBatchWithTransaction::default();
+    let mut rows = 0;
+    for kv in db.iterator_cf(&handle, mode) {
+        let (key, value) = kv?;
+        if resume_key.as_deref() == Some(&*key) {
+            continue;
+        }
+        f(&key, &value, &mut batch)?;
+        cursor.last_key = Some((col.rocksdb_name().into(), key.into()));
+
+        rows += 1;
+        if rows >= MIGRATION_BATCH_SIZE {
+            cursor.save_to_batch(db, &mut batch)?;
+            db.write(std::mem::take(&mut batch))?;
+            rows = 0;
+        }
+    }
+    cursor.save_to_batch(db, &mut batch)?;
+    db.write(batch)?;
+    Ok(())
+}
+
+/// Rewrites every row of `col` with `f`, resuming after the last key saved in the cursor.
+fn rewrite_column(
+    db: &DB,
+    cursor: &mut MigrationCursor,
+    col: Column,
+    mut f: impl FnMut(&[u8]) -> anyhow::Result<Vec<u8>>,
+) -> anyhow::Result<()> {
+    let handle = db.get_column(col);
+    migrate_column(db, cursor, col, |key, value, batch| {
+        batch.put_cf(&handle, key, f(value)?);
+        Ok(())
+    })
+}
+
+/// Version 5 added the L2 gas prices to [`GasPrices`], which changed the layout of the stored block headers. Blocks
+/// stored before did not have an L2 gas price, it is migrated as zero.
+fn v4_to_v5(db: &DB, cursor: &mut MigrationCursor) -> anyhow::Result<()> {
+    rewrite_column(db, cursor, Column::BlockNToBlockInfo, |bytes| {
+        let info: v4::MadaraBlockInfo = bincode::deserialize(bytes)?;
+        Ok(bincode::serialize(&MadaraBlockInfo::from(info))?)
+    })?;
+
+    let mut batch = WriteBatchWithTransaction::default();
+    let meta_col = db.get_column(Column::BlockStorageMeta);
+    if let Some(bytes) = db.get_cf(&meta_col, ROW_PENDING_INFO)? {
+        let info: v4::MadaraPendingBlockInfo = bincode::deserialize(&bytes)?;
+        batch.put_cf(&meta_col, ROW_PENDING_INFO, bincode::serialize(&MadaraPendingBlockInfo::from(info))?);
+    }
+    cursor.done = true;
+    cursor.save_to_batch(db, &mut batch)?;
+    db.write(batch)?;
+    Ok(())
+}
+
+/// Version 6 indexes the L2 to L1 messages sent by the transactions of closed blocks, which is backfilled from the
+/// stored receipts.
+fn v5_to_v6(db: &DB, cursor: &mut MigrationCursor) -> anyhow::Result<()> {
+    migrate_column(db, cursor, Column::BlockNToBlockInner, |_, bytes, batch| {
+        let inner: MadaraBlockInner = bincode::deserialize(bytes)?;
+        Ok(index_messages_to_l1(db, &inner.receipts, batch)?)
+    })?;
+
+    let mut batch = WriteBatchWithTransaction::default();
+    cursor.done = true;
+    cursor.save_to_batch(db, &mut batch)?;
+    db.write(batch)?;
+    Ok(())
+}
+
+/// Layouts of the types stored in version 4 databases.
+pub(crate) mod v4 {
+    use super::*;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct GasPrices {
+        pub eth_l1_gas_price: u128,
+        pub strk_l1_gas_price: u128,
+        pub eth_l1_data_gas_price: u128,
+        pub strk_l1_data_gas_price: u128,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct Header {
+        pub parent_block_hash: Felt,
+        pub block_number: u64,
+        pub global_state_root: Felt,
+        pub sequencer_address: Felt,
+        pub block_timestamp: BlockTimestamp,
+        pub transaction_count: u64,
+        pub transaction_commitment: Felt,
+        pub event_count: u64,
+        pub event_commitment: Felt,
+        pub state_diff_length: Option<u64>,
+        pub state_diff_commitment: Option<Felt>,
+        pub receipt_commitment: Option<Felt>,
+        pub protocol_version: StarknetVersion,
+        pub l1_gas_price: GasPrices,
+        pub l1_da_mode: L1DataAvailabilityMode,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct PendingHeader {
+        pub parent_block_hash: Felt,
+        pub sequencer_address: Felt,
+        pub block_timestamp: BlockTimestamp,
+        pub protocol_version: StarknetVersion,
+        pub l1_gas_price: GasPrices,
+        pub l1_da_mode: L1DataAvailabilityMode,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct MadaraBlockInfo {
+        pub header: Header,
+        pub block_hash: Felt,
+        pub tx_hashes: Vec<Felt>,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct MadaraPendingBlockInfo {
+        pub header: PendingHeader,
+        pub tx_hashes: Vec<Felt>,
+    }
+}
+
+impl From<v4::GasPrices> for GasPrices {
+    fn from(prices: v4::GasPrices) -> Self {
+        Self {
+            eth_l1_gas_price: prices.eth_l1_gas_price,
+            strk_l1_gas_price: prices.strk_l1_gas_price,
+            eth_l1_data_gas_price: prices.eth_l1_data_gas_price,
+            strk_l1_data_gas_price: prices.strk_l1_data_gas_price,
+            eth_l2_gas_price: 0,
+            strk_l2_gas_price: 0,
+        }
+    }
+}
+
+impl From<v4::MadaraBlockInfo> for MadaraBlockInfo {
+    fn from(info: v4::MadaraBlockInfo) -> Self {
+        let header = info.header;
+        Self {
+            header: Header {
+                parent_block_hash: header.parent_block_hash,
+                block_number: header.block_number,
+                global_state_root: header.global_state_root,
+                sequencer_address: header.sequencer_address,
+                block_timestamp: header.block_timestamp,
+                transaction_count: header.transaction_count,
+                transaction_commitment: header.transaction_commitment,
+                event_count: header.event_count,
+                event_commitment: header.event_commitment,
+                state_diff_length: header.state_diff_length,
+                state_diff_commitment: header.state_diff_commitment,
+                receipt_commitment: header.receipt_commitment,
+                protocol_version: header.protocol_version,
+                l1_gas_price: header.l1_gas_price.into(),
+                l1_da_mode: header.l1_da_mode,
+            },
+            block_hash: info.block_hash,
+            tx_hashes: info.tx_hashes,
+        }
+    }
+}
+
+impl From<v4::MadaraPendingBlockInfo> for MadaraPendingBlockInfo {
+    fn from(info: v4::MadaraPendingBlockInfo) -> Self {
+        let header = info.header;
+        Self {
+            header: PendingHeader {
+                parent_block_hash: header.parent_block_hash,
+                sequencer_address: header.sequencer_address,
+                block_timestamp: header.block_timestamp,
+                protocol_version: header.protocol_version,
+                l1_gas_price: header.l1_gas_price.into(),
+                l1_da_mode: header.l1_da_mode,
+            },
+            tx_hashes: info.tx_hashes,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::db_block_id::RawDbBlockId;
+use crate::{Column, DatabaseExt, MadaraBackend, MadaraStorageError, WriteBatchWithTransaction};
+use std::sync::Weak;
+use tokio::sync::mpsc;
+
+type Result<T, E = MadaraStorageError> = std::result::Result<T, E>;
+
+/// Storage mode of the contract state history, meaning the class hash, nonce and storage of every contract at every
+/// block. Blocks, receipts, state diffs and the latest state are kept in both modes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StorageMode {
+    /// Keep the state history of every block.
+    #[default]
+    Archive,
+    /// Only keep the state history of the last `keep_blocks` blocks. Older history is compacted away in the
+    /// background, and reading the state of an older block returns [`MadaraStorageError::BlockPruned`].
+    Pruned { keep_blocks: u64 },
+}
+
+const ROW_OLDEST_STATE_BLOCK: &[u8] = b"oldest_state_block";
+
+/// This runs in another thread so that pruning does not slow down block import.
+pub(crate) fn spawn_prune_history_task(backend: Weak<MadaraBackend>, mut recv: mpsc::Receiver<()>) {
+    std::thread::spawn(move || {
+        while recv.blocking_recv().is_some() {
+            let Some(backend) = backend.upgrade() else { break };
+            if let Err(err) = backend.prune_history() {
+                tracing::error!("❗ Error while pruning the state history: {err:#}");
+            }
+        }
+    });
+}
+
+impl MadaraBackend {
+    /// The oldest block whose state can still be read, or [`None`] when the state history has never been pruned.
+    pub fn oldest_state_block(&self) -> Option<u64> {
+        self.oldest_state_block.current()
+    }
+
+    /// Returns [`MadaraStorageError::BlockPruned`] when the state of `block_n` has been pruned.
+    pub fn check_state_not_pruned(&self, block_n: u64) -> Result<()> {
+        match self.oldest_state_block() {
+            Some(oldest_available) if block_n < oldest_available => {
+                Err(MadaraStorageError::BlockPruned { block_n, oldest_available })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn load_oldest_state_block_from_db(&self) -> Result<()> {
+        let col = self.db.get_column(Column::BlockStorageMeta);
+        if let Some(res) = self.db.get_pinned_cf(&col, ROW_OLDEST_STATE_BLOCK)? {
+            self.oldest_state_block.set_current(Some(bincode::deserialize(&res)?));
+        }
+        Ok(())
+    }
+
+    /// Wake up the background pruning task, if the database is in pruned mode. This does nothing if the task is
+    /// already pruning.
+    pub(crate) fn request_history_pruning(&self) {
+        if let Some(sender) = &self.prune_handle {
+            let _ = sender.try_send(());
+        }
+    }
+
+    /// Compact away the contract state history of the blocks older than the last `keep_blocks` blocks. For every
+    /// contract storage key, nonce and class hash, only the latest entry at or before the new oldest state block is
+    /// kept, so that the state of that block and of every later block can still be read.
+    ///
+    /// This does nothing in archive mode. It is called in the background as new blocks are imported.
+    #[tracing::instrument(skip(self), fields(module = "PruneDB"))]
+    pub fn prune_history(&self) -> Result<()> {
+        let StorageMode::Pruned { keep_blocks } = self.config.storage_mode else { return Ok(()) };
+        let _guard = self.prune_lock.lock().expect("Poisoned lock");
+
+        let Some(latest_block_n) = self.head_status.latest_full_block_n() else { return Ok(()) };
+        let Some(new_oldest) = latest_block_n.checked_sub(keep_blocks) else { return Ok(()) };
+        let first_block_n = self.oldest_state_block().map(|n| n + 1).unwrap_or(0);
+        if first_block_n > new_oldest {
+            return Ok(());
+        }
+
+        let meta = self.db.get_column(Column::BlockStorageMeta);
+        for block_n in first_block_n..=new_oldest {
+            let state_diff = self.get_block_state_diff(&RawDbBlockId::Number(block_n))?.ok_or_else(|| {
+                MadaraStorageError::InconsistentStorage(format!("State diff of block #{block_n} not found").into())
+            })?;
+
+            let mut batch = WriteBatchWithTransaction::default();
+            self.contract_db_prune_block(block_n, &state_diff, &mut batch)?;
+            batch.put_cf(&meta, ROW_OLDEST_STATE_BLOCK, bincode::serialize(&block_n)?);
+            self.db.write_opt(batch, &self.writeopts_no_wal)?;
+            self.oldest_state_block.set_current(Some(block_n));
+        }
+
+        tracing::debug!("Pruned the state history of blocks #{first_block_n} to #{new_oldest}");
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use crate::bonsai_db::DatabaseKeyMapping;
+use crate::db_block_id::{DbBlockId, RawDbBlockId};
+use crate::mempool_db::NonceInfo;
+use crate::{MadaraBackend, MadaraStorageError, WriteBatchWithTransaction};
+use anyhow::Context;
+use bonsai_trie::id::BasicId;
+use mp_block::MadaraMaybePendingBlock;
+use mp_transactions::validated::{TxTimestamp, ValidatedMempoolTx};
+use mp_transactions::Transaction;
+use starknet_api::core::Nonce;
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon};
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+
+impl MadaraBackend {
+    /// Revert the chain to `block_n`, which becomes the new latest block.
+    ///
+    /// Every block above `block_n` is deleted along with its transactions, receipts, state diff, events, traces and
+    /// declared classes. The contract history columns and the global tries are rolled back, the latter using the trie
+    /// log. This means the revert cannot be deeper than the number of trie logs kept by the database.
+    ///
+    /// The account transactions of the reverted blocks and of the pending block are saved to the mempool, which will
+    /// pick them up the next time it is started. L1 handler transactions are not restored as they come from the
+    /// settlement layer: their messages are unmarked and the messaging sync is rewound to the earliest of them, so
+    /// they are processed again.
+    ///
+    /// The services writing to the database (block production and sync) must not be running during a revert.
+    ///
+    /// Returns the transactions that were saved to the mempool, a running mempool must be given them as well.
+    pub fn revert_to(&self, block_n: u64) -> anyhow::Result<Vec<ValidatedMempoolTx>> {
+        let latest_block_n =
+            self.get_latest_block_n()?.ok_or_else(|| Self::invalid_revert_target(block_n, "the database is empty"))?;
+        if block_n >= latest_block_n {
+            return Err(Self::invalid_revert_target(block_n, format!("the latest block is #{latest_block_n}")).into());
+        }
+
+        let restored_txs = self.revert_blocks(block_n, latest_block_n, /* restore_txs */ true)?;
+
+        for tx in &restored_txs {
+            let nonce = Nonce(tx.tx.nonce());
+            let current_nonce = Nonce(
+                self.get_contract_nonce_at(&DbBlockId::Number(block_n), &tx.contract_address)?.unwrap_or_default(),
+            );
+            let nonce_next = Nonce(nonce.0 + Felt::ONE);
+            let nonce_info = if nonce == current_nonce {
+                NonceInfo::ready(nonce, nonce_next)
+            } else {
+                NonceInfo::pending(nonce, nonce_next)
+            };
+            self.save_mempool_transaction(tx, &nonce_info)?;
+        }
+
+        self.flush()?;
+        tracing::info!(
+            "⏪ Reverted the chain to block #{block_n}, {} transactions restored to the mempool",
+            restored_txs.len()
+        );
+
+        Ok(restored_txs)
+    }
+
+    /// Revert the chain to `block_n`, the common ancestor with an upstream chain that has been reorganised.
+    ///
+    /// Unlike [`Self::revert_to`], the transactions of the reverted blocks are not restored to the mempool as they are
+    /// handled by the upstream sequencer, and `block_n` may be the latest block. The blocks the sync process saved past
+    /// the latest full block are removed in both cases, since they may come from the stale branch.
+    ///
+    /// The sync pipelines must be drained before calling this function.
+    pub fn revert_reorged_blocks(&self, block_n: u64) -> anyhow::Result<()> {
+        let latest_block_n =
+            self.get_latest_block_n()?.ok_or_else(|| Self::invalid_revert_target(block_n, "the database is empty"))?;
+        if block_n > latest_block_n {
+            return Err(Self::invalid_revert_target(block_n, format!("the latest block is #{latest_block_n}")).into());
+        }
+
+        self.revert_blocks(block_n, latest_block_n, /* restore_txs */ false)?;
+
+        self.flush()?;
+        Ok(())
+    }
+
+    fn invalid_revert_target(block_n: u64, reason: impl Into<Cow<'static, str>>) -> MadaraStorageError {
+        MadaraStorageError::InvalidRevertTarget { target: block_n, reason: reason.into() }
+    }
+
+    /// Delete every stored block above `block_n` and roll back the global tries and the head status. When
+    /// `restore_txs` is set, returns the account transactions of the reverted full blocks and of the pending block and
+    /// unmarks the L1 messages of their L1 handler transactions.
+    fn revert_blocks(
+        &self,
+        block_n: u64,
+        latest_block_n: u64,
+        restore_txs: bool,
+    ) -> anyhow::Result<Vec<ValidatedMempoolTx>> {
+        let _guard = self.prune_lock.lock().expect("Poisoned lock");
+        if let Some(oldest_available) = self.oldest_state_block().filter(|oldest| *oldest > block_n) {
+            return Err(Self::invalid_revert_target(
+                block_n,
+                format!("the state history has been pruned up to block #{oldest_available}"),
+            )
+            .into());
+        }
+        if let Some(l1_last_confirmed) = self.get_l1_last_confirmed_block()? {
+            if l1_last_confirmed > block_n {
+                return Err(Self::invalid_revert_target(
+                    block_n,
+                    format!("block #{l1_last_confirmed} is already confirmed on L1"),
+                )
+                .into());
+            }
+        }
+        // The global tries are not computed when they are disabled.
+        let trie_head = self.head_status.global_trie.current().filter(|trie_head| *trie_head > block_n);
+        let max_saved_trie_logs = self.config.trie_log.max_saved_trie_logs as u64;
+        if trie_head.is_some_and(|trie_head| trie_head - block_n > max_saved_trie_logs) {
+            return Err(Self::invalid_revert_target(
+                block_n,
+                format!("the database only keeps the trie logs of the last {max_saved_trie_logs} blocks"),
+            )
+            .into());
+        }
+
+        tracing::info!("⏪ Reverting the chain from block #{latest_block_n} to block #{block_n}");
+
+        // The trie revert, the deleted blocks and the new head status are written in a single batch, a crash can't
+        // leave the tries and the block storage at different heights.
+        let captured_batch = Arc::new(Mutex::new(WriteBatchWithTransaction::default()));
+        if let Some(trie_head) = trie_head {
+            let (requested_id, current_id) = (BasicId::new(block_n), BasicId::new(trie_head));
+            self.get_bonsai_capturing_writes::<Pedersen>(DatabaseKeyMapping::CONTRACTS, Arc::clone(&captured_batch))
+                .revert_to(requested_id, current_id)
+                .context("Reverting the contract trie")?;
+            self.get_bonsai_capturing_writes::<Pedersen>(
+                DatabaseKeyMapping::CONTRACTS_STORAGE,
+                Arc::clone(&captured_batch),
+            )
+            .revert_to(requested_id, current_id)
+            .context("Reverting the contract storage trie")?;
+            self.get_bonsai_capturing_writes::<Poseidon>(DatabaseKeyMapping::CLASSES, Arc::clone(&captured_batch))
+                .revert_to(requested_id, current_id)
+                .context("Reverting the class trie")?;
+        }
+        let mut batch = Arc::into_inner(captured_batch)
+            .context("The reverted tries still hold the batch")?
+            .into_inner()
+            .expect("Poisoned lock");
+        let mut restored_txs = vec![];
+        let mut reverted_l1_nonces = vec![];
+
+        // This includes the blocks partially saved by the sync process above the latest full block.
+        for reverted_block_n in self.block_db_stored_blocks_above(block_n)? {
+            let id = RawDbBlockId::Number(reverted_block_n);
+            let info = self
+                .get_block_info(&id)?
+                .with_context(|| format!("Block #{reverted_block_n} not found"))?
+                .into_closed()
+                .context("Got a pending block when trying to get a closed one")?;
+
+            if restore_txs && reverted_block_n <= latest_block_n {
+                let inner =
+                    self.get_block_inner(&id)?.with_context(|| format!("Block #{reverted_block_n} not found"))?;
+                let id = DbBlockId::Number(reverted_block_n);
+                for (tx, tx_hash) in inner.transactions.into_iter().zip(info.tx_hashes.iter()) {
+                    if let Transaction::L1Handler(tx) = &tx {
+                        reverted_l1_nonces.push(Nonce(tx.nonce.into()));
+                    }
+                    restored_txs.extend(self.reverted_mempool_tx(&id, tx, *tx_hash)?);
+                }
+            }
+
+            self.block_db_revert_block(&info, &mut batch)?;
+            if let Some(state_diff) = self.get_block_state_diff(&id)? {
+                self.contract_db_revert_block(reverted_block_n, &state_diff, &mut batch)?;
+                self.class_db_revert_block(reverted_block_n, &state_diff, &mut batch)?;
+            }
+        }
+
+        if restore_txs && self.has_pending_block()? {
+            let id = DbBlockId::Pending;
+            let MadaraMaybePendingBlock { info, inner } = self.get_block(&id)?.context("Pending block not found")?;
+            for (tx, tx_hash) in inner.transactions.into_iter().zip(info.tx_hashes().iter()) {
+                if let Transaction::L1Handler(tx) = &tx {
+                    reverted_l1_nonces.push(Nonce(tx.nonce.into()));
+                }
+                restored_txs.extend(self.reverted_mempool_tx(&id, tx, *tx_hash)?);
+            }
+        }
+        self.l1_db_revert_messages(reverted_l1_nonces, &mut batch)?;
+
+        for status in [
+            &self.head_status.headers,
+            &self.head_status.state_diffs,
+            &self.head_status.classes,
+            &self.head_status.transactions,
+            &self.head_status.events,
+            &self.head_status.global_trie,
+            &self.head_status.full_block,
+        ] {
+            if status.current().is_some_and(|current| current > block_n) {
+                status.set_current(Some(block_n));
+            }
+        }
+        self.save_head_status_to_batch(&mut batch)?;
+        self.db.write(batch)?;
+
+        self.snapshots.revert_head(block_n);
+        self.clear_pending_block()?;
+
+        Ok(restored_txs)
+    }
+
+    fn reverted_mempool_tx(
+        &self,
+        id: &DbBlockId,
+        tx: Transaction,
+        tx_hash: Felt,
+    ) -> anyhow::Result<Option<ValidatedMempoolTx>> {
+        let (contract_address, converted_class) = match &tx {
+            Transaction::Invoke(tx) => (*tx.sender_address(), None),
+            Transaction::DeployAccount(tx) => (tx.calculate_contract_address(), None),
+            Transaction::Declare(tx) => {
+                let class = self
+                    .get_converted_class(id, tx.class_hash())?
+                    .with_context(|| format!("Class {:#x} declared in {id:?} not found", tx.class_hash()))?;
+                (*tx.sender_address(), Some(class))
+            }
+            // Legacy deploy transactions cannot be submitted anymore.
+            Transaction::L1Handler(_) | Transaction::Deploy(_) => return Ok(None),
+        };
+
+        Ok(Some(ValidatedMempoolTx {
+            tx,
+            paid_fee_on_l1: None,
+            contract_address,
+            arrived_at: TxTimestamp::now(),
+            converted_class,
+            tx_hash,
+        }))
+    }
+}
@@ -72,6 +72,18 @@ impl Snapshots {
         }
     }
 
+    /// Called when the chain has been reverted to `block_n`. Snapshots of the reverted blocks are dropped, and a new
+    /// head snapshot is made.
+    #[tracing::instrument(skip(self), fields(module = "BonsaiDB"))]
+    pub fn revert_head(&self, block_n: u64) {
+        let snapshot = Arc::new(SnapshotWithDBArc::new(Arc::clone(&self.db)));
+
+        let mut inner = self.inner.write().expect("Poisoned lock");
+        let _reverted = inner.historical.split_off(&(block_n + 1));
+        inner.head = snapshot;
+        inner.head_block_n = Some(block_n);
+    }
+
     /// Get the closest snapshot that had been made at or after the provided `block_n`.
     /// Also returns the block_n, which can be null if no block is in database in that snapshot.
     #[tracing::instrument(skip(self), fields(module = "BonsaiDB"))]
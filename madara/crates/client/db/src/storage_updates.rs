@@ -194,6 +194,7 @@ impl MadaraBackend {
         let (transactions, receipts) = value.into_iter().map(|t| (t.transaction, t.receipt)).unzip();
         let block_inner = MadaraBlockInner { transactions, receipts };
         tx.put_cf(&block_n_to_block_inner, &block_n_encoded, &bincode::serialize(&block_inner)?);
+        self.message_to_l1_db_store_block(&block_inner.receipts, &mut tx)?;
 
         self.db.write_opt(tx, &self.writeopts_no_wal)?;
         Ok(())
@@ -1,3 +1,8 @@
 pub mod common;
 pub mod test_block;
+pub mod test_message_to_l1;
+pub mod test_migrations;
 pub mod test_open;
+pub mod test_prune;
+pub mod test_revert;
+pub mod test_trace;
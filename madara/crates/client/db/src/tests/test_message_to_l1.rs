@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod message_to_l1_tests {
+    use super::super::common::temp_db::temp_db;
+    use super::super::common::*;
+    use crate::message_to_l1_db::{MessageToL1Event, MessageToL1Settlement};
+    use mp_block::{BlockHeaderWithSignatures, Header, TransactionWithReceipt};
+    use mp_receipt::{InvokeTransactionReceipt, MsgToL1};
+    use mp_transactions::InvokeTransactionV0;
+    use starknet_types_core::felt::Felt;
+
+    #[tokio::test]
+    async fn test_store_block_indexes_messages_to_l1() {
+        let db = temp_db().await;
+        let backend = db.backend();
+
+        let messages = vec![
+            MsgToL1 { from_address: Felt::ONE, to_address: Felt::TWO, payload: vec![Felt::THREE] },
+            MsgToL1 { from_address: Felt::ONE, to_address: Felt::TWO, payload: vec![] },
+        ];
+        let mut block = finalized_block_zero(Header::default());
+        block.inner.receipts[0] = InvokeTransactionReceipt {
+            transaction_hash: Felt::ZERO,
+            messages_sent: messages.clone(),
+            ..Default::default()
+        }
+        .into();
+        backend.store_block(block, finalized_state_diff_zero(), vec![]).unwrap();
+
+        assert_eq!(
+            backend.get_messages_to_l1_of_tx(&Felt::ZERO).unwrap(),
+            messages.iter().map(MsgToL1::compute_hash).collect::<Vec<_>>()
+        );
+        assert_eq!(backend.get_messages_to_l1_of_tx(&Felt::ONE).unwrap(), vec![]);
+        assert!(backend.is_message_to_l1_sent(&messages[1].compute_hash()).unwrap());
+        let unknown = MsgToL1 { from_address: Felt::TWO, to_address: Felt::TWO, payload: vec![] }.compute_hash();
+        assert!(!backend.is_message_to_l1_sent(&unknown).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_store_transactions_indexes_messages_to_l1() {
+        let db = temp_db().await;
+        let backend = db.backend();
+
+        let message = MsgToL1 { from_address: Felt::ONE, to_address: Felt::TWO, payload: vec![Felt::THREE] };
+        backend
+            .store_block_header(BlockHeaderWithSignatures {
+                header: Header::default(),
+                block_hash: Felt::ONE,
+                consensus_signatures: vec![],
+            })
+            .unwrap();
+        backend
+            .store_transactions(
+                0,
+                vec![TransactionWithReceipt {
+                    transaction: InvokeTransactionV0::default().into(),
+                    receipt: InvokeTransactionReceipt {
+                        transaction_hash: Felt::TWO,
+                        messages_sent: vec![message.clone()],
+                        ..Default::default()
+                    }
+                    .into(),
+                }],
+            )
+            .unwrap();
+
+        assert_eq!(backend.get_messages_to_l1_of_tx(&Felt::TWO).unwrap(), vec![message.compute_hash()]);
+        assert!(backend.is_message_to_l1_sent(&message.compute_hash()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_store_messages_to_l1_events() {
+        let db = temp_db().await;
+        let backend = db.backend();
+
+        let message = MsgToL1 { from_address: Felt::ONE, to_address: Felt::TWO, payload: vec![] }.compute_hash();
+        assert_eq!(backend.get_messages_to_l1_last_indexed_l1_block().unwrap(), None);
+        assert_eq!(backend.get_message_to_l1_settlement(&message).unwrap(), MessageToL1Settlement::default());
+
+        backend
+            .store_messages_to_l1_events(
+                &[MessageToL1Event::Logged(message.clone()), MessageToL1Event::Logged(message.clone())],
+                10,
+            )
+            .unwrap();
+        backend.store_messages_to_l1_events(&[MessageToL1Event::Consumed(message.clone())], 20).unwrap();
+
+        assert_eq!(backend.get_messages_to_l1_last_indexed_l1_block().unwrap(), Some(20));
+        assert_eq!(
+            backend.get_message_to_l1_settlement(&message).unwrap(),
+            MessageToL1Settlement { logged: 2, consumed: 1 }
+        );
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod migration_tests {
+    use crate::migrations::v4;
+    use crate::{Column, DatabaseExt, MadaraBackend, MadaraBackendConfig};
+    use mp_block::header::{BlockTimestamp, GasPrices};
+    use mp_block::{MadaraBlockInfo, MadaraBlockInner, MadaraPendingBlockInfo};
+    use mp_chain_config::{ChainConfig, L1DataAvailabilityMode, StarknetVersion};
+    use mp_receipt::{InvokeTransactionReceipt, MsgToL1};
+    use mp_transactions::InvokeTransactionV0;
+    use starknet_types_core::felt::Felt;
+    use std::sync::Arc;
+
+    fn gas_prices_v4() -> v4::GasPrices {
+        v4::GasPrices { eth_l1_gas_price: 1, strk_l1_gas_price: 2, eth_l1_data_gas_price: 3, strk_l1_data_gas_price: 4 }
+    }
+
+    fn block_info_v4(block_number: u64) -> v4::MadaraBlockInfo {
+        v4::MadaraBlockInfo {
+            header: v4::Header {
+                parent_block_hash: Felt::from(block_number),
+                block_number,
+                global_state_root: Felt::from(10),
+                sequencer_address: Felt::from(11),
+                block_timestamp: BlockTimestamp(12),
+                transaction_count: 1,
+                transaction_commitment: Felt::from(13),
+                event_count: 0,
+                event_commitment: Felt::from(14),
+                state_diff_length: Some(1),
+                state_diff_commitment: Some(Felt::from(15)),
+                receipt_commitment: None,
+                protocol_version: StarknetVersion::LATEST,
+                l1_gas_price: gas_prices_v4(),
+                l1_da_mode: L1DataAvailabilityMode::Blob,
+            },
+            block_hash: Felt::from(block_number + 100),
+            tx_hashes: vec![Felt::from(block_number + 200)],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_gas_prices_from_v4() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let chain_config = Arc::new(ChainConfig::madara_test());
+
+        {
+            let backend = MadaraBackend::open(chain_config.clone(), MadaraBackendConfig::new(&temp_dir)).await.unwrap();
+            let col = backend.db.get_column(Column::BlockNToBlockInfo);
+            for block_n in 0..3u64 {
+                let info = bincode::serialize(&block_info_v4(block_n)).unwrap();
+                backend.db.put_cf(&col, bincode::serialize(&block_n).unwrap(), info).unwrap();
+            }
+            let pending = v4::MadaraPendingBlockInfo {
+                header: v4::PendingHeader {
+                    parent_block_hash: Felt::from(102),
+                    sequencer_address: Felt::from(11),
+                    block_timestamp: BlockTimestamp(13),
+                    protocol_version: StarknetVersion::LATEST,
+                    l1_gas_price: gas_prices_v4(),
+                    l1_da_mode: L1DataAvailabilityMode::Blob,
+                },
+                tx_hashes: vec![],
+            };
+            let col = backend.db.get_column(Column::BlockStorageMeta);
+            backend.db.put_cf(&col, b"pending_info", bincode::serialize(&pending).unwrap()).unwrap();
+        }
+        std::fs::write(temp_dir.path().join(".db-version"), "4").unwrap();
+
+        let backend = MadaraBackend::open(chain_config, MadaraBackendConfig::new(&temp_dir)).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join(".db-version")).unwrap(),
+            crate::db_version::required_db_version().to_string()
+        );
+
+        let expected_gas_prices = GasPrices {
+            eth_l1_gas_price: 1,
+            strk_l1_gas_price: 2,
+            eth_l1_data_gas_price: 3,
+            strk_l1_data_gas_price: 4,
+            eth_l2_gas_price: 0,
+            strk_l2_gas_price: 0,
+        };
+        let col = backend.db.get_column(Column::BlockNToBlockInfo);
+        for block_n in 0..3u64 {
+            let bytes = backend.db.get_cf(&col, bincode::serialize(&block_n).unwrap()).unwrap().unwrap();
+            let info: MadaraBlockInfo = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(info, block_info_v4(block_n).into());
+            assert_eq!(info.header.l1_gas_price, expected_gas_prices);
+            assert_eq!(info.tx_hashes, vec![Felt::from(block_n + 200)]);
+        }
+        let pending = backend.get_pending_block_info_from_db().unwrap();
+        assert_eq!(pending.header.l1_gas_price, expected_gas_prices);
+        assert_eq!(pending.header.parent_block_hash, Felt::from(102));
+    }
+
+    #[tokio::test]
+    async fn test_migrate_messages_to_l1_from_v5() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let chain_config = Arc::new(ChainConfig::madara_test());
+        let tx_hash = Felt::from(200);
+        let message = MsgToL1 { from_address: Felt::ONE, to_address: Felt::TWO, payload: vec![Felt::THREE] };
+
+        {
+            let backend = MadaraBackend::open(chain_config.clone(), MadaraBackendConfig::new(&temp_dir)).await.unwrap();
+            let inner = MadaraBlockInner::new(
+                vec![InvokeTransactionV0::default().into()],
+                vec![InvokeTransactionReceipt {
+                    transaction_hash: tx_hash,
+                    messages_sent: vec![message.clone()],
+                    ..Default::default()
+                }
+                .into()],
+            );
+            let col = backend.db.get_column(Column::BlockNToBlockInner);
+            backend.db.put_cf(&col, bincode::serialize(&0u64).unwrap(), bincode::serialize(&inner).unwrap()).unwrap();
+        }
+        std::fs::write(temp_dir.path().join(".db-version"), "5").unwrap();
+
+        let backend = MadaraBackend::open(chain_config, MadaraBackendConfig::new(&temp_dir)).await.unwrap();
+        assert_eq!(backend.get_messages_to_l1_of_tx(&tx_hash).unwrap(), vec![message.compute_hash()]);
+        assert!(backend.is_message_to_l1_sent(&message.compute_hash()).unwrap());
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod prune_tests {
+    use crate::db_block_id::DbBlockId;
+    use crate::{Column, DatabaseExt, MadaraBackend, MadaraBackendConfig, MadaraStorageError, StorageMode};
+    use mp_block::{Header, MadaraBlockInfo, MadaraBlockInner, MadaraMaybePendingBlock};
+    use mp_chain_config::ChainConfig;
+    use mp_state_update::{ContractStorageDiffItem, NonceUpdate, StateDiff, StorageEntry};
+    use rocksdb::IteratorMode;
+    use starknet_types_core::felt::Felt;
+    use std::sync::Arc;
+
+    const CONTRACT: Felt = Felt::from_hex_unchecked("0x1234");
+    const STORAGE_KEY: Felt = Felt::from_hex_unchecked("0x10");
+
+    async fn pruned_backend(temp_dir: &tempfile::TempDir) -> Arc<MadaraBackend> {
+        let config = MadaraBackendConfig::new(temp_dir).storage_mode(StorageMode::Pruned { keep_blocks: 1 });
+        MadaraBackend::open(Arc::new(ChainConfig::madara_test()), config).await.unwrap()
+    }
+
+    fn store_block(backend: &MadaraBackend, block_n: u64) {
+        let state_diff = StateDiff {
+            storage_diffs: vec![ContractStorageDiffItem {
+                address: CONTRACT,
+                storage_entries: vec![StorageEntry { key: STORAGE_KEY, value: Felt::from(block_n) }],
+            }],
+            // The nonce is only updated in the genesis block, its entry must survive pruning.
+            nonces: (block_n == 0)
+                .then_some(NonceUpdate { contract_address: CONTRACT, nonce: Felt::ONE })
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let header = Header { block_number: block_n, ..Default::default() };
+        let block = MadaraMaybePendingBlock {
+            info: MadaraBlockInfo::new(header, vec![], Felt::from(block_n)).into(),
+            inner: MadaraBlockInner::new(vec![], vec![]),
+        };
+        backend.store_block(block, state_diff, vec![]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_prune_history() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let backend = pruned_backend(&temp_dir).await;
+
+        for block_n in 0..4 {
+            store_block(&backend, block_n);
+        }
+        assert_eq!(backend.oldest_state_block(), None);
+        backend.prune_history().unwrap();
+        assert_eq!(backend.oldest_state_block(), Some(2));
+
+        let storage_at =
+            |block_n| backend.get_contract_storage_at(&DbBlockId::Number(block_n), &CONTRACT, &STORAGE_KEY);
+        assert_eq!(storage_at(2).unwrap(), Some(Felt::TWO));
+        assert_eq!(storage_at(3).unwrap(), Some(Felt::THREE));
+        assert_eq!(
+            backend.get_contract_storage_at(&DbBlockId::Pending, &CONTRACT, &STORAGE_KEY).unwrap(),
+            Some(Felt::THREE)
+        );
+        assert!(matches!(storage_at(1), Err(MadaraStorageError::BlockPruned { block_n: 1, oldest_available: 2 })));
+        assert_eq!(backend.get_contract_nonce_at(&DbBlockId::Number(2), &CONTRACT).unwrap(), Some(Felt::ONE));
+
+        // Only the entries of blocks 2 and 3 are left.
+        let col = backend.db.get_column(Column::ContractStorage);
+        assert_eq!(backend.db.iterator_cf(&col, IteratorMode::Start).count(), 2);
+
+        // Blocks are kept.
+        assert!(backend.get_block_info(&DbBlockId::Number(0)).unwrap().is_some());
+
+        // The pruned state cannot be reverted to.
+        assert!(backend.revert_reorged_blocks(1).is_err());
+
+        // Nothing to do until a new block is imported.
+        backend.prune_history().unwrap();
+        assert_eq!(backend.oldest_state_block(), Some(2));
+        store_block(&backend, 4);
+        backend.prune_history().unwrap();
+        assert_eq!(backend.oldest_state_block(), Some(3));
+
+        drop(backend);
+        let backend = pruned_backend(&temp_dir).await;
+        assert_eq!(backend.oldest_state_block(), Some(3));
+        assert_eq!(
+            backend.get_contract_storage_at(&DbBlockId::Number(4), &CONTRACT, &STORAGE_KEY).unwrap(),
+            Some(Felt::from(4))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_mode_does_not_prune() {
+        let backend = MadaraBackend::open_for_testing(Arc::new(ChainConfig::madara_test()));
+        for block_n in 0..4 {
+            store_block(&backend, block_n);
+        }
+        backend.prune_history().unwrap();
+        assert_eq!(backend.oldest_state_block(), None);
+        assert_eq!(
+            backend.get_contract_storage_at(&DbBlockId::Number(0), &CONTRACT, &STORAGE_KEY).unwrap(),
+            Some(Felt::ZERO)
+        );
+    }
+}
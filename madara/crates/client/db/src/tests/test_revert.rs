@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod revert_tests {
+    use super::super::common::*;
+    use crate::db_block_id::{DbBlockId, RawDbBlockId};
+    use crate::l1_db::LastSyncedEventBlock;
+    use crate::{bonsai_identifier, MadaraBackend, MadaraBackendConfig, MadaraStorageError, TrieLogConfig};
+    use mp_block::{BlockHeaderWithSignatures, Header, MadaraBlockInfo, MadaraBlockInner, MadaraMaybePendingBlock};
+    use mp_chain_config::ChainConfig;
+    use mp_receipt::{InvokeTransactionReceipt, L1HandlerTransactionReceipt};
+    use mp_state_update::{ContractStorageDiffItem, NonceUpdate, StateDiff, StorageEntry};
+    use mp_transactions::validated::ValidatedMempoolTx;
+    use mp_transactions::{InvokeTransactionV1, L1HandlerTransaction};
+    use starknet_api::core::Nonce;
+    use starknet_types_core::felt::Felt;
+    use std::sync::Arc;
+
+    const CONTRACT: Felt = Felt::from_hex_unchecked("0x1234");
+    const STORAGE_KEY: Felt = Felt::from_hex_unchecked("0x10");
+
+    async fn backend_with_trie_logs(temp_dir: &tempfile::TempDir) -> Arc<MadaraBackend> {
+        let config =
+            MadaraBackendConfig::new(temp_dir).trie_log(TrieLogConfig { max_saved_trie_logs: 2, ..Default::default() });
+        MadaraBackend::open(Arc::new(ChainConfig::madara_test()), config).await.unwrap()
+    }
+
+    fn state_diff(value: Felt, nonce: Option<Felt>) -> StateDiff {
+        StateDiff {
+            storage_diffs: vec![ContractStorageDiffItem {
+                address: CONTRACT,
+                storage_entries: vec![StorageEntry { key: STORAGE_KEY, value }],
+            }],
+            nonces: nonce.map(|nonce| NonceUpdate { contract_address: CONTRACT, nonce }).into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    fn block_one() -> MadaraMaybePendingBlock {
+        let transactions = vec![
+            InvokeTransactionV1 { sender_address: CONTRACT, ..Default::default() }.into(),
+            L1HandlerTransaction::default().into(),
+        ];
+        let receipts = vec![InvokeTransactionReceipt::default().into(), L1HandlerTransactionReceipt::default().into()];
+        let header = Header { block_number: 1, ..Default::default() };
+        let info = MadaraBlockInfo::new(header, vec![Felt::from(10), Felt::from(11)], Felt::from(1));
+        MadaraMaybePendingBlock { info: info.into(), inner: MadaraBlockInner::new(transactions, receipts) }
+    }
+
+    #[tokio::test]
+    async fn test_revert_to() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let backend = backend_with_trie_logs(&temp_dir).await;
+
+        let state_diff_zero = state_diff(Felt::ONE, None);
+        backend.apply_to_global_trie(0, [&state_diff_zero]).unwrap();
+        backend.store_block(finalized_block_zero(Header::default()), state_diff_zero, vec![]).unwrap();
+        let root_zero = backend.contract_trie().root_hash(bonsai_identifier::CONTRACT).unwrap();
+
+        let state_diff_one = state_diff(Felt::TWO, Some(Felt::ONE));
+        backend.apply_to_global_trie(1, [&state_diff_one]).unwrap();
+        backend.store_block(block_one(), state_diff_one, vec![]).unwrap();
+        assert_ne!(backend.contract_trie().root_hash(bonsai_identifier::CONTRACT).unwrap(), root_zero);
+
+        // The message of the l1 handler transaction was processed at L1 block #5, after which the sync went on.
+        backend.set_l1_messaging_nonce_from_event(Nonce(Felt::ZERO), &LastSyncedEventBlock::new(5, 1)).unwrap();
+        backend.set_l1_messaging_nonce_from_event(Nonce(Felt::ONE), &LastSyncedEventBlock::new(7, 0)).unwrap();
+        backend.messaging_update_last_synced_l1_block_with_event(LastSyncedEventBlock::new(8, 0)).unwrap();
+
+        // Only the invoke transaction is restored, l1 handler transactions come from the settlement layer.
+        let restored: Vec<_> = backend.revert_to(0).unwrap().into_iter().map(|tx| tx.tx_hash).collect();
+        assert_eq!(restored, vec![Felt::from(10)]);
+
+        // The reverted message is processed again by the messaging sync, which resumes from its L1 block. The message
+        // that wasn't included in a reverted block is still marked as processed.
+        assert!(!backend.has_l1_messaging_nonce(Nonce(Felt::ZERO)).unwrap());
+        assert!(backend.has_l1_messaging_nonce(Nonce(Felt::ONE)).unwrap());
+        let last_synced = backend.messaging_last_synced_l1_block_with_event().unwrap().unwrap();
+        assert_eq!((last_synced.block_number, last_synced.event_index), (5, 1));
+
+        assert_eq!(backend.get_latest_block_n().unwrap(), Some(0));
+        assert_eq!(backend.head_status().global_trie.current(), Some(0));
+        assert!(backend.get_block_info(&DbBlockId::Number(1)).unwrap().is_none());
+        assert!(backend.find_tx_hash_block_info(&Felt::from(10)).unwrap().is_none());
+        assert!(backend.find_tx_hash_block_info(&Felt::from(0)).unwrap().is_some());
+        assert_eq!(
+            backend.get_contract_storage_at(&DbBlockId::Pending, &CONTRACT, &STORAGE_KEY).unwrap(),
+            Some(Felt::ONE)
+        );
+        assert_eq!(backend.get_contract_nonce_at(&DbBlockId::Pending, &CONTRACT).unwrap(), None);
+        assert_eq!(backend.contract_trie().root_hash(bonsai_identifier::CONTRACT).unwrap(), root_zero);
+
+        let mempool_txs: Vec<_> = backend.get_mempool_transactions().collect::<Result<_, _>>().unwrap();
+        assert_eq!(mempool_txs.len(), 1);
+        let (hash, tx_info) = &mempool_txs[0];
+        assert_eq!(*hash, Felt::from(10));
+        assert_eq!(tx_info.tx.contract_address, CONTRACT);
+        assert_eq!(tx_info.nonce_readiness, crate::mempool_db::NonceInfo::default());
+
+        // Blocks can be imported again on top of the reverted chain.
+        let state_diff_one = state_diff(Felt::THREE, Some(Felt::ONE));
+        backend.apply_to_global_trie(1, [&state_diff_one]).unwrap();
+        backend.store_block(block_one(), state_diff_one, vec![]).unwrap();
+        assert_eq!(backend.get_latest_block_n().unwrap(), Some(1));
+        assert_eq!(
+            backend.get_contract_storage_at(&DbBlockId::Pending, &CONTRACT, &STORAGE_KEY).unwrap(),
+            Some(Felt::THREE)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revert_to_invalid_target() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let backend = backend_with_trie_logs(&temp_dir).await;
+
+        let is_invalid_target = |res: anyhow::Result<Vec<ValidatedMempoolTx>>| {
+            matches!(res.unwrap_err().downcast_ref(), Some(MadaraStorageError::InvalidRevertTarget { .. }))
+        };
+
+        // Empty database.
+        assert!(is_invalid_target(backend.revert_to(0)));
+
+        for block_n in 0..4 {
+            let state_diff = state_diff(Felt::from(block_n), None);
+            backend.apply_to_global_trie(block_n, [&state_diff]).unwrap();
+            let header = Header { block_number: block_n, ..Default::default() };
+            let info = MadaraBlockInfo::new(header, vec![], Felt::from(block_n));
+            let block = MadaraMaybePendingBlock { info: info.into(), inner: MadaraBlockInner::new(vec![], vec![]) };
+            backend.store_block(block, state_diff, vec![]).unwrap();
+        }
+
+        // Not below the latest block.
+        assert!(is_invalid_target(backend.revert_to(3)));
+        // Deeper than the saved trie logs.
+        assert!(is_invalid_target(backend.revert_to(0)));
+        // Confirmed on L1.
+        backend.write_last_confirmed_block(2).unwrap();
+        assert!(is_invalid_target(backend.revert_to(1)));
+
+        assert!(backend.revert_to(2).unwrap().is_empty());
+        assert_eq!(backend.get_latest_block_n().unwrap(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_revert_reorged_blocks() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let backend = backend_with_trie_logs(&temp_dir).await;
+
+        for block_n in 0..2 {
+            let state_diff = state_diff(Felt::from(block_n), None);
+            backend.apply_to_global_trie(block_n, [&state_diff]).unwrap();
+            let header = Header { block_number: block_n, ..Default::default() };
+            let info = MadaraBlockInfo::new(header, vec![], Felt::from(block_n));
+            let block = MadaraMaybePendingBlock { info: info.into(), inner: MadaraBlockInner::new(vec![], vec![]) };
+            backend.store_block(block, state_diff, vec![]).unwrap();
+        }
+
+        // Block #2 of the stale branch, partially saved by the sync process.
+        let header = Header { block_number: 2, ..Default::default() };
+        backend
+            .store_block_header(BlockHeaderWithSignatures {
+                header,
+                block_hash: Felt::from(2),
+                consensus_signatures: vec![],
+            })
+            .unwrap();
+        backend.store_state_diff(2, state_diff(Felt::from(42), None)).unwrap();
+
+        // The latest block is kept, only the partially saved block is removed.
+        backend.revert_reorged_blocks(1).unwrap();
+        assert_eq!(backend.get_latest_block_n().unwrap(), Some(1));
+        assert!(backend.get_block_info(&RawDbBlockId::Number(2)).unwrap().is_none());
+        assert!(backend.get_block_state_diff(&RawDbBlockId::Number(2)).unwrap().is_none());
+        assert_eq!(
+            backend.get_contract_storage_at(&DbBlockId::Pending, &CONTRACT, &STORAGE_KEY).unwrap(),
+            Some(Felt::ONE)
+        );
+
+        backend.revert_reorged_blocks(0).unwrap();
+        assert_eq!(backend.get_latest_block_n().unwrap(), Some(0));
+        assert_eq!(backend.head_status().global_trie.current(), Some(0));
+        assert!(backend.get_block_info(&DbBlockId::Number(1)).unwrap().is_none());
+        // Reorged transactions are not restored to the mempool.
+        assert_eq!(backend.get_mempool_transactions().count(), 0);
+
+        assert!(matches!(
+            backend.revert_reorged_blocks(1).unwrap_err().downcast_ref(),
+            Some(MadaraStorageError::InvalidRevertTarget { .. })
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+use {
+    crate::{DatabaseService, MadaraBackendConfig, TraceStorageConfig},
+    mp_chain_config::ChainConfig,
+    mp_rpc::{
+        DataAvailability, DeclareTransactionTrace, ExecutionResources, TraceBlockTransactionsResult, TransactionTrace,
+    },
+    starknet_types_core::felt::Felt,
+};
+
+#[cfg(test)]
+fn declare_trace(transaction_hash: Felt, steps: u64) -> TraceBlockTransactionsResult {
+    TraceBlockTransactionsResult {
+        trace_root: TransactionTrace::Declare(DeclareTransactionTrace {
+            execution_resources: ExecutionResources {
+                bitwise_builtin_applications: None,
+                ec_op_builtin_applications: None,
+                ecdsa_builtin_applications: None,
+                keccak_builtin_applications: None,
+                memory_holes: None,
+                pedersen_builtin_applications: None,
+                poseidon_builtin_applications: None,
+                range_check_builtin_applications: Some(3),
+                segment_arena_builtin: None,
+                steps,
+                data_availability: DataAvailability { l1_data_gas: 128, l1_gas: 0 },
+            },
+            fee_transfer_invocation: None,
+            state_diff: None,
+            validate_invocation: None,
+        }),
+        transaction_hash,
+    }
+}
+
+#[tokio::test]
+async fn test_store_and_get_block_traces() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config =
+        MadaraBackendConfig::new(&temp_dir).trace_storage(TraceStorageConfig { enabled: true, retention_blocks: None });
+    let db = DatabaseService::new(std::sync::Arc::new(ChainConfig::madara_test()), config).await.unwrap();
+    let backend = db.backend();
+
+    assert!(backend.trace_storage_enabled());
+    assert_eq!(backend.get_block_traces(0).unwrap(), None);
+
+    let traces = vec![declare_trace(Felt::ONE, 100), declare_trace(Felt::TWO, 200)];
+    backend.store_block_traces(0, &traces).unwrap();
+    assert_eq!(backend.get_block_traces(0).unwrap(), Some(traces));
+
+    backend.store_block_traces(1, &[]).unwrap();
+    assert_eq!(backend.get_block_traces(1).unwrap(), Some(vec![]));
+}
+
+#[tokio::test]
+async fn test_block_traces_retention() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = MadaraBackendConfig::new(&temp_dir)
+        .trace_storage(TraceStorageConfig { enabled: true, retention_blocks: Some(2) });
+    let db = DatabaseService::new(std::sync::Arc::new(ChainConfig::madara_test()), config).await.unwrap();
+    let backend = db.backend();
+
+    for block_n in 0..5 {
+        backend.store_block_traces(block_n, &[declare_trace(Felt::from(block_n), block_n)]).unwrap();
+    }
+
+    assert_eq!(backend.get_block_traces(0).unwrap(), None);
+    assert_eq!(backend.get_block_traces(2).unwrap(), None);
+    assert_eq!(backend.get_block_traces(3).unwrap(), Some(vec![declare_trace(Felt::from(3), 3)]));
+    assert_eq!(backend.get_block_traces(4).unwrap(), Some(vec![declare_trace(Felt::from(4), 4)]));
+}
@@ -0,0 +1,67 @@
+use crate::{Column, DatabaseExt, MadaraBackend, MadaraStorageError, WriteBatchWithTransaction};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use mp_rpc::TraceBlockTransactionsResult;
+use std::io::{Read, Write};
+
+type Result<T, E = MadaraStorageError> = std::result::Result<T, E>;
+
+/// Configuration for the storage of transaction traces at import time.
+///
+/// When enabled, block production and sync save the trace of every transaction of a block once it is
+/// closed, which lets the trace rpc methods serve them without re-executing the whole block.
+#[derive(Debug, Clone, Default)]
+pub struct TraceStorageConfig {
+    /// Store the transaction traces of every new block.
+    pub enabled: bool,
+    /// Only keep the traces of the last `retention_blocks` blocks. [`None`] means traces are kept forever.
+    pub retention_blocks: Option<u64>,
+}
+
+// Traces are stored as gzip-compressed json: the rpc trace types use internally tagged and untagged enums,
+// which bincode cannot deserialize.
+fn encode_traces(traces: &[TraceBlockTransactionsResult]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&serde_json::to_vec(traces)?)?;
+    Ok(encoder.finish()?)
+}
+
+fn decode_traces(bytes: &[u8]) -> Result<Vec<TraceBlockTransactionsResult>> {
+    let mut json = Vec::new();
+    GzDecoder::new(bytes).read_to_end(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+impl MadaraBackend {
+    /// Whether transaction traces should be saved when importing new blocks.
+    pub fn trace_storage_enabled(&self) -> bool {
+        self.config.trace_storage.enabled
+    }
+
+    /// Save the traces of all the transactions of a closed block, in order. Traces falling out of the
+    /// retention window are deleted in the same batch.
+    #[tracing::instrument(skip(self, traces), fields(module = "TraceDB"))]
+    pub fn store_block_traces(&self, block_n: u64, traces: &[TraceBlockTransactionsResult]) -> Result<()> {
+        let col = self.db.get_column(Column::BlockNToTxTraces);
+        let mut batch = WriteBatchWithTransaction::default();
+        batch.put_cf(&col, block_n.to_be_bytes(), encode_traces(traces)?);
+
+        if let Some(retention) = self.config.trace_storage.retention_blocks {
+            // Keep the range `block_n + 1 - retention..=block_n`.
+            if let Some(first_kept) = (block_n + 1).checked_sub(retention.max(1)) {
+                batch.delete_range_cf(&col, 0u64.to_be_bytes(), first_kept.to_be_bytes());
+            }
+        }
+
+        self.db.write_opt(batch, &self.writeopts_no_wal)?;
+        Ok(())
+    }
+
+    /// Returns the stored traces of all the transactions of a block, if they were saved at import time and
+    /// are still within the retention window.
+    #[tracing::instrument(skip(self), fields(module = "TraceDB"))]
+    pub fn get_block_traces(&self, block_n: u64) -> Result<Option<Vec<TraceBlockTransactionsResult>>> {
+        let col = self.db.get_column(Column::BlockNToTxTraces);
+        let Some(res) = self.db.get_pinned_cf(&col, block_n.to_be_bytes())? else { return Ok(None) };
+        Ok(Some(decode_traces(&res)?))
+    }
+}
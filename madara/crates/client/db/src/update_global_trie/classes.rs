@@ -1,9 +1,11 @@
+use crate::bonsai_db::BonsaiTransaction;
 use crate::MadaraBackend;
 use crate::{bonsai_identifier, MadaraStorageError};
 use bitvec::order::Msb0;
 use bitvec::vec::BitVec;
 use bitvec::view::AsBits;
 use bonsai_trie::id::BasicId;
+use bonsai_trie::BonsaiStorage;
 use mp_state_update::DeclaredClassItem;
 use rayon::prelude::*;
 use starknet_types_core::felt::Felt;
@@ -12,6 +14,10 @@ use starknet_types_core::hash::{Poseidon, StarkHash};
 // "CONTRACT_CLASS_LEAF_V0"
 const CONTRACT_CLASS_HASH_VERSION: Felt = Felt::from_hex_unchecked("0x434f4e54524143545f434c4153535f4c4541465f5630");
 
+fn class_leaf_hash(compiled_class_hash: &Felt) -> Felt {
+    Poseidon::hash(&CONTRACT_CLASS_HASH_VERSION, compiled_class_hash)
+}
+
 pub fn class_trie_root(
     backend: &MadaraBackend,
     declared_classes: &[DeclaredClassItem],
@@ -22,8 +28,7 @@ pub fn class_trie_root(
     let updates: Vec<_> = declared_classes
         .into_par_iter()
         .map(|DeclaredClassItem { class_hash, compiled_class_hash }| {
-            let hash = Poseidon::hash(&CONTRACT_CLASS_HASH_VERSION, compiled_class_hash);
-            (*class_hash, hash)
+            (*class_hash, class_leaf_hash(compiled_class_hash))
         })
         .collect();
 
@@ -44,6 +49,22 @@ pub fn class_trie_root(
     Ok(root_hash)
 }
 
+/// Same as [`class_trie_root`], on a transactional state of the class trie: nothing is written to the database.
+pub(crate) fn class_trie_root_on_transactional_state(
+    class_trie: &mut BonsaiStorage<BasicId, BonsaiTransaction, Poseidon>,
+    declared_classes: &[DeclaredClassItem],
+    block_number: u64,
+) -> Result<Felt, MadaraStorageError> {
+    for DeclaredClassItem { class_hash, compiled_class_hash } in declared_classes {
+        let bytes = class_hash.to_bytes_be();
+        let bv: BitVec<u8, Msb0> = bytes.as_bits()[5..].to_owned();
+        class_trie.insert(bonsai_identifier::CLASS, &bv, &class_leaf_hash(compiled_class_hash))?;
+    }
+    class_trie.transactional_commit(BasicId::new(block_number))?;
+
+    Ok(class_trie.root_hash(bonsai_identifier::CLASS)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
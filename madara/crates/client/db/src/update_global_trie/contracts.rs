@@ -1,3 +1,4 @@
+use crate::bonsai_db::BonsaiTransaction;
 use crate::db_block_id::RawDbBlockId;
 use crate::MadaraBackend;
 use crate::{bonsai_identifier, MadaraStorageError};
@@ -5,7 +6,10 @@ use bitvec::order::Msb0;
 use bitvec::vec::BitVec;
 use bitvec::view::AsBits;
 use bonsai_trie::id::BasicId;
-use mp_state_update::{ContractStorageDiffItem, DeployedContractItem, NonceUpdate, ReplacedClassItem, StorageEntry};
+use bonsai_trie::BonsaiStorage;
+use mp_state_update::{
+    ContractStorageDiffItem, DeployedContractItem, NonceUpdate, ReplacedClassItem, StateDiff, StorageEntry,
+};
 use rayon::prelude::*;
 use starknet_types_core::felt::Felt;
 use starknet_types_core::hash::{Pedersen, StarkHash};
@@ -58,17 +62,7 @@ pub fn contract_trie_root(
     // Then we commit them
     contract_storage_trie.commit(BasicId::new(block_number))?;
 
-    for NonceUpdate { contract_address, nonce } in nonces {
-        contract_leafs.entry(*contract_address).or_default().nonce = Some(*nonce);
-    }
-
-    for DeployedContractItem { address, class_hash } in deployed_contracts {
-        contract_leafs.entry(*address).or_default().class_hash = Some(*class_hash);
-    }
-
-    for ReplacedClassItem { contract_address, class_hash } in replaced_classes {
-        contract_leafs.entry(*contract_address).or_default().class_hash = Some(*class_hash);
-    }
+    add_contract_leafs(&mut contract_leafs, deployed_contracts, replaced_classes, nonces);
 
     let mut contract_trie = backend.contract_trie();
 
@@ -98,6 +92,67 @@ pub fn contract_trie_root(
     Ok(root_hash)
 }
 
+fn add_contract_leafs(
+    contract_leafs: &mut HashMap<Felt, ContractLeaf>,
+    deployed_contracts: &[DeployedContractItem],
+    replaced_classes: &[ReplacedClassItem],
+    nonces: &[NonceUpdate],
+) {
+    for NonceUpdate { contract_address, nonce } in nonces {
+        contract_leafs.entry(*contract_address).or_default().nonce = Some(*nonce);
+    }
+
+    for DeployedContractItem { address, class_hash } in deployed_contracts {
+        contract_leafs.entry(*address).or_default().class_hash = Some(*class_hash);
+    }
+
+    for ReplacedClassItem { contract_address, class_hash } in replaced_classes {
+        contract_leafs.entry(*contract_address).or_default().class_hash = Some(*class_hash);
+    }
+}
+
+/// Same as [`contract_trie_root`], on transactional states of the contract tries at the end of block
+/// `parent_block_n`: nothing is written to the database. The nonces and class hashes of the contracts which are not in
+/// the state diff are read from the state of the parent block.
+pub(crate) fn contract_trie_root_on_transactional_state(
+    backend: &MadaraBackend,
+    contract_trie: &mut BonsaiStorage<BasicId, BonsaiTransaction, Pedersen>,
+    contract_storage_trie: &mut BonsaiStorage<BasicId, BonsaiTransaction, Pedersen>,
+    state_diff: &StateDiff,
+    parent_block_n: u64,
+) -> Result<Felt, MadaraStorageError> {
+    let id = BasicId::new(parent_block_n + 1);
+    let mut contract_leafs: HashMap<Felt, ContractLeaf> = HashMap::new();
+
+    for ContractStorageDiffItem { address, storage_entries } in &state_diff.storage_diffs {
+        for StorageEntry { key, value } in storage_entries {
+            let bytes = key.to_bytes_be();
+            let bv: BitVec<u8, Msb0> = bytes.as_bits()[5..].to_owned();
+            contract_storage_trie.insert(&address.to_bytes_be(), &bv, value)?;
+        }
+        contract_leafs.insert(*address, Default::default());
+    }
+    contract_storage_trie.transactional_commit(id)?;
+
+    add_contract_leafs(
+        &mut contract_leafs,
+        &state_diff.deployed_contracts,
+        &state_diff.replaced_classes,
+        &state_diff.nonces,
+    );
+
+    for (contract_address, mut leaf) in contract_leafs {
+        leaf.storage_root = Some(contract_storage_trie.root_hash(&contract_address.to_bytes_be())?);
+        let leaf_hash = contract_state_leaf_hash(backend, &contract_address, &leaf, parent_block_n)?;
+        let bytes = contract_address.to_bytes_be();
+        let bv: BitVec<u8, Msb0> = bytes.as_bits()[5..].to_owned();
+        contract_trie.insert(bonsai_identifier::CONTRACT, &bv, &leaf_hash)?;
+    }
+    contract_trie.transactional_commit(id)?;
+
+    Ok(contract_trie.root_hash(bonsai_identifier::CONTRACT)?)
+}
+
 /// Computes the contract state leaf hash
 ///
 /// # Arguments
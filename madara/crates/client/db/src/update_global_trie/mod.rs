@@ -1,4 +1,5 @@
-use crate::{MadaraBackend, MadaraStorageError};
+use crate::{bonsai_identifier, MadaraBackend, MadaraStorageError};
+use bonsai_trie::id::BasicId;
 use mp_state_update::StateDiff;
 use starknet_types_core::{
     felt::Felt,
@@ -43,6 +44,47 @@ impl MadaraBackend {
         }
         state_root.ok_or(MadaraStorageError::EmptyBatch)
     }
+
+    /// Computes the global state root at the end of block `block_n` by applying `state_diff` on top of the global
+    /// tries at the end of its parent block. Unlike [`Self::apply_to_global_trie`], nothing is written to the tries:
+    /// the state diff is applied to transactional states of the tries. This is used to check the state root of a
+    /// re-executed block independently of the stored state diff.
+    /// Returns `None` for the genesis block, and when the tries have not reached the parent block or do not keep
+    /// enough trie logs to go back to it.
+    pub fn compute_global_state_root(
+        &self,
+        block_n: u64,
+        state_diff: &StateDiff,
+    ) -> Result<Option<Felt>, MadaraStorageError> {
+        let Some(parent_block_n) = block_n.checked_sub(1) else { return Ok(None) };
+        if !self.head_status().global_trie.current().is_some_and(|trie_head| parent_block_n <= trie_head) {
+            return Ok(None);
+        }
+
+        let id = BasicId::new(parent_block_n);
+        let contract_trie = self.contract_trie();
+        let contract_storage_trie = self.contract_storage_trie();
+        let class_trie = self.class_trie();
+        let (Some(mut contract_trie), Some(mut contract_storage_trie), Some(mut class_trie)) = (
+            contract_trie.get_transactional_state(id, contract_trie.get_config())?,
+            contract_storage_trie.get_transactional_state(id, contract_storage_trie.get_config())?,
+            class_trie.get_transactional_state(id, class_trie.get_config())?,
+        ) else {
+            return Ok(None);
+        };
+
+        let contract_trie_root = contracts::contract_trie_root_on_transactional_state(
+            self,
+            &mut contract_trie,
+            &mut contract_storage_trie,
+            state_diff,
+            parent_block_n,
+        )?;
+        let class_trie_root =
+            classes::class_trie_root_on_transactional_state(&mut class_trie, &state_diff.declared_classes, block_n)?;
+
+        Ok(Some(calculate_state_root(contract_trie_root, class_trie_root)))
+    }
 }
 
 /// "STARKNET_STATE_V0"
@@ -60,8 +102,10 @@ fn calculate_state_root(contracts_trie_root: Felt, classes_trie_root: Felt) -> F
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db_block_id::DbBlockId;
     use crate::MadaraBackend;
     use mp_chain_config::ChainConfig;
+    use mp_state_update::{ContractStorageDiffItem, DeclaredClassItem, NonceUpdate, StorageEntry};
     use rstest::*;
     use starknet_api::felt;
     use std::sync::Arc;
@@ -102,4 +146,29 @@ mod tests {
         // THEN: The calculated state root should match the expected result
         assert_eq!(result, expected_result, "State root should match the expected result");
     }
+
+    #[rstest]
+    fn test_compute_global_state_root(setup_test_backend: Arc<MadaraBackend>) {
+        let backend = setup_test_backend;
+        let state_diff = |value: Felt| StateDiff {
+            storage_diffs: vec![ContractStorageDiffItem {
+                address: felt!("0x1"),
+                storage_entries: vec![StorageEntry { key: felt!("0x1"), value }],
+            }],
+            nonces: vec![NonceUpdate { contract_address: felt!("0x1"), nonce: value }],
+            declared_classes: vec![DeclaredClassItem { class_hash: value, compiled_class_hash: felt!("0x10") }],
+            ..Default::default()
+        };
+        assert_eq!(backend.compute_global_state_root(0, &state_diff(felt!("0x2"))).unwrap(), None);
+
+        backend.apply_to_global_trie(0, [&state_diff(felt!("0x2"))]).unwrap();
+        backend.snapshots.set_new_head(DbBlockId::Number(0));
+        assert_eq!(backend.compute_global_state_root(2, &state_diff(felt!("0x3"))).unwrap(), None);
+
+        let computed = backend.compute_global_state_root(1, &state_diff(felt!("0x3"))).unwrap();
+        // Computing the state root does not modify the tries.
+        assert_eq!(backend.compute_global_state_root(1, &state_diff(felt!("0x3"))).unwrap(), computed);
+        let state_root = backend.apply_to_global_trie(1, [&state_diff(felt!("0x3"))]).unwrap();
+        assert_eq!(computed, Some(state_root));
+    }
 }
@@ -9,14 +9,17 @@ use super::StorageDiffs;
 
 #[derive(Clone, Debug, Default)]
 pub struct ContractFeeTokensBalance {
+    /// Balance in the smallest unit of the native fee token, which is the fri for STRK.
     pub fri: Felt,
     pub wei: Felt,
 }
 
 impl ContractFeeTokensBalance {
     pub fn as_u128_fri_wei(&self) -> anyhow::Result<(u128, u128)> {
-        let fri =
-            self.fri.try_into().with_context(|| format!("Converting STRK balance felt {:#x} to u128", self.fri))?;
+        let fri = self
+            .fri
+            .try_into()
+            .with_context(|| format!("Converting native fee token balance felt {:#x} to u128", self.fri))?;
         let wei =
             self.wei.try_into().with_context(|| format!("Converting ETH balance felt {:#x} to u128", self.wei))?;
         Ok((fri, wei))
@@ -52,13 +55,14 @@ impl InitialBalances {
             let low_key = starknet_api::abi::abi_utils::get_fee_token_var_address(*contract_address);
             // let high_key = low_key.next_storage_key()?;
 
-            // ETH erc20
-            let erc20_contract = chain_config.parent_fee_token_address;
-            let kv = storage_diffs.contract_mut(erc20_contract);
-            kv.insert(low_key, bal.wei);
-            // kv.insert(high_key, Felt::ZERO);
+            // ETH erc20, absent on chains with a single fee token
+            if let Some(erc20_contract) = chain_config.parent_fee_token_address {
+                let kv = storage_diffs.contract_mut(erc20_contract);
+                kv.insert(low_key, bal.wei);
+                // kv.insert(high_key, Felt::ZERO);
+            }
 
-            // STRK erc20
+            // Native fee token erc20, STRK for starknet
             let erc20_contract = chain_config.native_fee_token_address;
             let kv = storage_diffs.contract_mut(erc20_contract);
             kv.insert(low_key, bal.fri);
@@ -32,8 +32,6 @@ pub use predeployed_contracts::*;
 
 // 1 ETH = 1e18 WEI
 const ETH_WEI_DECIMALS: u128 = 1_000_000_000_000_000_000;
-// 1 STRK = 1e18 FRI
-const STRK_FRI_DECIMALS: u128 = 1_000_000_000_000_000_000;
 
 #[derive(Debug, Clone, Default)]
 pub struct StorageDiffs(HashMap<ContractAddress, HashMap<StorageKey, Felt>>);
@@ -53,7 +51,7 @@ impl StorageDiffs {
     }
 }
 
-// We allow ourselves to lie about the contract_address. This is because we want the UDC and the two ERC20 contracts to have well known addresses on every chain.
+// We allow ourselves to lie about the contract_address. This is because we want the UDC and the fee token ERC20 contracts to have well known addresses on every chain.
 
 /// Universal Deployer Contract.
 const UDC_CLASS_DEFINITION: &[u8] =
@@ -63,10 +61,6 @@ const UDC_CONTRACT_ADDRESS: Felt =
 
 const ERC20_CLASS_DEFINITION: &[u8] =
     include_bytes!("../../../../../build-artifacts/cairo_artifacts/openzeppelin_ERC20Upgradeable.contract_class.json");
-const ERC20_STRK_CONTRACT_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
-const ERC20_ETH_CONTRACT_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7");
 
 const ACCOUNT_CLASS_DEFINITION: &[u8] = include_bytes!(
     "../../../../../build-artifacts/cairo_artifacts/openzeppelin_AccountUpgradeable.contract_class.json"
@@ -80,22 +74,40 @@ pub struct ChainGenesisDescription {
     pub deployed_contracts: InitiallyDeployedContracts,
     /// This is filled in with the initial_balances too when building.
     pub initial_storage: StorageDiffs,
+    /// Balance given to the devnet accounts: 10 000 of each fee token of the chain.
+    pub devnet_account_balance: ContractFeeTokensBalance,
 }
 
 impl ChainGenesisDescription {
-    #[tracing::instrument(fields(module = "ChainGenesisDescription"))]
-    pub fn base_config() -> anyhow::Result<Self> {
+    /// The fee token ERC20 contracts are deployed at the addresses of the chain config.
+    #[tracing::instrument(skip(chain_config), fields(module = "ChainGenesisDescription"))]
+    pub fn base_config(chain_config: &ChainConfig) -> anyhow::Result<Self> {
         let udc_class = InitiallyDeclaredClass::new_legacy(UDC_CLASS_DEFINITION).context("Failed to add UDC class")?;
         let erc20_class =
             InitiallyDeclaredClass::new_sierra(ERC20_CLASS_DEFINITION).context("Failed to add ERC20 class")?;
+
+        let mut deployed_contracts = InitiallyDeployedContracts::default()
+            .with(UDC_CONTRACT_ADDRESS, udc_class.class_hash())
+            .with(chain_config.native_fee_token_address.to_felt(), erc20_class.class_hash());
+        if let Some(parent_fee_token_address) = chain_config.parent_fee_token_address {
+            deployed_contracts.insert(parent_fee_token_address.to_felt(), erc20_class.class_hash());
+        }
+
+        let devnet_account_balance = ContractFeeTokensBalance {
+            fri: chain_config.native_fee_token.unit().saturating_mul(10_000).into(),
+            wei: if chain_config.supports_legacy_fee_payment() {
+                (10_000 * ETH_WEI_DECIMALS).into()
+            } else {
+                Felt::ZERO
+            },
+        };
+
         Ok(Self {
             initial_balances: InitialBalances::default(),
-            deployed_contracts: InitiallyDeployedContracts::default()
-                .with(UDC_CONTRACT_ADDRESS, udc_class.class_hash())
-                .with(ERC20_ETH_CONTRACT_ADDRESS, erc20_class.class_hash())
-                .with(ERC20_STRK_CONTRACT_ADDRESS, erc20_class.class_hash()),
+            deployed_contracts,
             declared_classes: InitiallyDeclaredClasses::default().with(udc_class).with(erc20_class),
             initial_storage: StorageDiffs::default(),
+            devnet_account_balance,
         })
     }
 
@@ -128,10 +140,7 @@ impl ChainGenesisDescription {
                     let calculated_address =
                         calculate_contract_address(Felt::ZERO, account_class_hash, &[pubkey.scalar()], Felt::ZERO);
 
-                    let balance = ContractFeeTokensBalance {
-                        fri: (10_000 * ETH_WEI_DECIMALS).into(),
-                        wei: (10_000 * STRK_FRI_DECIMALS).into(),
-                    };
+                    let balance = self.devnet_account_balance.clone();
 
                     self.deployed_contracts.insert(calculated_address, account_class_hash);
                     self.initial_balances
@@ -176,6 +185,8 @@ impl ChainGenesisDescription {
                         strk_l1_gas_price: 5,
                         eth_l1_data_gas_price: 5,
                         strk_l1_data_gas_price: 5,
+                        eth_l2_gas_price: 5,
+                        strk_l2_gas_price: 5,
                     },
                     l1_da_mode: chain_config.l1_da_mode,
                 },
@@ -211,6 +222,11 @@ impl ChainGenesisDescription {
 mod tests {
     use super::*;
 
+    // 1 STRK = 1e18 FRI
+    const STRK_FRI_DECIMALS: u128 = 1_000_000_000_000_000_000;
+    const ERC20_STRK_CONTRACT_ADDRESS: Felt =
+        Felt::from_hex_unchecked("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
+
     use std::sync::Arc;
     use std::time::Duration;
 
@@ -227,12 +243,13 @@ mod tests {
         TransactionValidator, TransactionValidatorConfig,
     };
     use mp_block::{BlockId, BlockTag};
+    use mp_chain_config::{CallListPolicy, CallTarget, FeeTokenConfig, TransactionPolicy};
     use mp_class::{ClassInfo, FlattenedSierraClass};
     use mp_receipt::{Event, ExecutionResult, FeePayment, InvokeTransactionReceipt, PriceUnit, TransactionReceipt};
     use mp_rpc::{
         AddInvokeTransactionResult, BroadcastedDeclareTxn, BroadcastedDeclareTxnV3, BroadcastedDeployAccountTxn,
         BroadcastedInvokeTxn, BroadcastedTxn, ClassAndTxnHash, ContractAndTxnHash, DaMode, DeployAccountTxnV3,
-        InvokeTxnV3, ResourceBounds, ResourceBoundsMapping,
+        InvokeTxnV1, InvokeTxnV3, ResourceBounds, ResourceBoundsMapping,
     };
     use mp_transactions::compute_hash::calculate_contract_address;
     use mp_transactions::BroadcastedTransactionExt;
@@ -348,7 +365,7 @@ mod tests {
             .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
             .with_test_writer()
             .try_init();
-        let mut g = ChainGenesisDescription::base_config().unwrap();
+        let mut g = ChainGenesisDescription::base_config(&chain_config).unwrap();
         let contracts = g.add_devnet_contracts(10).unwrap();
 
         let backend = MadaraBackend::open_for_testing(Arc::new(chain_config));
@@ -361,6 +378,7 @@ mod tests {
             strk_l1_gas_price: 128,
             eth_l1_data_gas_price: 128,
             strk_l1_data_gas_price: 128,
+            ..Default::default()
         });
         let l1_data_provider = Arc::new(l1_data_provider) as Arc<dyn L1DataProvider>;
         let mempool = Arc::new(Mempool::new(Arc::clone(&backend), MempoolConfig::new(mempool_limits)));
@@ -387,7 +405,7 @@ mod tests {
     #[tokio::test]
     async fn test_declare(#[case] contract: &[u8]) {
         let mut chain = chain_with_mempool_limits(MempoolLimits::for_testing()).await;
-        tracing::info!("{}", chain.contracts);
+        tracing::info!("{}", chain.contracts.display(chain.backend.chain_config()));
 
         let sender_address = &chain.contracts.0[0];
 
@@ -626,7 +644,7 @@ mod tests {
     #[tokio::test]
     async fn test_basic_transfer(#[case] transfer_amount: u128, #[case] expect_reverted: bool) {
         let mut chain = chain_with_mempool_limits(MempoolLimits::for_testing()).await;
-        tracing::info!("{}", chain.contracts);
+        tracing::info!("{}", chain.contracts.display(chain.backend.chain_config()));
 
         let sequencer_address = chain.backend.chain_config().sequencer_address.to_felt();
         let contract_0 = &chain.contracts.0[0];
@@ -775,7 +793,7 @@ mod tests {
             max_transactions: 5,
         })
         .await;
-        tracing::info!("{}", chain.contracts);
+        tracing::info!("{}", chain.contracts.display(chain.backend.chain_config()));
 
         let contract_0 = &chain.contracts.0[0];
         let contract_1 = &chain.contracts.0[1];
@@ -861,7 +879,7 @@ mod tests {
             max_transactions: 5,
         })
         .await;
-        tracing::info!("{}", chain.contracts);
+        tracing::info!("{}", chain.contracts.display(chain.backend.chain_config()));
 
         let contract_0 = &chain.contracts.0[0];
         let contract_1 = &chain.contracts.0[1];
@@ -910,4 +928,137 @@ mod tests {
         assert_eq!(block.inner.receipts, vec![]);
         assert!(chain.mempool.is_empty().await);
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_single_fee_token() {
+        let chain_config = ChainConfig {
+            native_fee_token: FeeTokenConfig { symbol: "USDC".into(), decimals: 6, ..Default::default() },
+            parent_fee_token_address: None,
+            ..ChainConfig::madara_devnet()
+        };
+        let chain = chain_with_mempool_limits_and_chain_config(MempoolLimits::for_testing(), chain_config).await;
+        let fee_token_address = chain.backend.chain_config().native_fee_token_address.to_felt();
+
+        let contract_0 = &chain.contracts.0[0];
+        let contract_1 = &chain.contracts.0[1];
+
+        // 10 000 tokens with 6 decimals, and no ETH.
+        assert_eq!(chain.get_bal_strk_eth(contract_0.address), (10_000 * 1_000_000, 0));
+        let display = chain.contracts.display(chain.backend.chain_config()).to_string();
+        assert!(display.contains("Balance: 10000 USDC\n"), "{display}");
+
+        let calldata: Vec<Felt> = Multicall::default()
+            .with(Call {
+                to: fee_token_address,
+                selector: Selector::from("transfer"),
+                calldata: vec![contract_1.address, 15.into(), Felt::ZERO],
+            })
+            .flatten()
+            .collect();
+
+        // Legacy transactions pay their fees in wei.
+        let res = chain
+            .sign_and_add_invoke_tx(
+                BroadcastedInvokeTxn::V1(InvokeTxnV1 {
+                    sender_address: contract_0.address,
+                    calldata: calldata.clone().into(),
+                    max_fee: Felt::from(u64::MAX),
+                    signature: vec![].into(), // Signature is filled in by `sign_and_add_invoke_tx`.
+                    nonce: Felt::ZERO,
+                }),
+                contract_0,
+            )
+            .await;
+        assert_matches!(
+            res,
+            Err(SubmitTransactionError::Rejected(RejectedTransactionError {
+                kind: RejectedTransactionErrorKind::InvalidTransactionVersion,
+                ..
+            }))
+        );
+        assert!(format!("{:#}", res.unwrap_err()).contains("Fees can only be paid in USDC"));
+
+        chain
+            .sign_and_add_invoke_tx(
+                BroadcastedInvokeTxn::V3(InvokeTxnV3 {
+                    sender_address: contract_0.address,
+                    calldata: calldata.into(),
+                    signature: vec![].into(), // Signature is filled in by `sign_and_add_invoke_tx`.
+                    nonce: Felt::ZERO,
+                    resource_bounds: ResourceBoundsMapping {
+                        l1_gas: ResourceBounds { max_amount: 60000, max_price_per_unit: 10000 },
+                        l2_gas: ResourceBounds { max_amount: 60000, max_price_per_unit: 10000 },
+                    },
+                    tip: 0,
+                    paymaster_data: vec![],
+                    account_deployment_data: vec![],
+                    nonce_data_availability_mode: DaMode::L1,
+                    fee_data_availability_mode: DaMode::L1,
+                }),
+                contract_0,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_transaction_policy() {
+        let transfer_target = CallTarget {
+            contract_address: ERC20_STRK_CONTRACT_ADDRESS,
+            selector: Some(Selector::from("transfer").into()),
+        };
+        let chain_config = ChainConfig {
+            transaction_policy: TransactionPolicy {
+                calls: CallListPolicy { allow: None, deny: vec![transfer_target] },
+                ..Default::default()
+            },
+            ..ChainConfig::madara_devnet()
+        };
+        let chain = chain_with_mempool_limits_and_chain_config(MempoolLimits::for_testing(), chain_config).await;
+
+        let contract_0 = &chain.contracts.0[0];
+        let contract_1 = &chain.contracts.0[1];
+
+        let transfer_tx = BroadcastedInvokeTxn::V3(InvokeTxnV3 {
+            sender_address: contract_0.address,
+            calldata: Multicall::default()
+                .with(Call {
+                    to: ERC20_STRK_CONTRACT_ADDRESS,
+                    selector: Selector::from("transfer"),
+                    calldata: vec![contract_1.address, 15.into(), Felt::ZERO],
+                })
+                .flatten()
+                .collect::<Vec<_>>()
+                .into(),
+            signature: vec![].into(), // Signature is filled in by `sign_and_add_invoke_tx`.
+            nonce: Felt::ZERO,
+            resource_bounds: ResourceBoundsMapping {
+                l1_gas: ResourceBounds { max_amount: 60000, max_price_per_unit: 10000 },
+                l2_gas: ResourceBounds { max_amount: 60000, max_price_per_unit: 10000 },
+            },
+            tip: 0,
+            paymaster_data: vec![],
+            account_deployment_data: vec![],
+            nonce_data_availability_mode: DaMode::L1,
+            fee_data_availability_mode: DaMode::L1,
+        });
+
+        let res = chain.sign_and_add_invoke_tx(transfer_tx.clone(), contract_0).await;
+        assert_matches!(
+            res,
+            Err(SubmitTransactionError::Rejected(RejectedTransactionError {
+                kind: RejectedTransactionErrorKind::TransactionNotPermitted,
+                ..
+            }))
+        );
+        assert!(format!("{:#}", res.unwrap_err()).contains("is not allowed"));
+        assert!(chain.mempool.is_empty().await);
+
+        // The policy replaced at runtime applies to the next transactions.
+        chain.backend.set_transaction_policy(TransactionPolicy::default());
+        chain.sign_and_add_invoke_tx(transfer_tx, contract_0).await.unwrap();
+        assert!(!chain.mempool.is_empty().await);
+    }
 }
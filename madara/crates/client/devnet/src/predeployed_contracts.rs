@@ -6,10 +6,10 @@ use starknet_api::abi::abi_utils::get_fee_token_var_address;
 use starknet_signers::SigningKey;
 use starknet_types_core::felt::Felt;
 
-use crate::{
-    ContractFeeTokensBalance, ERC20_ETH_CONTRACT_ADDRESS, ERC20_STRK_CONTRACT_ADDRESS, ETH_WEI_DECIMALS,
-    STRK_FRI_DECIMALS,
-};
+use mp_chain_config::ChainConfig;
+use mp_convert::ToFelt;
+
+use crate::{ContractFeeTokensBalance, ETH_WEI_DECIMALS};
 
 pub struct DevnetPredeployedContract {
     pub address: Felt,
@@ -21,18 +21,29 @@ pub struct DevnetPredeployedContract {
 
 pub struct DevnetKeys(pub Vec<DevnetPredeployedContract>);
 
-impl fmt::Display for DevnetKeys {
+/// Displays the devnet keys along with their balances in the fee tokens of the chain.
+pub struct DevnetKeysDisplay<'a> {
+    keys: &'a DevnetKeys,
+    chain_config: &'a ChainConfig,
+}
+
+impl fmt::Display for DevnetKeysDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let native_fee_token = &self.chain_config.native_fee_token;
         writeln!(f)?;
         writeln!(f, "==== DEVNET PREDEPLOYED CONTRACTS ====")?;
         writeln!(f)?;
-        for (i, contract) in self.0.iter().enumerate() {
+        for (i, contract) in self.keys.0.iter().enumerate() {
             writeln!(f, "(#{}) Address: {}", i + 1, contract.address.to_fixed_hex_string())?;
             writeln!(f, "  Private key: {}", contract.secret.secret_scalar().to_fixed_hex_string())?;
             match contract.balance.as_u128_fri_wei() {
                 Ok((fri, wei)) => {
-                    let (strk, eth) = (fri / STRK_FRI_DECIMALS, wei / ETH_WEI_DECIMALS);
-                    writeln!(f, "  Balance: {strk} STRK, {eth} ETH")?;
+                    let native = fri / native_fee_token.unit();
+                    write!(f, "  Balance: {native} {}", native_fee_token.symbol)?;
+                    if self.chain_config.supports_legacy_fee_payment() {
+                        write!(f, ", {} ETH", wei / ETH_WEI_DECIMALS)?;
+                    }
+                    writeln!(f)?;
                     writeln!(f)?;
                 }
                 Err(err) => writeln!(f, "Error getting balance: {err:#}\n")?,
@@ -70,18 +81,28 @@ pub fn get_bal_contract(
     Ok(low)
 }
 
-/// (STRK in FRI, ETH in WEI)
+/// (native fee token in its smallest unit, ETH in WEI). The ETH balance is zero on chains with a single fee token.
 pub fn get_fee_tokens_balance(
     backend: &MadaraBackend,
     contract_address: Felt,
 ) -> anyhow::Result<ContractFeeTokensBalance> {
+    let chain_config = backend.chain_config();
     Ok(ContractFeeTokensBalance {
-        fri: get_bal_contract(backend, contract_address, ERC20_STRK_CONTRACT_ADDRESS)?,
-        wei: get_bal_contract(backend, contract_address, ERC20_ETH_CONTRACT_ADDRESS)?,
+        fri: get_bal_contract(backend, contract_address, chain_config.native_fee_token_address.to_felt())?,
+        wei: match chain_config.parent_fee_token_address {
+            Some(parent_fee_token_address) => {
+                get_bal_contract(backend, contract_address, parent_fee_token_address.to_felt())?
+            }
+            None => Felt::ZERO,
+        },
     })
 }
 
 impl DevnetKeys {
+    pub fn display<'a>(&'a self, chain_config: &'a ChainConfig) -> DevnetKeysDisplay<'a> {
+        DevnetKeysDisplay { keys: self, chain_config }
+    }
+
     #[tracing::instrument(skip(backend), fields(module = "DevnetKeys"))]
     pub fn from_db(backend: &MadaraBackend) -> anyhow::Result<Self> {
         let keys = backend
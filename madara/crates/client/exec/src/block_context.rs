@@ -13,9 +13,11 @@ use starknet_api::block::{BlockInfo, BlockNumber, BlockTimestamp};
 
 use mc_db::{db_block_id::DbBlockId, MadaraBackend};
 use mp_block::MadaraMaybePendingBlockInfo;
-use mp_chain_config::L1DataAvailabilityMode;
+use mp_rpc::ContractStateOverride;
 
-use crate::{blockifier_state_adapter::BlockifierStateAdapter, Error, LayeredStateAdaptor};
+use crate::{
+    blockifier_state_adapter::BlockifierStateAdapter, Error, LayeredStateAdaptor, StateOverrideAdaptor, StateOverrides,
+};
 
 /// Extension trait that provides execution capabilities on the madara backend.
 pub trait MadaraBackendExecutionExt {
@@ -65,7 +67,7 @@ impl MadaraBackendExecutionExt for MadaraBackend {
                         .try_into()
                         .map_err(|_| Error::InvalidSequencerAddress(pending_block.header.sequencer_address))?,
                     gas_prices: (&pending_block.header.l1_gas_price).into(),
-                    use_kzg_da: pending_block.header.l1_da_mode == L1DataAvailabilityMode::Blob,
+                    use_kzg_da: pending_block.header.l1_da_mode.use_kzg_da(),
                 },
                 self.chain_config().blockifier_chain_info(),
                 self.chain_config().exec_constants_by_protocol_version(pending_block.header.protocol_version)?,
@@ -81,6 +83,8 @@ pub struct ExecutionContext {
     pub(crate) block_context: Arc<BlockContext>,
     /// None means we are executing the genesis block. (no latest block)
     pub(crate) latest_visible_block: Option<DbBlockId>,
+    /// Applied on top of the state when executing transactions and calls.
+    pub(crate) state_overrides: Arc<StateOverrides>,
 }
 
 impl ExecutionContext {
@@ -110,6 +114,23 @@ impl ExecutionContext {
         ))
     }
 
+    /// Same as [`Self::init_cached_state`], with the state overrides of this context applied on top.
+    pub(crate) fn init_overridden_cached_state(&self) -> CachedState<StateOverrideAdaptor<BlockifierStateAdapter>> {
+        let adaptor = BlockifierStateAdapter::new(
+            Arc::clone(&self.backend),
+            self.block_context.block_info().block_number.0,
+            self.latest_visible_block,
+        );
+        CachedState::new(StateOverrideAdaptor::new(adaptor, Arc::clone(&self.state_overrides)))
+    }
+
+    /// Override parts of the state during execution, see [`StateOverrideAdaptor`].
+    pub fn with_state_overrides(self, overrides: &[ContractStateOverride]) -> Result<Self, Error> {
+        let state_overrides =
+            StateOverrides::from_rpc(overrides, &self.block_context.chain_info().fee_token_addresses)?;
+        Ok(Self { state_overrides: state_overrides.into(), ..self })
+    }
+
     /// Init execution at the beginning of a block. The header of the block will be used, but all of the
     /// transactions' state modifications will not be visible.
     ///
@@ -192,7 +213,7 @@ impl ExecutionContext {
                 .try_into()
                 .map_err(|_| Error::InvalidSequencerAddress(sequencer_address))?,
             gas_prices: (&l1_gas_price).into(),
-            use_kzg_da: l1_da_mode == L1DataAvailabilityMode::Blob,
+            use_kzg_da: l1_da_mode.use_kzg_da(),
         };
 
         Ok(ExecutionContext {
@@ -205,6 +226,7 @@ impl ExecutionContext {
             .into(),
             latest_visible_block,
             backend,
+            state_overrides: Default::default(),
         })
     }
 }
@@ -53,7 +53,7 @@ impl ExecutionContext {
             SierraGasRevertTracker::new(entrypoint.initial_gas.into()),
         );
 
-        let mut cached_state = self.init_cached_state();
+        let mut cached_state = self.init_overridden_cached_state();
 
         let mut remaining_gas = entrypoint.initial_gas;
 
@@ -0,0 +1,211 @@
+use blockifier::execution::call_info::CallInfo;
+use blockifier::state::cached_state::CommitmentStateDiff;
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::StateReader;
+use blockifier::transaction::transaction_execution::Transaction;
+use mp_convert::ToFelt;
+use mp_rpc::admin::{DebugCallFrame, DebugGasConsumed, DebugRevertInfo, DebugStorageAccess, DebugTransactionTrace};
+use starknet_types_core::felt::Felt;
+
+use crate::trace::{
+    call_type, collect_call_info_ordered_events, collect_call_info_ordered_messages, computation_resources,
+    entry_point_type,
+};
+use crate::{Error, ExecutionContext, ExecutionResult};
+
+/// Cairo 1 serializes a `ByteArray` panic as this magic value, followed by the serialized byte array.
+const BYTE_ARRAY_MAGIC: Felt =
+    Felt::from_hex_unchecked("0x46a6158a16a947e5916b2a2ca68501a45e93d7110e81aa2d6438b1c57c879a3");
+
+impl ExecutionContext {
+    /// Execute transactions and return the debug traces of the `transactions_to_trace`, in order. This works the
+    /// same way as [`ExecutionContext::re_execute_transactions`]: the `transactions_before` are executed first and
+    /// their results are discarded.
+    pub fn debug_trace_transactions(
+        &self,
+        transactions_before: impl IntoIterator<Item = Transaction>,
+        transactions_to_trace: impl IntoIterator<Item = Transaction>,
+    ) -> Result<Vec<DebugTransactionTrace>, Error> {
+        self.re_execute_transactions_with(transactions_before, transactions_to_trace, |result, state_before| {
+            execution_result_to_debug_trace(&result, state_before)
+        })
+    }
+}
+
+fn execution_result_to_debug_trace(
+    execution_result: &ExecutionResult,
+    state_before: &dyn StateReader,
+) -> Result<DebugTransactionTrace, Error> {
+    let ExecutionResult { hash, execution_info, state_diff, .. } = execution_result;
+
+    let to_frame = |call_info: &Option<CallInfo>| {
+        call_info.as_ref().map(|call_info| debug_call_frame(call_info, state_diff, state_before)).transpose()
+    };
+
+    let gas = &execution_info.receipt.gas;
+    Ok(DebugTransactionTrace {
+        transaction_hash: hash.to_felt(),
+        validate_invocation: to_frame(&execution_info.validate_call_info)?,
+        execute_invocation: to_frame(&execution_info.execute_call_info)?,
+        fee_transfer_invocation: to_frame(&execution_info.fee_transfer_call_info)?,
+        revert_reason: execution_info.revert_error.as_ref().map(ToString::to_string),
+        actual_fee: execution_info.receipt.fee.0.into(),
+        gas_consumed: DebugGasConsumed { l1_gas: gas.l1_gas.0, l1_data_gas: gas.l1_data_gas.0, l2_gas: gas.l2_gas.0 },
+    })
+}
+
+fn debug_call_frame(
+    call_info: &CallInfo,
+    state_diff: &CommitmentStateDiff,
+    state_before: &dyn StateReader,
+) -> Result<DebugCallFrame, StateError> {
+    let contract_address = call_info.call.storage_address;
+    let storage_updates = state_diff.storage_updates.get(&contract_address);
+
+    let mut storage_accesses = call_info
+        .storage_access_tracker
+        .accessed_storage_keys
+        .iter()
+        .map(|key| {
+            let value_before = state_before.get_storage_at(contract_address, *key)?;
+            let value_after = storage_updates.and_then(|updates| updates.get(key)).copied().unwrap_or(value_before);
+            Ok(DebugStorageAccess {
+                key: key.to_felt(),
+                value_before,
+                value_after,
+                modified: value_before != value_after,
+            })
+        })
+        .collect::<Result<Vec<_>, StateError>>()?;
+    // Accessed keys are stored in a hash set, sort them to get a stable output.
+    storage_accesses.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let calls = call_info
+        .inner_calls
+        .iter()
+        .map(|inner_call| debug_call_frame(inner_call, state_diff, state_before))
+        .collect::<Result<_, _>>()?;
+
+    let revert = call_info.execution.failed.then(|| DebugRevertInfo {
+        panic_data: call_info.execution.retdata.0.clone(),
+        reason: decode_panic_data(&call_info.execution.retdata.0),
+    });
+
+    Ok(DebugCallFrame {
+        contract_address: contract_address.to_felt(),
+        class_hash: call_info.call.class_hash.map(ToFelt::to_felt),
+        caller_address: call_info.call.caller_address.to_felt(),
+        entry_point_selector: call_info.call.entry_point_selector.0,
+        entry_point_type: entry_point_type(call_info),
+        call_type: call_type(call_info),
+        calldata: call_info.call.calldata.0.to_vec(),
+        result: call_info.execution.retdata.0.clone(),
+        failed: call_info.execution.failed,
+        revert,
+        gas_consumed: call_info.execution.gas_consumed,
+        execution_resources: computation_resources(&call_info.resources),
+        storage_accesses,
+        events: collect_call_info_ordered_events(&call_info.execution.events),
+        messages: collect_call_info_ordered_messages(call_info),
+        calls,
+    })
+}
+
+/// Renders cairo panic data in a human readable way: felts that are valid short strings are shown alongside
+/// their text, and serialized byte arrays are decoded.
+fn decode_panic_data(panic_data: &[Felt]) -> String {
+    let mut parts = vec![];
+    let mut rest = panic_data;
+    while let Some((felt, tail)) = rest.split_first() {
+        if *felt == BYTE_ARRAY_MAGIC {
+            if let Some((string, tail)) = decode_byte_array(tail) {
+                parts.push(format!("{string:?}"));
+                rest = tail;
+                continue;
+            }
+        }
+        parts.push(match decode_short_string(felt) {
+            Some(string) => format!("{felt:#x} ('{string}')"),
+            None => format!("{felt:#x}"),
+        });
+        rest = tail;
+    }
+    parts.join(", ")
+}
+
+/// A `ByteArray` is serialized as the number of full 31 bytes words, the words, the pending word and the
+/// length of the pending word.
+fn decode_byte_array(data: &[Felt]) -> Option<(String, &[Felt])> {
+    let (n_words, data) = data.split_first()?;
+    // The length comes from untrusted panic data, don't let it overflow or index out of bounds.
+    let n_words = usize::try_from(u64::try_from(*n_words).ok()?).ok()?;
+    let words = data.get(..n_words)?;
+    let (pending_word, pending_len, rest) = match data.get(n_words..n_words.checked_add(2)?)? {
+        [pending_word, pending_len] => (*pending_word, *pending_len, &data[n_words + 2..]),
+        _ => return None,
+    };
+    let pending_len = u64::try_from(pending_len).ok().filter(|len| *len < 31)? as usize;
+
+    let mut bytes: Vec<u8> = words.iter().flat_map(|word| word.to_bytes_be()[1..].to_vec()).collect();
+    bytes.extend_from_slice(&pending_word.to_bytes_be()[32 - pending_len..]);
+
+    Some((String::from_utf8_lossy(&bytes).into_owned(), rest))
+}
+
+fn decode_short_string(felt: &Felt) -> Option<String> {
+    let bytes = felt.to_bytes_be();
+    let bytes = &bytes[bytes.iter().position(|b| *b != 0)?..];
+    if bytes.len() > 31 || !bytes.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        return None;
+    }
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_panic_data_short_strings() {
+        let panic_data = [Felt::from_bytes_be_slice(b"argent/invalid-signature"), Felt::from(0x1234u64)];
+        assert_eq!(
+            decode_panic_data(&panic_data),
+            "0x617267656e742f696e76616c69642d7369676e6174757265 ('argent/invalid-signature'), 0x1234"
+        );
+        assert_eq!(decode_panic_data(&[]), "");
+    }
+
+    #[test]
+    fn decode_panic_data_byte_array() {
+        let message = "this error message is longer than thirty-one bytes";
+        let (word, pending) = message.as_bytes().split_at(31);
+        let panic_data = [
+            BYTE_ARRAY_MAGIC,
+            Felt::ONE,
+            Felt::from_bytes_be_slice(word),
+            Felt::from_bytes_be_slice(pending),
+            Felt::from(pending.len() as u64),
+            Felt::from_bytes_be_slice(b"ENTRYPOINT_FAILED"),
+        ];
+        assert_eq!(
+            decode_panic_data(&panic_data),
+            format!("{message:?}, 0x454e545259504f494e545f4641494c4544 ('ENTRYPOINT_FAILED')")
+        );
+    }
+
+    #[test]
+    fn decode_panic_data_invalid_byte_array() {
+        // Not enough words after the magic value: falls back to raw felts.
+        let panic_data = [BYTE_ARRAY_MAGIC, Felt::from(3u64)];
+        assert_eq!(decode_panic_data(&panic_data), format!("{BYTE_ARRAY_MAGIC:#x}, 0x3"));
+    }
+
+    #[test]
+    fn decode_panic_data_huge_byte_array_length() {
+        // A length word close to the integer bounds must not overflow or panic.
+        for n_words in [Felt::from(u64::MAX), Felt::from(u64::MAX - 1), Felt::from(usize::MAX as u64 - 2)] {
+            let panic_data = [BYTE_ARRAY_MAGIC, n_words, Felt::ONE, Felt::ONE];
+            assert_eq!(decode_panic_data(&panic_data), format!("{BYTE_ARRAY_MAGIC:#x}, {n_words:#x}, 0x1, 0x1"));
+        }
+    }
+}
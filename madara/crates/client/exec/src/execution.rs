@@ -1,7 +1,8 @@
 use crate::{Error, ExecutionContext, ExecutionResult, TxExecError};
 use blockifier::fee::fee_utils::get_fee_by_gas_vector;
 use blockifier::fee::gas_usage::estimate_minimal_gas_vector;
-use blockifier::state::cached_state::TransactionalState;
+use blockifier::state::cached_state::{CachedState, TransactionalState};
+use blockifier::state::state_api::StateReader;
 use blockifier::transaction::account_transaction::AccountTransaction;
 use blockifier::transaction::errors::TransactionExecutionError;
 use blockifier::transaction::objects::{HasRelatedFeeType, TransactionExecutionInfo};
@@ -23,13 +24,35 @@ impl ExecutionContext {
         transactions_before: impl IntoIterator<Item = Transaction>,
         transactions_to_trace: impl IntoIterator<Item = Transaction>,
     ) -> Result<Vec<ExecutionResult>, Error> {
-        let mut cached_state = self.init_cached_state();
+        self.re_execute_transactions_with(transactions_before, transactions_to_trace, |result, _| Ok(result))
+    }
 
+    /// Same as [`Self::re_execute_transactions`], but every `ExecutionResult` is passed to `on_executed`
+    /// along with the state as it was right before the transaction, before its changes are committed.
+    pub(crate) fn re_execute_transactions_with<T>(
+        &self,
+        transactions_before: impl IntoIterator<Item = Transaction>,
+        transactions_to_trace: impl IntoIterator<Item = Transaction>,
+        on_executed: impl FnMut(ExecutionResult, &dyn StateReader) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error> {
+        let mut cached_state = self.init_overridden_cached_state();
+        self.re_execute_transactions_on(&mut cached_state, transactions_before, transactions_to_trace, on_executed)
+    }
+
+    /// Same as [`Self::re_execute_transactions_with`], on top of `cached_state`. The changes of every transaction are
+    /// committed to it, which allows reading the state changes of the whole block once this returns.
+    pub(crate) fn re_execute_transactions_on<S: StateReader, T>(
+        &self,
+        cached_state: &mut CachedState<S>,
+        transactions_before: impl IntoIterator<Item = Transaction>,
+        transactions_to_trace: impl IntoIterator<Item = Transaction>,
+        mut on_executed: impl FnMut(ExecutionResult, &dyn StateReader) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error> {
         let mut executed_prev = 0;
         for (index, tx) in transactions_before.into_iter().enumerate() {
             let hash = tx.tx_hash();
             tracing::debug!("executing {:#x}", hash.to_felt());
-            tx.execute(&mut cached_state, &self.block_context).map_err(|err| TxExecError {
+            tx.execute(cached_state, &self.block_context).map_err(|err| TxExecError {
                 block_n: self.latest_visible_block.into(),
                 hash,
                 index,
@@ -67,7 +90,7 @@ impl ExecutionContext {
                     err,
                 };
 
-                let mut transactional_state = TransactionalState::create_transactional(&mut cached_state);
+                let mut transactional_state = TransactionalState::create_transactional(cached_state);
                 // NB: We use execute_raw because execute already does transaactional state.
                 let execution_info = tx
                     .execute_raw(&mut transactional_state, &self.block_context, false)
@@ -93,16 +116,19 @@ impl ExecutionContext {
                     .to_state_diff()
                     .map_err(TransactionExecutionError::StateError)
                     .map_err(make_reexec_error)?;
-                transactional_state.commit();
-
-                Ok(ExecutionResult {
+                let result = ExecutionResult {
                     hash,
                     tx_type,
                     fee_type,
                     minimal_l1_gas: minimal_gas,
                     execution_info,
                     state_diff: state_diff.state_maps.into(),
-                })
+                };
+                // The transactional state wraps the state as it was before this transaction.
+                let res = on_executed(result, &transactional_state.state)?;
+                transactional_state.commit();
+
+                Ok(res)
             })
             .collect::<Result<Vec<_>, _>>()
     }
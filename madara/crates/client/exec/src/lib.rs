@@ -1,7 +1,7 @@
 use core::fmt;
 
 use blockifier::{
-    state::cached_state::CommitmentStateDiff,
+    state::{cached_state::CommitmentStateDiff, errors::StateError},
     transaction::{errors::TransactionExecutionError, objects::TransactionExecutionInfo},
 };
 use mc_db::{db_block_id::DbBlockId, MadaraStorageError};
@@ -13,15 +13,21 @@ use starknet_types_core::felt::Felt;
 mod block_context;
 mod blockifier_state_adapter;
 mod call;
+mod debug_trace;
 pub mod execution;
 mod fee;
 mod layered_state_adaptor;
+mod state_diff;
+mod state_override_adaptor;
 mod trace;
 pub mod transaction;
+pub mod verify;
 
 pub use block_context::{ExecutionContext, MadaraBackendExecutionExt};
 pub use blockifier_state_adapter::BlockifierStateAdapter;
 pub use layered_state_adaptor::LayeredStateAdaptor;
+pub use state_diff::state_map_to_state_diff;
+pub use state_override_adaptor::{StateOverrideAdaptor, StateOverrides};
 pub use trace::execution_result_to_tx_trace;
 
 #[derive(Debug)]
@@ -54,8 +60,12 @@ pub enum Error {
     CallContract(#[from] CallContractError),
     #[error("Storage error: {0:#}")]
     Storage(#[from] MadaraStorageError),
+    #[error("State error: {0:#}")]
+    State(#[from] StateError),
     #[error("Invalid sequencer address: {0:#x}")]
     InvalidSequencerAddress(Felt),
+    #[error("Invalid state override: {0}")]
+    InvalidStateOverride(String),
 }
 
 #[derive(thiserror::Error, Debug)]
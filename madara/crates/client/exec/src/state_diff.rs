@@ -0,0 +1,93 @@
+use blockifier::state::cached_state::StateMaps;
+use mc_db::{db_block_id::DbBlockId, MadaraBackend, MadaraStorageError};
+use mp_convert::ToFelt;
+use mp_state_update::{
+    ContractStorageDiffItem, DeclaredClassItem, DeployedContractItem, NonceUpdate, ReplacedClassItem, StateDiff,
+    StorageEntry,
+};
+use starknet_api::core::ContractAddress;
+use std::collections::{hash_map, HashMap};
+
+/// Converts the state changes of a block execution into a [`StateDiff`]. The contracts which already had a class
+/// in the state of `on_top_of` are sorted into the replaced classes, the others into the deployed contracts.
+pub fn state_map_to_state_diff(
+    backend: &MadaraBackend,
+    on_top_of: &Option<DbBlockId>,
+    diff: StateMaps,
+) -> Result<StateDiff, MadaraStorageError> {
+    let mut backing_map = HashMap::<ContractAddress, usize>::default();
+    let mut storage_diffs = Vec::<ContractStorageDiffItem>::default();
+    for ((address, key), value) in diff.storage {
+        match backing_map.entry(address) {
+            hash_map::Entry::Vacant(e) => {
+                e.insert(storage_diffs.len());
+                storage_diffs.push(ContractStorageDiffItem {
+                    address: address.to_felt(),
+                    storage_entries: vec![StorageEntry { key: key.to_felt(), value }],
+                });
+            }
+            hash_map::Entry::Occupied(e) => {
+                storage_diffs[*e.get()].storage_entries.push(StorageEntry { key: key.to_felt(), value });
+            }
+        }
+    }
+
+    let mut deprecated_declared_classes = Vec::default();
+    for (class_hash, _) in diff.declared_contracts {
+        if !diff.compiled_class_hashes.contains_key(&class_hash) {
+            deprecated_declared_classes.push(class_hash.to_felt());
+        }
+    }
+
+    let declared_classes = diff
+        .compiled_class_hashes
+        .iter()
+        .map(|(class_hash, compiled_class_hash)| DeclaredClassItem {
+            class_hash: class_hash.to_felt(),
+            compiled_class_hash: compiled_class_hash.to_felt(),
+        })
+        .collect();
+
+    let nonces = diff
+        .nonces
+        .into_iter()
+        .map(|(contract_address, nonce)| NonceUpdate {
+            contract_address: contract_address.to_felt(),
+            nonce: nonce.to_felt(),
+        })
+        .collect();
+
+    let mut deployed_contracts = Vec::new();
+    let mut replaced_classes = Vec::new();
+    for (contract_address, new_class_hash) in diff.class_hashes {
+        let replaced = if let Some(on_top_of) = on_top_of {
+            match backend.get_contract_class_hash_at(on_top_of, &contract_address.to_felt())? {
+                Some(class_hash) => class_hash != new_class_hash.to_felt(),
+                None => false,
+            }
+        } else {
+            // Executing genesis block: nothing being redefined here
+            false
+        };
+        if replaced {
+            replaced_classes.push(ReplacedClassItem {
+                contract_address: contract_address.to_felt(),
+                class_hash: new_class_hash.to_felt(),
+            })
+        } else {
+            deployed_contracts.push(DeployedContractItem {
+                address: contract_address.to_felt(),
+                class_hash: new_class_hash.to_felt(),
+            })
+        }
+    }
+
+    Ok(StateDiff {
+        storage_diffs,
+        deprecated_declared_classes,
+        declared_classes,
+        nonces,
+        deployed_contracts,
+        replaced_classes,
+    })
+}
@@ -0,0 +1,164 @@
+use std::{collections::HashMap, sync::Arc};
+
+use blockifier::{
+    abi::abi_utils::get_fee_token_var_address,
+    context::FeeTokenAddresses,
+    execution::contract_class::RunnableCompiledClass,
+    state::state_api::{StateReader, StateResult},
+};
+use mp_rpc::ContractStateOverride;
+use starknet_api::{
+    core::{ClassHash, CompiledClassHash, ContractAddress, Nonce},
+    state::StorageKey,
+};
+use starknet_types_core::felt::Felt;
+
+use crate::Error;
+
+/// Values replacing the state of the chain during execution. See [`StateOverrideAdaptor`].
+#[derive(Debug, Default)]
+pub struct StateOverrides {
+    storage: HashMap<(ContractAddress, StorageKey), Felt>,
+    nonces: HashMap<ContractAddress, Nonce>,
+    class_hashes: HashMap<ContractAddress, ClassHash>,
+}
+
+impl StateOverrides {
+    /// Fee token balances are overridden in both the ETH and STRK fee tokens, as the `u256` stored in the
+    /// `ERC20_balances` mapping.
+    pub fn from_rpc(
+        overrides: &[ContractStateOverride],
+        fee_token_addresses: &FeeTokenAddresses,
+    ) -> Result<Self, Error> {
+        let mut this = Self::default();
+        for over in overrides {
+            let contract_address = to_contract_address(over.contract_address)?;
+
+            if let Some(nonce) = over.nonce {
+                this.nonces.insert(contract_address, Nonce(nonce));
+            }
+            if let Some(class_hash) = over.class_hash {
+                this.class_hashes.insert(contract_address, ClassHash(class_hash));
+            }
+            for entry in &over.storage {
+                this.storage.insert((contract_address, to_storage_key(entry.key)?), entry.value);
+            }
+            if let Some(balance) = over.fee_token_balance {
+                let balance_key = get_fee_token_var_address(contract_address);
+                let balance_key_high = balance_key.next_storage_key().map_err(|err| {
+                    Error::InvalidStateOverride(format!("Balance key of {:#x}: {err:#}", over.contract_address))
+                })?;
+                let bytes = balance.to_bytes_be();
+                let (high, low) = (Felt::from_bytes_be_slice(&bytes[..16]), Felt::from_bytes_be_slice(&bytes[16..]));
+                for token in [fee_token_addresses.eth_fee_token_address, fee_token_addresses.strk_fee_token_address] {
+                    this.storage.insert((token, balance_key), low);
+                    this.storage.insert((token, balance_key_high), high);
+                }
+            }
+        }
+        Ok(this)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty() && self.nonces.is_empty() && self.class_hashes.is_empty()
+    }
+}
+
+fn to_contract_address(felt: Felt) -> Result<ContractAddress, Error> {
+    felt.try_into().map_err(|err| Error::InvalidStateOverride(format!("Invalid contract address {felt:#x}: {err:#}")))
+}
+
+fn to_storage_key(felt: Felt) -> Result<StorageKey, Error> {
+    felt.try_into().map_err(|err| Error::InvalidStateOverride(format!("Invalid storage key {felt:#x}: {err:#}")))
+}
+
+/// Overlay on top of another state reader, used to answer "what if" queries in the `call`, `estimateFee` and
+/// `simulateTransactions` rpc endpoints. Overridden values shadow the values of the inner state.
+pub struct StateOverrideAdaptor<S> {
+    inner: S,
+    overrides: Arc<StateOverrides>,
+}
+
+impl<S: StateReader> StateOverrideAdaptor<S> {
+    pub fn new(inner: S, overrides: Arc<StateOverrides>) -> Self {
+        Self { inner, overrides }
+    }
+}
+
+impl<S: StateReader> StateReader for StateOverrideAdaptor<S> {
+    fn get_storage_at(&self, contract_address: ContractAddress, key: StorageKey) -> StateResult<Felt> {
+        if let Some(value) = self.overrides.storage.get(&(contract_address, key)) {
+            return Ok(*value);
+        }
+        self.inner.get_storage_at(contract_address, key)
+    }
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        if let Some(nonce) = self.overrides.nonces.get(&contract_address) {
+            return Ok(*nonce);
+        }
+        self.inner.get_nonce_at(contract_address)
+    }
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        if let Some(class_hash) = self.overrides.class_hashes.get(&contract_address) {
+            return Ok(*class_hash);
+        }
+        self.inner.get_class_hash_at(contract_address)
+    }
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        self.inner.get_compiled_class(class_hash)
+    }
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.inner.get_compiled_class_hash(class_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StateOverrideAdaptor, StateOverrides};
+    use crate::BlockifierStateAdapter;
+    use blockifier::{abi::abi_utils::get_fee_token_var_address, state::state_api::StateReader};
+    use mc_db::MadaraBackend;
+    use mp_chain_config::ChainConfig;
+    use mp_convert::Felt;
+    use mp_rpc::{ContractStateOverride, KeyValuePair};
+    use starknet_api::core::{ClassHash, Nonce};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_state_override_adaptor() {
+        let chain_config = Arc::new(ChainConfig::madara_test());
+        let backend = MadaraBackend::open_for_testing(Arc::clone(&chain_config));
+        let fee_token_addresses = chain_config.blockifier_chain_info().fee_token_addresses;
+
+        let overrides = StateOverrides::from_rpc(
+            &[ContractStateOverride {
+                contract_address: Felt::ONE,
+                nonce: Some(Felt::TWO),
+                class_hash: Some(Felt::THREE),
+                storage: vec![KeyValuePair { key: Felt::ONE, value: Felt::THREE }],
+                fee_token_balance: Some(Felt::from_hex_unchecked("0x200000000000000000000000000000005")),
+            }],
+            &fee_token_addresses,
+        )
+        .unwrap();
+        assert!(!overrides.is_empty());
+
+        let adaptor = StateOverrideAdaptor::new(BlockifierStateAdapter::new(backend, 0, None), Arc::new(overrides));
+        let contract = Felt::ONE.try_into().unwrap();
+        let other_contract = Felt::TWO.try_into().unwrap();
+
+        assert_eq!(adaptor.get_nonce_at(contract).unwrap(), Nonce(Felt::TWO));
+        assert_eq!(adaptor.get_nonce_at(other_contract).unwrap(), Nonce(Felt::ZERO));
+        assert_eq!(adaptor.get_class_hash_at(contract).unwrap(), ClassHash(Felt::THREE));
+        assert_eq!(adaptor.get_class_hash_at(other_contract).unwrap(), ClassHash(Felt::ZERO));
+        assert_eq!(adaptor.get_storage_at(contract, Felt::ONE.try_into().unwrap()).unwrap(), Felt::THREE);
+        assert_eq!(adaptor.get_storage_at(contract, Felt::TWO.try_into().unwrap()).unwrap(), Felt::ZERO);
+
+        // The balance is a u256 split in two storage slots, in both fee tokens.
+        let balance_key = get_fee_token_var_address(contract);
+        for token in [fee_token_addresses.eth_fee_token_address, fee_token_addresses.strk_fee_token_address] {
+            assert_eq!(adaptor.get_storage_at(token, balance_key).unwrap(), Felt::from(5u64));
+            assert_eq!(adaptor.get_storage_at(token, balance_key.next_storage_key().unwrap()).unwrap(), Felt::TWO);
+        }
+    }
+}
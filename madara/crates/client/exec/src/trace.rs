@@ -119,16 +119,8 @@ fn try_get_funtion_invocation_from_call_info(
     let inner_calls =
         call_info.inner_calls.iter().map(try_get_funtion_invocation_from_call_info).collect::<Result<_, _>>()?;
 
-    let entry_point_type = match call_info.call.entry_point_type {
-        starknet_api::contract_class::EntryPointType::Constructor => mp_rpc::EntryPointType::Constructor,
-        starknet_api::contract_class::EntryPointType::External => mp_rpc::EntryPointType::External,
-        starknet_api::contract_class::EntryPointType::L1Handler => mp_rpc::EntryPointType::L1Handler,
-    };
-
-    let call_type = match call_info.call.call_type {
-        blockifier::execution::entry_point::CallType::Call => mp_rpc::CallType::Regular,
-        blockifier::execution::entry_point::CallType::Delegate => mp_rpc::CallType::Delegate,
-    };
+    let entry_point_type = entry_point_type(call_info);
+    let call_type = call_type(call_info);
 
     // Field `class_hash` into `FunctionInvocation` should be an Option
     let class_hash = call_info.call.class_hash.map(ToFelt::to_felt).unwrap_or_default();
@@ -152,7 +144,22 @@ fn try_get_funtion_invocation_from_call_info(
     })
 }
 
-fn collect_call_info_ordered_messages(call_info: &CallInfo) -> Vec<mp_rpc::OrderedMessage> {
+pub(crate) fn entry_point_type(call_info: &CallInfo) -> mp_rpc::EntryPointType {
+    match call_info.call.entry_point_type {
+        starknet_api::contract_class::EntryPointType::Constructor => mp_rpc::EntryPointType::Constructor,
+        starknet_api::contract_class::EntryPointType::External => mp_rpc::EntryPointType::External,
+        starknet_api::contract_class::EntryPointType::L1Handler => mp_rpc::EntryPointType::L1Handler,
+    }
+}
+
+pub(crate) fn call_type(call_info: &CallInfo) -> mp_rpc::CallType {
+    match call_info.call.call_type {
+        blockifier::execution::entry_point::CallType::Call => mp_rpc::CallType::Regular,
+        blockifier::execution::entry_point::CallType::Delegate => mp_rpc::CallType::Delegate,
+    }
+}
+
+pub(crate) fn collect_call_info_ordered_messages(call_info: &CallInfo) -> Vec<mp_rpc::OrderedMessage> {
     call_info
         .execution
         .l2_to_l1_messages
@@ -169,7 +176,7 @@ fn collect_call_info_ordered_messages(call_info: &CallInfo) -> Vec<mp_rpc::Order
         .collect()
 }
 
-fn collect_call_info_ordered_events(
+pub(crate) fn collect_call_info_ordered_events(
     ordered_events: &[blockifier::execution::call_info::OrderedEvent],
 ) -> Vec<mp_rpc::OrderedEvent> {
     ordered_events
@@ -184,7 +191,7 @@ fn collect_call_info_ordered_events(
         .collect()
 }
 
-fn computation_resources(
+pub(crate) fn computation_resources(
     vm_resources: &cairo_vm::vm::runners::cairo_runner::ExecutionResources,
 ) -> mp_rpc::ComputationResources {
     let steps = vm_resources.n_steps as u64;
@@ -0,0 +1,412 @@
+//! Re-execution of stored blocks, to check that this binary still agrees with the stored chain after a blockifier or
+//! versioned constants upgrade.
+
+use crate::{execution::TxInfo, state_map_to_state_diff, transaction::to_blockifier_transaction, Error};
+use crate::{ExecutionContext, TxExecError};
+use blockifier::state::cached_state::CommitmentStateDiff;
+use blockifier::state::state_api::State;
+use blockifier::transaction::transaction_execution::Transaction;
+use mc_db::{db_block_id::DbBlockId, MadaraBackend, MadaraStorageError};
+use mp_block::{BlockId, MadaraMaybePendingBlockInfo};
+use mp_chain_config::StarknetVersion;
+use mp_convert::ToFelt;
+use mp_receipt::from_blockifier_execution_info;
+use mp_state_update::StateDiff;
+use starknet_api::transaction::TransactionHash;
+use starknet_types_core::felt::Felt;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    sync::Arc,
+};
+
+/// Number of blocks between a block and the block whose hash is written to the storage of contract 0x1.
+const STORED_BLOCK_HASH_BUFFER: u64 = 10;
+const BLOCK_HASH_CONTRACT_ADDRESS: Felt = Felt::ONE;
+
+#[derive(thiserror::Error, Debug)]
+pub enum VerifyExecutionError {
+    #[error("Block #{0} not found")]
+    BlockNotFound(u64),
+    #[error("State diff of block #{0} not found")]
+    StateDiffNotFound(u64),
+    #[error("Storage error: {0:#}")]
+    Storage(#[from] MadaraStorageError),
+    #[error("Converting transaction {hash:#x} to blockifier format: {err:#}")]
+    TransactionConversion {
+        hash: Felt,
+        #[source]
+        err: crate::transaction::Error,
+    },
+    #[error(transparent)]
+    Execution(#[from] Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// The transaction cannot be executed anymore.
+    Execution,
+    /// The receipt differs, apart from its events.
+    Receipt,
+    /// The events emitted by the transaction differ.
+    Events,
+    StateDiff,
+    StateRoot,
+}
+
+impl fmt::Display for DivergenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Execution => write!(f, "execution"),
+            Self::Receipt => write!(f, "receipt"),
+            Self::Events => write!(f, "events"),
+            Self::StateDiff => write!(f, "state diff"),
+            Self::StateRoot => write!(f, "state root"),
+        }
+    }
+}
+
+/// The first difference found between the re-execution of a block and the stored block.
+#[derive(Debug)]
+pub struct ExecutionDivergence {
+    pub block_n: u64,
+    pub kind: DivergenceKind,
+    /// Index and hash of the first divergent transaction, when the divergence can be attributed to one.
+    pub transaction: Option<(usize, Felt)>,
+    /// Stored values are prefixed with `-` and re-executed values with `+`.
+    pub diff: String,
+}
+
+impl fmt::Display for ExecutionDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.transaction {
+            Some((index, hash)) => writeln!(
+                f,
+                "Block #{} diverges on the {} of transaction {hash:#x} (index {index}):",
+                self.block_n, self.kind
+            )?,
+            None => writeln!(f, "Block #{} diverges on its {}:", self.block_n, self.kind)?,
+        }
+        write!(f, "{}", self.diff)
+    }
+}
+
+#[derive(Debug)]
+pub enum BlockVerification {
+    /// The re-execution matches the stored block. The state root is not checked when the global tries do not keep
+    /// enough trie logs to go back to the parent block.
+    Matches {
+        state_root_checked: bool,
+    },
+    /// Blocks older than Starknet v0.13.0 cannot be executed by the blockifier.
+    Unsupported,
+    Diverges(ExecutionDivergence),
+}
+
+/// Re-executes block `block_n` on top of the state of its parent, and compares the receipts, events and state diff with
+/// the stored block. When the state diffs match, the state root of the stored header is compared with the root computed
+/// by applying the re-executed state diff on top of the global tries at the end of the parent block.
+pub fn verify_block_execution(
+    backend: &Arc<MadaraBackend>,
+    block_n: u64,
+) -> Result<BlockVerification, VerifyExecutionError> {
+    let block_id = DbBlockId::Number(block_n);
+    let block = backend.get_block(&block_id)?.ok_or(VerifyExecutionError::BlockNotFound(block_n))?;
+    let MadaraMaybePendingBlockInfo::NotPending(info) = &block.info else {
+        return Err(VerifyExecutionError::BlockNotFound(block_n));
+    };
+    if info.header.protocol_version < StarknetVersion::V0_13_0 {
+        return Ok(BlockVerification::Unsupported);
+    }
+    // Re-executing needs the state at the end of the parent block.
+    let parent_block_id = block_n.checked_sub(1).map(DbBlockId::Number);
+    if let Some(parent_block_n) = block_n.checked_sub(1) {
+        backend.check_state_not_pruned(parent_block_n)?;
+    }
+    let stored_state_diff =
+        backend.get_block_state_diff(&block_id)?.ok_or(VerifyExecutionError::StateDiffNotFound(block_n))?;
+
+    let transactions = Iterator::zip(block.inner.transactions.into_iter(), &info.tx_hashes)
+        .map(|(tx, hash)| {
+            to_blockifier_transaction(Arc::clone(backend), BlockId::Number(block_n), tx, &TransactionHash(*hash))
+                .map_err(|err| VerifyExecutionError::TransactionConversion { hash: *hash, err })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let exec_context = ExecutionContext::new_at_block_start(Arc::clone(backend), &block.info)?;
+    let mut cached_state = exec_context.init_overridden_cached_state();
+
+    // Starknet writes the hash of block `n - 10` to the storage of contract 0x1 before executing block `n`. When the
+    // stored block has this write, it is replayed like the blockifier does in `pre_process_block`.
+    if let Some(old_block_n) = block_n.checked_sub(STORED_BLOCK_HASH_BUFFER) {
+        let key = Felt::from(old_block_n);
+        let has_block_hash_write = stored_state_diff
+            .storage_diffs
+            .iter()
+            .filter(|item| item.address == BLOCK_HASH_CONTRACT_ADDRESS)
+            .any(|item| item.storage_entries.iter().any(|entry| entry.key == key));
+        if has_block_hash_write {
+            let old_block_hash = backend
+                .get_block_hash(&DbBlockId::Number(old_block_n))?
+                .ok_or(VerifyExecutionError::BlockNotFound(old_block_n))?;
+            cached_state
+                .set_storage_at(
+                    BLOCK_HASH_CONTRACT_ADDRESS.try_into().expect("Valid contract address"),
+                    key.try_into().expect("Valid storage key"),
+                    old_block_hash,
+                )
+                .map_err(Error::State)?;
+        }
+    }
+    let results = match exec_context.re_execute_transactions_on(
+        &mut cached_state,
+        [],
+        transactions.iter().cloned(),
+        |result, _| Ok(result),
+    ) {
+        Ok(results) => results,
+        Err(Error::Reexecution(TxExecError { hash, index, err, .. })) => {
+            return Ok(BlockVerification::Diverges(ExecutionDivergence {
+                block_n,
+                kind: DivergenceKind::Execution,
+                transaction: Some((index, hash.to_felt())),
+                diff: format!("+ {err:#}\n"),
+            }))
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    // Receipts and events.
+
+    for (index, ((result, tx), stored)) in results.iter().zip(&transactions).zip(&block.inner.receipts).enumerate() {
+        let receipt = from_blockifier_execution_info(&result.execution_info, tx);
+        if &receipt != stored {
+            let kind =
+                if receipt.events() != stored.events() { DivergenceKind::Events } else { DivergenceKind::Receipt };
+            return Ok(BlockVerification::Diverges(ExecutionDivergence {
+                block_n,
+                kind,
+                transaction: Some((index, stored.transaction_hash())),
+                diff: diff_lines(&to_pretty_json(stored), &to_pretty_json(&receipt)),
+            }));
+        }
+    }
+
+    // State diff.
+
+    let state_maps = cached_state.to_state_diff().map_err(Error::State)?.state_maps;
+    let state_diff = state_map_to_state_diff(backend, &parent_block_id, state_maps)?;
+    let (stored_entries, entries) = (state_diff_entries(&stored_state_diff), state_diff_entries(&state_diff));
+    if stored_entries != entries {
+        let divergent_keys: BTreeSet<_> = stored_entries
+            .keys()
+            .chain(entries.keys())
+            .filter(|key| stored_entries.get(*key) != entries.get(*key))
+            .cloned()
+            .collect();
+        // The first transaction writing to one of the divergent entries.
+        let transaction = results
+            .iter()
+            .zip(&transactions)
+            .position(|(result, tx)| {
+                touched_keys(&result.state_diff, tx).iter().any(|key| divergent_keys.contains(key))
+            })
+            .map(|index| (index, results[index].hash.to_felt()));
+        return Ok(BlockVerification::Diverges(ExecutionDivergence {
+            block_n,
+            kind: DivergenceKind::StateDiff,
+            transaction,
+            diff: diff_entries(&stored_entries, &entries, &divergent_keys),
+        }));
+    }
+
+    // State root, computed from the re-executed state diff on top of the global tries at the end of the parent block.
+
+    let state_root_checked = match backend.compute_global_state_root(block_n, &state_diff)? {
+        Some(state_root) if state_root != info.header.global_state_root => {
+            return Ok(BlockVerification::Diverges(ExecutionDivergence {
+                block_n,
+                kind: DivergenceKind::StateRoot,
+                transaction: None,
+                diff: format!("- {:#x}\n+ {state_root:#x}\n", info.header.global_state_root),
+            }));
+        }
+        Some(_) => true,
+        None => false,
+    };
+
+    Ok(BlockVerification::Matches { state_root_checked })
+}
+
+fn to_pretty_json(value: &impl serde::Serialize) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|err| format!("<{err}>"))
+}
+
+fn storage_key(address: Felt, key: Felt) -> String {
+    format!("storage {address:#x} {key:#x}")
+}
+fn nonce_key(address: Felt) -> String {
+    format!("nonce {address:#x}")
+}
+fn class_hash_key(address: Felt) -> String {
+    format!("class_hash {address:#x}")
+}
+fn declared_class_key(class_hash: Felt) -> String {
+    format!("declared {class_hash:#x}")
+}
+
+/// Flattens a state diff into a sorted map of entries, so that two state diffs can be compared regardless of the order
+/// of their items.
+fn state_diff_entries(state_diff: &StateDiff) -> BTreeMap<String, String> {
+    let mut entries = BTreeMap::new();
+    for item in &state_diff.storage_diffs {
+        for entry in &item.storage_entries {
+            entries.insert(storage_key(item.address, entry.key), format!("{:#x}", entry.value));
+        }
+    }
+    for item in &state_diff.nonces {
+        entries.insert(nonce_key(item.contract_address), format!("{:#x}", item.nonce));
+    }
+    for item in &state_diff.deployed_contracts {
+        entries.insert(class_hash_key(item.address), format!("{:#x} (deployed)", item.class_hash));
+    }
+    for item in &state_diff.replaced_classes {
+        entries.insert(class_hash_key(item.contract_address), format!("{:#x} (replaced)", item.class_hash));
+    }
+    for item in &state_diff.declared_classes {
+        entries.insert(
+            declared_class_key(item.class_hash),
+            format!("compiled class hash {:#x}", item.compiled_class_hash),
+        );
+    }
+    for class_hash in &state_diff.deprecated_declared_classes {
+        entries.insert(declared_class_key(*class_hash), "legacy class".into());
+    }
+    entries
+}
+
+/// Keys of the state diff entries written by a transaction.
+fn touched_keys(state_diff: &CommitmentStateDiff, tx: &Transaction) -> Vec<String> {
+    let storage = state_diff
+        .storage_updates
+        .iter()
+        .flat_map(|(address, updates)| updates.keys().map(|key| storage_key(address.to_felt(), key.to_felt())));
+    let nonces = state_diff.address_to_nonce.keys().map(|address| nonce_key(address.to_felt()));
+    let class_hashes = state_diff.address_to_class_hash.keys().map(|address| class_hash_key(address.to_felt()));
+    let declared = state_diff
+        .class_hash_to_compiled_class_hash
+        .keys()
+        .copied()
+        .chain(TxInfo::declared_class_hash(tx))
+        .map(|class_hash| declared_class_key(class_hash.to_felt()));
+    storage.chain(nonces).chain(class_hashes).chain(declared).collect()
+}
+
+fn diff_entries(
+    stored: &BTreeMap<String, String>,
+    re_executed: &BTreeMap<String, String>,
+    divergent_keys: &BTreeSet<String>,
+) -> String {
+    let mut out = String::new();
+    for key in divergent_keys {
+        if let Some(value) = stored.get(key) {
+            out.push_str(&format!("- {key} = {value}\n"));
+        }
+        if let Some(value) = re_executed.get(key) {
+            out.push_str(&format!("+ {key} = {value}\n"));
+        }
+    }
+    out
+}
+
+/// Line diff between `stored` and `re_executed`, keeping a few lines of context around the changes.
+fn diff_lines(stored: &str, re_executed: &str) -> String {
+    const CONTEXT: usize = 3;
+
+    let (a, b): (Vec<_>, Vec<_>) = (stored.lines().collect(), re_executed.lines().collect());
+    // lcs[i][j] is the length of the longest common subsequence of a[i..] and b[j..].
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(a.len().max(b.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            lines.push((' ', a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(('-', a[i]));
+            i += 1;
+        } else {
+            lines.push(('+', b[j]));
+            j += 1;
+        }
+    }
+
+    let mut keep = vec![false; lines.len()];
+    for (index, _) in lines.iter().enumerate().filter(|(_, (tag, _))| *tag != ' ') {
+        keep[index.saturating_sub(CONTEXT)..(index + CONTEXT + 1).min(lines.len())].fill(true);
+    }
+    let mut out = String::new();
+    let mut skipped = false;
+    for ((tag, line), keep) in lines.into_iter().zip(keep) {
+        if !keep {
+            skipped = true;
+            continue;
+        }
+        if std::mem::take(&mut skipped) {
+            out.push_str("  ...\n");
+        }
+        out.push_str(&format!("{tag} {line}\n"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mp_state_update::{ContractStorageDiffItem, DeployedContractItem, StorageEntry};
+
+    #[test]
+    fn test_diff_lines() {
+        let stored = "{\n  \"a\": 1,\n  \"b\": 2,\n  \"c\": 3\n}";
+        let re_executed = "{\n  \"a\": 1,\n  \"b\": 4,\n  \"c\": 3\n}";
+        assert_eq!(diff_lines(stored, stored), "");
+        assert_eq!(
+            diff_lines(stored, re_executed),
+            "  {\n    \"a\": 1,\n-   \"b\": 2,\n+   \"b\": 4,\n    \"c\": 3\n  }\n"
+        );
+    }
+
+    #[test]
+    fn test_state_diff_entries_ignore_order() {
+        let storage_diffs =
+            |entries: Vec<StorageEntry>| vec![ContractStorageDiffItem { address: Felt::ONE, storage_entries: entries }];
+        let a = StateDiff {
+            storage_diffs: storage_diffs(vec![
+                StorageEntry { key: Felt::ONE, value: Felt::TWO },
+                StorageEntry { key: Felt::TWO, value: Felt::THREE },
+            ]),
+            deployed_contracts: vec![DeployedContractItem { address: Felt::TWO, class_hash: Felt::ONE }],
+            ..Default::default()
+        };
+        let b = StateDiff {
+            storage_diffs: storage_diffs(vec![
+                StorageEntry { key: Felt::TWO, value: Felt::THREE },
+                StorageEntry { key: Felt::ONE, value: Felt::TWO },
+            ]),
+            ..a.clone()
+        };
+        assert_eq!(state_diff_entries(&a), state_diff_entries(&b));
+
+        let c = StateDiff { deployed_contracts: vec![], ..a.clone() };
+        let (a, c) = (state_diff_entries(&a), state_diff_entries(&c));
+        let divergent_keys: BTreeSet<_> = [class_hash_key(Felt::TWO)].into();
+        assert_eq!(diff_entries(&a, &c, &divergent_keys), "- class_hash 0x2 = 0x1 (deployed)\n");
+    }
+}
@@ -52,6 +52,7 @@ fn map_gateway_error(err: SequencerError) -> SubmitTransactionError {
             GWErrCode::DuplicatedTransaction => rejected(DuplicatedTransaction, e.message),
             GWErrCode::InvalidContractClassVersion => rejected(InvalidContractClassVersion, e.message),
             GWErrCode::RateLimited => rejected(RateLimited, e.message),
+            GWErrCode::TransactionNotPermitted => rejected(TransactionNotPermitted, e.message),
 
             // These should not really happen?
             GWErrCode::BlockNotFound
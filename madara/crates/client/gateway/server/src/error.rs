@@ -74,6 +74,7 @@ fn map_rejected_tx_error(value: RejectedTransactionError) -> StarknetError {
         E::DuplicatedTransaction => DuplicatedTransaction,
         E::InvalidContractClassVersion => InvalidContractClassVersion,
         E::RateLimited => RateLimited,
+        E::TransactionNotPermitted => TransactionNotPermitted,
     };
     StarknetError { code, message: value.message.unwrap_or_default().into() }
 }
@@ -1,9 +1,10 @@
 //! TODO: this should be in the backend
 use mp_block::header::GasPrices;
+use mp_chain_config::ETH_DECIMALS;
 use mp_oracle::Oracle;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 #[derive(Clone)]
 pub struct GasPriceProvider {
@@ -20,6 +21,10 @@ pub struct GasPriceProvider {
     strk_gas_price_sync_enabled: Arc<AtomicBool>,
     strk_data_gas_price_sync_enabled: Arc<AtomicBool>,
     pub oracle_provider: Option<Arc<dyn Oracle>>,
+    /// Oracle prices older than this are rejected, and the previous STRK gas prices are kept.
+    pub oracle_max_age: Option<Duration>,
+    /// Decimals of the native fee token the STRK gas prices are expressed in.
+    pub native_fee_token_decimals: u8,
 }
 
 impl GasPriceProvider {
@@ -32,6 +37,8 @@ impl GasPriceProvider {
             strk_gas_price_sync_enabled: Arc::new(AtomicBool::new(true)),
             strk_data_gas_price_sync_enabled: Arc::new(AtomicBool::new(true)),
             oracle_provider: None,
+            oracle_max_age: None,
+            native_fee_token_decimals: ETH_DECIMALS,
         }
     }
 
@@ -46,6 +53,16 @@ impl GasPriceProvider {
         self
     }
 
+    pub fn set_oracle_max_age(&mut self, max_age: Option<Duration>) -> &mut Self {
+        self.oracle_max_age = max_age;
+        self
+    }
+
+    pub fn set_native_fee_token_decimals(&mut self, decimals: u8) -> &mut Self {
+        self.native_fee_token_decimals = decimals;
+        self
+    }
+
     pub fn set_gas_prices(&self, new_prices: GasPrices) {
         self.update_eth_l1_gas_price(new_prices.eth_l1_gas_price);
         self.update_strk_l1_gas_price(new_prices.strk_l1_gas_price);
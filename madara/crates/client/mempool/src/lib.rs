@@ -376,7 +376,7 @@ pub(crate) mod tests {
     #[rstest::fixture]
     async fn backend() -> Arc<mc_db::MadaraBackend> {
         let backend = mc_db::MadaraBackend::open_for_testing(Arc::new(mp_chain_config::ChainConfig::madara_test()));
-        let mut genesis = mc_devnet::ChainGenesisDescription::base_config().unwrap();
+        let mut genesis = mc_devnet::ChainGenesisDescription::base_config(backend.chain_config()).unwrap();
         genesis.add_devnet_contracts(10).unwrap();
         genesis.build_and_store(&backend).await.unwrap();
         backend
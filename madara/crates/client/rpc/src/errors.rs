@@ -107,6 +107,14 @@ pub enum StarknetRpcApiError {
     ProofLimitExceeded { kind: StorageProofLimit, limit: usize, got: usize },
     #[error("Cannot create a storage proof for a block that old")]
     CannotMakeProofOnOldBlock,
+    #[error("The state of this block has been pruned")]
+    BlockPruned { block_n: u64, oldest_available: u64 },
+    #[error("Transaction not permitted by the chain policy")]
+    TransactionNotPermitted { error: Cow<'static, str> },
+    #[error("L2 to L1 message not found")]
+    MessageToL1NotFound,
+    #[error("Invalid params")]
+    InvalidParams { error: Cow<'static, str> },
 }
 
 impl StarknetRpcApiError {
@@ -181,6 +189,10 @@ impl From<&StarknetRpcApiError> for i32 {
             StarknetRpcApiError::UnimplementedMethod => 501,
             StarknetRpcApiError::ProofLimitExceeded { .. } => 10000,
             StarknetRpcApiError::CannotMakeProofOnOldBlock => 10001,
+            StarknetRpcApiError::BlockPruned { .. } => 10002,
+            StarknetRpcApiError::TransactionNotPermitted { .. } => 10003,
+            StarknetRpcApiError::MessageToL1NotFound => 10004,
+            StarknetRpcApiError::InvalidParams { .. } => jsonrpsee::types::ErrorCode::InvalidParams.code(),
         }
     }
 }
@@ -196,6 +208,9 @@ impl StarknetRpcApiError {
             StarknetRpcApiError::ProofLimitExceeded { kind, limit, got } => {
                 Some(json!({ "kind": kind, "limit": limit, "got": got }))
             }
+            StarknetRpcApiError::BlockPruned { block_n, oldest_available } => {
+                Some(json!({ "block_number": block_n, "oldest_available_block_number": oldest_available }))
+            }
             StarknetRpcApiError::ErrUnexpectedError { error }
             | StarknetRpcApiError::ValidationFailure { error }
             | StarknetRpcApiError::ContractNotFound { error }
@@ -211,7 +226,9 @@ impl StarknetRpcApiError {
             | StarknetRpcApiError::DuplicateTxn { error }
             | StarknetRpcApiError::CompiledClassHashMismatch { error }
             | StarknetRpcApiError::UnsupportedTxnVersion { error }
-            | StarknetRpcApiError::UnsupportedContractClassVersion { error } => {
+            | StarknetRpcApiError::UnsupportedContractClassVersion { error }
+            | StarknetRpcApiError::TransactionNotPermitted { error }
+            | StarknetRpcApiError::InvalidParams { error } => {
                 if error.is_empty() {
                     None
                 } else {
@@ -231,14 +248,18 @@ impl StarknetRpcApiError {
             | StarknetRpcApiError::ContractError
             | StarknetRpcApiError::InternalServerError
             | StarknetRpcApiError::UnimplementedMethod
-            | StarknetRpcApiError::CannotMakeProofOnOldBlock => None,
+            | StarknetRpcApiError::CannotMakeProofOnOldBlock
+            | StarknetRpcApiError::MessageToL1NotFound => None,
         }
     }
 }
 
 impl From<mc_exec::Error> for StarknetRpcApiError {
     fn from(err: mc_exec::Error) -> Self {
-        Self::TxnExecutionError { tx_index: 0, error: format!("{:#}", err) }
+        match err {
+            mc_exec::Error::InvalidStateOverride(error) => Self::InvalidParams { error: error.into() },
+            err => Self::TxnExecutionError { tx_index: 0, error: format!("{:#}", err) },
+        }
     }
 }
 
@@ -282,6 +303,9 @@ impl From<StarknetError> for StarknetRpcApiError {
             StarknetErrorCode::OutOfRangeBlockHash => StarknetRpcApiError::InvalidBlockHash,
             StarknetErrorCode::OutOfRangeTransactionHash => StarknetRpcApiError::InvalidTxnHash,
             StarknetErrorCode::InvalidTransactionVersion => StarknetRpcApiError::unsupported_txn_version(),
+            StarknetErrorCode::TransactionNotPermitted => {
+                StarknetRpcApiError::TransactionNotPermitted { error: err.message.into() }
+            }
             _ => StarknetRpcApiError::ErrUnexpectedError { error: err.message.into() },
         }
     }
@@ -289,8 +313,15 @@ impl From<StarknetError> for StarknetRpcApiError {
 
 impl From<MadaraStorageError> for StarknetRpcApiError {
     fn from(err: MadaraStorageError) -> Self {
-        display_internal_server_error(err);
-        StarknetRpcApiError::InternalServerError
+        match err {
+            MadaraStorageError::BlockPruned { block_n, oldest_available } => {
+                StarknetRpcApiError::BlockPruned { block_n, oldest_available }
+            }
+            err => {
+                display_internal_server_error(err);
+                StarknetRpcApiError::InternalServerError
+            }
+        }
     }
 }
 
@@ -354,6 +385,7 @@ impl From<RejectedTransactionError> for StarknetRpcApiError {
             E::DuplicatedTransaction => DuplicateTxn { error },
             E::InvalidContractClassVersion => UnsupportedContractClassVersion { error },
             E::RateLimited => ErrUnexpectedError { error },
+            E::TransactionNotPermitted => TransactionNotPermitted { error },
         }
     }
 }
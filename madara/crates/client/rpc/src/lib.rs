@@ -11,12 +11,14 @@ pub mod utils;
 pub mod versions;
 
 use jsonrpsee::RpcModule;
+use mc_block_production::BlockProductionHandle;
 use mc_db::db_block_id::DbBlockIdResolvable;
 use mc_db::MadaraBackend;
-use mc_submit_tx::SubmitTransaction;
+use mc_submit_tx::{SubmitTransaction, SubmitValidatedTransaction};
 use mp_block::{BlockId, BlockTag, MadaraMaybePendingBlock, MadaraMaybePendingBlockInfo};
 use mp_chain_config::ChainConfig;
 use mp_convert::ToFelt;
+use mp_rpc::BroadcastedTxn;
 use mp_utils::service::ServiceContext;
 use starknet_types_core::felt::Felt;
 use std::sync::Arc;
@@ -47,6 +49,10 @@ pub struct Starknet {
     backend: Arc<MadaraBackend>,
     pub(crate) add_transaction_provider: Arc<dyn SubmitTransaction>,
     storage_proof_config: StorageProofConfig,
+    /// Only set on sequencers.
+    pub(crate) block_production_handle: Option<BlockProductionHandle>,
+    /// Only set on sequencers, receives the transactions restored by a chain revert.
+    pub(crate) mempool: Option<Arc<dyn SubmitValidatedTransaction>>,
     pub ctx: ServiceContext,
 }
 
@@ -57,7 +63,24 @@ impl Starknet {
         storage_proof_config: StorageProofConfig,
         ctx: ServiceContext,
     ) -> Self {
-        Self { backend, add_transaction_provider, storage_proof_config, ctx }
+        Self {
+            backend,
+            add_transaction_provider,
+            storage_proof_config,
+            block_production_handle: None,
+            mempool: None,
+            ctx,
+        }
+    }
+
+    pub fn with_block_production_handle(mut self, block_production_handle: BlockProductionHandle) -> Self {
+        self.block_production_handle = Some(block_production_handle);
+        self
+    }
+
+    pub fn with_mempool(mut self, mempool: Arc<dyn SubmitValidatedTransaction>) -> Self {
+        self.mempool = Some(mempool);
+        self
     }
 
     pub fn clone_backend(&self) -> Arc<MadaraBackend> {
@@ -92,6 +115,32 @@ impl Starknet {
             .ok_or(StarknetRpcApiError::BlockNotFound)
     }
 
+    /// Returns [`StarknetRpcApiError::BlockPruned`] when the state of this block has been pruned from the database.
+    pub fn ensure_state_not_pruned(&self, block_id: &impl DbBlockIdResolvable) -> StarknetRpcResult<()> {
+        let Some(block_n) =
+            self.backend.get_block_n(block_id).or_internal_server_error("Error getting block number")?
+        else {
+            return Ok(());
+        };
+        Ok(self.backend.check_state_not_pruned(block_n)?)
+    }
+
+    /// Returns [`StarknetRpcApiError::UnsupportedTxnVersion`] when a transaction pays its fees in wei on a chain where
+    /// the native token is the only fee token.
+    pub fn ensure_fee_token_supported(&self, transactions: &[BroadcastedTxn]) -> StarknetRpcResult<()> {
+        let chain_config = self.backend.chain_config();
+        if chain_config.supports_legacy_fee_payment() || transactions.iter().all(|tx| tx.resource_bounds().is_some()) {
+            return Ok(());
+        }
+        Err(StarknetRpcApiError::UnsupportedTxnVersion {
+            error: format!(
+                "Fees can only be paid in {} on this chain, which requires V3 transactions",
+                chain_config.native_fee_token.symbol
+            )
+            .into(),
+        })
+    }
+
     pub fn chain_id(&self) -> Felt {
         self.backend.chain_config().chain_id.clone().to_felt()
     }
@@ -118,6 +167,7 @@ pub fn rpc_api_user(starknet: &Starknet) -> anyhow::Result<RpcModule<()>> {
     rpc_api.merge(versions::user::v0_7_1::StarknetWriteRpcApiV0_7_1Server::into_rpc(starknet.clone()))?;
     rpc_api.merge(versions::user::v0_7_1::StarknetTraceRpcApiV0_7_1Server::into_rpc(starknet.clone()))?;
     rpc_api.merge(versions::user::v0_8_0::StarknetWsRpcApiV0_8_0Server::into_rpc(starknet.clone()))?;
+    rpc_api.merge(versions::user::v0_8_0::MadaraMessagingRpcApiV0_8_0Server::into_rpc(starknet.clone()))?;
 
     Ok(rpc_api)
 }
@@ -128,6 +178,8 @@ pub fn rpc_api_admin(starknet: &Starknet) -> anyhow::Result<RpcModule<()>> {
     rpc_api.merge(versions::admin::v0_1_0::MadaraWriteRpcApiV0_1_0Server::into_rpc(starknet.clone()))?;
     rpc_api.merge(versions::admin::v0_1_0::MadaraStatusRpcApiV0_1_0Server::into_rpc(starknet.clone()))?;
     rpc_api.merge(versions::admin::v0_1_0::MadaraServicesRpcApiV0_1_0Server::into_rpc(starknet.clone()))?;
+    rpc_api.merge(versions::admin::v0_1_0::MadaraDebugRpcApiV0_1_0Server::into_rpc(starknet.clone()))?;
+    rpc_api.merge(versions::admin::v0_1_0::MadaraBlockProductionRpcApiV0_1_0Server::into_rpc(starknet.clone()))?;
 
     Ok(rpc_api)
 }
@@ -64,8 +64,11 @@ impl SubmitTransaction for TestTransactionProvider {
 
 #[fixture]
 pub fn rpc_test_setup() -> (Arc<MadaraBackend>, Starknet) {
-    let chain_config = std::sync::Arc::new(mp_chain_config::ChainConfig::madara_test());
-    let backend = mc_db::MadaraBackend::open_for_testing(chain_config);
+    rpc_test_setup_with_chain_config(mp_chain_config::ChainConfig::madara_test())
+}
+
+pub fn rpc_test_setup_with_chain_config(chain_config: mp_chain_config::ChainConfig) -> (Arc<MadaraBackend>, Starknet) {
+    let backend = mc_db::MadaraBackend::open_for_testing(Arc::new(chain_config));
     let validation = mc_submit_tx::TransactionValidatorConfig { disable_validation: true, disable_fee: false };
     let mempool = std::sync::Arc::new(mc_mempool::Mempool::new(
         std::sync::Arc::clone(&backend),
@@ -82,6 +85,19 @@ pub fn rpc_test_setup() -> (Arc<MadaraBackend>, Starknet) {
     (backend, rpc)
 }
 
+/// A devnet genesis block, with the fee tokens and `n_accounts` funded accounts deployed. Used to test the rpcs
+/// which execute contracts.
+pub async fn devnet_rpc_test_setup(n_accounts: u64) -> (mc_devnet::DevnetKeys, Starknet) {
+    let chain_config = mp_chain_config::ChainConfig::madara_devnet();
+    let mut genesis = mc_devnet::ChainGenesisDescription::base_config(&chain_config).unwrap();
+    let accounts = genesis.add_devnet_contracts(n_accounts).unwrap();
+
+    let (backend, rpc) = rpc_test_setup_with_chain_config(chain_config);
+    genesis.build_and_store(&backend).await.unwrap();
+
+    (accounts, rpc)
+}
+
 // This sample chain is only used to test get tx / get block rpcs.
 pub struct SampleChainForBlockGetters {
     pub block_hashes: Vec<Felt>,
@@ -227,6 +243,8 @@ pub fn make_sample_chain_for_block_getters(backend: &MadaraBackend) -> SampleCha
                                 strk_l1_gas_price: 12,
                                 eth_l1_data_gas_price: 44,
                                 strk_l1_data_gas_price: 52,
+                                eth_l2_gas_price: 1,
+                                strk_l2_gas_price: 1,
                             },
                             l1_da_mode: L1DataAvailabilityMode::Blob,
                         },
@@ -402,6 +420,27 @@ fn defaut_execution_resources() -> mp_rpc::ExecutionResources {
     }
 }
 
+/// Traces to store for a block of the sample chains, one per transaction hash. Each trace has a distinct number of
+/// steps, so they cannot be mistaken for one another or for the result of re-executing the block.
+pub fn sample_block_traces(tx_hashes: &[Felt]) -> Vec<mp_rpc::TraceBlockTransactionsResult> {
+    tx_hashes
+        .iter()
+        .enumerate()
+        .map(|(i, transaction_hash)| mp_rpc::TraceBlockTransactionsResult {
+            trace_root: mp_rpc::TransactionTrace::Declare(mp_rpc::DeclareTransactionTrace {
+                execution_resources: mp_rpc::ExecutionResources {
+                    steps: 1000 + i as u64,
+                    ..defaut_execution_resources()
+                },
+                fee_transfer_invocation: None,
+                state_diff: None,
+                validate_invocation: None,
+            }),
+            transaction_hash: *transaction_hash,
+        })
+        .collect()
+}
+
 // This sample chain is used for every rpcs that query info gotten from state updates.
 pub struct SampleChainForStateUpdates {
     pub block_hashes: Vec<Felt>,
@@ -1,8 +1,13 @@
 use jsonrpsee::core::RpcResult;
 use m_proc_macros::versioned_rpc;
-use mp_rpc::{admin::BroadcastedDeclareTxnV0, ClassAndTxnHash};
+use mc_block_production::{BlockProductionConfigUpdate, BlockProductionStatus};
+use mp_block::BlockId;
+use mp_chain_config::TransactionPolicy;
+use mp_rpc::admin::{BroadcastedDeclareTxnV0, DebugTransactionTrace};
+use mp_rpc::{BroadcastedTxn, ClassAndTxnHash, SimulationFlag};
 use mp_utils::service::{MadaraServiceId, MadaraServiceStatus};
 use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -21,6 +26,27 @@ pub trait MadaraWriteRpcApi {
         &self,
         declare_v0_transaction: BroadcastedDeclareTxnV0,
     ) -> RpcResult<ClassAndTxnHash>;
+
+    /// Reverts the chain to a block, deleting every block above it and rolling back the global state. The
+    /// block production, L1 sync and L2 sync services must be stopped beforehand. The L1 messages of the reverted
+    /// l1 handler transactions are processed again once the L1 sync service is restarted.
+    ///
+    /// # Returns
+    ///
+    /// * The hashes of the reverted transactions that were saved back to the mempool. They are also added to the
+    ///   running mempool of a sequencer.
+    #[method(name = "revertTo")]
+    async fn revert_to(&self, block_number: u64) -> RpcResult<Vec<Felt>>;
+
+    /// Replaces the transaction policy of the chain, which is initially loaded from the chain config. New
+    /// transactions are validated against the new policy, transactions already in the mempool are kept. The policy
+    /// is not persisted, the chain config policy is used again when the node restarts.
+    #[method(name = "setTransactionPolicy")]
+    async fn set_transaction_policy(&self, policy: TransactionPolicy) -> RpcResult<()>;
+
+    /// Returns the transaction policy currently enforced by the node.
+    #[method(name = "getTransactionPolicy")]
+    async fn get_transaction_policy(&self) -> RpcResult<TransactionPolicy>;
 }
 
 #[versioned_rpc("V0_1_0", "madara")]
@@ -60,3 +86,47 @@ pub trait MadaraServicesRpcApi {
     #[method(name = "service")]
     async fn service(&self, service: Vec<MadaraServiceId>, status: ServiceRequest) -> RpcResult<MadaraServiceStatus>;
 }
+
+#[versioned_rpc("V0_1_0", "madara")]
+pub trait MadaraBlockProductionRpcApi {
+    /// Stops executing transactions and closing blocks, while still accepting transactions into the mempool. The
+    /// pause is not persisted: block production runs again when the node restarts, and when another sequencer takes
+    /// over the leader lease.
+    #[method(name = "pauseBlockProduction")]
+    async fn pause_block_production(&self) -> RpcResult<BlockProductionStatus>;
+
+    /// Resumes block production after `pauseBlockProduction`.
+    #[method(name = "resumeBlockProduction")]
+    async fn resume_block_production(&self) -> RpcResult<BlockProductionStatus>;
+
+    /// Changes the block time, pending block update time or empty block production. Durations are given as strings
+    /// such as `"500ms"` or `"6s"`. The changes are not persisted, the chain config values are used again when block
+    /// production restarts.
+    #[method(name = "setBlockProductionConfig")]
+    async fn set_block_production_config(
+        &self,
+        update: BlockProductionConfigUpdate,
+    ) -> RpcResult<BlockProductionStatus>;
+
+    /// Returns whether block production is paused, and its current config.
+    #[method(name = "getBlockProductionStatus")]
+    async fn get_block_production_status(&self) -> RpcResult<BlockProductionStatus>;
+}
+
+#[versioned_rpc("V0_1_0", "madara")]
+pub trait MadaraDebugRpcApi {
+    /// Re-executes a transaction and returns its full call tree, with the storage accesses, gas consumption and
+    /// decoded revert reason of every call.
+    #[method(name = "debugTraceTransaction")]
+    async fn debug_trace_transaction(&self, transaction_hash: Felt) -> RpcResult<DebugTransactionTrace>;
+
+    /// Executes a transaction on top of a block without submitting it, and returns its full call tree as in
+    /// `debugTraceTransaction`.
+    #[method(name = "debugTraceCall")]
+    async fn debug_trace_call(
+        &self,
+        block_id: BlockId,
+        transaction: BroadcastedTxn,
+        simulation_flags: Vec<SimulationFlag>,
+    ) -> RpcResult<DebugTransactionTrace>;
+}
@@ -0,0 +1,44 @@
+use crate::{versions::admin::v0_1_0::MadaraBlockProductionRpcApiV0_1_0Server, Starknet};
+use jsonrpsee::core::{async_trait, RpcResult};
+use jsonrpsee::types::{ErrorCode, ErrorObject, ErrorObjectOwned};
+use mc_block_production::{
+    BlockProductionConfigUpdate, BlockProductionHandle, BlockProductionStatus, ExecutorCommandError,
+};
+
+impl Starknet {
+    fn block_production_handle(&self) -> RpcResult<&BlockProductionHandle> {
+        self.block_production_handle.as_ref().ok_or_else(|| {
+            ErrorObject::owned(ErrorCode::InvalidRequest.code(), "This node is not a sequencer", Some(()))
+        })
+    }
+}
+
+fn to_rpc_error(err: ExecutorCommandError) -> ErrorObjectOwned {
+    let code = match err {
+        ExecutorCommandError::ChannelClosed => ErrorCode::InvalidRequest,
+        ExecutorCommandError::InvalidConfig(_) => ErrorCode::InvalidParams,
+    };
+    ErrorObject::owned(code.code(), err.to_string(), Some(()))
+}
+
+#[async_trait]
+impl MadaraBlockProductionRpcApiV0_1_0Server for Starknet {
+    async fn pause_block_production(&self) -> RpcResult<BlockProductionStatus> {
+        self.block_production_handle()?.pause().await.map_err(to_rpc_error)
+    }
+
+    async fn resume_block_production(&self) -> RpcResult<BlockProductionStatus> {
+        self.block_production_handle()?.resume().await.map_err(to_rpc_error)
+    }
+
+    async fn set_block_production_config(
+        &self,
+        update: BlockProductionConfigUpdate,
+    ) -> RpcResult<BlockProductionStatus> {
+        self.block_production_handle()?.update_config(update).await.map_err(to_rpc_error)
+    }
+
+    async fn get_block_production_status(&self) -> RpcResult<BlockProductionStatus> {
+        self.block_production_handle()?.status().await.map_err(to_rpc_error)
+    }
+}
@@ -0,0 +1,95 @@
+use crate::errors::{StarknetRpcApiError, StarknetRpcResult};
+use crate::utils::{tx_api_to_blockifier, OptionExt, ResultExt};
+use crate::versions::admin::v0_1_0::MadaraDebugRpcApiV0_1_0Server;
+use crate::versions::user::v0_7_1::methods::trace::trace_transaction::EXECUTION_UNSUPPORTED_BELOW_VERSION;
+use crate::Starknet;
+use blockifier::transaction::account_transaction::ExecutionFlags;
+use jsonrpsee::core::{async_trait, RpcResult};
+use mc_exec::transaction::to_blockifier_transaction;
+use mc_exec::ExecutionContext;
+use mp_block::BlockId;
+use mp_rpc::admin::DebugTransactionTrace;
+use mp_rpc::{BroadcastedTxn, SimulationFlag};
+use mp_transactions::BroadcastedTransactionExt;
+use starknet_api::transaction::TransactionHash;
+use starknet_types_core::felt::Felt;
+use std::sync::Arc;
+
+#[async_trait]
+impl MadaraDebugRpcApiV0_1_0Server for Starknet {
+    async fn debug_trace_transaction(&self, transaction_hash: Felt) -> RpcResult<DebugTransactionTrace> {
+        Ok(debug_trace_transaction(self, transaction_hash).await?)
+    }
+
+    async fn debug_trace_call(
+        &self,
+        block_id: BlockId,
+        transaction: BroadcastedTxn,
+        simulation_flags: Vec<SimulationFlag>,
+    ) -> RpcResult<DebugTransactionTrace> {
+        Ok(debug_trace_call(self, block_id, transaction, simulation_flags).await?)
+    }
+}
+
+pub async fn debug_trace_transaction(
+    starknet: &Starknet,
+    transaction_hash: Felt,
+) -> StarknetRpcResult<DebugTransactionTrace> {
+    let (block, tx_index) = starknet
+        .backend
+        .find_tx_hash_block(&transaction_hash)
+        .or_internal_server_error("Error while getting block from tx hash")?
+        .ok_or(StarknetRpcApiError::TxnHashNotFound)?;
+
+    if block.info.protocol_version() < &EXECUTION_UNSUPPORTED_BELOW_VERSION {
+        return Err(StarknetRpcApiError::unsupported_txn_version());
+    }
+
+    let exec_context = ExecutionContext::new_at_block_start(Arc::clone(&starknet.backend), &block.info)?;
+
+    let mut block_txs =
+        Iterator::zip(block.inner.transactions.into_iter(), block.info.tx_hashes()).map(|(tx, hash)| {
+            to_blockifier_transaction(starknet.clone_backend(), block.info.block_id(), tx, &TransactionHash(*hash))
+                .or_internal_server_error("Failed to convert transaction to blockifier format")
+        });
+
+    let transactions_before: Vec<_> = block_txs.by_ref().take(tx_index.0 as usize).collect::<Result<_, _>>()?;
+    let transaction =
+        block_txs.next().ok_or_internal_server_error("There should be at least one transaction in the block")??;
+
+    exec_context
+        .debug_trace_transactions(transactions_before, [transaction])?
+        .pop()
+        .ok_or_internal_server_error("No debug trace returned for the transaction")
+}
+
+pub async fn debug_trace_call(
+    starknet: &Starknet,
+    block_id: BlockId,
+    transaction: BroadcastedTxn,
+    simulation_flags: Vec<SimulationFlag>,
+) -> StarknetRpcResult<DebugTransactionTrace> {
+    let block_info = starknet.get_block_info(&block_id)?;
+    let starknet_version = *block_info.protocol_version();
+
+    if starknet_version < EXECUTION_UNSUPPORTED_BELOW_VERSION {
+        return Err(StarknetRpcApiError::unsupported_txn_version());
+    }
+    let exec_context = ExecutionContext::new_at_block_end(Arc::clone(&starknet.backend), &block_info)?;
+
+    let charge_fee = !simulation_flags.contains(&SimulationFlag::SkipFeeCharge);
+    let validate = !simulation_flags.contains(&SimulationFlag::SkipValidate);
+    let only_query = transaction.is_query();
+
+    let (api_tx, _) = transaction
+        .into_starknet_api(starknet.chain_id(), starknet_version)
+        .or_internal_server_error("Failed to convert broadcasted transaction to blockifier")?;
+    let execution_flags = ExecutionFlags { only_query, charge_fee, validate, strict_nonce_check: true };
+    let transaction = tx_api_to_blockifier(api_tx, execution_flags)
+        .or_internal_server_error("Failed to convert broadcasted transaction to blockifier")?;
+
+    exec_context
+        .debug_trace_transactions([], [transaction])?
+        .pop()
+        .ok_or_internal_server_error("No debug trace returned for the transaction")
+}
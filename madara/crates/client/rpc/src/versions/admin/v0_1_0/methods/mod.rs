@@ -1,3 +1,5 @@
+pub mod block_production;
+pub mod debug;
 pub mod services;
 pub mod status;
 pub mod write;
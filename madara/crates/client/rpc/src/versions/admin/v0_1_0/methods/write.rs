@@ -1,6 +1,11 @@
+use crate::utils::display_internal_server_error;
 use crate::{versions::admin::v0_1_0::MadaraWriteRpcApiV0_1_0Server, Starknet, StarknetRpcApiError};
 use jsonrpsee::core::{async_trait, RpcResult};
+use mc_db::MadaraStorageError;
+use mp_chain_config::TransactionPolicy;
 use mp_rpc::{admin::BroadcastedDeclareTxnV0, ClassAndTxnHash};
+use mp_utils::service::{MadaraServiceId, MadaraServiceStatus};
+use starknet_types_core::felt::Felt;
 
 #[async_trait]
 impl MadaraWriteRpcApiV0_1_0Server for Starknet {
@@ -23,4 +28,76 @@ impl MadaraWriteRpcApiV0_1_0Server for Starknet {
             .await
             .map_err(StarknetRpcApiError::from)?)
     }
+
+    /// Revert the chain to a block
+    ///
+    /// # Arguments
+    ///
+    /// * `block_number` - the block which becomes the new latest block
+    ///
+    /// # Returns
+    ///
+    /// * `restored_transactions` - the hashes of the reverted transactions saved to the mempool
+    ///
+    /// The restored transactions are also given to the running mempool so they are included again without a restart.
+    async fn revert_to(&self, block_number: u64) -> RpcResult<Vec<Felt>> {
+        for svc in [MadaraServiceId::BlockProduction, MadaraServiceId::L1Sync, MadaraServiceId::L2Sync] {
+            if self.ctx.service_status(svc) == MadaraServiceStatus::On {
+                return Err(jsonrpsee::types::ErrorObject::owned(
+                    jsonrpsee::types::ErrorCode::InvalidRequest.code(),
+                    format!("The {svc} service must be stopped before reverting the chain"),
+                    Some(()),
+                ));
+            }
+        }
+
+        let backend = self.clone_backend();
+        let res = match tokio::task::spawn_blocking(move || backend.revert_to(block_number)).await {
+            Ok(res) => res,
+            Err(err) => Err(err.into()),
+        };
+
+        let restored_txs = res.map_err(|err| match err.downcast_ref::<MadaraStorageError>() {
+            Some(err @ MadaraStorageError::InvalidRevertTarget { .. }) => jsonrpsee::types::ErrorObject::owned(
+                jsonrpsee::types::ErrorCode::InvalidParams.code(),
+                err.to_string(),
+                Some(()),
+            ),
+            _ => {
+                display_internal_server_error(format!("Reverting the chain: {err:#}"));
+                StarknetRpcApiError::InternalServerError.into()
+            }
+        })?;
+
+        let restored_hashes = restored_txs.iter().map(|tx| tx.tx_hash).collect();
+        if let Some(mempool) = &self.mempool {
+            for tx in restored_txs {
+                let tx_hash = tx.tx_hash;
+                if let Err(err) = mempool.submit_validated_transaction(tx).await {
+                    tracing::warn!("Could not restore reverted transaction {tx_hash:#x} to the mempool: {err:#}");
+                }
+            }
+        }
+        Ok(restored_hashes)
+    }
+
+    /// Replace the transaction policy
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - the allow and deny lists enforced on new transactions
+    async fn set_transaction_policy(&self, policy: TransactionPolicy) -> RpcResult<()> {
+        tracing::info!("📜 Updating the transaction policy");
+        self.backend.set_transaction_policy(policy);
+        Ok(())
+    }
+
+    /// Get the transaction policy
+    ///
+    /// # Returns
+    ///
+    /// * `policy` - the allow and deny lists enforced on new transactions
+    async fn get_transaction_policy(&self) -> RpcResult<TransactionPolicy> {
+        Ok(self.backend.transaction_policy().as_ref().clone())
+    }
 }
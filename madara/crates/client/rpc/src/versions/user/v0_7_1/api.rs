@@ -6,8 +6,8 @@ use mp_rpc::{
     BroadcastedInvokeTxn, BroadcastedTxn, ClassAndTxnHash, ContractAndTxnHash, EventFilterWithPageRequest, EventsChunk,
     FeeEstimate, FunctionCall, MaybeDeprecatedContractClass, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs,
     MaybePendingStateUpdate, MsgFromL1, SimulateTransactionsResult, SimulationFlag, SimulationFlagForEstimateFee,
-    StarknetGetBlockWithTxsAndReceiptsResult, SyncingStatus, TraceBlockTransactionsResult, TraceTransactionResult,
-    TxnFinalityAndExecutionStatus, TxnReceiptWithBlockInfo, TxnWithHash,
+    StarknetGetBlockWithTxsAndReceiptsResult, StateOverride, SyncingStatus, TraceBlockTransactionsResult,
+    TraceTransactionResult, TxnFinalityAndExecutionStatus, TxnReceiptWithBlockInfo, TxnWithHash,
 };
 use starknet_types_core::felt::Felt;
 
@@ -52,9 +52,14 @@ pub trait StarknetReadRpcApi {
     #[method(name = "blockHashAndNumber", and_versions = ["V0_8_0"])]
     fn block_hash_and_number(&self) -> RpcResult<BlockHashAndNumber>;
 
-    /// Call a contract function at a given block id
+    /// Call a contract function at a given block id. Madara also accepts optional state overrides.
     #[method(name = "call", and_versions = ["V0_8_0"])]
-    fn call(&self, request: FunctionCall, block_id: BlockId) -> RpcResult<Vec<Felt>>;
+    fn call(
+        &self,
+        request: FunctionCall,
+        block_id: BlockId,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Vec<Felt>>;
 
     /// Get the chain id
     #[method(name = "chainId", and_versions = ["V0_8_0"])]
@@ -64,13 +69,14 @@ pub trait StarknetReadRpcApi {
     #[method(name = "getBlockTransactionCount", and_versions = ["V0_8_0"])]
     fn get_block_transaction_count(&self, block_id: BlockId) -> RpcResult<u128>;
 
-    /// Estimate the fee associated with transaction
+    /// Estimate the fee associated with transaction. Madara also accepts optional state overrides.
     #[method(name = "estimateFee", and_versions = ["V0_8_0"])]
     async fn estimate_fee(
         &self,
         request: Vec<BroadcastedTxn>,
         simulation_flags: Vec<SimulationFlagForEstimateFee>,
         block_id: BlockId,
+        state_override: Option<StateOverride>,
     ) -> RpcResult<Vec<FeeEstimate>>;
 
     /// Estimate the L2 fee of a message sent on L1
@@ -141,13 +147,15 @@ pub trait StarknetReadRpcApi {
 
 #[versioned_rpc("V0_7_1", "starknet")]
 pub trait StarknetTraceRpcApi {
-    /// Returns the execution trace of a transaction by simulating it in the runtime.
+    /// Returns the execution trace of a transaction by simulating it in the runtime. Madara also accepts optional
+    /// state overrides.
     #[method(name = "simulateTransactions", and_versions = ["V0_8_0"])]
     async fn simulate_transactions(
         &self,
         block_id: BlockId,
         transactions: Vec<BroadcastedTxn>,
         simulation_flags: Vec<SimulationFlag>,
+        state_override: Option<StateOverride>,
     ) -> RpcResult<Vec<SimulateTransactionsResult>>;
 
     #[method(name = "traceBlockTransactions", and_versions = ["V0_8_0"])]
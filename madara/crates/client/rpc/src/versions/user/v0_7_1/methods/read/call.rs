@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use mc_exec::ExecutionContext;
 use mp_block::BlockId;
-use mp_rpc::FunctionCall;
+use mp_rpc::{FunctionCall, StateOverride};
 use starknet_types_core::felt::Felt;
 
 use crate::errors::StarknetRpcApiError;
@@ -18,6 +18,8 @@ use crate::Starknet;
 ///   contract address, function signature, and arguments.
 /// * `block_id` - The identifier of the block used to reference the state or call the transaction
 ///   on. This can be the hash of the block, its number (height), or a specific block tag.
+/// * `state_override` - Madara extension: storage, nonce, class hash and fee token balance values to
+///   use instead of the ones in the block state.
 ///
 /// ### Returns
 ///
@@ -30,10 +32,18 @@ use crate::Starknet;
 /// * `CONTRACT_NOT_FOUND` - If the specified contract address does not exist.
 /// * `CONTRACT_ERROR` - If there is an error with the contract or the function call.
 /// * `BLOCK_NOT_FOUND` - If the specified block does not exist in the blockchain.
-pub fn call(starknet: &Starknet, request: FunctionCall, block_id: BlockId) -> StarknetRpcResult<Vec<Felt>> {
+/// * `INVALID_PARAMS` - If the state override has an invalid contract address or storage key.
+pub fn call(
+    starknet: &Starknet,
+    request: FunctionCall,
+    block_id: BlockId,
+    state_override: Option<StateOverride>,
+) -> StarknetRpcResult<Vec<Felt>> {
     let block_info = starknet.get_block_info(&block_id)?;
+    starknet.ensure_state_not_pruned(&block_id)?;
 
-    let exec_context = ExecutionContext::new_at_block_end(Arc::clone(&starknet.backend), &block_info)?;
+    let exec_context = ExecutionContext::new_at_block_end(Arc::clone(&starknet.backend), &block_info)?
+        .with_state_overrides(&state_override.unwrap_or_default())?;
 
     if block_info.protocol_version() < &EXECUTION_UNSUPPORTED_BELOW_VERSION {
         return Err(StarknetRpcApiError::unsupported_txn_version());
@@ -44,3 +54,60 @@ pub fn call(starknet: &Starknet, request: FunctionCall, block_id: BlockId) -> St
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::devnet_rpc_test_setup;
+    use assert_matches::assert_matches;
+    use mp_block::BlockTag;
+    use mp_convert::ToFelt;
+    use mp_rpc::ContractStateOverride;
+    use starknet_api::abi::abi_utils::selector_from_name;
+
+    fn balance_of(
+        rpc: &Starknet,
+        account: Felt,
+        state_override: Option<StateOverride>,
+    ) -> StarknetRpcResult<Vec<Felt>> {
+        let request = FunctionCall {
+            contract_address: rpc.backend.chain_config().native_fee_token_address.to_felt(),
+            entry_point_selector: selector_from_name("balance_of").0,
+            calldata: vec![account],
+        };
+        call(rpc, request, BlockId::Tag(BlockTag::Latest), state_override)
+    }
+
+    #[tokio::test]
+    async fn test_call_with_state_override() {
+        let (accounts, rpc) = devnet_rpc_test_setup(1).await;
+        let account = &accounts.0[0];
+
+        assert_eq!(balance_of(&rpc, account.address, None).unwrap(), vec![account.balance.fri, Felt::ZERO]);
+
+        let state_override = vec![ContractStateOverride {
+            contract_address: account.address,
+            fee_token_balance: Some(Felt::from(42u64)),
+            ..Default::default()
+        }];
+        assert_eq!(
+            balance_of(&rpc, account.address, Some(state_override)).unwrap(),
+            vec![Felt::from(42u64), Felt::ZERO]
+        );
+
+        // The overrides do not outlive the call.
+        assert_eq!(balance_of(&rpc, account.address, None).unwrap(), vec![account.balance.fri, Felt::ZERO]);
+    }
+
+    #[tokio::test]
+    async fn test_call_with_invalid_state_override() {
+        let (accounts, rpc) = devnet_rpc_test_setup(1).await;
+
+        let state_override =
+            vec![ContractStateOverride { contract_address: Felt::MAX, nonce: Some(Felt::ONE), ..Default::default() }];
+        assert_matches!(
+            balance_of(&rpc, accounts.0[0].address, Some(state_override)),
+            Err(StarknetRpcApiError::InvalidParams { .. })
+        );
+    }
+}
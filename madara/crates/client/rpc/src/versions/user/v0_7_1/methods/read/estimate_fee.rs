@@ -7,7 +7,7 @@ use crate::Starknet;
 use blockifier::transaction::account_transaction::ExecutionFlags;
 use mc_exec::ExecutionContext;
 use mp_block::BlockId;
-use mp_rpc::{BroadcastedTxn, FeeEstimate, SimulationFlagForEstimateFee};
+use mp_rpc::{BroadcastedTxn, FeeEstimate, SimulationFlagForEstimateFee, StateOverride};
 use mp_transactions::BroadcastedTransactionExt;
 use mp_transactions::ToBlockifierError;
 use std::sync::Arc;
@@ -18,6 +18,7 @@ use std::sync::Arc;
 ///
 /// * `request` - starknet transaction request
 /// * `block_id` - hash of the requested block, number (height), or tag
+/// * `state_override` - Madara extension: state values to use instead of the ones in the block state
 ///
 /// # Returns
 ///
@@ -27,16 +28,21 @@ pub async fn estimate_fee(
     request: Vec<BroadcastedTxn>,
     simulation_flags: Vec<SimulationFlagForEstimateFee>,
     block_id: BlockId,
+    state_override: Option<StateOverride>,
 ) -> StarknetRpcResult<Vec<FeeEstimate>> {
     tracing::debug!("estimate fee on block_id {block_id:?}");
     let block_info = starknet.get_block_info(&block_id)?;
+    starknet.ensure_state_not_pruned(&block_id)?;
     let starknet_version = *block_info.protocol_version();
 
     if starknet_version < EXECUTION_UNSUPPORTED_BELOW_VERSION {
         return Err(StarknetRpcApiError::unsupported_txn_version());
     }
 
-    let exec_context = ExecutionContext::new_at_block_end(Arc::clone(&starknet.backend), &block_info)?;
+    starknet.ensure_fee_token_supported(&request)?;
+
+    let exec_context = ExecutionContext::new_at_block_end(Arc::clone(&starknet.backend), &block_info)?
+        .with_state_overrides(&state_override.unwrap_or_default())?;
     let validate = !simulation_flags.contains(&SimulationFlagForEstimateFee::SkipValidate);
 
     let transactions = request
@@ -68,3 +74,92 @@ pub async fn estimate_fee(
 
     Ok(fee_estimates)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::devnet_rpc_test_setup;
+    use assert_matches::assert_matches;
+    use mp_block::BlockTag;
+    use mp_convert::ToFelt;
+    use mp_rpc::{
+        BroadcastedInvokeTxn, ContractStateOverride, DaMode, InvokeTxnV3, ResourceBounds, ResourceBoundsMapping,
+    };
+    use starknet_api::abi::abi_utils::selector_from_name;
+    use starknet_types_core::felt::Felt;
+
+    /// A transfer of 1 fri from the devnet account `sender` to `recipient`. The signature is left empty, the
+    /// validation is skipped when estimating.
+    fn transfer_tx(rpc: &Starknet, sender: Felt, recipient: Felt, nonce: Felt) -> BroadcastedTxn {
+        let fee_token = rpc.backend.chain_config().native_fee_token_address.to_felt();
+        let transfer_calldata = [recipient, Felt::ONE, Felt::ZERO];
+        let calldata = [Felt::ONE, fee_token, selector_from_name("transfer").0, transfer_calldata.len().into()]
+            .into_iter()
+            .chain(transfer_calldata)
+            .collect::<Vec<_>>();
+
+        BroadcastedTxn::Invoke(BroadcastedInvokeTxn::V3(InvokeTxnV3 {
+            sender_address: sender,
+            calldata: calldata.into(),
+            signature: vec![].into(),
+            nonce,
+            resource_bounds: ResourceBoundsMapping {
+                l1_gas: ResourceBounds { max_amount: 60000, max_price_per_unit: 10000 },
+                l2_gas: ResourceBounds { max_amount: 60000, max_price_per_unit: 10000 },
+            },
+            tip: 0,
+            paymaster_data: vec![],
+            account_deployment_data: vec![],
+            nonce_data_availability_mode: DaMode::L1,
+            fee_data_availability_mode: DaMode::L1,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_with_state_override() {
+        let (accounts, rpc) = devnet_rpc_test_setup(2).await;
+        let (sender, recipient) = (accounts.0[0].address, accounts.0[1].address);
+        let estimate = |state_override: Option<StateOverride>| {
+            estimate_fee(
+                &rpc,
+                vec![transfer_tx(&rpc, sender, recipient, Felt::from(5u64))],
+                vec![SimulationFlagForEstimateFee::SkipValidate],
+                BlockId::Tag(BlockTag::Latest),
+                state_override,
+            )
+        };
+
+        // The nonce of the sender is 0 in the block state.
+        assert_matches!(estimate(None).await, Err(StarknetRpcApiError::TxnExecutionError { tx_index: 0, .. }));
+
+        let state_override = vec![ContractStateOverride {
+            contract_address: sender,
+            nonce: Some(Felt::from(5u64)),
+            ..Default::default()
+        }];
+        assert_eq!(estimate(Some(state_override)).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_with_invalid_state_override() {
+        let (accounts, rpc) = devnet_rpc_test_setup(2).await;
+        let (sender, recipient) = (accounts.0[0].address, accounts.0[1].address);
+
+        let state_override = vec![ContractStateOverride {
+            contract_address: sender,
+            storage: vec![mp_rpc::KeyValuePair { key: Felt::MAX, value: Felt::ONE }],
+            ..Default::default()
+        }];
+        assert_matches!(
+            estimate_fee(
+                &rpc,
+                vec![transfer_tx(&rpc, sender, recipient, Felt::ZERO)],
+                vec![SimulationFlagForEstimateFee::SkipValidate],
+                BlockId::Tag(BlockTag::Latest),
+                Some(state_override),
+            )
+            .await,
+            Err(StarknetRpcApiError::InvalidParams { .. })
+        );
+    }
+}
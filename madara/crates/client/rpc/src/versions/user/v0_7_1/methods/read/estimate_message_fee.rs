@@ -35,6 +35,7 @@ pub async fn estimate_message_fee(
     block_id: BlockId,
 ) -> StarknetRpcResult<FeeEstimate> {
     let block_info = starknet.get_block_info(&block_id)?;
+    starknet.ensure_state_not_pruned(&block_id)?;
 
     if block_info.protocol_version() < &EXECUTION_UNSUPPORTED_BELOW_VERSION {
         return Err(StarknetRpcApiError::unsupported_txn_version());
@@ -225,6 +225,8 @@ mod tests {
                                 strk_l1_gas_price: 12,
                                 eth_l1_data_gas_price: 44,
                                 strk_l1_data_gas_price: 52,
+                                eth_l2_gas_price: 1,
+                                strk_l2_gas_price: 1,
                             },
                             l1_da_mode: mp_chain_config::L1DataAvailabilityMode::Blob,
                         },
@@ -35,6 +35,7 @@ pub fn get_class_at(
         .resolve_block_id(&block_id)
         .or_internal_server_error("Error resolving block id")?
         .ok_or(StarknetRpcApiError::BlockNotFound)?;
+    starknet.ensure_state_not_pruned(&resolved_block_id)?;
 
     let class_hash = starknet
         .backend
@@ -28,6 +28,7 @@ pub fn get_nonce(starknet: &Starknet, block_id: BlockId, contract_address: Felt)
     if !block_exists {
         return Err(StarknetRpcApiError::BlockNotFound);
     }
+    starknet.ensure_state_not_pruned(&block_id)?;
 
     if !starknet
         .backend
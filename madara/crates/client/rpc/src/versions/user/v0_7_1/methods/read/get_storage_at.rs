@@ -44,6 +44,7 @@ pub fn get_storage_at(
     if !block_exists {
         return Err(StarknetRpcApiError::BlockNotFound);
     }
+    starknet.ensure_state_not_pruned(&block_id)?;
 
     let block_number = block_id.resolve_db_block_id(&starknet.backend)?;
 
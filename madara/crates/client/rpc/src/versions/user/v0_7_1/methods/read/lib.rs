@@ -7,7 +7,7 @@ use mp_rpc::{
     MsgFromL1, StarknetGetBlockWithTxsAndReceiptsResult, SyncingStatus, TxnFinalityAndExecutionStatus,
     TxnReceiptWithBlockInfo, TxnWithHash,
 };
-use mp_rpc::{BroadcastedTxn, SimulationFlagForEstimateFee};
+use mp_rpc::{BroadcastedTxn, SimulationFlagForEstimateFee, StateOverride};
 use starknet_types_core::felt::Felt;
 
 use super::block_hash_and_number::*;
@@ -48,8 +48,13 @@ impl StarknetReadRpcApiV0_7_1Server for Starknet {
         Ok(block_hash_and_number(self)?)
     }
 
-    fn call(&self, request: FunctionCall, block_id: BlockId) -> RpcResult<Vec<Felt>> {
-        Ok(call(self, request, block_id)?)
+    fn call(
+        &self,
+        request: FunctionCall,
+        block_id: BlockId,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Vec<Felt>> {
+        Ok(call(self, request, block_id, state_override)?)
     }
 
     fn chain_id(&self) -> RpcResult<Felt> {
@@ -65,8 +70,9 @@ impl StarknetReadRpcApiV0_7_1Server for Starknet {
         request: Vec<BroadcastedTxn>,
         simulation_flags: Vec<SimulationFlagForEstimateFee>,
         block_id: BlockId,
+        state_override: Option<StateOverride>,
     ) -> RpcResult<Vec<FeeEstimate>> {
-        Ok(estimate_fee(self, request, simulation_flags, block_id).await?)
+        Ok(estimate_fee(self, request, simulation_flags, block_id, state_override).await?)
     }
 
     async fn estimate_message_fee(&self, message: MsgFromL1, block_id: BlockId) -> RpcResult<FeeEstimate> {
@@ -2,7 +2,8 @@ use crate::{versions::user::v0_7_1::StarknetTraceRpcApiV0_7_1Server, Starknet};
 use jsonrpsee::core::{async_trait, RpcResult};
 use mp_block::BlockId;
 use mp_rpc::{
-    BroadcastedTxn, SimulateTransactionsResult, SimulationFlag, TraceBlockTransactionsResult, TraceTransactionResult,
+    BroadcastedTxn, SimulateTransactionsResult, SimulationFlag, StateOverride, TraceBlockTransactionsResult,
+    TraceTransactionResult,
 };
 use simulate_transactions::simulate_transactions;
 use starknet_types_core::felt::Felt;
@@ -20,8 +21,9 @@ impl StarknetTraceRpcApiV0_7_1Server for Starknet {
         block_id: BlockId,
         transactions: Vec<BroadcastedTxn>,
         simulation_flags: Vec<SimulationFlag>,
+        state_override: Option<StateOverride>,
     ) -> RpcResult<Vec<SimulateTransactionsResult>> {
-        Ok(simulate_transactions(self, block_id, transactions, simulation_flags).await?)
+        Ok(simulate_transactions(self, block_id, transactions, simulation_flags, state_override).await?)
     }
 
     async fn trace_block_transactions(&self, block_id: BlockId) -> RpcResult<Vec<TraceBlockTransactionsResult>> {
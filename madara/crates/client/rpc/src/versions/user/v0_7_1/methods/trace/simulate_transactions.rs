@@ -5,7 +5,7 @@ use crate::Starknet;
 use blockifier::transaction::account_transaction::ExecutionFlags;
 use mc_exec::{execution_result_to_tx_trace, ExecutionContext};
 use mp_block::BlockId;
-use mp_rpc::{BroadcastedTxn, SimulateTransactionsResult, SimulationFlag};
+use mp_rpc::{BroadcastedTxn, SimulateTransactionsResult, SimulationFlag, StateOverride};
 use mp_transactions::{BroadcastedTransactionExt, ToBlockifierError};
 use std::sync::Arc;
 
@@ -14,14 +14,19 @@ pub async fn simulate_transactions(
     block_id: BlockId,
     transactions: Vec<BroadcastedTxn>,
     simulation_flags: Vec<SimulationFlag>,
+    state_override: Option<StateOverride>,
 ) -> StarknetRpcResult<Vec<SimulateTransactionsResult>> {
     let block_info = starknet.get_block_info(&block_id)?;
+    starknet.ensure_state_not_pruned(&block_id)?;
     let starknet_version = *block_info.protocol_version();
 
     if starknet_version < EXECUTION_UNSUPPORTED_BELOW_VERSION {
         return Err(StarknetRpcApiError::unsupported_txn_version());
     }
-    let exec_context = ExecutionContext::new_at_block_end(Arc::clone(&starknet.backend), &block_info)?;
+    starknet.ensure_fee_token_supported(&transactions)?;
+
+    let exec_context = ExecutionContext::new_at_block_end(Arc::clone(&starknet.backend), &block_info)?
+        .with_state_overrides(&state_override.unwrap_or_default())?;
 
     let charge_fee = !simulation_flags.contains(&SimulationFlag::SkipFeeCharge);
     let validate = !simulation_flags.contains(&SimulationFlag::SkipValidate);
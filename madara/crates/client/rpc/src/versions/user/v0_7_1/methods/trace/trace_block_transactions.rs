@@ -1,6 +1,7 @@
+use mc_db::db_block_id::DbBlockId;
 use mc_exec::transaction::to_blockifier_transaction;
 use mc_exec::{execution_result_to_tx_trace, ExecutionContext};
-use mp_block::BlockId;
+use mp_block::{BlockId, MadaraMaybePendingBlockInfo};
 use mp_convert::ToFelt;
 use mp_rpc::TraceBlockTransactionsResult;
 use starknet_api::transaction::TransactionHash;
@@ -17,9 +18,23 @@ pub async fn trace_block_transactions(
 ) -> StarknetRpcResult<Vec<TraceBlockTransactionsResult>> {
     let block = starknet.get_block(&block_id)?;
 
+    if let MadaraMaybePendingBlockInfo::NotPending(info) = &block.info {
+        if let Some(traces) = starknet
+            .backend
+            .get_block_traces(info.header.block_number)
+            .or_internal_server_error("Error getting stored block traces")?
+        {
+            return Ok(traces);
+        }
+    }
+
     if block.info.protocol_version() < &EXECUTION_UNSUPPORTED_BELOW_VERSION {
         return Err(StarknetRpcApiError::unsupported_txn_version());
     }
+    // Re-executing needs the state at the end of the parent block.
+    if let Some(parent_block_n) = block.info.block_n().and_then(|block_n| block_n.checked_sub(1)) {
+        starknet.ensure_state_not_pruned(&DbBlockId::Number(parent_block_n))?;
+    }
 
     let exec_context = ExecutionContext::new_at_block_start(Arc::clone(&starknet.backend), &block.info)?;
 
@@ -48,3 +63,24 @@ pub async fn trace_block_transactions(
 
     Ok(traces)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{sample_block_traces, sample_chain_for_block_getters, SampleChainForBlockGetters};
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_trace_block_transactions_serves_stored_traces(
+        sample_chain_for_block_getters: (SampleChainForBlockGetters, Starknet),
+    ) {
+        let (SampleChainForBlockGetters { tx_hashes, .. }, rpc) = sample_chain_for_block_getters;
+
+        // The state of the sample chain is empty, the traces can only come from the storage.
+        let traces = sample_block_traces(&tx_hashes[1..3]);
+        rpc.backend.store_block_traces(2, &traces).unwrap();
+
+        assert_eq!(trace_block_transactions(&rpc, BlockId::Number(2)).await.unwrap(), traces);
+    }
+}
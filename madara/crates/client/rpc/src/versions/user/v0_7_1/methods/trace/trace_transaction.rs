@@ -2,9 +2,11 @@ use crate::errors::StarknetRpcApiError;
 use crate::errors::StarknetRpcResult;
 use crate::utils::{OptionExt, ResultExt};
 use crate::Starknet;
+use mc_db::db_block_id::DbBlockId;
 use mc_exec::execution_result_to_tx_trace;
 use mc_exec::transaction::to_blockifier_transaction;
 use mc_exec::ExecutionContext;
+use mp_block::MadaraMaybePendingBlockInfo;
 use mp_chain_config::StarknetVersion;
 use mp_rpc::TraceTransactionResult;
 use starknet_api::transaction::TransactionHash;
@@ -24,9 +26,26 @@ pub async fn trace_transaction(
         .or_internal_server_error("Error while getting block from tx hash")?
         .ok_or(StarknetRpcApiError::TxnHashNotFound)?;
 
+    if let MadaraMaybePendingBlockInfo::NotPending(info) = &block.info {
+        if let Some(mut traces) = starknet
+            .backend
+            .get_block_traces(info.header.block_number)
+            .or_internal_server_error("Error getting stored block traces")?
+        {
+            if (tx_index.0 as usize) < traces.len() {
+                let trace = traces.swap_remove(tx_index.0 as usize).trace_root;
+                return Ok(TraceTransactionResult { trace });
+            }
+        }
+    }
+
     if block.info.protocol_version() < &EXECUTION_UNSUPPORTED_BELOW_VERSION {
         return Err(StarknetRpcApiError::unsupported_txn_version());
     }
+    // Re-executing needs the state at the end of the parent block.
+    if let Some(parent_block_n) = block.info.block_n().and_then(|block_n| block_n.checked_sub(1)) {
+        starknet.ensure_state_not_pruned(&DbBlockId::Number(parent_block_n))?;
+    }
 
     let exec_context = ExecutionContext::new_at_block_start(Arc::clone(&starknet.backend), &block.info)?;
 
@@ -52,3 +71,28 @@ pub async fn trace_transaction(
 
     Ok(TraceTransactionResult { trace })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{sample_block_traces, sample_chain_for_block_getters, SampleChainForBlockGetters};
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_trace_transaction_serves_stored_traces(
+        sample_chain_for_block_getters: (SampleChainForBlockGetters, Starknet),
+    ) {
+        let (SampleChainForBlockGetters { tx_hashes, .. }, rpc) = sample_chain_for_block_getters;
+
+        // The state of the sample chain is empty, the traces can only come from the storage.
+        let traces = sample_block_traces(&tx_hashes[1..3]);
+        rpc.backend.store_block_traces(2, &traces).unwrap();
+
+        // Second transaction of block 2.
+        assert_eq!(
+            trace_transaction(&rpc, tx_hashes[2]).await.unwrap(),
+            TraceTransactionResult { trace: traces[1].trace_root.clone() }
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use jsonrpsee::core::RpcResult;
 use m_proc_macros::versioned_rpc;
 use mp_block::BlockId;
+use mp_receipt::Hash256;
 use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt;
 
@@ -54,6 +55,29 @@ pub struct GetStorageProofResult {
     pub global_roots: GlobalRoots,
 }
 
+/// Where an L2 to L1 message stands on the settlement layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MessageToL1Status {
+    /// The block that sent the message has not been settled yet.
+    Sent,
+    /// The message was registered on the settlement layer and can be consumed.
+    ReadyToWithdraw,
+    /// The message was consumed on the settlement layer.
+    Withdrawn,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageToL1StatusResult {
+    pub message_hash: Hash256,
+    pub status: MessageToL1Status,
+    /// Number of times the message was registered on the settlement layer. The same message can be sent several
+    /// times.
+    pub settled_count: u64,
+    /// Number of times the message was consumed on the settlement layer.
+    pub consumed_count: u64,
+}
+
 #[versioned_rpc("V0_8_0", "starknet")]
 pub trait StarknetWsRpcApi {
     #[subscription(name = "subscribeNewHeads", unsubscribe = "unsubscribeNewHeads", item = NewHead, param_kind = map)]
@@ -105,3 +129,14 @@ pub trait StarknetReadRpcApi {
         contracts_storage_keys: Option<Vec<ContractStorageKeysItem>>,
     ) -> RpcResult<GetStorageProofResult>;
 }
+
+#[versioned_rpc("V0_8_0", "madara")]
+pub trait MadaraMessagingRpcApi {
+    /// Returns the settlement status of the L2 to L1 messages sent by a transaction, in order.
+    #[method(name = "getMessagesToL1Status")]
+    fn get_messages_to_l1_status(&self, transaction_hash: Felt) -> RpcResult<Vec<MessageToL1StatusResult>>;
+
+    /// Returns the settlement status of an L2 to L1 message, from its settlement layer hash.
+    #[method(name = "getMessageToL1Status")]
+    fn get_message_to_l1_status(&self, message_hash: Hash256) -> RpcResult<MessageToL1StatusResult>;
+}
@@ -0,0 +1,167 @@
+use crate::errors::{StarknetRpcApiError, StarknetRpcResult};
+use crate::utils::ResultExt;
+use crate::versions::user::v0_8_0::{MadaraMessagingRpcApiV0_8_0Server, MessageToL1Status, MessageToL1StatusResult};
+use crate::Starknet;
+use jsonrpsee::core::{async_trait, RpcResult};
+use mc_db::db_block_id::DbBlockId;
+use mp_block::MadaraMaybePendingBlockInfo;
+use mp_receipt::Hash256;
+use starknet_types_core::felt::Felt;
+
+fn message_to_l1_status(starknet: &Starknet, message_hash: Hash256) -> StarknetRpcResult<MessageToL1StatusResult> {
+    let settlement = starknet
+        .backend
+        .get_message_to_l1_settlement(&message_hash)
+        .or_internal_server_error("Error getting L2 to L1 message settlement")?;
+    if settlement.logged == 0
+        && !starknet
+            .backend
+            .is_message_to_l1_sent(&message_hash)
+            .or_internal_server_error("Error getting L2 to L1 message")?
+    {
+        return Err(StarknetRpcApiError::MessageToL1NotFound);
+    }
+
+    let status = if settlement.logged == 0 {
+        MessageToL1Status::Sent
+    } else if settlement.logged > settlement.consumed {
+        MessageToL1Status::ReadyToWithdraw
+    } else {
+        MessageToL1Status::Withdrawn
+    };
+
+    Ok(MessageToL1StatusResult {
+        message_hash,
+        status,
+        settled_count: settlement.logged,
+        consumed_count: settlement.consumed,
+    })
+}
+
+fn messages_to_l1_status(
+    starknet: &Starknet,
+    transaction_hash: Felt,
+) -> StarknetRpcResult<Vec<MessageToL1StatusResult>> {
+    let (block_info, tx_index) = starknet
+        .backend
+        .find_tx_hash_block_info(&transaction_hash)
+        .or_internal_server_error("Error getting block from tx_hash")?
+        .ok_or(StarknetRpcApiError::TxnHashNotFound)?;
+
+    let message_hashes = match block_info {
+        MadaraMaybePendingBlockInfo::NotPending(_) => starknet
+            .backend
+            .get_messages_to_l1_of_tx(&transaction_hash)
+            .or_internal_server_error("Error getting L2 to L1 messages of transaction")?,
+        // Messages are indexed when the block is closed.
+        MadaraMaybePendingBlockInfo::Pending(_) => starknet
+            .backend
+            .get_block_inner(&DbBlockId::Pending)
+            .or_internal_server_error("Error getting pending block")?
+            .and_then(|inner| inner.receipts.into_iter().nth(tx_index.0 as usize))
+            .ok_or(StarknetRpcApiError::TxnHashNotFound)?
+            .messages_sent()
+            .iter()
+            .map(|message| message.compute_hash())
+            .collect(),
+    };
+
+    message_hashes.into_iter().map(|message_hash| message_to_l1_status(starknet, message_hash)).collect()
+}
+
+#[async_trait]
+impl MadaraMessagingRpcApiV0_8_0Server for Starknet {
+    fn get_messages_to_l1_status(&self, transaction_hash: Felt) -> RpcResult<Vec<MessageToL1StatusResult>> {
+        Ok(messages_to_l1_status(self, transaction_hash)?)
+    }
+
+    fn get_message_to_l1_status(&self, message_hash: Hash256) -> RpcResult<MessageToL1StatusResult> {
+        Ok(message_to_l1_status(self, message_hash)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::rpc_test_setup;
+    use mc_db::message_to_l1_db::MessageToL1Event;
+    use mc_db::MadaraBackend;
+    use mp_block::{Header, MadaraBlockInfo, MadaraBlockInner, MadaraMaybePendingBlock};
+    use mp_receipt::{InvokeTransactionReceipt, MsgToL1};
+    use mp_state_update::StateDiff;
+    use mp_transactions::InvokeTransactionV0;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    #[rstest]
+    fn test_get_messages_to_l1_status(rpc_test_setup: (Arc<MadaraBackend>, Starknet)) {
+        let (backend, rpc) = rpc_test_setup;
+        let tx_hash = Felt::from_hex_unchecked("0x8888888");
+        let messages = vec![
+            MsgToL1 { from_address: Felt::ONE, to_address: Felt::TWO, payload: vec![Felt::THREE] },
+            MsgToL1 { from_address: Felt::ONE, to_address: Felt::TWO, payload: vec![] },
+            MsgToL1 { from_address: Felt::TWO, to_address: Felt::TWO, payload: vec![] },
+        ];
+        backend
+            .store_block(
+                MadaraMaybePendingBlock {
+                    info: MadaraBlockInfo::new(Header::default(), vec![tx_hash], Felt::ONE).into(),
+                    inner: MadaraBlockInner::new(
+                        vec![InvokeTransactionV0::default().into()],
+                        vec![InvokeTransactionReceipt {
+                            transaction_hash: tx_hash,
+                            messages_sent: messages.clone(),
+                            ..Default::default()
+                        }
+                        .into()],
+                    ),
+                },
+                StateDiff::default(),
+                vec![],
+            )
+            .unwrap();
+
+        let [withdrawn, ready, sent] =
+            [messages[0].compute_hash(), messages[1].compute_hash(), messages[2].compute_hash()];
+        backend
+            .store_messages_to_l1_events(
+                &[
+                    MessageToL1Event::Logged(withdrawn.clone()),
+                    MessageToL1Event::Logged(ready.clone()),
+                    MessageToL1Event::Consumed(withdrawn.clone()),
+                ],
+                1,
+            )
+            .unwrap();
+
+        assert_eq!(
+            rpc.get_messages_to_l1_status(tx_hash).unwrap(),
+            vec![
+                MessageToL1StatusResult {
+                    message_hash: withdrawn,
+                    status: MessageToL1Status::Withdrawn,
+                    settled_count: 1,
+                    consumed_count: 1,
+                },
+                MessageToL1StatusResult {
+                    message_hash: ready.clone(),
+                    status: MessageToL1Status::ReadyToWithdraw,
+                    settled_count: 1,
+                    consumed_count: 0,
+                },
+                MessageToL1StatusResult {
+                    message_hash: sent.clone(),
+                    status: MessageToL1Status::Sent,
+                    settled_count: 0,
+                    consumed_count: 0,
+                },
+            ]
+        );
+        assert_eq!(rpc.get_message_to_l1_status(ready).unwrap().status, MessageToL1Status::ReadyToWithdraw);
+        assert_eq!(rpc.get_message_to_l1_status(sent).unwrap().status, MessageToL1Status::Sent);
+
+        let unknown = MsgToL1 { from_address: Felt::ONE, to_address: Felt::ONE, payload: vec![] }.compute_hash();
+        assert_eq!(message_to_l1_status(&rpc, unknown), Err(StarknetRpcApiError::MessageToL1NotFound));
+        assert_eq!(messages_to_l1_status(&rpc, Felt::ONE), Err(StarknetRpcApiError::TxnHashNotFound));
+    }
+}
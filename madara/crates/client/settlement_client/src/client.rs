@@ -4,6 +4,7 @@ use crate::state_update::{StateUpdate, StateUpdateWorker};
 use async_trait::async_trait;
 use futures::Stream;
 use mc_db::l1_db::LastSyncedEventBlock;
+use mc_db::message_to_l1_db::MessageToL1Event;
 #[cfg(test)]
 use mockall::automock;
 use mp_utils::service::ServiceContext;
@@ -84,6 +85,10 @@ pub trait SettlementClientTrait: Send + Sync {
 
     fn get_client_type(&self) -> ClientType;
     async fn get_latest_block_number(&self) -> Result<u64, SettlementClientError>;
+
+    /// Returns the latest block number that is deep enough for its events and state to be acted upon, following the
+    /// confirmation policy of the client.
+    async fn get_confirmed_block_number(&self) -> Result<u64, SettlementClientError>;
     async fn get_last_event_block_number(&self) -> Result<u64, SettlementClientError>;
 
     /// Retrieves the initial state from the settlement layer
@@ -125,6 +130,19 @@ pub trait SettlementClientTrait: Send + Sync {
     /// * Other value - Timestamp when the message was cancelled
     async fn get_l1_to_l2_message_cancellations(&self, msg_hash: &[u8]) -> Result<Felt, SettlementClientError>;
 
+    /// Returns the events of the core contract about L2 to L1 messages in the settlement layer blocks
+    /// `from_block..=to_block`: messages registered by a state update, which can then be consumed, and consumed
+    /// messages.
+    async fn get_messages_to_l1_events(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<MessageToL1Event>, SettlementClientError>;
+
+    /// Returns the settlement layer block in which the core contract was deployed, the first block that can contain
+    /// its events. Looking back at the state of old blocks may require an archive node.
+    async fn get_core_contract_deployment_block(&self) -> Result<u64, SettlementClientError>;
+
     // ============================================================
     // Stream Implementations :
     // ============================================================
@@ -0,0 +1,390 @@
+//! Settlement layer endpoints with health tracking, failover and quorum reads.
+//!
+//! Both settlement clients keep one connection per endpoint in an [`EndpointPool`], and route their rpc calls through
+//! it: regular reads go to the first healthy endpoint and fail over to the next ones, while critical reads can require
+//! several endpoints to agree on the result.
+
+use crate::error::SettlementClientError;
+use alloy::transports::{TransportError, TransportErrorKind};
+use futures::future::join_all;
+use starknet_providers::{ProviderError, ProviderImplError};
+use std::any::Any;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// How long an endpoint is put aside after a failure. Doubles with each consecutive failure, up to [`MAX_BACKOFF`].
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// How long an endpoint has to answer a call, so that a hanging endpoint does not hold back the failover to the next
+/// endpoints or the other endpoints of a quorum read.
+const CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Errors of the calls routed through an [`EndpointPool`].
+pub trait EndpointError: fmt::Display {
+    /// Whether the error comes from failing to reach the endpoint or get a valid answer from it, rather than from the
+    /// call itself, such as a reverted contract call. Only these errors put the endpoint aside.
+    fn is_endpoint_failure(&self) -> bool;
+    /// The error of a call the endpoint did not answer in time.
+    fn timed_out(timeout: Duration) -> Self;
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("No answer after {0:?}")]
+pub struct EndpointTimeout(Duration);
+
+impl ProviderImplError for EndpointTimeout {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl EndpointError for SettlementClientError {
+    fn is_endpoint_failure(&self) -> bool {
+        SettlementClientError::is_endpoint_failure(self)
+    }
+
+    fn timed_out(timeout: Duration) -> Self {
+        SettlementClientError::Endpoints(EndpointTimeout(timeout).to_string())
+    }
+}
+
+impl EndpointError for TransportError {
+    /// Error responses, such as a reverted call, are answers from the node.
+    fn is_endpoint_failure(&self) -> bool {
+        !self.is_error_resp()
+    }
+
+    fn timed_out(timeout: Duration) -> Self {
+        TransportErrorKind::custom(EndpointTimeout(timeout))
+    }
+}
+
+impl EndpointError for alloy::contract::Error {
+    fn is_endpoint_failure(&self) -> bool {
+        matches!(self, alloy::contract::Error::TransportError(e) if e.is_endpoint_failure())
+    }
+
+    fn timed_out(timeout: Duration) -> Self {
+        alloy::contract::Error::TransportError(TransportError::timed_out(timeout))
+    }
+}
+
+impl EndpointError for ProviderError {
+    /// Starknet errors, such as a reverted call or a missing class, are answers from the node.
+    fn is_endpoint_failure(&self) -> bool {
+        !matches!(self, ProviderError::StarknetError(_))
+    }
+
+    fn timed_out(timeout: Duration) -> Self {
+        ProviderError::Other(Box::new(EndpointTimeout(timeout)))
+    }
+}
+
+#[derive(Default)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
+struct Endpoint<T> {
+    /// Scheme, host and port of the endpoint url. The rest of the url may contain an api key, we don't log it.
+    name: String,
+    client: T,
+    health: Mutex<EndpointHealth>,
+}
+
+impl<T> Endpoint<T> {
+    fn new(url: &Url, client: T) -> Self {
+        Self { name: url.origin().ascii_serialization(), client, health: Default::default() }
+    }
+
+    fn unhealthy_until(&self) -> Option<Instant> {
+        self.health.lock().expect("Poisoned lock").unhealthy_until
+    }
+
+    fn record_success(&self) {
+        let mut health = self.health.lock().expect("Poisoned lock");
+        if health.consecutive_failures > 0 {
+            tracing::info!("🟢 Settlement layer endpoint {} recovered", self.name);
+        }
+        *health = EndpointHealth::default();
+    }
+
+    fn record_failure(&self, error: &impl fmt::Display) {
+        let mut health = self.health.lock().expect("Poisoned lock");
+        health.consecutive_failures += 1;
+        let backoff = BASE_BACKOFF.saturating_mul(1 << (health.consecutive_failures - 1).min(16)).min(MAX_BACKOFF);
+        health.unhealthy_until = Some(Instant::now() + backoff);
+        tracing::warn!(
+            "Settlement layer endpoint {} failed ({} in a row), putting it aside for {backoff:?}: {error}",
+            self.name,
+            health.consecutive_failures
+        );
+    }
+}
+
+/// The endpoints of a settlement layer, in order of preference.
+pub struct EndpointPool<T> {
+    endpoints: Vec<Endpoint<T>>,
+    /// Number of endpoints that need to agree on the result of a [`EndpointPool::call_quorum`] read.
+    quorum: usize,
+    /// Index of the endpoint that served the last successful call.
+    current: AtomicUsize,
+}
+
+impl<T> EndpointPool<T> {
+    pub fn new(endpoints: impl IntoIterator<Item = (Url, T)>, quorum: usize) -> Result<Self, SettlementClientError> {
+        let endpoints: Vec<_> = endpoints.into_iter().map(|(url, client)| Endpoint::new(&url, client)).collect();
+        if endpoints.is_empty() {
+            return Err(SettlementClientError::Endpoints("At least one endpoint is required".into()));
+        }
+        if quorum == 0 || quorum > endpoints.len() {
+            return Err(SettlementClientError::Endpoints(format!(
+                "The quorum must be between 1 and the number of endpoints ({}), got {quorum}",
+                endpoints.len()
+            )));
+        }
+        Ok(Self { endpoints, quorum, current: AtomicUsize::new(0) })
+    }
+
+    pub fn single(url: Url, client: T) -> Self {
+        Self { endpoints: vec![Endpoint::new(&url, client)], quorum: 1, current: AtomicUsize::new(0) }
+    }
+
+    /// The endpoint that served the last successful call.
+    pub fn current(&self) -> &T {
+        &self.endpoints[self.current.load(Ordering::Relaxed)].client
+    }
+
+    /// The healthy endpoints in order of preference, followed by the unhealthy ones starting with the one that will
+    /// recover first.
+    fn failover_order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let mut order: Vec<_> = (0..self.endpoints.len())
+            .map(|i| (self.endpoints[i].unhealthy_until().filter(|until| *until > now), i))
+            .collect();
+        order.sort();
+        order.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// Runs `f` against the endpoints in failover order until it succeeds. Returns the last error when every endpoint
+    /// failed.
+    ///
+    /// Each endpoint has [`CALL_TIMEOUT`] to answer. Endpoints are only marked unhealthy when they time out or fail
+    /// with an error for which [`EndpointError::is_endpoint_failure`] holds, but every error fails over to the next
+    /// endpoint.
+    pub async fn call<'a, R, E, F, Fut>(&'a self, f: F) -> Result<R, E>
+    where
+        F: Fn(&'a T) -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+        E: EndpointError,
+    {
+        let mut last_error = None;
+        for i in self.failover_order() {
+            let endpoint = &self.endpoints[i];
+            let result = tokio::time::timeout(CALL_TIMEOUT, f(&endpoint.client))
+                .await
+                .unwrap_or_else(|_| Err(E::timed_out(CALL_TIMEOUT)));
+            match result {
+                Ok(res) => {
+                    endpoint.record_success();
+                    if self.current.swap(i, Ordering::Relaxed) != i {
+                        tracing::info!("🔀 Switched to settlement layer endpoint {}", endpoint.name);
+                    }
+                    return Ok(res);
+                }
+                Err(e) => {
+                    // A reverted call is an answer from the endpoint, only a failure to reach it makes it unhealthy.
+                    if e.is_endpoint_failure() {
+                        endpoint.record_failure(&e);
+                    } else {
+                        endpoint.record_success();
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.expect("An endpoint pool is never empty"))
+    }
+
+    /// Runs `f` against every endpoint and returns the result that at least `quorum` of them agree on. This is a
+    /// regular [`EndpointPool::call`] when the quorum is 1.
+    ///
+    /// Each endpoint has [`CALL_TIMEOUT`] to answer. Endpoints are only marked unhealthy when they time out or fail
+    /// with an error for which [`SettlementClientError::is_endpoint_failure`] holds.
+    pub async fn call_quorum<'a, R, F, Fut>(&'a self, f: F) -> Result<R, SettlementClientError>
+    where
+        F: Fn(&'a T) -> Fut,
+        Fut: Future<Output = Result<R, SettlementClientError>>,
+        R: PartialEq + fmt::Debug,
+    {
+        if self.quorum <= 1 {
+            return self.call(f).await;
+        }
+
+        let results = join_all(self.endpoints.iter().map(|endpoint| async {
+            tokio::time::timeout(CALL_TIMEOUT, f(&endpoint.client))
+                .await
+                .unwrap_or_else(|_| Err(SettlementClientError::timed_out(CALL_TIMEOUT)))
+        }))
+        .await;
+
+        let mut answers: Vec<(R, Vec<&str>)> = vec![];
+        let mut errors = vec![];
+        for (endpoint, result) in self.endpoints.iter().zip(results) {
+            match result {
+                Ok(value) => {
+                    endpoint.record_success();
+                    match answers.iter_mut().find(|(answer, _)| *answer == value) {
+                        Some((_, names)) => names.push(endpoint.name.as_str()),
+                        None => answers.push((value, vec![endpoint.name.as_str()])),
+                    }
+                }
+                Err(e) => {
+                    // A reverted call is an answer from the endpoint, only a failure to reach it makes it unhealthy.
+                    if e.is_endpoint_failure() {
+                        endpoint.record_failure(&e);
+                    } else {
+                        endpoint.record_success();
+                    }
+                    errors.push(format!("{}: {e}", endpoint.name));
+                }
+            }
+        }
+
+        if answers.len() > 1 {
+            tracing::warn!("⚠️ Settlement layer endpoints disagree: {answers:?}");
+        }
+
+        let Some(best) = answers.iter().map(|(_, names)| names.len()).max().filter(|n| *n >= self.quorum) else {
+            return Err(SettlementClientError::Quorum(format!(
+                "{} of the {} endpoints needed to agree, got answers {answers:?} and errors {errors:?}",
+                self.quorum,
+                self.endpoints.len()
+            )));
+        };
+        Ok(answers.into_iter().find(|(_, names)| names.len() == best).map(|(value, _)| value).expect("Found above"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::error::EthereumClientError;
+    use assert_matches::assert_matches;
+
+    fn pool(answers: &[Option<u64>], quorum: usize) -> EndpointPool<Option<u64>> {
+        EndpointPool::new(
+            answers
+                .iter()
+                .enumerate()
+                .map(|(i, answer)| (format!("http://endpoint-{i}:8545").parse().unwrap(), *answer)),
+            quorum,
+        )
+        .unwrap()
+    }
+
+    async fn answer(answer: &Option<u64>) -> Result<u64, SettlementClientError> {
+        answer.ok_or_else(|| EthereumClientError::Rpc("Endpoint is down".into()).into())
+    }
+
+    #[tokio::test]
+    async fn test_call_fails_over_to_healthy_endpoints() {
+        let pool = pool(&[None, Some(2), Some(3)], 1);
+
+        assert_eq!(pool.call(answer).await.unwrap(), 2);
+        assert_eq!(*pool.current(), Some(2));
+        // The first endpoint is put aside until its backoff expires.
+        assert_eq!(pool.failover_order(), vec![1, 2, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_call_returns_last_error_when_all_endpoints_fail() {
+        let pool = pool(&[None, None], 1);
+
+        assert_matches!(pool.call(answer).await, Err(SettlementClientError::Ethereum(EthereumClientError::Rpc(_))));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_call_times_out_hanging_endpoints() {
+        let pool = pool(&[Some(1), Some(2)], 1);
+
+        let res = pool
+            .call(|answer| async move {
+                if *answer == Some(1) {
+                    std::future::pending::<()>().await;
+                }
+                Ok::<_, SettlementClientError>(answer.unwrap())
+            })
+            .await;
+        assert_eq!(res.unwrap(), 2);
+        assert_eq!(*pool.current(), Some(2));
+        assert_eq!(pool.failover_order(), vec![1, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_call_contract_errors_keep_endpoints_healthy() {
+        let pool = pool(&[None, Some(2)], 1);
+
+        let res = pool
+            .call(|answer| async move {
+                answer.ok_or_else(|| -> SettlementClientError {
+                    EthereumClientError::Contract("Execution reverted".into()).into()
+                })
+            })
+            .await;
+        // The call still fails over to the next endpoint.
+        assert_eq!(res.unwrap(), 2);
+        assert!(pool.endpoints.iter().all(|endpoint| endpoint.unhealthy_until().is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_call_quorum() {
+        assert_eq!(pool(&[Some(1), None, Some(1)], 2).call_quorum(answer).await.unwrap(), 1);
+        assert_matches!(
+            pool(&[Some(1), Some(2), None], 2).call_quorum(answer).await,
+            Err(SettlementClientError::Quorum(_))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_call_quorum_times_out_hanging_endpoints() {
+        let pool = pool(&[Some(1), None, Some(1)], 2);
+
+        let res = pool
+            .call_quorum(|answer| async move {
+                if answer.is_none() {
+                    std::future::pending::<()>().await;
+                }
+                Ok(answer.unwrap())
+            })
+            .await;
+        assert_eq!(res.unwrap(), 1);
+        assert_eq!(pool.failover_order(), vec![0, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_call_quorum_contract_errors_keep_endpoints_healthy() {
+        let pool = pool(&[Some(1), None, Some(1)], 2);
+
+        let res = pool
+            .call_quorum(|answer| async move {
+                answer.ok_or_else(|| EthereumClientError::Contract("Execution reverted".into()).into())
+            })
+            .await;
+        assert_eq!(res.unwrap(), 1);
+        assert!(pool.endpoints.iter().all(|endpoint| endpoint.unhealthy_until().is_none()));
+    }
+
+    #[test]
+    fn test_invalid_quorum() {
+        let urls = || [("http://localhost:8545".parse().unwrap(), ())];
+        assert_matches!(EndpointPool::new(urls(), 0), Err(SettlementClientError::Endpoints(_)));
+        assert_matches!(EndpointPool::new(urls(), 2), Err(SettlementClientError::Endpoints(_)));
+        assert_matches!(EndpointPool::<()>::new([], 1), Err(SettlementClientError::Endpoints(_)));
+    }
+}
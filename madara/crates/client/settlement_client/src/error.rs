@@ -67,10 +67,31 @@ pub enum SettlementClientError {
     #[error("State event listener error: {0}")]
     StateEventListener(String),
 
+    #[error("Settlement layer endpoints error: {0}")]
+    Endpoints(String),
+
+    #[error("Settlement layer endpoints quorum not reached: {0}")]
+    Quorum(String),
+
     #[error("{0}")]
     Other(String),
 }
 
+impl SettlementClientError {
+    /// Whether the error comes from failing to reach the endpoint or get a valid answer from it, rather than from the
+    /// call itself, such as a reverted contract call.
+    pub fn is_endpoint_failure(&self) -> bool {
+        matches!(
+            self,
+            SettlementClientError::Ethereum(
+                EthereumClientError::Rpc(_) | EthereumClientError::NetworkConnection { .. }
+            ) | SettlementClientError::Starknet(StarknetClientError::NetworkConnection { .. })
+                | SettlementClientError::Endpoints(_)
+                | SettlementClientError::InvalidResponse(_)
+        )
+    }
+}
+
 // 1. Ensure EthereumClientError can be converted to SettlementClientError
 impl From<EthereumClientError> for SettlementClientError {
     fn from(err: EthereumClientError) -> Self {
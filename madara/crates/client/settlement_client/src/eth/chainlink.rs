@@ -0,0 +1,145 @@
+use crate::endpoints::EndpointPool;
+use crate::error::SettlementClientError;
+use alloy::primitives::Address;
+use alloy::providers::{ProviderBuilder, ReqwestProvider};
+use alloy::sol;
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use mp_oracle::{Oracle, OraclePrice};
+use std::time::{Duration, SystemTime};
+use url::Url;
+
+sol!(
+    #[sol(rpc)]
+    interface AggregatorV3Interface {
+        function decimals() external view returns (uint8);
+        function latestRoundData() external view returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound);
+    }
+);
+
+/// Minimum number of decimals of an inverted price, so that inverting a feed with few decimals doesn't lose precision.
+const INVERTED_PRICE_DECIMALS: u32 = 18;
+
+/// Reads the price from a Chainlink-style aggregator contract on L1.
+///
+/// The returned price is always in fee tokens per ETH, like the other oracles. A feed quoting the fee token in ETH,
+/// such as a STRK/ETH feed, needs `invert` to be set.
+pub struct ChainlinkOracle {
+    endpoints: EndpointPool<ReqwestProvider>,
+    feed: Address,
+    /// Whether the feed answers in ETH per fee token rather than fee tokens per ETH.
+    invert: bool,
+}
+
+impl ChainlinkOracle {
+    pub fn new(urls: Vec<Url>, feed: Address, invert: bool) -> Result<Self, SettlementClientError> {
+        let endpoints =
+            EndpointPool::new(urls.into_iter().map(|url| (url.clone(), ProviderBuilder::new().on_http(url))), 1)?;
+        Ok(Self { endpoints, feed, invert })
+    }
+}
+
+#[async_trait]
+impl Oracle for ChainlinkOracle {
+    async fn fetch_eth_strk_price(&self) -> anyhow::Result<OraclePrice> {
+        let feed = self.feed;
+        let (decimals, round) = self
+            .endpoints
+            .call(|provider| async move {
+                let aggregator = AggregatorV3Interface::new(feed, provider.clone());
+                let decimals = aggregator.decimals().call().await?._0;
+                let round = aggregator.latestRoundData().call().await?;
+                Ok::<_, alloy::contract::Error>((decimals, round))
+            })
+            .await
+            .with_context(|| format!("failed to read the price feed at {feed}"))?;
+
+        if !round.answer.is_positive() {
+            bail!("Price feed at {feed} returned a non-positive price: {}", round.answer);
+        }
+        let answer = u128::try_from(round.answer.into_raw()).context("Price does not fit in 128 bits")?;
+        let (price, decimals) = if self.invert {
+            // answer / 10^d ETH per fee token is 10^(d + e) / answer / 10^e fee tokens per ETH.
+            let feed_decimals = u32::from(decimals);
+            let decimals = feed_decimals.max(INVERTED_PRICE_DECIMALS);
+            let scale = 10u128.checked_pow(feed_decimals + decimals).context("Price feed has too many decimals")?;
+            (scale / answer, decimals)
+        } else {
+            (answer, u32::from(decimals))
+        };
+        let updated_at = u64::try_from(round.updatedAt).ok().context("Invalid price timestamp")?;
+
+        Ok(OraclePrice { price, decimals, timestamp: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(updated_at)) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::Method::POST;
+    use httpmock::MockServer;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const UPDATED_AT: u64 = 1_700_000_000;
+
+    fn mock_eth_call(server: &MockServer, selector: &str, words: &[u128]) {
+        let result: String = words.iter().map(|word| format!("{word:064x}")).collect();
+        server.mock(|when, then| {
+            when.method(POST).path("/").body_contains("eth_call").body_contains(selector);
+            then.status(200).header("content-type", "application/json").json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 0,
+                "result": format!("0x{result}")
+            }));
+        });
+    }
+
+    /// Mocks a feed with 8 decimals answering `answer`.
+    fn oracle(server: &MockServer, answer: u128, invert: bool) -> ChainlinkOracle {
+        // decimals()
+        mock_eth_call(server, "313ce567", &[8]);
+        // latestRoundData()
+        mock_eth_call(server, "feaf968c", &[1, answer, u128::from(UPDATED_AT), u128::from(UPDATED_AT), 1]);
+        ChainlinkOracle::new(vec![server.url("/").parse().unwrap()], Address::ZERO, invert).unwrap()
+    }
+
+    /// Both feed orientations give 2500 fee tokens per ETH.
+    #[rstest]
+    #[case::direct(250_000_000_000, false, 250_000_000_000, 8)]
+    #[case::inverted(40_000, true, 2_500_000_000_000_000_000_000, 18)]
+    #[tokio::test]
+    async fn test_chainlink_price(
+        #[case] answer: u128,
+        #[case] invert: bool,
+        #[case] expected: u128,
+        #[case] expected_decimals: u32,
+    ) {
+        let server = MockServer::start();
+
+        assert_eq!(
+            oracle(&server, answer, invert).fetch_eth_strk_price().await.unwrap(),
+            OraclePrice {
+                price: expected,
+                decimals: expected_decimals,
+                timestamp: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(UPDATED_AT))
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chainlink_inverted_price_keeps_precision() {
+        let server = MockServer::start();
+
+        // 0.00003 ETH per fee token is 33333.333333333333333333 fee tokens per ETH.
+        let price = oracle(&server, 3_000, true).fetch_eth_strk_price().await.unwrap();
+        assert_eq!((price.price, price.decimals), (33_333_333_333_333_333_333_333, 18));
+    }
+
+    #[tokio::test]
+    async fn test_chainlink_rejects_non_positive_price() {
+        let server = MockServer::start();
+
+        assert!(oracle(&server, 0, false).fetch_eth_strk_price().await.is_err());
+    }
+}
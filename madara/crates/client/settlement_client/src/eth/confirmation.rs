@@ -0,0 +1,287 @@
+use crate::endpoints::EndpointPool;
+use crate::error::SettlementClientError;
+use crate::eth::error::EthereumClientError;
+use crate::eth::EthereumEndpoint;
+use alloy::eips::{BlockId, BlockNumberOrTag};
+use alloy::primitives::B256;
+use alloy::providers::Provider;
+use alloy::rpc::types::{BlockTransactionsKind, Log};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// How deep an L1 block needs to be before we act on the events it contains.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmationPolicy {
+    /// Use the chain tip. Events may still be reorged out. This is the default, as local L1 nodes such as anvil never
+    /// finalize blocks.
+    #[default]
+    Latest,
+    /// Wait until the block is this many blocks behind the chain tip.
+    Depth(u64),
+    /// Use the `safe` block of the L1 node.
+    Safe,
+    /// Use the `finalized` block of the L1 node.
+    Finalized,
+}
+
+impl FromStr for ConfirmationPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "latest" => Ok(Self::Latest),
+            "safe" => Ok(Self::Safe),
+            "finalized" => Ok(Self::Finalized),
+            _ => s.parse().map(Self::Depth).map_err(|_| {
+                format!(
+                    "Invalid confirmation policy `{s}`: expected `latest`, `safe`, `finalized` or a number of blocks"
+                )
+            }),
+        }
+    }
+}
+
+impl fmt::Display for ConfirmationPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Latest => write!(f, "latest"),
+            Self::Depth(depth) => write!(f, "{depth}"),
+            Self::Safe => write!(f, "safe"),
+            Self::Finalized => write!(f, "finalized"),
+        }
+    }
+}
+
+pub(crate) async fn get_block_number_with_tag(
+    endpoints: &EndpointPool<EthereumEndpoint>,
+    tag: BlockNumberOrTag,
+) -> Result<u64, SettlementClientError> {
+    let block = endpoints
+        .call(|endpoint| async move {
+            endpoint.provider.get_block(BlockId::Number(tag), BlockTransactionsKind::Hashes).await
+        })
+        .await
+        .map_err(|e| -> SettlementClientError { EthereumClientError::Rpc(e.to_string()).into() })?
+        .ok_or_else(|| -> SettlementClientError {
+            EthereumClientError::Rpc(format!("The L1 node does not know the {tag} block")).into()
+        })?;
+    Ok(block.header.number)
+}
+
+struct ProcessedBlock {
+    hash: B256,
+    events: Vec<String>,
+}
+
+/// Remembers the L1 blocks of the events we already processed until they are finalized, so that we can tell when one
+/// of them is reorged out from under us.
+#[derive(Default)]
+pub(crate) struct ReorgDetector {
+    processed: BTreeMap<u64, ProcessedBlock>,
+}
+
+impl ReorgDetector {
+    pub fn track(&mut self, log: &Log, event: String) {
+        let (Some(block_number), Some(hash)) = (log.block_number, log.block_hash) else { return };
+        let block = self.processed.entry(block_number).or_insert_with(|| ProcessedBlock { hash, events: vec![] });
+        if block.hash != hash {
+            // The block was replaced in between two of our queries.
+            block.hash = hash;
+            block.events.clear();
+        }
+        block.events.push(event);
+    }
+
+    /// Forgets about the finalized blocks and logs the events of the blocks that are no longer part of the canonical
+    /// chain.
+    pub async fn check(&mut self, endpoints: &EndpointPool<EthereumEndpoint>) -> Result<(), SettlementClientError> {
+        if self.processed.is_empty() {
+            return Ok(());
+        }
+
+        let finalized = get_block_number_with_tag(endpoints, BlockNumberOrTag::Finalized).await?;
+        self.processed = self.processed.split_off(&(finalized + 1));
+
+        // Block hashes commit to their parents: once we find a processed block that is still canonical, all of the
+        // older ones are too.
+        while let Some((&block_number, block)) = self.processed.last_key_value() {
+            let canonical = endpoints
+                .call(|endpoint| async move {
+                    endpoint.provider.get_block(BlockId::number(block_number), BlockTransactionsKind::Hashes).await
+                })
+                .await
+                .map_err(|e| -> SettlementClientError { EthereumClientError::Rpc(e.to_string()).into() })?
+                .map(|block| block.header.hash);
+            if canonical == Some(block.hash) {
+                break;
+            }
+
+            for event in &block.events {
+                tracing::error!(
+                    "⚠️ L1 reorg detected: {event} was processed from L1 block #{block_number} ({:#x}), which is no \
+                     longer part of the canonical chain",
+                    block.hash
+                );
+            }
+            self.processed.remove(&block_number);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::SettlementClientTrait;
+    use crate::eth::EthereumClient;
+    use alloy::primitives::Address;
+    use httpmock::Method::POST;
+    use httpmock::MockServer;
+    use rstest::rstest;
+    use serde_json::json;
+    use std::sync::Arc;
+    use url::Url;
+
+    fn log(block_number: u64, block_hash: B256) -> Log {
+        Log { block_number: Some(block_number), block_hash: Some(block_hash), ..Default::default() }
+    }
+
+    fn block_response(block_number: u64, block_hash: B256) -> serde_json::Value {
+        let zero = B256::ZERO;
+        json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "result": {
+                "hash": block_hash,
+                "parentHash": zero,
+                "sha3Uncles": zero,
+                "miner": Address::ZERO,
+                "stateRoot": zero,
+                "transactionsRoot": zero,
+                "receiptsRoot": zero,
+                "logsBloom": format!("0x{}", "0".repeat(512)),
+                "difficulty": "0x0",
+                "number": format!("{block_number:#x}"),
+                "gasLimit": "0x0",
+                "gasUsed": "0x0",
+                "timestamp": "0x0",
+                "extraData": "0x",
+                "mixHash": zero,
+                "nonce": "0x0000000000000000",
+                "uncles": [],
+                "transactions": []
+            }
+        })
+    }
+
+    /// Mocks `eth_getBlockByNumber` for the block number or tag `param`.
+    fn mock_block<'a>(server: &'a MockServer, param: &str, block_number: u64, block_hash: B256) -> httpmock::Mock<'a> {
+        server.mock(|when, then| {
+            when.method(POST).path("/").body_contains("eth_getBlockByNumber").body_contains(format!("\"{param}\""));
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(block_response(block_number, block_hash));
+        })
+    }
+
+    fn endpoints(server: &MockServer) -> EndpointPool<EthereumEndpoint> {
+        let url: Url = server.url("/").parse().unwrap();
+        EndpointPool::single(url.clone(), EthereumEndpoint::new(url, Address::ZERO))
+    }
+
+    #[test]
+    fn test_reorg_detector_track_replaced_block() {
+        let mut reorgs = ReorgDetector::default();
+        reorgs.track(&log(5, B256::repeat_byte(1)), "event a".into());
+        reorgs.track(&log(5, B256::repeat_byte(1)), "event b".into());
+        assert_eq!(reorgs.processed[&5].events, ["event a", "event b"]);
+
+        // The block changed in between two queries: the events of the previous block are forgotten.
+        reorgs.track(&log(5, B256::repeat_byte(2)), "event c".into());
+        assert_eq!(reorgs.processed[&5].hash, B256::repeat_byte(2));
+        assert_eq!(reorgs.processed[&5].events, ["event c"]);
+
+        // Pending logs have no block.
+        reorgs.track(&Log::default(), "event d".into());
+        assert_eq!(reorgs.processed.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reorg_detector_check() {
+        let server = MockServer::start();
+        mock_block(&server, "finalized", 4, B256::repeat_byte(4));
+        // Block 7 was reorged out, block 5 is still canonical.
+        let block_7 = mock_block(&server, "0x7", 7, B256::repeat_byte(0x77));
+        let block_5 = mock_block(&server, "0x5", 5, B256::repeat_byte(5));
+        let block_3 = mock_block(&server, "0x3", 3, B256::repeat_byte(3));
+
+        let mut reorgs = ReorgDetector::default();
+        reorgs.track(&log(3, B256::repeat_byte(3)), "event 3".into());
+        reorgs.track(&log(5, B256::repeat_byte(5)), "event 5".into());
+        reorgs.track(&log(7, B256::repeat_byte(7)), "event 7".into());
+
+        reorgs.check(&endpoints(&server)).await.unwrap();
+
+        // The finalized block is forgotten, the reorged block is dropped, and the older blocks are not queried once a
+        // canonical one is found.
+        assert_eq!(reorgs.processed.keys().copied().collect::<Vec<_>>(), [5]);
+        block_7.assert();
+        block_5.assert();
+        block_3.assert_hits(0);
+    }
+
+    #[tokio::test]
+    async fn test_gas_prices_read_at_confirmed_block() {
+        let server = MockServer::start();
+        mock_block(&server, "finalized", 4, B256::repeat_byte(4));
+        let fee_history = server.mock(|when, then| {
+            when.method(POST).path("/").body_contains("eth_feeHistory").body_contains("\"0x4\"");
+            then.status(200).header("content-type", "application/json").json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 0,
+                "result": {
+                    "oldestBlock": "0x2",
+                    "baseFeePerGas": ["0x1", "0x2", "0x3"],
+                    "gasUsedRatio": [0.5, 0.5],
+                    "baseFeePerBlobGas": ["0x4", "0x6", "0x8"],
+                    "blobGasUsedRatio": [0.5, 0.5]
+                }
+            }));
+        });
+
+        let client = EthereumClient {
+            endpoints: Arc::new(endpoints(&server)),
+            confirmation_policy: ConfirmationPolicy::Finalized,
+        };
+        assert_eq!(client.get_gas_prices().await.unwrap(), (3, 6));
+        fee_history.assert();
+    }
+
+    #[tokio::test]
+    async fn test_reorg_detector_check_nothing_processed() {
+        let server = MockServer::start();
+        let finalized = mock_block(&server, "finalized", 4, B256::repeat_byte(4));
+
+        ReorgDetector::default().check(&endpoints(&server)).await.unwrap();
+        finalized.assert_hits(0);
+    }
+
+    #[rstest]
+    #[case("latest", ConfirmationPolicy::Latest)]
+    #[case("safe", ConfirmationPolicy::Safe)]
+    #[case("finalized", ConfirmationPolicy::Finalized)]
+    #[case("12", ConfirmationPolicy::Depth(12))]
+    fn test_confirmation_policy_from_str(#[case] s: &str, #[case] expected: ConfirmationPolicy) {
+        assert_eq!(s.parse::<ConfirmationPolicy>().unwrap(), expected);
+        assert_eq!(expected.to_string(), s);
+    }
+
+    #[test]
+    fn test_confirmation_policy_from_str_invalid() {
+        assert!("pending".parse::<ConfirmationPolicy>().is_err());
+        assert!("-1".parse::<ConfirmationPolicy>().is_err());
+    }
+}
@@ -1,12 +1,15 @@
+use crate::client::SettlementClientTrait;
 use crate::error::SettlementClientError;
+use crate::eth::confirmation::ReorgDetector;
 use crate::eth::error::EthereumClientError;
 use crate::eth::StarknetCoreContract::LogMessageToL2;
+use crate::eth::{EthereumClient, EVENT_SEARCH_BLOCK_RANGE};
 use crate::messaging::L1toL2MessagingEventData;
-use alloy::contract::EventPoller;
+use alloy::providers::Provider;
 use alloy::rpc::types::Log;
-use alloy::transports::http::{Client, Http};
 use futures::ready;
 use futures::Stream;
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -18,12 +21,67 @@ pub struct EthereumEventStream {
 }
 
 impl EthereumEventStream {
-    pub fn new(watcher: EventPoller<Http<Client>, LogMessageToL2>) -> Self {
-        let stream = watcher.into_stream();
+    /// Streams the `LogMessageToL2` events of the core contract starting at `from_block`, as their L1 blocks satisfy
+    /// the confirmation policy of the client.
+    pub fn new(client: EthereumClient, from_block: u64) -> Self {
+        let poller = ConfirmedEventPoller {
+            client,
+            next_block: from_block,
+            pending: VecDeque::new(),
+            reorgs: Default::default(),
+        };
+        let stream = futures::stream::unfold(poller, |mut poller| async move {
+            let item = poller.next_event().await;
+            Some((item, poller))
+        });
         Self { stream: Box::pin(stream) }
     }
 }
 
+struct ConfirmedEventPoller {
+    client: EthereumClient,
+    next_block: u64,
+    pending: VecDeque<(LogMessageToL2, Log)>,
+    reorgs: ReorgDetector,
+}
+
+impl ConfirmedEventPoller {
+    async fn next_event(&mut self) -> EthereumStreamItem {
+        loop {
+            if let Some((event, log)) = self.pending.pop_front() {
+                self.reorgs.track(&log, format!("L1 to L2 message with nonce {}", event.nonce));
+                return Ok((event, log));
+            }
+
+            match self.poll_confirmed_events().await {
+                // Only wait for new blocks once we have caught up with the confirmed block.
+                Ok(true) if self.pending.is_empty() => {
+                    tokio::time::sleep(self.client.endpoints.current().provider.client().poll_interval()).await
+                }
+                Ok(_) => {}
+                Err(e) => return Err(alloy::sol_types::Error::custom(e.to_string())),
+            }
+        }
+    }
+
+    /// Fetches the next range of confirmed events. Returns whether we have caught up with the confirmed block.
+    async fn poll_confirmed_events(&mut self) -> Result<bool, SettlementClientError> {
+        self.reorgs.check(&self.client.endpoints).await?;
+
+        let confirmed_block = self.client.get_confirmed_block_number().await?;
+        if confirmed_block < self.next_block {
+            return Ok(true);
+        }
+
+        let to_block = confirmed_block.min(self.next_block.saturating_add(EVENT_SEARCH_BLOCK_RANGE));
+        let events = self.client.get_core_contract_events::<LogMessageToL2>(self.next_block, to_block).await?;
+        self.pending.extend(events);
+        self.next_block = to_block + 1;
+
+        Ok(to_block == confirmed_block)
+    }
+}
+
 impl Stream for EthereumEventStream {
     type Item = Result<L1toL2MessagingEventData, SettlementClientError>;
 
@@ -46,13 +104,21 @@ impl Stream for EthereumEventStream {
 #[cfg(test)]
 pub mod eth_event_stream_tests {
     use super::*;
+    use crate::endpoints::EndpointPool;
+    use crate::eth::confirmation::ConfirmationPolicy;
+    use crate::eth::EthereumEndpoint;
     use alloy::primitives::{Address, LogData, B256, U256};
     use assert_matches::assert_matches;
     use futures::stream::iter;
     use futures::StreamExt;
+    use httpmock::Method::POST;
+    use httpmock::MockServer;
     use rstest::*;
+    use serde_json::json;
     use starknet_types_core::felt::Felt;
     use std::str::FromStr;
+    use std::sync::Arc;
+    use url::Url;
 
     #[fixture]
     fn mock_event(#[default(1)] index: u64) -> LogMessageToL2 {
@@ -234,4 +300,82 @@ pub mod eth_event_stream_tests {
             assert_eq!(*field, "transaction_hash in Ethereum log", "Error should mention missing transaction hash");
         });
     }
+
+    fn mock_block_number(server: &MockServer, block_number: u64) {
+        server.mock(|when, then| {
+            when.method(POST).path("/").body_contains("eth_blockNumber");
+            then.status(200).header("content-type", "application/json").json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 0,
+                "result": format!("{block_number:#x}")
+            }));
+        });
+    }
+
+    fn mock_get_logs(server: &MockServer, from_block: u64, to_block: u64) -> httpmock::Mock<'_> {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains("eth_getLogs")
+                .body_contains(format!("\"fromBlock\":\"{from_block:#x}\""))
+                .body_contains(format!("\"toBlock\":\"{to_block:#x}\""));
+            then.status(200).header("content-type", "application/json").json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 0,
+                "result": []
+            }));
+        })
+    }
+
+    fn poller(server: &MockServer, confirmation_policy: ConfirmationPolicy, next_block: u64) -> ConfirmedEventPoller {
+        let url: Url = server.url("/").parse().unwrap();
+        let client = EthereumClient {
+            endpoints: Arc::new(EndpointPool::single(url.clone(), EthereumEndpoint::new(url, Address::ZERO))),
+            confirmation_policy,
+        };
+        ConfirmedEventPoller { client, next_block, pending: VecDeque::new(), reorgs: Default::default() }
+    }
+
+    #[rstest]
+    // Waits for the confirmation depth.
+    #[case(ConfirmationPolicy::Depth(2), 16, 10, 14, true)]
+    // Catches up in ranges of `EVENT_SEARCH_BLOCK_RANGE` blocks.
+    #[case(ConfirmationPolicy::Latest, 8192, 0, EVENT_SEARCH_BLOCK_RANGE, false)]
+    #[tokio::test]
+    async fn test_confirmed_event_poller_range(
+        #[case] confirmation_policy: ConfirmationPolicy,
+        #[case] latest_block: u64,
+        #[case] next_block: u64,
+        #[case] expected_to_block: u64,
+        #[case] expected_caught_up: bool,
+    ) {
+        let server = MockServer::start();
+        mock_block_number(&server, latest_block);
+        let get_logs = mock_get_logs(&server, next_block, expected_to_block);
+
+        let mut poller = poller(&server, confirmation_policy, next_block);
+        assert_eq!(poller.poll_confirmed_events().await.unwrap(), expected_caught_up);
+        assert_eq!(poller.next_block, expected_to_block + 1);
+        get_logs.assert();
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_event_poller_nothing_confirmed() {
+        let server = MockServer::start();
+        mock_block_number(&server, 16);
+        let get_logs = server.mock(|when, then| {
+            when.method(POST).path("/").body_contains("eth_getLogs");
+            then.status(200).header("content-type", "application/json").json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 0,
+                "result": []
+            }));
+        });
+
+        // Block 15 is only 1 block deep.
+        let mut poller = poller(&server, ConfirmationPolicy::Depth(2), 15);
+        assert!(poller.poll_confirmed_events().await.unwrap());
+        assert_eq!(poller.next_block, 15);
+        get_logs.assert_hits(0);
+    }
 }
@@ -1,8 +1,12 @@
+pub mod chainlink;
+pub mod confirmation;
 pub mod error;
 pub mod event;
 
 use crate::client::{ClientType, SettlementClientTrait};
+use crate::endpoints::EndpointPool;
 use crate::error::SettlementClientError;
+use crate::eth::confirmation::{get_block_number_with_tag, ConfirmationPolicy, ReorgDetector};
 use crate::eth::event::EthereumEventStream;
 use crate::eth::StarknetCoreContract::{LogMessageToL2, StarknetCoreContractInstance};
 use crate::messaging::L1toL2MessagingEventData;
@@ -11,16 +15,17 @@ use crate::utils::convert_log_state_update;
 use alloy::eips::{BlockId, BlockNumberOrTag};
 use alloy::primitives::{keccak256, Address, B256, I256, U256};
 use alloy::providers::{Provider, ProviderBuilder, ReqwestProvider, RootProvider};
-use alloy::rpc::types::Filter;
+use alloy::rpc::types::{Filter, Log};
 use alloy::sol;
-use alloy::sol_types::SolValue;
+use alloy::sol_types::{SolEvent, SolValue};
 use alloy::transports::http::{Client, Http};
 use async_trait::async_trait;
 use bitvec::macros::internal::funty::Fundamental;
 use error::EthereumClientError;
-use futures::StreamExt;
 use mc_db::l1_db::LastSyncedEventBlock;
+use mc_db::message_to_l1_db::MessageToL1Event;
 use mp_convert::{felt_to_u256, ToFelt};
+use mp_receipt::MsgToL1;
 use mp_utils::service::ServiceContext;
 use starknet_types_core::felt::Felt;
 use std::sync::Arc;
@@ -36,46 +41,166 @@ sol!(
     "src/eth/starknet_core.json"
 );
 
-pub struct EthereumClient {
-    pub provider: Arc<ReqwestProvider>,
+/// Connection to one of the L1 endpoints.
+#[derive(Clone)]
+pub struct EthereumEndpoint {
+    pub provider: ReqwestProvider,
     pub l1_core_contract: StarknetCoreContractInstance<Http<Client>, RootProvider<Http<Client>>>,
 }
 
-#[derive(Clone)]
-pub struct EthereumClientConfig {
-    pub url: Url,
-    pub l1_core_address: Address,
+impl EthereumEndpoint {
+    pub fn new(url: Url, l1_core_address: Address) -> Self {
+        let provider = ProviderBuilder::new().on_http(url);
+        let l1_core_contract = StarknetCoreContract::new(l1_core_address, provider.clone());
+        Self { provider, l1_core_contract }
+    }
+
+    async fn get_core_contract_state(&self, block_n: u64) -> Result<StateUpdate, SettlementClientError> {
+        let block_number = self
+            .l1_core_contract
+            .stateBlockNumber()
+            .block(BlockId::number(block_n))
+            .call()
+            .await
+            .map_err(|e| contract_call_error("state block number", e))?;
+        // when the block 0 is not settled yet, this should be prev block number, this would be the output from the snos as well while
+        // executing the block 0.
+        // link: https://github.com/starkware-libs/cairo-lang/blob/master/src/starkware/starknet/solidity/StarknetState.sol#L32
+        let block_number: Option<u64> = if block_number._0 == I256::MINUS_ONE {
+            None // initial contract state
+        } else {
+            Some(block_number._0.as_u64())
+        };
+
+        let global_root = self
+            .l1_core_contract
+            .stateRoot()
+            .block(BlockId::number(block_n))
+            .call()
+            .await
+            .map_err(|e| contract_call_error("state root", e))?;
+        let global_root = global_root._0.to_felt();
+
+        let block_hash = self
+            .l1_core_contract
+            .stateBlockHash()
+            .block(BlockId::number(block_n))
+            .call()
+            .await
+            .map_err(|e| contract_call_error("state block hash", e))?;
+        let block_hash = block_hash._0.to_felt();
+
+        Ok(StateUpdate { global_root, block_number, block_hash })
+    }
 }
 
-impl Clone for EthereumClient {
-    fn clone(&self) -> Self {
-        EthereumClient { provider: Arc::clone(&self.provider), l1_core_contract: self.l1_core_contract.clone() }
+/// Errors returned by the node itself, such as a reverted call, are contract errors. Failing to reach the node is an
+/// rpc error, which puts the endpoint aside.
+fn contract_call_error(what: &str, e: alloy::contract::Error) -> SettlementClientError {
+    match e {
+        alloy::contract::Error::TransportError(e) if !e.is_error_resp() => {
+            EthereumClientError::Rpc(format!("Failed to get {what}: {e:#}")).into()
+        }
+        e => EthereumClientError::Contract(format!("Failed to get {what}: {e:#}")).into(),
     }
 }
 
+#[derive(Clone)]
+pub struct EthereumClient {
+    pub endpoints: Arc<EndpointPool<EthereumEndpoint>>,
+    pub confirmation_policy: ConfirmationPolicy,
+}
+
+#[derive(Clone)]
+pub struct EthereumClientConfig {
+    /// L1 rpc endpoints, in order of preference.
+    pub urls: Vec<Url>,
+    pub l1_core_address: Address,
+    /// Number of endpoints that need to agree on the state of the core contract.
+    pub quorum: usize,
+    /// How deep L1 blocks need to be before their events and state are used.
+    pub confirmation_policy: ConfirmationPolicy,
+}
+
 impl EthereumClient {
     pub async fn new(config: EthereumClientConfig) -> Result<Self, SettlementClientError> {
-        let provider = ProviderBuilder::new().on_http(config.url);
+        let l1_core_address = config.l1_core_address;
+        let endpoints = EndpointPool::new(
+            config.urls.into_iter().map(|url| (url.clone(), EthereumEndpoint::new(url, l1_core_address))),
+            config.quorum,
+        )?;
         // Check if contract exists
-        if !provider
-            .get_code_at(config.l1_core_address)
+        if !endpoints
+            .call(|endpoint| async move { endpoint.provider.get_code_at(l1_core_address).await })
             .await
             .map_err(|e| -> SettlementClientError { EthereumClientError::Rpc(e.to_string()).into() })?
             .is_empty()
         {
-            let contract = StarknetCoreContract::new(config.l1_core_address, provider.clone());
-            Ok(Self { provider: Arc::new(provider), l1_core_contract: contract })
+            Ok(Self { endpoints: Arc::new(endpoints), confirmation_policy: config.confirmation_policy })
         } else {
             Err(SettlementClientError::Ethereum(EthereumClientError::Contract(
                 "Core contract not found at given address".into(),
             )))
         }
     }
+
+    /// Returns the `E` events emitted by the core contract in the L1 blocks `from_block..=to_block`.
+    pub(crate) async fn get_core_contract_events<E: SolEvent>(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<(E, Log)>, SettlementClientError> {
+        self.endpoints
+            .call(|endpoint| async move {
+                endpoint.l1_core_contract.event_filter::<E>().from_block(from_block).to_block(to_block).query().await
+            })
+            .await
+            .map_err(|e| -> SettlementClientError {
+                EthereumClientError::ArchiveRequired(format!(
+                    "Could not fetch events, archive node may be required: {}",
+                    e
+                ))
+                .into()
+            })
+    }
+
+    /// Processes the state updates of the L1 blocks that got confirmed since `next_block`.
+    async fn process_confirmed_state_updates(
+        &self,
+        next_block: &mut u64,
+        reorgs: &mut ReorgDetector,
+        worker: &StateUpdateWorker,
+    ) -> Result<(), SettlementClientError> {
+        reorgs.check(&self.endpoints).await?;
+
+        let confirmed_block = self.get_confirmed_block_number().await?;
+        if confirmed_block < *next_block {
+            return Ok(());
+        }
+
+        for (event, log) in
+            self.get_core_contract_events::<StarknetCoreContract::LogStateUpdate>(*next_block, confirmed_block).await?
+        {
+            let format_event = convert_log_state_update(event).map_err(|e| -> SettlementClientError {
+                EthereumClientError::StateUpdate { message: format!("Failed to convert log state update: {e:#}") }
+                    .into()
+            })?;
+
+            reorgs.track(&log, format!("state update to global root {:#x}", format_event.global_root));
+
+            worker.update_state(format_event).map_err(|e| -> SettlementClientError {
+                EthereumClientError::StateUpdate { message: format!("Failed to update L1 state: {e:#}") }.into()
+            })?;
+        }
+
+        *next_block = confirmed_block + 1;
+        Ok(())
+    }
 }
 
 const HISTORY_SIZE: usize = 300; // Number of blocks to use for gas price calculation (approx. 1 hour at 12 sec block time)
 const POLL_INTERVAL: Duration = Duration::from_secs(5); // Interval between event polling attempts
-const EVENT_SEARCH_BLOCK_RANGE: u64 = 6000; // Number of blocks to search backwards for events (approx. 24h at 15 sec block time)
+pub(crate) const EVENT_SEARCH_BLOCK_RANGE: u64 = 6000; // Number of blocks to search backwards for events (approx. 24h at 15 sec block time)
 
 #[async_trait]
 impl SettlementClientTrait for EthereumClient {
@@ -87,26 +212,39 @@ impl SettlementClientTrait for EthereumClient {
 
     /// Retrieves the latest Ethereum block number
     async fn get_latest_block_number(&self) -> Result<u64, SettlementClientError> {
-        self.provider
-            .get_block_number()
+        self.endpoints
+            .call(|endpoint| async move { endpoint.provider.get_block_number().await })
             .await
             .map(|n| n.as_u64())
             .map_err(|e| -> SettlementClientError { EthereumClientError::Rpc(e.to_string()).into() })
     }
 
+    /// Retrieves the latest Ethereum block number satisfying the confirmation policy of the client
+    async fn get_confirmed_block_number(&self) -> Result<u64, SettlementClientError> {
+        match self.confirmation_policy {
+            ConfirmationPolicy::Latest => self.get_latest_block_number().await,
+            ConfirmationPolicy::Depth(depth) => Ok(self.get_latest_block_number().await?.saturating_sub(depth)),
+            ConfirmationPolicy::Safe => get_block_number_with_tag(&self.endpoints, BlockNumberOrTag::Safe).await,
+            ConfirmationPolicy::Finalized => {
+                get_block_number_with_tag(&self.endpoints, BlockNumberOrTag::Finalized).await
+            }
+        }
+    }
+
     /// Get the block number of the last occurrence of the LogStateUpdate event.
     async fn get_last_event_block_number(&self) -> Result<u64, SettlementClientError> {
-        let latest_block = self.get_latest_block_number().await?;
+        let latest_block = self.get_confirmed_block_number().await?;
 
         // Assuming an avg Block time of 15sec we check for a LogStateUpdate occurence in the last ~24h
         let filter = Filter::new()
             .from_block(latest_block.saturating_sub(EVENT_SEARCH_BLOCK_RANGE))
             .to_block(latest_block)
-            .address(*self.l1_core_contract.address());
+            .address(*self.endpoints.current().l1_core_contract.address());
 
+        let filter = &filter;
         let logs = self
-            .provider
-            .get_logs(&filter)
+            .endpoints
+            .call(|endpoint| async move { endpoint.provider.get_logs(filter).await })
             .await
             .map_err(|e| -> SettlementClientError { EthereumClientError::Rpc(e.to_string()).into() })?;
 
@@ -128,49 +266,19 @@ impl SettlementClientTrait for EthereumClient {
         }
     }
 
+    /// Reads the state of the core contract at the confirmed block, from enough endpoints to reach the quorum.
     async fn get_current_core_contract_state(&self) -> Result<StateUpdate, SettlementClientError> {
-        // Get the latest block_n first, to guard against the case when the contract state changed in between the calls following calls.
-        let latest_block_n = self.get_latest_block_number().await?;
-
-        let block_number =
-            self.l1_core_contract.stateBlockNumber().block(BlockId::number(latest_block_n)).call().await.map_err(
-                |e| -> SettlementClientError {
-                    EthereumClientError::Contract(format!("Failed to get state block number: {e:#}")).into()
-                },
-            )?;
-        // when the block 0 is not settled yet, this should be prev block number, this would be the output from the snos as well while
-        // executing the block 0.
-        // link: https://github.com/starkware-libs/cairo-lang/blob/master/src/starkware/starknet/solidity/StarknetState.sol#L32
-        let block_number: Option<u64> = if block_number._0 == I256::MINUS_ONE {
-            None // initial contract state
-        } else {
-            Some(block_number._0.as_u64())
-        };
-
-        let global_root =
-            self.l1_core_contract.stateRoot().block(BlockId::number(latest_block_n)).call().await.map_err(
-                |e| -> SettlementClientError {
-                    EthereumClientError::Contract(format!("Failed to get state root: {e:#}")).into()
-                },
-            )?;
-        let global_root = global_root._0.to_felt();
-
-        let block_hash =
-            self.l1_core_contract.stateBlockHash().block(BlockId::number(latest_block_n)).call().await.map_err(
-                |e| -> SettlementClientError {
-                    EthereumClientError::Contract(format!("Failed to get state block number: {e:#}")).into()
-                },
-            )?;
-        let block_hash = block_hash._0.to_felt();
+        // Get the confirmed block_n first, to guard against the case when the contract state changed in between the calls following calls.
+        let latest_block_n = self.get_confirmed_block_number().await?;
 
-        Ok(StateUpdate { global_root, block_number, block_hash })
+        self.endpoints.call_quorum(|endpoint| endpoint.get_core_contract_state(latest_block_n)).await
     }
 
     /// Listen for state update events from the L1 core contract and process them
     ///
     /// This function runs a blocking loop that continuously polls for new state update events.
-    /// It will run until the context is cancelled. Each event is processed and used to update
-    /// the L1 state in the backend database.
+    /// It will run until the context is cancelled. Each event is processed once its L1 block satisfies the
+    /// confirmation policy, and used to update the L1 state in the backend database.
     ///
     /// # Note
     /// This is a long-running function that blocks the current task until cancelled.
@@ -179,50 +287,40 @@ impl SettlementClientTrait for EthereumClient {
         mut ctx: ServiceContext,
         worker: StateUpdateWorker,
     ) -> Result<(), SettlementClientError> {
-        let event_filter = self.l1_core_contract.event_filter::<StarknetCoreContract::LogStateUpdate>();
-
-        let mut event_stream = match ctx.run_until_cancelled(event_filter.watch()).await {
-            Some(res) => res
-                .map_err(|e| -> SettlementClientError {
-                    EthereumClientError::EventStream { message: format!("Failed to watch events: {}", e) }.into()
-                })?
-                .into_stream(),
+        // The initial state has been read at the confirmed block, we only need the events that come after it.
+        let mut next_block = match ctx.run_until_cancelled(self.get_confirmed_block_number()).await {
+            Some(res) => res? + 1,
             None => return Ok(()),
         };
+        let mut reorgs = ReorgDetector::default();
 
         // Create a ticker that fires at regular intervals
         let mut interval = tokio::time::interval(POLL_INTERVAL);
 
         // Process events in a loop until the context is cancelled
-        while let Some(Some(event_result)) = ctx
+        while let Some(res) = ctx
             .run_until_cancelled(async {
                 interval.tick().await; // Wait for the next interval tick
-                event_stream.next().await
+                self.process_confirmed_state_updates(&mut next_block, &mut reorgs, &worker).await
             })
             .await
         {
-            let log = event_result.map_err(|e| -> SettlementClientError {
-                EthereumClientError::EventStream { message: format!("Failed to process event: {e:#}") }.into()
-            })?;
-
-            let format_event = convert_log_state_update(log.0.clone()).map_err(|e| -> SettlementClientError {
-                EthereumClientError::StateUpdate { message: format!("Failed to convert log state update: {e:#}") }
-                    .into()
-            })?;
-
-            worker.update_state(format_event).map_err(|e| -> SettlementClientError {
-                EthereumClientError::StateUpdate { message: format!("Failed to update L1 state: {e:#}") }.into()
-            })?;
+            res?;
         }
 
         Ok(())
     }
 
     async fn get_gas_prices(&self) -> Result<(u128, u128), SettlementClientError> {
-        let block_number = self.get_latest_block_number().await?;
+        let block_number = self.get_confirmed_block_number().await?;
         let fee_history = self
-            .provider
-            .get_fee_history(HISTORY_SIZE as u64, BlockNumberOrTag::Number(block_number), &[])
+            .endpoints
+            .call(|endpoint| async move {
+                endpoint
+                    .provider
+                    .get_fee_history(HISTORY_SIZE as u64, BlockNumberOrTag::Number(block_number), &[])
+                    .await
+            })
             .await
             .map_err(|e| -> SettlementClientError {
                 EthereumClientError::GasPriceCalculation {
@@ -294,38 +392,83 @@ impl SettlementClientTrait for EthereumClient {
     ///     - timestamp of the cancellation if it has been cancelled
     /// - An Error if the call fail
     async fn get_l1_to_l2_message_cancellations(&self, msg_hash: &[u8]) -> Result<Felt, SettlementClientError> {
-        let cancellation_timestamp =
-            self.l1_core_contract.l1ToL2MessageCancellations(B256::from_slice(msg_hash)).call().await.map_err(
-                |e| -> SettlementClientError {
-                    EthereumClientError::L1ToL2Messaging {
-                        message: format!("Failed to check message cancellation status: {}", e),
-                    }
-                    .into()
-                },
-            )?;
+        let msg_hash = B256::from_slice(msg_hash);
+        let cancellation_timestamp = self
+            .endpoints
+            .call(|endpoint| async move { endpoint.l1_core_contract.l1ToL2MessageCancellations(msg_hash).call().await })
+            .await
+            .map_err(|e| -> SettlementClientError {
+                EthereumClientError::L1ToL2Messaging {
+                    message: format!("Failed to check message cancellation status: {}", e),
+                }
+                .into()
+            })?;
 
         Ok(cancellation_timestamp._0.to_felt())
     }
 
+    async fn get_messages_to_l1_events(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<MessageToL1Event>, SettlementClientError> {
+        let logged =
+            self.get_core_contract_events::<StarknetCoreContract::LogMessageToL1>(from_block, to_block).await?;
+        let consumed =
+            self.get_core_contract_events::<StarknetCoreContract::ConsumedMessageToL1>(from_block, to_block).await?;
+
+        let message_hash = |from_address: U256, to_address: Address, payload: Vec<U256>| {
+            MsgToL1 {
+                from_address: from_address.to_felt(),
+                to_address: Felt::from_bytes_be_slice(to_address.as_slice()),
+                payload: payload.into_iter().map(ToFelt::to_felt).collect(),
+            }
+            .compute_hash()
+        };
+        Ok(logged
+            .into_iter()
+            .map(|(event, _)| MessageToL1Event::Logged(message_hash(event.fromAddress, event.toAddress, event.payload)))
+            .chain(consumed.into_iter().map(|(event, _)| {
+                MessageToL1Event::Consumed(message_hash(event.fromAddress, event.toAddress, event.payload))
+            }))
+            .collect())
+    }
+
+    /// Binary search of the first block at which the core contract has code.
+    async fn get_core_contract_deployment_block(&self) -> Result<u64, SettlementClientError> {
+        let (mut low, mut high) = (0, self.get_latest_block_number().await?);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let code = self
+                .endpoints
+                .call(|endpoint| async move {
+                    endpoint
+                        .provider
+                        .get_code_at(*endpoint.l1_core_contract.address())
+                        .block_id(BlockId::number(mid))
+                        .await
+                })
+                .await
+                .map_err(|e| -> SettlementClientError {
+                    EthereumClientError::ArchiveRequired(format!(
+                        "Could not get the core contract code at block {mid}, archive node may be required: {e}"
+                    ))
+                    .into()
+                })?;
+            if code.is_empty() {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
     async fn get_messaging_stream(
         &self,
         last_synced_event_block: LastSyncedEventBlock,
     ) -> Result<Self::StreamType, SettlementClientError> {
-        let filter = self.l1_core_contract.event_filter::<LogMessageToL2>();
-        let event_stream = filter
-            .from_block(last_synced_event_block.block_number)
-            .to_block(BlockNumberOrTag::Finalized)
-            .watch()
-            .await
-            .map_err(|e| -> SettlementClientError {
-                EthereumClientError::ArchiveRequired(format!(
-                    "Could not fetch events, archive node may be required: {}",
-                    e
-                ))
-                .into()
-            })?;
-
-        Ok(EthereumEventStream::new(event_stream))
+        Ok(EthereumEventStream::new(self.clone(), last_synced_event_block.block_number))
     }
 }
 
@@ -360,10 +503,11 @@ pub mod eth_client_getter_test {
 
     pub fn create_ethereum_client(url: String) -> EthereumClient {
         let rpc_url: Url = url.parse().expect("issue while parsing URL");
-        let provider = ProviderBuilder::new().on_http(rpc_url.clone());
         let address = Address::parse_checksummed(CORE_CONTRACT_ADDRESS, None).unwrap();
-        let contract = StarknetCoreContract::new(address, provider.clone());
-        EthereumClient { provider: Arc::new(provider), l1_core_contract: contract }
+        EthereumClient {
+            endpoints: Arc::new(EndpointPool::single(rpc_url.clone(), EthereumEndpoint::new(rpc_url, address))),
+            confirmation_policy: ConfirmationPolicy::Latest,
+        }
     }
 
     #[tokio::test]
@@ -374,7 +518,12 @@ pub mod eth_client_getter_test {
         let rpc_url: Url = get_anvil_url().parse().unwrap();
         let core_contract_address = Address::parse_checksummed(INVALID_CORE_CONTRACT_ADDRESS, None)
             .expect("Should parse valid Ethereum address in test");
-        let ethereum_client_config = EthereumClientConfig { url: rpc_url, l1_core_address: core_contract_address };
+        let ethereum_client_config = EthereumClientConfig {
+            urls: vec![rpc_url],
+            l1_core_address: core_contract_address,
+            quorum: 1,
+            confirmation_policy: ConfirmationPolicy::Latest,
+        };
         let new_client_result = EthereumClient::new(ethereum_client_config).await;
         assert!(new_client_result.is_err(), "EthereumClient::new should fail with an invalid core contract address");
     }
@@ -382,8 +531,14 @@ pub mod eth_client_getter_test {
     #[tokio::test]
     async fn get_latest_block_number_works() {
         let eth_client = create_ethereum_client(get_anvil_url());
-        let block_number =
-            eth_client.provider.get_block_number().await.expect("issue while fetching the block number").as_u64();
+        let block_number = eth_client
+            .endpoints
+            .current()
+            .provider
+            .get_block_number()
+            .await
+            .expect("issue while fetching the block number")
+            .as_u64();
         assert_eq!(block_number, L1_BLOCK_NUMBER, "provider unable to get the correct block number");
     }
 
@@ -431,17 +586,17 @@ pub mod eth_client_getter_test {
         });
 
         // Set up client with mock server
-        let config = EthereumClientConfig {
-            url: server.url("/").parse().unwrap(),
-            l1_core_address: Address::parse_checksummed("0xc662c410C0ECf747543f5bA90660f6ABeBD9C8c4", None).unwrap(),
+        let url: Url = server.url("/").parse().unwrap();
+        let l1_core_address = Address::parse_checksummed("0xc662c410C0ECf747543f5bA90660f6ABeBD9C8c4", None).unwrap();
+        let eth_client = EthereumClient {
+            endpoints: Arc::new(EndpointPool::single(url.clone(), EthereumEndpoint::new(url, l1_core_address))),
+            confirmation_policy: ConfirmationPolicy::Latest,
         };
 
-        let provider = ProviderBuilder::new().on_http(config.url);
-        let contract = StarknetCoreContract::new(config.l1_core_address, provider.clone());
-        let eth_client = EthereumClient { provider: Arc::new(provider), l1_core_contract: contract };
-
         // Call contract and verify we get -1 as int256
         let block_number = eth_client
+            .endpoints
+            .current()
             .l1_core_contract
             .stateBlockNumber()
             .block(BlockId::number(10000))
@@ -468,7 +623,9 @@ mod l1_messaging_tests {
 
     use self::DummyContract::DummyContractInstance;
     use crate::client::SettlementClientTrait;
-    use crate::eth::{EthereumClient, StarknetCoreContract};
+    use crate::endpoints::EndpointPool;
+    use crate::eth::confirmation::ConfirmationPolicy;
+    use crate::eth::{EthereumClient, EthereumEndpoint};
     use crate::messaging::{sync, L1toL2MessagingEventData};
     use alloy::{
         hex::FromHex,
@@ -601,15 +758,18 @@ mod l1_messaging_tests {
 
         // Set up provider
         let rpc_url: Url = anvil.endpoint().parse().expect("issue while parsing");
-        let provider = ProviderBuilder::new().on_http(rpc_url);
+        let provider = ProviderBuilder::new().on_http(rpc_url.clone());
 
         // Set up dummy contract
         let contract = DummyContract::deploy(provider.clone()).await.unwrap();
 
-        let core_contract = StarknetCoreContract::new(*contract.address(), provider.clone());
-
-        let eth_client =
-            EthereumClient { provider: Arc::new(provider.clone()), l1_core_contract: core_contract.clone() };
+        let eth_client = EthereumClient {
+            endpoints: Arc::new(EndpointPool::single(
+                rpc_url.clone(),
+                EthereumEndpoint::new(rpc_url, *contract.address()),
+            )),
+            confirmation_policy: ConfirmationPolicy::Latest,
+        };
 
         TestRunner { anvil, db_service: db, dummy_contract: contract, eth_client, mempool }
     }
@@ -857,7 +1017,7 @@ mod l1_messaging_tests {
 mod eth_client_event_subscription_test {
     use super::*;
     use crate::eth::event::EthereumEventStream;
-    use crate::eth::{EthereumClient, EthereumClientConfig, StarknetCoreContract};
+    use crate::eth::{EthereumClient, EthereumClientConfig};
     use crate::gas_price::L1BlockMetrics;
     use crate::state_update::state_update_worker;
     use alloy::{node_bindings::Anvil, providers::ProviderBuilder, sol};
@@ -925,13 +1085,17 @@ mod eth_client_event_subscription_test {
         let backend = MadaraBackend::open_for_testing(ChainConfig::madara_test().into());
 
         let rpc_url: Url = anvil.endpoint().parse().expect("issue while parsing");
-        let provider = ProviderBuilder::new().on_http(rpc_url);
+        let provider = ProviderBuilder::new().on_http(rpc_url.clone());
 
         let contract = DummyContract::deploy(provider.clone()).await.unwrap();
-        let core_contract = StarknetCoreContract::new(*contract.address(), provider.clone());
 
-        let eth_client =
-            EthereumClient { provider: Arc::new(provider.clone()), l1_core_contract: core_contract.clone() };
+        let eth_client = EthereumClient {
+            endpoints: Arc::new(EndpointPool::single(
+                rpc_url.clone(),
+                EthereumEndpoint::new(rpc_url, *contract.address()),
+            )),
+            confirmation_policy: ConfirmationPolicy::Latest,
+        };
         let l1_block_metrics = L1BlockMetrics::register().unwrap();
         let (snd, mut recv) = tokio::sync::watch::channel(None);
 
@@ -1,6 +1,9 @@
 use crate::client::SettlementClientTrait;
+use anyhow::Context;
 use bigdecimal::BigDecimal;
 use mc_mempool::{GasPriceProvider, L1DataProvider};
+use mp_chain_config::ETH_DECIMALS;
+use mp_oracle::OraclePrice;
 use std::sync::Arc;
 use std::time::{Duration, UNIX_EPOCH};
 
@@ -143,26 +146,25 @@ where
 
     // fetch eth/strk price and update
     if let Some(oracle_provider) = &l1_gas_provider.oracle_provider {
-        let (eth_strk_price, decimals) = oracle_provider.fetch_eth_strk_price().await.map_err(|e| {
+        let price = oracle_provider.fetch_eth_strk_price().await.map_err(|e| {
             SettlementClientError::PriceOracle(format!("Failed to fetch ETH/STRK price from oracle: {}", e))
         })?;
-
-        let strk_gas_price = (BigDecimal::new(eth_gas_price.into(), decimals.into())
-            / BigDecimal::new(eth_strk_price.into(), decimals.into()))
-        .as_bigint_and_exponent();
-
-        let strk_data_gas_price = (BigDecimal::new(avg_blob_base_fee.into(), decimals.into())
-            / BigDecimal::new(eth_strk_price.into(), decimals.into()))
-        .as_bigint_and_exponent();
-
+        if let Some(max_age) = l1_gas_provider.oracle_max_age {
+            price.ensure_fresh(max_age).map_err(|e| {
+                SettlementClientError::PriceOracle(format!("Rejected ETH/STRK price from oracle: {}", e))
+            })?;
+        }
+        let decimals = l1_gas_provider.native_fee_token_decimals;
         l1_gas_provider.update_strk_l1_gas_price(
-            strk_gas_price.0.to_str_radix(10).parse::<u128>().map_err(|e| {
-                SettlementClientError::ConversionError(format!("Failed to parse STRK gas price: {}", e))
+            wei_to_native_fee_token(eth_gas_price, &price, decimals).map_err(|e| {
+                SettlementClientError::ConversionError(format!("Failed to compute STRK gas price: {}", e))
+            })?,
+        );
+        l1_gas_provider.update_strk_l1_data_gas_price(
+            wei_to_native_fee_token(avg_blob_base_fee, &price, decimals).map_err(|e| {
+                SettlementClientError::ConversionError(format!("Failed to compute STRK data gas price: {}", e))
             })?,
         );
-        l1_gas_provider.update_strk_l1_data_gas_price(strk_data_gas_price.0.to_str_radix(10).parse::<u128>().map_err(
-            |e| SettlementClientError::ConversionError(format!("Failed to parse STRK data gas price: {}", e)),
-        )?);
     }
 
     l1_gas_provider.update_last_update_timestamp();
@@ -182,6 +184,21 @@ where
     Ok(())
 }
 
+/// Converts a settlement layer gas price in wei into the smallest unit of the native fee token, using the price of the
+/// oracles in fee tokens per ETH. The price of the oracles is for a token with as many decimals as ETH, it is scaled
+/// to the decimals of the native fee token. The result is rounded down.
+fn wei_to_native_fee_token(
+    gas_price: u128,
+    price: &OraclePrice,
+    native_fee_token_decimals: u8,
+) -> anyhow::Result<u128> {
+    let converted = BigDecimal::new(gas_price.into(), 0)
+        * BigDecimal::new(price.price.into(), price.decimals.into())
+        * BigDecimal::new(1.into(), i64::from(ETH_DECIMALS) - i64::from(native_fee_token_decimals));
+    let (converted, _) = converted.with_scale(0).into_bigint_and_exponent();
+    converted.to_str_radix(10).parse::<u128>().with_context(|| format!("Converted gas price {converted} out of range"))
+}
+
 async fn update_l1_block_metrics(
     block_number: u64,
     l1_block_metrics: Arc<L1BlockMetrics>,
@@ -201,6 +218,26 @@ async fn update_l1_block_metrics(
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wei_to_native_fee_token() {
+        // 4 fee tokens per ETH.
+        let price = OraclePrice { price: 4_000, decimals: 3, timestamp: None };
+        assert_eq!(wei_to_native_fee_token(1_000_000, &price, 18).unwrap(), 4_000_000);
+        // A token with 6 decimals has 10^12 times less base units than a token with 18 decimals.
+        assert_eq!(wei_to_native_fee_token(10_000_000_000_000, &price, 6).unwrap(), 40);
+        // Rounded down.
+        assert_eq!(wei_to_native_fee_token(1_000_001, &price, 12).unwrap(), 4);
+        assert_eq!(wei_to_native_fee_token(1_000, &price, 20).unwrap(), 400_000);
+        // 2500.5 fee tokens per ETH.
+        let price = OraclePrice { price: 25_005, decimals: 1, timestamp: None };
+        assert_eq!(wei_to_native_fee_token(2, &price, 18).unwrap(), 5_001);
+    }
+}
+
 #[cfg(test)]
 mod eth_client_gas_price_worker_test {
     use super::*;
@@ -1,7 +1,9 @@
 pub mod client;
+pub mod endpoints;
 pub mod error;
 pub mod eth;
 pub mod gas_price;
+pub mod messages_to_l1;
 pub mod messaging;
 pub mod starknet;
 pub mod state_update;
@@ -0,0 +1,155 @@
+use crate::client::{ClientType, SettlementClientTrait};
+use crate::error::SettlementClientError;
+use crate::messaging::L1toL2MessagingEventData;
+use futures::Stream;
+use mc_db::MadaraBackend;
+use mp_utils::service::ServiceContext;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Number of settlement layer blocks scanned for L2 to L1 message events per query.
+const INDEX_BLOCK_RANGE: u64 = 6000;
+const POLL_INTERVAL: Duration = Duration::from_secs(12);
+
+/// Indexes the settlement layer blocks following the last indexed one, up to the confirmed block and at most
+/// [`INDEX_BLOCK_RANGE`] blocks at a time. Returns whether there are more confirmed blocks left to index.
+///
+/// The first time, indexing starts at `start_block` when it is set, or at the block in which the core contract was
+/// deployed.
+async fn index_messages_to_l1_once<C, S>(
+    backend: &MadaraBackend,
+    settlement_client: &dyn SettlementClientTrait<Config = C, StreamType = S>,
+    start_block: Option<u64>,
+) -> Result<bool, SettlementClientError>
+where
+    S: Stream<Item = Result<L1toL2MessagingEventData, SettlementClientError>> + Send + 'static,
+{
+    let confirmed_block = settlement_client.get_confirmed_block_number().await?;
+    let from_block = match backend.get_messages_to_l1_last_indexed_l1_block().map_err(|e| {
+        SettlementClientError::DatabaseError(format!("Failed to get last indexed L2 to L1 messages block: {}", e))
+    })? {
+        Some(block_n) => block_n + 1,
+        None => match start_block {
+            Some(block_n) => block_n,
+            None => settlement_client.get_core_contract_deployment_block().await?,
+        },
+    };
+    if from_block > confirmed_block {
+        return Ok(false);
+    }
+
+    let to_block = confirmed_block.min(from_block + INDEX_BLOCK_RANGE - 1);
+    let events = settlement_client.get_messages_to_l1_events(from_block, to_block).await?;
+    backend
+        .store_messages_to_l1_events(&events, to_block)
+        .map_err(|e| SettlementClientError::DatabaseError(format!("Failed to store L2 to L1 message events: {}", e)))?;
+    tracing::debug!("Indexed {} L2 to L1 message events in blocks {from_block}..={to_block}", events.len());
+
+    Ok(to_block < confirmed_block)
+}
+
+/// Keeps track of the L2 to L1 messages that were made consumable by a state update, or consumed, on the settlement
+/// layer. The L2 side of the messages is indexed by the database when blocks are stored.
+///
+/// Only Ethereum settlement layers are supported.
+pub async fn messages_to_l1_worker<C, S>(
+    backend: Arc<MadaraBackend>,
+    settlement_client: Arc<dyn SettlementClientTrait<Config = C, StreamType = S>>,
+    start_block: Option<u64>,
+    mut ctx: ServiceContext,
+) -> Result<(), SettlementClientError>
+where
+    S: Stream<Item = Result<L1toL2MessagingEventData, SettlementClientError>> + Send + 'static,
+{
+    if let ClientType::Starknet = settlement_client.get_client_type() {
+        tracing::info!("L2 to L1 messages are not indexed on a Starknet settlement layer");
+        return Ok(());
+    }
+
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    while ctx.run_until_cancelled(interval.tick()).await.is_some() {
+        // Catch up with the confirmed block before waiting for the next tick.
+        loop {
+            match ctx
+                .run_until_cancelled(index_messages_to_l1_once(&backend, settlement_client.as_ref(), start_block))
+                .await
+            {
+                None => return Ok(()),
+                Some(Ok(true)) => continue,
+                Some(Ok(false)) => break,
+                Some(Err(e)) => {
+                    tracing::warn!("Failed to index L2 to L1 messages, retrying later: {e:#}");
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{
+        test_types::{DummyConfig, DummyStream},
+        MockSettlementClientTrait,
+    };
+    use mc_db::message_to_l1_db::{MessageToL1Event, MessageToL1Settlement};
+    use mp_chain_config::ChainConfig;
+    use mp_receipt::Hash256;
+
+    #[tokio::test]
+    async fn test_index_messages_to_l1_in_ranges() {
+        let backend = MadaraBackend::open_for_testing(Arc::new(ChainConfig::madara_test()));
+        let message = Hash256::from_bytes([1; 32]);
+
+        let mut client = MockSettlementClientTrait::default();
+        client.expect_get_confirmed_block_number().returning(|| Ok(INDEX_BLOCK_RANGE + 10));
+        client.expect_get_core_contract_deployment_block().never();
+        let logged = message.clone();
+        client
+            .expect_get_messages_to_l1_events()
+            .withf(|from, to| (*from, *to) == (0, INDEX_BLOCK_RANGE - 1))
+            .times(1)
+            .returning(move |_, _| Ok(vec![MessageToL1Event::Logged(logged.clone())]));
+        let consumed = message.clone();
+        client
+            .expect_get_messages_to_l1_events()
+            .withf(|from, to| (*from, *to) == (INDEX_BLOCK_RANGE, INDEX_BLOCK_RANGE + 10))
+            .times(1)
+            .returning(move |_, _| Ok(vec![MessageToL1Event::Consumed(consumed.clone())]));
+        let client: &dyn SettlementClientTrait<Config = DummyConfig, StreamType = DummyStream> = &client;
+
+        assert!(index_messages_to_l1_once(&backend, client, Some(0)).await.unwrap());
+        assert!(!index_messages_to_l1_once(&backend, client, Some(0)).await.unwrap());
+        // Nothing left to index.
+        assert!(!index_messages_to_l1_once(&backend, client, Some(0)).await.unwrap());
+
+        assert_eq!(backend.get_messages_to_l1_last_indexed_l1_block().unwrap(), Some(INDEX_BLOCK_RANGE + 10));
+        assert_eq!(
+            backend.get_message_to_l1_settlement(&message).unwrap(),
+            MessageToL1Settlement { logged: 1, consumed: 1 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_index_messages_to_l1_starts_at_core_contract_deployment() {
+        let backend = MadaraBackend::open_for_testing(Arc::new(ChainConfig::madara_test()));
+
+        let mut client = MockSettlementClientTrait::default();
+        client.expect_get_confirmed_block_number().returning(|| Ok(150));
+        client.expect_get_core_contract_deployment_block().times(1).returning(|| Ok(100));
+        client
+            .expect_get_messages_to_l1_events()
+            .withf(|from, to| (*from, *to) == (100, 150))
+            .times(1)
+            .returning(|_, _| Ok(vec![]));
+        let client: &dyn SettlementClientTrait<Config = DummyConfig, StreamType = DummyStream> = &client;
+
+        assert!(!index_messages_to_l1_once(&backend, client, None).await.unwrap());
+        assert_eq!(backend.get_messages_to_l1_last_indexed_l1_block().unwrap(), Some(150));
+    }
+}
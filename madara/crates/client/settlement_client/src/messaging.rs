@@ -211,10 +211,10 @@ where
 
                     let block_sent =
                         LastSyncedEventBlock::new(event_data.block_number, event_data.event_index.unwrap_or(0));
-                    backend.messaging_update_last_synced_l1_block_with_event(block_sent).map_err(|e| {
+                    backend.messaging_update_last_synced_l1_block_with_event(block_sent.clone()).map_err(|e| {
                         SettlementClientError::DatabaseError(format!("Failed to update last synced block: {}", e))
                     })?;
-                    backend.set_l1_messaging_nonce(tx_nonce).map_err(|e| {
+                    backend.set_l1_messaging_nonce_from_event(tx_nonce, &block_sent).map_err(|e| {
                         SettlementClientError::DatabaseError(format!("Failed to set messaging nonce: {}", e))
                     })?;
                 }
@@ -307,8 +307,10 @@ async fn process_message(
         .await
         .map_err(|e| SettlementClientError::SubmitTx(format!("Failed to accept transaction in mempool: {e:#}")))?;
     // HERMAN TODO: Actually this should be updated after the tx l1 handler is executed
+    // The event is recorded so that the message is processed again if the block including it is reverted.
+    let event_block = LastSyncedEventBlock::new(event.block_number, event.event_index.unwrap_or(0));
     backend
-        .set_l1_messaging_nonce(tx_nonce)
+        .set_l1_messaging_nonce_from_event(tx_nonce, &event_block)
         .map_err(|e| SettlementClientError::DatabaseError(format!("Failed to set nonce in process_message: {}", e)))?;
     Ok(Some(res.transaction_hash))
 }
@@ -17,6 +17,7 @@
 //! we are using the term "L1-to-L2 messaging" in the name. This is temporary and will be changed
 //! in the future.
 
+use crate::endpoints::EndpointPool;
 use crate::error::SettlementClientError;
 use crate::messaging::L1toL2MessagingEventData;
 use crate::starknet::error::StarknetClientError;
@@ -50,8 +51,8 @@ type FutureType = Pin<Box<dyn Future<Output = anyhow::Result<(Option<EmittedEven
 ///
 /// PS: As of now the event stream is for L1-to-L2 messaging events.
 pub struct StarknetEventStream {
-    /// The Starknet JSON-RPC client used to fetch events.
-    provider: Arc<JsonRpcClient<HttpTransport>>,
+    /// The Starknet JSON-RPC endpoints used to fetch events.
+    endpoints: Arc<EndpointPool<JsonRpcClient<HttpTransport>>>,
 
     /// The filter used to specify which events to retrieve.
     filter: EventFilter,
@@ -67,19 +68,23 @@ pub struct StarknetEventStream {
 }
 
 impl StarknetEventStream {
-    /// Creates a new `StarknetEventStream` with the specified provider, filter, and polling interval.
+    /// Creates a new `StarknetEventStream` with the specified endpoints, filter, and polling interval.
     ///
     /// # Arguments
     ///
-    /// * `provider` - Arc-wrapped Starknet JSON-RPC client to use for fetching events
+    /// * `endpoints` - Arc-wrapped Starknet JSON-RPC endpoints to use for fetching events
     /// * `filter` - Event filter that specifies starting block and ending block, event keys are fixed (Because we are fetching L1-to-L2 messaging events only)
     /// * `polling_interval` - Time interval between consecutive polling attempts
     ///
     /// # Returns
     ///
     /// A new `StarknetEventStream` instance configured with the provided parameters.
-    pub fn new(provider: Arc<JsonRpcClient<HttpTransport>>, filter: EventFilter, polling_interval: Duration) -> Self {
-        Self { provider, filter, processed_events: HashSet::new(), future: None, polling_interval }
+    pub fn new(
+        endpoints: Arc<EndpointPool<JsonRpcClient<HttpTransport>>>,
+        filter: EventFilter,
+        polling_interval: Duration,
+    ) -> Self {
+        Self { endpoints, filter, processed_events: HashSet::new(), future: None, polling_interval }
     }
 
     /// Fetches events from the Starknet provider based on the provided filter.
@@ -112,7 +117,7 @@ impl StarknetEventStream {
     ///
     /// # Arguments
     ///
-    /// * `provider` - Arc-wrapped Starknet JSON-RPC client
+    /// * `endpoints` - Arc-wrapped Starknet JSON-RPC endpoints
     /// * `filter` - Event filter specifying the block range to query
     /// * `processed_events` - Set of already processed event nonces for deduplication
     /// * `polling_interval` - Time interval between consecutive polling attempts
@@ -124,7 +129,7 @@ impl StarknetEventStream {
     /// - The updated `EventFilter` for the next polling attempt, with an adjusted block range
     ///   that slides forward to capture new blocks
     async fn fetch_l1_to_l2_messaging_events(
-        provider: Arc<JsonRpcClient<HttpTransport>>,
+        endpoints: Arc<EndpointPool<JsonRpcClient<HttpTransport>>>,
         mut filter: EventFilter,
         mut processed_events: HashSet<Felt>,
         polling_interval: Duration,
@@ -136,17 +141,22 @@ impl StarknetEventStream {
         let mut continuation_token: Option<String> = None;
 
         while !page_indicator {
-            let events = provider
-                .get_events(
-                    EventFilter {
-                        from_block: filter.from_block,
-                        to_block: filter.to_block,
-                        address: filter.address,
-                        keys: filter.keys.clone(),
-                    },
-                    continuation_token.clone(),
-                    1000,
-                )
+            let (filter_ref, continuation_token_ref) = (&filter, &continuation_token);
+            let events = endpoints
+                .call(|provider| async move {
+                    provider
+                        .get_events(
+                            EventFilter {
+                                from_block: filter_ref.from_block,
+                                to_block: filter_ref.to_block,
+                                address: filter_ref.address,
+                                keys: filter_ref.keys.clone(),
+                            },
+                            continuation_token_ref.clone(),
+                            1000,
+                        )
+                        .await
+                })
                 .await?;
 
             // Process this page of events immediately
@@ -169,7 +179,7 @@ impl StarknetEventStream {
 
         // If we get here, we didn't find any unprocessed events
         // So we update the filter and return None
-        let latest_block = provider.block_number().await?;
+        let latest_block = endpoints.call(|provider| async move { provider.block_number().await }).await?;
         filter.from_block = filter.to_block;
         filter.to_block = Some(BlockId::Number(latest_block));
 
@@ -192,13 +202,13 @@ impl Stream for StarknetEventStream {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         if self.future.is_none() {
-            let provider = self.provider.clone();
+            let endpoints = self.endpoints.clone();
             let filter = self.filter.clone();
             let processed_events = self.processed_events.clone();
             let polling_interval = self.polling_interval;
 
             let future = async move {
-                Self::fetch_l1_to_l2_messaging_events(provider, filter, processed_events, polling_interval).await
+                Self::fetch_l1_to_l2_messaging_events(endpoints, filter, processed_events, polling_interval).await
             };
 
             self.future = Some(Box::pin(future));
@@ -360,11 +370,11 @@ mod starknet_event_stream_tests {
     }
 
     fn create_stream(mock_server: &MockStarknetServer) -> StarknetEventStream {
-        let provider =
-            JsonRpcClient::new(HttpTransport::new(Url::from_str(&mock_server.url()).expect("Failed to parse URL")));
+        let url = Url::from_str(&mock_server.url()).expect("Failed to parse URL");
+        let provider = JsonRpcClient::new(HttpTransport::new(url.clone()));
 
         StarknetEventStream::new(
-            Arc::new(provider),
+            Arc::new(EndpointPool::single(url, provider)),
             EventFilter {
                 from_block: Some(BlockId::Number(0)),
                 to_block: Some(BlockId::Number(100)),
@@ -1,4 +1,5 @@
 use crate::client::{ClientType, SettlementClientTrait};
+use crate::endpoints::EndpointPool;
 use crate::error::SettlementClientError;
 use crate::messaging::L1toL2MessagingEventData;
 use crate::starknet::error::StarknetClientError;
@@ -7,12 +8,13 @@ use crate::state_update::{StateUpdate, StateUpdateWorker};
 use async_trait::async_trait;
 use bigdecimal::ToPrimitive;
 use mc_db::l1_db::LastSyncedEventBlock;
+use mc_db::message_to_l1_db::MessageToL1Event;
 use mp_utils::service::ServiceContext;
 use starknet_core::types::{BlockId, BlockTag, EmittedEvent, EventFilter, FunctionCall};
 use starknet_core::utils::get_selector_from_name;
 use starknet_crypto::poseidon_hash_many;
 use starknet_providers::jsonrpc::HttpTransport;
-use starknet_providers::{JsonRpcClient, Provider};
+use starknet_providers::{JsonRpcClient, Provider, ProviderError};
 use starknet_types_core::felt::Felt;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -32,21 +34,24 @@ const INITIAL_STATE_BLOCK_NUMBER: &str = "0x800000000000011000000000000000000000
 
 #[derive(Debug)]
 pub struct StarknetClient {
-    pub provider: Arc<JsonRpcClient<HttpTransport>>,
+    pub endpoints: Arc<EndpointPool<JsonRpcClient<HttpTransport>>>,
     pub l2_core_contract: Felt,
     pub processed_update_state_block: AtomicU64,
 }
 
 #[derive(Clone)]
 pub struct StarknetClientConfig {
-    pub url: Url,
+    /// Starknet rpc endpoints, in order of preference.
+    pub urls: Vec<Url>,
     pub l2_contract_address: Felt,
+    /// Number of endpoints that need to agree on the state of the core contract.
+    pub quorum: usize,
 }
 
 impl Clone for StarknetClient {
     fn clone(&self) -> Self {
         StarknetClient {
-            provider: Arc::clone(&self.provider),
+            endpoints: Arc::clone(&self.endpoints),
             l2_core_contract: self.l2_core_contract,
             processed_update_state_block: AtomicU64::new(self.processed_update_state_block.load(Ordering::Relaxed)),
         }
@@ -56,17 +61,24 @@ impl Clone for StarknetClient {
 // Add this new implementation block for constructor
 impl StarknetClient {
     pub async fn new(config: StarknetClientConfig) -> Result<Self, SettlementClientError> {
-        let provider = JsonRpcClient::new(HttpTransport::new(config.url));
+        let l2_contract_address = config.l2_contract_address;
+        let endpoints = EndpointPool::new(
+            config.urls.into_iter().map(|url| (url.clone(), JsonRpcClient::new(HttpTransport::new(url)))),
+            config.quorum,
+        )?;
         // Check if l2 contract exists
-        provider.get_class_at(BlockId::Tag(BlockTag::Latest), config.l2_contract_address).await.map_err(
-            |e| -> SettlementClientError {
+        endpoints
+            .call(|provider| async move {
+                provider.get_class_at(BlockId::Tag(BlockTag::Latest), l2_contract_address).await
+            })
+            .await
+            .map_err(|e| -> SettlementClientError {
                 StarknetClientError::NetworkConnection { message: format!("Failed to connect to L2 contract: {}", e) }
                     .into()
-            },
-        )?;
+            })?;
 
         Ok(Self {
-            provider: Arc::new(provider),
+            endpoints: Arc::new(endpoints),
             l2_core_contract: config.l2_contract_address,
             processed_update_state_block: AtomicU64::new(0), // Keeping this as 0 initially when client is initialized.
         })
@@ -86,10 +98,19 @@ impl SettlementClientTrait for StarknetClient {
     }
 
     async fn get_latest_block_number(&self) -> Result<u64, SettlementClientError> {
-        self.provider.block_number().await.map_err(|e| -> SettlementClientError {
-            StarknetClientError::NetworkConnection { message: format!("Failed to fetch latest block number: {}", e) }
+        self.endpoints.call(|provider| async move { provider.block_number().await }).await.map_err(
+            |e| -> SettlementClientError {
+                StarknetClientError::NetworkConnection {
+                    message: format!("Failed to fetch latest block number: {}", e),
+                }
                 .into()
-        })
+            },
+        )
+    }
+
+    /// Blocks accepted by the Starknet sequencer are not reorged, so every block is confirmed as soon as it exists.
+    async fn get_confirmed_block_number(&self) -> Result<u64, SettlementClientError> {
+        self.get_latest_block_number().await
     }
 
     async fn get_last_event_block_number(&self) -> Result<u64, SettlementClientError> {
@@ -140,8 +161,9 @@ impl SettlementClientTrait for StarknetClient {
         }
     }
 
+    /// Reads the state of the core contract from enough endpoints to reach the quorum.
     async fn get_current_core_contract_state(&self) -> Result<StateUpdate, SettlementClientError> {
-        let state = self.get_state_call().await?; // Returns (StateRoot, BlockNumber, BlockHash).
+        let state = self.endpoints.call_quorum(|provider| self.get_state_call(provider)).await?; // Returns (StateRoot, BlockNumber, BlockHash).
         let global_root = state[0];
         let block_number = if state[1] == Felt::from_hex(INITIAL_STATE_BLOCK_NUMBER).unwrap() {
             None
@@ -234,23 +256,21 @@ impl SettlementClientTrait for StarknetClient {
 
     async fn get_l1_to_l2_message_cancellations(&self, msg_hash: &[u8]) -> Result<Felt, SettlementClientError> {
         // function name taken from: https://github.com/keep-starknet-strange/piltover/blob/main/src/messaging/interface.cairo#L56
-        let call_res = self
-            .provider
-            .call(
-                FunctionCall {
-                    contract_address: self.l2_core_contract,
-                    entry_point_selector: get_selector_from_name("sn_to_appchain_messages").map_err(
-                        |e| -> SettlementClientError {
-                            StarknetClientError::L1ToL2Messaging {
-                                message: format!("Failed to get sn_to_appchain_messages selector: {}", e),
-                            }
-                            .into()
-                        },
-                    )?,
-                    calldata: vec![Felt::from_bytes_be_slice(msg_hash)],
+        let request = &FunctionCall {
+            contract_address: self.l2_core_contract,
+            entry_point_selector: get_selector_from_name("sn_to_appchain_messages").map_err(
+                |e| -> SettlementClientError {
+                    StarknetClientError::L1ToL2Messaging {
+                        message: format!("Failed to get sn_to_appchain_messages selector: {}", e),
+                    }
+                    .into()
                 },
-                BlockId::Tag(BlockTag::Pending),
-            )
+            )?,
+            calldata: vec![Felt::from_bytes_be_slice(msg_hash)],
+        };
+        let call_res = self
+            .endpoints
+            .call(|provider| async move { provider.call(request.clone(), BlockId::Tag(BlockTag::Pending)).await })
             .await
             .map_err(|e| -> SettlementClientError {
                 StarknetClientError::L1ToL2Messaging {
@@ -333,6 +353,26 @@ impl SettlementClientTrait for StarknetClient {
         Ok(result)
     }
 
+    /// Piltover hashes the messages sent to Starknet with poseidon rather than keccak, and registers them under
+    /// these hashes. We do not index them yet.
+    async fn get_messages_to_l1_events(
+        &self,
+        _from_block: u64,
+        _to_block: u64,
+    ) -> Result<Vec<MessageToL1Event>, SettlementClientError> {
+        Err(StarknetClientError::MessageProcessing {
+            message: "L2 to L1 messages are not indexed on a Starknet settlement layer".into(),
+        }
+        .into())
+    }
+
+    async fn get_core_contract_deployment_block(&self) -> Result<u64, SettlementClientError> {
+        Err(StarknetClientError::MessageProcessing {
+            message: "L2 to L1 messages are not indexed on a Starknet settlement layer".into(),
+        }
+        .into())
+    }
+
     async fn get_messaging_stream(
         &self,
         last_synced_event_block: LastSyncedEventBlock,
@@ -346,7 +386,7 @@ impl SettlementClientTrait for StarknetClient {
                     .into()
             })?]]),
         };
-        Ok(StarknetEventStream::new(self.provider.clone(), filter, Duration::from_secs(1)))
+        Ok(StarknetEventStream::new(Arc::clone(&self.endpoints), filter, Duration::from_secs(1)))
     }
 }
 
@@ -363,18 +403,18 @@ impl StarknetClient {
         let mut continuation_token: Option<String> = None;
 
         while !page_indicator {
+            let filter = &EventFilter {
+                from_block: Some(from_block),
+                to_block: Some(to_block),
+                address: Some(contract_address),
+                keys: Some(vec![keys.clone()]),
+            };
+            let continuation_token_ref = &continuation_token;
             let events = self
-                .provider
-                .get_events(
-                    EventFilter {
-                        from_block: Some(from_block),
-                        to_block: Some(to_block),
-                        address: Some(contract_address),
-                        keys: Some(vec![keys.clone()]),
-                    },
-                    continuation_token.clone(),
-                    1000,
-                )
+                .endpoints
+                .call(|provider| async move {
+                    provider.get_events(filter.clone(), continuation_token_ref.clone(), 1000).await
+                })
                 .await
                 .map_err(|e| -> SettlementClientError {
                     StarknetClientError::EventSubscription { message: format!("Failed to fetch events: {}", e) }.into()
@@ -401,9 +441,11 @@ impl StarknetClient {
             .collect()
     }
 
-    pub async fn get_state_call(&self) -> Result<Vec<Felt>, SettlementClientError> {
-        let call_res = self
-            .provider
+    pub async fn get_state_call(
+        &self,
+        provider: &JsonRpcClient<HttpTransport>,
+    ) -> Result<Vec<Felt>, SettlementClientError> {
+        let call_res = provider
             .call(
                 FunctionCall {
                     contract_address: self.l2_core_contract,
@@ -425,7 +467,14 @@ impl StarknetClient {
             )
             .await
             .map_err(|e| -> SettlementClientError {
-                StarknetClientError::StateInitialization { message: format!("Failed to get state: {}", e) }.into()
+                match e {
+                    // The call reached the node, which rejected it.
+                    ProviderError::StarknetError(e) => {
+                        StarknetClientError::StateInitialization { message: format!("Failed to get state: {}", e) }
+                    }
+                    e => StarknetClientError::NetworkConnection { message: format!("Failed to get state: {}", e) },
+                }
+                .into()
             })?;
 
         if call_res.len() != 3 {
@@ -453,7 +502,7 @@ pub mod starknet_client_tests {
     use starknet_core::types::MaybePendingBlockWithTxHashes::{Block, PendingBlock};
     use starknet_providers::jsonrpc::HttpTransport;
     use starknet_providers::ProviderError::StarknetError;
-    use starknet_providers::{JsonRpcClient, Provider};
+    use starknet_providers::{JsonRpcClient, Provider, ProviderError};
     use starknet_types_core::felt::Felt;
     use std::str::FromStr;
     use std::time::Duration;
@@ -480,8 +529,9 @@ pub mod starknet_client_tests {
 
         // Create the client
         let client = StarknetClient::new(StarknetClientConfig {
-            url: context.url.clone(),
+            urls: vec![context.url.clone()],
             l2_contract_address: context.deployed_appchain_contract_address,
+            quorum: 1,
         })
         .await?;
 
@@ -497,8 +547,9 @@ pub mod starknet_client_tests {
         let fixture = test_fixture.await?;
 
         let starknet_client = StarknetClient::new(StarknetClientConfig {
-            url: fixture.context.url,
+            urls: vec![fixture.context.url],
             l2_contract_address: Felt::from_str("0xdeadbeef")?,
+            quorum: 1,
         })
         .await;
         assert!(starknet_client.is_err(), "Should fail to create a new client");
@@ -718,8 +769,9 @@ mod starknet_client_messaging_test {
         let db = Arc::new(DatabaseService::open_for_testing(chain_config.clone()));
 
         let starknet_client = StarknetClient::new(StarknetClientConfig {
-            url: context.url.clone(),
+            urls: vec![context.url.clone()],
             l2_contract_address: context.deployed_appchain_contract_address,
+            quorum: 1,
         })
         .await?;
 
@@ -867,8 +919,9 @@ mod starknet_client_event_subscription_test {
         let db = Arc::new(DatabaseService::open_for_testing(chain_config.clone()));
 
         let starknet_client = StarknetClient::new(StarknetClientConfig {
-            url: context.url,
+            urls: vec![context.url],
             l2_contract_address: context.deployed_appchain_contract_address,
+            quorum: 1,
         })
         .await?;
 
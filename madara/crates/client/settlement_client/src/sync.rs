@@ -1,6 +1,7 @@
 use crate::client::SettlementClientTrait;
 use crate::error::SettlementClientError;
 use crate::gas_price::{gas_price_worker, L1BlockMetrics};
+use crate::messages_to_l1::messages_to_l1_worker;
 use crate::messaging::{sync, L1toL2MessagingEventData};
 use crate::state_update::{state_update_worker, L1HeadSender};
 use futures::Stream;
@@ -20,6 +21,8 @@ pub struct SyncWorkerConfig<C: 'static, S> {
     pub ctx: ServiceContext,
     pub l1_block_metrics: Arc<L1BlockMetrics>,
     pub l1_head_sender: L1HeadSender,
+    /// Settlement layer block from which to index the L2 to L1 messages, instead of the core contract deployment block.
+    pub messages_to_l1_start_block: Option<u64>,
 }
 
 pub async fn sync_worker<C: 'static, S>(config: SyncWorkerConfig<C, S>) -> anyhow::Result<()>
@@ -43,6 +46,13 @@ where
         config.ctx.clone(),
     ));
 
+    join_set.spawn(messages_to_l1_worker(
+        Arc::clone(&config.backend),
+        config.settlement_client.clone(),
+        config.messages_to_l1_start_block,
+        config.ctx.clone(),
+    ));
+
     if !config.gas_price_sync_disabled {
         join_set.spawn(gas_price_worker(
             config.settlement_client.clone(),
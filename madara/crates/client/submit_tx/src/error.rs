@@ -103,4 +103,7 @@ pub enum RejectedTransactionErrorKind {
     InvalidContractClassVersion,
     #[error("RateLimited")]
     RateLimited,
+    /// Rejected by the transaction policy of a permissioned chain.
+    #[error("TransactionNotPermitted")]
+    TransactionNotPermitted,
 }
@@ -21,7 +21,7 @@ use mp_rpc::{
 };
 use mp_transactions::{
     validated::{TxTimestamp, ValidatedMempoolTx},
-    BroadcastedTransactionExt, ToBlockifierError,
+    BroadcastedTransactionExt, DeployAccountTransaction, InvokeTransaction, ToBlockifierError,
 };
 use starknet_api::{
     executable_transaction::{AccountTransaction as ApiAccountTransaction, TransactionType},
@@ -157,9 +157,11 @@ impl From<mc_exec::Error> for SubmitTransactionError {
             E::Reexecution(_) | E::FeeEstimation(_) | E::MessageFeeEstimation(_) | E::CallContract(_) => {
                 rejected(ValidateFailure, format!("{value:#}"))
             }
-            E::UnsupportedProtocolVersion(_) | E::Storage(_) | E::InvalidSequencerAddress(_) => {
-                Internal(anyhow::anyhow!(value))
-            }
+            E::UnsupportedProtocolVersion(_)
+            | E::Storage(_)
+            | E::State(_)
+            | E::InvalidSequencerAddress(_)
+            | E::InvalidStateOverride(_) => Internal(anyhow::anyhow!(value)),
         }
     }
 }
@@ -192,6 +194,71 @@ impl TransactionValidator {
         Self { inner, backend, config }
     }
 
+    /// Rejects the transactions that are not permitted by the transaction policy of the chain. The policy is enforced
+    /// even when validation is disabled.
+    fn check_transaction_policy(&self, tx: &ApiAccountTransaction) -> Result<(), SubmitTransactionError> {
+        let policy = self.backend.transaction_policy();
+        if policy.is_unrestricted() {
+            return Ok(());
+        }
+
+        policy
+            .check_sender(&tx.contract_address().to_felt())
+            .and_then(|()| match tx {
+                ApiAccountTransaction::Declare(tx) => policy.check_declare(&tx.class_hash().to_felt()),
+                ApiAccountTransaction::DeployAccount(tx) => {
+                    policy.check_deploy(DeployAccountTransaction::from(tx.tx.clone()).class_hash())
+                }
+                ApiAccountTransaction::Invoke(tx) => match InvokeTransaction::from(tx.tx.clone()) {
+                    InvokeTransaction::V0(tx) => {
+                        policy.check_call(&tx.contract_address, &tx.entry_point_selector, &tx.calldata)
+                    }
+                    tx => policy.check_account_calls(tx.calldata()),
+                },
+            })
+            .map_err(|err| rejected(RejectedTransactionErrorKind::TransactionNotPermitted, format!("{err:#}")))
+    }
+
+    /// Rejects the transactions paying their fees in wei on chains where the native token is the only fee token. These
+    /// are the legacy (V0, V1 and V2) transactions.
+    fn check_fee_token(&self, tx: &ApiAccountTransaction, charge_fee: bool) -> Result<(), SubmitTransactionError> {
+        let chain_config = self.backend.chain_config();
+        if !charge_fee || chain_config.supports_legacy_fee_payment() || tx.version() >= TransactionVersion::THREE {
+            return Ok(());
+        }
+        Err(rejected(
+            RejectedTransactionErrorKind::InvalidTransactionVersion,
+            format!(
+                "Fees can only be paid in {} on this chain, which requires V3 transactions",
+                chain_config.native_fee_token.symbol
+            ),
+        ))
+    }
+
+    /// Rejects V3 transactions whose max L2 gas price is lower than the L2 gas price of the pending block, when the
+    /// chain adjusts the L2 gas price from block to block.
+    fn check_l2_gas_price(&self, tx: &BroadcastedTxn) -> Result<(), SubmitTransactionError> {
+        if self.config.disable_validation
+            || self.config.disable_fee
+            || self.backend.chain_config().l2_gas_price.is_none()
+        {
+            return Ok(());
+        }
+        let Some(resource_bounds) = tx.resource_bounds() else { return Ok(()) };
+
+        let l2_gas_price = self.backend.latest_pending_block().header.l1_gas_price.strk_l2_gas_price;
+        if resource_bounds.l2_gas.max_price_per_unit < l2_gas_price {
+            return Err(rejected(
+                RejectedTransactionErrorKind::InsufficientMaxFee,
+                format!(
+                    "Max L2 gas price per unit {} is lower than the current L2 gas price {l2_gas_price}",
+                    resource_bounds.l2_gas.max_price_per_unit
+                ),
+            ));
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self, tx, converted_class), fields(module = "TxValidation"))]
     async fn accept_tx(
         &self,
@@ -201,6 +268,8 @@ impl TransactionValidator {
     ) -> Result<(), SubmitTransactionError> {
         let tx_hash = tx.tx_hash().to_felt();
 
+        self.check_transaction_policy(&tx)?;
+
         // We have to skip part of the validation in the very specific case where you send an invoke tx directly after a deploy account:
         // the account is not deployed yet but the tx should be accepted.
         let validate = !(tx.tx_type() == TransactionType::InvokeFunction && tx.nonce().to_felt() == Felt::ONE);
@@ -210,6 +279,8 @@ impl TransactionValidator {
             && tx.version() == TransactionVersion(Felt::ZERO))
             || self.config.disable_fee);
 
+        self.check_fee_token(&tx, charge_fee)?;
+
         let account_tx = AccountTransaction {
             tx,
             execution_flags: ExecutionFlags { only_query: false, charge_fee, validate, strict_nonce_check: false },
@@ -277,6 +348,7 @@ impl SubmitTransaction for TransactionValidator {
 
         let arrived_at = TxTimestamp::now();
         let tx: BroadcastedTxn = BroadcastedTxn::Declare(tx);
+        self.check_l2_gas_price(&tx)?;
         let (api_tx, class) = tx.into_starknet_api(
             self.backend.chain_config().chain_id.to_felt(),
             self.backend.chain_config().latest_protocol_version,
@@ -308,6 +380,7 @@ impl SubmitTransaction for TransactionValidator {
 
         let arrived_at = TxTimestamp::now();
         let tx = BroadcastedTxn::DeployAccount(tx);
+        self.check_l2_gas_price(&tx)?;
         let (api_tx, class) = tx.into_starknet_api(
             self.backend.chain_config().chain_id.to_felt(),
             self.backend.chain_config().latest_protocol_version,
@@ -339,6 +412,7 @@ impl SubmitTransaction for TransactionValidator {
 
         let arrived_at = TxTimestamp::now();
         let tx = BroadcastedTxn::Invoke(tx);
+        self.check_l2_gas_price(&tx)?;
         let (api_tx, class) = tx.into_starknet_api(
             self.backend.chain_config().chain_id.to_felt(),
             self.backend.chain_config().latest_protocol_version,
@@ -34,7 +34,6 @@ pub fn block_with_state_update_pipeline(
     )
 }
 
-// TODO: check that the headers follow each other
 pub struct GatewaySyncSteps {
     backend: Arc<MadaraBackend>,
     importer: Arc<BlockImporter>,
@@ -132,6 +131,9 @@ impl PipelineSteps for GatewaySyncSteps {
         input: Self::SequentialStepInput,
     ) -> anyhow::Result<ApplyOutcome<Self::Output>> {
         tracing::debug!("Gateway sync sequential step: {block_range:?}");
+        let block_range_ = block_range.clone();
+        self.importer.run_in_rayon_pool(move |importer| importer.verify_parent_hashes(block_range_)).await?;
+
         if let Some(block_n) = block_range.last() {
             self.backend.clear_pending_block().context("Clearing pending block")?;
             self.backend.head_status().headers.set_current(Some(block_n));
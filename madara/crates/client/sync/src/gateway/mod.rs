@@ -1,6 +1,6 @@
 use crate::{
     apply_state::ApplyStateSync,
-    import::BlockImporter,
+    import::{BlockImportError, BlockImporter},
     metrics::SyncMetrics,
     probe::ThrottledRepeatedFuture,
     sync::{ForwardPipeline, SyncController, SyncControllerConfig},
@@ -13,10 +13,13 @@ use mc_gateway_client::GatewayProvider;
 use mp_block::{BlockId, BlockTag};
 use mp_gateway::block::ProviderBlockHeader;
 use std::{iter, sync::Arc, time::Duration};
+use traces::BlockTracesSaver;
 
 pub(crate) mod blocks;
 pub(crate) mod classes;
+pub(crate) mod traces;
 
+#[derive(Clone)]
 pub struct ForwardSyncConfig {
     pub block_parallelization: usize,
     pub block_batch_size: usize,
@@ -26,6 +29,8 @@ pub struct ForwardSyncConfig {
     pub apply_state_batch_size: usize,
     pub disable_tries: bool,
     pub keep_pre_v0_13_2_hashes: bool,
+    /// Maximum number of local blocks that can be reverted when the upstream chain reorgs.
+    pub max_reorg_depth: u64,
 }
 
 impl Default for ForwardSyncConfig {
@@ -39,6 +44,7 @@ impl Default for ForwardSyncConfig {
             apply_state_batch_size: 4,
             disable_tries: false,
             keep_pre_v0_13_2_hashes: false,
+            max_reorg_depth: 64,
         }
     }
 }
@@ -50,6 +56,9 @@ impl ForwardSyncConfig {
     pub fn keep_pre_v0_13_2_hashes(self, val: bool) -> Self {
         Self { keep_pre_v0_13_2_hashes: val, ..self }
     }
+    pub fn max_reorg_depth(self, val: u64) -> Self {
+        Self { max_reorg_depth: val, ..self }
+    }
 }
 
 pub type GatewaySync = SyncController<GatewayForwardSync>;
@@ -76,7 +85,11 @@ pub struct GatewayForwardSync {
     blocks_pipeline: GatewayBlockSync,
     classes_pipeline: ClassesSync,
     apply_state_pipeline: ApplyStateSync,
+    traces_saver: Option<BlockTracesSaver>,
     backend: Arc<MadaraBackend>,
+    importer: Arc<BlockImporter>,
+    client: Arc<GatewayProvider>,
+    config: ForwardSyncConfig,
 }
 
 impl GatewayForwardSync {
@@ -112,7 +125,80 @@ impl GatewayForwardSync {
             config.apply_state_batch_size,
             config.disable_tries,
         );
-        Self { blocks_pipeline, classes_pipeline, apply_state_pipeline, backend }
+        let traces_saver =
+            backend.trace_storage_enabled().then(|| BlockTracesSaver::new(importer.clone(), starting_block_n));
+        Self {
+            blocks_pipeline,
+            classes_pipeline,
+            apply_state_pipeline,
+            traces_saver,
+            backend,
+            importer,
+            client,
+            config,
+        }
+    }
+
+    /// Called when the upstream chain does not extend the local chain anymore. The local blocks above the common
+    /// ancestor with the upstream chain are reverted, and the pipelines are restarted from there.
+    async fn handle_reorg(&mut self, metrics: &mut SyncMetrics) -> anyhow::Result<()> {
+        // Wait for the in-flight steps so that nothing is written to the database during the revert. The blocks
+        // pipeline outputs are dropped, as they may come from either branch.
+        while let Some(_res) = self.blocks_pipeline.next().await {}
+        while let Some(res) = self.classes_pipeline.next().await {
+            res?;
+        }
+        while let Some(res) = self.apply_state_pipeline.next().await {
+            res?;
+        }
+        if let Some(traces_saver) = self.traces_saver.take() {
+            traces_saver.stop().await;
+        }
+
+        let latest_block_n = self.backend.head_status().latest_full_block_n();
+        let mut common_ancestor = latest_block_n;
+        let mut depth = 0;
+        while let Some(block_n) = common_ancestor {
+            let local_hash = self
+                .backend
+                .get_block_hash(&RawDbBlockId::Number(block_n))
+                .context("Getting block hash")?
+                .context("Block not found")?;
+            let upstream_hash = self
+                .client
+                .get_header(BlockId::Number(block_n))
+                .await
+                .with_context(|| format!("Getting the header of block_n={block_n} from the gateway"))?
+                .block_hash;
+            if local_hash == upstream_hash {
+                break;
+            }
+
+            depth += 1;
+            if depth > self.config.max_reorg_depth {
+                anyhow::bail!(
+                    "The upstream chain reorg is deeper than the maximum reorg depth of {} blocks",
+                    self.config.max_reorg_depth
+                );
+            }
+            common_ancestor = block_n.checked_sub(1);
+        }
+        let Some(common_ancestor) = common_ancestor else {
+            anyhow::bail!("The upstream chain has no common ancestor with the local chain");
+        };
+
+        tracing::warn!(
+            "🔀 Upstream chain reorg, reverting {depth} blocks to the common ancestor block #{common_ancestor}"
+        );
+        let backend = self.backend.clone();
+        self.importer
+            .run_in_rayon_pool(move |_| backend.revert_reorged_blocks(common_ancestor))
+            .await
+            .context("Reverting the reorged blocks")?;
+        metrics.on_reorg(common_ancestor, depth);
+
+        *self = Self::new(self.backend.clone(), self.importer.clone(), self.client.clone(), self.config.clone());
+        Ok(())
     }
 
     fn pipeline_status(&self) -> PipelineStatus {
@@ -155,6 +241,7 @@ impl ForwardPipeline for GatewayForwardSync {
             }
 
             let start_next_block = self.pipeline_status().min().map(|n| n + 1).unwrap_or(0);
+            let mut reorg_detected = false;
 
             tokio::select! {
                 Some(res) = self.apply_state_pipeline.next() => {
@@ -164,14 +251,27 @@ impl ForwardPipeline for GatewayForwardSync {
                     res?;
                 }
                 Some(res) = self.blocks_pipeline.next(), if self.classes_pipeline.can_schedule_more() && self.apply_state_pipeline.can_schedule_more() => {
-                    let (range, state_diffs) = res?;
-                    self.classes_pipeline.push(range.clone(), state_diffs.iter().map(|s| s.all_declared_classes()));
-                    self.apply_state_pipeline.push(range, state_diffs);
+                    match res {
+                        Err(err) if err.downcast_ref::<BlockImportError>().is_some_and(BlockImportError::is_reorg) => {
+                            tracing::debug!("Reorg detected: {err:#}");
+                            reorg_detected = true;
+                        }
+                        res => {
+                            let (range, state_diffs) = res?;
+                            self.classes_pipeline.push(range.clone(), state_diffs.iter().map(|s| s.all_declared_classes()));
+                            self.apply_state_pipeline.push(range, state_diffs);
+                        }
+                    }
                 }
                 // all pipelines are empty, we're done :)
                 else => done = true,
             }
 
+            if reorg_detected {
+                self.handle_reorg(metrics).await?;
+                continue;
+            }
+
             let new_next_block = self.pipeline_status().min().map(|n| n + 1).unwrap_or(0);
             for block_n in start_next_block..new_next_block {
                 // Notify of a new full block here.
@@ -192,6 +292,10 @@ impl ForwardPipeline for GatewayForwardSync {
 
                 self.backend.on_full_block_imported(block_info.into(), block_events).await?;
                 metrics.update(block_n, &self.backend).context("Updating metrics")?;
+
+                if let Some(traces_saver) = &self.traces_saver {
+                    traces_saver.on_full_block_imported(block_n);
+                }
             }
         }
 
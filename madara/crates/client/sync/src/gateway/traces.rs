@@ -0,0 +1,58 @@
+use crate::import::BlockImporter;
+use std::sync::Arc;
+use tokio::{sync::watch, task::JoinHandle};
+
+/// Saves the transaction traces of the fully imported blocks in a background task. Re-executing the blocks is
+/// much slower than importing them, so the task is allowed to lag behind the sync instead of holding it back.
+///
+/// Stored traces are optional: the trace rpc methods fall back to re-executing the blocks which have not been
+/// traced yet, including the ones left behind when the node stops.
+pub(crate) struct BlockTracesSaver {
+    latest_full_block_n: watch::Sender<Option<u64>>,
+    task: JoinHandle<()>,
+}
+
+impl BlockTracesSaver {
+    /// Traces every block from `starting_block_n` as they are reported by [`Self::on_full_block_imported`].
+    pub fn new(importer: Arc<BlockImporter>, starting_block_n: u64) -> Self {
+        let (latest_full_block_n, mut latest_recv) = watch::channel(None::<u64>);
+        let task = tokio::spawn(async move {
+            let mut next_block_n = starting_block_n;
+            loop {
+                // Returns when the sender is dropped, i.e. the saver is stopped.
+                let Ok(latest_block_n) =
+                    latest_recv.wait_for(|latest| latest.is_some_and(|n| n >= next_block_n)).await.map(|n| *n)
+                else {
+                    return;
+                };
+                let latest_block_n = latest_block_n.expect("Checked in wait_for");
+
+                for block_n in next_block_n..=latest_block_n {
+                    if latest_recv.has_changed().is_err() {
+                        return;
+                    }
+                    if let Err(err) =
+                        importer.run_in_rayon_pool(move |importer| importer.save_block_traces(block_n)).await
+                    {
+                        tracing::warn!("Failed to save transaction traces for block #{block_n}: {err:#}");
+                    }
+                }
+                next_block_n = latest_block_n + 1;
+            }
+        });
+        Self { latest_full_block_n, task }
+    }
+
+    /// Queue the blocks up to `block_n` for tracing.
+    pub fn on_full_block_imported(&self, block_n: u64) {
+        self.latest_full_block_n.send_replace(Some(block_n));
+    }
+
+    /// Stops the background task once the block being traced is saved, the blocks still queued are not traced.
+    pub async fn stop(self) {
+        drop(self.latest_full_block_n);
+        if let Err(err) = self.task.await {
+            tracing::warn!("Transaction traces task failed: {err:#}");
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use anyhow::Context;
 use mc_db::{db_block_id::RawDbBlockId, MadaraBackend, MadaraStorageError};
+use mc_exec::{execution_result_to_tx_trace, transaction::to_blockifier_transaction, ExecutionContext};
 use mp_block::{
     commitments::{compute_event_commitment, compute_receipt_commitment, compute_transaction_commitment},
-    BlockHeaderWithSignatures, Header, PendingFullBlock, TransactionWithReceipt,
+    BlockHeaderWithSignatures, BlockId, Header, PendingFullBlock, TransactionWithReceipt,
 };
 use mp_chain_config::StarknetVersion;
 use mp_class::{
@@ -11,10 +12,12 @@ use mp_class::{
 };
 use mp_convert::ToFelt;
 use mp_receipt::EventWithTransactionHash;
+use mp_rpc::TraceBlockTransactionsResult;
 use mp_state_update::{DeclaredClassCompiledClass, StateDiff};
 use mp_utils::rayon::{global_spawn_rayon_task, RayonPool};
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use starknet_api::core::ChainId;
+use starknet_api::transaction::TransactionHash;
 use starknet_core::types::Felt;
 use std::{borrow::Cow, collections::HashMap, ops::Range, sync::Arc};
 
@@ -83,6 +86,9 @@ pub enum BlockImportError {
     BlockNumber { got: u64, expected: u64 },
     #[error("Block hash mismatch: expected {expected:#x}, got {got:#x}")]
     BlockHash { got: Felt, expected: Felt },
+    /// The upstream chain does not extend the local chain, see [`BlockImportError::is_reorg`].
+    #[error("Parent block hash mismatch for block #{block_n}: expected {expected:#x}, got {got:#x}")]
+    ParentHash { block_n: u64, got: Felt, expected: Felt },
 
     #[error("Global state root mismatch: expected {expected:#x}, got {got:#x}")]
     GlobalStateRoot { got: Felt, expected: Felt },
@@ -98,6 +104,10 @@ impl BlockImportError {
     pub fn is_internal(&self) -> bool {
         matches!(self, BlockImportError::InternalDb { .. } | BlockImportError::Internal(_))
     }
+    /// The upstream chain has been reorganised.
+    pub fn is_reorg(&self) -> bool {
+        matches!(self, BlockImportError::ParentHash { .. })
+    }
 }
 
 /// Shared verification & saving logic between gateway and (yet-to-be-merged) p2p.
@@ -217,6 +227,41 @@ impl BlockImporterCtx {
         Ok(())
     }
 
+    /// Check that the saved headers of `block_range` follow each other and the block before the range. This needs the
+    /// previous header to be saved, and thus has to be called from a sequential step.
+    /// This check is how reorgs of the upstream chain are detected, and is done even when verifications are disabled.
+    pub fn verify_parent_hashes(&self, block_range: Range<u64>) -> Result<(), BlockImportError> {
+        let get_header = |block_n: u64| {
+            self.db
+                .get_block_info(&RawDbBlockId::Number(block_n))
+                .map_err(|error| BlockImportError::InternalDb {
+                    error,
+                    context: format!("Getting block info for {block_n}").into(),
+                })
+                .map(|info| info.and_then(|info| info.into_closed()))
+        };
+
+        for block_n in block_range {
+            let Some(parent_block_n) = block_n.checked_sub(1) else { continue };
+            let Some(parent) = get_header(parent_block_n)? else {
+                // The parent block is not in the database when starting the sync at some height.
+                if self.config.trust_parent_hash {
+                    continue;
+                }
+                return Err(BlockImportError::Internal(anyhow::anyhow!("Block #{parent_block_n} not found")));
+            };
+            let header = get_header(block_n)?.ok_or_else(|| anyhow::anyhow!("Block #{block_n} not found"))?;
+            if header.header.parent_block_hash != parent.block_hash {
+                return Err(BlockImportError::ParentHash {
+                    block_n,
+                    got: header.header.parent_block_hash,
+                    expected: parent.block_hash,
+                });
+            }
+        }
+        Ok(())
+    }
+
     pub fn save_header(&self, block_n: u64, signed_header: BlockHeaderWithSignatures) -> Result<(), BlockImportError> {
         self.db.store_block_header(signed_header).map_err(|error| BlockImportError::InternalDb {
             error,
@@ -566,6 +611,54 @@ impl BlockImporterCtx {
 
         Ok(())
     }
+
+    // TRACES
+
+    /// Called in a rayon-pool context.
+    /// Re-executes a fully imported block on top of its parent state and saves the traces of its transactions.
+    pub fn save_block_traces(&self, block_n: u64) -> Result<(), BlockImportError> {
+        let block = self
+            .db
+            .get_block(&RawDbBlockId::Number(block_n))
+            .map_err(|error| BlockImportError::InternalDb {
+                error,
+                context: format!("Getting block #{block_n} for tracing").into(),
+            })?
+            .context("Block cannot be found")?;
+
+        // Blockifier does not support execution for versions earlier than that.
+        if block.info.protocol_version() < &StarknetVersion::V0_13_0 {
+            return Ok(());
+        }
+
+        let exec_context = ExecutionContext::new_at_block_start(Arc::clone(&self.db), &block.info)
+            .context("Creating execution context")?;
+        let transactions = Iterator::zip(block.inner.transactions.into_iter(), block.info.tx_hashes())
+            .map(|(tx, hash)| {
+                to_blockifier_transaction(Arc::clone(&self.db), BlockId::Number(block_n), tx, &TransactionHash(*hash))
+                    .context("Converting transaction to blockifier format")
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let traces = exec_context
+            .re_execute_transactions([], transactions)
+            .with_context(|| format!("Re-executing block #{block_n}"))?
+            .iter()
+            .map(|result| {
+                Ok(TraceBlockTransactionsResult {
+                    trace_root: execution_result_to_tx_trace(result)
+                        .context("Converting execution infos to tx trace")?,
+                    transaction_hash: result.hash.to_felt(),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        self.db.store_block_traces(block_n, &traces).map_err(|error| BlockImportError::InternalDb {
+            error,
+            context: format!("Storing transaction traces for {block_n}").into(),
+        })?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
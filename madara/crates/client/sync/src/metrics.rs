@@ -29,6 +29,8 @@ pub struct SyncMetrics {
     pub l2_state_size: Histogram<f64>, // TODO: remove this, as well as the return value from db_metrics update.
     pub transaction_count: Counter<u64>,
     pub event_count: Counter<u64>,
+    pub l2_reorg_count: Counter<u64>,
+    pub l2_reorg_depth: Histogram<f64>,
     // L1 network metrics
     // gas price is also define in eth/client.rs but this would be the gas used in the block and it's price
     pub l1_gas_price_wei: Histogram<f64>,
@@ -94,6 +96,20 @@ impl SyncMetrics {
             "".to_string(),
         );
 
+        let l2_reorg_count = register_counter_metric_instrument(
+            &block_meter,
+            "l2_reorg_count".to_string(),
+            "Counter for madara L2 upstream chain reorgs".to_string(),
+            "".to_string(),
+        );
+
+        let l2_reorg_depth = register_histogram_metric_instrument(
+            &block_meter,
+            "l2_reorg_depth".to_string(),
+            "Histogram for madara L2 upstream chain reorg depth".to_string(),
+            "".to_string(),
+        );
+
         let l1_gas_price_wei = register_histogram_metric_instrument(
             &block_meter,
             "l1_gas_price_wei".to_string(),
@@ -124,12 +140,22 @@ impl SyncMetrics {
 
             transaction_count,
             event_count,
+            l2_reorg_count,
+            l2_reorg_depth,
 
             l1_gas_price_wei,
             l1_gas_price_strk,
         }
     }
 
+    /// Called when the local chain has been reverted to `common_ancestor` after a reorg of the upstream chain.
+    pub fn on_reorg(&mut self, common_ancestor: u64, depth: u64) {
+        self.l2_reorg_count.add(1, &[]);
+        self.l2_reorg_depth.record(depth as f64, &[]);
+        // The reverted blocks will be imported again.
+        self.starting_block = self.starting_block.min(common_ancestor + 1);
+    }
+
     pub fn update(&mut self, block_n: u64, backend: &MadaraBackend) -> anyhow::Result<()> {
         let now = Instant::now();
 
@@ -463,3 +463,50 @@ async fn test_pending_declared_class(mut ctx: TestContext) {
         .unwrap()
         .is_some());
 }
+
+#[rstest]
+#[tokio::test]
+/// The pipeline should revert to the common ancestor when the upstream chain reorgs, and follow the new branch.
+async fn test_reorg(mut ctx: TestContext) {
+    ctx.gateway_mock.mock_block(0, felt!("0x10"), felt!("0x0"));
+    let block_1 = ctx.gateway_mock.mock_block(1, felt!("0x11"), felt!("0x10"));
+    let block_2 = ctx.gateway_mock.mock_block(2, felt!("0x12"), felt!("0x11"));
+    let latest = ctx.gateway_mock.mock_header_latest(2, felt!("0x12"));
+
+    let mut sync = crate::gateway::forward_sync(
+        ctx.backend.clone(),
+        ctx.importer,
+        ctx.gateway_mock.client(),
+        SyncControllerConfig::default().service_state_sender(ctx.service_state_sender).no_pending_block(true),
+        ForwardSyncConfig::default().disable_tries(true),
+    );
+
+    let _task = AbortOnDrop::spawn(async move { sync.run(ServiceContext::default()).await.unwrap() });
+
+    assert_eq!(ctx.service_state_recv.recv().await.unwrap(), ServiceEvent::Starting);
+    assert_eq!(ctx.service_state_recv.recv().await.unwrap(), ServiceEvent::Idle);
+    assert_eq!(ctx.service_state_recv.recv().await.unwrap(), ServiceEvent::SyncingTo { target: 2 });
+    assert_eq!(ctx.service_state_recv.recv().await.unwrap(), ServiceEvent::Idle);
+    assert_eq!(ctx.backend.get_block_hash(&DbBlockId::Number(2)).unwrap(), Some(felt!("0x12")));
+
+    // Blocks #1 and #2 are replaced by another branch.
+    for mut mock in [block_1, block_2, latest] {
+        mock.delete();
+    }
+    ctx.gateway_mock.mock_header(0, felt!("0x10"));
+    ctx.gateway_mock.mock_header(1, felt!("0x21"));
+    ctx.gateway_mock.mock_header(2, felt!("0x22"));
+    ctx.gateway_mock.mock_block(1, felt!("0x21"), felt!("0x10"));
+    ctx.gateway_mock.mock_block(2, felt!("0x22"), felt!("0x21"));
+    ctx.gateway_mock.mock_block(3, felt!("0x23"), felt!("0x22"));
+    ctx.gateway_mock.mock_header_latest(3, felt!("0x23"));
+
+    assert_eq!(ctx.service_state_recv.recv().await.unwrap(), ServiceEvent::SyncingTo { target: 3 });
+    assert_eq!(ctx.service_state_recv.recv().await.unwrap(), ServiceEvent::Idle);
+
+    assert_eq!(ctx.backend.get_block_hash(&DbBlockId::Number(0)).unwrap(), Some(felt!("0x10")));
+    assert_eq!(ctx.backend.get_block_hash(&DbBlockId::Number(1)).unwrap(), Some(felt!("0x21")));
+    assert_eq!(ctx.backend.get_block_hash(&DbBlockId::Number(2)).unwrap(), Some(felt!("0x22")));
+    assert_eq!(ctx.backend.get_block_hash(&DbBlockId::Number(3)).unwrap(), Some(felt!("0x23")));
+    assert_eq!(ctx.backend.get_block_n(&mp_block::BlockId::Hash(felt!("0x11"))).unwrap(), None);
+}
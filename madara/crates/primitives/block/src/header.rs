@@ -114,6 +114,9 @@ pub struct GasPrices {
     pub strk_l1_gas_price: u128,
     pub eth_l1_data_gas_price: u128,
     pub strk_l1_data_gas_price: u128,
+    /// Set by the L2 gas price controller of the block producer, see [`mp_chain_config::L2GasPriceConfig`].
+    pub eth_l2_gas_price: u128,
+    pub strk_l2_gas_price: u128,
 }
 
 // Starknet API can't have null gas prices, so the default null gas prices are set to 1.
@@ -125,15 +128,16 @@ impl From<&GasPrices> for starknet_api::block::GasPrices {
                     .unwrap_or_default(),
                 l1_data_gas_price: starknet_api::block::NonzeroGasPrice::new(gas_prices.eth_l1_data_gas_price.into())
                     .unwrap_or_default(),
-                // TODO: L2 gas price is not used in the current implementation, but it should be set to 1
-                l2_gas_price: Default::default(), // 1
+                l2_gas_price: starknet_api::block::NonzeroGasPrice::new(gas_prices.eth_l2_gas_price.into())
+                    .unwrap_or_default(),
             },
             strk_gas_prices: starknet_api::block::GasPriceVector {
                 l1_gas_price: starknet_api::block::NonzeroGasPrice::new(gas_prices.strk_l1_gas_price.into())
                     .unwrap_or_default(),
                 l1_data_gas_price: starknet_api::block::NonzeroGasPrice::new(gas_prices.strk_l1_data_gas_price.into())
                     .unwrap_or_default(),
-                l2_gas_price: Default::default(), // 1
+                l2_gas_price: starknet_api::block::NonzeroGasPrice::new(gas_prices.strk_l2_gas_price.into())
+                    .unwrap_or_default(),
             },
         }
     }
@@ -153,6 +157,13 @@ impl GasPrices {
             price_in_wei: self.eth_l1_data_gas_price.into(),
         }
     }
+
+    pub fn l2_gas_price(&self) -> mp_rpc::ResourcePrice {
+        mp_rpc::ResourcePrice {
+            price_in_fri: self.strk_l2_gas_price.into(),
+            price_in_wei: self.eth_l2_gas_price.into(),
+        }
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -280,10 +291,7 @@ fn concat_counts(
     state_diff_length: u64,
     l1_da_mode: L1DataAvailabilityMode,
 ) -> Felt {
-    let l1_data_availability_byte: u8 = match l1_da_mode {
-        L1DataAvailabilityMode::Calldata => 0,
-        L1DataAvailabilityMode::Blob => 0b10000000,
-    };
+    let l1_data_availability_byte: u8 = if l1_da_mode.use_kzg_da() { 0b10000000 } else { 0 };
 
     let concat_bytes = [
         transaction_count.to_be_bytes(),
@@ -309,6 +317,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_concat_counts_validium() {
+        // Validium blocks are executed with kzg DA, they are hashed like blob blocks.
+        assert_eq!(
+            concat_counts(4, 3, 2, L1DataAvailabilityMode::Validium),
+            concat_counts(4, 3, 2, L1DataAvailabilityMode::Blob)
+        );
+        assert_eq!(
+            concat_counts(4, 3, 2, L1DataAvailabilityMode::Calldata),
+            Felt::from_hex_unchecked("0x0000000000000004000000000000000300000000000000020000000000000000")
+        );
+    }
+
     #[test]
     fn test_header_new() {
         let header = Header::new(
@@ -330,6 +351,8 @@ mod tests {
                 strk_l1_gas_price: 15,
                 eth_l1_data_gas_price: 16,
                 strk_l1_data_gas_price: 17,
+                eth_l2_gas_price: 18,
+                strk_l2_gas_price: 19,
             },
             L1DataAvailabilityMode::Blob,
         );
@@ -384,6 +407,8 @@ mod tests {
                 strk_l1_gas_price: 15,
                 eth_l1_data_gas_price: 16,
                 strk_l1_data_gas_price: 17,
+                eth_l2_gas_price: 18,
+                strk_l2_gas_price: 19,
             },
             l1_da_mode: L1DataAvailabilityMode::Blob,
         }
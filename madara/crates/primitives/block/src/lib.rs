@@ -3,7 +3,6 @@
 use crate::header::GasPrices;
 use commitments::{BlockCommitments, CommitmentComputationContext};
 use header::{BlockTimestamp, PendingHeader};
-use mp_chain_config::L1DataAvailabilityMode;
 
 use mp_chain_config::StarknetVersion;
 use mp_receipt::{EventWithTransactionHash, TransactionReceipt};
@@ -151,16 +150,20 @@ impl From<MadaraBlockInfo> for mp_rpc::BlockHeader {
             block_hash,
             ..
         } = info;
-        let GasPrices { eth_l1_gas_price, strk_l1_gas_price, eth_l1_data_gas_price, strk_l1_data_gas_price } =
-            l1_gas_price;
+        // The v0.7.1 rpc block header has no L2 gas price.
+        let GasPrices {
+            eth_l1_gas_price,
+            strk_l1_gas_price,
+            eth_l1_data_gas_price,
+            strk_l1_data_gas_price,
+            eth_l2_gas_price: _,
+            strk_l2_gas_price: _,
+        } = l1_gas_price;
 
         Self {
             block_hash,
             block_number,
-            l1_da_mode: match l1_da_mode {
-                L1DataAvailabilityMode::Blob => mp_rpc::L1DaMode::Blob,
-                L1DataAvailabilityMode::Calldata => mp_rpc::L1DaMode::Calldata,
-            },
+            l1_da_mode: l1_da_mode.into(),
             l1_data_gas_price: mp_rpc::ResourcePrice {
                 price_in_fri: Felt::from(strk_l1_data_gas_price),
                 price_in_wei: Felt::from(eth_l1_data_gas_price),
@@ -4,7 +4,7 @@
 //! the user needing to clone the repo.
 //! Only use `fs` for constants when writing tests.
 
-use crate::{L1DataAvailabilityMode, StarknetVersion};
+use crate::{FeeTokenConfig, L1DataAvailabilityMode, L2GasPriceConfig, StarknetVersion, TransactionPolicy};
 use anyhow::{bail, Context, Result};
 use blockifier::blockifier::config::ConcurrencyConfig;
 use blockifier::blockifier_versioned_constants::{RawVersionedConstants, VersionedConstants};
@@ -103,8 +103,15 @@ pub struct ChainConfig {
 
     /// For starknet, this is the STRK ERC-20 contract on starknet.
     pub native_fee_token_address: ContractAddress,
-    /// For starknet, this is the ETH ERC-20 contract on starknet.
-    pub parent_fee_token_address: ContractAddress,
+    /// Symbol, decimals and price source of the native fee token.
+    /// Default: STRK, with 18 decimals, priced by the price oracles.
+    #[serde(default)]
+    pub native_fee_token: FeeTokenConfig,
+    /// For starknet, this is the ETH ERC-20 contract on starknet. Fees of legacy (V0, V1 and V2) transactions are paid
+    /// in wei with this token.
+    /// When none, the native token is the only fee token of the chain, and legacy transactions are rejected.
+    #[serde(default)]
+    pub parent_fee_token_address: Option<ContractAddress>,
 
     #[serde(default)]
     pub versioned_constants: ChainVersionedConstants,
@@ -165,6 +172,17 @@ pub struct ChainConfig {
     /// Configuration for parallel execution in Blockifier. Only used for block production.
     #[serde(default)]
     pub block_production_concurrency: BlockProductionConfig,
+
+    /// Allow and deny lists restricting the transactions accepted by the node, for permissioned chains.
+    /// Default: every transaction is allowed.
+    #[serde(default)]
+    pub transaction_policy: TransactionPolicy,
+
+    /// Only used for block production.
+    /// Adjust the L2 gas price from block to block depending on the utilization of the previous block.
+    /// When none, the L2 gas price of produced blocks is not set.
+    #[serde(default)]
+    pub l2_gas_price: Option<L2GasPriceConfig>,
 }
 
 impl ChainConfig {
@@ -201,6 +219,10 @@ impl ChainConfig {
         if self.pending_block_update_time.is_some_and(|t| t.is_zero()) {
             bail!("Pending block update time cannot be zero for block production.")
         }
+        if let Some(l2_gas_price) = &self.l2_gas_price {
+            l2_gas_price.validate().context("Invalid L2 gas price config")?;
+        }
+        self.native_fee_token.validate().context("Invalid native fee token config")?;
         Ok(())
     }
 
@@ -224,12 +246,13 @@ impl ChainConfig {
                 ))
                 .unwrap(),
             ),
-            parent_fee_token_address: ContractAddress(
+            native_fee_token: FeeTokenConfig::default(),
+            parent_fee_token_address: Some(ContractAddress(
                 PatriciaKey::try_from(Felt::from_hex_unchecked(
                     "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7",
                 ))
                 .unwrap(),
-            ),
+            )),
             versioned_constants: ChainVersionedConstants::default(),
 
             eth_core_contract_address: eth_core_contract_address::MAINNET.parse().expect("parsing a constant"),
@@ -267,6 +290,10 @@ impl ChainConfig {
             mempool_tx_max_age: Some(Duration::from_secs(60 * 60)), // an hour?
 
             block_production_concurrency: BlockProductionConfig::default(),
+
+            transaction_policy: TransactionPolicy::default(),
+
+            l2_gas_price: None,
         }
     }
 
@@ -339,12 +366,18 @@ impl ChainConfig {
         Err(UnsupportedProtocolVersion(version))
     }
 
+    /// Whether fees can be paid in wei with the parent fee token, which legacy (V0, V1 and V2) transactions need.
+    pub fn supports_legacy_fee_payment(&self) -> bool {
+        self.parent_fee_token_address.is_some()
+    }
+
     pub fn blockifier_chain_info(&self) -> ChainInfo {
         ChainInfo {
             chain_id: self.chain_id.clone(),
             fee_token_addresses: FeeTokenAddresses {
                 strk_fee_token_address: self.native_fee_token_address,
-                eth_fee_token_address: self.parent_fee_token_address,
+                // Legacy transactions are rejected before execution on chains with a single fee token.
+                eth_fee_token_address: self.parent_fee_token_address.unwrap_or(self.native_fee_token_address),
             },
         }
     }
@@ -481,7 +514,11 @@ mod tests {
         let parent_fee_token_address =
             Felt::from_hex("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7").unwrap();
         assert_eq!(chain_config.native_fee_token_address, ContractAddress::try_from(native_fee_token_address).unwrap());
-        assert_eq!(chain_config.parent_fee_token_address, ContractAddress::try_from(parent_fee_token_address).unwrap());
+        assert_eq!(
+            chain_config.parent_fee_token_address,
+            Some(ContractAddress::try_from(parent_fee_token_address).unwrap())
+        );
+        assert_eq!(chain_config.native_fee_token, FeeTokenConfig::default());
 
         // Check versioned constants
         // Load and parse the JSON file
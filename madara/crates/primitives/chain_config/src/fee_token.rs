@@ -0,0 +1,95 @@
+use anyhow::Context;
+use mp_utils::parsers::parse_decimal_price;
+use serde::{Deserialize, Serialize};
+
+/// Number of decimals of ETH, the token the settlement layer gas prices are denominated in (wei).
+pub const ETH_DECIMALS: u8 = 18;
+/// Number of decimals a [`FeeTokenPriceSource::Fixed`] price is parsed with. Extra decimals are truncated.
+pub const FIXED_PRICE_DECIMALS: u32 = 18;
+
+/// Description of the native fee token of a chain, the token V3 transactions pay their fees with. For starknet, this is
+/// STRK, and its smallest unit is the fri.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeeTokenConfig {
+    /// Ticker of the token, used for display.
+    pub symbol: String,
+    /// Number of decimals of the token. Gas prices and fees are expressed in its smallest unit.
+    pub decimals: u8,
+    /// How the settlement layer gas prices are converted into this token.
+    pub price_source: FeeTokenPriceSource,
+}
+
+impl Default for FeeTokenConfig {
+    fn default() -> Self {
+        Self { symbol: "STRK".into(), decimals: 18, price_source: FeeTokenPriceSource::Oracle }
+    }
+}
+
+impl FeeTokenConfig {
+    /// Number of base units in one token.
+    pub fn unit(&self) -> u128 {
+        10u128.pow(self.decimals.into())
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(!self.symbol.is_empty(), "Fee token symbol cannot be empty");
+        // 10^39 does not fit in an u128.
+        anyhow::ensure!(self.decimals <= 38, "Fee token cannot have more than 38 decimals");
+        if let FeeTokenPriceSource::Fixed(price) = &self.price_source {
+            let price = parse_decimal_price(price, FIXED_PRICE_DECIMALS).context("Invalid fixed fee token price")?;
+            anyhow::ensure!(price != 0, "Fixed fee token price cannot be zero");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeTokenPriceSource {
+    /// The ETH/token price is fetched from the price oracles configured on the command line.
+    #[default]
+    Oracle,
+    /// Fixed ETH/token price, as a decimal number with the same meaning as the prices returned by the oracles. It takes
+    /// precedence over the price oracles configured on the command line.
+    Fixed(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_token_config_from_yaml() {
+        let config: FeeTokenConfig =
+            serde_yaml::from_str("symbol: USDC\ndecimals: 6\nprice_source:\n  fixed: \"2500.5\"").unwrap();
+        assert_eq!(
+            config,
+            FeeTokenConfig {
+                symbol: "USDC".into(),
+                decimals: 6,
+                price_source: FeeTokenPriceSource::Fixed("2500.5".into())
+            }
+        );
+        assert_eq!(config.unit(), 1_000_000);
+        config.validate().unwrap();
+
+        let config: FeeTokenConfig = serde_yaml::from_str("price_source: oracle").unwrap();
+        assert_eq!(config, FeeTokenConfig::default());
+
+        assert!(FeeTokenConfig { decimals: 39, ..Default::default() }.validate().is_err());
+        for price in ["1e18", "1.2.3", ".", "", "0.00", "-1"] {
+            assert!(
+                FeeTokenConfig { price_source: FeeTokenPriceSource::Fixed(price.into()), ..Default::default() }
+                    .validate()
+                    .is_err(),
+                "{price:?} should be rejected"
+            );
+        }
+        for price in ["2500", "2500.5", ".5", "3."] {
+            FeeTokenConfig { price_source: FeeTokenPriceSource::Fixed(price.into()), ..Default::default() }
+                .validate()
+                .unwrap();
+        }
+    }
+}
@@ -8,13 +8,34 @@ pub enum L1DataAvailabilityMode {
     #[serde(alias = "Blob")]
     #[default]
     Blob,
+    /// State diffs are not posted to L1 but kept by a data availability committee. Blocks are executed as with
+    /// blobs, but they are not charged any L1 data gas. The gateway serves these blocks with a `VALIDIUM` DA mode,
+    /// which only Madara nodes know: other gateway clients expect `CALLDATA` or `BLOB`. The RPC reports them as
+    /// `BLOB`.
+    #[serde(alias = "Validium")]
+    Validium,
+}
+
+impl L1DataAvailabilityMode {
+    /// Whether the state diffs are accounted for in L1 data gas rather than in L1 gas.
+    pub fn use_kzg_da(self) -> bool {
+        match self {
+            Self::Calldata => false,
+            Self::Blob | Self::Validium => true,
+        }
+    }
+
+    /// Whether the state diffs are charged any L1 data gas.
+    pub fn charges_l1_data_gas(self) -> bool {
+        self != Self::Validium
+    }
 }
 
 impl From<L1DataAvailabilityMode> for mp_rpc::L1DaMode {
     fn from(value: L1DataAvailabilityMode) -> Self {
         match value {
             L1DataAvailabilityMode::Calldata => Self::Calldata,
-            L1DataAvailabilityMode::Blob => Self::Blob,
+            L1DataAvailabilityMode::Blob | L1DataAvailabilityMode::Validium => Self::Blob,
         }
     }
 }
@@ -0,0 +1,153 @@
+//! EIP-1559 style controller for the L2 gas price of produced blocks. The price of every new block is derived from the
+//! price of its parent block and from how full the parent block was, as measured by the bouncer: the price goes up when
+//! blocks are fuller than the target utilization, and goes down when they are emptier.
+
+use blockifier::bouncer::BouncerWeights;
+use serde::{Deserialize, Serialize};
+
+/// Utilization ratios are computed in parts per million.
+const PPM: u128 = 1_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct L2GasPriceConfig {
+    /// L2 gas price in fri of the first block produced with this controller.
+    pub initial_price: u128,
+    /// The L2 gas price never goes below this value.
+    pub min_price: u128,
+    /// The L2 gas price never goes above this value.
+    pub max_price: u128,
+    /// Block utilization in percent at which the L2 gas price stays the same.
+    pub target_utilization_percent: u64,
+    /// Maximum change of the L2 gas price from one block to the next, in percent. It is reached when the parent block
+    /// is either full or empty.
+    pub max_change_percent: u64,
+}
+
+impl Default for L2GasPriceConfig {
+    fn default() -> Self {
+        Self {
+            initial_price: 1_000_000_000,
+            min_price: 1,
+            max_price: u64::MAX as u128,
+            target_utilization_percent: 50,
+            max_change_percent: 10,
+        }
+    }
+}
+
+impl L2GasPriceConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(self.min_price > 0, "L2 gas price floor cannot be zero");
+        anyhow::ensure!(self.min_price <= self.max_price, "L2 gas price floor cannot be above the ceiling");
+        anyhow::ensure!(
+            (self.min_price..=self.max_price).contains(&self.initial_price),
+            "Initial L2 gas price must be between the floor and the ceiling"
+        );
+        anyhow::ensure!(
+            (1..100).contains(&self.target_utilization_percent),
+            "Target block utilization must be between 1 and 99 percent"
+        );
+        anyhow::ensure!(
+            (1..=100).contains(&self.max_change_percent),
+            "Max L2 gas price change per block must be between 1 and 100 percent"
+        );
+        Ok(())
+    }
+
+    /// Computes the L2 gas price of a new block, from the L2 gas price and the bouncer weights of its parent block.
+    /// A zero parent price means the parent block was not produced with this controller, in which case the initial
+    /// price is used.
+    pub fn next_price(
+        &self,
+        parent_price: u128,
+        parent_weights: &BouncerWeights,
+        block_capacity: &BouncerWeights,
+    ) -> u128 {
+        if parent_price == 0 {
+            return self.initial_price.clamp(self.min_price, self.max_price);
+        }
+
+        let utilization = block_utilization_ppm(parent_weights, block_capacity);
+        let target = u128::from(self.target_utilization_percent) * PPM / 100;
+        let max_change = parent_price.saturating_mul(self.max_change_percent.into()) / 100;
+
+        let price = if utilization > target {
+            // Always move the price up a little, so that a low price can still go up.
+            let change = max_change.saturating_mul(utilization - target) / (PPM - target);
+            parent_price.saturating_add(change.max(1))
+        } else {
+            let change = max_change.saturating_mul(target - utilization) / target;
+            parent_price - change
+        };
+        price.clamp(self.min_price, self.max_price)
+    }
+}
+
+/// Utilization of the most used resource of a block, in parts per million of the block capacity.
+fn block_utilization_ppm(weights: &BouncerWeights, capacity: &BouncerWeights) -> u128 {
+    let ratio = |used: u128, capacity: u128| if capacity == 0 { 0 } else { used.saturating_mul(PPM) / capacity };
+    [
+        ratio(weights.l1_gas as _, capacity.l1_gas as _),
+        ratio(weights.message_segment_length as _, capacity.message_segment_length as _),
+        ratio(weights.n_events as _, capacity.n_events as _),
+        ratio(weights.state_diff_size as _, capacity.state_diff_size as _),
+        ratio(weights.sierra_gas.0.into(), capacity.sierra_gas.0.into()),
+        ratio(weights.n_txs as _, capacity.n_txs as _),
+    ]
+    .into_iter()
+    .max()
+    .unwrap_or_default()
+    .min(PPM)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_api::execution_resources::GasAmount;
+
+    fn capacity() -> BouncerWeights {
+        BouncerWeights { n_txs: 100, sierra_gas: GasAmount(1_000_000), ..BouncerWeights::max() }
+    }
+
+    fn weights(n_txs: usize) -> BouncerWeights {
+        BouncerWeights { n_txs, ..BouncerWeights::empty() }
+    }
+
+    #[test]
+    fn test_utilization_uses_the_most_used_resource() {
+        assert_eq!(block_utilization_ppm(&weights(0), &capacity()), 0);
+        assert_eq!(block_utilization_ppm(&weights(25), &capacity()), 250_000);
+        let gas_heavy = BouncerWeights { sierra_gas: GasAmount(900_000), ..weights(25) };
+        assert_eq!(block_utilization_ppm(&gas_heavy, &capacity()), 900_000);
+    }
+
+    #[test]
+    fn test_next_price() {
+        let config = L2GasPriceConfig::default();
+        assert_eq!(config.next_price(0, &weights(100), &capacity()), 1_000_000_000);
+        // At target.
+        assert_eq!(config.next_price(1000, &weights(50), &capacity()), 1000);
+        // Full and empty blocks move the price by the max change.
+        assert_eq!(config.next_price(1000, &weights(100), &capacity()), 1100);
+        assert_eq!(config.next_price(1000, &weights(0), &capacity()), 900);
+        // In between.
+        assert_eq!(config.next_price(1000, &weights(75), &capacity()), 1050);
+        assert_eq!(config.next_price(1000, &weights(25), &capacity()), 950);
+        // Low prices still go up.
+        assert_eq!(config.next_price(2, &weights(100), &capacity()), 3);
+    }
+
+    #[test]
+    fn test_next_price_bounds() {
+        let config = L2GasPriceConfig { initial_price: 100, min_price: 100, max_price: 105, ..Default::default() };
+        config.validate().unwrap();
+        assert_eq!(config.next_price(100, &weights(0), &capacity()), 100);
+        assert_eq!(config.next_price(100, &weights(100), &capacity()), 105);
+        // The bounds also apply when the config changed since the parent block.
+        assert_eq!(config.next_price(1000, &weights(50), &capacity()), 105);
+
+        assert!(L2GasPriceConfig { target_utilization_percent: 100, ..Default::default() }.validate().is_err());
+        assert!(L2GasPriceConfig { initial_price: 0, ..Default::default() }.validate().is_err());
+    }
+}
@@ -1,9 +1,15 @@
 mod chain_config;
+mod fee_token;
 mod l1_da_mode;
+mod l2_gas_price;
 mod rpc_version;
 mod starknet_version;
+mod transaction_policy;
 
 pub use chain_config::*;
+pub use fee_token::*;
 pub use l1_da_mode::*;
+pub use l2_gas_price::*;
 pub use rpc_version::*;
 pub use starknet_version::*;
+pub use transaction_policy::*;
@@ -0,0 +1,288 @@
+//! Permissioned appchains can restrict which transactions are accepted by the node, using allow and deny lists.
+//! The policy is checked by the transaction validator before a transaction enters the mempool, and it does not
+//! apply to L1 handler transactions.
+
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
+use std::collections::HashSet;
+
+/// Address of the Universal Deployer Contract. Its `deployContract` calls are checked against the deployed class
+/// hash policy.
+pub const UDC_ADDRESS: Felt =
+    Felt::from_hex_unchecked("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
+/// Selector of the UDC `deployContract` entrypoint.
+pub const UDC_DEPLOY_CONTRACT_SELECTOR: Felt =
+    Felt::from_hex_unchecked("0x1987cbd17808b9a23693d4de7e246a443cfe37e6e7fbaeabd7d7e6532b07c3d");
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum PolicyViolation {
+    #[error("Sender address {0:#x} is not allowed to transact")]
+    SenderAddress(Felt),
+    #[error("Calling entrypoint {selector:#x} of contract {contract_address:#x} is not allowed")]
+    Call { contract_address: Felt, selector: Felt },
+    #[error("Declaring class hash {0:#x} is not allowed")]
+    DeclaredClassHash(Felt),
+    #[error("Deploying class hash {0:#x} is not allowed")]
+    DeployedClassHash(Felt),
+    #[error("Cannot decode the calls of this transaction, only the standard multicall format is supported")]
+    UndecodableCalls,
+}
+
+/// A list of allowed and denied values. A value is allowed when it is not denied, and when it is in the allow list if
+/// there is one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeltListPolicy {
+    /// When set, only these values are allowed.
+    pub allow: Option<HashSet<Felt>>,
+    pub deny: HashSet<Felt>,
+}
+
+impl FeltListPolicy {
+    pub fn is_unrestricted(&self) -> bool {
+        self.allow.is_none() && self.deny.is_empty()
+    }
+
+    pub fn is_allowed(&self, value: &Felt) -> bool {
+        !self.deny.contains(value) && self.allow.as_ref().is_none_or(|allow| allow.contains(value))
+    }
+}
+
+/// A contract entrypoint. When no selector is given, this matches every entrypoint of the contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CallTarget {
+    pub contract_address: Felt,
+    #[serde(default)]
+    pub selector: Option<Felt>,
+}
+
+impl CallTarget {
+    pub fn matches(&self, contract_address: &Felt, selector: &Felt) -> bool {
+        &self.contract_address == contract_address && self.selector.as_ref().is_none_or(|s| s == selector)
+    }
+}
+
+/// Same as [`FeltListPolicy`], for contract entrypoints.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CallListPolicy {
+    /// When set, only these entrypoints can be called.
+    pub allow: Option<Vec<CallTarget>>,
+    pub deny: Vec<CallTarget>,
+}
+
+impl CallListPolicy {
+    pub fn is_unrestricted(&self) -> bool {
+        self.allow.is_none() && self.deny.is_empty()
+    }
+
+    pub fn is_allowed(&self, contract_address: &Felt, selector: &Felt) -> bool {
+        !self.deny.iter().any(|target| target.matches(contract_address, selector))
+            && self.allow.as_ref().is_none_or(|allow| allow.iter().any(|t| t.matches(contract_address, selector)))
+    }
+}
+
+/// Declarative transaction policy of a permissioned chain. The default policy allows every transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransactionPolicy {
+    /// Sender address of invoke and declare transactions, and address of the deployed account for deploy account
+    /// transactions.
+    pub sender_address: FeltListPolicy,
+    /// Contract entrypoints called by invoke transactions.
+    pub calls: CallListPolicy,
+    /// Class hash of declare transactions.
+    pub declared_class_hash: FeltListPolicy,
+    /// Class hash of deploy account transactions and of the contracts deployed through the UDC. Contracts deployed
+    /// using the `deploy` syscall from any other contract are not checked.
+    pub deployed_class_hash: FeltListPolicy,
+}
+
+impl TransactionPolicy {
+    pub fn is_unrestricted(&self) -> bool {
+        self.sender_address.is_unrestricted()
+            && self.calls.is_unrestricted()
+            && self.declared_class_hash.is_unrestricted()
+            && self.deployed_class_hash.is_unrestricted()
+    }
+
+    pub fn check_sender(&self, sender_address: &Felt) -> Result<(), PolicyViolation> {
+        if !self.sender_address.is_allowed(sender_address) {
+            return Err(PolicyViolation::SenderAddress(*sender_address));
+        }
+        Ok(())
+    }
+
+    pub fn check_declare(&self, class_hash: &Felt) -> Result<(), PolicyViolation> {
+        if !self.declared_class_hash.is_allowed(class_hash) {
+            return Err(PolicyViolation::DeclaredClassHash(*class_hash));
+        }
+        Ok(())
+    }
+
+    pub fn check_deploy(&self, class_hash: &Felt) -> Result<(), PolicyViolation> {
+        if !self.deployed_class_hash.is_allowed(class_hash) {
+            return Err(PolicyViolation::DeployedClassHash(*class_hash));
+        }
+        Ok(())
+    }
+
+    pub fn check_call(
+        &self,
+        contract_address: &Felt,
+        selector: &Felt,
+        calldata: &[Felt],
+    ) -> Result<(), PolicyViolation> {
+        if !self.calls.is_allowed(contract_address, selector) {
+            return Err(PolicyViolation::Call { contract_address: *contract_address, selector: *selector });
+        }
+        if contract_address == &UDC_ADDRESS && selector == &UDC_DEPLOY_CONTRACT_SELECTOR {
+            // deployContract(class_hash, salt, unique, calldata)
+            let class_hash = calldata.first().ok_or(PolicyViolation::UndecodableCalls)?;
+            self.check_deploy(class_hash)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the calls made by the `__execute__` entrypoint of an account, using the calldata of an invoke
+    /// transaction.
+    pub fn check_account_calls(&self, calldata: &[Felt]) -> Result<(), PolicyViolation> {
+        if self.calls.is_unrestricted() && self.deployed_class_hash.is_unrestricted() {
+            return Ok(());
+        }
+        for call in decode_multicall(calldata).ok_or(PolicyViolation::UndecodableCalls)? {
+            self.check_call(call.to, call.selector, call.calldata)?;
+        }
+        Ok(())
+    }
+}
+
+struct AccountCall<'a> {
+    to: &'a Felt,
+    selector: &'a Felt,
+    calldata: &'a [Felt],
+}
+
+/// Decodes the standard Cairo 1 account calldata: `[n_calls, (to, selector, calldata_len, calldata...)...]`.
+fn decode_multicall(calldata: &[Felt]) -> Option<Vec<AccountCall<'_>>> {
+    let (n_calls, mut rest) = calldata.split_first()?;
+    let n_calls = u64::try_from(*n_calls).ok()? as usize;
+    let mut calls = Vec::with_capacity(n_calls.min(rest.len()));
+    for _ in 0..n_calls {
+        let [to, selector, calldata_len, tail @ ..] = rest else { return None };
+        let calldata_len = u64::try_from(*calldata_len).ok()? as usize;
+        if calldata_len > tail.len() {
+            return None;
+        }
+        let (calldata, tail) = tail.split_at(calldata_len);
+        calls.push(AccountCall { to, selector, calldata });
+        rest = tail;
+    }
+    rest.is_empty().then_some(calls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: Felt = Felt::from_hex_unchecked("0xacc");
+    const TOKEN: Felt = Felt::from_hex_unchecked("0x70c");
+    const TRANSFER: Felt = Felt::from_hex_unchecked("0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e");
+    const APPROVE: Felt = Felt::from_hex_unchecked("0xa9e");
+
+    #[test]
+    fn test_default_policy_is_unrestricted() {
+        let policy = TransactionPolicy::default();
+        assert!(policy.is_unrestricted());
+        assert_eq!(policy.check_sender(&ACCOUNT), Ok(()));
+        assert_eq!(policy.check_account_calls(&[Felt::ONE]), Ok(()));
+    }
+
+    #[test]
+    fn test_sender_policy() {
+        let policy = TransactionPolicy {
+            sender_address: FeltListPolicy { allow: Some([ACCOUNT, Felt::TWO].into()), deny: [Felt::TWO].into() },
+            ..Default::default()
+        };
+        assert_eq!(policy.check_sender(&ACCOUNT), Ok(()));
+        assert_eq!(policy.check_sender(&Felt::ONE), Err(PolicyViolation::SenderAddress(Felt::ONE)));
+        // Deny wins over allow.
+        assert_eq!(policy.check_sender(&Felt::TWO), Err(PolicyViolation::SenderAddress(Felt::TWO)));
+    }
+
+    #[test]
+    fn test_call_policy() {
+        let policy = TransactionPolicy {
+            calls: CallListPolicy {
+                allow: None,
+                deny: vec![CallTarget { contract_address: TOKEN, selector: Some(APPROVE) }],
+            },
+            ..Default::default()
+        };
+        let calldata = |selector| vec![Felt::TWO, TOKEN, TRANSFER, Felt::ONE, Felt::ONE, TOKEN, selector, Felt::ZERO];
+
+        assert_eq!(policy.check_account_calls(&calldata(TRANSFER)), Ok(()));
+        assert_eq!(
+            policy.check_account_calls(&calldata(APPROVE)),
+            Err(PolicyViolation::Call { contract_address: TOKEN, selector: APPROVE })
+        );
+        // Trailing or missing felts.
+        assert_eq!(policy.check_account_calls(&[Felt::ONE, TOKEN, TRANSFER]), Err(PolicyViolation::UndecodableCalls));
+        assert_eq!(
+            policy.check_account_calls(&[Felt::ONE, TOKEN, TRANSFER, Felt::ZERO, Felt::ONE]),
+            Err(PolicyViolation::UndecodableCalls)
+        );
+    }
+
+    #[test]
+    fn test_udc_deploy_policy() {
+        let policy = TransactionPolicy {
+            deployed_class_hash: FeltListPolicy { allow: Some([Felt::ONE].into()), deny: Default::default() },
+            ..Default::default()
+        };
+        let calldata = |class_hash| {
+            vec![
+                Felt::ONE,
+                UDC_ADDRESS,
+                UDC_DEPLOY_CONTRACT_SELECTOR,
+                Felt::from(4),
+                class_hash,
+                Felt::ZERO,
+                Felt::ZERO,
+                Felt::ZERO,
+            ]
+        };
+
+        assert_eq!(policy.check_account_calls(&calldata(Felt::ONE)), Ok(()));
+        assert_eq!(
+            policy.check_account_calls(&calldata(Felt::TWO)),
+            Err(PolicyViolation::DeployedClassHash(Felt::TWO))
+        );
+        assert_eq!(policy.check_deploy(&Felt::TWO), Err(PolicyViolation::DeployedClassHash(Felt::TWO)));
+    }
+
+    #[test]
+    fn test_deserialize_policy() {
+        let policy: TransactionPolicy = serde_yaml::from_str(
+            r#"
+sender_address:
+  allow: ["0xacc"]
+calls:
+  deny:
+    - contract_address: "0x70c"
+      selector: "0xa9e"
+    - contract_address: "0x123"
+declared_class_hash:
+  deny: ["0x1"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(policy.sender_address.allow, Some([ACCOUNT].into()));
+        assert_eq!(policy.calls.deny.len(), 2);
+        assert!(policy.calls.is_allowed(&TOKEN, &TRANSFER));
+        assert!(!policy.calls.is_allowed(&Felt::from_hex_unchecked("0x123"), &TRANSFER));
+        assert_eq!(policy.check_declare(&Felt::ONE), Err(PolicyViolation::DeclaredClassHash(Felt::ONE)));
+        assert!(policy.deployed_class_hash.is_unrestricted());
+    }
+}
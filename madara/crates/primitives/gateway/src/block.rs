@@ -111,9 +111,12 @@ pub struct ProviderBlock {
     #[serde(default)]
     pub state_diff_length: Option<u64>,
     pub status: BlockStatus,
+    /// `VALIDIUM` for the blocks of validium chains, see [`L1DataAvailabilityMode::Validium`].
     pub l1_da_mode: L1DataAvailabilityMode,
     pub l1_gas_price: ResourcePrice,
     pub l1_data_gas_price: ResourcePrice,
+    #[serde(default)]
+    pub l2_gas_price: ResourcePrice,
     pub transactions: Vec<Transaction>,
     pub transaction_receipts: Vec<ConfirmedReceipt>,
     #[serde(default)]
@@ -174,6 +177,10 @@ impl ProviderBlock {
                 price_in_wei: block.info.header.l1_gas_price.eth_l1_data_gas_price,
                 price_in_fri: block.info.header.l1_gas_price.strk_l1_data_gas_price,
             },
+            l2_gas_price: ResourcePrice {
+                price_in_wei: block.info.header.l1_gas_price.eth_l2_gas_price,
+                price_in_fri: block.info.header.l1_gas_price.strk_l2_gas_price,
+            },
             transactions,
             transaction_receipts,
             starknet_version,
@@ -201,6 +208,8 @@ impl ProviderBlock {
                 strk_l1_gas_price: self.l1_gas_price.price_in_fri,
                 eth_l1_data_gas_price: self.l1_data_gas_price.price_in_wei,
                 strk_l1_data_gas_price: self.l1_data_gas_price.price_in_fri,
+                eth_l2_gas_price: self.l2_gas_price.price_in_wei,
+                strk_l2_gas_price: self.l2_gas_price.price_in_fri,
             },
             l1_da_mode: self.l1_da_mode,
             block_number: self.block_number,
@@ -222,9 +231,12 @@ impl ProviderBlock {
 pub struct ProviderBlockPending {
     pub parent_block_hash: Felt,
     pub status: BlockStatus,
+    /// `VALIDIUM` for the blocks of validium chains, see [`L1DataAvailabilityMode::Validium`].
     pub l1_da_mode: L1DataAvailabilityMode,
     pub l1_gas_price: ResourcePrice,
     pub l1_data_gas_price: ResourcePrice,
+    #[serde(default)]
+    pub l2_gas_price: ResourcePrice,
     pub transactions: Vec<Transaction>,
     pub timestamp: u64,
     #[serde(default)]
@@ -263,6 +275,10 @@ impl ProviderBlockPending {
                 price_in_wei: block.info.header.l1_gas_price.eth_l1_data_gas_price,
                 price_in_fri: block.info.header.l1_gas_price.strk_l1_data_gas_price,
             },
+            l2_gas_price: ResourcePrice {
+                price_in_wei: block.info.header.l1_gas_price.eth_l2_gas_price,
+                price_in_fri: block.info.header.l1_gas_price.strk_l2_gas_price,
+            },
             transactions,
             timestamp: block.info.header.block_timestamp.0,
             sequencer_address: block.info.header.sequencer_address,
@@ -282,6 +298,8 @@ impl ProviderBlockPending {
                 strk_l1_gas_price: self.l1_gas_price.price_in_fri,
                 eth_l1_data_gas_price: self.l1_data_gas_price.price_in_wei,
                 strk_l1_data_gas_price: self.l1_data_gas_price.price_in_fri,
+                eth_l2_gas_price: self.l2_gas_price.price_in_wei,
+                strk_l2_gas_price: self.l2_gas_price.price_in_fri,
             },
             l1_da_mode: self.l1_da_mode,
         })
@@ -304,7 +322,7 @@ pub struct ProviderBlockSignature {
 }
 
 #[serde_as]
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "deny_unknown_fields", serde(deny_unknown_fields))]
 #[cfg_attr(test, derive(Eq))]
 pub struct ResourcePrice {
@@ -177,4 +177,6 @@ pub enum StarknetErrorCode {
     InvalidContractClassVersion,
     #[serde(rename = "StarknetErrorCode.RATE_LIMITED")]
     RateLimited,
+    #[serde(rename = "StarknetErrorCode.TRANSACTION_NOT_PERMITTED")]
+    TransactionNotPermitted,
 }
@@ -0,0 +1,29 @@
+use anyhow::bail;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::{Oracle, OraclePrice};
+
+/// Always returns the same price, for fee tokens with a fixed ETH exchange rate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixedOracle {
+    pub price: u128,
+    pub decimals: u32,
+}
+
+impl FixedOracle {
+    pub fn new(price: u128, decimals: u32) -> Self {
+        Self { price, decimals }
+    }
+}
+
+#[async_trait]
+impl Oracle for FixedOracle {
+    /// The price is never stale, so it has no timestamp.
+    async fn fetch_eth_strk_price(&self) -> anyhow::Result<OraclePrice> {
+        if self.price == 0 {
+            bail!("Fixed oracle price is 0");
+        }
+        Ok(OraclePrice { price: self.price, decimals: self.decimals, timestamp: None })
+    }
+}
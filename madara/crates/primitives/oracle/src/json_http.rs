@@ -0,0 +1,117 @@
+use std::time::{Duration, SystemTime};
+
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use mp_utils::serde::{deserialize_url, serialize_url};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{parse_decimal_price, Oracle, OraclePrice};
+
+/// Reads the price from any HTTP endpoint returning JSON.
+///
+/// Paths are dot-separated object keys and array indices, such as `data.0.price`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonHttpOracle {
+    #[serde(serialize_with = "serialize_url", deserialize_with = "deserialize_url")]
+    pub url: Url,
+    /// Extra headers sent with the request, such as an api key.
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// Path to the price. It can be a JSON number, a decimal string, or a `0x` prefixed hex string that already
+    /// includes the decimals.
+    pub price_path: String,
+    /// Decimals of the returned price.
+    pub decimals: u32,
+    /// Path to the unix timestamp of the price, in seconds.
+    #[serde(default)]
+    pub timestamp_path: Option<String>,
+}
+
+fn select<'a>(value: &'a Value, path: &str) -> anyhow::Result<&'a Value> {
+    path.split('.').filter(|key| !key.is_empty()).try_fold(value, |value, key| {
+        let next = match value {
+            Value::Array(array) => key.parse::<usize>().ok().and_then(|i| array.get(i)),
+            _ => value.get(key),
+        };
+        next.with_context(|| format!("No `{key}` in the response at path `{path}`"))
+    })
+}
+
+fn parse_price(value: &Value, decimals: u32) -> anyhow::Result<u128> {
+    let s = match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => bail!("Expected a number or a string for the price, got {value}"),
+    };
+    match s.strip_prefix("0x") {
+        Some(hex) => u128::from_str_radix(hex, 16).context("Invalid hex price"),
+        None => parse_decimal_price(&s, decimals),
+    }
+}
+
+impl JsonHttpOracle {
+    fn parse_response(&self, response: &Value) -> anyhow::Result<OraclePrice> {
+        let price = parse_price(select(response, &self.price_path)?, self.decimals)?;
+        if price == 0 {
+            bail!("JSON oracle returned 0 for the price");
+        }
+        let timestamp = match &self.timestamp_path {
+            Some(path) => {
+                let secs = select(response, path)?.as_u64().context("Expected an integer for the price timestamp")?;
+                Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            }
+            None => None,
+        };
+        Ok(OraclePrice { price, decimals: self.decimals, timestamp })
+    }
+}
+
+#[async_trait]
+impl Oracle for JsonHttpOracle {
+    async fn fetch_eth_strk_price(&self) -> anyhow::Result<OraclePrice> {
+        let mut request = reqwest::Client::new().get(self.url.clone());
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let response = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .context("failed to retrieve price from json oracle")?;
+        let response = response.json::<Value>().await.context("failed to parse api response")?;
+        self.parse_response(&response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn oracle(price_path: &str, timestamp_path: Option<&str>) -> JsonHttpOracle {
+        JsonHttpOracle {
+            url: Url::parse("http://localhost").unwrap(),
+            headers: vec![],
+            price_path: price_path.into(),
+            decimals: 4,
+            timestamp_path: timestamp_path.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response = json!({ "data": [{ "price": 2500.25, "updated_at": 1700000000 }], "hex": "0x10", "str": "3.5" });
+
+        let price = oracle("data.0.price", Some("data.0.updated_at")).parse_response(&response).unwrap();
+        assert_eq!(price.price, 25002500);
+        assert_eq!(price.decimals, 4);
+        assert_eq!(price.timestamp, Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1700000000)));
+
+        assert_eq!(oracle("hex", None).parse_response(&response).unwrap().price, 16);
+        assert_eq!(oracle("str", None).parse_response(&response).unwrap().price, 35000);
+        assert!(oracle("data.1.price", None).parse_response(&response).is_err());
+        assert!(oracle("data", None).parse_response(&response).is_err());
+    }
+}
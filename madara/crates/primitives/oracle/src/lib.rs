@@ -1,8 +1,64 @@
+use anyhow::bail;
 use async_trait::async_trait;
+use std::time::{Duration, SystemTime};
 
+pub mod fixed;
+pub mod json_http;
+pub mod median;
 pub mod pragma;
 
+pub use mp_utils::parsers::parse_decimal_price;
+
+/// ETH price of the fee token, as `price / 10^decimals` fee tokens per ETH.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: u128,
+    pub decimals: u32,
+    /// When the price was last updated by its source. [`None`] when the source does not say.
+    pub timestamp: Option<SystemTime>,
+}
+
+impl OraclePrice {
+    /// Returns the price with `decimals` decimals, which must not be lower than the current ones.
+    pub fn with_decimals(&self, decimals: u32) -> anyhow::Result<u128> {
+        let scale = decimals.checked_sub(self.decimals).context("Cannot remove decimals from a price")?;
+        10u128.checked_pow(scale).and_then(|scale| self.price.checked_mul(scale)).context("Price overflow")
+    }
+
+    /// Fails when the price is older than `max_age`. Prices without a timestamp are never stale.
+    pub fn ensure_fresh(&self, max_age: Duration) -> anyhow::Result<()> {
+        let Some(timestamp) = self.timestamp else { return Ok(()) };
+        // A timestamp in the future is not stale.
+        let age = SystemTime::now().duration_since(timestamp).unwrap_or_default();
+        if age > max_age {
+            bail!("Price is stale: last updated {age:?} ago, the maximum age is {max_age:?}");
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 pub trait Oracle: Send + Sync {
-    async fn fetch_eth_strk_price(&self) -> anyhow::Result<(u128, u32)>;
+    async fn fetch_eth_strk_price(&self) -> anyhow::Result<OraclePrice>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oracle_price_with_decimals() {
+        let price = OraclePrice { price: 25, decimals: 1, timestamp: None };
+        assert_eq!(price.with_decimals(3).unwrap(), 2500);
+        assert!(price.with_decimals(0).is_err());
+    }
+
+    #[test]
+    fn test_oracle_price_ensure_fresh() {
+        let max_age = Duration::from_secs(60);
+        let price = |age| OraclePrice { price: 1, decimals: 0, timestamp: Some(SystemTime::now() - age) };
+        assert!(price(Duration::from_secs(10)).ensure_fresh(max_age).is_ok());
+        assert!(price(Duration::from_secs(120)).ensure_fresh(max_age).is_err());
+        assert!(OraclePrice { price: 1, decimals: 0, timestamp: None }.ensure_fresh(max_age).is_ok());
+    }
 }
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::bail;
+use async_trait::async_trait;
+use futures::future::join_all;
+
+use crate::{Oracle, OraclePrice};
+
+/// Queries several oracles and returns the median of their prices.
+///
+/// Sources that fail or return a stale price are left out, as long as at least `min_sources` of them answer.
+pub struct MedianOracle {
+    oracles: Vec<Arc<dyn Oracle>>,
+    min_sources: usize,
+    max_age: Option<Duration>,
+}
+
+impl MedianOracle {
+    pub fn new(oracles: Vec<Arc<dyn Oracle>>, min_sources: usize, max_age: Option<Duration>) -> Self {
+        Self { oracles, min_sources: min_sources.max(1), max_age }
+    }
+}
+
+#[async_trait]
+impl Oracle for MedianOracle {
+    /// The returned price has the highest number of decimals of the sources, and the timestamp of the oldest one. Sources
+    /// without a timestamp, such as fixed prices, are not taken into account for the timestamp.
+    async fn fetch_eth_strk_price(&self) -> anyhow::Result<OraclePrice> {
+        let mut prices = vec![];
+        for res in join_all(self.oracles.iter().map(|oracle| oracle.fetch_eth_strk_price())).await {
+            let res = res.and_then(|price| match self.max_age {
+                Some(max_age) => price.ensure_fresh(max_age).map(|_| price),
+                None => Ok(price),
+            });
+            match res {
+                Ok(price) => prices.push(price),
+                Err(e) => tracing::warn!("Price oracle source failed: {e:#}"),
+            }
+        }
+        if prices.len() < self.min_sources {
+            bail!(
+                "Only {} of the {} price sources answered, {} are required",
+                prices.len(),
+                self.oracles.len(),
+                self.min_sources
+            );
+        }
+
+        let decimals = prices.iter().map(|price| price.decimals).max().unwrap_or_default();
+        let timestamp = prices.iter().filter_map(|price| price.timestamp).min();
+        let mut values =
+            prices.iter().map(|price| price.with_decimals(decimals)).collect::<anyhow::Result<Vec<_>>>()?;
+        values.sort_unstable();
+        let mid = values.len() / 2;
+        let price = if values.len() % 2 == 0 { values[mid - 1].midpoint(values[mid]) } else { values[mid] };
+
+        Ok(OraclePrice { price, decimals, timestamp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixed::FixedOracle;
+    use std::time::SystemTime;
+
+    struct StaticOracle(anyhow::Result<OraclePrice>);
+
+    #[async_trait]
+    impl Oracle for StaticOracle {
+        async fn fetch_eth_strk_price(&self) -> anyhow::Result<OraclePrice> {
+            self.0.as_ref().map(|price| *price).map_err(|e| anyhow::anyhow!("{e}"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_median_of_sources() {
+        let oracle = MedianOracle::new(
+            vec![
+                Arc::new(FixedOracle::new(10, 0)),
+                Arc::new(FixedOracle::new(1200, 2)),
+                Arc::new(FixedOracle::new(30, 0)),
+                Arc::new(StaticOracle(Err(anyhow::anyhow!("down")))),
+            ],
+            2,
+            None,
+        );
+        assert_eq!(
+            oracle.fetch_eth_strk_price().await.unwrap(),
+            OraclePrice { price: 1200, decimals: 2, timestamp: None }
+        );
+
+        let oracle =
+            MedianOracle::new(vec![Arc::new(FixedOracle::new(10, 0)), Arc::new(FixedOracle::new(13, 0))], 1, None);
+        assert_eq!(oracle.fetch_eth_strk_price().await.unwrap().price, 11);
+    }
+
+    #[tokio::test]
+    async fn test_median_skips_stale_sources() {
+        let stale =
+            OraclePrice { price: 1, decimals: 0, timestamp: Some(SystemTime::now() - Duration::from_secs(3600)) };
+        let oracle = MedianOracle::new(
+            vec![Arc::new(StaticOracle(Ok(stale))), Arc::new(FixedOracle::new(10, 0))],
+            1,
+            Some(Duration::from_secs(60)),
+        );
+        assert_eq!(oracle.fetch_eth_strk_price().await.unwrap().price, 10);
+
+        let oracle = MedianOracle::new(vec![Arc::new(StaticOracle(Ok(stale)))], 1, Some(Duration::from_secs(60)));
+        assert!(oracle.fetch_eth_strk_price().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_median_timestamp_of_oldest_timestamped_source() {
+        let now = SystemTime::now();
+        let price = |price, age| OraclePrice { price, decimals: 0, timestamp: Some(now - Duration::from_secs(age)) };
+        let oracle = MedianOracle::new(
+            vec![
+                Arc::new(StaticOracle(Ok(price(10, 30)))),
+                Arc::new(FixedOracle::new(11, 0)),
+                Arc::new(StaticOracle(Ok(price(12, 10)))),
+            ],
+            1,
+            None,
+        );
+        assert_eq!(
+            oracle.fetch_eth_strk_price().await.unwrap(),
+            OraclePrice { price: 11, decimals: 0, timestamp: Some(now - Duration::from_secs(30)) }
+        );
+    }
+}
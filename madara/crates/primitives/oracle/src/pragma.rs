@@ -1,4 +1,5 @@
 use std::fmt;
+use std::time::{Duration, SystemTime};
 
 use anyhow::{bail, Context};
 use async_trait::async_trait;
@@ -6,7 +7,7 @@ use mp_utils::serde::{deserialize_url, serialize_url};
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
-use crate::Oracle;
+use crate::{Oracle, OraclePrice};
 
 pub const DEFAULT_API_URL: &str = "https://api.dev.pragma.build/node/v1/data/";
 
@@ -55,9 +56,9 @@ impl Oracle for PragmaOracle {
     /// Methods to retrieve ETH/STRK price from Pragma Oracle
     ///
     /// Return values:
-    /// Ok((u128, u32)) : return the price tuple as (price, decimals)
+    /// Ok(OraclePrice) : return the price, its decimals and the time it was last updated
     /// Err(e) : return an error if anything went wrong in the fetching process or eth/strk price is 0
-    async fn fetch_eth_strk_price(&self) -> anyhow::Result<(u128, u32)> {
+    async fn fetch_eth_strk_price(&self) -> anyhow::Result<OraclePrice> {
         let response = reqwest::Client::new()
             .get(self.get_fetch_url(String::from("eth"), String::from("strk")))
             .header("x-api-key", self.api_key.clone())
@@ -74,7 +75,11 @@ impl Oracle for PragmaOracle {
         if !self.is_in_bounds(eth_strk_price) {
             bail!("ETH/STRK price outside of bounds");
         }
-        Ok((eth_strk_price, oracle_api_response.decimals))
+        Ok(OraclePrice {
+            price: eth_strk_price,
+            decimals: oracle_api_response.decimals,
+            timestamp: oracle_api_response.timestamp.map(|ms| SystemTime::UNIX_EPOCH + Duration::from_millis(ms)),
+        })
     }
 }
 
@@ -149,6 +154,9 @@ fn default_oracle_api_url() -> Url {
 struct PragmaApiResponse {
     price: String,
     decimals: u32,
+    /// Unix timestamp of the price, in milliseconds.
+    #[serde(default)]
+    timestamp: Option<u64>,
 }
 
 pub struct PragmaOracleBuilder {
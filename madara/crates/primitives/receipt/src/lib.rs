@@ -283,6 +283,23 @@ pub struct MsgToL1 {
     pub payload: Vec<Felt>,
 }
 
+// Specification reference: https://docs.starknet.io/architecture-and-concepts/network-architecture/messaging-mechanism/#l2_l1_message_structure
+//
+// This is the hash under which the core contract registers the message once its block is settled, and which
+// `consumeMessageFromL2` checks when the message is consumed on L1.
+impl MsgToL1 {
+    pub fn compute_hash(&self) -> Hash256 {
+        let mut hasher = Keccak256::new();
+        hasher.update(self.from_address.to_bytes_be());
+        hasher.update(self.to_address.to_bytes_be());
+        hasher.update([0u8; 24]); // Padding
+        hasher.update((self.payload.len() as u64).to_be_bytes());
+        self.payload.iter().for_each(|felt| hasher.update(felt.to_bytes_be()));
+        let bytes = hasher.finalize().as_slice().try_into().expect("Byte array length mismatch");
+        Hash256::from_bytes(bytes)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct MsgToL2 {
@@ -461,6 +478,15 @@ mod tests {
         assert_eq!(hash, expected_hash,);
     }
 
+    #[test]
+    fn test_msg_to_l1_compute_hash() {
+        let msg = MsgToL1 { from_address: Felt::ONE, to_address: Felt::TWO, payload: vec![Felt::THREE, Felt::from(4)] };
+        let expected_hash =
+            Hash256::from_hex("0x2cac3db3b1d4d30a6799a472c477b4a01a3a4bc43fd92f1e6506ce82d7d810dd").unwrap();
+
+        assert_eq!(msg.compute_hash(), expected_hash);
+    }
+
     #[test]
     fn test_execution_result_compute_hash() {
         let succeeded = ExecutionResult::Succeeded;
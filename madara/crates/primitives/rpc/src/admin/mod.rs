@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt;
 
-use crate::{Address, DeprecatedContractClass, Signature};
+use crate::{
+    Address, CallType, ComputationResources, DeprecatedContractClass, EntryPointType, OrderedEvent, OrderedMessage,
+    Signature, TxnHash,
+};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct BroadcastedDeclareTxnV0 {
@@ -20,3 +23,81 @@ impl BroadcastedDeclareTxnV0 {
         self.is_query
     }
 }
+
+/// The trace of a transaction as returned by `madara_debugTraceTransaction` and `madara_debugTraceCall`. Unlike
+/// the spec traces, every call frame carries its storage accesses, its gas consumption and its revert reason.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DebugTransactionTrace {
+    pub transaction_hash: TxnHash,
+    #[serde(default)]
+    pub validate_invocation: Option<DebugCallFrame>,
+    /// The `__execute__`, constructor or l1 handler invocation, depending on the transaction type
+    #[serde(default)]
+    pub execute_invocation: Option<DebugCallFrame>,
+    #[serde(default)]
+    pub fee_transfer_invocation: Option<DebugCallFrame>,
+    /// The revert reason of the transaction, if it was reverted
+    #[serde(default)]
+    pub revert_reason: Option<String>,
+    /// The fee charged for the transaction
+    pub actual_fee: Felt,
+    pub gas_consumed: DebugGasConsumed,
+}
+
+/// Total gas consumed by a transaction, including data availability.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DebugGasConsumed {
+    pub l1_gas: u64,
+    pub l1_data_gas: u64,
+    pub l2_gas: u64,
+}
+
+/// A single call frame of a transaction, alongside the frames of the calls it made.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DebugCallFrame {
+    pub contract_address: Felt,
+    /// The hash of the class being called, if it could be resolved
+    #[serde(default)]
+    pub class_hash: Option<Felt>,
+    /// The address of the invoking contract. 0 for the root invocation
+    pub caller_address: Felt,
+    pub entry_point_selector: Felt,
+    pub entry_point_type: EntryPointType,
+    pub call_type: CallType,
+    pub calldata: Vec<Felt>,
+    /// The value returned by the call, or its panic data if it failed
+    pub result: Vec<Felt>,
+    pub failed: bool,
+    /// Details on why the call failed. Only set when `failed` is true
+    #[serde(default)]
+    pub revert: Option<DebugRevertInfo>,
+    /// Sierra gas consumed by this call, including its inner calls
+    pub gas_consumed: u64,
+    /// Steps and builtins used by this call, including its inner calls
+    pub execution_resources: ComputationResources,
+    /// The storage slots of `contract_address` read or written by this call
+    pub storage_accesses: Vec<DebugStorageAccess>,
+    pub events: Vec<OrderedEvent>,
+    pub messages: Vec<OrderedMessage>,
+    pub calls: Vec<DebugCallFrame>,
+}
+
+/// A storage slot accessed during a call. Values are taken before and after the whole transaction, since
+/// blockifier does not record the order of accesses within a call.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DebugStorageAccess {
+    pub key: Felt,
+    pub value_before: Felt,
+    pub value_after: Felt,
+    /// Whether the transaction changed the value of this slot
+    pub modified: bool,
+}
+
+/// The revert reason of a failed call frame.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DebugRevertInfo {
+    /// The raw cairo panic data
+    pub panic_data: Vec<Felt>,
+    /// The panic data decoded as short strings and byte arrays where possible
+    pub reason: String,
+}
@@ -1,7 +1,9 @@
 mod block_id;
 mod query;
+mod state_override;
 mod syncing_status;
 
 pub use self::block_id::*;
 pub use self::query::*;
+pub use self::state_override::*;
 pub use self::syncing_status::*;
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
+
+use crate::KeyValuePair;
+
+/// Changes applied on top of the state of a block before executing `call`, `estimateFee` and
+/// `simulateTransactions`. This is not part of the Starknet specs.
+pub type StateOverride = Vec<ContractStateOverride>;
+
+/// Overrides for the state of a single contract. Fields that are not set keep their value from the block.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ContractStateOverride {
+    pub contract_address: Felt,
+    /// Replace the nonce of the contract
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Felt>,
+    /// Replace the class of the contract. The class must be declared
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_hash: Option<Felt>,
+    /// Replace individual storage slots of the contract
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub storage: Vec<KeyValuePair>,
+    /// Replace the balance of the contract in both the ETH and STRK fee tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_token_balance: Option<Felt>,
+}
@@ -1,6 +1,7 @@
 //! v0.7.1 of the API.
 pub use crate::custom::{
-    BlockId, BroadcastedDeclareTxn, BroadcastedDeployAccountTxn, BroadcastedInvokeTxn, SyncingStatus,
+    BlockId, BroadcastedDeclareTxn, BroadcastedDeployAccountTxn, BroadcastedInvokeTxn, ContractStateOverride,
+    StateOverride, SyncingStatus,
 };
 
 mod starknet_api_openrpc;
@@ -171,6 +171,21 @@ impl BroadcastedTxn {
             BroadcastedTxn::DeployAccount(txn) => txn.is_query(),
         }
     }
+    /// Resource bounds of V3 transactions.
+    pub fn resource_bounds(&self) -> Option<&ResourceBoundsMapping> {
+        match self {
+            BroadcastedTxn::Invoke(BroadcastedInvokeTxn::V3(txn) | BroadcastedInvokeTxn::QueryV3(txn)) => {
+                Some(&txn.resource_bounds)
+            }
+            BroadcastedTxn::Declare(BroadcastedDeclareTxn::V3(txn) | BroadcastedDeclareTxn::QueryV3(txn)) => {
+                Some(&txn.resource_bounds)
+            }
+            BroadcastedTxn::DeployAccount(
+                BroadcastedDeployAccountTxn::V3(txn) | BroadcastedDeployAccountTxn::QueryV3(txn),
+            ) => Some(&txn.resource_bounds),
+            _ => None,
+        }
+    }
 }
 
 /// StarkNet chain id, given in hex representation.
@@ -623,6 +623,13 @@ impl DeployAccountTransaction {
         }
     }
 
+    pub fn class_hash(&self) -> &Felt {
+        match self {
+            DeployAccountTransaction::V1(tx) => &tx.class_hash,
+            DeployAccountTransaction::V3(tx) => &tx.class_hash,
+        }
+    }
+
     pub fn nonce(&self) -> &Felt {
         match self {
             DeployAccountTransaction::V1(tx) => &tx.nonce,
@@ -46,6 +46,17 @@ pub fn parse_felt(s: &str) -> anyhow::Result<Felt> {
     Felt::from_hex(s).with_context(|| format!("Invalid felt format: {s}"))
 }
 
+/// Parses a decimal number such as `2500` or `2500.25` into an integer with `decimals` decimals. Extra decimals are
+/// truncated.
+pub fn parse_decimal_price(s: &str, decimals: u32) -> anyhow::Result<u128> {
+    let (int, frac) = s.trim().split_once('.').unwrap_or((s.trim(), ""));
+    if int.is_empty() && frac.is_empty() || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit()) {
+        bail!("Invalid decimal price `{s}`");
+    }
+    let frac: String = frac.chars().chain(std::iter::repeat('0')).take(decimals as usize).collect();
+    format!("{int}{frac}").parse().with_context(|| format!("Price `{s}` does not fit in 128 bits"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,4 +76,15 @@ mod tests {
         assert!(parse_duration("-5s").is_err());
         assert!(parse_duration("5.5s").is_err());
     }
+
+    #[rstest]
+    fn test_parse_decimal_price() {
+        assert_eq!(parse_decimal_price("2500", 2).unwrap(), 250000);
+        assert_eq!(parse_decimal_price("2500.25", 4).unwrap(), 25002500);
+        assert_eq!(parse_decimal_price("0.123456", 3).unwrap(), 123);
+        assert_eq!(parse_decimal_price(".5", 1).unwrap(), 5);
+        assert!(parse_decimal_price("", 2).is_err());
+        assert!(parse_decimal_price("-1", 2).is_err());
+        assert!(parse_decimal_price("1e10", 2).is_err());
+    }
 }
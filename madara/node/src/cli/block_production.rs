@@ -1,4 +1,8 @@
+use mp_utils::parsers::{parse_duration, parse_url};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use url::Url;
 
 /// Parameters used to config block production.
 #[derive(Clone, Debug, clap::Parser, Deserialize, Serialize)]
@@ -11,4 +15,29 @@ pub struct BlockProductionParams {
     /// Create this number of contracts in the genesis block for the devnet configuration.
     #[arg(env = "MADARA_DEVNET_CONTRACTS", long, default_value_t = 10)]
     pub devnet_contracts: u64,
+
+    /// Run this sequencer in high availability mode, with a leader lease stored in this file. The file must be shared
+    /// by all the sequencer nodes of the chain. Only the node holding the lease produces blocks, the other nodes stay
+    /// in standby and follow the leader through L2 sync, using `--gateway-url` to point to the leader.
+    #[arg(env = "MADARA_HA_LEASE_FILE", long, value_name = "PATH", conflicts_with = "ha_lease_url")]
+    pub ha_lease_file: Option<PathBuf>,
+
+    /// Same as `--ha-lease-file`, with the leader lease served by an http lease server.
+    #[arg(env = "MADARA_HA_LEASE_URL", long, value_parser = parse_url, value_name = "URL")]
+    pub ha_lease_url: Option<Url>,
+
+    /// Unique identifier of this node in high availability mode. Defaults to the node name.
+    #[arg(env = "MADARA_HA_NODE_ID", long, value_name = "ID")]
+    pub ha_node_id: Option<String>,
+
+    /// In high availability mode, a standby node takes over block production when the leader did not renew its lease
+    /// for this duration.
+    #[arg(env = "MADARA_HA_LEASE_TTL", long, default_value = "15s", value_parser = parse_duration)]
+    pub ha_lease_ttl: Duration,
+}
+
+impl BlockProductionParams {
+    pub fn high_availability(&self) -> bool {
+        self.ha_lease_file.is_some() || self.ha_lease_url.is_some()
+    }
 }
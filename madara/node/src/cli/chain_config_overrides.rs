@@ -8,8 +8,8 @@ use serde_yaml::Value;
 use starknet_api::core::{ChainId, ContractAddress};
 
 use mp_chain_config::{
-    deserialize_starknet_version, serialize_starknet_version, BlockProductionConfig, ChainConfig,
-    L1DataAvailabilityMode, StarknetVersion,
+    deserialize_starknet_version, serialize_starknet_version, BlockProductionConfig, ChainConfig, FeeTokenConfig,
+    L1DataAvailabilityMode, L2GasPriceConfig, StarknetVersion, TransactionPolicy,
 };
 use mp_utils::parsers::parse_key_value_yaml;
 use mp_utils::serde::{
@@ -39,8 +39,13 @@ pub struct ChainConfigOverrideParams {
     ///   * native_fee_token_address: on-chain address of this chain's native
     ///     token
     ///
+    ///   * native_fee_token: symbol, decimals and price source of this
+    ///     chain's native token. This has to be yaml-encoded following the
+    ///     format in yaml chain config files.
+    ///
     ///   * parent_fee_token_address: on-chain address of the native token of
-    ///     this chain's settlement layer.
+    ///     this chain's settlement layer. Set it to null for chains whose
+    ///     native token is the only fee token.
     ///
     ///   * latest_protocol_version: latest version of the chain, update on new
     ///     method release, consensus change, etc...
@@ -88,7 +93,8 @@ pub struct ChainConfigOverridesInner {
     pub feeder_gateway_url: Url,
     pub gateway_url: Url,
     pub native_fee_token_address: ContractAddress,
-    pub parent_fee_token_address: ContractAddress,
+    pub native_fee_token: FeeTokenConfig,
+    pub parent_fee_token_address: Option<ContractAddress>,
     #[serde(deserialize_with = "deserialize_starknet_version", serialize_with = "serialize_starknet_version")]
     pub latest_protocol_version: StarknetVersion,
     #[serde(deserialize_with = "deserialize_duration", serialize_with = "serialize_duration")]
@@ -105,6 +111,8 @@ pub struct ChainConfigOverridesInner {
     pub mempool_tx_max_age: Option<Duration>,
     pub no_empty_blocks: bool,
     pub block_production_concurrency: BlockProductionConfig,
+    pub transaction_policy: TransactionPolicy,
+    pub l2_gas_price: Option<L2GasPriceConfig>,
 }
 
 impl ChainConfigOverrideParams {
@@ -116,6 +124,7 @@ impl ChainConfigOverrideParams {
             chain_id: chain_config.chain_id,
             l1_da_mode: chain_config.l1_da_mode,
             native_fee_token_address: chain_config.native_fee_token_address,
+            native_fee_token: chain_config.native_fee_token,
             parent_fee_token_address: chain_config.parent_fee_token_address,
             latest_protocol_version: chain_config.latest_protocol_version,
             block_time: chain_config.block_time,
@@ -131,6 +140,8 @@ impl ChainConfigOverrideParams {
             gateway_url: chain_config.gateway_url,
             no_empty_blocks: chain_config.no_empty_blocks,
             block_production_concurrency: chain_config.block_production_concurrency,
+            transaction_policy: chain_config.transaction_policy,
+            l2_gas_price: chain_config.l2_gas_price,
         })
         .context("Failed to convert ChainConfig to Value")?;
 
@@ -170,6 +181,7 @@ impl ChainConfigOverrideParams {
             feeder_gateway_url: chain_config_overrides.feeder_gateway_url,
             gateway_url: chain_config_overrides.gateway_url,
             native_fee_token_address: chain_config_overrides.native_fee_token_address,
+            native_fee_token: chain_config_overrides.native_fee_token,
             parent_fee_token_address: chain_config_overrides.parent_fee_token_address,
             latest_protocol_version: chain_config_overrides.latest_protocol_version,
             block_time: chain_config_overrides.block_time,
@@ -185,6 +197,8 @@ impl ChainConfigOverrideParams {
             mempool_tx_max_age: chain_config_overrides.mempool_tx_max_age,
             no_empty_blocks: chain_config_overrides.no_empty_blocks,
             block_production_concurrency: chain_config_overrides.block_production_concurrency,
+            transaction_policy: chain_config_overrides.transaction_policy,
+            l2_gas_price: chain_config_overrides.l2_gas_price,
         })
     }
 }
@@ -1,4 +1,4 @@
-use mc_db::{MadaraBackendConfig, RocksDBConfig, TrieLogConfig};
+use mc_db::{MadaraBackendConfig, RocksDBConfig, TraceStorageConfig, TrieLogConfig};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -35,6 +35,15 @@ impl From<StatsLevel> for mc_db::StatsLevel {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum, PartialEq, Deserialize, Serialize)]
+pub enum StorageMode {
+    /// Keep the contract state history of every block.
+    #[default]
+    Archive,
+    /// Only keep the contract state history of the last `--db-pruning-keep-blocks` blocks.
+    Pruned,
+}
+
 #[derive(Clone, Debug, clap::Args, Deserialize, Serialize)]
 pub struct DbParams {
     /// The path where madara will store the database. You should probably change it.
@@ -115,6 +124,31 @@ pub struct DbParams {
     /// Set the rocksdb prefix bloom filter ratio.
     #[clap(env = "MADARA_DB_MEMTABLE_PREFIX_BLOOM_FILTER_RATIO", long, default_value_t = 0.0)]
     pub db_memtable_prefix_bloom_filter_ratio: f64,
+
+    /// Store the execution trace of every transaction when a block is produced or synced. The trace rpc
+    /// methods and the feeder gateway `get_block_traces` endpoint will then serve the stored traces instead
+    /// of re-executing whole blocks on every request.
+    /// Note that when syncing, this means every block is re-executed once at import time.
+    #[clap(env = "MADARA_DB_STORE_TRACES", long)]
+    pub db_store_traces: bool,
+
+    /// Only keep the stored transaction traces of the last N blocks. Older traces are deleted, and tracing
+    /// these blocks falls back to re-execution. By default, traces are kept forever.
+    /// The argument `--db-store-traces` is needed for this argument to have an effect.
+    #[clap(env = "MADARA_DB_TRACES_RETENTION", long, value_name = "NUMBER OF BLOCKS")]
+    pub db_traces_retention: Option<u64>,
+
+    /// Storage mode for the contract state history, meaning the storage, nonce and class hash of every contract at
+    /// every block. In pruned mode, the history older than `--db-pruning-keep-blocks` blocks is compacted away in the
+    /// background, and reading the state of these blocks returns a "block pruned" error. Blocks, receipts and the
+    /// latest state are always kept.
+    #[clap(env = "MADARA_DB_STORAGE_MODE", long, value_enum, default_value_t = StorageMode::Archive)]
+    pub db_storage_mode: StorageMode,
+
+    /// Number of recent blocks whose contract state history is kept in pruned storage mode.
+    /// The argument `--db-storage-mode pruned` is needed for this argument to have an effect.
+    #[clap(env = "MADARA_DB_PRUNING_KEEP_BLOCKS", long, default_value_t = 1024, value_name = "NUMBER OF BLOCKS")]
+    pub db_pruning_keep_blocks: u64,
 }
 
 impl DbParams {
@@ -139,6 +173,14 @@ impl DbParams {
                 memtable_other_budget_mib: self.db_memtable_other_budget_mib,
                 memtable_prefix_bloom_filter_ratio: self.db_memtable_prefix_bloom_filter_ratio,
             },
+            trace_storage: TraceStorageConfig {
+                enabled: self.db_store_traces,
+                retention_blocks: self.db_traces_retention,
+            },
+            storage_mode: match self.db_storage_mode {
+                StorageMode::Archive => mc_db::StorageMode::Archive,
+                StorageMode::Pruned => mc_db::StorageMode::Pruned { keep_blocks: self.db_pruning_keep_blocks },
+            },
         }
     }
 }
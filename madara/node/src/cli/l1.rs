@@ -1,10 +1,12 @@
 use std::fmt;
 use std::time::Duration;
 
+use alloy::primitives::Address;
 use derive_more::FromStr;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use mc_settlement_client::eth::confirmation::ConfirmationPolicy;
 use mp_utils::parsers::{parse_duration, parse_url};
 
 #[derive(Clone, Debug, FromStr, Deserialize, Serialize)]
@@ -32,6 +34,38 @@ pub struct L1SyncParams {
     #[clap(env = "MADARA_L1_ENDPOINT", long, value_parser = parse_url, value_name = "ETHEREUM RPC URL")]
     pub l1_endpoint: Option<Url>,
 
+    /// Comma-separated L1 rpc endpoint urls to fail over to, in order of preference, when the main endpoint is
+    /// unavailable.
+    #[clap(
+        env = "MADARA_L1_FALLBACK_ENDPOINTS",
+        long,
+        value_parser = parse_url,
+        value_delimiter = ',',
+        value_name = "URLS",
+        requires = "l1_endpoint"
+    )]
+    pub l1_fallback_endpoints: Vec<Url>,
+
+    /// Number of L1 endpoints that need to agree on the state of the core contract before we use it.
+    #[clap(env = "MADARA_L1_QUORUM", long, default_value_t = 1, value_name = "N")]
+    pub l1_quorum: usize,
+
+    /// How deep an L1 block needs to be before we process its messages and state updates and read its gas prices.
+    /// Either `latest`, `safe`, `finalized`, or a number of blocks behind the chain tip. Production nodes should use
+    /// `finalized`, local L1 nodes such as anvil never finalize blocks. Only applies to an Ethereum settlement layer.
+    #[clap(
+        env = "MADARA_L1_CONFIRMATIONS",
+        long,
+        default_value_t = ConfirmationPolicy::Latest,
+        value_name = "POLICY"
+    )]
+    pub l1_confirmations: ConfirmationPolicy,
+
+    /// L1 block from which to index the L2 to L1 message events of the core contract, when they were never indexed.
+    /// Defaults to the block in which the core contract was deployed, which may require an archive node to find.
+    #[clap(env = "MADARA_L1_MESSAGES_TO_L1_START_BLOCK", long, value_name = "BLOCK NUMBER")]
+    pub l1_messages_to_l1_start_block: Option<u64>,
+
     /// Fix the gas price. If the gas price is fixed it won't fetch the fee history from the ethereum.
     #[clap(env = "MADARA_GAS_PRICE", long, alias = "gas-price")]
     pub gas_price: Option<u64>,
@@ -56,6 +90,45 @@ pub struct L1SyncParams {
     #[clap(env = "ORACLE_API_KEY", long, alias = "oracle-api-key")]
     pub oracle_api_key: Option<String>,
 
+    /// Fixed ETH price of the fee token, as a decimal number of fee tokens per ETH. When several oracles are
+    /// configured, the median of their prices is used.
+    #[clap(env = "MADARA_ORACLE_FIXED_PRICE", long, value_name = "PRICE")]
+    pub oracle_fixed_price: Option<String>,
+
+    /// Url of an http endpoint returning the ETH price of the fee token as JSON.
+    #[clap(env = "MADARA_ORACLE_JSON_URL", long, value_name = "URL", requires = "oracle_json_price_path")]
+    pub oracle_json_url: Option<Url>,
+
+    /// Dot-separated path to the price in the JSON oracle response, such as `data.0.price`.
+    #[clap(env = "MADARA_ORACLE_JSON_PRICE_PATH", long, value_name = "PATH")]
+    pub oracle_json_price_path: Option<String>,
+
+    /// Dot-separated path to the unix timestamp of the price in the JSON oracle response, in seconds.
+    #[clap(env = "MADARA_ORACLE_JSON_TIMESTAMP_PATH", long, value_name = "PATH")]
+    pub oracle_json_timestamp_path: Option<String>,
+
+    /// Decimals of the price returned by the JSON oracle.
+    #[clap(env = "MADARA_ORACLE_JSON_DECIMALS", long, default_value_t = 18)]
+    pub oracle_json_decimals: u32,
+
+    /// Address of a Chainlink-style aggregator contract on L1 answering in fee tokens per ETH, such as an ETH/STRK
+    /// feed.
+    #[clap(env = "MADARA_ORACLE_CHAINLINK_FEED", long, value_name = "ADDRESS", requires = "l1_endpoint")]
+    pub oracle_chainlink_feed: Option<Address>,
+
+    /// The Chainlink feed answers in ETH per fee token instead, such as a STRK/ETH feed, and is inverted to get fee
+    /// tokens per ETH.
+    #[clap(env = "MADARA_ORACLE_CHAINLINK_INVERT", long)]
+    pub oracle_chainlink_invert: bool,
+
+    /// Minimum number of oracles that need to return a fresh price, when several oracles are configured.
+    #[clap(env = "MADARA_ORACLE_MIN_SOURCES", long, default_value_t = 1)]
+    pub oracle_min_sources: usize,
+
+    /// Oracle prices older than this are rejected.
+    #[clap(env = "MADARA_ORACLE_MAX_AGE", long, value_parser = parse_duration)]
+    pub oracle_max_age: Option<Duration>,
+
     /// Time in which the gas price worker will fetch the gas price.
     #[clap(
 		env = "MADARA_GAS_PRICE_POLL",
@@ -72,3 +145,10 @@ pub struct L1SyncParams {
     )]
     pub settlement_layer: MadaraSettlementLayer,
 }
+
+impl L1SyncParams {
+    /// The main L1 endpoint followed by the fallback ones.
+    pub fn l1_endpoints(&self) -> Vec<Url> {
+        self.l1_endpoint.iter().chain(&self.l1_fallback_endpoints).cloned().collect()
+    }
+}
@@ -67,6 +67,12 @@ pub struct L2SyncParams {
     #[clap(env = "MADARA_STOP_NO_PENDING_SYNC", long)]
     pub no_pending_sync: bool,
 
+    /// Maximum number of local blocks that can be reverted when the chain followed by the sync reorgs. Deeper
+    /// reorgs stop the sync. Reverting blocks needs the global tries to be rolled back, so this should not be greater
+    /// than `--db-max-saved-trie-logs` unless the tries are disabled.
+    #[clap(env = "MADARA_SYNC_MAX_REORG_DEPTH", long, value_name = "BLOCKS", default_value_t = 64)]
+    pub sync_max_reorg_depth: u64,
+
     /// Compute post-v0.13.2 hashes. This means that the feeder gateway will display different block commitments
     /// for blocks that were created before v0.13.2. When p2p sync will be merged, this option will become the
     /// default, as post-v0.13.2 commitments are mandatory for checking the integrity of these old blocks.
@@ -224,6 +224,43 @@ pub struct RunCmd {
     /// The private key used to sign the blocks.
     #[clap(env = "MADARA_PRIVATE_KEY", long, value_name = "PRIVATE KEY")]
     pub private_key: Option<String>,
+
+    #[allow(missing_docs)]
+    #[clap(subcommand)]
+    #[serde(skip)]
+    pub command: Option<NodeCommand>,
+}
+
+/// Maintenance operations, run on the database instead of starting the node.
+#[derive(Clone, Debug, clap::Subcommand)]
+pub enum NodeCommand {
+    /// Reverts the chain to a block and exits. Every block above it is deleted, the global state is rolled back
+    /// and the reverted transactions are saved to the mempool. The depth of the revert is limited by
+    /// --db-max-saved-trie-logs.
+    Revert(RevertParams),
+    /// Re-executes a range of blocks on top of their parent state and exits. The receipts, events, state diffs and
+    /// state roots are compared with the stored chain, and the first divergent transaction is reported with a diff.
+    VerifyExecution(VerifyExecutionParams),
+}
+
+/// Parameters of the revert command.
+#[derive(Clone, Debug, clap::Args)]
+pub struct RevertParams {
+    /// The block which becomes the new latest block.
+    #[arg(long, value_name = "BLOCK NUMBER")]
+    pub to_block: u64,
+}
+
+/// Parameters of the verify-execution command.
+#[derive(Clone, Debug, clap::Args)]
+pub struct VerifyExecutionParams {
+    /// The first block to re-execute.
+    #[arg(long, value_name = "BLOCK NUMBER")]
+    pub from: u64,
+
+    /// The last block to re-execute. Defaults to the latest block.
+    #[arg(long, value_name = "BLOCK NUMBER")]
+    pub to: Option<u64>,
 }
 
 impl RunCmd {
@@ -10,16 +10,18 @@ use crate::cli::l1::MadaraSettlementLayer;
 use crate::service::L1SyncConfig;
 use anyhow::{bail, Context};
 use clap::Parser;
-use cli::RunCmd;
+use cli::{NodeCommand, RunCmd, VerifyExecutionParams};
 use figment::{
     providers::{Format, Json, Serialized, Toml, Yaml},
     Figment,
 };
 use http::{HeaderName, HeaderValue};
 use mc_analytics::Analytics;
-use mc_db::DatabaseService;
+use mc_db::{DatabaseService, MadaraBackend};
+use mc_exec::verify::{verify_block_execution, BlockVerification};
 use mc_gateway_client::GatewayProvider;
 use mc_mempool::{GasPriceProvider, L1DataProvider, Mempool, MempoolConfig, MempoolLimits};
+use mc_settlement_client::eth::chainlink::ChainlinkOracle;
 use mc_settlement_client::eth::event::EthereumEventStream;
 use mc_settlement_client::eth::EthereumClientConfig;
 use mc_settlement_client::gas_price::L1BlockMetrics;
@@ -27,7 +29,12 @@ use mc_settlement_client::starknet::event::StarknetEventStream;
 use mc_settlement_client::starknet::StarknetClientConfig;
 use mc_submit_tx::{SubmitTransaction, TransactionValidator};
 use mc_telemetry::{SysInfo, TelemetryService};
+use mp_chain_config::{FeeTokenPriceSource, FIXED_PRICE_DECIMALS};
+use mp_oracle::fixed::FixedOracle;
+use mp_oracle::json_http::JsonHttpOracle;
+use mp_oracle::median::MedianOracle;
 use mp_oracle::pragma::PragmaOracleBuilder;
+use mp_oracle::{parse_decimal_price, Oracle};
 use mp_utils::service::{MadaraServiceId, ServiceMonitor};
 use service::{BlockProductionService, GatewayService, L1SyncService, RpcService, SyncService, WarpUpdateConfig};
 use starknet_api::core::ChainId;
@@ -38,6 +45,9 @@ use submit_tx::{MakeSubmitTransactionSwitch, MakeSubmitValidatedTransactionSwitc
 const GREET_IMPL_NAME: &str = "Madara";
 const GREET_SUPPORT_URL: &str = "https://github.com/madara-alliance/madara/issues";
 
+/// Decimals of the price given with `--oracle-fixed-price`.
+const FIXED_ORACLE_DECIMALS: u32 = 18;
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     crate::util::setup_rayon_threadpool()?;
@@ -50,9 +60,12 @@ async fn main() -> anyhow::Result<()> {
     // If there are cli arguments, check if they are pointing to a file
     // If yes, load from that file. If not, load the values from the cli
     // If there are no cli args, load the default file
+    let mut command = None;
     if env::args().count() > 1 {
         // This is done to overwrite the preset with the args
         let cli_args = RunCmd::parse().apply_arg_preset();
+        // Subcommands are not part of the config file.
+        command = cli_args.command.clone();
 
         if let Some(config_path) = cli_args.config_file.clone() {
             config = match config_path.extension() {
@@ -148,9 +161,24 @@ async fn main() -> anyhow::Result<()> {
         .await
         .context("Initializing db service")?;
 
+    match command {
+        Some(NodeCommand::Revert(params)) => {
+            let restored_txs = service_db.backend().revert_to(params.to_block).context("Reverting the chain")?;
+            tracing::info!(
+                "⏪ Chain reverted to block #{}, {} transactions saved to the mempool",
+                params.to_block,
+                restored_txs.len()
+            );
+            return Ok(());
+        }
+        Some(NodeCommand::VerifyExecution(params)) => return verify_execution(service_db.backend(), params),
+        None => {}
+    }
+
     // L1 Sync
 
     let mut l1_gas_setter = GasPriceProvider::new();
+    l1_gas_setter.set_native_fee_token_decimals(chain_config.native_fee_token.decimals);
 
     if let Some(fix_gas) = run_cmd.l1_sync_params.gas_price {
         l1_gas_setter.update_eth_l1_gas_price(fix_gas as u128);
@@ -168,14 +196,57 @@ async fn main() -> anyhow::Result<()> {
         l1_gas_setter.update_strk_l1_data_gas_price(strk_fix_blob_gas as u128);
         l1_gas_setter.set_strk_data_gas_price_sync_enabled(false);
     }
+    let mut oracles: Vec<Arc<dyn Oracle>> = vec![];
     if let Some(ref oracle_url) = run_cmd.l1_sync_params.oracle_url {
         if let Some(ref oracle_api_key) = run_cmd.l1_sync_params.oracle_api_key {
             let oracle = PragmaOracleBuilder::new()
                 .with_api_url(oracle_url.clone())
                 .with_api_key(oracle_api_key.clone())
                 .build();
-            l1_gas_setter.set_oracle_provider(oracle);
+            oracles.push(Arc::new(oracle));
+        }
+    }
+    if let Some(ref price) = run_cmd.l1_sync_params.oracle_fixed_price {
+        let price = parse_decimal_price(price, FIXED_ORACLE_DECIMALS).context("Parsing the fixed oracle price")?;
+        oracles.push(Arc::new(FixedOracle::new(price, FIXED_ORACLE_DECIMALS)));
+    }
+    if let Some(ref url) = run_cmd.l1_sync_params.oracle_json_url {
+        oracles.push(Arc::new(JsonHttpOracle {
+            url: url.clone(),
+            headers: vec![],
+            price_path: run_cmd
+                .l1_sync_params
+                .oracle_json_price_path
+                .clone()
+                .context("Missing JSON oracle price path")?,
+            decimals: run_cmd.l1_sync_params.oracle_json_decimals,
+            timestamp_path: run_cmd.l1_sync_params.oracle_json_timestamp_path.clone(),
+        }));
+    }
+    if let Some(feed) = run_cmd.l1_sync_params.oracle_chainlink_feed {
+        let oracle = ChainlinkOracle::new(
+            run_cmd.l1_sync_params.l1_endpoints(),
+            feed,
+            run_cmd.l1_sync_params.oracle_chainlink_invert,
+        )
+        .context("Creating the Chainlink price oracle")?;
+        oracles.push(Arc::new(oracle));
+    }
+    if let FeeTokenPriceSource::Fixed(ref price) = chain_config.native_fee_token.price_source {
+        // A price fixed by the chain config replaces the price oracles of the command line.
+        if !oracles.is_empty() {
+            tracing::warn!("The chain config fixes the native fee token price, ignoring the price oracles");
+        }
+        let price = parse_decimal_price(price, FIXED_PRICE_DECIMALS).context("Parsing the fixed fee token price")?;
+        l1_gas_setter.set_oracle_provider(FixedOracle::new(price, FIXED_PRICE_DECIMALS));
+    } else if !oracles.is_empty() {
+        let min_sources = run_cmd.l1_sync_params.oracle_min_sources;
+        if min_sources > oracles.len() {
+            bail!("{min_sources} price oracles are required to agree, but only {} are configured", oracles.len());
         }
+        let max_age = run_cmd.l1_sync_params.oracle_max_age;
+        l1_gas_setter.set_oracle_provider(MedianOracle::new(oracles, min_sources, max_age));
+        l1_gas_setter.set_oracle_max_age(max_age);
     }
 
     if !run_cmd.full
@@ -298,6 +369,7 @@ async fn main() -> anyhow::Result<()> {
         &service_db,
         Arc::clone(&mempool),
         Arc::clone(&l1_data_provider),
+        &node_name,
     )?;
 
     // Add transaction provider
@@ -331,8 +403,13 @@ async fn main() -> anyhow::Result<()> {
 
     // Admin-facing RPC (for node operators)
 
-    let service_rpc_admin =
+    let mut service_rpc_admin =
         RpcService::admin(run_cmd.rpc_params.clone(), Arc::clone(service_db.backend()), tx_submit.clone());
+    if run_cmd.is_sequencer() {
+        service_rpc_admin = service_rpc_admin
+            .with_block_production_handle(service_block_production.handle())
+            .with_mempool(Arc::clone(&mempool) as _);
+    }
 
     // Feeder gateway
 
@@ -404,3 +481,40 @@ async fn main() -> anyhow::Result<()> {
 
     app.start().await
 }
+
+/// Runs the verify-execution command, stopping at the first block which diverges from the stored chain.
+fn verify_execution(backend: &Arc<MadaraBackend>, params: VerifyExecutionParams) -> anyhow::Result<()> {
+    let to = match params.to {
+        Some(to) => to,
+        None => backend.get_latest_block_n()?.context("The database has no blocks")?,
+    };
+    tracing::info!("🔎 Re-executing blocks #{}..=#{}", params.from, to);
+
+    let (mut unsupported, mut state_roots_unchecked) = (0, 0);
+    for block_n in params.from..=to {
+        match verify_block_execution(backend, block_n).with_context(|| format!("Re-executing block #{block_n}"))? {
+            BlockVerification::Matches { state_root_checked } => {
+                if !state_root_checked {
+                    state_roots_unchecked += 1;
+                }
+                tracing::debug!("Block #{block_n} matches the stored chain");
+            }
+            BlockVerification::Unsupported => unsupported += 1,
+            BlockVerification::Diverges(divergence) => {
+                tracing::error!("❌ {divergence}");
+                bail!("Block #{block_n} diverges from the stored chain");
+            }
+        }
+    }
+
+    if unsupported > 0 {
+        tracing::warn!("{unsupported} blocks older than Starknet v0.13.0 were skipped, as they cannot be re-executed");
+    }
+    if state_roots_unchecked > 0 {
+        tracing::warn!(
+            "The state root of {state_roots_unchecked} blocks was not checked, as the global tries do not keep enough trie logs to go back to their parent block"
+        );
+    }
+    tracing::info!("✅ Blocks #{}..=#{} match the stored chain", params.from, to);
+    Ok(())
+}
@@ -1,10 +1,13 @@
 use crate::cli::block_production::BlockProductionParams;
 use anyhow::Context;
-use mc_block_production::{metrics::BlockProductionMetrics, BlockProductionTask};
+use futures::FutureExt;
+use mc_block_production::leader_election::{FileLeaseBackend, HttpLeaseBackend, LeaderLease, LeaseBackend, NotLeader};
+use mc_block_production::{metrics::BlockProductionMetrics, BlockProductionHandle, BlockProductionTask};
 use mc_db::{DatabaseService, MadaraBackend};
 use mc_devnet::{ChainGenesisDescription, DevnetKeys};
 use mc_mempool::{L1DataProvider, Mempool};
-use mp_utils::service::{MadaraServiceId, PowerOfTwo, Service, ServiceId, ServiceRunner};
+use mp_utils::service::{MadaraServiceId, PowerOfTwo, Service, ServiceContext, ServiceId, ServiceRunner};
+use std::time::Duration;
 use std::{io::Write, sync::Arc};
 
 pub struct BlockProductionService {
@@ -14,6 +17,8 @@ pub struct BlockProductionService {
     l1_data_provider: Arc<dyn L1DataProvider>,
     n_devnet_contracts: u64,
     disabled: bool,
+    leader_lease: Option<LeaderLease>,
+    handle: BlockProductionHandle,
 }
 
 impl BlockProductionService {
@@ -23,9 +28,23 @@ impl BlockProductionService {
         db_service: &DatabaseService,
         mempool: Arc<mc_mempool::Mempool>,
         l1_data_provider: Arc<dyn L1DataProvider>,
+        node_name: &str,
     ) -> anyhow::Result<Self> {
         let metrics = Arc::new(BlockProductionMetrics::register());
 
+        let lease_backend: Option<Arc<dyn LeaseBackend>> = match (&config.ha_lease_file, &config.ha_lease_url) {
+            (Some(path), _) => Some(Arc::new(FileLeaseBackend::new(path))),
+            // Lease requests must not take longer than the renew interval.
+            (None, Some(url)) => Some(Arc::new(
+                HttpLeaseBackend::new(url.clone(), config.ha_lease_ttl / 3).context("Creating lease client")?,
+            )),
+            (None, None) => None,
+        };
+        let leader_lease = lease_backend.map(|lease_backend| {
+            let node_id = config.ha_node_id.clone().unwrap_or_else(|| node_name.to_owned());
+            LeaderLease::new(lease_backend, node_id, config.ha_lease_ttl)
+        });
+
         Ok(Self {
             backend: Arc::clone(db_service.backend()),
             l1_data_provider,
@@ -33,6 +52,8 @@ impl BlockProductionService {
             metrics,
             n_devnet_contracts: config.devnet_contracts,
             disabled: config.block_production_disabled,
+            leader_lease,
+            handle: BlockProductionHandle::default(),
         })
     }
 }
@@ -41,23 +62,122 @@ impl BlockProductionService {
 impl Service for BlockProductionService {
     #[tracing::instrument(skip(self, runner), fields(module = "BlockProductionService"))]
     async fn start<'a>(&mut self, runner: ServiceRunner<'a>) -> anyhow::Result<()> {
-        let Self { backend, l1_data_provider, mempool, metrics, disabled, .. } = self;
+        let Self { backend, l1_data_provider, mempool, metrics, disabled, leader_lease, handle, .. } = self;
+
+        if *disabled {
+            return Ok(());
+        }
 
-        let block_production_task = BlockProductionTask::new(
-            Arc::clone(backend),
-            Arc::clone(mempool),
-            Arc::clone(metrics),
-            Arc::clone(l1_data_provider),
-        );
+        let new_task = {
+            let (backend, mempool, metrics, l1_data_provider, handle) = (
+                Arc::clone(backend),
+                Arc::clone(mempool),
+                Arc::clone(metrics),
+                Arc::clone(l1_data_provider),
+                handle.clone(),
+            );
+            move || {
+                BlockProductionTask::new(
+                    Arc::clone(&backend),
+                    Arc::clone(&mempool),
+                    Arc::clone(&metrics),
+                    Arc::clone(&l1_data_provider),
+                )
+                .with_handle(handle.clone())
+            }
+        };
 
-        if !*disabled {
-            runner.service_loop(move |ctx| block_production_task.run(ctx));
+        match leader_lease.clone() {
+            Some(leader_lease) => {
+                let backend = Arc::clone(backend);
+                runner.service_loop(move |ctx| run_with_leader_election(backend, leader_lease, new_task, ctx));
+            }
+            None => runner.service_loop(move |ctx| new_task().run(ctx)),
         }
 
         Ok(())
     }
 }
 
+/// Sequencer high availability: the node stays in standby following the leader through L2 sync until it acquires the
+/// leader lease, then produces blocks until it loses the lease and goes back to standby.
+async fn run_with_leader_election(
+    backend: Arc<MadaraBackend>,
+    leader_lease: LeaderLease,
+    new_task: impl Fn() -> BlockProductionTask,
+    mut ctx: ServiceContext,
+) -> anyhow::Result<()> {
+    let mut interval = tokio::time::interval(leader_lease.renew_interval());
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tracing::info!("💤 Sequencer {} is in standby, following the leader", leader_lease.node_id());
+        ctx.service_add(MadaraServiceId::L2Sync);
+
+        let recorded_head = loop {
+            if ctx.run_until_cancelled(interval.tick()).await.is_none() {
+                return Ok(());
+            }
+            match leader_lease.try_acquire(None).await {
+                Ok(lease) if leader_lease.is_leader() => break lease.head_block_n,
+                Ok(lease) => tracing::debug!("Sequencer leader is {} (term {})", lease.holder, lease.term),
+                Err(err) => tracing::warn!("Failed to acquire the sequencer leader lease: {err:#}"),
+            }
+        };
+
+        // Catch up with the last block closed by the previous leader, so that we never produce a conflicting block.
+        // We keep the lease meanwhile, so that no other standby node takes over.
+        let mut waiting_since = tokio::time::Instant::now();
+        while let Some(head) = recorded_head.filter(|head| backend.head_status().latest_full_block_n() < Some(*head)) {
+            if waiting_since.elapsed() > leader_lease.renew_interval() * 3 {
+                tracing::warn!(
+                    "⏳ Waiting to sync block #{head} recorded by the previous sequencer leader before taking over. If \
+                     the previous leader stopped before saving this block, reset the leader lease."
+                );
+                waiting_since = tokio::time::Instant::now();
+            }
+            if ctx.run_until_cancelled(tokio::time::sleep(CATCH_UP_POLL_INTERVAL)).await.is_none() {
+                release_lease(&leader_lease).await;
+                return Ok(());
+            }
+            if interval.tick().now_or_never().is_some() {
+                let _ = leader_lease.renew().await;
+            }
+            if !leader_lease.is_leader() {
+                break;
+            }
+        }
+        if leader_lease.renew().await.is_err() {
+            continue;
+        }
+
+        tracing::info!("👑 Sequencer {} is now the leader, starting block production", leader_lease.node_id());
+        ctx.service_remove(MadaraServiceId::L2Sync);
+
+        match new_task().with_leader_lease(leader_lease.clone()).run(ctx.clone()).await {
+            Err(err) if err.downcast_ref::<NotLeader>().is_some() => {
+                tracing::warn!(
+                    "⚠️ Sequencer {} lost the leader lease, stopping block production",
+                    leader_lease.node_id()
+                )
+            }
+            res => {
+                // Let a standby node take over right away.
+                release_lease(&leader_lease).await;
+                return res;
+            }
+        }
+    }
+}
+
+const CATCH_UP_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+async fn release_lease(leader_lease: &LeaderLease) {
+    if let Err(err) = leader_lease.release().await {
+        tracing::warn!("Failed to release the sequencer leader lease: {err:#}");
+    }
+}
+
 impl ServiceId for BlockProductionService {
     #[inline(always)]
     fn svc_id(&self) -> PowerOfTwo {
@@ -66,6 +186,11 @@ impl ServiceId for BlockProductionService {
 }
 
 impl BlockProductionService {
+    /// Controls block production at runtime. The handle stays valid when the service is restarted.
+    pub fn handle(&self) -> BlockProductionHandle {
+        self.handle.clone()
+    }
+
     /// Initializes the genesis state of a devnet. This is needed for local sequencers.
     ///
     /// This methods was made external to [Service::start] as it needs to be
@@ -78,8 +203,8 @@ impl BlockProductionService {
             // deploy devnet genesis
             tracing::info!("⛏️  Deploying devnet genesis block");
 
-            let mut genesis_config =
-                ChainGenesisDescription::base_config().context("Failed to create base genesis config")?;
+            let mut genesis_config = ChainGenesisDescription::base_config(backend.chain_config())
+                .context("Failed to create base genesis config")?;
             let contracts =
                 genesis_config.add_devnet_contracts(*n_devnet_contracts).context("Failed to add devnet contracts")?;
 
@@ -95,7 +220,7 @@ impl BlockProductionService {
 
         // display devnet welcome message :)
         // we display it to stdout instead of stderr
-        let msg = format!("{}", keys);
+        let msg = format!("{}", keys.display(backend.chain_config()));
         std::io::stdout().write(msg.as_bytes()).context("Writing devnet welcome message to stdout")?;
 
         anyhow::Ok(())
@@ -45,6 +45,7 @@ where
     gas_price_poll: Duration,
     mempool: Arc<Mempool>,
     l1_block_metrics: Arc<L1BlockMetrics>,
+    messages_to_l1_start_block: Option<u64>,
 }
 
 pub type EthereumSyncService = L1SyncService<EthereumClientConfig, EthereumEventStream>;
@@ -54,11 +55,13 @@ pub type StarknetSyncService = L1SyncService<StarknetClientConfig, StarknetEvent
 impl EthereumSyncService {
     pub async fn new(config: &L1SyncParams, sync_config: L1SyncConfig<'_>) -> anyhow::Result<Self> {
         let settlement_client = {
-            if let Some(l1_rpc_url) = &config.l1_endpoint {
+            if config.l1_endpoint.is_some() {
                 let core_address = Address::from_str(sync_config.l1_core_address.as_str())?;
                 let client = EthereumClient::new(EthereumClientConfig {
-                    url: l1_rpc_url.clone(),
+                    urls: config.l1_endpoints(),
                     l1_core_address: core_address,
+                    quorum: config.l1_quorum,
+                    confirmation_policy: config.l1_confirmations,
                 })
                 .await
                 .context("Creating ethereum client")?;
@@ -77,11 +80,12 @@ impl EthereumSyncService {
 impl StarknetSyncService {
     pub async fn new(config: &L1SyncParams, sync_config: L1SyncConfig<'_>) -> anyhow::Result<Self> {
         let settlement_client = {
-            if let Some(l1_rpc_url) = &config.l1_endpoint {
+            if config.l1_endpoint.is_some() {
                 let core_address = Felt::from_str(sync_config.l1_core_address.as_str())?;
                 let client = StarknetClient::new(StarknetClientConfig {
-                    url: l1_rpc_url.clone(),
+                    urls: config.l1_endpoints(),
                     l2_contract_address: core_address,
+                    quorum: config.l1_quorum,
                 })
                 .await
                 .context("Creating starknet client")?;
@@ -135,6 +139,7 @@ where
             mempool: sync_config.mempool,
             l1_block_metrics: sync_config.l1_block_metrics,
             l1_head_snd: Some(sync_config.l1_head_snd),
+            messages_to_l1_start_block: config.l1_messages_to_l1_start_block,
         })
     }
 
@@ -163,6 +168,7 @@ where
             let mempool = Arc::clone(&self.mempool);
             let l1_block_metrics = self.l1_block_metrics.clone();
             let l1_head_sender = self.l1_head_snd.take().expect("Service already starteds");
+            let messages_to_l1_start_block = self.messages_to_l1_start_block;
 
             runner.service_loop(move |ctx| {
                 mc_settlement_client::sync::sync_worker(SyncWorkerConfig {
@@ -175,6 +181,7 @@ where
                     l1_head_sender,
                     ctx,
                     l1_block_metrics,
+                    messages_to_l1_start_block,
                 })
             });
         } else {
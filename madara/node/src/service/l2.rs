@@ -39,6 +39,7 @@ struct StartArgs {
 pub struct SyncService {
     start_args: Option<StartArgs>,
     disabled: bool,
+    started: bool,
 }
 
 impl SyncService {
@@ -56,6 +57,7 @@ impl SyncService {
                 warp_update,
             }),
             disabled: config.l2_sync_disabled,
+            started: false,
         })
     }
 }
@@ -66,7 +68,15 @@ impl Service for SyncService {
         if self.disabled {
             return Ok(());
         }
-        let this = self.start_args.take().expect("Service already started");
+        let mut this = self.start_args.clone().expect("Sync service is not configured");
+        // The service is restarted when a sequencer in high availability mode goes back to standby. Warp update and
+        // the unsafe starting block only apply to the first start.
+        let first_start = !self.started;
+        self.started = true;
+        if !first_start {
+            this.warp_update = None;
+        }
+
         let importer = Arc::new(BlockImporter::new(
             this.db_backend.clone(),
             BlockValidationConfig::default().trust_parent_hash(this.params.unsafe_starting_block.is_some()),
@@ -79,7 +89,7 @@ impl Service for SyncService {
             .stop_on_sync(this.params.stop_on_sync)
             .no_pending_block(this.params.no_pending_sync);
 
-        if let Some(starting_block) = this.params.unsafe_starting_block {
+        if let Some(starting_block) = this.params.unsafe_starting_block.filter(|_| first_start) {
             // We state that starting_block - 1 is the chain head.
             this.db_backend.head_status().set_latest_full_block_n(starting_block.checked_sub(1));
         }
@@ -155,7 +165,8 @@ impl Service for SyncService {
                 config,
                 mc_sync::gateway::ForwardSyncConfig::default()
                     .disable_tries(this.params.disable_tries)
-                    .keep_pre_v0_13_2_hashes(this.params.keep_pre_v0_13_2_hashes()),
+                    .keep_pre_v0_13_2_hashes(this.params.keep_pre_v0_13_2_hashes())
+                    .max_reorg_depth(this.params.sync_max_reorg_depth),
             )
             .run(ctx)
             .await
@@ -1,8 +1,10 @@
 use self::server::rpc_api_build;
 use crate::{cli::RpcParams, submit_tx::MakeSubmitTransactionSwitch};
 use jsonrpsee::server::ServerHandle;
+use mc_block_production::BlockProductionHandle;
 use mc_db::MadaraBackend;
 use mc_rpc::{rpc_api_admin, rpc_api_user, Starknet};
+use mc_submit_tx::SubmitValidatedTransaction;
 use metrics::RpcMetrics;
 use mp_utils::service::{MadaraServiceId, PowerOfTwo, Service, ServiceId, ServiceRunner};
 use server::{start_server, ServerConfig};
@@ -24,6 +26,8 @@ pub struct RpcService {
     submit_tx_provider: MakeSubmitTransactionSwitch,
     server_handle: Option<ServerHandle>,
     rpc_type: RpcType,
+    block_production_handle: Option<BlockProductionHandle>,
+    mempool: Option<Arc<dyn SubmitValidatedTransaction>>,
 }
 
 impl RpcService {
@@ -32,7 +36,15 @@ impl RpcService {
         backend: Arc<MadaraBackend>,
         submit_tx_provider: MakeSubmitTransactionSwitch,
     ) -> Self {
-        Self { config, backend, submit_tx_provider, server_handle: None, rpc_type: RpcType::User }
+        Self {
+            config,
+            backend,
+            submit_tx_provider,
+            server_handle: None,
+            rpc_type: RpcType::User,
+            block_production_handle: None,
+            mempool: None,
+        }
     }
 
     pub fn admin(
@@ -40,7 +52,27 @@ impl RpcService {
         backend: Arc<MadaraBackend>,
         submit_tx_provider: MakeSubmitTransactionSwitch,
     ) -> Self {
-        Self { config, backend, submit_tx_provider, server_handle: None, rpc_type: RpcType::Admin }
+        Self {
+            config,
+            backend,
+            submit_tx_provider,
+            server_handle: None,
+            rpc_type: RpcType::Admin,
+            block_production_handle: None,
+            mempool: None,
+        }
+    }
+
+    /// Exposes the block production controls through the admin rpc methods.
+    pub fn with_block_production_handle(mut self, block_production_handle: BlockProductionHandle) -> Self {
+        self.block_production_handle = Some(block_production_handle);
+        self
+    }
+
+    /// Gives the transactions restored by the revert admin rpc method to the running mempool.
+    pub fn with_mempool(mut self, mempool: Arc<dyn SubmitValidatedTransaction>) -> Self {
+        self.mempool = Some(mempool);
+        self
     }
 }
 
@@ -51,6 +83,8 @@ impl Service for RpcService {
         let backend = Arc::clone(&self.backend);
         let submit_tx_provider = self.submit_tx_provider.clone();
         let rpc_type = self.rpc_type.clone();
+        let block_production_handle = self.block_production_handle.clone();
+        let mempool = self.mempool.clone();
 
         let (stop_handle, server_handle) = jsonrpsee::server::stop_channel();
 
@@ -59,7 +93,13 @@ impl Service for RpcService {
         runner.service_loop(move |ctx| async move {
             let submit_tx = Arc::new(submit_tx_provider.make(ctx.clone()));
 
-            let starknet = Starknet::new(backend.clone(), submit_tx, config.storage_proof_config(), ctx.clone());
+            let mut starknet = Starknet::new(backend.clone(), submit_tx, config.storage_proof_config(), ctx.clone());
+            if let Some(block_production_handle) = block_production_handle.clone() {
+                starknet = starknet.with_block_production_handle(block_production_handle);
+            }
+            if let Some(mempool) = mempool.clone() {
+                starknet = starknet.with_mempool(mempool);
+            }
             let metrics = RpcMetrics::register()?;
 
             let server_config = {
@@ -0,0 +1,213 @@
+#![allow(missing_docs)]
+#![allow(clippy::missing_docs_in_private_items)]
+
+//! DA client submitting the state diffs as namespaced blobs to a Celestia light or bridge node, through its
+//! JSON-RPC API. Blobs are referenced by the height they were included at and their share commitment, which
+//! is what gets settled.
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use color_eyre::eyre::{bail, ensure, eyre, WrapErr};
+use color_eyre::Result;
+use orchestrator_da_client_interface::{DaClient, DaVerificationStatus};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use url::Url;
+
+#[cfg(test)]
+mod tests;
+
+/// Celestia namespaces are made of a version byte followed by a 28 bytes id. Version 0 ids must start with
+/// 18 zero bytes, leaving 10 bytes to the user.
+pub const NAMESPACE_SIZE: usize = 29;
+const NAMESPACE_V0_USER_BYTES: usize = 10;
+/// Code of the error returned by the node when there is no blob with the requested commitment at a height.
+const BLOB_NOT_FOUND_CODE: i64 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CelestiaDaValidatedArgs {
+    pub celestia_da_rpc_url: Url,
+    /// Token used to authenticate to the node, from `celestia <node-type> auth write`.
+    pub celestia_da_auth_token: Option<String>,
+    /// Version 0 namespace the blobs are submitted to, see [`parse_namespace`].
+    pub celestia_da_namespace: [u8; NAMESPACE_SIZE],
+}
+
+pub struct CelestiaDaClient {
+    client: reqwest::Client,
+    rpc_url: Url,
+    auth_token: Option<String>,
+    namespace: [u8; NAMESPACE_SIZE],
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CelestiaBlob {
+    namespace: String,
+    data: String,
+    share_version: u32,
+    #[serde(default)]
+    commitment: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// Error answered by the Celestia node to a JSON-RPC call.
+#[derive(Debug)]
+struct NodeError {
+    method: String,
+    error: JsonRpcError,
+}
+
+impl std::fmt::Display for NodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Celestia node error on {} ({}): {}", self.method, self.error.code, self.error.message)
+    }
+}
+
+impl std::error::Error for NodeError {}
+
+impl CelestiaDaClient {
+    pub fn new_with_args(celestia_da_params: &CelestiaDaValidatedArgs) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            rpc_url: celestia_da_params.celestia_da_rpc_url.clone(),
+            auth_token: celestia_da_params.celestia_da_auth_token.clone(),
+            namespace: celestia_da_params.celestia_da_namespace,
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: serde_json::Value) -> Result<T> {
+        let mut request = self
+            .client
+            .post(self.rpc_url.clone())
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }));
+        if let Some(auth_token) = &self.auth_token {
+            request = request.bearer_auth(auth_token);
+        }
+        let response: JsonRpcResponse<T> = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .wrap_err_with(|| format!("Calling {method} on the Celestia node"))?
+            .json()
+            .await
+            .wrap_err_with(|| format!("Decoding the response to {method} from the Celestia node"))?;
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(NodeError { method: method.to_string(), error }.into()),
+            (Some(result), None) => Ok(result),
+            (None, None) => bail!("Empty response to {method} from the Celestia node"),
+        }
+    }
+}
+
+#[async_trait]
+impl DaClient for CelestiaDaClient {
+    /// Submits the state diff as a single blob and returns `<height>:<commitment>`.
+    async fn publish_state_diff(&self, state_diff: Vec<Vec<u8>>, _to: &[u8; 32]) -> Result<String> {
+        ensure!(state_diff.len() == 1, "Celestia DA expects a single blob per state diff, got {}", state_diff.len());
+        let data = BASE64.encode(&state_diff[0]);
+        let namespace = BASE64.encode(self.namespace);
+        tracing::info!(
+            log_type = "starting",
+            category = "da",
+            function_type = "publish_state_diff",
+            "Submitting blob to Celestia."
+        );
+
+        // The commitment is computed by the node, from the namespace and the data.
+        let blob = CelestiaBlob {
+            namespace: namespace.clone(),
+            data: data.clone(),
+            share_version: 0,
+            commitment: String::new(),
+        };
+        let height: u64 = self.call("blob.Submit", json!([[blob], {}])).await?;
+
+        let blobs: Vec<CelestiaBlob> = self.call("blob.GetAll", json!([height, [namespace]])).await?;
+        let blob = blobs
+            .into_iter()
+            .find(|blob| blob.data == data)
+            .ok_or_else(|| eyre!("Submitted blob not found at Celestia height {height}"))?;
+        let commitment = BASE64.decode(&blob.commitment).wrap_err("Invalid blob commitment")?;
+
+        let external_id = format!("{height}:0x{}", hex::encode(commitment));
+        tracing::info!(
+            log_type = "completed",
+            category = "da",
+            function_type = "publish_state_diff",
+            external_id = %external_id,
+            "Blob submitted to Celestia."
+        );
+        Ok(external_id)
+    }
+
+    /// Celestia blocks are final once produced, the blob is available as soon as the node finds it.
+    async fn verify_inclusion(&self, external_id: &str) -> Result<DaVerificationStatus> {
+        let (height, commitment) = parse_external_id(external_id)?;
+        let params = json!([height, BASE64.encode(self.namespace), BASE64.encode(commitment)]);
+        match self.call::<CelestiaBlob>("blob.Get", params).await {
+            Ok(_) => Ok(DaVerificationStatus::Verified),
+            Err(err) if err.downcast_ref::<NodeError>().is_some_and(|err| err.error.code == BLOB_NOT_FOUND_CODE) => {
+                Ok(DaVerificationStatus::Rejected(format!("Blob {external_id} not found on Celestia")))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn da_commitment(&self, external_id: &str) -> Result<Option<[u8; 32]>> {
+        parse_external_id(external_id).map(|(_, commitment)| Some(commitment))
+    }
+
+    /// Inclusion is proven by the Celestia light nodes, there is no attestation to hand to the settlement layer.
+    async fn da_attestation(&self, _external_id: &str) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    async fn blob_versioned_hashes(&self, _external_id: &str) -> Result<Vec<[u8; 32]>> {
+        Ok(Vec::new())
+    }
+
+    async fn max_blob_per_txn(&self) -> u64 {
+        1
+    }
+
+    async fn max_bytes_per_blob(&self) -> u64 {
+        // Leaves some room below the ~2MB that fit in a Celestia block.
+        1_500_000
+    }
+}
+
+/// Parses the id of a version 0 namespace, given as up to 10 hex encoded bytes.
+pub fn parse_namespace(namespace_id: &str) -> Result<[u8; NAMESPACE_SIZE]> {
+    let id = hex::decode(namespace_id.trim_start_matches("0x")).wrap_err("Invalid Celestia namespace")?;
+    ensure!(
+        !id.is_empty() && id.len() <= NAMESPACE_V0_USER_BYTES,
+        "Celestia namespace id must be between 1 and {NAMESPACE_V0_USER_BYTES} bytes long"
+    );
+    let mut namespace = [0u8; NAMESPACE_SIZE];
+    namespace[NAMESPACE_SIZE - id.len()..].copy_from_slice(&id);
+    Ok(namespace)
+}
+
+fn parse_external_id(external_id: &str) -> Result<(u64, [u8; 32])> {
+    let (height, commitment) =
+        external_id.split_once(':').ok_or_else(|| eyre!("Invalid Celestia external id {external_id}"))?;
+    let height = height.parse().wrap_err_with(|| format!("Invalid height in Celestia external id {external_id}"))?;
+    let commitment = hex::decode(commitment.trim_start_matches("0x"))
+        .ok()
+        .and_then(|commitment| commitment.try_into().ok())
+        .ok_or_else(|| eyre!("Invalid commitment in Celestia external id {external_id}"))?;
+    Ok((height, commitment))
+}
@@ -0,0 +1,99 @@
+use httpmock::prelude::*;
+use rstest::rstest;
+
+use super::*;
+
+const NAMESPACE_ID: &str = "0x6d6164617261";
+
+fn client(server: &MockServer) -> CelestiaDaClient {
+    CelestiaDaClient::new_with_args(&CelestiaDaValidatedArgs {
+        celestia_da_rpc_url: Url::parse(&server.base_url()).unwrap(),
+        celestia_da_auth_token: Some("token".into()),
+        celestia_da_namespace: parse_namespace(NAMESPACE_ID).unwrap(),
+    })
+}
+
+#[rstest]
+fn test_parse_namespace() {
+    let namespace = parse_namespace(NAMESPACE_ID).unwrap();
+    assert_eq!(namespace[..23], [0; 23]);
+    assert_eq!(&namespace[23..], b"madara");
+
+    assert!(parse_namespace("").is_err());
+    assert!(parse_namespace(&"01".repeat(11)).is_err());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_publish_state_diff_and_verify_inclusion() {
+    let server = MockServer::start();
+    let namespace = BASE64.encode(parse_namespace(NAMESPACE_ID).unwrap());
+    let data = vec![1u8, 2, 3];
+    let commitment = [7u8; 32];
+
+    let submit = server.mock(|when, then| {
+        when.method(POST).header("authorization", "Bearer token").body_contains(r#""method":"blob.Submit""#);
+        then.status(200).json_body(json!({ "jsonrpc": "2.0", "id": 1, "result": 42 }));
+    });
+    let blob = json!({
+        "namespace": namespace,
+        "data": BASE64.encode(&data),
+        "share_version": 0,
+        "commitment": BASE64.encode(commitment),
+    });
+    let get_all = server.mock(|when, then| {
+        when.method(POST).body_contains(r#""method":"blob.GetAll""#);
+        then.status(200).json_body(json!({ "jsonrpc": "2.0", "id": 1, "result": [blob] }));
+    });
+    let get = server.mock(|when, then| {
+        when.method(POST).body_contains(r#""method":"blob.Get""#);
+        then.status(200).json_body(json!({ "jsonrpc": "2.0", "id": 1, "result": blob }));
+    });
+
+    let client = client(&server);
+    let external_id = client.publish_state_diff(vec![data], &[0; 32]).await.unwrap();
+    assert_eq!(external_id, format!("42:0x{}", hex::encode(commitment)));
+    assert_eq!(client.da_commitment(&external_id).await.unwrap(), Some(commitment));
+    assert_eq!(client.verify_inclusion(&external_id).await.unwrap(), DaVerificationStatus::Verified);
+
+    submit.assert();
+    get_all.assert();
+    get.assert();
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_verify_inclusion_blob_not_found() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).body_contains(r#""method":"blob.Get""#);
+        then.status(200).json_body(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": 1, "message": "getting blob: blob: not found" },
+        }));
+    });
+
+    let external_id = format!("42:0x{}", hex::encode([7u8; 32]));
+    let status = client(&server).verify_inclusion(&external_id).await.unwrap();
+    assert!(matches!(status, DaVerificationStatus::Rejected(_)));
+    assert!(client(&server).verify_inclusion("42").await.is_err());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_verify_inclusion_other_node_error() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).body_contains(r#""method":"blob.Get""#);
+        then.status(200).json_body(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32000, "message": "getting blob: blob: not found in the local store" },
+        }));
+    });
+
+    // Only the error code tells a missing blob apart, any other node error is not a rejection.
+    let external_id = format!("42:0x{}", hex::encode([7u8; 32]));
+    assert!(client(&server).verify_inclusion(&external_id).await.is_err());
+}
@@ -22,6 +22,18 @@ pub trait DaClient: Send + Sync {
     async fn publish_state_diff(&self, state_diff: Vec<Vec<u8>>, to: &[u8; 32]) -> Result<String>;
     /// Should verify the inclusion of the state diff in the DA layer and return the status
     async fn verify_inclusion(&self, external_id: &str) -> Result<DaVerificationStatus>;
+    /// Should return the commitment to the data published under `external_id`, which is given to the
+    /// settlement layer in place of the data itself. DA layers whose data is checked by the settlement
+    /// contract directly, like Ethereum blobs, return `None`.
+    async fn da_commitment(&self, external_id: &str) -> Result<Option<[u8; 32]>>;
+    /// Should return the attestation of the data published under `external_id`, signed by the parties
+    /// keeping the data, for the settlement layer to check before accepting its commitment. DA layers
+    /// without such attestation return `None`.
+    async fn da_attestation(&self, external_id: &str) -> Result<Option<Vec<u8>>>;
+    /// Should return the versioned hashes of the EIP-4844 blobs published under `external_id`, for the state
+    /// update to reference the blobs already published. DA layers not publishing Ethereum blobs return an empty
+    /// list.
+    async fn blob_versioned_hashes(&self, external_id: &str) -> Result<Vec<[u8; 32]>>;
     /// Should return the max blobs per txn
     async fn max_blob_per_txn(&self) -> u64;
     /// Should return the max bytes per blob
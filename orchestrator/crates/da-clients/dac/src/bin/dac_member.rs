@@ -0,0 +1,46 @@
+//! Runs a data availability committee member, see [`orchestrator_dac_da_client::member`].
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use alloy::signers::local::PrivateKeySigner;
+use clap::Parser;
+use color_eyre::eyre::WrapErr;
+use color_eyre::Result;
+use orchestrator_dac_da_client::member::DacMember;
+
+#[derive(Debug, Parser)]
+#[command(about = "Data availability committee member storing state diffs and signing their hash")]
+struct Args {
+    /// Address the member listens on.
+    #[arg(env = "DAC_MEMBER_ADDRESS", long, default_value = "0.0.0.0:8090")]
+    address: SocketAddr,
+
+    /// Private key the data hashes are signed with. Its address must be registered in the committee contract.
+    #[arg(env = "DAC_MEMBER_PRIVATE_KEY", long)]
+    private_key: String,
+
+    /// Token the orchestrator authenticates with to store data, as a bearer token.
+    #[arg(env = "DAC_MEMBER_AUTH_TOKEN", long)]
+    auth_token: String,
+
+    /// Directory where the data is stored. The data is kept in memory when it is not set.
+    #[arg(env = "DAC_MEMBER_DATA_DIR", long)]
+    data_dir: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    color_eyre::install()?;
+    tracing_subscriber::fmt().with_env_filter(tracing_subscriber::EnvFilter::from_default_env()).init();
+
+    let args = Args::parse();
+    let signer: PrivateKeySigner = args.private_key.parse().wrap_err("Invalid private key")?;
+    let signer_address = signer.address();
+    let member = Arc::new(DacMember::new(signer, args.auth_token, args.data_dir));
+
+    let listener = tokio::net::TcpListener::bind(args.address).await.wrap_err("Binding the member address")?;
+    tracing::info!(address = %args.address, signer = %signer_address, "DAC member started");
+    axum::serve(listener, member.router()).await.wrap_err("Serving the member api")
+}
@@ -0,0 +1,207 @@
+#![allow(missing_docs)]
+#![allow(clippy::missing_docs_in_private_items)]
+
+//! DA client for validium chains: the state diffs are sent to the members of a data availability committee
+//! (DAC), which store them and sign their keccak hash. The data is available once enough members signed it,
+//! and their signatures form the attestation checked by the committee contract on the settlement layer.
+
+use alloy::primitives::{keccak256, Address, PrimitiveSignature, B256};
+use async_trait::async_trait;
+use color_eyre::eyre::{ensure, eyre, WrapErr};
+use color_eyre::Result;
+use futures::future::join_all;
+use orchestrator_da_client_interface::{DaClient, DaVerificationStatus};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::types::{SignedDataHash, StoreRequest};
+
+pub mod member;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DacDaValidatedArgs {
+    /// Urls of the committee members.
+    pub dac_da_member_urls: Vec<Url>,
+    /// Number of member signatures needed for the data to be available.
+    pub dac_da_threshold: usize,
+    /// Token the members require to store data, see [`member`].
+    pub dac_da_auth_token: String,
+}
+
+pub struct DacDaClient {
+    client: reqwest::Client,
+    member_urls: Vec<Url>,
+    threshold: usize,
+    auth_token: String,
+}
+
+/// Answer of a member asked for its signature of some data.
+enum MemberSignature {
+    Signed(Address, PrimitiveSignature),
+    NotStored,
+    Unavailable,
+}
+
+impl DacDaClient {
+    pub fn new_with_args(dac_da_params: &DacDaValidatedArgs) -> Self {
+        assert!(
+            dac_da_params.dac_da_threshold > 0
+                && dac_da_params.dac_da_threshold <= dac_da_params.dac_da_member_urls.len(),
+            "The DAC threshold must be between 1 and the number of members"
+        );
+        Self {
+            client: reqwest::Client::new(),
+            member_urls: dac_da_params.dac_da_member_urls.clone(),
+            threshold: dac_da_params.dac_da_threshold,
+            auth_token: dac_da_params.dac_da_auth_token.clone(),
+        }
+    }
+
+    async fn store(&self, member_url: &Url, data: &str, data_hash: B256) -> Result<(Address, PrimitiveSignature)> {
+        let response = self
+            .client
+            .post(member_url.join("store")?)
+            .bearer_auth(&self.auth_token)
+            .json(&StoreRequest { data: data.to_string() })
+            .send()
+            .await?
+            .error_for_status()?;
+        response.json::<SignedDataHash>().await?.verify(data_hash)
+    }
+
+    async fn signature(&self, member_url: &Url, data_hash: B256) -> MemberSignature {
+        let signature = async {
+            let response = self.client.get(member_url.join(&format!("signature/{data_hash}"))?).send().await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok::<_, color_eyre::Report>(None);
+            }
+            response.error_for_status()?.json::<SignedDataHash>().await?.verify(data_hash).map(Some)
+        };
+        match signature.await {
+            Ok(Some((signer, signature))) => MemberSignature::Signed(signer, signature),
+            Ok(None) => MemberSignature::NotStored,
+            Err(err) => {
+                tracing::warn!(category = "da", member = %member_url, error = ?err, "DAC member signature unavailable");
+                MemberSignature::Unavailable
+            }
+        }
+    }
+
+    /// Returns the signatures of the members storing the data, along with the number of members which answered
+    /// they do not store it.
+    async fn signatures(&self, data_hash: B256) -> (Vec<(Address, PrimitiveSignature)>, usize) {
+        let answers = join_all(self.member_urls.iter().map(|member_url| self.signature(member_url, data_hash))).await;
+        let mut signatures = Vec::with_capacity(answers.len());
+        let mut not_stored = 0;
+        for answer in answers {
+            match answer {
+                MemberSignature::Signed(signer, signature) => signatures.push((signer, signature)),
+                MemberSignature::NotStored => not_stored += 1,
+                MemberSignature::Unavailable => {}
+            }
+        }
+        (sorted_signatures(signatures), not_stored)
+    }
+
+    fn parse_external_id(external_id: &str) -> Result<B256> {
+        external_id.parse().wrap_err_with(|| format!("DAC external id {external_id} is not a 32 bytes hash"))
+    }
+}
+
+/// Keeps one signature per signer, ordered by signer address as expected by the committee contract.
+fn sorted_signatures(mut signatures: Vec<(Address, PrimitiveSignature)>) -> Vec<(Address, PrimitiveSignature)> {
+    signatures.sort_by_key(|(signer, _)| *signer);
+    signatures.dedup_by_key(|(signer, _)| *signer);
+    signatures
+}
+
+#[async_trait]
+impl DaClient for DacDaClient {
+    /// Sends the data to every member and returns its hash once at least the threshold of members signed it.
+    async fn publish_state_diff(&self, state_diff: Vec<Vec<u8>>, _to: &[u8; 32]) -> Result<String> {
+        let data = state_diff.concat();
+        let data_hash = keccak256(&data);
+        let encoded_data = format!("0x{}", hex::encode(&data));
+
+        let results =
+            join_all(self.member_urls.iter().map(|member_url| self.store(member_url, &encoded_data, data_hash))).await;
+        let mut signatures = Vec::with_capacity(results.len());
+        for (member_url, result) in self.member_urls.iter().zip(results) {
+            match result {
+                Ok(signature) => signatures.push(signature),
+                Err(err) => {
+                    tracing::warn!(category = "da", member = %member_url, error = ?err, "DAC member did not store the data")
+                }
+            }
+        }
+
+        let signed = sorted_signatures(signatures).len();
+        ensure!(
+            signed >= self.threshold,
+            "Only {signed} DAC members out of the {} required signed data {data_hash}",
+            self.threshold
+        );
+        tracing::info!(
+            category = "da",
+            function_type = "publish_state_diff",
+            data_hash = %data_hash,
+            signed,
+            "State diff stored by the DAC."
+        );
+        Ok(data_hash.to_string())
+    }
+
+    /// The data is available while at least the threshold of members still sign it. It is rejected once too many
+    /// members answered they do not store it, and pending while members are unreachable.
+    async fn verify_inclusion(&self, external_id: &str) -> Result<DaVerificationStatus> {
+        let data_hash = Self::parse_external_id(external_id)?;
+        let (signatures, not_stored) = self.signatures(data_hash).await;
+        let signed = signatures.len();
+
+        if signed >= self.threshold {
+            Ok(DaVerificationStatus::Verified)
+        } else if self.member_urls.len() - not_stored < self.threshold {
+            Ok(DaVerificationStatus::Rejected(format!("Only {signed} DAC members store data {data_hash}")))
+        } else {
+            Ok(DaVerificationStatus::Pending)
+        }
+    }
+
+    async fn da_commitment(&self, external_id: &str) -> Result<Option<[u8; 32]>> {
+        Self::parse_external_id(external_id).map(|data_hash| Some(data_hash.0))
+    }
+
+    /// The attestation is made of the 65 bytes signatures (`r`, `s`, `v`) of the threshold of members, ordered by
+    /// signer address.
+    async fn da_attestation(&self, external_id: &str) -> Result<Option<Vec<u8>>> {
+        let data_hash = Self::parse_external_id(external_id)?;
+        let (signatures, _) = self.signatures(data_hash).await;
+        if signatures.len() < self.threshold {
+            return Err(eyre!(
+                "Only {} DAC members out of the {} required signed data {data_hash}",
+                signatures.len(),
+                self.threshold
+            ));
+        }
+
+        Ok(Some(signatures.into_iter().take(self.threshold).flat_map(|(_, signature)| signature.as_bytes()).collect()))
+    }
+
+    async fn blob_versioned_hashes(&self, _external_id: &str) -> Result<Vec<[u8; 32]>> {
+        Ok(Vec::new())
+    }
+
+    async fn max_blob_per_txn(&self) -> u64 {
+        1
+    }
+
+    async fn max_bytes_per_blob(&self) -> u64 {
+        // Members store the state diff of a block at once.
+        member::MAX_DATA_BYTES as u64
+    }
+}
@@ -0,0 +1,151 @@
+//! Reference implementation of a committee member: a small HTTP service storing the data it is sent, in memory
+//! or in a directory, and signing its keccak hash.
+//!
+//! - `POST /store` stores the data and returns the signed hash. It requires the member's token as a bearer token.
+//! - `GET /signature/{hash}` returns the signed hash of data already stored.
+//! - `GET /data/{hash}` returns the stored data.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use alloy::primitives::{keccak256, B256};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::SignerSync;
+use axum::extract::{DefaultBodyLimit, Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use color_eyre::eyre::WrapErr;
+use color_eyre::Result;
+use tokio::sync::RwLock;
+
+use crate::types::{SignedDataHash, StoreRequest, StoredData};
+
+/// Largest data a member stores at once.
+pub const MAX_DATA_BYTES: usize = 1 << 26;
+/// The data is sent hex encoded in a JSON body, which leaves some room for its `0x` prefix and the JSON syntax.
+const MAX_STORE_BODY_BYTES: usize = 2 * MAX_DATA_BYTES + 1024;
+
+pub struct DacMember {
+    signer: PrivateKeySigner,
+    auth_token: String,
+    data_dir: Option<PathBuf>,
+    in_memory: RwLock<HashMap<B256, Vec<u8>>>,
+}
+
+impl DacMember {
+    /// Creates a member signing with `signer` and storing the data of the clients presenting `auth_token`. The
+    /// data is kept in memory when `data_dir` is not set.
+    pub fn new(signer: PrivateKeySigner, auth_token: String, data_dir: Option<PathBuf>) -> Self {
+        Self { signer, auth_token, data_dir, in_memory: Default::default() }
+    }
+
+    pub fn router(self: Arc<Self>) -> Router {
+        Router::new()
+            .route("/store", post(store).layer(DefaultBodyLimit::max(MAX_STORE_BODY_BYTES)))
+            .route("/signature/:hash", get(signature))
+            .route("/data/:hash", get(data))
+            .with_state(self)
+    }
+
+    async fn store(&self, data: Vec<u8>) -> Result<B256> {
+        let data_hash = keccak256(&data);
+        match &self.data_dir {
+            Some(data_dir) => {
+                tokio::fs::create_dir_all(data_dir).await.wrap_err("Creating data directory")?;
+                tokio::fs::write(data_dir.join(Self::file_name(&data_hash)), &data).await.wrap_err("Writing data")?;
+            }
+            None => {
+                self.in_memory.write().await.insert(data_hash, data);
+            }
+        }
+        Ok(data_hash)
+    }
+
+    async fn get(&self, data_hash: &B256) -> Result<Option<Vec<u8>>> {
+        match &self.data_dir {
+            Some(data_dir) => match tokio::fs::read(data_dir.join(Self::file_name(data_hash))).await {
+                Ok(data) => Ok(Some(data)),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err).wrap_err("Reading data"),
+            },
+            None => Ok(self.in_memory.read().await.get(data_hash).cloned()),
+        }
+    }
+
+    fn sign(&self, data_hash: B256) -> Result<SignedDataHash> {
+        let signature = self.signer.sign_hash_sync(&data_hash).wrap_err("Signing data hash")?;
+        Ok(SignedDataHash::new(data_hash, self.signer.address(), signature))
+    }
+
+    fn is_authorized(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| token == self.auth_token)
+    }
+
+    fn file_name(data_hash: &B256) -> String {
+        format!("{}.bin", hex::encode(data_hash))
+    }
+}
+
+struct MemberError(StatusCode, String);
+
+impl IntoResponse for MemberError {
+    fn into_response(self) -> Response {
+        (self.0, self.1).into_response()
+    }
+}
+
+impl From<color_eyre::Report> for MemberError {
+    fn from(err: color_eyre::Report) -> Self {
+        tracing::error!(error = ?err, "DAC member request failed");
+        Self(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+    }
+}
+
+fn parse_hash(hash: &str) -> Result<B256, MemberError> {
+    hash.parse().map_err(|_| MemberError(StatusCode::BAD_REQUEST, format!("Invalid data hash {hash}")))
+}
+
+async fn store(
+    State(member): State<Arc<DacMember>>,
+    headers: HeaderMap,
+    Json(request): Json<StoreRequest>,
+) -> Result<Json<SignedDataHash>, MemberError> {
+    if !member.is_authorized(&headers) {
+        return Err(MemberError(StatusCode::UNAUTHORIZED, "Invalid or missing auth token".to_string()));
+    }
+    let data = hex::decode(request.data.trim_start_matches("0x"))
+        .map_err(|_| MemberError(StatusCode::BAD_REQUEST, "Data is not hex encoded".to_string()))?;
+    if data.len() > MAX_DATA_BYTES {
+        return Err(MemberError(StatusCode::PAYLOAD_TOO_LARGE, format!("Data is larger than {MAX_DATA_BYTES} bytes")));
+    }
+    let data_len = data.len();
+    let data_hash = member.store(data).await?;
+    tracing::info!(data_hash = %data_hash, data_len, "Stored data");
+    Ok(Json(member.sign(data_hash)?))
+}
+
+async fn signature(
+    State(member): State<Arc<DacMember>>,
+    Path(hash): Path<String>,
+) -> Result<Json<SignedDataHash>, MemberError> {
+    let data_hash = parse_hash(&hash)?;
+    if member.get(&data_hash).await?.is_none() {
+        return Err(MemberError(StatusCode::NOT_FOUND, format!("No data with hash {data_hash}")));
+    }
+    Ok(Json(member.sign(data_hash)?))
+}
+
+async fn data(State(member): State<Arc<DacMember>>, Path(hash): Path<String>) -> Result<Json<StoredData>, MemberError> {
+    let data_hash = parse_hash(&hash)?;
+    match member.get(&data_hash).await? {
+        Some(data) => Ok(Json(StoredData { data: format!("0x{}", hex::encode(data)) })),
+        None => Err(MemberError(StatusCode::NOT_FOUND, format!("No data with hash {data_hash}"))),
+    }
+}
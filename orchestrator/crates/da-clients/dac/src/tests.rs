@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use alloy::signers::local::PrivateKeySigner;
+use rstest::rstest;
+
+use super::*;
+use crate::member::DacMember;
+use crate::types::StoredData;
+
+const AUTH_TOKEN: &str = "member-token";
+
+async fn spawn_member(signer: PrivateKeySigner, data_dir: Option<std::path::PathBuf>) -> Url {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let member = Arc::new(DacMember::new(signer, AUTH_TOKEN.to_string(), data_dir));
+    tokio::spawn(async move { axum::serve(listener, member.router()).await.unwrap() });
+    Url::parse(&format!("http://{address}")).unwrap()
+}
+
+fn client(member_urls: Vec<Url>, threshold: usize) -> DacDaClient {
+    DacDaClient::new_with_args(&DacDaValidatedArgs {
+        dac_da_member_urls: member_urls,
+        dac_da_threshold: threshold,
+        dac_da_auth_token: AUTH_TOKEN.to_string(),
+    })
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_publish_state_diff_and_attestation() {
+    let signers: Vec<PrivateKeySigner> = (0..3).map(|_| PrivateKeySigner::random()).collect();
+    let mut member_urls = Vec::new();
+    for signer in &signers {
+        member_urls.push(spawn_member(signer.clone(), None).await);
+    }
+    let client = client(member_urls, 2);
+
+    let state_diff = vec![vec![1u8; 64], vec![2u8; 32]];
+    let external_id = client.publish_state_diff(state_diff.clone(), &[0; 32]).await.unwrap();
+    let data_hash = keccak256(state_diff.concat());
+    assert_eq!(external_id, data_hash.to_string());
+    assert_eq!(client.verify_inclusion(&external_id).await.unwrap(), DaVerificationStatus::Verified);
+    assert_eq!(client.da_commitment(&external_id).await.unwrap(), Some(data_hash.0));
+
+    // The attestation holds the signatures of the two members with the lowest addresses, in order.
+    let attestation = client.da_attestation(&external_id).await.unwrap().unwrap();
+    assert_eq!(attestation.len(), 2 * 65);
+    let mut expected_signers: Vec<Address> = signers.iter().map(|signer| signer.address()).collect();
+    expected_signers.sort();
+    let attestation_signers: Vec<Address> = attestation
+        .chunks(65)
+        .map(|signature| {
+            PrimitiveSignature::try_from(signature).unwrap().recover_address_from_prehash(&data_hash).unwrap()
+        })
+        .collect();
+    assert_eq!(attestation_signers, expected_signers[..2]);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_publish_state_diff_below_threshold_fails() {
+    let member_url = spawn_member(PrivateKeySigner::random(), None).await;
+    let unreachable_url = Url::parse("http://127.0.0.1:1").unwrap();
+    let client = client(vec![member_url, unreachable_url], 2);
+
+    let result = client.publish_state_diff(vec![vec![1u8; 32]], &[0; 32]).await;
+    assert!(result.unwrap_err().to_string().contains("Only 1 DAC members out of the 2 required"));
+
+    // The unreachable member could still get the data.
+    let external_id = keccak256([1u8; 32]).to_string();
+    assert_eq!(client.verify_inclusion(&external_id).await.unwrap(), DaVerificationStatus::Pending);
+    assert!(client.da_attestation(&external_id).await.is_err());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_verify_inclusion_unknown_data_is_rejected() {
+    let member_urls = vec![
+        spawn_member(PrivateKeySigner::random(), None).await,
+        spawn_member(PrivateKeySigner::random(), None).await,
+    ];
+    let client = client(member_urls, 1);
+
+    let status = client.verify_inclusion(&B256::repeat_byte(1).to_string()).await.unwrap();
+    assert!(matches!(status, DaVerificationStatus::Rejected(_)));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_member_serves_stored_data() {
+    let data_dir = tempfile::tempdir().unwrap();
+    let member_url = spawn_member(PrivateKeySigner::random(), Some(data_dir.path().to_path_buf())).await;
+    let client = client(vec![member_url.clone()], 1);
+
+    let external_id = client.publish_state_diff(vec![vec![7u8; 96]], &[0; 32]).await.unwrap();
+
+    let stored: StoredData = reqwest::get(member_url.join(&format!("data/{external_id}")).unwrap())
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(stored.data, format!("0x{}", hex::encode([7u8; 96])));
+    assert_eq!(std::fs::read_dir(data_dir.path()).unwrap().count(), 1);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_member_rejects_unauthenticated_store() {
+    let member_url = spawn_member(PrivateKeySigner::random(), None).await;
+    let store_url = member_url.join("store").unwrap();
+    let request = StoreRequest { data: format!("0x{}", hex::encode([1u8; 32])) };
+    let http = reqwest::Client::new();
+
+    let response = http.post(store_url.clone()).json(&request).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = http.post(store_url).bearer_auth("other-token").json(&request).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let client = DacDaClient::new_with_args(&DacDaValidatedArgs {
+        dac_da_member_urls: vec![member_url],
+        dac_da_threshold: 1,
+        dac_da_auth_token: "other-token".to_string(),
+    });
+    assert!(client.publish_state_diff(vec![vec![1u8; 32]], &[0; 32]).await.is_err());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_member_stores_data_above_the_default_body_limit() {
+    let member_url = spawn_member(PrivateKeySigner::random(), None).await;
+    let client = client(vec![member_url], 1);
+
+    // Hex encoded, 3MB of data are well above the 2MB axum accepts by default.
+    let state_diff = vec![vec![3u8; 3 << 20]];
+    let external_id = client.publish_state_diff(state_diff.clone(), &[0; 32]).await.unwrap();
+    assert_eq!(external_id, keccak256(state_diff.concat()).to_string());
+}
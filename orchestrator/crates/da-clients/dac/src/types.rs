@@ -0,0 +1,50 @@
+//! Messages exchanged between the DAC client and the committee members.
+
+use alloy::primitives::{Address, PrimitiveSignature, B256};
+use color_eyre::eyre::{ensure, WrapErr};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+/// Data sent to a member to store, hex encoded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreRequest {
+    pub data: String,
+}
+
+/// Data returned by a member, hex encoded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredData {
+    pub data: String,
+}
+
+/// Signature of a member over the keccak hash of the data it stores. The hash is signed as is, without the
+/// Ethereum signed message prefix, so that it can be checked on chain with `ecrecover`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedDataHash {
+    pub data_hash: String,
+    pub signer: String,
+    pub signature: String,
+}
+
+impl SignedDataHash {
+    pub fn new(data_hash: B256, signer: Address, signature: PrimitiveSignature) -> Self {
+        Self {
+            data_hash: data_hash.to_string(),
+            signer: signer.to_string(),
+            signature: format!("0x{}", hex::encode(signature.as_bytes())),
+        }
+    }
+
+    /// Checks the signature is over `data_hash` and was made by the announced signer, returning both.
+    pub fn verify(&self, data_hash: B256) -> Result<(Address, PrimitiveSignature)> {
+        let signed_hash: B256 = self.data_hash.parse().wrap_err("Invalid data hash")?;
+        ensure!(signed_hash == data_hash, "Member signed {signed_hash} instead of {data_hash}");
+
+        let signer: Address = self.signer.parse().wrap_err("Invalid signer address")?;
+        let signature = hex::decode(self.signature.trim_start_matches("0x")).wrap_err("Invalid signature encoding")?;
+        let signature = PrimitiveSignature::try_from(signature.as_slice()).wrap_err("Invalid signature")?;
+        let recovered = signature.recover_address_from_prehash(&data_hash).wrap_err("Invalid signature")?;
+        ensure!(recovered == signer, "Signature is from {recovered} instead of {signer}");
+        Ok((signer, signature))
+    }
+}
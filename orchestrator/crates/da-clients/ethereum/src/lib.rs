@@ -1,55 +1,201 @@
 #![allow(missing_docs)]
 #![allow(clippy::missing_docs_in_private_items)]
 
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
-use alloy::network::Ethereum;
-use alloy::providers::{ProviderBuilder, RootProvider};
-use alloy::rpc::client::RpcClient;
+use alloy::consensus::{BlobTransactionSidecar, Transaction as _};
+use alloy::eips::eip4844::BYTES_PER_BLOB;
+use alloy::network::EthereumWallet;
+use alloy::primitives::{Address, Bytes, FixedBytes, B256};
+use alloy::providers::{Provider, ProviderBuilder, RootProvider};
+use alloy::rpc::types::TransactionReceipt;
+use alloy::signers::local::PrivateKeySigner;
 use alloy::transports::http::Http;
 use async_trait::async_trait;
+use c_kzg::{Blob, KzgCommitment, KzgProof, KzgSettings};
+use color_eyre::eyre::bail;
 use color_eyre::Result;
+use lazy_static::lazy_static;
 use mockall::automock;
 use mockall::predicate::*;
 use orchestrator_da_client_interface::{DaClient, DaVerificationStatus};
+use orchestrator_ethereum_settlement_client::tx_manager::{InFlightTxStore, TxManager, TxManagerConfig, TxRequest};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+#[cfg(test)]
+mod tests;
+
+/// External id returned when the blobs are not published by the DA client, in which case they are sent along
+/// with the state update by the settlement client.
+pub const BLOBS_NOT_PUBLISHED: &str = "NA";
+
+const REQUIRED_BLOCK_CONFIRMATIONS: u64 = 3;
+// Receipts of the blob transactions in flight are polled once per slot
+const TX_RECEIPT_POLL_INTERVAL_SECS: u64 = 12;
+
+lazy_static! {
+    pub static ref KZG_SETTINGS: KzgSettings =
+        KzgSettings::load_trusted_setup_file(&PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("trusted_setup.txt"))
+            .expect("Error loading trusted setup file");
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EthereumDaValidatedArgs {
     pub ethereum_da_rpc_url: Url,
+
+    /// Private key of the account sending the blob transactions. Blobs are only published by the DA client when
+    /// it is set, the state update then references them by their versioned hashes.
+    pub ethereum_da_private_key: Option<String>,
+
+    /// Fee bumping and fee budget of the blob transactions, shared with the Ethereum settlement client.
+    #[serde(skip)]
+    pub tx_manager_config: TxManagerConfig,
+}
+
+struct BlobPublisher {
+    address: Address,
+    tx_manager: TxManager,
 }
 
 pub struct EthereumDaClient {
-    #[allow(dead_code)]
-    provider: RootProvider<Ethereum, Http<Client>>,
+    provider: Arc<RootProvider<Http<Client>>>,
+    publisher: Option<BlobPublisher>,
 }
 
 impl EthereumDaClient {
-    pub async fn new_with_args(ethereum_da_params: &EthereumDaValidatedArgs) -> Self {
-        let client = RpcClient::new_http(
-            Url::from_str(ethereum_da_params.ethereum_da_rpc_url.as_str())
-                .expect("Failed to parse ethereum_da_rpc_url"),
-        );
-        let provider = ProviderBuilder::<_, Ethereum>::new().on_client(client);
-        Self { provider }
+    /// The blob transactions in flight are persisted in `tx_store`, so that a restarted orchestrator resumes them.
+    pub async fn new_with_args(
+        ethereum_da_params: &EthereumDaValidatedArgs,
+        tx_store: Arc<dyn InFlightTxStore>,
+    ) -> Self {
+        let provider = Arc::new(ProviderBuilder::new().on_http(ethereum_da_params.ethereum_da_rpc_url.clone()));
+        let publisher = ethereum_da_params.ethereum_da_private_key.as_ref().map(|private_key| {
+            let signer: PrivateKeySigner = private_key.parse().expect("Failed to parse ethereum_da_private_key");
+            let address = signer.address();
+            let tx_manager = TxManager::new(
+                provider.clone(),
+                EthereumWallet::from(signer),
+                address,
+                ethereum_da_params.tx_manager_config.clone(),
+                tx_store,
+                Duration::from_secs(TX_RECEIPT_POLL_INTERVAL_SECS),
+            );
+            BlobPublisher { address, tx_manager }
+        });
+        Self { provider, publisher }
     }
 }
 
 #[automock]
 #[async_trait]
 impl DaClient for EthereumDaClient {
-    async fn publish_state_diff(&self, _state_diff: Vec<Vec<u8>>, _to: &[u8; 32]) -> Result<String> {
-        // Here in case of ethereum we are not publishing the state diff because we are doing it all
-        // together in update_state job. So we don't need to send the blob here.
-        Ok("NA".to_string())
+    /// Sends the blobs in a standalone EIP-4844 transaction, waits until it is included and returns its hash.
+    /// When no private key is configured, the blobs are sent along with the state update by the settlement client
+    /// instead.
+    async fn publish_state_diff(&self, state_diff: Vec<Vec<u8>>, to: &[u8; 32]) -> Result<String> {
+        let Some(publisher) = &self.publisher else {
+            return Ok(BLOBS_NOT_PUBLISHED.to_string());
+        };
+        tracing::info!(
+            log_type = "starting",
+            category = "da",
+            function_type = "publish_state_diff",
+            blob_count = state_diff.len(),
+            "Publishing blobs to Ethereum."
+        );
+
+        let sidecar = prepare_sidecar(&state_diff, &KZG_SETTINGS)?;
+
+        // The blobs are sent to the publisher itself unless a recipient is given.
+        let recipient = Address::from_slice(&to[12..]);
+        let recipient = if recipient.is_zero() { publisher.address } else { recipient };
+
+        let versioned_hashes: Vec<B256> = sidecar.versioned_hashes().collect();
+
+        // Gas, fees and nonce are handled by the transaction manager, which bumps the fees until the transaction
+        // is included
+        let tx_request = TxRequest { to: recipient, input: Bytes::new(), sidecar: Some(sidecar) };
+        let tx_receipt = publisher.tx_manager.send(tx_request).await?;
+        if !tx_receipt.status() {
+            bail!("Blob tx 0x{:x} reverted", tx_receipt.transaction_hash);
+        }
+        let tx_hash = tx_receipt.transaction_hash.to_string();
+
+        tracing::info!(
+            log_type = "completed",
+            category = "da",
+            function_type = "publish_state_diff",
+            tx_hash = %tx_hash,
+            versioned_hashes = ?versioned_hashes,
+            "Blobs published to Ethereum."
+        );
+        Ok(tx_hash)
     }
 
-    async fn verify_inclusion(&self, _external_id: &str) -> Result<DaVerificationStatus> {
+    /// The blobs are considered available once their transaction has [`REQUIRED_BLOCK_CONFIRMATIONS`]
+    /// confirmations.
+    async fn verify_inclusion(&self, external_id: &str) -> Result<DaVerificationStatus> {
+        if external_id == BLOBS_NOT_PUBLISHED {
+            return Ok(DaVerificationStatus::Verified);
+        }
+
+        let tx_hash = B256::from_str(external_id)?;
+        let maybe_receipt: Option<TransactionReceipt> = self.provider.get_transaction_receipt(tx_hash).await?;
+        let Some(receipt) = maybe_receipt else {
+            tracing::info!(category = "da", function_type = "verify_inclusion", tx_hash = %tx_hash, "Blob tx pending.");
+            return Ok(DaVerificationStatus::Pending);
+        };
+        if !receipt.status() {
+            return Ok(DaVerificationStatus::Rejected(format!("Blob transaction {tx_hash} reverted")));
+        }
+        let Some(block_number) = receipt.block_number else {
+            return Ok(DaVerificationStatus::Pending);
+        };
+
+        let latest_block_number = self.provider.get_block_number().await?;
+        if latest_block_number.saturating_sub(block_number) < REQUIRED_BLOCK_CONFIRMATIONS {
+            tracing::info!(
+                category = "da",
+                function_type = "verify_inclusion",
+                tx_hash = %tx_hash,
+                block_number,
+                "Blob tx waiting for confirmations."
+            );
+            return Ok(DaVerificationStatus::Pending);
+        }
         Ok(DaVerificationStatus::Verified)
     }
 
+    /// Blobs are checked by the core contract through their versioned hashes.
+    async fn da_commitment(&self, _external_id: &str) -> Result<Option<[u8; 32]>> {
+        Ok(None)
+    }
+
+    async fn da_attestation(&self, _external_id: &str) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    /// Versioned hashes carried by the blob transaction, none when the blobs are sent along with the state update.
+    async fn blob_versioned_hashes(&self, external_id: &str) -> Result<Vec<[u8; 32]>> {
+        if external_id == BLOBS_NOT_PUBLISHED {
+            return Ok(Vec::new());
+        }
+
+        let tx_hash = B256::from_str(external_id)?;
+        let Some(tx) = self.provider.get_transaction_by_hash(tx_hash).await? else {
+            bail!("Blob tx {tx_hash} not found");
+        };
+        let Some(versioned_hashes) = tx.blob_versioned_hashes() else {
+            bail!("Tx {tx_hash} carries no blobs");
+        };
+        Ok(versioned_hashes.iter().map(|hash| hash.0).collect())
+    }
+
     async fn max_blob_per_txn(&self) -> u64 {
         6
     }
@@ -58,3 +204,24 @@ impl DaClient for EthereumDaClient {
         131072
     }
 }
+
+/// Builds the sidecar of a blob transaction: the blobs along with their KZG commitments and proofs.
+fn prepare_sidecar(state_diff: &[Vec<u8>], trusted_setup: &KzgSettings) -> Result<BlobTransactionSidecar> {
+    let mut sidecar_blobs = vec![];
+    let mut sidecar_commitments = vec![];
+    let mut sidecar_proofs = vec![];
+
+    for blob_data in state_diff {
+        let fixed_size_blob: [u8; BYTES_PER_BLOB] = blob_data.as_slice().try_into()?;
+        let blob = Blob::new(fixed_size_blob);
+
+        let commitment = KzgCommitment::blob_to_kzg_commitment(&blob, trusted_setup)?;
+        let proof = KzgProof::compute_blob_kzg_proof(&blob, &commitment.to_bytes(), trusted_setup)?;
+
+        sidecar_blobs.push(FixedBytes::new(fixed_size_blob));
+        sidecar_commitments.push(FixedBytes::new(commitment.to_bytes().into_inner()));
+        sidecar_proofs.push(FixedBytes::new(proof.to_bytes().into_inner()));
+    }
+
+    Ok(BlobTransactionSidecar::new(sidecar_blobs, sidecar_commitments, sidecar_proofs))
+}
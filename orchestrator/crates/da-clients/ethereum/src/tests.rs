@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use alloy::consensus::Transaction as _;
+use alloy::node_bindings::Anvil;
+use orchestrator_ethereum_settlement_client::tx_manager::MockInFlightTxStore;
+use rstest::rstest;
+
+use super::*;
+
+/// Store of the transaction manager, the blob transactions of a test are never resumed.
+fn in_flight_tx_store() -> Arc<dyn InFlightTxStore> {
+    let mut store = MockInFlightTxStore::new();
+    store.expect_get_in_flight_tx().returning(|_, _| Ok(None));
+    store.expect_save_in_flight_tx().returning(|_| Ok(()));
+    store.expect_remove_in_flight_tx().returning(|_, _| Ok(()));
+    store.expect_prune_in_flight_txs().returning(|_, _| Ok(()));
+    Arc::new(store)
+}
+
+/// Blob whose field elements are all below the BLS modulus.
+fn test_blob(seed: u8) -> Vec<u8> {
+    let mut blob = vec![0u8; BYTES_PER_BLOB];
+    blob.chunks_mut(32).for_each(|field_element| field_element[31] = seed);
+    blob
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_blobs_not_published_without_private_key() {
+    let client = EthereumDaClient::new_with_args(
+        &EthereumDaValidatedArgs {
+            ethereum_da_rpc_url: Url::parse("http://localhost:8545").unwrap(),
+            ethereum_da_private_key: None,
+            tx_manager_config: TxManagerConfig::default(),
+        },
+        in_flight_tx_store(),
+    )
+    .await;
+
+    let external_id = client.publish_state_diff(vec![test_blob(1)], &[0; 32]).await.unwrap();
+    assert_eq!(external_id, BLOBS_NOT_PUBLISHED);
+    assert_eq!(client.verify_inclusion(&external_id).await.unwrap(), DaVerificationStatus::Verified);
+    assert_eq!(client.blob_versioned_hashes(&external_id).await.unwrap(), Vec::<[u8; 32]>::new());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_publish_state_diff_and_verify_inclusion() {
+    let anvil = Anvil::new().block_time(1).arg("--hardfork").arg("cancun").try_spawn().expect("Could not spawn Anvil.");
+    let client = EthereumDaClient::new_with_args(
+        &EthereumDaValidatedArgs {
+            ethereum_da_rpc_url: anvil.endpoint_url(),
+            ethereum_da_private_key: Some(alloy::hex::encode(anvil.keys()[0].to_bytes())),
+            tx_manager_config: TxManagerConfig::default(),
+        },
+        in_flight_tx_store(),
+    )
+    .await;
+
+    let state_diff = vec![test_blob(1), test_blob(2)];
+    let tx_hash = client.publish_state_diff(state_diff.clone(), &[0; 32]).await.unwrap();
+
+    // The tx needs a few blocks on top of it to be considered included.
+    let mut status = DaVerificationStatus::Pending;
+    for _ in 0..20 {
+        status = client.verify_inclusion(&tx_hash).await.unwrap();
+        if status != DaVerificationStatus::Pending {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    assert_eq!(status, DaVerificationStatus::Verified);
+
+    let tx = client.provider.get_transaction_by_hash(B256::from_str(&tx_hash).unwrap()).await.unwrap().unwrap();
+    let expected_hashes: Vec<B256> = prepare_sidecar(&state_diff, &KZG_SETTINGS).unwrap().versioned_hashes().collect();
+    assert_eq!(tx.blob_versioned_hashes(), Some(expected_hashes.as_slice()));
+    assert_eq!(tx.to(), Some(anvil.addresses()[0]));
+    assert_eq!(
+        client.blob_versioned_hashes(&tx_hash).await.unwrap(),
+        expected_hashes.iter().map(|hash| hash.0).collect::<Vec<_>>()
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_verify_inclusion_unknown_tx_is_pending() {
+    let anvil = Anvil::new().arg("--hardfork").arg("cancun").try_spawn().expect("Could not spawn Anvil.");
+    let client = EthereumDaClient::new_with_args(
+        &EthereumDaValidatedArgs {
+            ethereum_da_rpc_url: anvil.endpoint_url(),
+            ethereum_da_private_key: None,
+            tx_manager_config: TxManagerConfig::default(),
+        },
+        in_flight_tx_store(),
+    )
+    .await;
+
+    let status = client.verify_inclusion(&B256::repeat_byte(1).to_string()).await.unwrap();
+    assert_eq!(status, DaVerificationStatus::Pending);
+}
@@ -0,0 +1,111 @@
+#![allow(missing_docs)]
+#![allow(clippy::missing_docs_in_private_items)]
+
+//! DA client keeping the published data on the machine running the orchestrator, in memory or in a
+//! directory. It is meant for tests and local setups, the data is not made available to anyone else.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use orchestrator_da_client_interface::{DaClient, DaVerificationStatus};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LocalDaValidatedArgs {
+    /// Directory where the published data is written. The data is kept in memory when it is not set.
+    pub local_da_path: Option<PathBuf>,
+}
+
+#[derive(Default)]
+pub struct LocalDaClient {
+    path: Option<PathBuf>,
+    in_memory: Mutex<HashMap<[u8; 32], Vec<u8>>>,
+}
+
+impl LocalDaClient {
+    pub fn new_with_args(local_da_params: &LocalDaValidatedArgs) -> Self {
+        Self { path: local_da_params.local_da_path.clone(), in_memory: Default::default() }
+    }
+
+    /// Returns the data published with the given commitment, if any.
+    pub async fn get_data(&self, commitment: &[u8; 32]) -> Result<Option<Vec<u8>>> {
+        match &self.path {
+            Some(path) => match tokio::fs::read(path.join(Self::file_name(commitment))).await {
+                Ok(data) => Ok(Some(data)),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err).wrap_err("Reading local DA data"),
+            },
+            None => Ok(self.in_memory.lock().expect("Poisoned lock").get(commitment).cloned()),
+        }
+    }
+
+    fn file_name(commitment: &[u8; 32]) -> String {
+        format!("{}.bin", hex::encode(commitment))
+    }
+
+    fn parse_external_id(external_id: &str) -> Result<[u8; 32]> {
+        let bytes = hex::decode(external_id.trim_start_matches("0x")).wrap_err("Invalid local DA external id")?;
+        bytes.try_into().map_err(|_| eyre!("Local DA external id {external_id} is not a 32 bytes commitment"))
+    }
+}
+
+#[async_trait]
+impl DaClient for LocalDaClient {
+    /// The data is referenced by its keccak hash, which is also its commitment.
+    async fn publish_state_diff(&self, state_diff: Vec<Vec<u8>>, _to: &[u8; 32]) -> Result<String> {
+        let data = state_diff.concat();
+        let commitment: [u8; 32] = Keccak256::digest(&data).into();
+
+        match &self.path {
+            Some(path) => {
+                tokio::fs::create_dir_all(path).await.wrap_err("Creating local DA directory")?;
+                tokio::fs::write(path.join(Self::file_name(&commitment)), &data)
+                    .await
+                    .wrap_err("Writing local DA data")?;
+            }
+            None => {
+                self.in_memory.lock().expect("Poisoned lock").insert(commitment, data);
+            }
+        }
+
+        tracing::debug!(category = "da", function_type = "publish_state_diff", "Published data to the local DA.");
+        Ok(format!("0x{}", hex::encode(commitment)))
+    }
+
+    async fn verify_inclusion(&self, external_id: &str) -> Result<DaVerificationStatus> {
+        let commitment = Self::parse_external_id(external_id)?;
+        match self.get_data(&commitment).await? {
+            Some(_) => Ok(DaVerificationStatus::Verified),
+            None => Ok(DaVerificationStatus::Rejected(format!("No data published with commitment {external_id}"))),
+        }
+    }
+
+    async fn da_commitment(&self, external_id: &str) -> Result<Option<[u8; 32]>> {
+        Self::parse_external_id(external_id).map(Some)
+    }
+
+    async fn da_attestation(&self, _external_id: &str) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    async fn blob_versioned_hashes(&self, _external_id: &str) -> Result<Vec<[u8; 32]>> {
+        Ok(Vec::new())
+    }
+
+    async fn max_blob_per_txn(&self) -> u64 {
+        1
+    }
+
+    async fn max_bytes_per_blob(&self) -> u64 {
+        // Large enough to hold the state diff of any block.
+        1 << 26
+    }
+}
@@ -0,0 +1,35 @@
+use rstest::rstest;
+
+use super::*;
+
+#[rstest]
+#[tokio::test]
+async fn test_publish_and_verify_in_memory() {
+    let client = LocalDaClient::new_with_args(&LocalDaValidatedArgs::default());
+
+    let external_id = client.publish_state_diff(vec![vec![1, 2], vec![3]], &[0; 32]).await.unwrap();
+    let commitment = client.da_commitment(&external_id).await.unwrap().unwrap();
+    assert_eq!(commitment, <[u8; 32]>::from(Keccak256::digest([1, 2, 3])));
+    assert_eq!(client.get_data(&commitment).await.unwrap(), Some(vec![1, 2, 3]));
+    assert_eq!(client.verify_inclusion(&external_id).await.unwrap(), DaVerificationStatus::Verified);
+
+    let unknown = format!("0x{}", hex::encode([1; 32]));
+    assert!(matches!(client.verify_inclusion(&unknown).await.unwrap(), DaVerificationStatus::Rejected(_)));
+    assert!(client.verify_inclusion("0x1234").await.is_err());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_publish_and_verify_filesystem() {
+    let dir = tempfile::tempdir().unwrap();
+    let args = LocalDaValidatedArgs { local_da_path: Some(dir.path().join("da")) };
+
+    let external_id =
+        LocalDaClient::new_with_args(&args).publish_state_diff(vec![vec![42; 64]], &[0; 32]).await.unwrap();
+
+    // The data outlives the client which published it.
+    let client = LocalDaClient::new_with_args(&args);
+    let commitment = client.da_commitment(&external_id).await.unwrap().unwrap();
+    assert_eq!(client.get_data(&commitment).await.unwrap(), Some(vec![42; 64]));
+    assert_eq!(client.verify_inclusion(&external_id).await.unwrap(), DaVerificationStatus::Verified);
+}
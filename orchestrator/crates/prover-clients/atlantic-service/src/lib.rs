@@ -72,6 +72,9 @@ impl ProverClient for AtlanticProverService {
                 // The temporary file will be automatically deleted when `temp_file` goes out of scope
                 Ok(atlantic_job_response.atlantic_query_id)
             }
+            Task::Aggregation(_) => {
+                Err(ProverClientError::UnsupportedTask("proof aggregation is not available on Atlantic".to_string()))
+            }
         }
     }
 
@@ -129,6 +132,15 @@ impl ProverClient for AtlanticProverService {
             }
         }
     }
+
+    /// Proofs stay with Atlantic: only their facts are registered on chain.
+    async fn get_proof(&self, _task_id: &str) -> Result<Option<Vec<u8>>, ProverClientError> {
+        Ok(None)
+    }
+
+    async fn get_aggregator_output(&self, _task_id: &str) -> Result<Vec<[u8; 32]>, ProverClientError> {
+        Err(ProverClientError::UnsupportedTask("proof aggregation is not available on Atlantic".to_string()))
+    }
 }
 
 impl AtlanticProverService {
@@ -0,0 +1,28 @@
+use orchestrator_prover_client_interface::ProverClientError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LocalProverError {
+    #[error("Failed to prepare the task directory: {0}")]
+    TaskDirectory(#[source] std::io::Error),
+
+    #[error("Failed to read the outcome of task {0}: {1}")]
+    OutcomeRead(String, #[source] std::io::Error),
+
+    #[error("Failed to parse the outcome of task {0}: {1}")]
+    OutcomeParse(String, #[source] serde_json::Error),
+
+    #[error("Failed to read the proof of task {0}: {1}")]
+    ProofRead(String, #[source] std::io::Error),
+
+    #[error("Failed to read the aggregator output of task {0}: {1}")]
+    AggregatorOutputRead(String, #[source] std::io::Error),
+
+    #[error("Failed to parse the aggregator output of task {0}: {1}")]
+    AggregatorOutputParse(String, String),
+}
+
+impl From<LocalProverError> for ProverClientError {
+    fn from(value: LocalProverError) -> Self {
+        Self::Internal(Box::new(value))
+    }
+}
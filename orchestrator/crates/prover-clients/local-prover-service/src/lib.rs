@@ -0,0 +1,421 @@
+pub mod error;
+pub mod types;
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use cairo_vm::types::layout_name::LayoutName;
+use cairo_vm::Felt252;
+use orchestrator_prover_client_interface::{AggregationTask, ProverClient, ProverClientError, Task, TaskStatus};
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+
+use crate::error::LocalProverError;
+pub use crate::types::ProverRunOutcome;
+use crate::types::{AggregatedTask, AggregatorInput};
+
+/// Placeholder replaced by the path of the Cairo PIE in the prover arguments.
+pub const CAIRO_PIE_PLACEHOLDER: &str = "{cairo_pie}";
+/// Placeholder replaced by the path the proof must be written to in the prover arguments.
+pub const PROOF_PLACEHOLDER: &str = "{proof}";
+/// Placeholder replaced by the proof layout in the prover arguments.
+pub const LAYOUT_PLACEHOLDER: &str = "{layout}";
+/// Placeholder replaced by the path of the aggregated tasks in the aggregator arguments.
+pub const AGGREGATOR_INPUT_PLACEHOLDER: &str = "{aggregator_input}";
+/// Placeholder replaced by the path the aggregator program output must be written to in the aggregator arguments.
+pub const AGGREGATOR_OUTPUT_PLACEHOLDER: &str = "{aggregator_output}";
+
+const CAIRO_PIE_FILE_NAME: &str = "cairo_pie.zip";
+const PROOF_FILE_NAME: &str = "proof.json";
+const OUTCOME_FILE_NAME: &str = "outcome.json";
+const AGGREGATOR_INPUT_FILE_NAME: &str = "aggregator_input.json";
+const AGGREGATOR_OUTPUT_FILE_NAME: &str = "aggregator_output.json";
+const STDOUT_FILE_NAME: &str = "stdout.log";
+const STDERR_FILE_NAME: &str = "stderr.log";
+/// Number of bytes of the prover stderr kept in the failure reason.
+const STDERR_TAIL_LEN: usize = 2048;
+
+#[derive(Debug, Clone)]
+pub struct LocalProverValidatedArgs {
+    /// Prover executable, e.g. the stone `cpu_air_prover` wrapper or the stwo CLI.
+    pub local_prover_command: PathBuf,
+    /// Arguments of the prover, in which the `{cairo_pie}`, `{proof}` and `{layout}` placeholders are replaced.
+    pub local_prover_args: Vec<String>,
+    /// Directory holding one sub directory per task with its PIE, proof, logs and outcome.
+    pub local_prover_work_dir: PathBuf,
+    /// Time after which a prover run is killed.
+    pub local_prover_timeout: Duration,
+    /// Virtual memory limit of a prover run, in megabytes.
+    pub local_prover_max_memory_mb: Option<u64>,
+    /// CPU time limit of a prover run, in seconds.
+    pub local_prover_max_cpu_seconds: Option<u64>,
+    /// Number of prover runs allowed at the same time, the other tasks wait for their turn.
+    pub local_prover_max_concurrent_runs: usize,
+    /// Executable running the aggregator program over proven tasks and proving it. Aggregation tasks are
+    /// refused when it is not set.
+    pub local_prover_aggregator_command: Option<PathBuf>,
+    /// Arguments of the aggregator, in which the `{aggregator_input}`, `{aggregator_output}`, `{proof}` and
+    /// `{layout}` placeholders are replaced.
+    pub local_prover_aggregator_args: Vec<String>,
+}
+
+/// Proves the tasks on the orchestrator host by running a prover binary, one process per task.
+///
+/// Each task gets a directory in the work dir named after its id. The outcome of the run is written there when
+/// the prover exits, so that the status of finished tasks survives restarts of the orchestrator. Proofs are not
+/// registered on chain, the fact is left to the settlement flow.
+///
+/// Aggregation tasks run the configured aggregator over the proofs of tasks proven by this prover, it writes the
+/// aggregated proof and the program output to settle in the directory of the aggregation task.
+#[derive(Clone)]
+pub struct LocalProverService {
+    inner: Arc<LocalProver>,
+}
+
+struct LocalProver {
+    args: LocalProverValidatedArgs,
+    proof_layout: LayoutName,
+    /// Tasks submitted by this process whose prover has not exited yet.
+    running: Mutex<HashSet<String>>,
+    permits: Semaphore,
+}
+
+impl LocalProverService {
+    pub fn new_with_args(local_prover_params: &LocalProverValidatedArgs, proof_layout: &LayoutName) -> Self {
+        assert!(
+            local_prover_params.local_prover_max_concurrent_runs > 0,
+            "The local prover must be allowed at least one concurrent run"
+        );
+        Self {
+            inner: Arc::new(LocalProver {
+                args: local_prover_params.clone(),
+                proof_layout: proof_layout.to_owned(),
+                running: Mutex::new(HashSet::new()),
+                permits: Semaphore::new(local_prover_params.local_prover_max_concurrent_runs),
+            }),
+        }
+    }
+
+    /// Returns the directory of a task, refusing ids which are not ones this client generated.
+    fn task_dir(&self, task_id: &str) -> Result<PathBuf, ProverClientError> {
+        let task_id = Uuid::parse_str(task_id).map_err(|e| ProverClientError::InvalidJobKey(e.to_string()))?;
+        Ok(self.inner.args.local_prover_work_dir.join(task_id.to_string()))
+    }
+
+    fn spawn_run(&self, task_id: &str, task_dir: PathBuf, aggregation: bool) {
+        self.inner.set_running(task_id, true);
+        let prover = self.inner.clone();
+        tokio::spawn({
+            let task_id = task_id.to_string();
+            async move { prover.run(task_id, task_dir, aggregation).await }
+        });
+        tracing::debug!(task_id = %task_id, aggregation, "Started the local prover run");
+    }
+
+    /// Lists the proofs of the aggregated tasks along with their program outputs. The tasks must have been
+    /// proven by this prover, as the aggregator reads their proofs from the work dir.
+    async fn aggregator_input(&self, aggregation_task: AggregationTask) -> Result<AggregatorInput, ProverClientError> {
+        if aggregation_task.task_ids.is_empty()
+            || aggregation_task.task_ids.len() != aggregation_task.program_outputs.len()
+        {
+            return Err(ProverClientError::TaskInvalid(format!(
+                "Aggregation needs one program output per task, got {} tasks and {} outputs",
+                aggregation_task.task_ids.len(),
+                aggregation_task.program_outputs.len()
+            )));
+        }
+
+        let mut tasks = Vec::with_capacity(aggregation_task.task_ids.len());
+        for (task_id, program_output) in aggregation_task.task_ids.into_iter().zip(aggregation_task.program_outputs) {
+            let proof = self.task_dir(&task_id)?.join(PROOF_FILE_NAME);
+            if !tokio::fs::try_exists(&proof).await.unwrap_or(false) {
+                return Err(ProverClientError::TaskInvalid(format!("No proof found for local prover task {task_id}")));
+            }
+            let program_output =
+                program_output.iter().map(|word| format!("{:#x}", Felt252::from_bytes_be(word))).collect();
+            tasks.push(AggregatedTask { task_id, proof, program_output });
+        }
+        Ok(AggregatorInput { tasks })
+    }
+}
+
+impl LocalProver {
+    fn is_running(&self, task_id: &str) -> bool {
+        self.running.lock().expect("Local prover running tasks lock poisoned").contains(task_id)
+    }
+
+    fn set_running(&self, task_id: &str, running: bool) {
+        let mut tasks = self.running.lock().expect("Local prover running tasks lock poisoned");
+        if running {
+            tasks.insert(task_id.to_string());
+        } else {
+            tasks.remove(task_id);
+        }
+    }
+
+    /// Runs the prover, or the aggregator, for a task once a permit is available and records its outcome.
+    async fn run(&self, task_id: String, task_dir: PathBuf, aggregation: bool) {
+        let outcome = match self.permits.acquire().await {
+            Ok(_permit) if aggregation => self.run_aggregator(&task_dir).await,
+            Ok(_permit) => self.run_prover(&task_dir).await,
+            Err(e) => ProverRunOutcome::Failed { reason: format!("Local prover is shut down: {e}") },
+        };
+        match &outcome {
+            ProverRunOutcome::Succeeded => {
+                tracing::info!(category = "local_prover", task_id = %task_id, "Prover run succeeded.")
+            }
+            ProverRunOutcome::Failed { reason } => {
+                tracing::warn!(category = "local_prover", task_id = %task_id, %reason, "Prover run failed.")
+            }
+        }
+
+        let written = match serde_json::to_vec(&outcome) {
+            Ok(outcome) => tokio::fs::write(task_dir.join(OUTCOME_FILE_NAME), outcome).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(error) = written {
+            tracing::error!(category = "local_prover", task_id = %task_id, %error, "Failed to record the prover outcome.");
+        }
+        // The PIE is kept in the storage by the SNOS job, only the proof and the logs are worth keeping here.
+        let _ = tokio::fs::remove_file(task_dir.join(CAIRO_PIE_FILE_NAME)).await;
+        self.set_running(&task_id, false);
+    }
+
+    async fn run_prover(&self, task_dir: &Path) -> ProverRunOutcome {
+        let command = self.prover_command(task_dir);
+        match self.run_command(command, &self.args.local_prover_command, task_dir).await {
+            ProverRunOutcome::Succeeded => require_output(task_dir, PROOF_FILE_NAME, "a proof").await,
+            failed => failed,
+        }
+    }
+
+    async fn run_aggregator(&self, task_dir: &Path) -> ProverRunOutcome {
+        let Some(aggregator_command) = &self.args.local_prover_aggregator_command else {
+            return ProverRunOutcome::Failed { reason: "No aggregator command is configured".to_string() };
+        };
+        let command = self.aggregator_command(aggregator_command, task_dir);
+        match self.run_command(command, aggregator_command, task_dir).await {
+            ProverRunOutcome::Succeeded => match require_output(task_dir, PROOF_FILE_NAME, "a proof").await {
+                ProverRunOutcome::Succeeded => {
+                    require_output(task_dir, AGGREGATOR_OUTPUT_FILE_NAME, "the aggregator output").await
+                }
+                failed => failed,
+            },
+            failed => failed,
+        }
+    }
+
+    /// Runs a command in the task directory, logging its output there, until it exits or times out.
+    async fn run_command(&self, mut command: Command, program: &Path, task_dir: &Path) -> ProverRunOutcome {
+        let stdio = std::fs::File::create(task_dir.join(STDOUT_FILE_NAME))
+            .and_then(|stdout| Ok((stdout, std::fs::File::create(task_dir.join(STDERR_FILE_NAME))?)));
+        let (stdout, stderr) = match stdio {
+            Ok(stdio) => stdio,
+            Err(e) => return ProverRunOutcome::Failed { reason: format!("Failed to create the prover logs: {e}") },
+        };
+        command.current_dir(task_dir).stdin(Stdio::null()).stdout(stdout).stderr(stderr).kill_on_drop(true);
+
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                return ProverRunOutcome::Failed {
+                    reason: format!("Failed to start prover {}: {e}", program.display()),
+                }
+            }
+        };
+
+        let status = match tokio::time::timeout(self.args.local_prover_timeout, child.wait()).await {
+            Ok(Ok(status)) => status,
+            Ok(Err(e)) => return ProverRunOutcome::Failed { reason: format!("Failed to wait for the prover: {e}") },
+            Err(_) => {
+                if let Err(e) = child.kill().await {
+                    tracing::error!(category = "local_prover", error = %e, "Failed to kill the timed out prover.");
+                }
+                return ProverRunOutcome::Failed {
+                    reason: format!("Prover timed out after {}s", self.args.local_prover_timeout.as_secs()),
+                };
+            }
+        };
+
+        if !status.success() {
+            // Runs stopped by a resource limit show up as killed by a signal (SIGKILL, SIGXCPU...).
+            return ProverRunOutcome::Failed {
+                reason: format!("Prover exited with {status}: {}", stderr_tail(task_dir).await),
+            };
+        }
+        ProverRunOutcome::Succeeded
+    }
+
+    fn prover_command(&self, task_dir: &Path) -> Command {
+        let cairo_pie = task_dir.join(CAIRO_PIE_FILE_NAME);
+        let proof = task_dir.join(PROOF_FILE_NAME);
+        let prover_args = self.args.local_prover_args.iter().map(|arg| {
+            arg.replace(CAIRO_PIE_PLACEHOLDER, &cairo_pie.to_string_lossy())
+                .replace(PROOF_PLACEHOLDER, &proof.to_string_lossy())
+                .replace(LAYOUT_PLACEHOLDER, self.proof_layout.to_str())
+        });
+        self.limited_command(&self.args.local_prover_command, prover_args)
+    }
+
+    fn aggregator_command(&self, aggregator_command: &Path, task_dir: &Path) -> Command {
+        let input = task_dir.join(AGGREGATOR_INPUT_FILE_NAME);
+        let output = task_dir.join(AGGREGATOR_OUTPUT_FILE_NAME);
+        let proof = task_dir.join(PROOF_FILE_NAME);
+        let aggregator_args = self.args.local_prover_aggregator_args.iter().map(|arg| {
+            arg.replace(AGGREGATOR_INPUT_PLACEHOLDER, &input.to_string_lossy())
+                .replace(AGGREGATOR_OUTPUT_PLACEHOLDER, &output.to_string_lossy())
+                .replace(PROOF_PLACEHOLDER, &proof.to_string_lossy())
+                .replace(LAYOUT_PLACEHOLDER, self.proof_layout.to_str())
+        });
+        self.limited_command(aggregator_command, aggregator_args)
+    }
+
+    /// Builds the command of a run. Resource limits are applied by a `sh` wrapper setting them with `ulimit`
+    /// before replacing itself with the program.
+    fn limited_command(&self, program: &Path, args: impl Iterator<Item = String>) -> Command {
+        let mut limits = Vec::new();
+        if let Some(max_memory_mb) = self.args.local_prover_max_memory_mb {
+            limits.push(format!("ulimit -v {}", max_memory_mb * 1024));
+        }
+        if let Some(max_cpu_seconds) = self.args.local_prover_max_cpu_seconds {
+            limits.push(format!("ulimit -t {max_cpu_seconds}"));
+        }
+        if limits.is_empty() {
+            let mut command = Command::new(program);
+            command.args(args);
+            return command;
+        }
+
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(format!("{} && exec \"$0\" \"$@\"", limits.join(" && "))).arg(program).args(args);
+        command
+    }
+}
+
+/// Checks that a run which exited successfully wrote one of its outputs.
+async fn require_output(task_dir: &Path, file_name: &str, description: &str) -> ProverRunOutcome {
+    match tokio::fs::metadata(task_dir.join(file_name)).await {
+        Ok(metadata) if metadata.len() > 0 => ProverRunOutcome::Succeeded,
+        _ => ProverRunOutcome::Failed { reason: format!("Prover exited successfully without writing {description}") },
+    }
+}
+
+/// Returns the end of the prover stderr, where the cause of a failure usually is.
+async fn stderr_tail(task_dir: &Path) -> String {
+    match tokio::fs::read(task_dir.join(STDERR_FILE_NAME)).await {
+        Ok(stderr) => {
+            let tail = &stderr[stderr.len().saturating_sub(STDERR_TAIL_LEN)..];
+            String::from_utf8_lossy(tail).trim().to_string()
+        }
+        Err(e) => format!("stderr unavailable ({e})"),
+    }
+}
+
+#[async_trait]
+impl ProverClient for LocalProverService {
+    #[tracing::instrument(skip(self, task))]
+    async fn submit_task(&self, task: Task, _n_steps: Option<usize>) -> Result<String, ProverClientError> {
+        tracing::info!(
+            log_type = "starting",
+            category = "submit_task",
+            function_type = "cairo_pie",
+            "Submitting Cairo PIE task."
+        );
+        match task {
+            Task::CairoPie(cairo_pie) => {
+                let task_id = Uuid::new_v4().to_string();
+                let task_dir = self.task_dir(&task_id)?;
+                tokio::fs::create_dir_all(&task_dir).await.map_err(LocalProverError::TaskDirectory)?;
+                cairo_pie
+                    .write_zip_file(&task_dir.join(CAIRO_PIE_FILE_NAME))
+                    .map_err(|e| ProverClientError::FailedToWriteFile(e.to_string()))?;
+
+                self.spawn_run(&task_id, task_dir, false);
+                Ok(task_id)
+            }
+            Task::Aggregation(aggregation_task) => {
+                if self.inner.args.local_prover_aggregator_command.is_none() {
+                    return Err(ProverClientError::UnsupportedTask(
+                        "no aggregator command is configured for the local prover".to_string(),
+                    ));
+                }
+                let aggregator_input = self.aggregator_input(aggregation_task).await?;
+                let task_id = Uuid::new_v4().to_string();
+                let task_dir = self.task_dir(&task_id)?;
+                tokio::fs::create_dir_all(&task_dir).await.map_err(LocalProverError::TaskDirectory)?;
+                let aggregator_input = serde_json::to_vec(&aggregator_input)
+                    .map_err(|e| ProverClientError::FailedToWriteFile(e.to_string()))?;
+                tokio::fs::write(task_dir.join(AGGREGATOR_INPUT_FILE_NAME), aggregator_input)
+                    .await
+                    .map_err(|e| ProverClientError::FailedToWriteFile(e.to_string()))?;
+
+                self.spawn_run(&task_id, task_dir, true);
+                Ok(task_id)
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_task_status(
+        &self,
+        task_id: &str,
+        _fact: Option<String>,
+        cross_verify: bool,
+    ) -> Result<TaskStatus, ProverClientError> {
+        let task_dir = self.task_dir(task_id)?;
+        if cross_verify {
+            tracing::debug!("Skipping cross-verification as local proofs are not registered on chain");
+        }
+
+        match tokio::fs::read(task_dir.join(OUTCOME_FILE_NAME)).await {
+            Ok(outcome) => {
+                let outcome: ProverRunOutcome = serde_json::from_slice(&outcome)
+                    .map_err(|e| LocalProverError::OutcomeParse(task_id.to_string(), e))?;
+                Ok(outcome.into())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                if self.inner.is_running(task_id) {
+                    Ok(TaskStatus::Processing)
+                } else if tokio::fs::try_exists(&task_dir).await.unwrap_or(false) {
+                    // The orchestrator stopped while the prover was running.
+                    Ok(TaskStatus::Failed("Prover run was interrupted before completing".to_string()))
+                } else {
+                    Err(ProverClientError::TaskInvalid(format!("Unknown local prover task {task_id}")))
+                }
+            }
+            Err(e) => Err(LocalProverError::OutcomeRead(task_id.to_string(), e).into()),
+        }
+    }
+
+    async fn get_proof(&self, task_id: &str) -> Result<Option<Vec<u8>>, ProverClientError> {
+        let task_dir = self.task_dir(task_id)?;
+        let proof = tokio::fs::read(task_dir.join(PROOF_FILE_NAME))
+            .await
+            .map_err(|e| LocalProverError::ProofRead(task_id.to_string(), e))?;
+        Ok(Some(proof))
+    }
+
+    /// Reads the program output written by the aggregator, a JSON array of hex encoded felts.
+    async fn get_aggregator_output(&self, task_id: &str) -> Result<Vec<[u8; 32]>, ProverClientError> {
+        let task_dir = self.task_dir(task_id)?;
+        let output = tokio::fs::read(task_dir.join(AGGREGATOR_OUTPUT_FILE_NAME))
+            .await
+            .map_err(|e| LocalProverError::AggregatorOutputRead(task_id.to_string(), e))?;
+        let output: Vec<String> = serde_json::from_slice(&output)
+            .map_err(|e| LocalProverError::AggregatorOutputParse(task_id.to_string(), e.to_string()))?;
+        output
+            .iter()
+            .map(|felt| {
+                Felt252::from_hex(felt).map(|felt| felt.to_bytes_be()).map_err(|e| {
+                    LocalProverError::AggregatorOutputParse(task_id.to_string(), format!("invalid felt {felt}: {e}"))
+                        .into()
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use orchestrator_prover_client_interface::TaskStatus;
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a prover run, written to the task directory once the prover exited so that it outlives the
+/// orchestrator process.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ProverRunOutcome {
+    Succeeded,
+    Failed { reason: String },
+}
+
+impl From<ProverRunOutcome> for TaskStatus {
+    fn from(outcome: ProverRunOutcome) -> Self {
+        match outcome {
+            ProverRunOutcome::Succeeded => TaskStatus::Succeeded,
+            ProverRunOutcome::Failed { reason } => TaskStatus::Failed(reason),
+        }
+    }
+}
+
+/// Input of the aggregator, written to the task directory of an aggregation task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregatorInput {
+    /// Aggregated tasks, in the order their outputs are aggregated.
+    pub tasks: Vec<AggregatedTask>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregatedTask {
+    pub task_id: String,
+    /// Path of the proof of the task.
+    pub proof: PathBuf,
+    /// Program output of the task, as hex encoded felts.
+    pub program_output: Vec<String>,
+}
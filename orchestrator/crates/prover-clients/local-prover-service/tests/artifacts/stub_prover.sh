@@ -0,0 +1,29 @@
+#!/bin/sh
+# Stands in for a prover binary: `stub_prover.sh <mode> <cairo_pie> <proof> <layout>`, or for an aggregator:
+# `stub_prover.sh aggregate <aggregator_input> <proof> <layout> <aggregator_output>`.
+set -e
+mode="$1"
+cairo_pie="$2"
+proof="$3"
+layout="$4"
+
+case "$mode" in
+  prove)
+    test -s "$cairo_pie"
+    echo "{\"layout\": \"$layout\", \"proof\": \"stub\"}" > "$proof"
+    ;;
+  fail)
+    echo "Invalid Cairo PIE" >&2
+    exit 3
+    ;;
+  hang)
+    sleep 30
+    ;;
+  no_proof)
+    ;;
+  aggregate)
+    test -s "$cairo_pie"
+    echo "{\"layout\": \"$layout\", \"proof\": \"aggregated\"}" > "$proof"
+    echo '["0x1", "0x2a"]' > "$5"
+    ;;
+esac
@@ -0,0 +1,9 @@
+use std::time::Duration;
+
+// Shared with the atlantic service tests.
+pub const CAIRO_PIE_PATH: &str = "/../atlantic-service/tests/artifacts/fibonacci.zip";
+pub const STUB_PROVER_PATH: &str = "/tests/artifacts/stub_prover.sh";
+
+// Poll for the task status until the prover exited or the timeout is reached
+pub const MAX_RETRIES: u32 = 200;
+pub const RETRY_DELAY: Duration = Duration::from_millis(50);
@@ -0,0 +1,191 @@
+use std::path::Path;
+use std::time::Duration;
+
+use cairo_vm::types::layout_name::LayoutName;
+use cairo_vm::vm::runners::cairo_pie::CairoPie;
+use orchestrator_local_prover_service::{LocalProverService, LocalProverValidatedArgs};
+use orchestrator_prover_client_interface::{AggregationTask, ProverClient, ProverClientError, Task, TaskStatus};
+use rstest::rstest;
+
+use crate::constants::{CAIRO_PIE_PATH, MAX_RETRIES, RETRY_DELAY, STUB_PROVER_PATH};
+mod constants;
+
+/// Runs the stub prover in `mode` through `sh`, so that the script does not need to be executable.
+fn local_prover_args(work_dir: &Path, mode: &str) -> LocalProverValidatedArgs {
+    LocalProverValidatedArgs {
+        local_prover_command: "sh".into(),
+        local_prover_args: vec![
+            env!("CARGO_MANIFEST_DIR").to_string() + STUB_PROVER_PATH,
+            mode.to_string(),
+            "{cairo_pie}".to_string(),
+            "{proof}".to_string(),
+            "{layout}".to_string(),
+        ],
+        local_prover_work_dir: work_dir.to_path_buf(),
+        local_prover_timeout: Duration::from_secs(10),
+        local_prover_max_memory_mb: None,
+        local_prover_max_cpu_seconds: None,
+        local_prover_max_concurrent_runs: 1,
+        local_prover_aggregator_command: Some("sh".into()),
+        local_prover_aggregator_args: vec![
+            env!("CARGO_MANIFEST_DIR").to_string() + STUB_PROVER_PATH,
+            "aggregate".to_string(),
+            "{aggregator_input}".to_string(),
+            "{proof}".to_string(),
+            "{layout}".to_string(),
+            "{aggregator_output}".to_string(),
+        ],
+    }
+}
+
+fn cairo_pie_task() -> Task {
+    let cairo_pie_path = env!("CARGO_MANIFEST_DIR").to_string() + CAIRO_PIE_PATH;
+    let cairo_pie = CairoPie::read_zip_file(cairo_pie_path.as_ref()).expect("failed to read cairo pie zip");
+    Task::CairoPie(Box::new(cairo_pie))
+}
+
+async fn wait_for_task(prover: &LocalProverService, task_id: &str) -> TaskStatus {
+    for _ in 0..MAX_RETRIES {
+        match prover.get_task_status(task_id, None, false).await.unwrap() {
+            TaskStatus::Processing => tokio::time::sleep(RETRY_DELAY).await,
+            status => return status,
+        }
+    }
+    panic!("Local prover task {task_id} did not complete in time");
+}
+
+#[rstest]
+#[tokio::test]
+async fn local_prover_proves_cairo_pie() {
+    let work_dir = tempfile::tempdir().unwrap();
+    let prover = LocalProverService::new_with_args(&local_prover_args(work_dir.path(), "prove"), &LayoutName::dynamic);
+
+    let task_id = prover.submit_task(cairo_pie_task(), None).await.unwrap();
+
+    assert_eq!(wait_for_task(&prover, &task_id).await, TaskStatus::Succeeded);
+    let proof = prover.get_proof(&task_id).await.unwrap().unwrap();
+    assert_eq!(String::from_utf8(proof).unwrap().trim(), r#"{"layout": "dynamic", "proof": "stub"}"#);
+    // The PIE is removed once proven.
+    assert!(!work_dir.path().join(&task_id).join("cairo_pie.zip").exists());
+}
+
+#[rstest]
+#[case::prover_error("fail", "Invalid Cairo PIE")]
+#[case::missing_proof("no_proof", "without writing a proof")]
+#[tokio::test]
+async fn local_prover_reports_failures(#[case] mode: &str, #[case] expected_reason: &str) {
+    let work_dir = tempfile::tempdir().unwrap();
+    let prover = LocalProverService::new_with_args(&local_prover_args(work_dir.path(), mode), &LayoutName::dynamic);
+
+    let task_id = prover.submit_task(cairo_pie_task(), None).await.unwrap();
+
+    match wait_for_task(&prover, &task_id).await {
+        TaskStatus::Failed(reason) => assert!(reason.contains(expected_reason), "unexpected reason: {reason}"),
+        status => panic!("Expected the task to fail, got {status:?}"),
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn local_prover_kills_prover_on_timeout() {
+    let work_dir = tempfile::tempdir().unwrap();
+    let mut args = local_prover_args(work_dir.path(), "hang");
+    args.local_prover_timeout = Duration::from_millis(500);
+    let prover = LocalProverService::new_with_args(&args, &LayoutName::dynamic);
+
+    let task_id = prover.submit_task(cairo_pie_task(), None).await.unwrap();
+
+    match wait_for_task(&prover, &task_id).await {
+        TaskStatus::Failed(reason) => assert!(reason.contains("timed out"), "unexpected reason: {reason}"),
+        status => panic!("Expected the task to time out, got {status:?}"),
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn local_prover_applies_resource_limits() {
+    let work_dir = tempfile::tempdir().unwrap();
+    let mut args = local_prover_args(work_dir.path(), "prove");
+    args.local_prover_max_memory_mb = Some(1024);
+    args.local_prover_max_cpu_seconds = Some(60);
+    let prover = LocalProverService::new_with_args(&args, &LayoutName::dynamic);
+
+    let task_id = prover.submit_task(cairo_pie_task(), None).await.unwrap();
+
+    assert_eq!(wait_for_task(&prover, &task_id).await, TaskStatus::Succeeded);
+}
+
+#[rstest]
+#[tokio::test]
+async fn local_prover_outcome_survives_restart() {
+    let work_dir = tempfile::tempdir().unwrap();
+    let args = local_prover_args(work_dir.path(), "prove");
+    let prover = LocalProverService::new_with_args(&args, &LayoutName::dynamic);
+    let task_id = prover.submit_task(cairo_pie_task(), None).await.unwrap();
+    assert_eq!(wait_for_task(&prover, &task_id).await, TaskStatus::Succeeded);
+
+    let restarted_prover = LocalProverService::new_with_args(&args, &LayoutName::dynamic);
+
+    assert_eq!(restarted_prover.get_task_status(&task_id, None, true).await.unwrap(), TaskStatus::Succeeded);
+    assert!(restarted_prover.get_proof(&task_id).await.unwrap().is_some());
+}
+
+#[rstest]
+#[tokio::test]
+async fn local_prover_rejects_unknown_tasks() {
+    let work_dir = tempfile::tempdir().unwrap();
+    let prover = LocalProverService::new_with_args(&local_prover_args(work_dir.path(), "prove"), &LayoutName::dynamic);
+
+    let unknown_task = prover.get_task_status("0b6f2b6e-8c4b-4e5c-9f52-4a1b6e1c7d10", None, false).await;
+    assert!(matches!(unknown_task, Err(ProverClientError::TaskInvalid(_))));
+    let invalid_task = prover.get_task_status("../../etc", None, false).await;
+    assert!(matches!(invalid_task, Err(ProverClientError::InvalidJobKey(_))));
+}
+
+#[rstest]
+#[tokio::test]
+async fn local_prover_aggregates_proven_tasks() {
+    let work_dir = tempfile::tempdir().unwrap();
+    let prover = LocalProverService::new_with_args(&local_prover_args(work_dir.path(), "prove"), &LayoutName::dynamic);
+    let first_task = prover.submit_task(cairo_pie_task(), None).await.unwrap();
+    let second_task = prover.submit_task(cairo_pie_task(), None).await.unwrap();
+    assert_eq!(wait_for_task(&prover, &first_task).await, TaskStatus::Succeeded);
+    assert_eq!(wait_for_task(&prover, &second_task).await, TaskStatus::Succeeded);
+
+    let aggregation = AggregationTask {
+        task_ids: vec![first_task.clone(), second_task],
+        program_outputs: vec![vec![[1; 32]], vec![[2; 32]]],
+    };
+    let task_id = prover.submit_task(Task::Aggregation(aggregation), None).await.unwrap();
+
+    assert_eq!(wait_for_task(&prover, &task_id).await, TaskStatus::Succeeded);
+    let mut expected_output = vec![[0u8; 32]; 2];
+    expected_output[0][31] = 1;
+    expected_output[1][31] = 42;
+    assert_eq!(prover.get_aggregator_output(&task_id).await.unwrap(), expected_output);
+    let proof = prover.get_proof(&task_id).await.unwrap().unwrap();
+    assert_eq!(String::from_utf8(proof).unwrap().trim(), r#"{"layout": "dynamic", "proof": "aggregated"}"#);
+    let aggregator_input =
+        std::fs::read_to_string(work_dir.path().join(&task_id).join("aggregator_input.json")).unwrap();
+    assert!(aggregator_input.contains(&first_task));
+}
+
+#[rstest]
+#[tokio::test]
+async fn local_prover_rejects_invalid_aggregations() {
+    let work_dir = tempfile::tempdir().unwrap();
+    let mut args = local_prover_args(work_dir.path(), "prove");
+    let prover = LocalProverService::new_with_args(&args, &LayoutName::dynamic);
+
+    let unproven_task = AggregationTask {
+        task_ids: vec!["0b6f2b6e-8c4b-4e5c-9f52-4a1b6e1c7d10".to_string()],
+        program_outputs: vec![vec![[1; 32]]],
+    };
+    let result = prover.submit_task(Task::Aggregation(unproven_task.clone()), None).await;
+    assert!(matches!(result, Err(ProverClientError::TaskInvalid(_))));
+
+    args.local_prover_aggregator_command = None;
+    let prover = LocalProverService::new_with_args(&args, &LayoutName::dynamic);
+    let result = prover.submit_task(Task::Aggregation(unproven_task), None).await;
+    assert!(matches!(result, Err(ProverClientError::UnsupportedTask(_))));
+}
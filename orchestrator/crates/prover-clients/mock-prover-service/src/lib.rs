@@ -0,0 +1,95 @@
+//! Prover client for dev mode: no proof is generated, tasks succeed as soon as they are submitted.
+
+use alloy_primitives::{keccak256, B256};
+use async_trait::async_trait;
+use cairo_vm::types::builtin_name::BuiltinName;
+use cairo_vm::types::relocatable::MaybeRelocatable;
+use cairo_vm::vm::runners::cairo_pie::CairoPie;
+use orchestrator_prover_client_interface::{ProverClient, ProverClientError, Task, TaskStatus};
+
+/// Stands in for a proving service when iterating locally on DA and settlement. The id of a task is a fake fact,
+/// the keccak hash of the program output of its PIE, so that the same block always gets the same id.
+///
+/// Nothing is registered on chain: the state updates must be settled through a core contract which does not
+/// check the facts, like the dev core contract deployed by the bootstrapper.
+#[derive(Debug, Default, Clone)]
+pub struct MockProverService;
+
+impl MockProverService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the fake fact of a PIE, the keccak hash of its program output.
+    pub fn fake_fact(cairo_pie: &CairoPie) -> Result<B256, ProverClientError> {
+        let segment = cairo_pie
+            .metadata
+            .builtin_segments
+            .get(&BuiltinName::output)
+            .ok_or_else(|| ProverClientError::TaskInvalid("Cairo PIE has no output segment".to_string()))?;
+
+        let mut output = vec![[0u8; 32]; segment.size];
+        for ((index, offset), value) in cairo_pie.memory.0.iter() {
+            if *index != segment.index as usize {
+                continue;
+            }
+            match (value, output.get_mut(*offset)) {
+                (MaybeRelocatable::Int(felt), Some(word)) => *word = felt.to_bytes_be(),
+                _ => {
+                    return Err(ProverClientError::TaskInvalid(format!(
+                        "Cairo PIE has an invalid output at offset {offset}"
+                    )))
+                }
+            }
+        }
+        Ok(keccak256(output.concat()))
+    }
+}
+
+#[async_trait]
+impl ProverClient for MockProverService {
+    #[tracing::instrument(skip(self, task))]
+    async fn submit_task(&self, task: Task, _n_steps: Option<usize>) -> Result<String, ProverClientError> {
+        match task {
+            Task::CairoPie(cairo_pie) => {
+                let fact = Self::fake_fact(&cairo_pie)?;
+                tracing::info!(
+                    log_type = "completed",
+                    category = "submit_task",
+                    function_type = "cairo_pie",
+                    fact = %fact,
+                    "Mock prover accepted the Cairo PIE task."
+                );
+                Ok(fact.to_string())
+            }
+            Task::Aggregation(_) => Err(ProverClientError::UnsupportedTask(
+                "the mock prover cannot compute the output of the aggregator program".to_string(),
+            )),
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_task_status(
+        &self,
+        task_id: &str,
+        _fact: Option<String>,
+        cross_verify: bool,
+    ) -> Result<TaskStatus, ProverClientError> {
+        task_id.parse::<B256>().map_err(|e| ProverClientError::InvalidJobKey(e.to_string()))?;
+        if cross_verify {
+            tracing::debug!("Skipping cross-verification as the mock prover does not register facts");
+        }
+        Ok(TaskStatus::Succeeded)
+    }
+
+    /// The mock prover generates no proof.
+    async fn get_proof(&self, _task_id: &str) -> Result<Option<Vec<u8>>, ProverClientError> {
+        Ok(None)
+    }
+
+    async fn get_aggregator_output(&self, _task_id: &str) -> Result<Vec<[u8; 32]>, ProverClientError> {
+        Err(ProverClientError::UnsupportedTask(
+            "the mock prover cannot compute the output of the aggregator program".to_string(),
+        ))
+    }
+}
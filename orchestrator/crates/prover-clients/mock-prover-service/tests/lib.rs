@@ -0,0 +1,48 @@
+use cairo_vm::vm::runners::cairo_pie::CairoPie;
+use orchestrator_mock_prover_service::MockProverService;
+use orchestrator_prover_client_interface::{ProverClient, ProverClientError, Task, TaskStatus};
+use rstest::rstest;
+
+// Shared with the orchestrator tests.
+const CAIRO_PIE_PATH: &str = "/../../../src/tests/artifacts/fibonacci.zip";
+
+fn cairo_pie() -> CairoPie {
+    let cairo_pie_path = env!("CARGO_MANIFEST_DIR").to_string() + CAIRO_PIE_PATH;
+    CairoPie::read_zip_file(cairo_pie_path.as_ref()).expect("failed to read cairo pie zip")
+}
+
+#[rstest]
+#[tokio::test]
+async fn mock_prover_task_succeeds_immediately() {
+    let prover = MockProverService::new();
+
+    let task_id = prover.submit_task(Task::CairoPie(Box::new(cairo_pie())), None).await.unwrap();
+
+    assert_eq!(
+        prover.get_task_status(&task_id, Some("0x1234".to_string()), true).await.unwrap(),
+        TaskStatus::Succeeded
+    );
+    assert_eq!(prover.get_proof(&task_id).await.unwrap(), None);
+}
+
+#[rstest]
+#[tokio::test]
+async fn mock_prover_task_id_is_deterministic() {
+    let prover = MockProverService::new();
+
+    let first_task_id = prover.submit_task(Task::CairoPie(Box::new(cairo_pie())), None).await.unwrap();
+    let second_task_id = prover.submit_task(Task::CairoPie(Box::new(cairo_pie())), None).await.unwrap();
+
+    assert_eq!(first_task_id, second_task_id);
+    assert_eq!(first_task_id, MockProverService::fake_fact(&cairo_pie()).unwrap().to_string());
+}
+
+#[rstest]
+#[tokio::test]
+async fn mock_prover_rejects_invalid_task_id() {
+    let prover = MockProverService::new();
+
+    let status = prover.get_task_status("not-a-fact", None, false).await;
+
+    assert!(matches!(status, Err(ProverClientError::InvalidJobKey(_))));
+}
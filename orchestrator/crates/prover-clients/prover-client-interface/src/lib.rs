@@ -21,10 +21,25 @@ pub trait ProverClient: Send + Sync {
         fact: Option<String>,
         cross_verify: bool,
     ) -> Result<TaskStatus, ProverClientError>;
+    /// Returns the proof of a succeeded task, or `None` when the proving service does not hand out proofs.
+    async fn get_proof(&self, task_id: &str) -> Result<Option<Vec<u8>>, ProverClientError>;
+    /// Returns the program output of a succeeded aggregation task, which is settled in place of the outputs of
+    /// the aggregated blocks.
+    async fn get_aggregator_output(&self, task_id: &str) -> Result<Vec<[u8; 32]>, ProverClientError>;
 }
 
 pub enum Task {
     CairoPie(Box<CairoPie>),
+    /// Aggregates the proofs of succeeded tasks into a single proof
+    Aggregation(AggregationTask),
+}
+
+/// Input of the aggregator program: the tasks proving each block of a batch, in block order, along with the
+/// program output of each block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregationTask {
+    pub task_ids: Vec<String>,
+    pub program_outputs: Vec<Vec<[u8; 32]>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -52,4 +67,6 @@ pub enum ProverClientError {
     FailedToCreateTempFile(String),
     #[error("Failed to write file: {0}")]
     FailedToWriteFile(String),
+    #[error("Task is not supported by this prover: {0}")]
+    UnsupportedTask(String),
 }
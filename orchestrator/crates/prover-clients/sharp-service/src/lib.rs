@@ -60,6 +60,9 @@ impl ProverClient for SharpProverService {
                 );
                 Ok(job_key.to_string())
             }
+            Task::Aggregation(_) => {
+                Err(ProverClientError::UnsupportedTask("proof aggregation is not available on SHARP".to_string()))
+            }
         }
     }
 
@@ -153,6 +156,15 @@ impl ProverClient for SharpProverService {
             },
         }
     }
+
+    /// Proofs stay with SHARP: only their facts are registered on chain.
+    async fn get_proof(&self, _task_id: &str) -> Result<Option<Vec<u8>>, ProverClientError> {
+        Ok(None)
+    }
+
+    async fn get_aggregator_output(&self, _task_id: &str) -> Result<Vec<[u8; 32]>, ProverClientError> {
+        Err(ProverClientError::UnsupportedTask("proof aggregation is not available on SHARP".to_string()))
+    }
 }
 
 impl SharpProverService {
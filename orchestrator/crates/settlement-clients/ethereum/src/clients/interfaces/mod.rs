@@ -1 +1,2 @@
 pub mod validity_interface;
+pub mod validium_interface;
@@ -108,20 +108,9 @@ where
         onchain_data_hash: U256,
         onchain_data_size: U256,
     ) -> Result<TransactionReceipt, StarknetValidityContractError> {
-        let base_fee = self.as_ref().provider().as_ref().get_gas_price().await?;
-        let from_address = self.as_ref().provider().as_ref().get_accounts().await?[0];
-        let gas = self
-            .as_ref()
-            .updateState(program_output.clone(), onchain_data_hash, onchain_data_size)
-            .from(from_address)
-            .estimate_gas()
-            .await?;
-        let builder = self.as_ref().updateState(program_output, onchain_data_hash, onchain_data_size);
-        builder
-            .from(from_address)
-            .nonce(2)
-            .gas(gas)
-            .gas_price(base_fee)
+        // Gas, fees, nonce and signature are filled in by the wallet provider.
+        self.as_ref()
+            .updateState(program_output, onchain_data_hash, onchain_data_size)
             .send()
             .await?
             .get_receipt()
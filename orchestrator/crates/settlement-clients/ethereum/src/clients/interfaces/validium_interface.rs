@@ -0,0 +1,14 @@
+use alloy::sol;
+
+// Update path of core contracts supporting validium chains: the state update carries the signatures of the data
+// availability committee over the hash of the state diff, which the core contract checks against its committee
+// within the same transaction. Experimental: neither the Starknet core contract nor the contracts deployed by the
+// bootstrapper implement this interface.
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface StarknetValidiumContract {
+        function dataAvailabilityCommittee() external view returns (address);
+        function updateStateValidium(uint256[] calldata programOutput, uint256 dataHash, uint256 dataSize, bytes calldata availabilityProofs) external;
+    }
+}
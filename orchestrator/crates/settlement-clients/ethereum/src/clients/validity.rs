@@ -26,6 +26,9 @@ impl StarknetValidityContractClient {
     pub fn contract_address(&self) -> Address {
         *self.core_contract.address()
     }
+    pub fn provider(&self) -> &Arc<LocalWalletSignerMiddleware> {
+        self.core_contract.provider()
+    }
 }
 
 impl
@@ -3,12 +3,9 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
-use alloy::consensus::{
-    BlobTransactionSidecar, SignableTransaction, TxEip4844, TxEip4844Variant, TxEip4844WithSidecar, TxEnvelope,
-};
-#[cfg(not(feature = "testing"))]
-use alloy::eips::eip2718::Encodable2718;
-use alloy::eips::eip2930::AccessList;
+use alloy::consensus::BlobTransactionSidecar;
+#[cfg(feature = "testing")]
+use alloy::consensus::TxEnvelope;
 use alloy::eips::eip4844::BYTES_PER_BLOB;
 use alloy::hex;
 use alloy::network::EthereumWallet;
@@ -16,6 +13,7 @@ use alloy::primitives::{Address, Bytes, B256, U256};
 use alloy::providers::{Provider, ProviderBuilder};
 use alloy::rpc::types::TransactionReceipt;
 use alloy::signers::local::PrivateKeySigner;
+use alloy::sol_types::SolCall;
 use async_trait::async_trait;
 use c_kzg::{Blob, Bytes32, KzgCommitment, KzgProof, KzgSettings};
 use color_eyre::eyre::{bail, Ok};
@@ -26,12 +24,15 @@ use orchestrator_settlement_client_interface::{SettlementClient, SettlementVerif
 use orchestrator_utils::env_utils::get_env_var_or_panic;
 use url::Url;
 
-use crate::clients::interfaces::validity_interface::StarknetValidityContractTrait;
+use crate::clients::interfaces::validity_interface::{StarknetValidityContract, StarknetValidityContractTrait};
+use crate::clients::interfaces::validium_interface::StarknetValidiumContract;
 use crate::clients::StarknetValidityContractClient;
 use crate::conversion::{slice_u8_to_u256, vec_u8_32_to_vec_u256};
+use crate::tx_manager::{InFlightTxStore, TxManager, TxManagerConfig, TxRequest};
 pub mod clients;
 pub mod conversion;
 pub mod tests;
+pub mod tx_manager;
 pub mod types;
 use alloy::providers::RootProvider;
 use alloy::transports::http::Http;
@@ -52,6 +53,12 @@ const Y_HIGH_POINT_OFFSET: usize = Y_LOW_POINT_OFFSET + 1;
 const MAX_TX_FINALISATION_ATTEMPTS: usize = 30;
 const REQUIRED_BLOCK_CONFIRMATIONS: u64 = 3;
 const TX_WAIT_SLEEP_DELAY_SECS: u64 = 60;
+const DEV_TX_WAIT_SLEEP_DELAY_SECS: u64 = 1;
+// Receipts of the transactions in flight are polled once per slot
+const TX_RECEIPT_POLL_INTERVAL_SECS: u64 = 12;
+
+// Keeps the calldata DA transactions well below the 128kB transaction size limit of the mempools.
+const MAX_FELTS_PER_CALLDATA_TX: usize = 3_500;
 
 lazy_static! {
     pub static ref PROJECT_ROOT: PathBuf = PathBuf::from(format!("{}/../../../", env!("CARGO_MANIFEST_DIR")));
@@ -70,6 +77,13 @@ pub struct EthereumSettlementValidatedArgs {
     pub l1_core_contract_address: Address,
 
     pub starknet_operator_address: Address,
+
+    /// Settles on the dev core contract of a local L1, which accepts state updates without proof. Transactions
+    /// are final once included, as a local L1 only mines blocks when it receives transactions.
+    pub dev_mode: bool,
+
+    /// Fee bumping and fee budget of the transactions sent to the settlement layer.
+    pub tx_manager_config: TxManagerConfig,
 }
 
 #[allow(dead_code)]
@@ -78,11 +92,14 @@ pub struct EthereumSettlementClient {
     wallet: EthereumWallet,
     wallet_address: Address,
     provider: Arc<RootProvider<Http<Client>>>,
-    impersonate_account: Option<Address>,
+    tx_manager: TxManager,
+    required_block_confirmations: u64,
+    tx_wait_sleep_delay: Duration,
 }
 
 impl EthereumSettlementClient {
-    pub fn new_with_args(settlement_cfg: &EthereumSettlementValidatedArgs) -> Self {
+    /// The transactions in flight are persisted in `tx_store`, so that a restarted orchestrator resumes them.
+    pub fn new_with_args(settlement_cfg: &EthereumSettlementValidatedArgs, tx_store: Arc<dyn InFlightTxStore>) -> Self {
         let private_key = settlement_cfg.ethereum_private_key.clone();
         let signer: PrivateKeySigner = private_key.parse().expect("Failed to parse private key");
         let wallet_address = signer.address();
@@ -102,7 +119,32 @@ impl EthereumSettlementClient {
         let core_contract_client =
             StarknetValidityContractClient::new(settlement_cfg.l1_core_contract_address, filler_provider);
 
-        EthereumSettlementClient { provider, core_contract_client, wallet, wallet_address, impersonate_account: None }
+        let tx_manager = TxManager::new(
+            provider.clone(),
+            wallet.clone(),
+            wallet_address,
+            settlement_cfg.tx_manager_config.clone(),
+            tx_store,
+            Duration::from_secs(if settlement_cfg.dev_mode {
+                DEV_TX_WAIT_SLEEP_DELAY_SECS
+            } else {
+                TX_RECEIPT_POLL_INTERVAL_SECS
+            }),
+        );
+
+        EthereumSettlementClient {
+            provider,
+            core_contract_client,
+            wallet,
+            wallet_address,
+            tx_manager,
+            required_block_confirmations: if settlement_cfg.dev_mode { 0 } else { REQUIRED_BLOCK_CONFIRMATIONS },
+            tx_wait_sleep_delay: Duration::from_secs(if settlement_cfg.dev_mode {
+                DEV_TX_WAIT_SLEEP_DELAY_SECS
+            } else {
+                TX_WAIT_SLEEP_DELAY_SECS
+            }),
+        }
     }
 
     #[cfg(feature = "testing")]
@@ -111,6 +153,8 @@ impl EthereumSettlementClient {
         core_contract_address: Address,
         rpc_url: Url,
         impersonate_account: Option<Address>,
+        tx_manager_config: TxManagerConfig,
+        tx_store: Arc<dyn InFlightTxStore>,
     ) -> Self {
         let private_key = get_env_var_or_panic(ENV_PRIVATE_KEY);
         let signer: PrivateKeySigner = private_key.parse().expect("Failed to parse private key");
@@ -122,12 +166,25 @@ impl EthereumSettlementClient {
 
         let core_contract_client = StarknetValidityContractClient::new(core_contract_address, fill_provider);
 
+        let provider = Arc::new(provider);
+        let tx_manager = TxManager::new(
+            provider.clone(),
+            wallet.clone(),
+            wallet_address,
+            tx_manager_config,
+            tx_store,
+            Duration::from_secs(TX_RECEIPT_POLL_INTERVAL_SECS),
+        )
+        .with_impersonated_account(impersonate_account);
+
         EthereumSettlementClient {
-            provider: Arc::new(provider),
+            provider,
             core_contract_client,
             wallet,
             wallet_address,
-            impersonate_account,
+            tx_manager,
+            required_block_confirmations: REQUIRED_BLOCK_CONFIRMATIONS,
+            tx_wait_sleep_delay: Duration::from_secs(TX_WAIT_SLEEP_DELAY_SECS),
         }
     }
 
@@ -169,53 +226,14 @@ impl EthereumSettlementClient {
         }
         Ok(kzg_proof)
     }
-}
 
-#[automock]
-#[async_trait]
-impl SettlementClient for EthereumSettlementClient {
-    /// Should register the proof on the base layer and return an external id
-    /// which can be used to track the status.
-    #[allow(unused)]
-    async fn register_proof(&self, proof: [u8; 32]) -> Result<String> {
-        todo!("register_proof is not implemented yet")
-    }
-
-    /// Should be used to update state on core contract when DA is done in calldata
-    async fn update_state_calldata(
-        &self,
-        _snos_output: Vec<[u8; 32]>,
-        program_output: Vec<[u8; 32]>,
-        onchain_data_hash: [u8; 32],
-        onchain_data_size: [u8; 32],
-    ) -> Result<String> {
-        tracing::info!(
-            log_type = "starting",
-            category = "update_state",
-            function_type = "calldata",
-            "Updating state with calldata."
-        );
-        let program_output: Vec<U256> = vec_u8_32_to_vec_u256(program_output.as_slice())?;
-        let onchain_data_hash: U256 = slice_u8_to_u256(&onchain_data_hash)?;
-        let onchain_data_size = U256::from_be_bytes(onchain_data_size);
-        let tx_receipt =
-            self.core_contract_client.update_state(program_output, onchain_data_hash, onchain_data_size).await?;
-        tracing::info!(
-            log_type = "completed",
-            category = "update_state",
-            function_type = "calldata",
-            tx_hash = %tx_receipt.transaction_hash,
-            "State updated with calldata."
-        );
-        Ok(format!("0x{:x}", tx_receipt.transaction_hash))
-    }
-
-    /// Should be used to update state on core contract when DA is in blobs/alt DA
-    async fn update_state_with_blobs(
+    /// Sends the state update along with the blobs of the state diff. When the blobs were already published,
+    /// `published_blob_hashes` holds their versioned hashes, which must be the ones of the attached blobs.
+    async fn send_state_update_with_blobs(
         &self,
         program_output: Vec<[u8; 32]>,
         state_diff: Vec<Vec<u8>>,
-        _nonce: u64,
+        published_blob_hashes: &[[u8; 32]],
     ) -> Result<String> {
         tracing::info!(
             log_type = "starting",
@@ -225,11 +243,17 @@ impl SettlementClient for EthereumSettlementClient {
         );
         let (sidecar_blobs, sidecar_commitments, sidecar_proofs) = prepare_sidecar(&state_diff, &KZG_SETTINGS).await?;
         let sidecar = BlobTransactionSidecar::new(sidecar_blobs, sidecar_commitments, sidecar_proofs);
-
-        let eip1559_est = self.provider.estimate_eip1559_fees(None).await?;
-        let chain_id: u64 = self.provider.get_chain_id().await?.to_string().parse()?;
-
-        let max_fee_per_blob_gas: u128 = self.provider.get_blob_base_fee().await?.to_string().parse()?;
+        if !published_blob_hashes.is_empty() {
+            let versioned_hashes: Vec<[u8; 32]> = sidecar.versioned_hashes().map(|hash| hash.0).collect();
+            if versioned_hashes != published_blob_hashes {
+                bail!(
+                    "The blobs of the state diff do not match the published blobs: expected versioned hashes {:?}, \
+                     got {:?}",
+                    published_blob_hashes.iter().map(hex::encode).collect::<Vec<_>>(),
+                    versioned_hashes.iter().map(hex::encode).collect::<Vec<_>>()
+                );
+            }
+        }
 
         // calculating y_0 point
         let y_0 = Bytes32::from(
@@ -249,71 +273,187 @@ impl SettlementClient for EthereumSettlementClient {
 
         let input_bytes = get_input_data_for_eip_4844(program_output, kzg_proof)?;
 
-        let nonce = self.provider.get_transaction_count(self.wallet_address).await?.to_string().parse()?;
-
-        // add a safety margin to the gas price to handle fluctuations
-        let add_safety_margin = |n: u128, div_factor: u128| n + n / div_factor;
-
-        let max_fee_per_gas: u128 = eip1559_est.max_fee_per_gas.to_string().parse()?;
-        let max_priority_fee_per_gas: u128 = eip1559_est.max_priority_fee_per_gas.to_string().parse()?;
-
-        let tx: TxEip4844 = TxEip4844 {
-            chain_id,
-            nonce,
-            // we noticed Starknet uses the same limit on mainnet
-            // https://etherscan.io/tx/0x8a58b936faaefb63ee1371991337ae3b99d74cb3504d73868615bf21fa2f25a1
-            gas_limit: 5_500_000,
-            max_fee_per_gas: add_safety_margin(max_fee_per_gas, 5),
-            max_priority_fee_per_gas: add_safety_margin(max_priority_fee_per_gas, 5),
+        // Gas, fees and nonce are handled by the transaction manager, which bumps the fees until the transaction
+        // is included
+        let tx_request = TxRequest {
             to: self.core_contract_client.contract_address(),
-            value: U256::from(0),
-            access_list: AccessList(vec![]),
-            blob_versioned_hashes: sidecar.versioned_hashes().collect(),
-            max_fee_per_blob_gas: add_safety_margin(max_fee_per_blob_gas, 5),
             input: Bytes::from(hex::decode(input_bytes)?),
+            sidecar: Some(sidecar),
         };
-
-        let tx_sidecar = TxEip4844WithSidecar { tx: tx.clone(), sidecar: sidecar.clone() };
-
-        let mut variant = TxEip4844Variant::from(tx_sidecar);
-        let signature = self.wallet.default_signer().sign_transaction(&mut variant).await?;
-        let tx_signed = variant.into_signed(signature);
-        let tx_envelope: TxEnvelope = tx_signed.into();
-
-        #[cfg(feature = "testing")]
-        let pending_transaction = {
-            let txn_request = {
-                test_config::configure_transaction(self.provider.clone(), tx_envelope, self.impersonate_account).await
-            };
-            self.provider.send_transaction(txn_request).await?
-        };
-
-        #[cfg(not(feature = "testing"))]
-        let pending_transaction = {
-            let encoded = tx_envelope.encoded_2718();
-            self.provider.send_raw_transaction(encoded.as_slice()).await?
-        };
+        let tx_receipt = self.tx_manager.send(tx_request).await?;
+        let tx_hash = tx_receipt.transaction_hash.to_string();
 
         tracing::info!(
             log_type = "completed",
             category = "update_state",
             function_type = "blobs",
+            tx_hash = %tx_hash,
             "State updated with blobs."
         );
 
         log::warn!("⏳ Waiting for txn finality.......");
 
-        let res = self.wait_for_tx_finality(&pending_transaction.tx_hash().to_string()).await?;
+        let res = self.wait_for_tx_finality(&tx_hash).await?;
 
         match res {
             Some(_) => {
-                log::info!("Txn hash : {:?} Finalized ✅", pending_transaction.tx_hash().to_string());
+                log::info!("Txn hash : {:?} Finalized ✅", tx_hash);
             }
             None => {
                 log::error!("Txn hash not finalised");
             }
         }
-        Ok(pending_transaction.tx_hash().to_string())
+        Ok(tx_hash)
+    }
+}
+
+#[automock]
+#[async_trait]
+impl SettlementClient for EthereumSettlementClient {
+    /// Should register the proof on the base layer and return an external id
+    /// which can be used to track the status.
+    #[allow(unused)]
+    async fn register_proof(&self, proof: [u8; 32]) -> Result<String> {
+        todo!("register_proof is not implemented yet")
+    }
+
+    /// Should be used to update state on core contract when DA is done in calldata
+    async fn update_state_calldata(
+        &self,
+        _snos_output: Vec<[u8; 32]>,
+        program_output: Vec<[u8; 32]>,
+        onchain_data_hash: [u8; 32],
+        onchain_data_size: [u8; 32],
+    ) -> Result<String> {
+        tracing::info!(
+            log_type = "starting",
+            category = "update_state",
+            function_type = "calldata",
+            "Updating state with calldata."
+        );
+        let program_output: Vec<U256> = vec_u8_32_to_vec_u256(program_output.as_slice())?;
+        let onchain_data_hash: U256 = slice_u8_to_u256(&onchain_data_hash)?;
+        let onchain_data_size = U256::from_be_bytes(onchain_data_size);
+        let input = StarknetValidityContract::updateStateCall {
+            programOutput: program_output,
+            onchainDataHash: onchain_data_hash,
+            onchainDataSize: onchain_data_size,
+        }
+        .abi_encode();
+        let tx_receipt = self
+            .tx_manager
+            .send(TxRequest { to: self.core_contract_client.contract_address(), input: input.into(), sidecar: None })
+            .await?;
+        tracing::info!(
+            log_type = "completed",
+            category = "update_state",
+            function_type = "calldata",
+            tx_hash = %tx_receipt.transaction_hash,
+            "State updated with calldata."
+        );
+        Ok(format!("0x{:x}", tx_receipt.transaction_hash))
+    }
+
+    /// The state diff is sent in chunks as calldata of transactions from the operator to itself, each chunk is
+    /// awaited until it is included.
+    async fn publish_state_diff_calldata(
+        &self,
+        state_diff: Vec<[u8; 32]>,
+        chunk_index: usize,
+    ) -> Result<Option<String>> {
+        let Some(chunk) = state_diff.chunks(MAX_FELTS_PER_CALLDATA_TX).nth(chunk_index) else {
+            return Ok(None);
+        };
+        tracing::info!(
+            log_type = "starting",
+            category = "update_state",
+            function_type = "calldata_da",
+            felt_count = chunk.len(),
+            chunk_index = chunk_index,
+            "Publishing state diff chunk as calldata."
+        );
+        let tx_request = TxRequest { to: self.wallet_address, input: Bytes::from(chunk.concat()), sidecar: None };
+        let tx_receipt = self.tx_manager.send(tx_request).await?;
+        if !tx_receipt.status() {
+            bail!("State diff calldata tx 0x{:x} reverted", tx_receipt.transaction_hash);
+        }
+        let tx_hash = format!("0x{:x}", tx_receipt.transaction_hash);
+        tracing::info!(
+            log_type = "completed",
+            category = "update_state",
+            function_type = "calldata_da",
+            tx_hash = %tx_hash,
+            chunk_index = chunk_index,
+            "State diff chunk published as calldata."
+        );
+        Ok(Some(tx_hash))
+    }
+
+    /// Only core contracts exposing their data availability committee accept validium state updates, the
+    /// attestation is checked against that committee in the state update transaction.
+    async fn update_state_with_dac_attestation(
+        &self,
+        program_output: Vec<[u8; 32]>,
+        data_hash: [u8; 32],
+        data_size: [u8; 32],
+        attestation: Vec<u8>,
+    ) -> Result<String> {
+        let core_contract_address = self.core_contract_client.contract_address();
+        let core_contract = StarknetValidiumContract::new(core_contract_address, self.provider.as_ref());
+        let committee = core_contract.dataAvailabilityCommittee().call().await.wrap_err_with(|| {
+            format!("The core contract at {core_contract_address} does not support validium state updates")
+        })?;
+        tracing::info!(
+            log_type = "starting",
+            category = "update_state",
+            function_type = "dac_attestation",
+            data_hash = %B256::from(data_hash),
+            committee = %committee._0,
+            "Updating state with DAC attestation."
+        );
+        let input = StarknetValidiumContract::updateStateValidiumCall {
+            programOutput: vec_u8_32_to_vec_u256(program_output.as_slice())?,
+            dataHash: U256::from_be_bytes(data_hash),
+            dataSize: U256::from_be_bytes(data_size),
+            availabilityProofs: Bytes::from(attestation),
+        }
+        .abi_encode();
+        let tx_receipt =
+            self.tx_manager.send(TxRequest { to: core_contract_address, input: input.into(), sidecar: None }).await?;
+        if !tx_receipt.status() {
+            bail!("Validium state update tx 0x{:x} reverted", tx_receipt.transaction_hash);
+        }
+        tracing::info!(
+            log_type = "completed",
+            category = "update_state",
+            function_type = "dac_attestation",
+            tx_hash = %tx_receipt.transaction_hash,
+            "State updated with DAC attestation."
+        );
+        Ok(format!("0x{:x}", tx_receipt.transaction_hash))
+    }
+
+    /// Should be used to update state on core contract when DA is in blobs/alt DA
+    async fn update_state_with_blobs(
+        &self,
+        program_output: Vec<[u8; 32]>,
+        state_diff: Vec<Vec<u8>>,
+        _nonce: u64,
+    ) -> Result<String> {
+        self.send_state_update_with_blobs(program_output, state_diff, &[]).await
+    }
+
+    /// The core contract checks the blobs through the versioned hashes of the state update transaction itself, so
+    /// the blobs are attached to it again. They are checked against the versioned hashes of the published blobs
+    /// first, so that the state update settles the blobs made available by the DA client.
+    async fn update_state_with_published_blobs(
+        &self,
+        program_output: Vec<[u8; 32]>,
+        state_diff: Vec<Vec<u8>>,
+        blob_versioned_hashes: Vec<[u8; 32]>,
+        _nonce: u64,
+    ) -> Result<String> {
+        self.send_state_update_with_blobs(program_output, state_diff, &blob_versioned_hashes).await
     }
 
     /// Should verify the inclusion of a tx in the settlement layer
@@ -371,12 +511,12 @@ impl SettlementClient for EthereumSettlementClient {
                 if let Some(block_number) = receipt.block_number {
                     let latest_block = self.provider.get_block_number().await?;
                     let confirmations = latest_block.saturating_sub(block_number);
-                    if confirmations >= REQUIRED_BLOCK_CONFIRMATIONS {
+                    if confirmations >= self.required_block_confirmations {
                         return Ok(Some(block_number));
                     }
                 }
             }
-            sleep(Duration::from_secs(TX_WAIT_SLEEP_DELAY_SECS)).await;
+            sleep(self.tx_wait_sleep_delay).await;
         }
         Ok(None)
     }
@@ -409,13 +549,11 @@ mod test_config {
 
     use super::*;
 
-    #[allow(dead_code)]
-    pub async fn configure_transaction(
-        provider: Arc<RootProvider<Http<Client>>>,
-        tx_envelope: TxEnvelope,
-        impersonate_account: Option<Address>,
-    ) -> TransactionRequest {
-        let mut txn_request: TransactionRequest = tx_envelope.into();
+    /// Turns the signed transaction of the transaction manager into a request sent as the impersonated account of
+    /// an Anvil node, if any. The nonce picked by the transaction manager, which tracks the nonce of the
+    /// impersonated account, is kept so that the fee bumps replace the transaction.
+    pub fn configure_transaction(tx_envelope: TxEnvelope, impersonate_account: Option<Address>) -> TransactionRequest {
+        let txn_request: TransactionRequest = tx_envelope.into();
 
         // IMPORTANT to understand #[cfg(test)], #[cfg(not(test))] and SHOULD_IMPERSONATE_ACCOUNT
         // Two tests :  `update_state_blob_with_dummy_contract_works` &
@@ -426,13 +564,9 @@ mod test_config {
         //      - if "1" then : Testing via impersonating `Starknet Operator Address`.
         // Note : changing between "0" and "1" is handled automatically by each test function, `no` manual
         // change in `env.test` is needed.
-        if let Some(impersonate_account) = impersonate_account {
-            let nonce =
-                provider.get_transaction_count(impersonate_account).await.unwrap().to_string().parse::<u64>().unwrap();
-            txn_request.set_nonce(nonce);
-            txn_request = txn_request.with_from(impersonate_account);
+        match impersonate_account {
+            Some(impersonate_account) => txn_request.with_from(impersonate_account),
+            None => txn_request,
         }
-
-        txn_request
     }
 }
@@ -123,6 +123,7 @@ mod settlement_client_tests {
     use std::fs::File;
     use std::io::{BufRead, BufReader};
     use std::str::FromStr;
+    use std::sync::Arc;
     use std::time::Duration;
 
     use alloy::consensus::Transaction;
@@ -143,9 +144,20 @@ mod settlement_client_tests {
         DummyCoreContract, EthereumTestBuilder, Pipe, CURRENT_PATH, MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS,
         STARKNET_CORE_CONTRACT, STARKNET_CORE_CONTRACT_ADDRESS,
     };
+    use crate::tx_manager::{InFlightTxStore, MockInFlightTxStore, TxManagerConfig};
     use crate::types::{bytes_be_to_u128, convert_stark_bigint_to_u256};
     use crate::{EthereumSettlementClient, EthereumSettlementValidatedArgs, Y_HIGH_POINT_OFFSET, Y_LOW_POINT_OFFSET};
 
+    /// Store of the transaction manager, the transactions of a test are never resumed.
+    fn in_flight_tx_store() -> Arc<dyn InFlightTxStore> {
+        let mut store = MockInFlightTxStore::new();
+        store.expect_get_in_flight_tx().returning(|_, _| Ok(None));
+        store.expect_save_in_flight_tx().returning(|_| Ok(()));
+        store.expect_remove_in_flight_tx().returning(|_, _| Ok(()));
+        store.expect_prune_in_flight_txs().returning(|_, _| Ok(()));
+        Arc::new(store)
+    }
+
     #[rstest]
     #[tokio::test]
     /// Tests if the method is able to do a transaction with same function selector on a dummy
@@ -170,6 +182,8 @@ mod settlement_client_tests {
                 "MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS",
             ))
             .expect("Invalid Starknet operator address"),
+            dev_mode: false,
+            tx_manager_config: TxManagerConfig::default(),
         };
 
         // Deploying a dummy contract
@@ -179,6 +193,8 @@ mod settlement_client_tests {
             *contract.address(),
             ethereum_settlement_params.ethereum_rpc_url,
             None,
+            ethereum_settlement_params.tx_manager_config,
+            in_flight_tx_store(),
         );
 
         // Getting latest nonce after deployment
@@ -248,6 +264,8 @@ mod settlement_client_tests {
                 "MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS",
             ))
             .expect("Invalid Starknet operator address"),
+            dev_mode: false,
+            tx_manager_config: TxManagerConfig::default(),
         };
 
         let ethereum_settlement_client = EthereumSettlementClient::with_test_params(
@@ -255,6 +273,8 @@ mod settlement_client_tests {
             ethereum_settlement_params.l1_core_contract_address,
             ethereum_settlement_params.ethereum_rpc_url,
             Some(ethereum_settlement_params.starknet_operator_address),
+            ethereum_settlement_params.tx_manager_config,
+            in_flight_tx_store(),
         );
 
         // let nonce = ethereum_settlement_client.get_nonce().await.expect("Unable to fetch nonce");
@@ -320,6 +340,8 @@ mod settlement_client_tests {
                 "MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS",
             ))
             .expect("Invalid Starknet operator address"),
+            dev_mode: false,
+            tx_manager_config: TxManagerConfig::default(),
         };
 
         let ethereum_settlement_client = EthereumSettlementClient::with_test_params(
@@ -327,6 +349,8 @@ mod settlement_client_tests {
             ethereum_settlement_params.l1_core_contract_address,
             ethereum_settlement_params.ethereum_rpc_url,
             None,
+            ethereum_settlement_params.tx_manager_config,
+            in_flight_tx_store(),
         );
         assert_eq!(
             ethereum_settlement_client.get_last_settled_block().await.expect("Could not get last settled block."),
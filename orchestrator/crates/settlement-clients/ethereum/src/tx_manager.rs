@@ -0,0 +1,766 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use alloy::consensus::{
+    BlobTransactionSidecar, SignableTransaction, TxEip1559, TxEip4844, TxEip4844Variant, TxEip4844WithSidecar,
+    TxEnvelope,
+};
+#[cfg(not(feature = "testing"))]
+use alloy::eips::eip2718::Encodable2718;
+use alloy::eips::eip2930::AccessList;
+use alloy::eips::eip4844::DATA_GAS_PER_BLOB;
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::primitives::{keccak256, Address, Bytes, TxKind, B256, U256};
+use alloy::providers::{Provider, RootProvider};
+use alloy::rpc::types::{TransactionReceipt, TransactionRequest};
+use alloy::transports::http::Http;
+use async_trait::async_trait;
+use color_eyre::eyre::{bail, WrapErr};
+use color_eyre::Result;
+use lazy_static::lazy_static;
+use mockall::automock;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+pub const DEFAULT_FEE_BUMP_TIMEOUT_SECS: u64 = 180;
+pub const DEFAULT_FEE_BUMP_PERCENT: u64 = 20;
+
+// Mempools only replace a transaction when its fees are bumped by 10%, and the blob pool of geth only replaces a
+// blob transaction when all of its fees are doubled.
+const MIN_FEE_BUMP_PERCENT: u64 = 10;
+const MIN_BLOB_FEE_BUMP_PERCENT: u64 = 100;
+// Margin over the estimated gas and fees of a transaction when it is first sent
+const GAS_LIMIT_MARGIN_PERCENT: u64 = 20;
+const FEE_MARGIN_PERCENT: u64 = 20;
+// Number of fee bump timeouts after which sending a transaction fails. It stays in flight, and sending the same
+// transaction again resumes it with a new allowance of fee bumps.
+const MAX_FEE_BUMPS: u32 = 10;
+
+lazy_static! {
+    // Send locks of the senders, shared by all the transaction managers of the orchestrator so that the ones
+    // sending from the same account, such as the DA and the settlement clients, never compete for a nonce.
+    static ref SEND_LOCKS: StdMutex<HashMap<Address, Arc<Mutex<()>>>> = StdMutex::new(HashMap::new());
+}
+
+fn send_lock(sender: Address) -> Arc<Mutex<()>> {
+    SEND_LOCKS.lock().expect("Poisoned lock").entry(sender).or_default().clone()
+}
+
+/// Parameters of the transaction manager of the Ethereum settlement client.
+#[derive(Clone, Debug)]
+pub struct TxManagerConfig {
+    /// Time after which a transaction that is still not included is re-broadcast with bumped fees.
+    pub fee_bump_timeout: Duration,
+
+    /// Percentage by which the fees of a transaction are bumped. Replacements need a bump of at least 10%, and of
+    /// 100% for blob transactions.
+    pub fee_bump_percent: u64,
+
+    /// Maximum fee a transaction may cost, in wei. Transactions estimated above it are not sent, and fees are not
+    /// bumped beyond it.
+    pub max_tx_fee_wei: Option<u128>,
+}
+
+impl Default for TxManagerConfig {
+    fn default() -> Self {
+        Self {
+            fee_bump_timeout: Duration::from_secs(DEFAULT_FEE_BUMP_TIMEOUT_SECS),
+            fee_bump_percent: DEFAULT_FEE_BUMP_PERCENT,
+            max_tx_fee_wei: None,
+        }
+    }
+}
+
+/// A transaction to send from the operator wallet, with its blobs for blob transactions.
+pub struct TxRequest {
+    pub to: Address,
+    pub input: Bytes,
+    pub sidecar: Option<BlobTransactionSidecar>,
+}
+
+impl TxRequest {
+    /// Identifies the request across re-broadcasts and restarts, so that sending it again resumes the transaction
+    /// in flight instead of sending a new one.
+    fn hash(&self) -> B256 {
+        let mut preimage = self.to.to_vec();
+        preimage.extend_from_slice(&self.input);
+        if let Some(sidecar) = &self.sidecar {
+            sidecar.versioned_hashes().for_each(|hash| preimage.extend_from_slice(hash.as_slice()));
+        }
+        keccak256(preimage)
+    }
+
+    fn blob_count(&self) -> u64 {
+        self.sidecar.as_ref().map_or(0, |sidecar| sidecar.blobs.len() as u64)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxFees {
+    #[serde(with = "fee_as_string")]
+    pub max_fee_per_gas: u128,
+    #[serde(with = "fee_as_string")]
+    pub max_priority_fee_per_gas: u128,
+    #[serde(with = "fee_as_string::option")]
+    pub max_fee_per_blob_gas: Option<u128>,
+}
+
+impl TxFees {
+    /// Bumps each fee by `percent`, or up to the current fee estimate if it is higher.
+    fn bump(&self, percent: u64, estimate: &TxFees) -> TxFees {
+        let bump = |fee: u128, estimated: u128| (fee + fee * percent as u128 / 100).max(estimated);
+        TxFees {
+            max_fee_per_gas: bump(self.max_fee_per_gas, estimate.max_fee_per_gas),
+            max_priority_fee_per_gas: bump(self.max_priority_fee_per_gas, estimate.max_priority_fee_per_gas),
+            max_fee_per_blob_gas: self
+                .max_fee_per_blob_gas
+                .map(|fee| bump(fee, estimate.max_fee_per_blob_gas.unwrap_or_default())),
+        }
+    }
+
+    /// The most a transaction with these fees can cost.
+    fn max_cost(&self, gas_limit: u64, blob_count: u64) -> u128 {
+        gas_limit as u128 * self.max_fee_per_gas
+            + (blob_count * DATA_GAS_PER_BLOB) as u128 * self.max_fee_per_blob_gas.unwrap_or_default()
+    }
+}
+
+/// A transaction sent and not included yet. It keeps the hashes of all of its broadcasts, as any of them can be
+/// included.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InFlightTx {
+    pub sender: Address,
+    pub request_hash: B256,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub fees: TxFees,
+    pub tx_hashes: Vec<B256>,
+    pub fee_bumps: u32,
+    /// Unix timestamp of the last broadcast, in seconds.
+    pub last_broadcast: u64,
+}
+
+/// Persists the in-flight transactions of each sender, so that a restarted orchestrator resumes them.
+#[automock]
+#[async_trait]
+pub trait InFlightTxStore: Send + Sync {
+    /// Returns the in-flight transaction of the sender for the request with the given hash.
+    async fn get_in_flight_tx(&self, sender: Address, request_hash: B256) -> Result<Option<InFlightTx>>;
+
+    /// Inserts the transaction, or replaces the one with the same sender and nonce.
+    async fn save_in_flight_tx(&self, tx: &InFlightTx) -> Result<()>;
+
+    /// Removes the transaction of the sender with the given nonce, once it is included.
+    async fn remove_in_flight_tx(&self, sender: Address, nonce: u64) -> Result<()>;
+
+    /// Drops the transactions of the sender whose nonce was used, they were either included or replaced by
+    /// another transaction.
+    async fn prune_in_flight_txs(&self, sender: Address, next_nonce: u64) -> Result<()>;
+}
+
+/// Sends the transactions of the operator wallet and sees them through to inclusion: it estimates their gas,
+/// re-broadcasts them with bumped fees when they are not included in time, within the max fee budget, and
+/// persists them while in flight.
+pub struct TxManager {
+    provider: Arc<RootProvider<Http<Client>>>,
+    wallet: EthereumWallet,
+    sender: Address,
+    #[cfg(feature = "testing")]
+    impersonate_account: Option<Address>,
+    config: TxManagerConfig,
+    store: Arc<dyn InFlightTxStore>,
+    poll_interval: Duration,
+    // Transactions of the sender are sent one at a time, so that they never compete for a nonce
+    send_lock: Arc<Mutex<()>>,
+}
+
+impl TxManager {
+    pub fn new(
+        provider: Arc<RootProvider<Http<Client>>>,
+        wallet: EthereumWallet,
+        sender: Address,
+        config: TxManagerConfig,
+        store: Arc<dyn InFlightTxStore>,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            provider,
+            wallet,
+            sender,
+            #[cfg(feature = "testing")]
+            impersonate_account: None,
+            config,
+            store,
+            poll_interval,
+            send_lock: send_lock(sender),
+        }
+    }
+
+    /// Sends transactions as the impersonated account of an Anvil node, which signs them.
+    #[cfg(feature = "testing")]
+    pub fn with_impersonated_account(mut self, impersonate_account: Option<Address>) -> Self {
+        if let Some(account) = impersonate_account {
+            self.sender = account;
+            self.send_lock = send_lock(account);
+        }
+        self.impersonate_account = impersonate_account;
+        self
+    }
+
+    /// Sends the transaction, or resumes it if it is already in flight, and waits until it is included.
+    pub async fn send(&self, request: TxRequest) -> Result<TransactionReceipt> {
+        let _guard = self.send_lock.lock().await;
+        let request_hash = request.hash();
+        let chain_id: u64 = self.provider.get_chain_id().await?.to_string().parse()?;
+        let next_nonce = self.next_nonce().await?;
+
+        let mut tx = match self.store.get_in_flight_tx(self.sender, request_hash).await? {
+            Some(mut tx) => {
+                tracing::info!(nonce = tx.nonce, tx_hashes = ?tx.tx_hashes, "Resuming in-flight transaction");
+                // The fee bumps are counted per send, the resumed transaction keeps being bumped while pending
+                tx.fee_bumps = 0;
+                if let Some(receipt) = self.find_receipt(&tx).await? {
+                    self.store.remove_in_flight_tx(self.sender, tx.nonce).await?;
+                    return Ok(receipt);
+                }
+                if tx.nonce < next_nonce {
+                    self.store.remove_in_flight_tx(self.sender, tx.nonce).await?;
+                    bail!("Nonce {} of the in-flight transaction was used by another transaction", tx.nonce);
+                }
+                // The transaction may have been dropped from the mempool while the orchestrator was down
+                if let Err(e) = self.broadcast(&request, chain_id, &mut tx).await {
+                    tracing::warn!(nonce = tx.nonce, error = %e, "Failed to re-broadcast the in-flight transaction");
+                }
+                tx
+            }
+            None => {
+                self.store.prune_in_flight_txs(self.sender, next_nonce).await?;
+                let gas_limit = self.estimate_gas(&request).await?;
+                let fees = self.estimate_fees(&request).await?;
+                self.check_budget(&fees, gas_limit, &request)?;
+                let mut tx = InFlightTx {
+                    sender: self.sender,
+                    request_hash,
+                    nonce: next_nonce,
+                    gas_limit,
+                    fees,
+                    tx_hashes: Vec::new(),
+                    fee_bumps: 0,
+                    last_broadcast: 0,
+                };
+                self.broadcast(&request, chain_id, &mut tx).await?;
+                tx
+            }
+        };
+
+        loop {
+            sleep(self.poll_interval).await;
+            if let Some(receipt) = self.find_receipt(&tx).await? {
+                self.store.remove_in_flight_tx(self.sender, tx.nonce).await?;
+                tracing::info!(nonce = tx.nonce, tx_hash = %receipt.transaction_hash, "Transaction included");
+                return Ok(receipt);
+            }
+            if self.next_nonce().await? > tx.nonce {
+                // The nonce was used, one of the broadcasts may have been included since the receipts were fetched
+                if let Some(receipt) = self.find_receipt(&tx).await? {
+                    self.store.remove_in_flight_tx(self.sender, tx.nonce).await?;
+                    return Ok(receipt);
+                }
+                self.store.remove_in_flight_tx(self.sender, tx.nonce).await?;
+                bail!("Nonce {} of the in-flight transaction was used by another transaction", tx.nonce);
+            }
+            if unix_timestamp() < tx.last_broadcast + self.config.fee_bump_timeout.as_secs() {
+                continue;
+            }
+            if tx.fee_bumps >= MAX_FEE_BUMPS {
+                bail!(
+                    "Transaction with nonce {} not included after {} fee bumps, it stays in flight: {:?}",
+                    tx.nonce,
+                    tx.fee_bumps,
+                    tx.tx_hashes
+                );
+            }
+            tx.fee_bumps += 1;
+
+            let bumped_fees = tx.fees.bump(self.fee_bump_percent(&request), &self.estimate_fees(&request).await?);
+            if self.check_budget(&bumped_fees, tx.gas_limit, &request).is_err() {
+                tracing::warn!(
+                    nonce = tx.nonce,
+                    max_tx_fee_wei = ?self.config.max_tx_fee_wei,
+                    "Fee bump would exceed the max fee budget, waiting for the transaction as is"
+                );
+                tx.last_broadcast = unix_timestamp();
+                self.store.save_in_flight_tx(&tx).await?;
+                continue;
+            }
+            tracing::info!(nonce = tx.nonce, fee_bumps = tx.fee_bumps, fees = ?bumped_fees, "Bumping transaction fees");
+            let previous_fees = tx.fees;
+            tx.fees = bumped_fees;
+            if let Err(e) = self.broadcast(&request, chain_id, &mut tx).await {
+                // The previous broadcasts are still valid, they are awaited until the next bump
+                tracing::warn!(nonce = tx.nonce, error = %e, "Failed to broadcast the transaction with bumped fees");
+                tx.fees = previous_fees;
+                tx.last_broadcast = unix_timestamp();
+                self.store.save_in_flight_tx(&tx).await?;
+            }
+        }
+    }
+
+    async fn next_nonce(&self) -> Result<u64> {
+        Ok(self.provider.get_transaction_count(self.sender).await?.to_string().parse()?)
+    }
+
+    async fn find_receipt(&self, tx: &InFlightTx) -> Result<Option<TransactionReceipt>> {
+        for tx_hash in tx.tx_hashes.iter().rev() {
+            if let Some(receipt) = self.provider.get_transaction_receipt(*tx_hash).await? {
+                return Ok(Some(receipt));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn estimate_gas(&self, request: &TxRequest) -> Result<u64> {
+        let mut tx_request =
+            TransactionRequest::default().with_from(self.sender).with_to(request.to).with_input(request.input.clone());
+        if let Some(sidecar) = &request.sidecar {
+            tx_request.blob_versioned_hashes = Some(sidecar.versioned_hashes().collect());
+        }
+        let gas: u64 = self
+            .provider
+            .estimate_gas(&tx_request)
+            .await
+            .wrap_err("Failed to estimate gas, the transaction would revert")?
+            .to_string()
+            .parse()?;
+        Ok(gas + gas * GAS_LIMIT_MARGIN_PERCENT / 100)
+    }
+
+    async fn estimate_fees(&self, request: &TxRequest) -> Result<TxFees> {
+        let add_margin = |fee: u128| fee + fee * FEE_MARGIN_PERCENT as u128 / 100;
+        let eip1559_est = self.provider.estimate_eip1559_fees(None).await?;
+        let max_fee_per_blob_gas = match request.sidecar {
+            Some(_) => Some(add_margin(self.provider.get_blob_base_fee().await?.to_string().parse()?)),
+            None => None,
+        };
+        Ok(TxFees {
+            max_fee_per_gas: add_margin(eip1559_est.max_fee_per_gas.to_string().parse()?),
+            max_priority_fee_per_gas: add_margin(eip1559_est.max_priority_fee_per_gas.to_string().parse()?),
+            max_fee_per_blob_gas,
+        })
+    }
+
+    fn fee_bump_percent(&self, request: &TxRequest) -> u64 {
+        let min_bump = if request.sidecar.is_some() { MIN_BLOB_FEE_BUMP_PERCENT } else { MIN_FEE_BUMP_PERCENT };
+        self.config.fee_bump_percent.max(min_bump)
+    }
+
+    fn check_budget(&self, fees: &TxFees, gas_limit: u64, request: &TxRequest) -> Result<()> {
+        let max_cost = fees.max_cost(gas_limit, request.blob_count());
+        match self.config.max_tx_fee_wei {
+            Some(max_tx_fee_wei) if max_cost > max_tx_fee_wei => {
+                bail!("Transaction may cost up to {} wei, above the max fee of {} wei", max_cost, max_tx_fee_wei)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Signs and broadcasts the transaction with its current fees. It is persisted with its hash before being
+    /// broadcast, so that it is never lost if the orchestrator stops in between.
+    async fn broadcast(&self, request: &TxRequest, chain_id: u64, tx: &mut InFlightTx) -> Result<()> {
+        let tx_envelope = self.sign(request, chain_id, tx).await?;
+        let tx_hash = *tx_envelope.tx_hash();
+        if !tx.tx_hashes.contains(&tx_hash) {
+            tx.tx_hashes.push(tx_hash);
+        }
+        tx.last_broadcast = unix_timestamp();
+        self.store.save_in_flight_tx(tx).await?;
+
+        #[cfg(feature = "testing")]
+        let pending_transaction = {
+            let txn_request = crate::test_config::configure_transaction(tx_envelope, self.impersonate_account);
+            self.provider.send_transaction(txn_request).await?
+        };
+
+        #[cfg(not(feature = "testing"))]
+        let pending_transaction = {
+            let encoded = tx_envelope.encoded_2718();
+            self.provider.send_raw_transaction(encoded.as_slice()).await?
+        };
+
+        // An impersonated account signs the transaction itself, under another hash
+        let sent_hash = *pending_transaction.tx_hash();
+        if !tx.tx_hashes.contains(&sent_hash) {
+            tx.tx_hashes.push(sent_hash);
+            self.store.save_in_flight_tx(tx).await?;
+        }
+        tracing::info!(nonce = tx.nonce, tx_hash = %sent_hash, fees = ?tx.fees, "Transaction broadcast");
+        Ok(())
+    }
+
+    async fn sign(&self, request: &TxRequest, chain_id: u64, tx: &InFlightTx) -> Result<TxEnvelope> {
+        let signer = self.wallet.default_signer();
+        match &request.sidecar {
+            Some(sidecar) => {
+                let Some(max_fee_per_blob_gas) = tx.fees.max_fee_per_blob_gas else {
+                    bail!("Blob transaction with nonce {} has no blob fee", tx.nonce);
+                };
+                let tx_eip4844 = TxEip4844 {
+                    chain_id,
+                    nonce: tx.nonce,
+                    gas_limit: tx.gas_limit,
+                    max_fee_per_gas: tx.fees.max_fee_per_gas,
+                    max_priority_fee_per_gas: tx.fees.max_priority_fee_per_gas,
+                    to: request.to,
+                    value: U256::from(0),
+                    access_list: AccessList(vec![]),
+                    blob_versioned_hashes: sidecar.versioned_hashes().collect(),
+                    max_fee_per_blob_gas,
+                    input: request.input.clone(),
+                };
+                let mut variant =
+                    TxEip4844Variant::from(TxEip4844WithSidecar { tx: tx_eip4844, sidecar: sidecar.clone() });
+                let signature = signer.sign_transaction(&mut variant).await?;
+                Ok(variant.into_signed(signature).into())
+            }
+            None => {
+                let mut tx_eip1559 = TxEip1559 {
+                    chain_id,
+                    nonce: tx.nonce,
+                    gas_limit: tx.gas_limit,
+                    max_fee_per_gas: tx.fees.max_fee_per_gas,
+                    max_priority_fee_per_gas: tx.fees.max_priority_fee_per_gas,
+                    to: TxKind::Call(request.to),
+                    value: U256::from(0),
+                    access_list: AccessList(vec![]),
+                    input: request.input.clone(),
+                };
+                let signature = signer.sign_transaction(&mut tx_eip1559).await?;
+                Ok(tx_eip1559.into_signed(signature).into())
+            }
+        }
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default()
+}
+
+/// Fees are persisted as decimal strings, as BSON has no 128-bit integers.
+mod fee_as_string {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(fee: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&fee.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(fee: &Option<u128>, serializer: S) -> Result<S::Ok, S::Error> {
+            match fee {
+                Some(fee) => super::serialize(fee, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u128>, D::Error> {
+            Option::<String>::deserialize(deserializer)?.map(|fee| fee.parse().map_err(de::Error::custom)).transpose()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::providers::ProviderBuilder;
+    use alloy::signers::local::PrivateKeySigner;
+    use httpmock::prelude::*;
+    use rstest::*;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    // First account of Anvil
+    const PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    /// Keeps the in-flight transactions in memory, along with every saved version of them.
+    #[derive(Default)]
+    struct MemoryTxStore {
+        txs: StdMutex<Vec<InFlightTx>>,
+        saves: StdMutex<Vec<InFlightTx>>,
+    }
+
+    #[async_trait]
+    impl InFlightTxStore for MemoryTxStore {
+        async fn get_in_flight_tx(&self, sender: Address, request_hash: B256) -> Result<Option<InFlightTx>> {
+            let txs = self.txs.lock().unwrap();
+            Ok(txs.iter().find(|tx| tx.sender == sender && tx.request_hash == request_hash).cloned())
+        }
+
+        async fn save_in_flight_tx(&self, tx: &InFlightTx) -> Result<()> {
+            let mut txs = self.txs.lock().unwrap();
+            txs.retain(|saved| saved.sender != tx.sender || saved.nonce != tx.nonce);
+            txs.push(tx.clone());
+            self.saves.lock().unwrap().push(tx.clone());
+            Ok(())
+        }
+
+        async fn remove_in_flight_tx(&self, sender: Address, nonce: u64) -> Result<()> {
+            self.txs.lock().unwrap().retain(|tx| tx.sender != sender || tx.nonce != nonce);
+            Ok(())
+        }
+
+        async fn prune_in_flight_txs(&self, sender: Address, next_nonce: u64) -> Result<()> {
+            self.txs.lock().unwrap().retain(|tx| tx.sender != sender || tx.nonce >= next_nonce);
+            Ok(())
+        }
+    }
+
+    fn in_flight_tx(sender: Address, nonce: u64, request_hash: B256) -> InFlightTx {
+        InFlightTx {
+            sender,
+            request_hash,
+            nonce,
+            gas_limit: 100_000,
+            fees: TxFees { max_fee_per_gas: 10, max_priority_fee_per_gas: 1, max_fee_per_blob_gas: None },
+            tx_hashes: vec![B256::repeat_byte(nonce as u8)],
+            fee_bumps: 0,
+            last_broadcast: 1_700_000_000,
+        }
+    }
+
+    fn tx_request() -> TxRequest {
+        TxRequest { to: Address::repeat_byte(1), input: Bytes::from_static(b"input"), sidecar: None }
+    }
+
+    /// Transaction manager sending through the mocked node, which bumps the fees at each poll.
+    fn tx_manager(server: &MockServer, store: Arc<MemoryTxStore>, max_tx_fee_wei: Option<u128>) -> TxManager {
+        let provider = Arc::new(ProviderBuilder::new().on_http(server.base_url().parse().unwrap()));
+        let signer: PrivateKeySigner = PRIVATE_KEY.parse().unwrap();
+        let sender = signer.address();
+        let config = TxManagerConfig { fee_bump_timeout: Duration::ZERO, max_tx_fee_wei, ..Default::default() };
+        TxManager::new(provider, EthereumWallet::from(signer), sender, config, store, Duration::ZERO)
+    }
+
+    fn mock_rpc<'a>(server: &'a MockServer, method: &str, result: Value) -> httpmock::Mock<'a> {
+        server.mock(|when, then| {
+            when.method(POST).body_contains(format!(r#""method":"{method}""#));
+            then.status(200).json_body(json!({ "jsonrpc": "2.0", "id": 0, "result": result }));
+        })
+    }
+
+    /// Mocks the node answering the fee estimates, and accepting the broadcasts as raw or, with the `testing`
+    /// feature, as unsigned transactions.
+    fn mock_node(server: &MockServer, next_nonce: u64) -> [httpmock::Mock<'_>; 2] {
+        mock_rpc(server, "eth_chainId", json!("0x1"));
+        mock_rpc(server, "eth_getTransactionCount", json!(format!("{next_nonce:#x}")));
+        mock_rpc(server, "eth_estimateGas", json!("0x5208"));
+        mock_rpc(
+            server,
+            "eth_feeHistory",
+            json!({
+                "oldestBlock": "0x1",
+                "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00"],
+                "gasUsedRatio": [0.5],
+                "reward": [["0x3b9aca00"]],
+            }),
+        );
+        [
+            mock_rpc(server, "eth_sendRawTransaction", json!(B256::repeat_byte(0xee))),
+            mock_rpc(server, "eth_sendTransaction", json!(B256::repeat_byte(0xee))),
+        ]
+    }
+
+    fn receipt(tx_hash: B256) -> Value {
+        json!({
+            "type": "0x2",
+            "status": "0x1",
+            "cumulativeGasUsed": "0x5208",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "transactionHash": tx_hash,
+            "transactionIndex": "0x0",
+            "blockHash": B256::repeat_byte(0xbb),
+            "blockNumber": "0x10",
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x3b9aca00",
+            "from": Address::repeat_byte(2),
+            "to": Address::repeat_byte(1),
+            "contractAddress": null,
+        })
+    }
+
+    #[rstest]
+    #[case::bumped_by_percent(
+        TxFees { max_fee_per_gas: 100, max_priority_fee_per_gas: 10, max_fee_per_blob_gas: Some(50) },
+        TxFees { max_fee_per_gas: 80, max_priority_fee_per_gas: 5, max_fee_per_blob_gas: Some(40) },
+        TxFees { max_fee_per_gas: 200, max_priority_fee_per_gas: 20, max_fee_per_blob_gas: Some(100) }
+    )]
+    #[case::raised_to_estimate(
+        TxFees { max_fee_per_gas: 100, max_priority_fee_per_gas: 10, max_fee_per_blob_gas: Some(50) },
+        TxFees { max_fee_per_gas: 500, max_priority_fee_per_gas: 5, max_fee_per_blob_gas: Some(400) },
+        TxFees { max_fee_per_gas: 500, max_priority_fee_per_gas: 20, max_fee_per_blob_gas: Some(400) }
+    )]
+    #[case::without_blobs(
+        TxFees { max_fee_per_gas: 100, max_priority_fee_per_gas: 10, max_fee_per_blob_gas: None },
+        TxFees { max_fee_per_gas: 100, max_priority_fee_per_gas: 10, max_fee_per_blob_gas: None },
+        TxFees { max_fee_per_gas: 200, max_priority_fee_per_gas: 20, max_fee_per_blob_gas: None }
+    )]
+    fn tx_fees_bump(#[case] fees: TxFees, #[case] estimate: TxFees, #[case] expected: TxFees) {
+        assert_eq!(fees.bump(100, &estimate), expected);
+    }
+
+    #[rstest]
+    fn tx_fees_max_cost_includes_blob_gas() {
+        let fees = TxFees { max_fee_per_gas: 10, max_priority_fee_per_gas: 1, max_fee_per_blob_gas: Some(3) };
+        assert_eq!(fees.max_cost(21_000, 0), 210_000);
+        assert_eq!(fees.max_cost(21_000, 2), 210_000 + 2 * DATA_GAS_PER_BLOB as u128 * 3);
+    }
+
+    #[rstest]
+    fn tx_fees_are_persisted_as_strings() {
+        let fees = TxFees { max_fee_per_gas: u128::MAX, max_priority_fee_per_gas: 1, max_fee_per_blob_gas: None };
+        let value = serde_json::to_value(fees).unwrap();
+        assert_eq!(value["max_fee_per_gas"], json!(u128::MAX.to_string()));
+        assert_eq!(serde_json::from_value::<TxFees>(value).unwrap(), fees);
+    }
+
+    #[rstest]
+    fn tx_request_hash_identifies_request() {
+        let other_request =
+            TxRequest { to: Address::repeat_byte(1), input: Bytes::from_static(b"other"), sidecar: None };
+
+        assert_eq!(tx_request().hash(), tx_request().hash());
+        assert_ne!(tx_request().hash(), other_request.hash());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn send_resumes_included_in_flight_tx() {
+        let server = MockServer::start();
+        let [send_raw, send] = mock_node(&server, 6);
+        let store = Arc::new(MemoryTxStore::default());
+        let tx_manager = tx_manager(&server, store.clone(), None);
+        let tx = in_flight_tx(tx_manager.sender, 5, tx_request().hash());
+        store.save_in_flight_tx(&tx).await.unwrap();
+        mock_rpc(&server, "eth_getTransactionReceipt", receipt(tx.tx_hashes[0]));
+
+        let receipt = tx_manager.send(tx_request()).await.unwrap();
+
+        assert_eq!(receipt.transaction_hash, tx.tx_hashes[0]);
+        assert_eq!(store.get_in_flight_tx(tx.sender, tx.request_hash).await.unwrap(), None);
+        assert_eq!(send_raw.hits() + send.hits(), 0);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn send_fails_when_nonce_used_by_another_tx() {
+        let server = MockServer::start();
+        let [send_raw, send] = mock_node(&server, 6);
+        mock_rpc(&server, "eth_getTransactionReceipt", Value::Null);
+        let store = Arc::new(MemoryTxStore::default());
+        let tx_manager = tx_manager(&server, store.clone(), None);
+        let tx = in_flight_tx(tx_manager.sender, 5, tx_request().hash());
+        store.save_in_flight_tx(&tx).await.unwrap();
+
+        let err = tx_manager.send(tx_request()).await.unwrap_err();
+
+        assert!(err.to_string().contains("Nonce 5 of the in-flight transaction was used by another transaction"));
+        assert_eq!(store.get_in_flight_tx(tx.sender, tx.request_hash).await.unwrap(), None);
+        assert_eq!(send_raw.hits() + send.hits(), 0);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn send_bumps_fees_until_max_fee_bumps() {
+        let server = MockServer::start();
+        let [send_raw, send] = mock_node(&server, 5);
+        mock_rpc(&server, "eth_getTransactionReceipt", Value::Null);
+        let store = Arc::new(MemoryTxStore::default());
+        let tx_manager = tx_manager(&server, store.clone(), None);
+
+        let err = tx_manager.send(tx_request()).await.unwrap_err();
+
+        assert!(err.to_string().contains("not included after 10 fee bumps"));
+        assert_eq!(send_raw.hits() + send.hits(), MAX_FEE_BUMPS as usize + 1);
+        // Each broadcast bumps the fees of the previous one by the configured percentage
+        let mut broadcast_fees: Vec<TxFees> = store.saves.lock().unwrap().iter().map(|tx| tx.fees).collect();
+        broadcast_fees.dedup();
+        assert_eq!(broadcast_fees.len(), MAX_FEE_BUMPS as usize + 1);
+        for fees in broadcast_fees.windows(2) {
+            assert!(
+                fees[1].max_fee_per_gas >= fees[0].max_fee_per_gas * (100 + DEFAULT_FEE_BUMP_PERCENT as u128) / 100
+            );
+        }
+        // The transaction stays in flight, sending the request again resumes it
+        let tx = store.get_in_flight_tx(tx_manager.sender, tx_request().hash()).await.unwrap().unwrap();
+        assert_eq!((tx.nonce, tx.fee_bumps), (5, MAX_FEE_BUMPS));
+        assert_eq!(tx.fees, *broadcast_fees.last().unwrap());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn send_keeps_bumping_resumed_tx() {
+        let server = MockServer::start();
+        let [send_raw, send] = mock_node(&server, 5);
+        mock_rpc(&server, "eth_getTransactionReceipt", Value::Null);
+        let store = Arc::new(MemoryTxStore::default());
+        let tx_manager = tx_manager(&server, store.clone(), None);
+        // The previous send gave up on the transaction after all of its fee bumps
+        let tx = InFlightTx { fee_bumps: MAX_FEE_BUMPS, ..in_flight_tx(tx_manager.sender, 5, tx_request().hash()) };
+        store.save_in_flight_tx(&tx).await.unwrap();
+
+        let err = tx_manager.send(tx_request()).await.unwrap_err();
+
+        assert!(err.to_string().contains("not included after 10 fee bumps"));
+        // Re-broadcast on resume, then once per fee bump
+        assert_eq!(send_raw.hits() + send.hits(), MAX_FEE_BUMPS as usize + 1);
+        let resumed = store.get_in_flight_tx(tx.sender, tx.request_hash).await.unwrap().unwrap();
+        assert!(resumed.fees.max_fee_per_gas > tx.fees.max_fee_per_gas);
+    }
+
+    #[rstest]
+    fn tx_managers_of_a_sender_share_the_send_lock() {
+        let server = MockServer::start();
+        let store = Arc::new(MemoryTxStore::default());
+        let settlement_tx_manager = tx_manager(&server, store.clone(), None);
+        let da_tx_manager = tx_manager(&server, store.clone(), None);
+        let provider = Arc::new(ProviderBuilder::new().on_http(server.base_url().parse().unwrap()));
+        // Second account of Anvil
+        let other_signer: PrivateKeySigner =
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d".parse().unwrap();
+        let other_sender = other_signer.address();
+        let other_tx_manager = TxManager::new(
+            provider,
+            EthereumWallet::from(other_signer),
+            other_sender,
+            TxManagerConfig::default(),
+            store,
+            Duration::ZERO,
+        );
+
+        assert!(Arc::ptr_eq(&settlement_tx_manager.send_lock, &da_tx_manager.send_lock));
+        assert!(!Arc::ptr_eq(&settlement_tx_manager.send_lock, &other_tx_manager.send_lock));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn send_fails_when_estimate_exceeds_budget() {
+        let server = MockServer::start();
+        let [send_raw, send] = mock_node(&server, 5);
+        let store = Arc::new(MemoryTxStore::default());
+        let tx_manager = tx_manager(&server, store.clone(), Some(1));
+
+        let err = tx_manager.send(tx_request()).await.unwrap_err();
+
+        assert!(err.to_string().contains("above the max fee of 1 wei"));
+        assert_eq!(send_raw.hits() + send.hits(), 0);
+        assert!(store.saves.lock().unwrap().is_empty());
+    }
+}
@@ -29,6 +29,25 @@ pub trait SettlementClient: Send + Sync {
         onchain_data_size: [u8; 32],
     ) -> Result<String>;
 
+    /// Should publish the chunk at `chunk_index` of the encoded state diff as calldata, ahead of the state update
+    /// when DA is done in calldata. The state diff is split across as many transactions as needed, one per chunk.
+    /// Returns the hash of the transaction carrying the chunk, or `None` once all the chunks are published.
+    async fn publish_state_diff_calldata(
+        &self,
+        state_diff: Vec<[u8; 32]>,
+        chunk_index: usize,
+    ) -> Result<Option<String>>;
+
+    /// Should be used to update state on contract in validium mode, along with the attestation of a data
+    /// availability committee that it stores the state diff with the given hash and size in felts.
+    async fn update_state_with_dac_attestation(
+        &self,
+        program_output: Vec<[u8; 32]>,
+        data_hash: [u8; 32],
+        data_size: [u8; 32],
+        attestation: Vec<u8>,
+    ) -> Result<String>;
+
     /// Should be used to update state on contract and publish the blob on ethereum.
     async fn update_state_with_blobs(
         &self,
@@ -37,6 +56,16 @@ pub trait SettlementClient: Send + Sync {
         nonce: u64,
     ) -> Result<String>;
 
+    /// Should be used to update state on contract referencing the blobs already published by the DA client, whose
+    /// versioned hashes are given, instead of publishing other blobs.
+    async fn update_state_with_published_blobs(
+        &self,
+        program_output: Vec<[u8; 32]>,
+        state_diff: Vec<Vec<u8>>,
+        blob_versioned_hashes: Vec<[u8; 32]>,
+        nonce: u64,
+    ) -> Result<String>;
+
     /// Should verify the inclusion of a tx in the settlement layer
     async fn verify_tx_inclusion(&self, tx_hash: &str) -> Result<SettlementVerificationStatus>;
 
@@ -148,6 +148,26 @@ impl SettlementClient for StarknetSettlementClient {
         Ok(invoke_result.transaction_hash.to_hex_string())
     }
 
+    /// The state diff is part of the OS output given to the core contract, there is nothing to publish beforehand.
+    async fn publish_state_diff_calldata(
+        &self,
+        _state_diff: Vec<[u8; 32]>,
+        _chunk_index: usize,
+    ) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// The Starknet core contract has no data availability committee to register attestations with.
+    async fn update_state_with_dac_attestation(
+        &self,
+        _program_output: Vec<[u8; 32]>,
+        _data_hash: [u8; 32],
+        _data_size: [u8; 32],
+        _attestation: Vec<u8>,
+    ) -> Result<String> {
+        Err(eyre!("Data availability committees are not supported when settling on Starknet"))
+    }
+
     /// Should verify the inclusion of a tx in the settlement layer
     async fn verify_tx_inclusion(&self, tx_hash: &str) -> Result<SettlementVerificationStatus> {
         tracing::info!(
@@ -213,6 +233,17 @@ impl SettlementClient for StarknetSettlementClient {
         !unimplemented!("not implemented yet.")
     }
 
+    #[allow(unused)]
+    async fn update_state_with_published_blobs(
+        &self,
+        program_output: Vec<[u8; 32]>,
+        state_diff: Vec<Vec<u8>>,
+        blob_versioned_hashes: Vec<[u8; 32]>,
+        nonce: u64,
+    ) -> Result<String> {
+        !unimplemented!("not implemented yet.")
+    }
+
     /// Wait for a pending tx to achieve finality
     async fn wait_for_tx_finality(&self, tx_hash: &str) -> Result<Option<u64>> {
         let mut retries = 0;
@@ -0,0 +1,23 @@
+use clap::Args;
+use url::Url;
+
+/// Parameters used to config Celestia.
+#[derive(Debug, Clone, Args)]
+#[group(requires_all = ["celestia_da_rpc_url", "celestia_da_namespace"])]
+pub struct CelestiaDaCliArgs {
+    /// Use the Celestia DA layer.
+    #[arg(long)]
+    pub da_on_celestia: bool,
+
+    /// The RPC URL of the Celestia node.
+    #[arg(env = "MADARA_ORCHESTRATOR_CELESTIA_DA_RPC_URL", long)]
+    pub celestia_da_rpc_url: Option<Url>,
+
+    /// The auth token of the Celestia node.
+    #[arg(env = "MADARA_ORCHESTRATOR_CELESTIA_DA_AUTH_TOKEN", long)]
+    pub celestia_da_auth_token: Option<String>,
+
+    /// The namespace id the blobs are submitted to, as up to 10 hex encoded bytes.
+    #[arg(env = "MADARA_ORCHESTRATOR_CELESTIA_DA_NAMESPACE", long)]
+    pub celestia_da_namespace: Option<String>,
+}
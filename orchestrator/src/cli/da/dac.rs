@@ -0,0 +1,23 @@
+use clap::Args;
+use url::Url;
+
+/// Parameters used to config the data availability committee of a validium chain.
+#[derive(Debug, Clone, Args)]
+#[group(requires_all = ["dac_da_member_urls", "dac_da_threshold", "dac_da_auth_token"])]
+pub struct DacDaCliArgs {
+    /// Use a data availability committee.
+    #[arg(long)]
+    pub da_on_dac: bool,
+
+    /// The URLs of the committee members, comma separated.
+    #[arg(env = "MADARA_ORCHESTRATOR_DAC_DA_MEMBER_URLS", long, value_delimiter = ',')]
+    pub dac_da_member_urls: Option<Vec<Url>>,
+
+    /// The number of member signatures needed for the data to be available.
+    #[arg(env = "MADARA_ORCHESTRATOR_DAC_DA_THRESHOLD", long)]
+    pub dac_da_threshold: Option<usize>,
+
+    /// The token authenticating the orchestrator to the committee members when it sends them data to store.
+    #[arg(env = "MADARA_ORCHESTRATOR_DAC_DA_AUTH_TOKEN", long)]
+    pub dac_da_auth_token: Option<String>,
+}
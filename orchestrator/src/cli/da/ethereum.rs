@@ -12,4 +12,10 @@ pub struct EthereumDaCliArgs {
     /// The RPC URL of the Ethereum node.
     #[arg(env = "MADARA_ORCHESTRATOR_ETHEREUM_DA_RPC_URL", long)]
     pub ethereum_da_rpc_url: Option<Url>,
+
+    /// The private key of the Ethereum account sending the blob transactions. When it is not set, the blobs are
+    /// not published by the DA client but along with the state update. When it is set, the state update references
+    /// the published blobs by their versioned hashes.
+    #[arg(env = "MADARA_ORCHESTRATOR_ETHEREUM_DA_PRIVATE_KEY", long)]
+    pub ethereum_da_private_key: Option<String>,
 }
@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+/// Parameters used to config the local DA, which keeps the data on this machine.
+#[derive(Debug, Clone, Args)]
+pub struct LocalDaCliArgs {
+    /// Use the local DA layer, for tests and local setups only.
+    #[arg(long)]
+    pub da_on_local: bool,
+
+    /// The directory where the data is written. The data is kept in memory when it is not set.
+    #[arg(env = "MADARA_ORCHESTRATOR_LOCAL_DA_PATH", long)]
+    pub local_da_path: Option<PathBuf>,
+}
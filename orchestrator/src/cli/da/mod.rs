@@ -1 +1,4 @@
+pub mod celestia;
+pub mod dac;
 pub mod ethereum;
+pub mod local;
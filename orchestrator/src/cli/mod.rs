@@ -78,13 +78,13 @@ pub enum Commands {
     ),
     group(
         ArgGroup::new("prover")
-            .args(&["sharp", "atlantic"])
+            .args(&["sharp", "atlantic", "local_prover", "dev"])
             .required(true)
             .multiple(false)
     ),
     group(
         ArgGroup::new("da_layer")
-            .args(&["da_on_ethereum"])
+            .args(&["da_on_ethereum", "da_on_celestia", "da_on_dac", "da_on_local"])
             .required(true)
             .multiple(false)
     ),
@@ -118,6 +118,15 @@ pub struct RunCmd {
     #[clap(flatten)]
     pub ethereum_da_args: da::ethereum::EthereumDaCliArgs,
 
+    #[clap(flatten)]
+    pub celestia_da_args: da::celestia::CelestiaDaCliArgs,
+
+    #[clap(flatten)]
+    pub dac_da_args: da::dac::DacDaCliArgs,
+
+    #[clap(flatten)]
+    pub local_da_args: da::local::LocalDaCliArgs,
+
     #[clap(flatten)]
     pub proving_layout_args: prover_layout::ProverLayoutCliArgs,
 
@@ -135,6 +144,9 @@ pub struct RunCmd {
     #[clap(flatten)]
     pub atlantic_args: prover::atlantic::AtlanticCliArgs,
 
+    #[clap(flatten)]
+    pub local_prover_args: prover::local::LocalProverCliArgs,
+
     // SNOS
     #[clap(flatten)]
     pub snos_args: snos::SNOSCliArgs,
@@ -144,6 +156,12 @@ pub struct RunCmd {
     #[arg(env = "MADARA_ORCHESTRATOR_LAYER", long, default_value = "L2", value_enum)]
     pub layer: Layer,
 
+    /// Dev mode: the proving jobs run through the mock prover instead of a proving service, no proof registration
+    /// job is created and the state updates are settled through the dev core contract deployed by the bootstrapper,
+    /// which accepts them without proof.
+    #[arg(env = "MADARA_ORCHESTRATOR_DEV", long)]
+    pub dev: bool,
+
     // Service
     #[clap(flatten)]
     pub service_args: service::ServiceCliArgs,
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+/// Parameters used to config the local prover.
+#[derive(Debug, Clone, Args)]
+#[group(requires_all = ["local_prover_command"])]
+pub struct LocalProverCliArgs {
+    /// Use a prover binary run on the orchestrator host.
+    #[arg(long)]
+    pub local_prover: bool,
+
+    /// The prover executable (stone or stwo CLI).
+    #[arg(env = "MADARA_ORCHESTRATOR_LOCAL_PROVER_COMMAND", long)]
+    pub local_prover_command: Option<PathBuf>,
+
+    /// The prover arguments, space separated. `{cairo_pie}`, `{proof}` and `{layout}` are replaced by the path of
+    /// the Cairo PIE, the path the proof must be written to and the proof layout.
+    #[arg(
+        env = "MADARA_ORCHESTRATOR_LOCAL_PROVER_ARGS",
+        long,
+        value_delimiter = ' ',
+        default_value = "--cairo_pie {cairo_pie} --proof {proof} --layout {layout}",
+        allow_hyphen_values = true
+    )]
+    pub local_prover_args: Vec<String>,
+
+    /// The directory where the PIEs, proofs and prover logs are kept.
+    #[arg(env = "MADARA_ORCHESTRATOR_LOCAL_PROVER_WORK_DIR", long, default_value = "local_prover")]
+    pub local_prover_work_dir: PathBuf,
+
+    /// The time after which a prover run is killed, in seconds.
+    #[arg(env = "MADARA_ORCHESTRATOR_LOCAL_PROVER_TIMEOUT", long, default_value = "3600")]
+    pub local_prover_timeout: u64,
+
+    /// The virtual memory limit of a prover run, in megabytes.
+    #[arg(env = "MADARA_ORCHESTRATOR_LOCAL_PROVER_MAX_MEMORY_MB", long)]
+    pub local_prover_max_memory_mb: Option<u64>,
+
+    /// The CPU time limit of a prover run, in seconds.
+    #[arg(env = "MADARA_ORCHESTRATOR_LOCAL_PROVER_MAX_CPU_SECONDS", long)]
+    pub local_prover_max_cpu_seconds: Option<u64>,
+
+    /// The number of prover runs allowed at the same time.
+    #[arg(env = "MADARA_ORCHESTRATOR_LOCAL_PROVER_MAX_CONCURRENT_RUNS", long, default_value = "1")]
+    pub local_prover_max_concurrent_runs: usize,
+
+    /// The executable running the aggregator program over proven blocks and proving it, required for proof
+    /// aggregation.
+    #[arg(env = "MADARA_ORCHESTRATOR_LOCAL_PROVER_AGGREGATOR_COMMAND", long)]
+    pub local_prover_aggregator_command: Option<PathBuf>,
+
+    /// The aggregator arguments, space separated. `{aggregator_input}`, `{aggregator_output}`, `{proof}` and
+    /// `{layout}` are replaced by the path of the aggregated proofs and program outputs, the path the aggregator
+    /// program output must be written to, the path the proof must be written to and the proof layout.
+    #[arg(
+        env = "MADARA_ORCHESTRATOR_LOCAL_PROVER_AGGREGATOR_ARGS",
+        long,
+        value_delimiter = ' ',
+        default_value = "--input {aggregator_input} --output {aggregator_output} --proof {proof} --layout {layout}",
+        allow_hyphen_values = true
+    )]
+    pub local_prover_aggregator_args: Vec<String>,
+}
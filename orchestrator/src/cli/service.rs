@@ -21,4 +21,33 @@ pub struct ServiceCliArgs {
     /// The maximum number of proving jobs to process concurrently.
     #[arg(env = "MADARA_ORCHESTRATOR_MAX_CONCURRENT_PROVING_JOBS", long)]
     pub max_concurrent_proving_jobs: Option<usize>,
+
+    /// How the state diffs are made available on L1. This must match the DA mode of the blocks.
+    #[arg(env = "MADARA_ORCHESTRATOR_L1_DA_MODE", long, default_value = "blob", value_enum)]
+    pub l1_da_mode: L1DataAvailabilityMode,
+
+    /// Aggregate the proofs of the blocks of each batch into a single proof and settle each batch with a single
+    /// state update. Requires a prover able to run the aggregator program and the blob DA mode.
+    #[arg(env = "MADARA_ORCHESTRATOR_PROOF_AGGREGATION", long)]
+    pub proof_aggregation: bool,
+
+    /// Allow the validium L1 DA mode. Experimental: it needs a core contract exposing `updateStateValidium`,
+    /// which neither the Starknet core contract nor the contracts deployed by the bootstrapper implement.
+    #[arg(env = "MADARA_ORCHESTRATOR_EXPERIMENTAL_VALIDIUM", long)]
+    pub experimental_validium: bool,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum, PartialEq, Eq, Default)]
+pub enum L1DataAvailabilityMode {
+    /// State diffs are sent in EIP-4844 blobs along with the state update.
+    #[default]
+    Blob,
+    /// State diffs are sent as calldata before the state update.
+    Calldata,
+    /// State diffs are published to the configured DA layer, only their commitment is sent with the state update.
+    External,
+    /// State diffs are stored by a data availability committee, its attestation is registered with the state
+    /// update. Blocks are executed with kzg DA, as in the validium mode of Madara. Experimental, only allowed with
+    /// `--experimental-validium`.
+    Validium,
 }
@@ -23,4 +23,18 @@ pub struct EthereumSettlementCliArgs {
     /// The address of the Starknet operator.
     #[arg(env = "MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS", long)]
     pub starknet_operator_address: Option<String>,
+
+    /// The time after which a transaction that is still not included is re-broadcast with bumped fees, in seconds.
+    /// The fee settings also apply to the blob transactions of the Ethereum DA client.
+    #[arg(env = "MADARA_ORCHESTRATOR_ETHEREUM_TX_FEE_BUMP_TIMEOUT", long, default_value = "180")]
+    pub ethereum_tx_fee_bump_timeout: u64,
+
+    /// The percentage by which the fees of a transaction are bumped. Blob transactions are bumped by at least
+    /// 100%, as required to replace them.
+    #[arg(env = "MADARA_ORCHESTRATOR_ETHEREUM_TX_FEE_BUMP_PERCENT", long, default_value = "20")]
+    pub ethereum_tx_fee_bump_percent: u64,
+
+    /// The maximum fee a transaction may cost, in wei.
+    #[arg(env = "MADARA_ORCHESTRATOR_ETHEREUM_TX_MAX_FEE_WEI", long)]
+    pub ethereum_tx_max_fee_wei: Option<u128>,
 }
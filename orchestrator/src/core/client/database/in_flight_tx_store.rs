@@ -0,0 +1,37 @@
+use alloy::primitives::{Address, B256};
+use async_trait::async_trait;
+use color_eyre::Result;
+use orchestrator_ethereum_settlement_client::tx_manager::{InFlightTx, InFlightTxStore};
+
+use super::DatabaseClient;
+
+/// Persists the L1 transactions in flight of the Ethereum clients in the database of the orchestrator, so that
+/// they are resumed after a restart.
+pub struct DatabaseInFlightTxStore {
+    database: Box<dyn DatabaseClient>,
+}
+
+impl DatabaseInFlightTxStore {
+    pub fn new(database: Box<dyn DatabaseClient>) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait]
+impl InFlightTxStore for DatabaseInFlightTxStore {
+    async fn get_in_flight_tx(&self, sender: Address, request_hash: B256) -> Result<Option<InFlightTx>> {
+        Ok(self.database.get_in_flight_tx(sender, request_hash).await?)
+    }
+
+    async fn save_in_flight_tx(&self, tx: &InFlightTx) -> Result<()> {
+        Ok(self.database.upsert_in_flight_tx(tx).await?)
+    }
+
+    async fn remove_in_flight_tx(&self, sender: Address, nonce: u64) -> Result<()> {
+        Ok(self.database.delete_in_flight_tx(sender, nonce).await?)
+    }
+
+    async fn prune_in_flight_txs(&self, sender: Address, next_nonce: u64) -> Result<()> {
+        Ok(self.database.delete_in_flight_txs_below_nonce(sender, next_nonce).await?)
+    }
+}
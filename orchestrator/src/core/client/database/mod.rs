@@ -1,12 +1,15 @@
 pub mod error;
+pub mod in_flight_tx_store;
 pub mod mongodb;
 
 use crate::types::batch::{Batch, BatchUpdates};
 use crate::types::jobs::job_item::JobItem;
 use crate::types::jobs::job_updates::JobItemUpdates;
 use crate::types::jobs::types::{JobStatus, JobType};
+use alloy::primitives::{Address, B256};
 use async_trait::async_trait;
 pub use error::DatabaseError;
+use orchestrator_ethereum_settlement_client::tx_manager::InFlightTx;
 
 /// Trait defining database operations
 #[cfg_attr(test, mockall::automock)]
@@ -75,10 +78,38 @@ pub trait DatabaseClient: Send + Sync {
 
     /// get_latest_batch - Get the latest batch from DB. Returns `None` if the DB is empty
     async fn get_latest_batch(&self) -> Result<Option<Batch>, DatabaseError>;
+    /// get_batch_by_index - Get the batch with the given index. Returns `None` if it does not exist yet
+    async fn get_batch_by_index(&self, index: u64) -> Result<Option<Batch>, DatabaseError>;
     /// update_batch - Update the bath
     async fn update_batch(&self, batch: &Batch, update: BatchUpdates) -> Result<Batch, DatabaseError>;
     /// create_batch - Create a new batch
     async fn create_batch(&self, batch: Batch) -> Result<Batch, DatabaseError>;
     /// get_jobs_by_block_number - Get all jobs for a specific block number
     async fn get_jobs_by_block_number(&self, block_number: u64) -> Result<Vec<JobItem>, DatabaseError>;
+    /// get_jobs - Get jobs filtered by type, status and block range, most recently created first.
+    /// A job matches the block range if any block it covers lies within the inclusive range
+    async fn get_jobs(
+        &self,
+        job_type: Option<JobType>,
+        job_status: Option<JobStatus>,
+        block_range: Option<(u64, u64)>,
+        skip: u64,
+        limit: Option<i64>,
+    ) -> Result<Vec<JobItem>, DatabaseError>;
+
+    /// pause_job_creation - Stop the workers from creating new jobs of the given type
+    async fn pause_job_creation(&self, job_type: JobType) -> Result<(), DatabaseError>;
+    /// resume_job_creation - Let the workers create new jobs of the given type again
+    async fn resume_job_creation(&self, job_type: JobType) -> Result<(), DatabaseError>;
+    /// get_paused_job_types - Get the job types whose creation is currently paused
+    async fn get_paused_job_types(&self) -> Result<Vec<JobType>, DatabaseError>;
+
+    /// get_in_flight_tx - Get the L1 transaction in flight of the sender for the request with the given hash
+    async fn get_in_flight_tx(&self, sender: Address, request_hash: B256) -> Result<Option<InFlightTx>, DatabaseError>;
+    /// upsert_in_flight_tx - Insert the L1 transaction in flight, or replace the one with the same sender and nonce
+    async fn upsert_in_flight_tx(&self, tx: &InFlightTx) -> Result<(), DatabaseError>;
+    /// delete_in_flight_tx - Delete the L1 transaction in flight of the sender with the given nonce
+    async fn delete_in_flight_tx(&self, sender: Address, nonce: u64) -> Result<(), DatabaseError>;
+    /// delete_in_flight_txs_below_nonce - Delete the L1 transactions in flight of the sender whose nonce was used
+    async fn delete_in_flight_txs_below_nonce(&self, sender: Address, next_nonce: u64) -> Result<(), DatabaseError>;
 }
@@ -6,15 +6,19 @@ use crate::types::jobs::job_updates::JobItemUpdates;
 use crate::types::jobs::types::{JobStatus, JobType};
 use crate::types::params::database::DatabaseArgs;
 use crate::utils::metrics::ORCHESTRATOR_METRICS;
+use alloy::primitives::{Address, B256};
 use async_trait::async_trait;
-use chrono::{SubsecRound, Utc};
+use chrono::{DateTime, SubsecRound, Utc};
 use futures::TryStreamExt;
+use mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime;
 use mongodb::bson::{doc, Bson, Document};
 use mongodb::options::{
-    AggregateOptions, FindOneAndUpdateOptions, FindOptions, InsertOneOptions, ReturnDocument, UpdateOptions,
+    AggregateOptions, FindOneAndUpdateOptions, FindOptions, InsertOneOptions, ReplaceOptions, ReturnDocument,
+    UpdateOptions,
 };
 use mongodb::{bson, Client, Collection, Database};
 use opentelemetry::KeyValue;
+use orchestrator_ethereum_settlement_client::tx_manager::InFlightTx;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -53,6 +57,14 @@ pub struct MissingBlocksResponse {
     pub missing_blocks: Vec<u64>,
 }
 
+/// A job type whose creation has been paused by an operator
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PausedJobType {
+    pub job_type: JobType,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub paused_at: DateTime<Utc>,
+}
+
 /// MongoDB client implementation
 pub struct MongoDbClient {
     client: Client,
@@ -80,6 +92,14 @@ impl MongoDbClient {
         self.database.collection("batches")
     }
 
+    fn get_paused_job_types_collection(&self) -> Collection<PausedJobType> {
+        self.database.collection("paused_job_types")
+    }
+
+    fn get_in_flight_txs_collection(&self) -> Collection<InFlightTx> {
+        self.database.collection("in_flight_txs")
+    }
+
     pub fn get_collection(&self, name: &str) -> Collection<JobItem> {
         self.database.collection(name)
     }
@@ -813,6 +833,20 @@ impl DatabaseClient for MongoDbClient {
         }
     }
 
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_batch_by_index(&self, index: u64) -> Result<Option<Batch>, DatabaseError> {
+        let start = Instant::now();
+        let filter = doc! {
+            "index": index as i64,
+        };
+        let batch = self.get_batch_collection().find_one(filter, None).await?;
+        tracing::debug!(index = %index, category = "db_call", "Fetched batch by index");
+        let attributes = [KeyValue::new("db_operation_name", "get_batch_by_index")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(batch)
+    }
+
     async fn update_batch(&self, batch: &Batch, update: BatchUpdates) -> Result<Batch, DatabaseError> {
         let start = Instant::now();
         let filter = doc! {
@@ -907,6 +941,13 @@ impl DatabaseClient for MongoDbClient {
             "metadata.specific.blocks_to_settle": { "$elemMatch": { "$eq": block_number_i64 } }
         };
 
+        // Query for Aggregator jobs where the batch contains the block_number
+        let query3 = doc! {
+            "job_type": mongodb::bson::to_bson(&JobType::Aggregator)?,
+            "metadata.specific.start_block": { "$lte": block_number_i64 },
+            "metadata.specific.end_block": { "$gte": block_number_i64 },
+        };
+
         let mut results: Vec<JobItem> = Vec::new();
 
         let job_collection = self.get_job_collection();
@@ -919,6 +960,10 @@ impl DatabaseClient for MongoDbClient {
         let cursor2 = job_collection.find(query2, None).await?;
         results.extend(cursor2.try_collect::<Vec<JobItem>>().await?);
 
+        // Execute third query
+        let cursor3 = job_collection.find(query3, None).await?;
+        results.extend(cursor3.try_collect::<Vec<JobItem>>().await?);
+
         tracing::debug!(
             block_number = block_number,
             count = results.len(),
@@ -931,6 +976,195 @@ impl DatabaseClient for MongoDbClient {
 
         Ok(results)
     }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_jobs(
+        &self,
+        job_type: Option<JobType>,
+        job_status: Option<JobStatus>,
+        block_range: Option<(u64, u64)>,
+        skip: u64,
+        limit: Option<i64>,
+    ) -> Result<Vec<JobItem>, DatabaseError> {
+        let start = Instant::now();
+
+        let mut filter = doc! {};
+        if let Some(job_type) = &job_type {
+            filter.insert("job_type", mongodb::bson::to_bson(job_type)?);
+        }
+        if let Some(job_status) = &job_status {
+            filter.insert("status", mongodb::bson::to_bson(job_status)?);
+        }
+        if let Some((from_block, to_block)) = block_range {
+            let from_block = from_block as i64;
+            let to_block = to_block as i64;
+            // Same job/block relation as `get_jobs_by_block_number`, widened to a range
+            filter.insert(
+                "$or",
+                vec![
+                    doc! {
+                        "job_type": {
+                            "$in": [
+                                mongodb::bson::to_bson(&JobType::SnosRun)?,
+                                mongodb::bson::to_bson(&JobType::ProofCreation)?,
+                                mongodb::bson::to_bson(&JobType::ProofRegistration)?,
+                                mongodb::bson::to_bson(&JobType::DataSubmission)?,
+                            ]
+                        },
+                        "metadata.specific.block_number": { "$gte": from_block, "$lte": to_block },
+                    },
+                    doc! {
+                        "job_type": mongodb::bson::to_bson(&JobType::StateTransition)?,
+                        "metadata.specific.blocks_to_settle": {
+                            "$elemMatch": { "$gte": from_block, "$lte": to_block }
+                        },
+                    },
+                    doc! {
+                        "job_type": mongodb::bson::to_bson(&JobType::Aggregator)?,
+                        "metadata.specific.start_block": { "$lte": to_block },
+                        "metadata.specific.end_block": { "$gte": from_block },
+                    },
+                ],
+            );
+        }
+
+        // `$skip` has to come before `$limit` for the pagination to be correct
+        let mut pipeline = vec![
+            doc! { "$match": filter },
+            doc! { "$sort": { "created_at": -1, "_id": -1 } },
+            doc! { "$skip": skip as i64 },
+        ];
+        if let Some(limit) = limit {
+            pipeline.push(doc! { "$limit": limit });
+        }
+
+        tracing::debug!(
+            job_type = ?job_type,
+            job_status = ?job_status,
+            block_range = ?block_range,
+            skip = skip,
+            limit = ?limit,
+            category = "db_call",
+            "Fetching jobs"
+        );
+
+        let jobs = self.execute_pipeline::<JobItem, JobItem>(self.get_job_collection(), pipeline, None).await?;
+
+        let attributes = [KeyValue::new("db_operation_name", "get_jobs")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(jobs)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn pause_job_creation(&self, job_type: JobType) -> Result<(), DatabaseError> {
+        let start = Instant::now();
+        let job_type_bson = mongodb::bson::to_bson(&job_type)?;
+        let filter = doc! { "job_type": job_type_bson.clone() };
+        // Pausing an already paused job type keeps the original pause time
+        let update = doc! {
+            "$setOnInsert": {
+                "job_type": job_type_bson,
+                "paused_at": Bson::DateTime(Utc::now().round_subsecs(0).into()),
+            }
+        };
+        let options = UpdateOptions::builder().upsert(true).build();
+        self.update_one(self.get_paused_job_types_collection(), filter, update, Some(options)).await?;
+
+        tracing::debug!(job_type = ?job_type, category = "db_call", "Paused job creation");
+        let attributes = [KeyValue::new("db_operation_name", "pause_job_creation")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn resume_job_creation(&self, job_type: JobType) -> Result<(), DatabaseError> {
+        let start = Instant::now();
+        let filter = doc! { "job_type": mongodb::bson::to_bson(&job_type)? };
+        self.delete_one(self.get_paused_job_types_collection(), filter).await?;
+
+        tracing::debug!(job_type = ?job_type, category = "db_call", "Resumed job creation");
+        let attributes = [KeyValue::new("db_operation_name", "resume_job_creation")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_paused_job_types(&self) -> Result<Vec<JobType>, DatabaseError> {
+        let start = Instant::now();
+        let paused: Vec<PausedJobType> =
+            self.get_paused_job_types_collection().find(doc! {}, None).await?.try_collect().await?;
+
+        let attributes = [KeyValue::new("db_operation_name", "get_paused_job_types")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(paused.into_iter().map(|paused| paused.job_type).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn get_in_flight_tx(&self, sender: Address, request_hash: B256) -> Result<Option<InFlightTx>, DatabaseError> {
+        let start = Instant::now();
+        let filter = doc! {
+            "sender": mongodb::bson::to_bson(&sender)?,
+            "request_hash": mongodb::bson::to_bson(&request_hash)?,
+        };
+        let tx = self.get_in_flight_txs_collection().find_one(filter, None).await?;
+
+        let attributes = [KeyValue::new("db_operation_name", "get_in_flight_tx")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(tx)
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn upsert_in_flight_tx(&self, tx: &InFlightTx) -> Result<(), DatabaseError> {
+        let start = Instant::now();
+        let filter = doc! {
+            "sender": mongodb::bson::to_bson(&tx.sender)?,
+            "nonce": mongodb::bson::to_bson(&tx.nonce)?,
+        };
+        let options = ReplaceOptions::builder().upsert(true).build();
+        self.get_in_flight_txs_collection().replace_one(filter, tx, options).await?;
+
+        tracing::debug!(nonce = tx.nonce, category = "db_call", "Saved in-flight transaction");
+        let attributes = [KeyValue::new("db_operation_name", "upsert_in_flight_tx")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn delete_in_flight_tx(&self, sender: Address, nonce: u64) -> Result<(), DatabaseError> {
+        let start = Instant::now();
+        let filter = doc! {
+            "sender": mongodb::bson::to_bson(&sender)?,
+            "nonce": mongodb::bson::to_bson(&nonce)?,
+        };
+        self.delete_one(self.get_in_flight_txs_collection(), filter).await?;
+
+        let attributes = [KeyValue::new("db_operation_name", "delete_in_flight_tx")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(function_type = "db_call"), ret, err)]
+    async fn delete_in_flight_txs_below_nonce(&self, sender: Address, next_nonce: u64) -> Result<(), DatabaseError> {
+        let start = Instant::now();
+        let filter = doc! {
+            "sender": mongodb::bson::to_bson(&sender)?,
+            "nonce": { "$lt": mongodb::bson::to_bson(&next_nonce)? },
+        };
+        let result = self.get_in_flight_txs_collection().delete_many(filter, None).await?;
+
+        tracing::debug!(deleted_count = result.deleted_count, category = "db_call", "Pruned in-flight transactions");
+        let attributes = [KeyValue::new("db_operation_name", "delete_in_flight_txs_below_nonce")];
+        let duration = start.elapsed();
+        ORCHESTRATOR_METRICS.db_calls_response_time.record(duration.as_secs_f64(), &attributes);
+        Ok(())
+    }
 }
 
 // Generic utility function to convert Vec<T> to Option<T>
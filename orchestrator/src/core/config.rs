@@ -3,9 +3,15 @@ use alloy::providers::RootProvider;
 
 use cairo_vm::types::layout_name::LayoutName;
 use orchestrator_atlantic_service::AtlanticProverService;
+use orchestrator_celestia_da_client::CelestiaDaClient;
 use orchestrator_da_client_interface::DaClient;
+use orchestrator_dac_da_client::DacDaClient;
 use orchestrator_ethereum_da_client::EthereumDaClient;
+use orchestrator_ethereum_settlement_client::tx_manager::InFlightTxStore;
 use orchestrator_ethereum_settlement_client::EthereumSettlementClient;
+use orchestrator_local_da_client::LocalDaClient;
+use orchestrator_local_prover_service::LocalProverService;
+use orchestrator_mock_prover_service::MockProverService;
 use orchestrator_settlement_client_interface::SettlementClient;
 
 use orchestrator_prover_client_interface::ProverClient;
@@ -19,10 +25,10 @@ use url::Url;
 use crate::core::error::OrchestratorCoreResult;
 use crate::types::params::database::DatabaseArgs;
 use crate::{
-    cli::RunCmd,
+    cli::{service::L1DataAvailabilityMode, RunCmd},
     core::client::{
-        queue::QueueClient, storage::s3::AWSS3, storage::StorageClient, AlertClient, DatabaseClient, MongoDbClient,
-        SNS, SQS,
+        database::in_flight_tx_store::DatabaseInFlightTxStore, queue::QueueClient, storage::s3::AWSS3,
+        storage::StorageClient, AlertClient, DatabaseClient, MongoDbClient, SNS, SQS,
     },
     core::cloud::CloudProvider,
     types::params::da::DAConfig,
@@ -118,11 +124,18 @@ impl Config {
         let params = ConfigParam {
             madara_rpc_url: run_cmd.madara_rpc_url.clone(),
             snos_config: SNOSParams::from(run_cmd.snos_args.clone()),
-            service_config: ServiceParams::from(run_cmd.service_args.clone()),
+            service_config: ServiceParams {
+                dev_mode: run_cmd.dev,
+                ..ServiceParams::from(run_cmd.service_args.clone())
+            },
             server_config: ServerParams::from(run_cmd.server_args.clone()),
             snos_layout_name: Self::get_layout_name(run_cmd.proving_layout_args.prover_layout_name.clone().as_str())?,
             prover_layout_name: Self::get_layout_name(run_cmd.proving_layout_args.snos_layout_name.clone().as_str())?,
         };
+        if params.service_config.proof_aggregation {
+            Self::validate_proof_aggregation(&prover_config, &params.service_config)?;
+        }
+        Self::validate_l1_da_mode(&da_config, &params.service_config)?;
         let rpc_client = JsonRpcClient::new(HttpTransport::new(params.madara_rpc_url.clone()));
 
         let mut processing_locks = ProcessingLocks::default();
@@ -144,8 +157,9 @@ impl Config {
 
         // External Clients Initialization
         let prover_client = Self::build_prover_service(&prover_config, &params);
-        let da_client = Self::build_da_client(&da_config).await;
-        let settlement_client = Self::build_settlement_client(&settlement_config).await?;
+        let tx_store = Self::build_in_flight_tx_store(&db).await?;
+        let da_client = Self::build_da_client(&da_config, tx_store.clone()).await;
+        let settlement_client = Self::build_settlement_client(&settlement_config, tx_store).await?;
 
         Ok(Self {
             params,
@@ -167,6 +181,13 @@ impl Config {
         Ok(Box::new(MongoDbClient::new(db_args).await?))
     }
 
+    /// build_in_flight_tx_store - Build the store where the Ethereum clients persist their transactions in flight
+    pub(crate) async fn build_in_flight_tx_store(
+        db_args: &DatabaseArgs,
+    ) -> OrchestratorCoreResult<Arc<dyn InFlightTxStore>> {
+        Ok(Arc::new(DatabaseInFlightTxStore::new(Self::build_database_client(db_args).await?)))
+    }
+
     pub(crate) async fn build_storage_client(
         storage_config: &StorageArgs,
         provider_config: Arc<CloudProvider>,
@@ -209,25 +230,36 @@ impl Config {
             ProverConfig::Atlantic(atlantic_params) => {
                 Box::new(AtlanticProverService::new_with_args(atlantic_params, &params.prover_layout_name))
             }
+            ProverConfig::Local(local_prover_params) => {
+                Box::new(LocalProverService::new_with_args(local_prover_params, &params.prover_layout_name))
+            }
+            ProverConfig::Mock => Box::new(MockProverService::new()),
         }
     }
 
-    pub(crate) async fn build_da_client(da_params: &DAConfig) -> Box<dyn DaClient + Send + Sync> {
+    pub(crate) async fn build_da_client(
+        da_params: &DAConfig,
+        tx_store: Arc<dyn InFlightTxStore>,
+    ) -> Box<dyn DaClient + Send + Sync> {
         match da_params {
             DAConfig::Ethereum(ethereum_da_params) => {
-                Box::new(EthereumDaClient::new_with_args(ethereum_da_params).await)
+                Box::new(EthereumDaClient::new_with_args(ethereum_da_params, tx_store).await)
             }
+            DAConfig::Celestia(celestia_da_params) => Box::new(CelestiaDaClient::new_with_args(celestia_da_params)),
+            DAConfig::Dac(dac_da_params) => Box::new(DacDaClient::new_with_args(dac_da_params)),
+            DAConfig::Local(local_da_params) => Box::new(LocalDaClient::new_with_args(local_da_params)),
         }
     }
 
     pub(crate) async fn build_settlement_client(
         settlement_params: &SettlementConfig,
+        tx_store: Arc<dyn InFlightTxStore>,
     ) -> OrchestratorResult<Box<dyn SettlementClient + Send + Sync>> {
         match settlement_params {
             SettlementConfig::Ethereum(ethereum_settlement_params) => {
                 #[cfg(not(feature = "testing"))]
                 {
-                    Ok(Box::new(EthereumSettlementClient::new_with_args(ethereum_settlement_params)))
+                    Ok(Box::new(EthereumSettlementClient::new_with_args(ethereum_settlement_params, tx_store)))
                 }
                 #[cfg(feature = "testing")]
                 {
@@ -236,6 +268,8 @@ impl Config {
                         ethereum_settlement_params.l1_core_contract_address,
                         ethereum_settlement_params.ethereum_rpc_url.clone(),
                         Some(ethereum_settlement_params.starknet_operator_address),
+                        ethereum_settlement_params.tx_manager_config.clone(),
+                        tx_store,
                     )))
                 }
             }
@@ -245,6 +279,57 @@ impl Config {
         }
     }
 
+    /// Proof aggregation needs a prover running the aggregator program, only the local prover does for now. The
+    /// batches are settled with the blobs of their squashed state diff, which the aggregator output commits to.
+    fn validate_proof_aggregation(
+        prover_config: &ProverConfig,
+        service_config: &ServiceParams,
+    ) -> OrchestratorResult<()> {
+        match prover_config {
+            ProverConfig::Local(local_prover_params)
+                if local_prover_params.local_prover_aggregator_command.is_some() => {}
+            _ => {
+                return Err(OrchestratorError::ConfigError(
+                    "Proof aggregation requires the local prover with an aggregator command".to_string(),
+                ))
+            }
+        }
+        if service_config.l1_da_mode != L1DataAvailabilityMode::Blob {
+            return Err(OrchestratorError::ConfigError(format!(
+                "Proof aggregation only supports the blob L1 DA mode, got {:?}",
+                service_config.l1_da_mode
+            )));
+        }
+        Ok(())
+    }
+
+    /// The external mode only sends the commitment of state diffs published to a DA layer returning one, and the
+    /// validium mode the attestation of a data availability committee. The validium mode settles through an update
+    /// path that no deployed core contract implements yet, so it has to be opted into explicitly.
+    fn validate_l1_da_mode(da_config: &DAConfig, service_config: &ServiceParams) -> OrchestratorResult<()> {
+        match (service_config.l1_da_mode, da_config) {
+            (L1DataAvailabilityMode::External, DAConfig::Celestia(_) | DAConfig::Local(_)) => {}
+            (L1DataAvailabilityMode::External, _) => {
+                return Err(OrchestratorError::ConfigError(
+                    "The external L1 DA mode requires the Celestia or the local DA layer".to_string(),
+                ))
+            }
+            (L1DataAvailabilityMode::Validium, DAConfig::Dac(_)) => {}
+            (L1DataAvailabilityMode::Validium, _) => {
+                return Err(OrchestratorError::ConfigError(
+                    "The validium L1 DA mode requires the data availability committee DA layer".to_string(),
+                ))
+            }
+            _ => {}
+        }
+        if service_config.l1_da_mode == L1DataAvailabilityMode::Validium && !service_config.experimental_validium {
+            return Err(OrchestratorError::ConfigError(
+                "The validium L1 DA mode is experimental and requires --experimental-validium".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// get_layout_name - Returns the layout name based on the input string
     fn get_layout_name(layout_name: &str) -> OrchestratorResult<LayoutName> {
         Ok(match layout_name {
@@ -0,0 +1,23 @@
+use crate::error::other::OtherError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AggregatorError {
+    #[error("Batch #{batch_num:?} has {blocks} blocks but {task_ids} proving tasks - aggregator job #{internal_id:?}")]
+    MissingProvingTasks { internal_id: String, batch_num: u64, blocks: u64, task_ids: usize },
+
+    #[error("Aggregator output path is not specified - aggregator job #{internal_id:?}")]
+    AggregatorOutputPathMissing { internal_id: String },
+
+    #[error("Not able to get the squashed state update of the batch: {0}")]
+    SquashedStateUpdateFetchFailed(String),
+
+    #[error("Not able to store the aggregator output: {0}")]
+    AggregatorOutputStorageFailed(String),
+
+    #[error("Not able to store the proof: {0}")]
+    ProofStorageFailed(String),
+
+    #[error("Other error: {0}")]
+    Other(#[from] OtherError),
+}
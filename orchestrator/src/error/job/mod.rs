@@ -1,3 +1,4 @@
+pub mod aggregator;
 pub mod da_error;
 pub mod fact;
 pub mod proving;
@@ -13,6 +14,7 @@ use crate::error::other::OtherError;
 use crate::error::ConsumptionError;
 use crate::types::error::TypeError;
 use crate::types::jobs::types::{JobStatus, JobType};
+use aggregator::AggregatorError;
 use da_error::DaError;
 use proving::ProvingError;
 use state_update::StateUpdateError;
@@ -81,6 +83,10 @@ pub enum JobError {
     #[error("Proving Error: {0}")]
     ProvingJobError(#[from] ProvingError),
 
+    /// Wraps errors from aggregator operations
+    #[error("Aggregator Error: {0}")]
+    AggregatorJobError(#[from] AggregatorError),
+
     /// Wraps errors from state update operations
     #[error("Proving Error: {0}")]
     StateUpdateJobError(#[from] StateUpdateError),
@@ -12,6 +12,9 @@ pub enum ProvingError {
     #[error("Not able to get the PIE file from AWS S3 bucket.")]
     CairoPIEFileFetchFailed(String),
 
+    #[error("Not able to store the proof: {0}")]
+    ProofStorageFailed(String),
+
     #[error("Other error: {0}")]
     Other(#[from] OtherError),
 }
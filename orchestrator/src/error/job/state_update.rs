@@ -1,3 +1,4 @@
+use crate::cli::service::L1DataAvailabilityMode;
 use crate::error::other::OtherError;
 use thiserror::Error;
 
@@ -42,6 +43,21 @@ pub enum StateUpdateError {
     #[error("Block #{block_no:?} - SNOS error, [use_kzg_da] should be either 0 or 1.")]
     UseKZGDaError { block_no: u64 },
 
+    #[error("Block #{block_no:?} - Blobs published by DA transaction {da_tx_hash} are not included anymore: {status}")]
+    BlobsNotPublished { block_no: u64, da_tx_hash: String, status: String },
+
+    #[error("Block #{block_no:?} - SNOS output does not use the configured {l1_da_mode:?} L1 DA mode.")]
+    DaModeMismatch { block_no: u64, l1_da_mode: L1DataAvailabilityMode },
+
+    #[error("Block #{block_no:?} - State diff of {len} bytes is not made of 32 bytes felts.")]
+    InvalidStateDiffLength { block_no: u64, len: usize },
+
+    #[error("Block #{block_no:?} - No commitment was recorded for the state diff published to the DA layer.")]
+    MissingDaCommitment { block_no: u64 },
+
+    #[error("Block #{block_no:?} - The DA layer has no attestation of the state diff.")]
+    MissingDaAttestation { block_no: u64 },
+
     #[error("Other error: {0}")]
     Other(#[from] OtherError),
 }
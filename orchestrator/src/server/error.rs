@@ -21,6 +21,7 @@ pub type ApiServiceResult<T> = Result<T, JobRouteError>;
 /// * `InvalidJobState` - 409 Conflict
 /// * `DatabaseError` - 500 Internal Server Error
 /// * `InvalidStatus` - 400 Bad Request
+/// * `InvalidRequest` - 400 Bad Request
 ///
 /// # Examples
 /// ```
@@ -58,6 +59,10 @@ pub enum JobRouteError {
     /// Contains both the job ID and the current status
     #[error("Invalid status: {id}: {job_status}")]
     InvalidStatus { id: String, job_status: String },
+
+    /// Indicates that the request parameters are inconsistent, e.g. an empty block range
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
 }
 
 /// Implementation of axum's `IntoResponse` trait for converting errors into HTTP responses.
@@ -81,6 +86,7 @@ pub enum JobRouteError {
 /// * `InvalidJobState` -> 409 Conflict
 /// * `DatabaseError` -> 500 Internal Server Error
 /// * `InvalidStatus` -> 400 Bad Request
+/// * `InvalidRequest` -> 400 Bad Request
 impl IntoResponse for JobRouteError {
     fn into_response(self) -> Response {
         match self {
@@ -106,6 +112,9 @@ impl IntoResponse for JobRouteError {
                 Json(ApiResponse::error(format!("Cannot retry job {id}: invalid status {job_status}"))),
             )
                 .into_response(),
+            JobRouteError::InvalidRequest(msg) => {
+                (StatusCode::BAD_REQUEST, Json(ApiResponse::error(format!("Invalid request: {}", msg)))).into_response()
+            }
         }
     }
 }
@@ -1,20 +1,32 @@
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::response::IntoResponse;
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::{Json, Router};
 use opentelemetry::KeyValue;
 use tracing::{error, info, instrument};
 use uuid::Uuid;
 
 use super::super::error::JobRouteError;
-use super::super::types::{ApiResponse, BlockJobStatusResponse, JobId, JobRouteResult, JobStatusResponseItem};
+use super::super::types::{
+    ApiResponse, BlockJobStatusResponse, BlockRangeQuery, BulkRetryResponse, JobId, JobListQuery, JobListResponse,
+    JobResponseItem, JobRetryFailure, JobRouteResult, JobStatusResponseItem, PausedJobTypesResponse,
+};
 use crate::core::config::Config;
+use crate::error::job::JobError;
+use crate::types::jobs::types::{JobStatus, JobType};
 use crate::utils::metrics::ORCHESTRATOR_METRICS;
 use crate::worker::event_handler::service::JobHandlerService;
 use crate::worker::service::JobService;
 
+/// Number of jobs per page when the listing request doesn't specify one
+const DEFAULT_JOB_LIST_PAGE_SIZE: u64 = 50;
+/// Upper bound on the number of jobs returned by a single listing request
+const MAX_JOB_LIST_PAGE_SIZE: u64 = 500;
+/// Upper bound on the number of jobs retried by a single bulk retry request
+const MAX_BULK_RETRY_JOBS: i64 = 100;
+
 /// Handles HTTP requests to process a job.
 ///
 /// This endpoint initiates the processing of a job identified by its UUID. It performs the
@@ -99,9 +111,9 @@ async fn handle_verify_job_request(
     }
 }
 
-/// Handles HTTP requests to retry a failed job.
+/// Handles HTTP requests to retry a failed or cancelled job.
 ///
-/// This endpoint attempts to retry a previously failed job. It:
+/// This endpoint attempts to retry a previously failed or cancelled job. It:
 /// 1. Validates and parses the job ID
 /// 2. Initiates the retry process
 /// 3. Records metrics with additional retry context
@@ -145,6 +157,296 @@ async fn handle_retry_job_request(
     }
 }
 
+/// Handles HTTP requests to cancel a job.
+///
+/// A cancelled job isn't processed or verified again until it is retried, and the workers won't
+/// create another job for the same internal id. Completed jobs can't be cancelled.
+///
+/// # Arguments
+/// * `Path(JobId { id })` - The job ID extracted from the URL path
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult` - Success response or error details
+///
+/// # Errors
+/// * `JobRouteError::InvalidId` - If the provided ID is not a valid UUID
+/// * `JobRouteError::NotFound` - If the job doesn't exist
+/// * `JobRouteError::InvalidJobState` - If the job is already completed or cancelled
+/// * `JobRouteError::ProcessingError` - If the status update fails
+#[instrument(skip(config), fields(job_id = %id))]
+async fn handle_cancel_job_request(
+    Path(JobId { id }): Path<JobId>,
+    State(config): State<Arc<Config>>,
+) -> JobRouteResult {
+    let job_id = Uuid::parse_str(&id).map_err(|_| JobRouteError::InvalidId(id.clone()))?;
+
+    match JobHandlerService::cancel_job(job_id, config.clone()).await {
+        Ok(_) => {
+            info!("Job cancelled successfully");
+            ORCHESTRATOR_METRICS.successful_job_operations.add(1.0, &[KeyValue::new("operation_type", "cancel_job")]);
+            Ok(Json(ApiResponse::<()>::success(Some(format!("Job with id {} cancelled", id)))).into_response())
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to cancel job");
+            ORCHESTRATOR_METRICS.failed_job_operations.add(1.0, &[KeyValue::new("operation_type", "cancel_job")]);
+            match e {
+                JobError::JobNotFound { .. } => Err(JobRouteError::NotFound(id)),
+                JobError::InvalidStatus { job_status, .. } => {
+                    Err(JobRouteError::InvalidJobState(format!("job {} is {} and can't be cancelled", id, job_status)))
+                }
+                e => Err(JobRouteError::ProcessingError(e.to_string())),
+            }
+        }
+    }
+}
+
+/// Handles HTTP requests to get a job with all of its metadata.
+///
+/// The response includes the attempt counters, the timestamps and the failure reason kept in the
+/// common metadata, along with the job specific metadata.
+///
+/// # Arguments
+/// * `Path(JobId { id })` - The job ID extracted from the URL path
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult<JobResponseItem>` - Success response with the job or error details
+///
+/// # Errors
+/// * `JobRouteError::InvalidId` - If the provided ID is not a valid UUID
+/// * `JobRouteError::NotFound` - If the job doesn't exist
+/// * `JobRouteError::DatabaseError` - If the job can't be fetched
+#[instrument(skip(config), fields(job_id = %id))]
+async fn handle_get_job_request(Path(JobId { id }): Path<JobId>, State(config): State<Arc<Config>>) -> JobRouteResult {
+    let job_id = Uuid::parse_str(&id).map_err(|_| JobRouteError::InvalidId(id.clone()))?;
+
+    match config.database().get_job_by_id(job_id).await {
+        Ok(Some(job)) => Ok(Json(ApiResponse::<JobResponseItem>::success_with_data(job.into(), None)).into_response()),
+        Ok(None) => Err(JobRouteError::NotFound(id)),
+        Err(e) => {
+            error!(error = %e, "Failed to fetch job");
+            Err(JobRouteError::DatabaseError)
+        }
+    }
+}
+
+/// Handles HTTP requests to list jobs.
+///
+/// Jobs can be filtered by type, status and block range, and are returned most recently created
+/// first, one page at a time.
+///
+/// # Arguments
+/// * `Query(query)` - The filters and the page to return
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult<JobListResponse>` - Success response with the jobs of the page or error details
+///
+/// # Errors
+/// * `JobRouteError::InvalidRequest` - If the block range is empty or the page size is out of bounds
+/// * `JobRouteError::DatabaseError` - If the jobs can't be fetched
+#[instrument(skip(config))]
+async fn handle_list_jobs_request(
+    Query(query): Query<JobListQuery>,
+    State(config): State<Arc<Config>>,
+) -> JobRouteResult {
+    let block_range = parse_block_range(query.from_block, query.to_block)?;
+    let page = query.page.unwrap_or(0);
+    let page_size = query.page_size.unwrap_or(DEFAULT_JOB_LIST_PAGE_SIZE);
+    if page_size == 0 || page_size > MAX_JOB_LIST_PAGE_SIZE {
+        return Err(JobRouteError::InvalidRequest(format!(
+            "page_size must be between 1 and {}, got {}",
+            MAX_JOB_LIST_PAGE_SIZE, page_size
+        )));
+    }
+    let skip = page
+        .checked_mul(page_size)
+        .ok_or_else(|| JobRouteError::InvalidRequest(format!("page {} is out of bounds", page)))?;
+
+    match config.database().get_jobs(query.job_type, query.status, block_range, skip, Some(page_size as i64)).await {
+        Ok(jobs) => {
+            info!(count = jobs.len(), "Successfully fetched jobs");
+            Ok(Json(ApiResponse::<JobListResponse>::success_with_data(
+                JobListResponse { jobs: jobs.into_iter().map(JobResponseItem::from).collect(), page, page_size },
+                None,
+            ))
+            .into_response())
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to fetch jobs");
+            Err(JobRouteError::DatabaseError)
+        }
+    }
+}
+
+/// Handles HTTP requests to retry all the failed jobs of a type in a block range.
+///
+/// Each failed job goes through the same retry flow as the single job retry endpoint. A job that
+/// can't be retried doesn't stop the others and is reported in the response. At most
+/// [`MAX_BULK_RETRY_JOBS`] jobs are retried per request, the retried jobs are no longer failed so
+/// the next request picks up the remaining ones.
+///
+/// # Arguments
+/// * `Path(job_type)` - The type of the jobs to retry
+/// * `Query(BlockRangeQuery { from_block, to_block })` - The inclusive block range
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult<BulkRetryResponse>` - The retried jobs and the ones that couldn't be retried
+///
+/// # Errors
+/// * `JobRouteError::InvalidRequest` - If the block range is empty
+/// * `JobRouteError::DatabaseError` - If the failed jobs can't be fetched
+#[instrument(skip(config))]
+async fn handle_bulk_retry_jobs_request(
+    Path(job_type): Path<JobType>,
+    Query(BlockRangeQuery { from_block, to_block }): Query<BlockRangeQuery>,
+    State(config): State<Arc<Config>>,
+) -> JobRouteResult {
+    let block_range = parse_block_range(Some(from_block), Some(to_block))?;
+
+    let failed_jobs = config
+        .database()
+        .get_jobs(Some(job_type.clone()), Some(JobStatus::Failed), block_range, 0, Some(MAX_BULK_RETRY_JOBS))
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to fetch failed jobs");
+            JobRouteError::DatabaseError
+        })?;
+
+    let limit_reached = failed_jobs.len() as i64 == MAX_BULK_RETRY_JOBS;
+    let mut response = BulkRetryResponse { retried: Vec::new(), failed: Vec::new() };
+    for job in failed_jobs {
+        let attributes = [KeyValue::new("operation_type", "process_job"), KeyValue::new("operation_info", "retry_job")];
+        match JobHandlerService::retry_job(job.id, config.clone()).await {
+            Ok(_) => {
+                ORCHESTRATOR_METRICS.successful_job_operations.add(1.0, &attributes);
+                response.retried.push(job.id);
+            }
+            Err(e) => {
+                error!(job_id = %job.id, error = %e, "Failed to retry job");
+                ORCHESTRATOR_METRICS.failed_job_operations.add(1.0, &attributes);
+                response.failed.push(JobRetryFailure { id: job.id, error: e.to_string() });
+            }
+        }
+    }
+
+    info!(retried = response.retried.len(), failed = response.failed.len(), "Bulk retry completed");
+    let mut message = format!(
+        "Retry initiated for {} failed {:?} jobs between blocks {} and {}",
+        response.retried.len(),
+        job_type,
+        from_block,
+        to_block
+    );
+    if limit_reached {
+        message.push_str(&format!(", at most {} jobs are retried per request", MAX_BULK_RETRY_JOBS));
+    }
+    Ok(Json(ApiResponse::<BulkRetryResponse>::success_with_data(response, Some(message))).into_response())
+}
+
+/// Handles HTTP requests to pause the creation of the jobs of a type.
+///
+/// While paused, the worker creating jobs of this type skips its runs. Existing jobs of the type
+/// keep being processed and verified.
+///
+/// # Arguments
+/// * `Path(job_type)` - The type of the jobs to stop creating
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult` - Success response or error details
+///
+/// # Errors
+/// * `JobRouteError::DatabaseError` - If the pause can't be stored
+#[instrument(skip(config))]
+async fn handle_pause_job_type_request(
+    Path(job_type): Path<JobType>,
+    State(config): State<Arc<Config>>,
+) -> JobRouteResult {
+    match config.database().pause_job_creation(job_type.clone()).await {
+        Ok(_) => {
+            info!("Job creation paused");
+            Ok(Json(ApiResponse::<()>::success(Some(format!("Creation of {:?} jobs paused", job_type))))
+                .into_response())
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to pause job creation");
+            Err(JobRouteError::DatabaseError)
+        }
+    }
+}
+
+/// Handles HTTP requests to resume the creation of the jobs of a type.
+///
+/// # Arguments
+/// * `Path(job_type)` - The type of the jobs to create again
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult` - Success response or error details
+///
+/// # Errors
+/// * `JobRouteError::DatabaseError` - If the pause can't be removed
+#[instrument(skip(config))]
+async fn handle_resume_job_type_request(
+    Path(job_type): Path<JobType>,
+    State(config): State<Arc<Config>>,
+) -> JobRouteResult {
+    match config.database().resume_job_creation(job_type.clone()).await {
+        Ok(_) => {
+            info!("Job creation resumed");
+            Ok(Json(ApiResponse::<()>::success(Some(format!("Creation of {:?} jobs resumed", job_type))))
+                .into_response())
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to resume job creation");
+            Err(JobRouteError::DatabaseError)
+        }
+    }
+}
+
+/// Handles HTTP requests to get the job types whose creation is paused.
+///
+/// # Arguments
+/// * `State(config)` - Shared application configuration
+///
+/// # Returns
+/// * `JobRouteResult<PausedJobTypesResponse>` - Success response with the paused job types or error details
+#[instrument(skip(config))]
+async fn handle_get_paused_job_types_request(State(config): State<Arc<Config>>) -> JobRouteResult {
+    match config.database().get_paused_job_types().await {
+        Ok(job_types) => Ok(Json(ApiResponse::<PausedJobTypesResponse>::success_with_data(
+            PausedJobTypesResponse { job_types },
+            None,
+        ))
+        .into_response()),
+        Err(e) => {
+            error!(error = %e, "Failed to fetch paused job types");
+            Err(JobRouteError::DatabaseError)
+        }
+    }
+}
+
+/// Builds the inclusive block range used to filter jobs. A missing bound leaves that side of the
+/// range open.
+fn parse_block_range(from_block: Option<u64>, to_block: Option<u64>) -> Result<Option<(u64, u64)>, JobRouteError> {
+    if from_block.is_none() && to_block.is_none() {
+        return Ok(None);
+    }
+    // Block numbers are stored as i64 in the database
+    let from_block = from_block.unwrap_or(0);
+    let to_block = to_block.unwrap_or(i64::MAX as u64);
+    if from_block > to_block {
+        return Err(JobRouteError::InvalidRequest(format!(
+            "from_block {} is greater than to_block {}",
+            from_block, to_block
+        )));
+    }
+    Ok(Some((from_block, to_block)))
+}
+
 /// Creates a router for job-related endpoints.
 ///
 /// This function sets up the main router for all job-related operations,
@@ -157,8 +459,14 @@ async fn handle_retry_job_request(
 /// * `Router` - Configured router with all job endpoints
 pub fn job_router(config: Arc<Config>) -> Router {
     Router::new()
-        .nest("/:id", job_trigger_router(config.clone()))
-        .route("/block/:block_number/status", get(handle_get_job_status_by_block_request).with_state(config))
+        .route("/", get(handle_list_jobs_request))
+        .route("/paused", get(handle_get_paused_job_types_request))
+        .route("/type/:job_type/retry", post(handle_bulk_retry_jobs_request))
+        .route("/type/:job_type/pause", post(handle_pause_job_type_request))
+        .route("/type/:job_type/resume", post(handle_resume_job_type_request))
+        .route("/block/:block_number/status", get(handle_get_job_status_by_block_request))
+        .with_state(config.clone())
+        .nest("/:id", job_trigger_router(config))
 }
 
 /// Handles HTTP requests to get job statuses by block number.
@@ -199,8 +507,9 @@ async fn handle_get_job_status_by_block_request(
 
 /// Creates the nested router for job trigger endpoints.
 ///
-/// Sets up specific routes for processing, verifying, and retrying jobs.
-/// All endpoints are configured as GET requests and share the application config.
+/// Sets up specific routes for fetching, processing, verifying, retrying and cancelling jobs.
+/// Cancelling is a POST request, the other endpoints are GET requests, and all of them share the
+/// application config.
 ///
 /// # Arguments
 /// * `config` - Shared application configuration
@@ -209,8 +518,10 @@ async fn handle_get_job_status_by_block_request(
 /// * `Router` - Configured router with trigger endpoints
 pub(super) fn job_trigger_router(config: Arc<Config>) -> Router {
     Router::new()
+        .route("/", get(handle_get_job_request))
         .route("/process", get(handle_process_job_request))
         .route("/verify", get(handle_verify_job_request))
         .route("/retry", get(handle_retry_job_request))
+        .route("/cancel", post(handle_cancel_job_request))
         .with_state(config)
 }
@@ -1,5 +1,9 @@
+use crate::types::jobs::external_id::ExternalId;
+use crate::types::jobs::job_item::JobItem;
+use crate::types::jobs::metadata::JobMetadata;
 use crate::types::jobs::types::{JobStatus, JobType};
 use axum::response::Response;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -130,3 +134,83 @@ pub struct JobStatusResponseItem {
 pub struct BlockJobStatusResponse {
     pub jobs: Vec<JobStatusResponseItem>,
 }
+
+/// Query parameters of the job listing endpoint. All the filters are optional.
+///
+/// `from_block` and `to_block` are inclusive. When only one of them is set, the range is open on
+/// the other side.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct JobListQuery {
+    pub job_type: Option<JobType>,
+    pub status: Option<JobStatus>,
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+    /// Zero-based page number
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+/// Inclusive block range given as query parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlockRangeQuery {
+    pub from_block: u64,
+    pub to_block: u64,
+}
+
+/// A job with all of its metadata, including the attempt counters and the failure reason.
+///
+/// Mirrors `JobItem` with plain JSON encodings for the id and the timestamps.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobResponseItem {
+    pub id: Uuid,
+    pub internal_id: String,
+    pub job_type: JobType,
+    pub status: JobStatus,
+    pub external_id: ExternalId,
+    pub metadata: JobMetadata,
+    pub version: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<JobItem> for JobResponseItem {
+    fn from(job: JobItem) -> Self {
+        Self {
+            id: job.id,
+            internal_id: job.internal_id,
+            job_type: job.job_type,
+            status: job.status,
+            external_id: job.external_id,
+            metadata: job.metadata,
+            version: job.version,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobListResponse {
+    pub jobs: Vec<JobResponseItem>,
+    pub page: u64,
+    pub page_size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobRetryFailure {
+    pub id: Uuid,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkRetryResponse {
+    /// Ids of the jobs queued for retry
+    pub retried: Vec<Uuid>,
+    /// Jobs that couldn't be retried, with the reason
+    pub failed: Vec<JobRetryFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PausedJobTypesResponse {
+    pub job_types: Vec<JobType>,
+}
@@ -28,6 +28,7 @@ lazy_static! {
         WorkerTriggerType::DataSubmission,
         WorkerTriggerType::UpdateState,
         WorkerTriggerType::Batching,
+        WorkerTriggerType::Aggregator,
     ];
 }
 
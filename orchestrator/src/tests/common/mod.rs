@@ -36,7 +36,13 @@ pub fn default_job_item() -> JobItem {
         external_id: ExternalId::String("0".to_string().into_boxed_str()),
         metadata: JobMetadata {
             common: CommonMetadata::default(),
-            specific: JobSpecificMetadata::Da(DaMetadata { block_number: 0, blob_data_path: None, tx_hash: None }),
+            specific: JobSpecificMetadata::Da(DaMetadata {
+                block_number: 0,
+                blob_data_path: None,
+                tx_hash: None,
+                da_commitment: None,
+                blob_versioned_hashes: Vec::new(),
+            }),
         },
         version: 0,
         created_at: Utc::now().round_subsecs(0),
@@ -2,6 +2,7 @@ use std::net::SocketAddr;
 use std::str::FromStr as _;
 use std::sync::Arc;
 
+use crate::cli::service::L1DataAvailabilityMode;
 use crate::core::client::database::MockDatabaseClient;
 use crate::core::client::queue::MockQueueClient;
 use crate::core::client::storage::MockStorageClient;
@@ -26,6 +27,7 @@ use cairo_vm::types::layout_name::LayoutName;
 use httpmock::MockServer;
 use orchestrator_da_client_interface::{DaClient, MockDaClient};
 use orchestrator_ethereum_da_client::EthereumDaValidatedArgs;
+use orchestrator_ethereum_settlement_client::tx_manager::TxManagerConfig;
 use orchestrator_ethereum_settlement_client::EthereumSettlementValidatedArgs;
 use orchestrator_prover_client_interface::{MockProverClient, ProverClient};
 use orchestrator_settlement_client_interface::{MockSettlementClient, SettlementClient};
@@ -113,6 +115,12 @@ pub struct TestConfigBuilder {
     storage_type: ConfigType,
     /// API Service
     api_server_type: ConfigType,
+    /// How the state diffs are made available on L1
+    l1_da_mode: L1DataAvailabilityMode,
+    /// Whether the proofs of each batch are aggregated
+    proof_aggregation: bool,
+    /// Whether the orchestrator runs in dev mode
+    dev_mode: bool,
 }
 
 impl Default for TestConfigBuilder {
@@ -142,6 +150,9 @@ impl TestConfigBuilder {
             storage_type: ConfigType::default(),
             alerts_type: ConfigType::default(),
             api_server_type: ConfigType::default(),
+            l1_da_mode: L1DataAvailabilityMode::default(),
+            proof_aggregation: false,
+            dev_mode: false,
         }
     }
 
@@ -194,10 +205,28 @@ impl TestConfigBuilder {
         self
     }
 
+    pub fn configure_l1_da_mode(mut self, l1_da_mode: L1DataAvailabilityMode) -> TestConfigBuilder {
+        self.l1_da_mode = l1_da_mode;
+        self
+    }
+
+    pub fn configure_proof_aggregation(mut self, proof_aggregation: bool) -> TestConfigBuilder {
+        self.proof_aggregation = proof_aggregation;
+        self
+    }
+
+    pub fn configure_dev_mode(mut self, dev_mode: bool) -> TestConfigBuilder {
+        self.dev_mode = dev_mode;
+        self
+    }
+
     pub async fn build(self) -> TestConfigBuilderReturns {
         dotenvy::from_filename_override("../.env.test").expect("Failed to load the .env.test file");
 
-        let params = get_env_params();
+        let mut params = get_env_params();
+        params.orchestrator_params.service_config.l1_da_mode = self.l1_da_mode;
+        params.orchestrator_params.service_config.proof_aggregation = self.proof_aggregation;
+        params.orchestrator_params.service_config.dev_mode = self.dev_mode;
 
         let provider_config =
             Arc::new(CloudProvider::try_from(params.aws_params.clone()).expect("Failed to create provider config"));
@@ -213,6 +242,9 @@ impl TestConfigBuilder {
             queue_type,
             storage_type,
             api_server_type,
+            l1_da_mode: _,
+            proof_aggregation: _,
+            dev_mode: _,
         } = self;
 
         let (_starknet_rpc_url, starknet_client, starknet_server) =
@@ -223,10 +255,14 @@ impl TestConfigBuilder {
         // init alerts
         let alerts = implement_client::init_alerts(alerts_type, &params.alert_params, provider_config.clone()).await;
 
-        let da_client = implement_client::init_da_client(da_client_type, &params.da_params).await;
+        let da_client = implement_client::init_da_client(da_client_type, &params.da_params, &params.db_params).await;
 
-        let settlement_client =
-            implement_client::init_settlement_client(settlement_client_type, &params.settlement_params).await;
+        let settlement_client = implement_client::init_settlement_client(
+            settlement_client_type,
+            &params.settlement_params,
+            &params.db_params,
+        )
+        .await;
         // External Dependencies
         let storage =
             implement_client::init_storage_client(storage_type, &params.storage_params, provider_config.clone()).await;
@@ -338,10 +374,19 @@ pub mod implement_client {
     implement_mock_client_conversion!(SettlementClient, SettlementClient);
     implement_mock_client_conversion!(DaClient, DaClient);
 
-    pub(crate) async fn init_da_client(service: ConfigType, da_params: &DAConfig) -> Box<dyn DaClient> {
+    pub(crate) async fn init_da_client(
+        service: ConfigType,
+        da_params: &DAConfig,
+        database_params: &DatabaseArgs,
+    ) -> Box<dyn DaClient> {
         match service {
             ConfigType::Mock(client) => client.into(),
-            ConfigType::Actual => Config::build_da_client(da_params).await,
+            ConfigType::Actual => {
+                let tx_store = Config::build_in_flight_tx_store(database_params)
+                    .await
+                    .expect("error creating in-flight transaction store");
+                Config::build_da_client(da_params, tx_store).await
+            }
             ConfigType::Dummy => Box::new(MockDaClient::new()),
         }
     }
@@ -349,11 +394,17 @@ pub mod implement_client {
     pub(crate) async fn init_settlement_client(
         service: ConfigType,
         settlement_cfg: &SettlementConfig,
+        database_params: &DatabaseArgs,
     ) -> Box<dyn SettlementClient> {
         match service {
             ConfigType::Mock(client) => client.into(),
             ConfigType::Actual => {
-                Config::build_settlement_client(settlement_cfg).await.expect("Failed to initialise settlement_client")
+                let tx_store = Config::build_in_flight_tx_store(database_params)
+                    .await
+                    .expect("error creating in-flight transaction store");
+                Config::build_settlement_client(settlement_cfg, tx_store)
+                    .await
+                    .expect("Failed to initialise settlement_client")
             }
             ConfigType::Dummy => Box::new(MockSettlementClient::new()),
         }
@@ -506,6 +557,8 @@ pub(crate) fn get_env_params() -> EnvParams {
     let da_params = DAConfig::Ethereum(EthereumDaValidatedArgs {
         ethereum_da_rpc_url: Url::parse(&get_env_var_or_panic("MADARA_ORCHESTRATOR_ETHEREUM_DA_RPC_URL"))
             .expect("Failed to parse MADARA_ORCHESTRATOR_ETHEREUM_RPC_URL"),
+        ethereum_da_private_key: get_env_var_optional_or_panic("MADARA_ORCHESTRATOR_ETHEREUM_DA_PRIVATE_KEY"),
+        tx_manager_config: TxManagerConfig::default(),
     });
 
     let alert_params = AlertArgs {
@@ -526,6 +579,8 @@ pub(crate) fn get_env_params() -> EnvParams {
             "MADARA_ORCHESTRATOR_STARKNET_OPERATOR_ADDRESS",
         ))
         .expect("Invalid Starknet operator address"),
+        dev_mode: false,
+        tx_manager_config: TxManagerConfig::default(),
     });
 
     let snos_config = SNOSParams {
@@ -559,6 +614,10 @@ pub(crate) fn get_env_params() -> EnvParams {
         max_concurrent_created_snos_jobs,
         max_concurrent_snos_jobs,
         max_concurrent_proving_jobs,
+        l1_da_mode: L1DataAvailabilityMode::default(),
+        proof_aggregation: false,
+        experimental_validium: false,
+        dev_mode: false,
     };
 
     let server_config = ServerParams {
@@ -5,6 +5,8 @@ use crate::types::batch::{Batch, BatchUpdates};
 use crate::types::jobs::job_updates::JobItemUpdates;
 use crate::types::jobs::metadata::JobSpecificMetadata;
 use crate::types::jobs::types::{JobStatus, JobType};
+use alloy::primitives::{Address, B256};
+use orchestrator_ethereum_settlement_client::tx_manager::{InFlightTx, TxFees};
 use rstest::*;
 
 #[rstest]
@@ -268,6 +270,28 @@ async fn database_test_get_latest_batch(
     assert_eq!(latest_batch, batch3);
 }
 
+#[rstest]
+#[tokio::test]
+async fn database_test_get_batch_by_index(
+    #[from(build_batch)]
+    #[with(1, 100, 200)]
+    batch1: Batch,
+    #[from(build_batch)]
+    #[with(2, 201, 300)]
+    batch2: Batch,
+) {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let config = services.config;
+    let database_client = config.database();
+
+    database_client.create_batch(batch1.clone()).await.unwrap();
+    database_client.create_batch(batch2.clone()).await.unwrap();
+
+    assert_eq!(database_client.get_batch_by_index(1).await.unwrap(), Some(batch1));
+    assert_eq!(database_client.get_batch_by_index(2).await.unwrap(), Some(batch2));
+    assert_eq!(database_client.get_batch_by_index(3).await.unwrap(), None);
+}
+
 #[rstest]
 #[tokio::test]
 async fn database_test_update_batch(
@@ -323,3 +347,103 @@ async fn database_test_create_batch(
     let retrieved_batch = database_client.get_latest_batch().await.unwrap().unwrap();
     assert_eq!(retrieved_batch, batch);
 }
+
+/// Test for `get_jobs` operation in database trait.
+/// Block jobs match on their block number and state transition jobs on the blocks they settle.
+#[rstest]
+#[tokio::test]
+async fn database_get_jobs_by_block_range_works() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let config = services.config;
+    let database_client = config.database();
+
+    let snos_job = build_job_item(JobType::SnosRun, JobStatus::Completed, 5);
+    let snos_job_out_of_range = build_job_item(JobType::SnosRun, JobStatus::Completed, 9);
+    let mut state_transition_job = build_job_item(JobType::StateTransition, JobStatus::Failed, 1);
+    if let JobSpecificMetadata::StateUpdate(ref mut metadata) = state_transition_job.metadata.specific {
+        metadata.blocks_to_settle = vec![2, 3, 4];
+    }
+
+    database_client.create_job(snos_job.clone()).await.unwrap();
+    database_client.create_job(snos_job_out_of_range.clone()).await.unwrap();
+    database_client.create_job(state_transition_job.clone()).await.unwrap();
+
+    let jobs = database_client.get_jobs(None, None, Some((4, 6)), 0, None).await.unwrap();
+    assert_eq!(jobs.len(), 2);
+    assert!(jobs.contains(&snos_job));
+    assert!(jobs.contains(&state_transition_job));
+
+    let jobs = database_client.get_jobs(None, Some(JobStatus::Failed), Some((4, 6)), 0, None).await.unwrap();
+    assert_eq!(jobs, vec![state_transition_job]);
+
+    let jobs = database_client.get_jobs(Some(JobType::SnosRun), None, None, 1, Some(5)).await.unwrap();
+    assert_eq!(jobs.len(), 1);
+}
+
+/// Test for `pause_job_creation` and `resume_job_creation` operations in database trait.
+/// Pausing twice keeps a single entry and resuming removes it.
+#[rstest]
+#[tokio::test]
+async fn database_pause_and_resume_job_creation_works() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let config = services.config;
+    let database_client = config.database();
+
+    assert!(database_client.get_paused_job_types().await.unwrap().is_empty());
+
+    database_client.pause_job_creation(JobType::DataSubmission).await.unwrap();
+    database_client.pause_job_creation(JobType::DataSubmission).await.unwrap();
+    database_client.pause_job_creation(JobType::StateTransition).await.unwrap();
+
+    let paused = database_client.get_paused_job_types().await.unwrap();
+    assert_eq!(paused.len(), 2);
+    assert!(paused.contains(&JobType::DataSubmission));
+    assert!(paused.contains(&JobType::StateTransition));
+
+    database_client.resume_job_creation(JobType::DataSubmission).await.unwrap();
+    assert_eq!(database_client.get_paused_job_types().await.unwrap(), vec![JobType::StateTransition]);
+}
+
+fn build_in_flight_tx(sender: Address, nonce: u64, request_hash: B256) -> InFlightTx {
+    InFlightTx {
+        sender,
+        request_hash,
+        nonce,
+        gas_limit: 100_000,
+        fees: TxFees { max_fee_per_gas: u128::MAX, max_priority_fee_per_gas: 1, max_fee_per_blob_gas: Some(2) },
+        tx_hashes: vec![B256::repeat_byte(nonce as u8)],
+        fee_bumps: 0,
+        last_broadcast: 1_700_000_000,
+    }
+}
+
+/// Test for the in-flight transaction operations in database trait.
+/// Transactions are scoped to their sender, replaced by nonce and pruned once their nonce is used.
+#[rstest]
+#[tokio::test]
+async fn database_in_flight_txs_works() {
+    let services = TestConfigBuilder::new().configure_database(ConfigType::Actual).build().await;
+    let config = services.config;
+    let database_client = config.database();
+    let (sender, other_sender) = (Address::repeat_byte(1), Address::repeat_byte(2));
+
+    let mut tx = build_in_flight_tx(sender, 5, B256::repeat_byte(0xaa));
+    database_client.upsert_in_flight_tx(&tx).await.unwrap();
+    database_client.upsert_in_flight_tx(&build_in_flight_tx(sender, 6, B256::repeat_byte(0xbb))).await.unwrap();
+    database_client.upsert_in_flight_tx(&build_in_flight_tx(other_sender, 5, B256::repeat_byte(0xaa))).await.unwrap();
+    assert_eq!(database_client.get_in_flight_tx(sender, tx.request_hash).await.unwrap(), Some(tx.clone()));
+
+    // A fee bump replaces the transaction with the same nonce
+    tx.fee_bumps = 1;
+    tx.tx_hashes.push(B256::repeat_byte(0xcc));
+    database_client.upsert_in_flight_tx(&tx).await.unwrap();
+    assert_eq!(database_client.get_in_flight_tx(sender, tx.request_hash).await.unwrap(), Some(tx.clone()));
+
+    database_client.delete_in_flight_txs_below_nonce(sender, 6).await.unwrap();
+    assert_eq!(database_client.get_in_flight_tx(sender, tx.request_hash).await.unwrap(), None);
+    assert!(database_client.get_in_flight_tx(sender, B256::repeat_byte(0xbb)).await.unwrap().is_some());
+    assert!(database_client.get_in_flight_tx(other_sender, tx.request_hash).await.unwrap().is_some());
+
+    database_client.delete_in_flight_tx(sender, 6).await.unwrap();
+    assert_eq!(database_client.get_in_flight_tx(sender, B256::repeat_byte(0xbb)).await.unwrap(), None);
+}
@@ -0,0 +1,154 @@
+use bytes::Bytes;
+use mockall::predicate::eq;
+use orchestrator_prover_client_interface::{MockProverClient, Task, TaskStatus};
+use rstest::*;
+
+use super::super::common::default_job_item;
+use crate::core::client::storage::MockStorageClient;
+use crate::tests::config::TestConfigBuilder;
+use crate::types::jobs::job_item::JobItem;
+use crate::types::jobs::metadata::{AggregatorMetadata, CommonMetadata, JobMetadata, JobSpecificMetadata};
+use crate::types::jobs::status::JobVerificationStatus;
+use crate::types::jobs::types::{JobStatus, JobType};
+use crate::worker::event_handler::jobs::aggregator::AggregatorJobHandler;
+use crate::worker::event_handler::jobs::JobHandlerTrait;
+
+fn aggregator_metadata() -> AggregatorMetadata {
+    AggregatorMetadata {
+        batch_num: 1,
+        start_block: 0,
+        end_block: 1,
+        proving_task_ids: vec!["task_0".to_string(), "task_1".to_string()],
+        program_output_paths: vec!["0/program_output.txt".to_string(), "1/program_output.txt".to_string()],
+        squashed_state_update_path: "state_update/batch/1.json".to_string(),
+        blob_data_path: None,
+        aggregator_output_path: Some("aggregator/1/program_output.txt".to_string()),
+        download_proof: Some("aggregator/1/proof.json".to_string()),
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_create_job() {
+    let metadata = JobMetadata {
+        common: CommonMetadata::default(),
+        specific: JobSpecificMetadata::Aggregator(aggregator_metadata()),
+    };
+
+    let job = AggregatorJobHandler.create_job(String::from("1"), metadata).await.unwrap();
+
+    assert_eq!(job.job_type, JobType::Aggregator, "job_type should be Aggregator");
+    assert!(!(job.id.is_nil()), "id should not be nil");
+    assert_eq!(job.status, JobStatus::Created, "status should be Created");
+    assert_eq!(job.version, 0_i32, "version should be 0");
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_create_job_fails_without_all_proving_tasks() {
+    let mut aggregator_metadata = aggregator_metadata();
+    aggregator_metadata.proving_task_ids.pop();
+    let metadata = JobMetadata {
+        common: CommonMetadata::default(),
+        specific: JobSpecificMetadata::Aggregator(aggregator_metadata),
+    };
+
+    assert!(AggregatorJobHandler.create_job(String::from("1"), metadata).await.is_err());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_process_job(#[from(default_job_item)] mut job_item: JobItem) {
+    let mut storage = MockStorageClient::new();
+    for (block, path) in aggregator_metadata().program_output_paths.into_iter().enumerate() {
+        let program_output = bincode::serialize(&vec![[block as u8; 32]]).unwrap();
+        storage.expect_get_data().with(eq(path)).times(1).return_once(move |_| Ok(Bytes::from(program_output)));
+    }
+    let mut prover_client = MockProverClient::new();
+    prover_client
+        .expect_submit_task()
+        .withf(|task, _| match task {
+            Task::Aggregation(aggregation) => {
+                aggregation.task_ids == vec!["task_0".to_string(), "task_1".to_string()]
+                    && aggregation.program_outputs == vec![vec![[0; 32]], vec![[1; 32]]]
+            }
+            _ => false,
+        })
+        .times(1)
+        .returning(|_, _| Ok("aggregation_task".to_string()));
+
+    let services = TestConfigBuilder::new()
+        .configure_prover_client(prover_client.into())
+        .configure_storage_client(storage.into())
+        .build()
+        .await;
+
+    job_item.job_type = JobType::Aggregator;
+    job_item.metadata.specific = JobSpecificMetadata::Aggregator(aggregator_metadata());
+
+    assert_eq!(
+        AggregatorJobHandler.process_job(services.config, &mut job_item).await.unwrap(),
+        "aggregation_task".to_string()
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_verify_job_stores_aggregator_output(#[from(default_job_item)] mut job_item: JobItem) {
+    let aggregator_output = vec![[1; 32], [2; 32]];
+    let mut prover_client = MockProverClient::new();
+    prover_client.expect_get_task_status().times(1).returning(|_, _, _| Ok(TaskStatus::Succeeded));
+    prover_client.expect_get_aggregator_output().with(eq("0")).times(1).return_once({
+        let aggregator_output = aggregator_output.clone();
+        move |_| Ok(aggregator_output)
+    });
+    prover_client.expect_get_proof().times(1).returning(|_| Ok(Some(b"proof".to_vec())));
+    let mut storage = MockStorageClient::new();
+    storage
+        .expect_put_data()
+        .with(
+            eq(Bytes::from(bincode::serialize(&aggregator_output).unwrap())),
+            eq("aggregator/1/program_output.txt".to_string()),
+        )
+        .times(1)
+        .returning(|_, _| Ok(()));
+    storage
+        .expect_put_data()
+        .with(eq(Bytes::from_static(b"proof")), eq("aggregator/1/proof.json".to_string()))
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let services = TestConfigBuilder::new()
+        .configure_prover_client(prover_client.into())
+        .configure_storage_client(storage.into())
+        .build()
+        .await;
+
+    job_item.job_type = JobType::Aggregator;
+    job_item.metadata.specific = JobSpecificMetadata::Aggregator(aggregator_metadata());
+
+    assert_eq!(
+        AggregatorJobHandler.verify_job(services.config, &mut job_item).await.unwrap(),
+        JobVerificationStatus::Verified
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_verify_job_rejected_when_aggregation_fails(#[from(default_job_item)] mut job_item: JobItem) {
+    let mut prover_client = MockProverClient::new();
+    prover_client
+        .expect_get_task_status()
+        .times(1)
+        .returning(|_, _, _| Ok(TaskStatus::Failed("aggregator crashed".to_string())));
+
+    let services = TestConfigBuilder::new().configure_prover_client(prover_client.into()).build().await;
+
+    job_item.job_type = JobType::Aggregator;
+    job_item.metadata.specific = JobSpecificMetadata::Aggregator(aggregator_metadata());
+
+    assert!(matches!(
+        AggregatorJobHandler.verify_job(services.config, &mut job_item).await.unwrap(),
+        JobVerificationStatus::Rejected(reason) if reason.contains("aggregator crashed")
+    ));
+}
@@ -1,13 +1,14 @@
 use assert_matches::assert_matches;
 use chrono::{SubsecRound, Utc};
 use color_eyre::eyre::eyre;
-use mockall::predicate::always;
+use mockall::predicate::{always, eq};
 use orchestrator_da_client_interface::MockDaClient;
 use rstest::rstest;
 use serde_json::json;
 use starknet::core::types::{Felt, MaybePendingStateUpdate, PendingStateUpdate, StateDiff};
 use uuid::Uuid;
 
+use crate::cli::service::L1DataAvailabilityMode;
 use crate::error::job::da_error::DaError;
 use crate::error::job::JobError;
 use crate::tests::config::{ConfigType, TestConfigBuilder};
@@ -71,6 +72,8 @@ async fn test_da_job_process_job_failure_on_small_blob_size(
             block_number,
             blob_data_path: Some(format!("{}/{}", block_number, BLOB_DATA_FILE_NAME)),
             tx_hash: None,
+            da_commitment: None,
+            blob_versioned_hashes: Vec::new(),
         }),
     };
 
@@ -145,6 +148,8 @@ async fn test_da_job_process_job_failure_on_pending_block() {
             block_number,
             blob_data_path: Some(format!("{}/{}", block_number, BLOB_DATA_FILE_NAME)),
             tx_hash: None,
+            da_commitment: None,
+            blob_versioned_hashes: Vec::new(),
         }),
     };
 
@@ -208,6 +213,7 @@ async fn test_da_job_process_job_success(
     // Mocking DA client calls
     let mut da_client = MockDaClient::new();
     da_client.expect_publish_state_diff().with(always(), always()).returning(|_, _| Ok("Done".to_string()));
+    da_client.expect_blob_versioned_hashes().with(eq("Done")).returning(|_| Ok(vec![[0x01; 32]]));
     da_client.expect_max_blob_per_txn().with().returning(|| 6);
     da_client.expect_max_bytes_per_blob().with().returning(|| 131072);
 
@@ -239,6 +245,8 @@ async fn test_da_job_process_job_success(
             block_number,
             blob_data_path: Some(format!("{}/{}", block_number, BLOB_DATA_FILE_NAME)),
             tx_hash: None,
+            da_commitment: None,
+            blob_versioned_hashes: Vec::new(),
         }),
     };
 
@@ -267,3 +275,66 @@ async fn test_da_job_process_job_success(
 
     state_update_mock.assert();
 }
+
+/// Tests DA Job processing with an external DA layer.
+/// The state diff is published to the DA client and the commitment it returns is stored in the job metadata,
+/// for the state update job to send it to the settlement layer.
+#[rstest]
+#[tokio::test]
+async fn test_da_job_process_job_external_da_stores_commitment() {
+    let internal_id = "640641";
+    let mut da_client = MockDaClient::new();
+    da_client.expect_publish_state_diff().with(always(), always()).times(1).returning(|_, _| Ok("7:0xab".to_string()));
+    da_client.expect_da_commitment().with(eq("7:0xab")).times(1).returning(|_| Ok(Some([0xab; 32])));
+    da_client.expect_blob_versioned_hashes().never();
+    da_client.expect_max_blob_per_txn().with().returning(|| 1);
+    da_client.expect_max_bytes_per_blob().with().returning(|| 1_500_000);
+
+    let services = TestConfigBuilder::new()
+        .configure_starknet_client(ConfigType::Actual)
+        .configure_storage_client(ConfigType::Actual)
+        .configure_da_client(da_client.into())
+        .configure_l1_da_mode(L1DataAvailabilityMode::External)
+        .build()
+        .await;
+    let server = services.starknet_server.unwrap();
+
+    let state_update = read_state_update_from_file("src/tests/jobs/da_job/test_data/state_update/640641.txt")
+        .expect("issue while reading");
+    let state_update = serde_json::to_value(&state_update).unwrap();
+    let response = json!({ "id": 1,"jsonrpc":"2.0","result": state_update });
+    get_nonce_attached(&server, "src/tests/jobs/da_job/test_data/nonces/640641.txt");
+    server.mock(|when, then| {
+        when.path("/").body_includes("starknet_getStateUpdate");
+        then.status(200).body(serde_json::to_vec(&response).unwrap());
+    });
+
+    let block_number = internal_id.parse::<u64>().unwrap();
+    let mut job = JobItem {
+        id: Uuid::default(),
+        internal_id: internal_id.to_string(),
+        job_type: JobType::DataSubmission,
+        status: JobStatus::Created,
+        external_id: ExternalId::String(internal_id.to_string().into_boxed_str()),
+        metadata: JobMetadata {
+            common: CommonMetadata::default(),
+            specific: JobSpecificMetadata::Da(DaMetadata {
+                block_number,
+                blob_data_path: Some(format!("{}/{}", block_number, BLOB_DATA_FILE_NAME)),
+                tx_hash: None,
+                da_commitment: None,
+                blob_versioned_hashes: Vec::new(),
+            }),
+        },
+        version: 0,
+        created_at: Utc::now().round_subsecs(0),
+        updated_at: Utc::now().round_subsecs(0),
+    };
+
+    let external_id = DAJobHandler.process_job(services.config, &mut job).await.unwrap();
+    assert_eq!(external_id, "7:0xab");
+
+    let da_metadata: DaMetadata = job.metadata.specific.try_into().unwrap();
+    assert_eq!(da_metadata.tx_hash, Some("7:0xab".to_string()));
+    assert_eq!(da_metadata.da_commitment, Some(hex::encode([0xab; 32])));
+}
@@ -10,6 +10,9 @@ use crate::tests::common::MessagePayloadType;
 use crate::tests::config::{ConfigType, TestConfigBuilder};
 use crate::tests::utils::build_job_item;
 
+#[cfg(test)]
+pub mod aggregator_job;
+
 #[cfg(test)]
 pub mod da_job;
 
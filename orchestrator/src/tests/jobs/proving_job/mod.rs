@@ -16,7 +16,7 @@ use uuid::Uuid;
 use super::super::common::default_job_item;
 use crate::core::client::storage::MockStorageClient;
 use crate::tests::config::TestConfigBuilder;
-use crate::types::constant::CAIRO_PIE_FILE_NAME;
+use crate::types::constant::{CAIRO_PIE_FILE_NAME, PROOF_FILE_NAME};
 use crate::types::jobs::job_item::JobItem;
 use crate::types::jobs::metadata::{
     CommonMetadata, JobMetadata, JobSpecificMetadata, ProvingInputType, ProvingMetadata,
@@ -62,6 +62,32 @@ async fn test_verify_job(#[from(default_job_item)] mut job_item: JobItem) {
     assert!(ProvingJobHandler.verify_job(services.config, &mut job_item).await.is_ok());
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_verify_job_stores_proof(#[from(default_job_item)] mut job_item: JobItem) {
+    let proof_path = format!("0/{}", PROOF_FILE_NAME);
+    let mut prover_client = MockProverClient::new();
+    prover_client.expect_get_task_status().times(1).returning(|_, _, _| Ok(TaskStatus::Succeeded));
+    prover_client.expect_get_proof().times(1).returning(|_| Ok(Some(b"proof".to_vec())));
+    let mut storage = MockStorageClient::new();
+    storage
+        .expect_put_data()
+        .with(eq(Bytes::from_static(b"proof")), eq(proof_path.clone()))
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let services = TestConfigBuilder::new()
+        .configure_prover_client(prover_client.into())
+        .configure_storage_client(storage.into())
+        .build()
+        .await;
+
+    job_item.metadata.specific =
+        JobSpecificMetadata::Proving(ProvingMetadata { download_proof: Some(proof_path), ..Default::default() });
+
+    assert!(ProvingJobHandler.verify_job(services.config, &mut job_item).await.is_ok());
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_process_job() {
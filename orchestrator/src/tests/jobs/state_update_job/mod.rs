@@ -2,6 +2,7 @@ use std::fs;
 use std::fs::read_to_string;
 use std::path::PathBuf;
 
+use alloy::primitives::{keccak256, U256};
 use assert_matches::assert_matches;
 use bytes::Bytes;
 use color_eyre::eyre::eyre;
@@ -9,19 +10,23 @@ use httpmock::prelude::*;
 use lazy_static::lazy_static;
 use mockall::predicate::{always, eq};
 use num_bigint::BigUint;
+use orchestrator_da_client_interface::{DaVerificationStatus, MockDaClient};
 use orchestrator_settlement_client_interface::MockSettlementClient;
 use rstest::*;
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 use url::Url;
 
+use crate::cli::service::L1DataAvailabilityMode;
 use crate::core::client::storage::MockStorageClient;
 use crate::error::job::state_update::StateUpdateError;
 use crate::error::job::JobError;
 use crate::tests::common::default_job_item;
 use crate::tests::config::{ConfigType, TestConfigBuilder};
 use crate::types::constant::{BLOB_DATA_FILE_NAME, PROGRAM_OUTPUT_FILE_NAME, SNOS_OUTPUT_FILE_NAME};
-use crate::types::jobs::metadata::{CommonMetadata, JobMetadata, JobSpecificMetadata, StateUpdateMetadata};
+use crate::types::jobs::metadata::{
+    AggregatedSettlement, CommonMetadata, JobMetadata, JobSpecificMetadata, StateUpdateMetadata,
+};
 use crate::types::jobs::types::{JobStatus, JobType};
 use crate::worker::event_handler::jobs::state_update::StateUpdateJobHandler;
 use crate::worker::event_handler::jobs::JobHandlerTrait;
@@ -50,6 +55,11 @@ async fn test_process_job_attempt_not_present_fails() {
         blob_data_paths: vec![],
         last_failed_block_no: None,
         tx_hashes: vec![],
+        da_tx_hashes: Vec::new(),
+        da_commitments: Vec::new(),
+        blob_versioned_hashes: Vec::new(),
+        calldata_tx_hashes: Vec::new(),
+        aggregation: None,
     });
 
     let res = StateUpdateJobHandler.process_job(services.config, &mut job).await.unwrap_err();
@@ -170,6 +180,11 @@ async fn test_process_job_works(
             blob_data_paths,
             last_failed_block_no: failed_block_number,
             tx_hashes: Vec::new(), // Start with empty tx_hashes, they'll be populated during processing
+            da_tx_hashes: Vec::new(),
+            da_commitments: Vec::new(),
+            blob_versioned_hashes: Vec::new(),
+            calldata_tx_hashes: Vec::new(),
+            aggregation: None,
         }),
     };
 
@@ -200,6 +215,11 @@ async fn create_job_works() {
             blob_data_paths: vec![format!("1/{}", BLOB_DATA_FILE_NAME)],
             last_failed_block_no: None,
             tx_hashes: vec![],
+            da_tx_hashes: Vec::new(),
+            da_commitments: Vec::new(),
+            blob_versioned_hashes: Vec::new(),
+            calldata_tx_hashes: Vec::new(),
+            aggregation: None,
         }),
     };
 
@@ -225,7 +245,6 @@ async fn process_job_works_unit_test() {
     // Mock the latest block settled
     settlement_client.expect_get_last_settled_block().returning(|| Ok(Some(651052_u64)));
 
-    // TODO: have tests for update_state_calldata, only kzg for now
     let block_numbers = ["651053", "651054", "651055", "651056"];
     for block_no in block_numbers {
         let _state_diff: Vec<u8> = load_state_diff_file(block_no.parse::<u64>().unwrap()).await;
@@ -308,6 +327,11 @@ async fn process_job_works_unit_test() {
             blob_data_paths: block_numbers.iter().map(|block| format!("{}/{}", block, BLOB_DATA_FILE_NAME)).collect(),
             last_failed_block_no: None,
             tx_hashes: vec![],
+            da_tx_hashes: Vec::new(),
+            da_commitments: Vec::new(),
+            blob_versioned_hashes: Vec::new(),
+            calldata_tx_hashes: Vec::new(),
+            aggregation: None,
         }),
     };
 
@@ -318,6 +342,309 @@ async fn process_job_works_unit_test() {
     assert_eq!(StateUpdateJobHandler.process_job(services.config, &mut job).await.unwrap(), "651056".to_string())
 }
 
+#[rstest]
+#[case::first_attempt(Vec::new())]
+#[case::chunk_published_by_previous_attempt(vec!["0xda".to_string()])]
+#[tokio::test]
+async fn process_job_calldata_da_works(#[case] published_tx_hashes: Vec<String>) {
+    let mut settlement_client = MockSettlementClient::new();
+    let mut storage_client = MockStorageClient::new();
+    let block_no = "651053";
+
+    settlement_client.expect_get_last_settled_block().returning(|| Ok(Some(651052_u64)));
+    settlement_client.expect_get_nonce().returning(|| Ok(1));
+
+    // Same block as the blob tests, with the OS output switched to calldata DA.
+    let (state_diff, program_output) = mock_block_files(&mut storage_client, block_no, false);
+
+    let felts: Vec<[u8; 32]> = state_diff.chunks_exact(32).map(|felt| felt.try_into().unwrap()).collect();
+    let onchain_data_size = U256::from(felts.len()).to_be_bytes::<32>();
+    // The state diff fits in a single chunk, which is not published again when a previous attempt already did.
+    settlement_client
+        .expect_publish_state_diff_calldata()
+        .with(eq(felts.clone()), eq(0))
+        .times(if published_tx_hashes.is_empty() { 1 } else { 0 })
+        .returning(|_, _| Ok(Some("0xda".to_string())));
+    settlement_client.expect_publish_state_diff_calldata().with(eq(felts), eq(1)).times(1).returning(|_, _| Ok(None));
+    settlement_client
+        .expect_update_state_calldata()
+        .with(eq(program_output.clone()), eq(program_output), eq(keccak256(&state_diff).0), eq(onchain_data_size))
+        .times(1)
+        .returning(|_, _, _, _| Ok("0xbeef".to_string()));
+    settlement_client.expect_update_state_with_blobs().never();
+
+    let services = TestConfigBuilder::new()
+        .configure_settlement_client(settlement_client.into())
+        .configure_storage_client(storage_client.into())
+        .configure_l1_da_mode(L1DataAvailabilityMode::Calldata)
+        .build()
+        .await;
+
+    let metadata = JobMetadata {
+        common: CommonMetadata::default(),
+        specific: JobSpecificMetadata::StateUpdate(StateUpdateMetadata {
+            blocks_to_settle: vec![651053],
+            snos_output_paths: vec![format!("{}/{}", block_no, SNOS_OUTPUT_FILE_NAME)],
+            program_output_paths: vec![format!("{}/{}", block_no, PROGRAM_OUTPUT_FILE_NAME)],
+            blob_data_paths: vec![format!("{}/{}", block_no, BLOB_DATA_FILE_NAME)],
+            last_failed_block_no: None,
+            tx_hashes: vec![],
+            da_tx_hashes: Vec::new(),
+            da_commitments: Vec::new(),
+            blob_versioned_hashes: Vec::new(),
+            calldata_tx_hashes: vec![published_tx_hashes],
+            aggregation: None,
+        }),
+    };
+
+    let job = StateUpdateJobHandler.create_job(String::from("internal_id"), metadata).await.unwrap();
+    let mut job = services.config.database().create_job(job).await.unwrap();
+    assert_eq!(
+        StateUpdateJobHandler.process_job(services.config.clone(), &mut job).await.unwrap(),
+        "651053".to_string()
+    );
+    let state_metadata: StateUpdateMetadata = job.metadata.specific.try_into().unwrap();
+    assert_eq!(state_metadata.calldata_tx_hashes, vec![vec!["0xda".to_string()]]);
+
+    // The hash of the published chunk is persisted before the state update, so it isn't lost on a crash.
+    let stored_job = services.config.database().get_job_by_id(job.id).await.unwrap().unwrap();
+    let stored_metadata: StateUpdateMetadata = stored_job.metadata.specific.try_into().unwrap();
+    assert_eq!(stored_metadata.calldata_tx_hashes, vec![vec!["0xda".to_string()]]);
+}
+
+#[rstest]
+#[tokio::test]
+async fn process_job_references_published_blobs() {
+    let mut settlement_client = MockSettlementClient::new();
+    let mut storage_client = MockStorageClient::new();
+    let mut da_client = MockDaClient::new();
+    let block_no = "651053";
+    let blob_tx_hash = format!("0x{}", hex::encode([0xb1_u8; 32]));
+    let versioned_hash = [0x01_u8; 32];
+
+    settlement_client.expect_get_last_settled_block().returning(|| Ok(Some(651052_u64)));
+    settlement_client.expect_get_nonce().returning(|| Ok(1));
+
+    let (state_diff, program_output) = mock_block_files(&mut storage_client, block_no, true);
+
+    da_client
+        .expect_verify_inclusion()
+        .with(eq(blob_tx_hash.clone()))
+        .returning(|_| Ok(DaVerificationStatus::Verified));
+
+    // The blobs published by the DA client are referenced by their versioned hashes.
+    settlement_client
+        .expect_update_state_with_published_blobs()
+        .with(eq(program_output), eq(vec![state_diff]), eq(vec![versioned_hash]), always())
+        .times(1)
+        .returning(|_, _, _, _| Ok("0xbeef".to_string()));
+    settlement_client.expect_update_state_with_blobs().never();
+
+    let services = TestConfigBuilder::new()
+        .configure_settlement_client(settlement_client.into())
+        .configure_storage_client(storage_client.into())
+        .configure_da_client(da_client.into())
+        .build()
+        .await;
+
+    let metadata = JobMetadata {
+        common: CommonMetadata::default(),
+        specific: JobSpecificMetadata::StateUpdate(StateUpdateMetadata {
+            blocks_to_settle: vec![651053],
+            snos_output_paths: vec![format!("{}/{}", block_no, SNOS_OUTPUT_FILE_NAME)],
+            program_output_paths: vec![format!("{}/{}", block_no, PROGRAM_OUTPUT_FILE_NAME)],
+            blob_data_paths: vec![format!("{}/{}", block_no, BLOB_DATA_FILE_NAME)],
+            last_failed_block_no: None,
+            tx_hashes: vec![],
+            da_tx_hashes: vec![Some(blob_tx_hash)],
+            da_commitments: Vec::new(),
+            blob_versioned_hashes: vec![vec![hex::encode(versioned_hash)]],
+            calldata_tx_hashes: Vec::new(),
+            aggregation: None,
+        }),
+    };
+
+    let mut job = StateUpdateJobHandler.create_job(String::from("internal_id"), metadata).await.unwrap();
+    assert_eq!(StateUpdateJobHandler.process_job(services.config, &mut job).await.unwrap(), "651053".to_string());
+}
+
+#[rstest]
+#[tokio::test]
+async fn process_job_settles_aggregated_batch_in_one_transaction() {
+    let mut settlement_client = MockSettlementClient::new();
+    let mut storage_client = MockStorageClient::new();
+    let aggregator_output = vec![[1_u8; 32], [2_u8; 32]];
+    let batch_blob_data = vec![3_u8; 64];
+
+    settlement_client.expect_get_last_settled_block().returning(|| Ok(Some(9_u64)));
+    settlement_client.expect_get_nonce().returning(|| Ok(4));
+    storage_client.expect_get_data().with(eq("aggregator/1/program_output.txt".to_string())).times(1).return_once({
+        let aggregator_output = bincode::serialize(&aggregator_output).unwrap();
+        move |_| Ok(Bytes::from(aggregator_output))
+    });
+    storage_client.expect_get_data().with(eq("aggregator/1/blob_data.txt".to_string())).times(1).return_once({
+        let batch_blob_data = batch_blob_data.clone();
+        move |_| Ok(Bytes::from(batch_blob_data))
+    });
+    // The outputs of each block are replaced by the aggregated ones
+    storage_client.expect_get_data().with(eq("10/program_output.txt".to_string())).never();
+    settlement_client
+        .expect_update_state_with_blobs()
+        .with(eq(aggregator_output), eq(vec![batch_blob_data]), eq(4))
+        .times(1)
+        .returning(|_, _, _| Ok("0xbatch".to_string()));
+
+    let services = TestConfigBuilder::new()
+        .configure_settlement_client(settlement_client.into())
+        .configure_storage_client(storage_client.into())
+        .build()
+        .await;
+
+    let blocks_to_settle = vec![10, 11, 12];
+    let metadata = JobMetadata {
+        common: CommonMetadata::default(),
+        specific: JobSpecificMetadata::StateUpdate(StateUpdateMetadata {
+            snos_output_paths: blocks_to_settle
+                .iter()
+                .map(|block| format!("{block}/{SNOS_OUTPUT_FILE_NAME}"))
+                .collect(),
+            program_output_paths: blocks_to_settle
+                .iter()
+                .map(|block| format!("{block}/{PROGRAM_OUTPUT_FILE_NAME}"))
+                .collect(),
+            blob_data_paths: blocks_to_settle.iter().map(|block| format!("{block}/{BLOB_DATA_FILE_NAME}")).collect(),
+            blocks_to_settle,
+            aggregation: Some(AggregatedSettlement {
+                batch_num: 1,
+                aggregator_output_path: "aggregator/1/program_output.txt".to_string(),
+                blob_data_path: "aggregator/1/blob_data.txt".to_string(),
+            }),
+            ..Default::default()
+        }),
+    };
+
+    let mut job = StateUpdateJobHandler.create_job(String::from("10"), metadata).await.unwrap();
+    assert_eq!(StateUpdateJobHandler.process_job(services.config, &mut job).await.unwrap(), "12".to_string());
+    let state_metadata: StateUpdateMetadata = job.metadata.specific.try_into().unwrap();
+    assert_eq!(state_metadata.tx_hashes, vec!["0xbatch".to_string()]);
+}
+
+#[rstest]
+#[tokio::test]
+async fn process_job_external_da_works() {
+    let mut settlement_client = MockSettlementClient::new();
+    let mut storage_client = MockStorageClient::new();
+    let block_no = "651053";
+    let da_commitment = [0xab_u8; 32];
+
+    settlement_client.expect_get_last_settled_block().returning(|| Ok(Some(651052_u64)));
+    settlement_client.expect_get_nonce().returning(|| Ok(1));
+
+    // Same block as the blob tests, with the OS output switched to off-chain DA.
+    let (state_diff, program_output) = mock_block_files(&mut storage_client, block_no, false);
+    let onchain_data_size = U256::from(state_diff.len() / 32).to_be_bytes::<32>();
+
+    // The state diff is already on the DA layer, only its commitment is sent to the settlement layer.
+    settlement_client.expect_publish_state_diff_calldata().never();
+    settlement_client
+        .expect_update_state_calldata()
+        .with(eq(program_output.clone()), eq(program_output), eq(da_commitment), eq(onchain_data_size))
+        .times(1)
+        .returning(|_, _, _, _| Ok("0xbeef".to_string()));
+    settlement_client.expect_update_state_with_blobs().never();
+
+    let services = TestConfigBuilder::new()
+        .configure_settlement_client(settlement_client.into())
+        .configure_storage_client(storage_client.into())
+        .configure_l1_da_mode(L1DataAvailabilityMode::External)
+        .build()
+        .await;
+
+    let metadata = JobMetadata {
+        common: CommonMetadata::default(),
+        specific: JobSpecificMetadata::StateUpdate(StateUpdateMetadata {
+            blocks_to_settle: vec![651053],
+            snos_output_paths: vec![format!("{}/{}", block_no, SNOS_OUTPUT_FILE_NAME)],
+            program_output_paths: vec![format!("{}/{}", block_no, PROGRAM_OUTPUT_FILE_NAME)],
+            blob_data_paths: vec![format!("{}/{}", block_no, BLOB_DATA_FILE_NAME)],
+            last_failed_block_no: None,
+            tx_hashes: vec![],
+            da_tx_hashes: Vec::new(),
+            da_commitments: vec![Some(hex::encode(da_commitment))],
+            blob_versioned_hashes: Vec::new(),
+            calldata_tx_hashes: Vec::new(),
+            aggregation: None,
+        }),
+    };
+
+    let mut job = StateUpdateJobHandler.create_job(String::from("internal_id"), metadata).await.unwrap();
+    assert_eq!(StateUpdateJobHandler.process_job(services.config, &mut job).await.unwrap(), "651053".to_string());
+}
+
+#[rstest]
+#[tokio::test]
+async fn process_job_validium_registers_dac_attestation() {
+    let mut settlement_client = MockSettlementClient::new();
+    let mut storage_client = MockStorageClient::new();
+    let mut da_client = MockDaClient::new();
+    let block_no = "651053";
+    let data_hash = [0xab_u8; 32];
+    let da_tx_hash = format!("0x{}", hex::encode(data_hash));
+    let attestation = vec![0x1b_u8; 2 * 65];
+
+    settlement_client.expect_get_last_settled_block().returning(|| Ok(Some(651052_u64)));
+    settlement_client.expect_get_nonce().returning(|| Ok(1));
+
+    // Validium blocks are executed with kzg DA, the OS output of the blob tests is used as is.
+    let (state_diff, program_output) = mock_block_files(&mut storage_client, block_no, true);
+    let onchain_data_size = U256::from(state_diff.len() / 32).to_be_bytes::<32>();
+
+    da_client.expect_verify_inclusion().with(eq(da_tx_hash.clone())).returning(|_| Ok(DaVerificationStatus::Verified));
+    let attestation_clone = attestation.clone();
+    da_client
+        .expect_da_attestation()
+        .with(eq(da_tx_hash.clone()))
+        .times(1)
+        .returning(move |_| Ok(Some(attestation_clone.clone())));
+
+    // The attestation is sent along with the state update, the OS output is not settled through the calldata path.
+    settlement_client
+        .expect_update_state_with_dac_attestation()
+        .with(eq(program_output), eq(data_hash), eq(onchain_data_size), eq(attestation))
+        .times(1)
+        .returning(|_, _, _, _| Ok("0xbeef".to_string()));
+    settlement_client.expect_update_state_calldata().never();
+    settlement_client.expect_update_state_with_blobs().never();
+
+    let services = TestConfigBuilder::new()
+        .configure_settlement_client(settlement_client.into())
+        .configure_storage_client(storage_client.into())
+        .configure_da_client(da_client.into())
+        .configure_l1_da_mode(L1DataAvailabilityMode::Validium)
+        .build()
+        .await;
+
+    let metadata = JobMetadata {
+        common: CommonMetadata::default(),
+        specific: JobSpecificMetadata::StateUpdate(StateUpdateMetadata {
+            blocks_to_settle: vec![651053],
+            snos_output_paths: vec![format!("{}/{}", block_no, SNOS_OUTPUT_FILE_NAME)],
+            program_output_paths: vec![format!("{}/{}", block_no, PROGRAM_OUTPUT_FILE_NAME)],
+            blob_data_paths: vec![format!("{}/{}", block_no, BLOB_DATA_FILE_NAME)],
+            last_failed_block_no: None,
+            tx_hashes: vec![],
+            da_tx_hashes: vec![Some(da_tx_hash)],
+            da_commitments: vec![Some(hex::encode(data_hash))],
+            blob_versioned_hashes: Vec::new(),
+            calldata_tx_hashes: Vec::new(),
+            aggregation: None,
+        }),
+    };
+
+    let mut job = StateUpdateJobHandler.create_job(String::from("internal_id"), metadata).await.unwrap();
+    assert_eq!(StateUpdateJobHandler.process_job(services.config, &mut job).await.unwrap(), "651053".to_string());
+}
+
 #[rstest]
 #[case(vec![651052, 651054, 651051, 651056], "numbers aren't sorted in increasing order")]
 #[case(vec![651052, 651052, 651052, 651052], "Duplicated block numbers")]
@@ -355,6 +682,11 @@ async fn process_job_invalid_inputs_errors(#[case] block_numbers: Vec<u64>, #[ca
             blob_data_paths,
             last_failed_block_no: None,
             tx_hashes: vec![],
+            da_tx_hashes: Vec::new(),
+            da_commitments: Vec::new(),
+            blob_versioned_hashes: Vec::new(),
+            calldata_tx_hashes: Vec::new(),
+            aggregation: None,
         }),
     };
 
@@ -412,6 +744,11 @@ async fn process_job_invalid_input_gap_panics() {
             ],
             last_failed_block_no: None,
             tx_hashes: vec![],
+            da_tx_hashes: Vec::new(),
+            da_commitments: Vec::new(),
+            blob_versioned_hashes: Vec::new(),
+            calldata_tx_hashes: Vec::new(),
+            aggregation: None,
         }),
     };
 
@@ -429,6 +766,46 @@ async fn process_job_invalid_input_gap_panics() {
 
 // ==================== Utility functions ===========================
 
+/// Serve the SNOS output, the state diff and the program output of `block_no` from the storage mock, with the DA
+/// mode of the OS output set by `use_kzg_da`. Returns the state diff and the program output.
+fn mock_block_files(
+    storage_client: &mut MockStorageClient,
+    block_no: &str,
+    use_kzg_da: bool,
+) -> (Vec<u8>, Vec<[u8; 32]>) {
+    let test_data_dir = CURRENT_PATH.join(format!("src/tests/jobs/state_update_job/test_data/{}", block_no));
+
+    let mut snos_output_data = fs::read_to_string(test_data_dir.join(SNOS_OUTPUT_FILE_NAME))
+        .expect("Failed to read the snos output data json file");
+    if !use_kzg_da {
+        snos_output_data = snos_output_data.replace(r#""use_kzg_da": "0x1""#, r#""use_kzg_da": "0x0""#);
+    }
+    storage_client
+        .expect_get_data()
+        .with(eq(format!("{}/{}", block_no, SNOS_OUTPUT_FILE_NAME)))
+        .returning(move |_| Ok(Bytes::from(snos_output_data.clone())));
+
+    let state_diff = hex_string_to_u8_vec(
+        &fs::read_to_string(test_data_dir.join(BLOB_DATA_FILE_NAME)).expect("Failed to read the blob data txt file"),
+    )
+    .unwrap();
+    let state_diff_clone = state_diff.clone();
+    storage_client
+        .expect_get_data()
+        .with(eq(format!("{}/{}", block_no, BLOB_DATA_FILE_NAME)))
+        .returning(move |_| Ok(Bytes::from(state_diff_clone.clone())));
+
+    let program_output =
+        read_file_to_vec_u8_32(test_data_dir.join(PROGRAM_OUTPUT_FILE_NAME).to_str().unwrap()).unwrap();
+    let program_output_clone = program_output.clone();
+    storage_client
+        .expect_get_data()
+        .with(eq(format!("{}/{}", block_no, PROGRAM_OUTPUT_FILE_NAME)))
+        .returning(move |_| Ok(Bytes::from(bincode::serialize(&program_output_clone).unwrap())));
+
+    (state_diff, program_output)
+}
+
 async fn load_state_diff_file(block_no: u64) -> Vec<u8> {
     let file_path = format!("src/tests/jobs/state_update_job/test_data/{}/{}", block_no, BLOB_DATA_FILE_NAME);
     let file_data = fs::read_to_string(file_path).expect("Unable to read blob_data.txt").replace("0x", "");
@@ -11,7 +11,7 @@ use starknet::providers::JsonRpcClient;
 use url::Url;
 
 use crate::core::config::Config;
-use crate::server::types::ApiResponse;
+use crate::server::types::{ApiResponse, BulkRetryResponse, JobListResponse, JobResponseItem, PausedJobTypesResponse};
 use crate::tests::config::{ConfigType, TestConfigBuilder};
 use crate::tests::utils::build_job_item;
 use crate::types::jobs::metadata::JobSpecificMetadata;
@@ -19,7 +19,11 @@ use crate::types::jobs::types::{JobStatus, JobType};
 use crate::types::queue::QueueNameForJobType;
 use crate::worker::event_handler::factory::mock_factory::get_job_handler_context;
 use crate::worker::event_handler::jobs::{JobHandlerTrait, MockJobHandlerTrait};
+use crate::worker::event_handler::triggers::proving::ProvingJobTrigger;
+use crate::worker::event_handler::triggers::snos::SnosJobTrigger;
+use crate::worker::event_handler::triggers::JobTrigger;
 use crate::worker::parser::job_queue_message::JobQueueMessage;
+use crate::worker::service::JobService;
 
 #[fixture]
 async fn setup_trigger() -> (SocketAddr, Arc<Config>) {
@@ -137,13 +141,15 @@ async fn test_trigger_verify_job(#[future] setup_trigger: (SocketAddr, Arc<Confi
     assert_eq!(job_fetched.metadata.common.verification_retry_attempt_no, 1);
 }
 
-#[tokio::test]
 #[rstest]
-async fn test_trigger_retry_job_when_failed(#[future] setup_trigger: (SocketAddr, Arc<Config>)) {
+#[case::failed_job(JobStatus::Failed)]
+#[case::cancelled_job(JobStatus::Cancelled)]
+#[tokio::test]
+async fn test_trigger_retry_job(#[future] setup_trigger: (SocketAddr, Arc<Config>), #[case] initial_status: JobStatus) {
     let (addr, config) = setup_trigger.await;
     let job_type = JobType::DataSubmission;
 
-    let job_item = build_job_item(job_type.clone(), JobStatus::Failed, 1);
+    let job_item = build_job_item(job_type.clone(), initial_status, 1);
     config.database().create_job(job_item.clone()).await.unwrap();
     let job_id = job_item.clone().id;
 
@@ -295,3 +301,295 @@ async fn test_get_job_status_by_block_number_not_found(#[future] setup_trigger:
     let jobs_response = response_body.data.unwrap().jobs;
     assert_eq!(jobs_response.len(), 0);
 }
+
+#[tokio::test]
+#[rstest]
+async fn test_list_jobs_with_filters_and_pagination(#[future] setup_trigger: (SocketAddr, Arc<Config>)) {
+    let (addr, config) = setup_trigger.await;
+
+    let mut failed_jobs = Vec::new();
+    for block_number in 10..15 {
+        let job = build_job_item(JobType::ProofCreation, JobStatus::Failed, block_number);
+        config.database().create_job(job.clone()).await.unwrap();
+        failed_jobs.push(job);
+    }
+    // Jobs that don't match one of the filters
+    config.database().create_job(build_job_item(JobType::ProofCreation, JobStatus::Completed, 11)).await.unwrap();
+    config.database().create_job(build_job_item(JobType::SnosRun, JobStatus::Failed, 11)).await.unwrap();
+    config.database().create_job(build_job_item(JobType::ProofCreation, JobStatus::Failed, 20)).await.unwrap();
+
+    let client = hyper::Client::new();
+    let mut listed_ids = Vec::new();
+    for page in 0..3 {
+        let response = client
+            .request(
+                Request::builder()
+                    .uri(format!(
+                        "http://{}/jobs?job_type=ProofCreation&status=Failed&from_block=10&to_block=14&page={}&page_size=2",
+                        addr, page
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+        let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response_body: ApiResponse<JobListResponse> = serde_json::from_slice(&body_bytes).unwrap();
+        assert!(response_body.success);
+        let data = response_body.data.unwrap();
+        assert_eq!(data.page, page);
+        assert_eq!(data.page_size, 2);
+        assert!(data.jobs.iter().all(|job| job.job_type == JobType::ProofCreation && job.status == JobStatus::Failed));
+        listed_ids.extend(data.jobs.into_iter().map(|job| job.id));
+    }
+
+    // Every matching job is listed exactly once across the pages
+    assert_eq!(listed_ids.len(), failed_jobs.len());
+    assert!(failed_jobs.iter().all(|job| listed_ids.contains(&job.id)));
+}
+
+#[rstest]
+#[case::empty_block_range("from_block=10&to_block=5")]
+#[case::page_size_too_large("page_size=501")]
+#[case::empty_page("page_size=0")]
+#[tokio::test]
+async fn test_list_jobs_invalid_request(#[future] setup_trigger: (SocketAddr, Arc<Config>), #[case] query: &str) {
+    let (addr, _config) = setup_trigger.await;
+
+    let client = hyper::Client::new();
+    let response = client
+        .request(Request::builder().uri(format!("http://{}/jobs?{}", addr, query)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+#[rstest]
+async fn test_get_job_with_metadata(#[future] setup_trigger: (SocketAddr, Arc<Config>)) {
+    let (addr, config) = setup_trigger.await;
+
+    let mut job_item = build_job_item(JobType::DataSubmission, JobStatus::Failed, 1);
+    job_item.metadata.common.process_attempt_no = 2;
+    job_item.metadata.common.process_retry_attempt_no = 1;
+    job_item.metadata.common.failure_reason = Some("DA layer unavailable".to_string());
+    config.database().create_job(job_item.clone()).await.unwrap();
+
+    let client = hyper::Client::new();
+    let response = client
+        .request(Request::builder().uri(format!("http://{}/jobs/{}", addr, job_item.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let response_body: ApiResponse<JobResponseItem> = serde_json::from_slice(&body_bytes).unwrap();
+    assert!(response_body.success);
+    let job = response_body.data.unwrap();
+    assert_eq!(job.id, job_item.id);
+    assert_eq!(job.status, JobStatus::Failed);
+    assert_eq!(job.created_at, job_item.created_at);
+    assert_eq!(job.metadata, job_item.metadata);
+
+    // Unknown jobs are reported as not found
+    let response = client
+        .request(
+            Request::builder()
+                .uri(format!("http://{}/jobs/{}", addr, uuid::Uuid::new_v4()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[rstest]
+#[case::created_job(JobStatus::Created)]
+#[case::pending_verification_job(JobStatus::PendingVerification)]
+#[case::failed_job(JobStatus::Failed)]
+#[tokio::test]
+async fn test_cancel_job(#[future] setup_trigger: (SocketAddr, Arc<Config>), #[case] initial_status: JobStatus) {
+    let (addr, config) = setup_trigger.await;
+
+    let job_item = build_job_item(JobType::DataSubmission, initial_status, 1);
+    config.database().create_job(job_item.clone()).await.unwrap();
+    let job_id = job_item.id;
+
+    let client = hyper::Client::new();
+    let response = client
+        .request(
+            Request::builder()
+                .method("POST")
+                .uri(format!("http://{}/jobs/{}/cancel", addr, job_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let response: ApiResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert!(response.success);
+    assert_eq!(response.message, Some(format!("Job with id {} cancelled", job_id)));
+
+    let job_fetched = config.database().get_job_by_id(job_id).await.unwrap().expect("Could not get job from database");
+    assert_eq!(job_fetched.status, JobStatus::Cancelled);
+
+    // A cancelled job isn't picked up again by the failure handling
+    JobService::move_job_to_failed(&job_fetched, config.clone(), "failure".to_string()).await.unwrap();
+    let job_fetched = config.database().get_job_by_id(job_id).await.unwrap().expect("Could not get job from database");
+    assert_eq!(job_fetched.status, JobStatus::Cancelled);
+}
+
+#[rstest]
+#[case::completed_job(JobStatus::Completed)]
+#[case::cancelled_job(JobStatus::Cancelled)]
+#[tokio::test]
+async fn test_cancel_job_not_allowed(
+    #[future] setup_trigger: (SocketAddr, Arc<Config>),
+    #[case] initial_status: JobStatus,
+) {
+    let (addr, config) = setup_trigger.await;
+
+    let job_item = build_job_item(JobType::DataSubmission, initial_status.clone(), 1);
+    config.database().create_job(job_item.clone()).await.unwrap();
+    let job_id = job_item.id;
+
+    let client = hyper::Client::new();
+    let response = client
+        .request(
+            Request::builder()
+                .method("POST")
+                .uri(format!("http://{}/jobs/{}/cancel", addr, job_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 409);
+
+    let job_fetched = config.database().get_job_by_id(job_id).await.unwrap().expect("Could not get job from database");
+    assert_eq!(job_fetched.status, initial_status);
+}
+
+#[tokio::test]
+#[rstest]
+async fn test_bulk_retry_failed_jobs_in_block_range(#[future] setup_trigger: (SocketAddr, Arc<Config>)) {
+    let (addr, config) = setup_trigger.await;
+    let job_type = JobType::DataSubmission;
+
+    let failed_in_range: Vec<_> =
+        (1..=3).map(|block_number| build_job_item(job_type.clone(), JobStatus::Failed, block_number)).collect();
+    for job in &failed_in_range {
+        config.database().create_job(job.clone()).await.unwrap();
+    }
+    let failed_out_of_range = build_job_item(job_type.clone(), JobStatus::Failed, 10);
+    let completed_in_range = build_job_item(job_type.clone(), JobStatus::Completed, 4);
+    config.database().create_job(failed_out_of_range.clone()).await.unwrap();
+    config.database().create_job(completed_in_range.clone()).await.unwrap();
+
+    let client = hyper::Client::new();
+    let response = client
+        .request(
+            Request::builder()
+                .method("POST")
+                .uri(format!("http://{}/jobs/type/DataSubmission/retry?from_block=1&to_block=5", addr))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let response_body: ApiResponse<BulkRetryResponse> = serde_json::from_slice(&body_bytes).unwrap();
+    assert!(response_body.success);
+    let data = response_body.data.unwrap();
+    assert!(data.failed.is_empty());
+    assert_eq!(data.retried.len(), failed_in_range.len());
+
+    for job in &failed_in_range {
+        assert!(data.retried.contains(&job.id));
+        let job_fetched =
+            config.database().get_job_by_id(job.id).await.unwrap().expect("Could not get job from database");
+        assert_eq!(job_fetched.status, JobStatus::PendingRetry);
+        assert_eq!(job_fetched.metadata.common.process_retry_attempt_no, 1);
+    }
+
+    let job_fetched = config
+        .database()
+        .get_job_by_id(failed_out_of_range.id)
+        .await
+        .unwrap()
+        .expect("Could not get job from database");
+    assert_eq!(job_fetched.status, JobStatus::Failed);
+    let job_fetched =
+        config.database().get_job_by_id(completed_in_range.id).await.unwrap().expect("Could not get job from database");
+    assert_eq!(job_fetched.status, JobStatus::Completed);
+}
+
+#[tokio::test]
+#[rstest]
+async fn test_pause_and_resume_job_creation(#[future] setup_trigger: (SocketAddr, Arc<Config>)) {
+    let (addr, config) = setup_trigger.await;
+    let client = hyper::Client::new();
+
+    // Changing the job creation state isn't allowed through GET
+    let response = client
+        .request(
+            Request::builder()
+                .uri(format!("http://{}/jobs/type/ProofCreation/pause", addr))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 405);
+    assert!(config.database().get_paused_job_types().await.unwrap().is_empty());
+
+    let response = client
+        .request(
+            Request::builder()
+                .method("POST")
+                .uri(format!("http://{}/jobs/type/ProofCreation/pause", addr))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .request(Request::builder().uri(format!("http://{}/jobs/paused", addr)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let response_body: ApiResponse<PausedJobTypesResponse> = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(response_body.data.unwrap().job_types, vec![JobType::ProofCreation]);
+
+    // Only the trigger creating the paused job type is held back
+    assert!(ProvingJobTrigger.is_job_creation_paused(config.clone()).await.unwrap());
+    assert!(!SnosJobTrigger.is_job_creation_paused(config.clone()).await.unwrap());
+
+    let response = client
+        .request(
+            Request::builder()
+                .method("POST")
+                .uri(format!("http://{}/jobs/type/ProofCreation/resume", addr))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    assert!(config.database().get_paused_job_types().await.unwrap().is_empty());
+    assert!(!ProvingJobTrigger.is_job_creation_paused(config.clone()).await.unwrap());
+}
@@ -33,6 +33,11 @@ pub fn build_job_item(job_type: JobType, job_status: JobStatus, internal_id: u64
                 blob_data_paths: vec![format!("{}/{}", internal_id, BLOB_DATA_FILE_NAME)],
                 last_failed_block_no: None,
                 tx_hashes: Vec::new(),
+                da_tx_hashes: Vec::new(),
+                da_commitments: Vec::new(),
+                blob_versioned_hashes: Vec::new(),
+                calldata_tx_hashes: Vec::new(),
+                aggregation: None,
             }),
         },
         JobType::SnosRun => JobMetadata {
@@ -59,6 +64,8 @@ pub fn build_job_item(job_type: JobType, job_status: JobStatus, internal_id: u64
                 block_number: internal_id,
                 blob_data_path: Some(format!("{}/{}", internal_id, BLOB_DATA_FILE_NAME)),
                 tx_hash: None,
+                da_commitment: None,
+                blob_versioned_hashes: Vec::new(),
             }),
         },
         _ => panic!("Invalid job type"),
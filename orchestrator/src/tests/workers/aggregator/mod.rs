@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use mockall::predicate::eq;
+use rstest::*;
+
+use crate::core::config::Config;
+use crate::tests::config::{ConfigType, TestConfigBuilder};
+use crate::tests::utils::build_batch;
+use crate::tests::workers::utils::get_job_by_mock_id_vector;
+use crate::types::batch::Batch;
+use crate::types::constant::PROGRAM_OUTPUT_FILE_NAME;
+use crate::types::jobs::external_id::ExternalId;
+use crate::types::jobs::metadata::AggregatorMetadata;
+use crate::types::jobs::types::{JobStatus, JobType};
+use crate::worker::event_handler::factory::mock_factory::get_job_handler_context;
+use crate::worker::event_handler::jobs::aggregator::AggregatorJobHandler;
+use crate::worker::event_handler::triggers::aggregator::AggregatorJobTrigger;
+use crate::worker::event_handler::triggers::JobTrigger;
+
+/// Stores a ready batch of blocks 0 and 1 along with their completed SNOS jobs and their proving jobs.
+async fn store_proven_batch(config: Arc<Config>, mut batch: Batch, last_proving_status: JobStatus) {
+    batch.is_batch_ready = true;
+    config.database().create_batch(batch).await.unwrap();
+
+    for snos_job in get_job_by_mock_id_vector(JobType::SnosRun, JobStatus::Completed, 2, 0) {
+        config.database().create_job(snos_job).await.unwrap();
+    }
+    for mut proving_job in get_job_by_mock_id_vector(JobType::ProofCreation, JobStatus::Completed, 2, 0) {
+        proving_job.external_id = ExternalId::String(format!("task_{}", proving_job.internal_id).into_boxed_str());
+        if proving_job.internal_id == "1" {
+            proving_job.status = last_proving_status.clone();
+        }
+        config.database().create_job(proving_job).await.unwrap();
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn aggregator_worker_creates_job_for_proven_batch(
+    #[from(build_batch)]
+    #[with(1, 0, 1)]
+    batch: Batch,
+) {
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .configure_proof_aggregation(true)
+        .build()
+        .await;
+    store_proven_batch(services.config.clone(), batch, JobStatus::Completed).await;
+
+    let ctx = get_job_handler_context();
+    ctx.expect().with(eq(JobType::Aggregator)).returning(move |_| Arc::new(Box::new(AggregatorJobHandler)));
+
+    assert!(AggregatorJobTrigger.run_worker(services.config.clone()).await.is_ok());
+
+    let aggregator_job = services.config.database().get_latest_job_by_type(JobType::Aggregator).await.unwrap().unwrap();
+    assert_eq!(aggregator_job.internal_id, "1");
+    assert_eq!(aggregator_job.status, JobStatus::Created);
+    let aggregator_metadata: AggregatorMetadata = aggregator_job.metadata.specific.try_into().unwrap();
+    assert_eq!((aggregator_metadata.start_block, aggregator_metadata.end_block), (0, 1));
+    assert_eq!(aggregator_metadata.proving_task_ids, vec!["task_0".to_string(), "task_1".to_string()]);
+    assert_eq!(
+        aggregator_metadata.program_output_paths,
+        vec![format!("0/{}", PROGRAM_OUTPUT_FILE_NAME), format!("1/{}", PROGRAM_OUTPUT_FILE_NAME)]
+    );
+}
+
+#[rstest]
+#[case::proofs_pending(true, JobStatus::PendingVerification)]
+#[case::aggregation_disabled(false, JobStatus::Completed)]
+#[tokio::test]
+async fn aggregator_worker_skips_batch(
+    #[from(build_batch)]
+    #[with(1, 0, 1)]
+    batch: Batch,
+    #[case] proof_aggregation: bool,
+    #[case] last_proving_status: JobStatus,
+) {
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .configure_proof_aggregation(proof_aggregation)
+        .build()
+        .await;
+    store_proven_batch(services.config.clone(), batch, last_proving_status).await;
+
+    assert!(AggregatorJobTrigger.run_worker(services.config.clone()).await.is_ok());
+
+    assert!(services.config.database().get_latest_job_by_type(JobType::Aggregator).await.unwrap().is_none());
+}
@@ -1,4 +1,6 @@
 #[cfg(test)]
+pub mod aggregator;
+#[cfg(test)]
 pub mod proving;
 #[cfg(test)]
 pub mod snos;
@@ -10,6 +10,7 @@ use crate::types::jobs::types::{JobStatus, JobType};
 use crate::types::queue::QueueType;
 use crate::worker::event_handler::factory::mock_factory::get_job_handler_context;
 use crate::worker::event_handler::jobs::{JobHandlerTrait, MockJobHandlerTrait};
+use crate::worker::event_handler::triggers::data_submission_worker::DataSubmissionJobTrigger;
 use crate::worker::event_handler::triggers::proving::ProvingJobTrigger;
 use crate::worker::event_handler::triggers::JobTrigger;
 use httpmock::MockServer;
@@ -121,3 +122,42 @@ async fn test_proving_worker(#[case] incomplete_runs: bool) -> Result<(), Box<dy
 
     Ok(())
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_dev_mode_creates_proving_jobs() -> Result<(), Box<dyn Error>> {
+    let mut db = MockDatabaseClient::new();
+    let mut queue = MockQueueClient::new();
+
+    // The blocks are still proven in dev mode, by the mock prover, and the data submission jobs follow the
+    // proving jobs.
+    db.expect_get_jobs_without_successor()
+        .times(1)
+        .withf(|job_type, job_status, successor_type| {
+            *job_type == JobType::SnosRun
+                && *job_status == JobStatus::Completed
+                && *successor_type == JobType::ProofCreation
+        })
+        .returning(|_, _, _| Ok(Vec::new()));
+    db.expect_get_jobs_without_successor()
+        .times(1)
+        .withf(|job_type, job_status, successor_type| {
+            *job_type == JobType::ProofCreation
+                && *job_status == JobStatus::Completed
+                && *successor_type == JobType::DataSubmission
+        })
+        .returning(|_, _, _| Ok(Vec::new()));
+    queue.expect_send_message().never();
+
+    let services = TestConfigBuilder::new()
+        .configure_database(db.into())
+        .configure_queue_client(queue.into())
+        .configure_dev_mode(true)
+        .build()
+        .await;
+
+    ProvingJobTrigger.run_worker(services.config.clone()).await?;
+    DataSubmissionJobTrigger.run_worker(services.config).await?;
+
+    Ok(())
+}
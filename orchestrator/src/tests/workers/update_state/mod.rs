@@ -5,9 +5,13 @@ use rstest::*;
 use uuid::Uuid;
 
 use crate::tests::config::{ConfigType, TestConfigBuilder};
-use crate::tests::workers::utils::{create_and_store_prerequisite_jobs, get_job_item_mock_by_id};
+use crate::tests::workers::utils::{
+    create_and_store_prerequisite_jobs, get_job_by_mock_id_vector, get_job_item_mock_by_id,
+};
 use crate::types::constant::{BLOB_DATA_FILE_NAME, PROGRAM_OUTPUT_FILE_NAME, SNOS_OUTPUT_FILE_NAME};
-use crate::types::jobs::metadata::{CommonMetadata, JobMetadata, JobSpecificMetadata, StateUpdateMetadata};
+use crate::types::jobs::metadata::{
+    AggregatedSettlement, AggregatorMetadata, CommonMetadata, JobMetadata, JobSpecificMetadata, StateUpdateMetadata,
+};
 use crate::types::jobs::types::{JobStatus, JobType};
 use crate::worker::event_handler::factory::mock_factory::get_job_handler_context;
 use crate::worker::event_handler::jobs::state_update::StateUpdateJobHandler;
@@ -160,6 +164,11 @@ async fn update_state_worker_continues_from_previous_state_update() {
         ],
         last_failed_block_no: None,
         tx_hashes: Vec::new(),
+        da_tx_hashes: Vec::new(),
+        da_commitments: Vec::new(),
+        blob_versioned_hashes: Vec::new(),
+        calldata_tx_hashes: Vec::new(),
+        aggregation: None,
     };
 
     job_item.metadata =
@@ -228,6 +237,11 @@ async fn update_state_worker_next_block_missing() {
         ],
         last_failed_block_no: None,
         tx_hashes: Vec::new(),
+        da_tx_hashes: Vec::new(),
+        da_commitments: Vec::new(),
+        blob_versioned_hashes: Vec::new(),
+        calldata_tx_hashes: Vec::new(),
+        aggregation: None,
     };
 
     job_item.metadata =
@@ -244,3 +258,45 @@ async fn update_state_worker_next_block_missing() {
         services.config.database().get_latest_job_by_type(JobType::StateTransition).await.unwrap().unwrap();
     assert_eq!(latest_job.id, unique_id);
 }
+
+#[rstest]
+#[tokio::test]
+async fn update_state_worker_settles_aggregated_batch_without_da_jobs() {
+    let services = TestConfigBuilder::new()
+        .configure_database(ConfigType::Actual)
+        .configure_queue_client(ConfigType::Actual)
+        .configure_proof_aggregation(true)
+        .build()
+        .await;
+
+    // Only the aggregator job of the batch is completed, its blocks have no DA job.
+    let mut aggregator_job = get_job_by_mock_id_vector(JobType::Aggregator, JobStatus::Completed, 1, 1).remove(0);
+    aggregator_job.metadata.specific = JobSpecificMetadata::Aggregator(AggregatorMetadata {
+        batch_num: 1,
+        start_block: 0,
+        end_block: 1,
+        aggregator_output_path: Some("batch/1/aggregator_output.json".to_string()),
+        blob_data_path: Some("batch/1/blob_data.txt".to_string()),
+        ..Default::default()
+    });
+    services.config.database().create_job(aggregator_job).await.unwrap();
+
+    let ctx = get_job_handler_context();
+    ctx.expect().with(eq(JobType::StateTransition)).returning(move |_| Arc::new(Box::new(StateUpdateJobHandler)));
+
+    assert!(UpdateStateJobTrigger.run_worker(services.config.clone()).await.is_ok());
+
+    let latest_job =
+        services.config.database().get_latest_job_by_type(JobType::StateTransition).await.unwrap().unwrap();
+    let state_metadata: StateUpdateMetadata = latest_job.metadata.specific.try_into().unwrap();
+    assert_eq!(state_metadata.blocks_to_settle, vec![0, 1]);
+    assert!(state_metadata.da_tx_hashes.is_empty());
+    assert_eq!(
+        state_metadata.aggregation,
+        Some(AggregatedSettlement {
+            batch_num: 1,
+            aggregator_output_path: "batch/1/aggregator_output.json".to_string(),
+            blob_data_path: "batch/1/blob_data.txt".to_string(),
+        })
+    );
+}
@@ -107,6 +107,8 @@ fn create_metadata_for_job_type(job_type: JobType, block_number: u64) -> JobMeta
                 block_number,
                 blob_data_path: Some(format!("{}/{}", block_number, BLOB_DATA_FILE_NAME)),
                 tx_hash: None,
+                da_commitment: None,
+                blob_versioned_hashes: Vec::new(),
             }),
         },
         JobType::ProofCreation => JobMetadata {
@@ -126,6 +128,11 @@ fn create_metadata_for_job_type(job_type: JobType, block_number: u64) -> JobMeta
                 blob_data_paths: vec![format!("{}/{}", block_number, BLOB_DATA_FILE_NAME)],
                 last_failed_block_no: None,
                 tx_hashes: Vec::new(),
+                da_tx_hashes: Vec::new(),
+                da_commitments: Vec::new(),
+                blob_versioned_hashes: Vec::new(),
+                calldata_tx_hashes: Vec::new(),
+                aggregation: None,
             }),
         },
         // For any other job types, use a default metadata structure
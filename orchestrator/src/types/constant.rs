@@ -2,6 +2,8 @@ pub const BLOB_DATA_FILE_NAME: &str = "blob_data.txt";
 pub const SNOS_OUTPUT_FILE_NAME: &str = "snos_output.json";
 pub const PROGRAM_OUTPUT_FILE_NAME: &str = "program_output.txt";
 pub const CAIRO_PIE_FILE_NAME: &str = "cairo_pie.zip";
+pub const PROOF_FILE_NAME: &str = "proof.json";
 pub const STORAGE_STATE_UPDATE_DIR: &str = "state_update";
+pub const STORAGE_AGGREGATOR_DIR: &str = "aggregator";
 // TODO: Remove this constant when `assign_batch_to_block` method is updated
 pub const MAX_BATCH_SIZE: u64 = 50;
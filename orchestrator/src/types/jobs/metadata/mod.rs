@@ -40,7 +40,7 @@ pub struct CommonMetadata {
 ///
 /// # Field Management
 /// - Worker-initialized fields: block_number and blob_data_path
-/// - Job-populated fields: tx_hash (during processing)
+/// - Job-populated fields: tx_hash, da_commitment and blob_versioned_hashes (during processing)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct DaMetadata {
     // Worker-initialized fields
@@ -52,6 +52,12 @@ pub struct DaMetadata {
     // Job-populated fields
     /// Transaction hash after data submission
     pub tx_hash: Option<String>,
+    /// Hex encoded commitment of the published data, when the DA layer provides one
+    #[serde(default)]
+    pub da_commitment: Option<String>,
+    /// Hex encoded versioned hashes of the EIP-4844 blobs published by the DA client, if any
+    #[serde(default)]
+    pub blob_versioned_hashes: Vec<String>,
 }
 
 /// Input type specification for proving jobs.
@@ -83,6 +89,32 @@ pub struct ProvingMetadata {
     pub n_steps: Option<usize>,
 }
 
+/// Metadata specific to aggregator jobs.
+///
+/// # Field Management
+/// All fields are initialized by the worker during job creation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct AggregatorMetadata {
+    /// Index of the batch whose proofs are aggregated
+    pub batch_num: u64,
+    /// First block of the batch
+    pub start_block: u64,
+    /// Last block of the batch
+    pub end_block: u64,
+    /// Prover task ids of the proving jobs of each block, in block order
+    pub proving_task_ids: Vec<String>,
+    /// Paths to program output files for each block
+    pub program_output_paths: Vec<String>,
+    /// Path to the squashed state update of the batch
+    pub squashed_state_update_path: String,
+    /// Path where the blob data of the squashed state diff is stored
+    pub blob_data_path: Option<String>,
+    /// Path where the program output of the aggregator program is stored
+    pub aggregator_output_path: Option<String>,
+    /// Path where the aggregated proof should be downloaded. If `None`, the proof will not be downloaded.
+    pub download_proof: Option<String>,
+}
+
 /// Metadata specific to SNOS (Starknet OS) jobs.
 ///
 /// # Field Management
@@ -112,8 +144,8 @@ pub struct SnosMetadata {
 /// Metadata specific to state update jobs.
 ///
 /// # Field Management
-/// - Worker-initialized fields: blocks and paths configurations
-/// - Job-populated fields: last_failed_block_no and tx_hashes (during processing)
+/// - Worker-initialized fields: blocks, paths configurations and da_tx_hashes
+/// - Job-populated fields: last_failed_block_no, calldata_tx_hashes and tx_hashes (during processing)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct StateUpdateMetadata {
     // Worker-initialized fields
@@ -125,12 +157,40 @@ pub struct StateUpdateMetadata {
     pub program_output_paths: Vec<String>,
     /// Paths to blob data files for each block
     pub blob_data_paths: Vec<String>,
+    /// External ids returned by the DA layer when the blobs of each block were published, if any
+    #[serde(default)]
+    pub da_tx_hashes: Vec<Option<String>>,
+    /// Hex encoded commitments of the state diffs published to an external DA layer, if any
+    #[serde(default)]
+    pub da_commitments: Vec<Option<String>>,
+    /// Hex encoded versioned hashes of the blobs of each block already published by the DA client, referenced by
+    /// the state update. Empty for the blocks whose blobs are not published by the DA client
+    #[serde(default)]
+    pub blob_versioned_hashes: Vec<Vec<String>>,
+    /// Aggregated proof of the blocks to settle, set when the whole batch is settled with a single state update
+    #[serde(default)]
+    pub aggregation: Option<AggregatedSettlement>,
 
     // Job-populated fields
     /// Last block number that failed processing
     pub last_failed_block_no: Option<u64>,
     /// Transaction hashes for processed blocks
     pub tx_hashes: Vec<String>,
+    /// Hashes of the transactions already carrying the state diff of each block as calldata, kept so that a
+    /// retry only publishes the remaining chunks
+    #[serde(default)]
+    pub calldata_tx_hashes: Vec<Vec<String>>,
+}
+
+/// Outputs of the aggregator job of a batch, settled in place of the outputs of each of its blocks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct AggregatedSettlement {
+    /// Index of the settled batch
+    pub batch_num: u64,
+    /// Path to the program output of the aggregator program
+    pub aggregator_output_path: String,
+    /// Path to the blob data of the squashed state diff of the batch
+    pub blob_data_path: String,
 }
 
 /// Enum containing all possible job-specific metadata types.
@@ -148,6 +208,8 @@ pub enum JobSpecificMetadata {
     Proving(ProvingMetadata),
     /// Data availability job metadata
     Da(DaMetadata),
+    /// Aggregator job metadata
+    Aggregator(AggregatorMetadata),
 }
 
 /// Macro to implement TryInto for JobSpecificMetadata variants
@@ -174,6 +236,7 @@ impl_try_into_metadata!(Snos, SnosMetadata);
 impl_try_into_metadata!(Proving, ProvingMetadata);
 impl_try_into_metadata!(Da, DaMetadata);
 impl_try_into_metadata!(StateUpdate, StateUpdateMetadata);
+impl_try_into_metadata!(Aggregator, AggregatorMetadata);
 
 /// Complete job metadata containing both common and job-specific fields.
 ///
@@ -20,6 +20,8 @@ pub enum JobStatus {
     Failed,
     /// The job is being retried
     PendingRetry,
+    /// The job was cancelled by an operator and won't be processed or verified again
+    Cancelled,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -32,6 +34,8 @@ pub enum JobType {
     ProofCreation,
     /// Verifying the proof on the base layer
     ProofRegistration,
+    /// Aggregating the proofs of the blocks of a batch into a single proof
+    Aggregator,
     /// Updating the state root on the base layer
     StateTransition,
 }
@@ -1,15 +1,55 @@
 use crate::cli::RunCmd;
 use crate::OrchestratorError;
+use orchestrator_celestia_da_client::{parse_namespace, CelestiaDaValidatedArgs};
+use orchestrator_dac_da_client::DacDaValidatedArgs;
 use orchestrator_ethereum_da_client::EthereumDaValidatedArgs;
+use orchestrator_ethereum_settlement_client::tx_manager::TxManagerConfig;
+use orchestrator_local_da_client::LocalDaValidatedArgs;
 
 #[derive(Debug, Clone)]
 pub enum DAConfig {
     Ethereum(EthereumDaValidatedArgs),
+    Celestia(CelestiaDaValidatedArgs),
+    Dac(DacDaValidatedArgs),
+    Local(LocalDaValidatedArgs),
 }
 
 impl TryFrom<RunCmd> for DAConfig {
     type Error = OrchestratorError;
     fn try_from(run_cmd: RunCmd) -> Result<Self, Self::Error> {
+        if run_cmd.celestia_da_args.da_on_celestia {
+            let args = run_cmd.celestia_da_args;
+            return Ok(Self::Celestia(CelestiaDaValidatedArgs {
+                celestia_da_rpc_url: args.celestia_da_rpc_url.ok_or_else(|| {
+                    OrchestratorError::SetupCommandError("Celestia DA RPC URL is required".to_string())
+                })?,
+                celestia_da_auth_token: args.celestia_da_auth_token,
+                celestia_da_namespace: parse_namespace(&args.celestia_da_namespace.ok_or_else(|| {
+                    OrchestratorError::SetupCommandError("Celestia DA namespace is required".to_string())
+                })?)
+                .map_err(|e| OrchestratorError::SetupCommandError(e.to_string()))?,
+            }));
+        }
+        if run_cmd.dac_da_args.da_on_dac {
+            let args = run_cmd.dac_da_args;
+            let dac_da_member_urls = args.dac_da_member_urls.unwrap_or_default();
+            let dac_da_threshold = args
+                .dac_da_threshold
+                .ok_or_else(|| OrchestratorError::SetupCommandError("DAC DA threshold is required".to_string()))?;
+            if dac_da_threshold == 0 || dac_da_threshold > dac_da_member_urls.len() {
+                return Err(OrchestratorError::SetupCommandError(format!(
+                    "DAC DA threshold must be between 1 and the number of members ({})",
+                    dac_da_member_urls.len()
+                )));
+            }
+            let dac_da_auth_token = args
+                .dac_da_auth_token
+                .ok_or_else(|| OrchestratorError::SetupCommandError("DAC DA auth token is required".to_string()))?;
+            return Ok(Self::Dac(DacDaValidatedArgs { dac_da_member_urls, dac_da_threshold, dac_da_auth_token }));
+        }
+        if run_cmd.local_da_args.da_on_local {
+            return Ok(Self::Local(LocalDaValidatedArgs { local_da_path: run_cmd.local_da_args.local_da_path }));
+        }
         if !run_cmd.ethereum_da_args.da_on_ethereum {
             return Err(OrchestratorError::SetupCommandError("Ethereum DA is not enabled".to_string()));
         }
@@ -18,6 +58,8 @@ impl TryFrom<RunCmd> for DAConfig {
                 .ethereum_da_args
                 .ethereum_da_rpc_url
                 .ok_or_else(|| OrchestratorError::SetupCommandError("Ethereum DA RPC URL is required".to_string()))?,
+            ethereum_da_private_key: run_cmd.ethereum_da_args.ethereum_da_private_key,
+            tx_manager_config: TxManagerConfig::from(&run_cmd.ethereum_settlement_args),
         }))
     }
 }
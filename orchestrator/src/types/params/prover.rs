@@ -1,25 +1,35 @@
 use crate::cli::RunCmd;
 use crate::OrchestratorError;
 use orchestrator_atlantic_service::AtlanticValidatedArgs;
+use orchestrator_local_prover_service::LocalProverValidatedArgs;
 use orchestrator_sharp_service::SharpValidatedArgs;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum ProverConfig {
     Sharp(SharpValidatedArgs),
     Atlantic(AtlanticValidatedArgs),
+    Local(LocalProverValidatedArgs),
+    /// Used in dev mode, no proof is generated.
+    Mock,
 }
 
 impl TryFrom<RunCmd> for ProverConfig {
     type Error = OrchestratorError;
     fn try_from(run_cmd: RunCmd) -> Result<Self, Self::Error> {
-        match (run_cmd.sharp_args.sharp, run_cmd.atlantic_args.atlantic) {
-            (true, true) => {
-                Err(OrchestratorError::SetupCommandError("Cannot use both Sharp and Atlantic provers".to_string()))
+        if run_cmd.dev {
+            if run_cmd.sharp_args.sharp || run_cmd.atlantic_args.atlantic || run_cmd.local_prover_args.local_prover {
+                return Err(OrchestratorError::SetupCommandError(
+                    "Dev mode uses the mock prover, no other prover can be set".to_string(),
+                ));
             }
-            (false, false) => {
-                Err(OrchestratorError::SetupCommandError("Must use either Sharp or Atlantic prover".to_string()))
-            }
-            (true, false) => {
+            return Ok(Self::Mock);
+        }
+        match (run_cmd.sharp_args.sharp, run_cmd.atlantic_args.atlantic, run_cmd.local_prover_args.local_prover) {
+            (false, false, false) => Err(OrchestratorError::SetupCommandError(
+                "Must use either Sharp, Atlantic or the local prover".to_string(),
+            )),
+            (true, false, false) => {
                 let sharp_args = run_cmd.sharp_args;
                 Ok(Self::Sharp(SharpValidatedArgs {
                     sharp_customer_id: sharp_args.sharp_customer_id.ok_or_else(|| {
@@ -48,7 +58,7 @@ impl TryFrom<RunCmd> for ProverConfig {
                     })?,
                 }))
             }
-            (false, true) => {
+            (false, true, false) => {
                 let atlantic_args = run_cmd.atlantic_args;
                 Ok(Self::Atlantic(AtlanticValidatedArgs {
                     atlantic_api_key: atlantic_args.atlantic_api_key.ok_or_else(|| {
@@ -81,6 +91,28 @@ impl TryFrom<RunCmd> for ProverConfig {
                     })?,
                 }))
             }
+            (false, false, true) => {
+                let local_prover_args = run_cmd.local_prover_args;
+                if local_prover_args.local_prover_max_concurrent_runs == 0 {
+                    return Err(OrchestratorError::SetupCommandError(
+                        "Local prover max concurrent runs must be at least 1".to_string(),
+                    ));
+                }
+                Ok(Self::Local(LocalProverValidatedArgs {
+                    local_prover_command: local_prover_args.local_prover_command.ok_or_else(|| {
+                        OrchestratorError::SetupCommandError("Local prover command is required".to_string())
+                    })?,
+                    local_prover_args: local_prover_args.local_prover_args,
+                    local_prover_work_dir: local_prover_args.local_prover_work_dir,
+                    local_prover_timeout: Duration::from_secs(local_prover_args.local_prover_timeout),
+                    local_prover_max_memory_mb: local_prover_args.local_prover_max_memory_mb,
+                    local_prover_max_cpu_seconds: local_prover_args.local_prover_max_cpu_seconds,
+                    local_prover_max_concurrent_runs: local_prover_args.local_prover_max_concurrent_runs,
+                    local_prover_aggregator_command: local_prover_args.local_prover_aggregator_command,
+                    local_prover_aggregator_args: local_prover_args.local_prover_aggregator_args,
+                }))
+            }
+            _ => Err(OrchestratorError::SetupCommandError("Cannot use more than one prover".to_string())),
         }
     }
 }
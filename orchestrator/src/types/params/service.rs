@@ -1,5 +1,5 @@
 use crate::cli::server::ServerCliArgs;
-use crate::cli::service::ServiceCliArgs;
+use crate::cli::service::{L1DataAvailabilityMode, ServiceCliArgs};
 
 #[derive(Debug, Clone)]
 pub struct ServiceParams {
@@ -8,6 +8,12 @@ pub struct ServiceParams {
     pub max_concurrent_created_snos_jobs: u64,
     pub max_concurrent_snos_jobs: Option<usize>,
     pub max_concurrent_proving_jobs: Option<usize>,
+    pub l1_da_mode: L1DataAvailabilityMode,
+    pub proof_aggregation: bool,
+    pub experimental_validium: bool,
+    /// Set from the top level `--dev` flag: the blocks are proven by the mock prover and no proof registration job
+    /// is created.
+    pub dev_mode: bool,
 }
 
 impl From<ServiceCliArgs> for ServiceParams {
@@ -18,6 +24,10 @@ impl From<ServiceCliArgs> for ServiceParams {
             max_concurrent_created_snos_jobs: args.max_concurrent_created_snos_jobs,
             max_concurrent_snos_jobs: args.max_concurrent_snos_jobs,
             max_concurrent_proving_jobs: args.max_concurrent_proving_jobs,
+            l1_da_mode: args.l1_da_mode,
+            proof_aggregation: args.proof_aggregation,
+            experimental_validium: args.experimental_validium,
+            dev_mode: false,
         }
     }
 }
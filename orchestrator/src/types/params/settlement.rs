@@ -1,9 +1,23 @@
+use crate::cli::settlement::ethereum::EthereumSettlementCliArgs;
 use crate::cli::RunCmd;
 use crate::OrchestratorError;
 use alloy::primitives::Address;
+use orchestrator_ethereum_settlement_client::tx_manager::TxManagerConfig;
 use orchestrator_ethereum_settlement_client::EthereumSettlementValidatedArgs;
 use orchestrator_starknet_settlement_client::StarknetSettlementValidatedArgs;
 use std::str::FromStr as _;
+use std::time::Duration;
+
+/// The fee settings apply to the transactions of the Ethereum settlement and DA clients alike.
+impl From<&EthereumSettlementCliArgs> for TxManagerConfig {
+    fn from(args: &EthereumSettlementCliArgs) -> Self {
+        Self {
+            fee_bump_timeout: Duration::from_secs(args.ethereum_tx_fee_bump_timeout),
+            fee_bump_percent: args.ethereum_tx_fee_bump_percent,
+            max_tx_fee_wei: args.ethereum_tx_max_fee_wei,
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub enum SettlementConfig {
@@ -55,6 +69,8 @@ impl TryFrom<RunCmd> for SettlementConfig {
                     )?,
                     l1_core_contract_address,
                     starknet_operator_address: ethereum_operator_address,
+                    dev_mode: run_cmd.dev,
+                    tx_manager_config: TxManagerConfig::from(&run_cmd.ethereum_settlement_args),
                 };
                 Ok(Self::Ethereum(ethereum_params))
             }
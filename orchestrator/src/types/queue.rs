@@ -22,6 +22,10 @@ pub enum QueueType {
     ProofRegistrationJobProcessing,
     #[strum(serialize = "proof_registration_job_verification")]
     ProofRegistrationJobVerification,
+    #[strum(serialize = "aggregator_job_processing")]
+    AggregatorJobProcessing,
+    #[strum(serialize = "aggregator_job_verification")]
+    AggregatorJobVerification,
     #[strum(serialize = "data_submission_job_processing")]
     DataSubmissionJobProcessing,
     #[strum(serialize = "data_submission_job_verification")]
@@ -46,6 +50,8 @@ impl TryFrom<QueueType> for JobState {
             QueueType::ProvingJobVerification => JobState::Verification,
             QueueType::ProofRegistrationJobProcessing => JobState::Processing,
             QueueType::ProofRegistrationJobVerification => JobState::Verification,
+            QueueType::AggregatorJobProcessing => JobState::Processing,
+            QueueType::AggregatorJobVerification => JobState::Verification,
             QueueType::DataSubmissionJobProcessing => JobState::Processing,
             QueueType::DataSubmissionJobVerification => JobState::Verification,
             QueueType::UpdateStateJobProcessing => JobState::Processing,
@@ -68,6 +74,7 @@ impl QueueNameForJobType for JobType {
             JobType::SnosRun => QueueType::SnosJobProcessing,
             JobType::ProofCreation => QueueType::ProvingJobProcessing,
             JobType::ProofRegistration => QueueType::ProofRegistrationJobProcessing,
+            JobType::Aggregator => QueueType::AggregatorJobProcessing,
             JobType::DataSubmission => QueueType::DataSubmissionJobProcessing,
             JobType::StateTransition => QueueType::UpdateStateJobProcessing,
         }
@@ -77,6 +84,7 @@ impl QueueNameForJobType for JobType {
             JobType::SnosRun => QueueType::SnosJobVerification,
             JobType::ProofCreation => QueueType::ProvingJobVerification,
             JobType::ProofRegistration => QueueType::ProofRegistrationJobVerification,
+            JobType::Aggregator => QueueType::AggregatorJobVerification,
             JobType::DataSubmission => QueueType::DataSubmissionJobVerification,
             JobType::StateTransition => QueueType::UpdateStateJobVerification,
         }
@@ -54,10 +54,12 @@ impl WorkerController {
         vec![
             QueueType::SnosJobProcessing,
             QueueType::ProvingJobProcessing,
+            QueueType::AggregatorJobProcessing,
             QueueType::DataSubmissionJobProcessing,
             QueueType::UpdateStateJobProcessing,
             QueueType::SnosJobVerification,
             QueueType::ProvingJobVerification,
+            QueueType::AggregatorJobVerification,
             QueueType::DataSubmissionJobVerification,
             QueueType::UpdateStateJobVerification,
             QueueType::WorkerTrigger,
@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use color_eyre::eyre::WrapErr;
+use orchestrator_prover_client_interface::{AggregationTask, Task, TaskStatus};
+use starknet::core::types::StateUpdate;
+
+use crate::core::config::Config;
+use crate::error::job::aggregator::AggregatorError;
+use crate::error::job::JobError;
+use crate::error::other::OtherError;
+use crate::types::jobs::job_item::JobItem;
+use crate::types::jobs::metadata::{AggregatorMetadata, JobMetadata};
+use crate::types::jobs::status::JobVerificationStatus;
+use crate::types::jobs::types::{JobStatus, JobType};
+use crate::utils::helpers::JobProcessingState;
+use crate::worker::event_handler::jobs::da::DAJobHandler;
+use crate::worker::event_handler::jobs::JobHandlerTrait;
+use crate::worker::utils::{biguint_vec_to_u8_vec, fetch_program_output_for_block};
+
+/// Aggregates the proofs of the blocks of a batch into a single proof, so that the batch is settled with a
+/// single state update. The internal id of the job is the index of the batch.
+pub struct AggregatorJobHandler;
+
+#[async_trait]
+impl JobHandlerTrait for AggregatorJobHandler {
+    #[tracing::instrument(fields(category = "aggregator"), skip(self, metadata), ret, err)]
+    async fn create_job(&self, internal_id: String, metadata: JobMetadata) -> Result<JobItem, JobError> {
+        tracing::info!(log_type = "starting", category = "aggregator", function_type = "create_job", batch_no = %internal_id, "Aggregator job creation started.");
+
+        let aggregator_metadata: AggregatorMetadata = metadata.specific.clone().try_into()?;
+        let blocks = aggregator_metadata.end_block - aggregator_metadata.start_block + 1;
+        if aggregator_metadata.proving_task_ids.len() as u64 != blocks
+            || aggregator_metadata.program_output_paths.len() as u64 != blocks
+        {
+            Err(AggregatorError::MissingProvingTasks {
+                internal_id: internal_id.clone(),
+                batch_num: aggregator_metadata.batch_num,
+                blocks,
+                task_ids: aggregator_metadata.proving_task_ids.len(),
+            })?
+        }
+
+        let job_item = JobItem::create(internal_id.clone(), JobType::Aggregator, JobStatus::Created, metadata);
+
+        tracing::info!(log_type = "completed", category = "aggregator", function_type = "create_job", batch_no = %internal_id, "Aggregator job created.");
+        Ok(job_item)
+    }
+
+    #[tracing::instrument(fields(category = "aggregator"), skip(self, config), ret, err)]
+    async fn process_job(&self, config: Arc<Config>, job: &mut JobItem) -> Result<String, JobError> {
+        let internal_id = job.internal_id.clone();
+        tracing::info!(
+            log_type = "starting",
+            category = "aggregator",
+            function_type = "process_job",
+            job_id = ?job.id,
+            batch_no = %internal_id,
+            "Aggregator job processing started."
+        );
+
+        let aggregator_metadata: AggregatorMetadata = job.metadata.specific.clone().try_into()?;
+
+        // The aggregator output commits to the squashed state diff of the batch, which is sent in the blobs of
+        // the state update instead of the state diffs of each block.
+        if let Some(blob_data_path) = &aggregator_metadata.blob_data_path {
+            Self::store_batch_blob_data(&aggregator_metadata, blob_data_path, config.clone()).await?;
+            tracing::debug!(job_id = %job.internal_id, %blob_data_path, "Stored the blob data of the batch");
+        }
+
+        let mut program_outputs = Vec::with_capacity(aggregator_metadata.program_output_paths.len());
+        for index in 0..aggregator_metadata.program_output_paths.len() {
+            program_outputs.push(
+                fetch_program_output_for_block(index, config.clone(), &aggregator_metadata.program_output_paths)
+                    .await?,
+            );
+        }
+
+        tracing::debug!(job_id = %job.internal_id, "Submitting aggregation task to prover client");
+        let task = AggregationTask { task_ids: aggregator_metadata.proving_task_ids, program_outputs };
+        let external_id = config
+            .prover_client()
+            .submit_task(Task::Aggregation(task), None)
+            .await
+            .wrap_err("Prover Client Error".to_string())
+            .map_err(|e| {
+                tracing::error!(job_id = %job.internal_id, error = %e, "Failed to submit aggregation task to prover client");
+                JobError::Other(OtherError(e))
+            })?;
+
+        tracing::info!(
+            log_type = "completed",
+            category = "aggregator",
+            function_type = "process_job",
+            job_id = ?job.id,
+            batch_no = %internal_id,
+            %external_id,
+            "Aggregator job processed successfully."
+        );
+        Ok(external_id)
+    }
+
+    #[tracing::instrument(fields(category = "aggregator"), skip(self, config), ret, err)]
+    async fn verify_job(&self, config: Arc<Config>, job: &mut JobItem) -> Result<JobVerificationStatus, JobError> {
+        let internal_id = job.internal_id.clone();
+        tracing::info!(
+            log_type = "starting",
+            category = "aggregator",
+            function_type = "verify_job",
+            job_id = ?job.id,
+            batch_no = %internal_id,
+            "Aggregator job verification started."
+        );
+
+        let aggregator_metadata: AggregatorMetadata = job.metadata.specific.clone().try_into()?;
+        let task_id: String = job
+            .external_id
+            .unwrap_string()
+            .map_err(|e| {
+                tracing::error!(job_id = %job.internal_id, error = %e, "Failed to unwrap external_id");
+                JobError::Other(OtherError(e))
+            })?
+            .into();
+
+        let task_status = config
+            .prover_client()
+            .get_task_status(&task_id, None, false)
+            .await
+            .wrap_err("Prover Client Error".to_string())
+            .map_err(|e| {
+                tracing::error!(job_id = %job.internal_id, error = %e, "Failed to get task status from prover client");
+                JobError::Other(OtherError(e))
+            })?;
+
+        match task_status {
+            TaskStatus::Processing => {
+                tracing::info!(
+                    log_type = "pending",
+                    category = "aggregator",
+                    function_type = "verify_job",
+                    job_id = ?job.id,
+                    batch_no = %internal_id,
+                    "Aggregator job verification pending."
+                );
+                Ok(JobVerificationStatus::Pending)
+            }
+            TaskStatus::Succeeded => {
+                Self::store_aggregator_outputs(&aggregator_metadata, &task_id, &internal_id, config).await?;
+                tracing::info!(
+                    log_type = "completed",
+                    category = "aggregator",
+                    function_type = "verify_job",
+                    job_id = ?job.id,
+                    batch_no = %internal_id,
+                    "Aggregator job verification completed."
+                );
+                Ok(JobVerificationStatus::Verified)
+            }
+            TaskStatus::Failed(err) => {
+                tracing::info!(
+                    log_type = "failed",
+                    category = "aggregator",
+                    function_type = "verify_job",
+                    job_id = ?job.id,
+                    batch_no = %internal_id,
+                    "Aggregator job verification failed."
+                );
+                Ok(JobVerificationStatus::Rejected(format!(
+                    "Aggregator job #{} failed with error: {}",
+                    job.internal_id, err
+                )))
+            }
+        }
+    }
+
+    fn max_process_attempts(&self) -> u64 {
+        2
+    }
+
+    fn max_verification_attempts(&self) -> u64 {
+        300
+    }
+
+    fn verification_polling_delay_seconds(&self) -> u64 {
+        30
+    }
+
+    fn job_processing_lock(&self, _config: Arc<Config>) -> Option<Arc<JobProcessingState>> {
+        None
+    }
+}
+
+impl AggregatorJobHandler {
+    /// Builds the blob data of the squashed state diff of the batch, the same way the DA job does for a block.
+    async fn store_batch_blob_data(
+        aggregator_metadata: &AggregatorMetadata,
+        blob_data_path: &str,
+        config: Arc<Config>,
+    ) -> Result<(), JobError> {
+        let state_update = config
+            .storage()
+            .get_data(&aggregator_metadata.squashed_state_update_path)
+            .await
+            .map_err(|e| AggregatorError::SquashedStateUpdateFetchFailed(e.to_string()))?;
+        let state_update: StateUpdate = serde_json::from_slice(&state_update)?;
+
+        // Nonces missing from the state diff are read at the end of the batch
+        let blob_data =
+            DAJobHandler::state_update_to_blob_data(aggregator_metadata.end_block, state_update, config.clone())
+                .await?;
+        let transformed_data = DAJobHandler::fft_transformation(DAJobHandler::convert_to_biguint(blob_data))?;
+        config.storage().put_data(biguint_vec_to_u8_vec(&transformed_data).into(), blob_data_path).await?;
+        Ok(())
+    }
+
+    /// Stores the program output of the aggregator program, which is settled for the whole batch, and the
+    /// aggregated proof when the prover client hands it out.
+    async fn store_aggregator_outputs(
+        aggregator_metadata: &AggregatorMetadata,
+        task_id: &str,
+        internal_id: &str,
+        config: Arc<Config>,
+    ) -> Result<(), JobError> {
+        let aggregator_output_path = aggregator_metadata
+            .aggregator_output_path
+            .as_ref()
+            .ok_or_else(|| AggregatorError::AggregatorOutputPathMissing { internal_id: internal_id.to_string() })?;
+        let aggregator_output = config
+            .prover_client()
+            .get_aggregator_output(task_id)
+            .await
+            .wrap_err("Prover Client Error".to_string())
+            .map_err(|e| JobError::Other(OtherError(e)))?;
+        let aggregator_output = bincode::serialize(&aggregator_output)
+            .map_err(|e| AggregatorError::AggregatorOutputStorageFailed(e.to_string()))?;
+        config
+            .storage()
+            .put_data(Bytes::from(aggregator_output), aggregator_output_path)
+            .await
+            .map_err(|e| AggregatorError::AggregatorOutputStorageFailed(e.to_string()))?;
+
+        if let Some(download_path) = &aggregator_metadata.download_proof {
+            let proof = config
+                .prover_client()
+                .get_proof(task_id)
+                .await
+                .wrap_err("Prover Client Error".to_string())
+                .map_err(|e| JobError::Other(OtherError(e)))?;
+            match proof {
+                Some(proof) => {
+                    config
+                        .storage()
+                        .put_data(Bytes::from(proof), download_path)
+                        .await
+                        .map_err(|e| AggregatorError::ProofStorageFailed(e.to_string()))?;
+                }
+                None => {
+                    tracing::debug!(batch_no = %internal_id, "Prover client does not hand out proofs, nothing to store")
+                }
+            }
+        }
+        Ok(())
+    }
+}
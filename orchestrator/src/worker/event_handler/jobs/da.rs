@@ -1,3 +1,4 @@
+use crate::cli::service::L1DataAvailabilityMode;
 use crate::core::config::Config;
 use crate::error::job::da_error::DaError;
 use crate::error::job::JobError;
@@ -25,6 +26,9 @@ use std::sync::Arc;
 
 pub struct DAJobHandler;
 
+/// External id of the DA jobs in calldata DA mode, where the state diff is published by the state update job.
+pub const CALLDATA_DA_EXTERNAL_ID: &str = "calldata";
+
 lazy_static! {
     /// EIP-4844 BLS12-381 modulus.
     ///
@@ -241,6 +245,19 @@ impl DAJobHandler {
         Ok(blobs)
     }
 
+    /// Splits the state diff into blobs of at most `blob_size` bytes for an external DA layer. Each felt is
+    /// encoded on 32 bytes and the last blob is not padded, so the published data is exactly the state diff.
+    fn data_to_external_blobs(blob_size: u64, block_data: &[BigUint]) -> Result<Vec<Vec<u8>>, JobError> {
+        if blob_size < 32 {
+            Err(DaError::InsufficientBlobSize { blob_size })?
+        }
+
+        let bytes = biguint_vec_to_u8_vec(block_data);
+        // Blobs are cut on felt boundaries.
+        let chunk_size = (blob_size as usize / 32) * 32;
+        Ok(bytes.chunks(chunk_size).map(|chunk| chunk.to_vec()).collect())
+    }
+
     /// To store the blob data using the storage client with path <block_number>/blob_data.txt
     async fn store_blob_data(
         blob_data: Vec<BigUint>,
@@ -308,24 +325,27 @@ impl JobHandlerTrait for DAJobHandler {
         let blob_data_biguint = Self::convert_to_biguint(blob_data.clone());
         tracing::trace!(job_id = ?job.id, "Converted blob data to BigUint");
 
-        let transformed_data = Self::fft_transformation(blob_data_biguint)
-            .wrap_err("Failed to apply FFT transformation")
-            .map_err(|e| {
-                tracing::error!(job_id = ?job.id, error = ?e, "Failed to apply FFT transformation");
-                JobError::Other(OtherError(e))
-            })?;
-        tracing::trace!(job_id = ?job.id, "Applied FFT transformation");
-
         // Get blob data path from metadata
         let blob_data_path = da_metadata.blob_data_path.as_ref().ok_or_else(|| {
             tracing::error!(job_id = ?job.id, "Blob data path not found in metadata");
             JobError::Other(OtherError(eyre!("Blob data path not found in metadata")))
         })?;
 
-        // Store the transformed data
-        Self::store_blob_data(transformed_data.clone(), blob_data_path, config.clone()).await?;
-        tracing::debug!(job_id = ?job.id, "Stored blob data");
+        if config.service_config().l1_da_mode == L1DataAvailabilityMode::Calldata {
+            // The state diff is published as calldata by the state update job, it is stored without the FFT.
+            Self::store_blob_data(blob_data_biguint, blob_data_path, config.clone()).await?;
+            tracing::info!(
+                log_type = "completed",
+                category = "da",
+                function_type = "process_job",
+                job_id = ?job.id,
+                block_no = %internal_id,
+                "Stored state diff for calldata DA."
+            );
+            return Ok(CALLDATA_DA_EXTERNAL_ID.to_string());
+        }
 
+        let l1_da_mode = config.service_config().l1_da_mode;
         let max_bytes_per_blob = config.da_client().max_bytes_per_blob().await;
         let max_blob_per_txn = config.da_client().max_blob_per_txn().await;
         tracing::trace!(
@@ -335,7 +355,28 @@ impl JobHandlerTrait for DAJobHandler {
             "Retrieved DA client configuration"
         );
 
-        let blob_array = Self::data_to_blobs(max_bytes_per_blob, transformed_data)?;
+        let publishes_raw_data =
+            matches!(l1_da_mode, L1DataAvailabilityMode::External | L1DataAvailabilityMode::Validium);
+        let blob_array = if publishes_raw_data {
+            // External DA layers and committees take the state diff as is, the FFT is only needed for EIP-4844 blobs.
+            Self::store_blob_data(blob_data_biguint.clone(), blob_data_path, config.clone()).await?;
+            tracing::debug!(job_id = ?job.id, "Stored blob data");
+            Self::data_to_external_blobs(max_bytes_per_blob, &blob_data_biguint)?
+        } else {
+            let transformed_data = Self::fft_transformation(blob_data_biguint)
+                .wrap_err("Failed to apply FFT transformation")
+                .map_err(|e| {
+                    tracing::error!(job_id = ?job.id, error = ?e, "Failed to apply FFT transformation");
+                    JobError::Other(OtherError(e))
+                })?;
+            tracing::trace!(job_id = ?job.id, "Applied FFT transformation");
+
+            // Store the transformed data
+            Self::store_blob_data(transformed_data.clone(), blob_data_path, config.clone()).await?;
+            tracing::debug!(job_id = ?job.id, "Stored blob data");
+
+            Self::data_to_blobs(max_bytes_per_blob, transformed_data)?
+        };
         let current_blob_length: u64 = blob_array
             .len()
             .try_into()
@@ -368,6 +409,22 @@ impl JobHandlerTrait for DAJobHandler {
             JobError::Other(OtherError(e))
         })?;
 
+        if publishes_raw_data {
+            let commitment = config.da_client().da_commitment(&external_id).await.map_err(|e| {
+                tracing::error!(job_id = ?job.id, error = ?e, "Failed to get the DA commitment");
+                JobError::Other(OtherError(e))
+            })?;
+            let commitment = commitment.ok_or_else(|| {
+                JobError::Other(OtherError(eyre!("DA layer returned no commitment for external id {}", external_id)))
+            })?;
+            da_metadata.da_commitment = Some(hex::encode(commitment));
+        } else {
+            let blob_versioned_hashes = config.da_client().blob_versioned_hashes(&external_id).await.map_err(|e| {
+                tracing::error!(job_id = ?job.id, error = ?e, "Failed to get the versioned hashes of the blobs");
+                JobError::Other(OtherError(e))
+            })?;
+            da_metadata.blob_versioned_hashes = blob_versioned_hashes.iter().map(hex::encode).collect();
+        }
         da_metadata.tx_hash = Some(external_id.clone());
         job.metadata.specific = JobSpecificMetadata::Da(da_metadata);
 
@@ -391,6 +448,10 @@ impl JobHandlerTrait for DAJobHandler {
             function_type = "verify_job", job_id = ?job.id,
             block_no = %internal_id, "DA job verification started."
         );
+        if job.external_id.unwrap_string().is_ok_and(|external_id| external_id == CALLDATA_DA_EXTERNAL_ID) {
+            tracing::info!(log_type = "completed", category = "da", function_type = "verify_job", job_id = ?job.id, block_no = %internal_id, "Nothing to verify for calldata DA.");
+            return Ok(JobVerificationStatus::Verified);
+        }
         let verification_status = config
             .da_client()
             .verify_inclusion(job.external_id.unwrap_string().map_err(|e| {
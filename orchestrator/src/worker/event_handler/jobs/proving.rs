@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bytes::Bytes;
 use cairo_vm::vm::runners::cairo_pie::CairoPie;
 use chrono::{SubsecRound, Utc};
 use color_eyre::eyre::{eyre, WrapErr};
@@ -164,7 +165,26 @@ impl JobHandlerTrait for ProvingJobHandler {
                         "Downloading and storing proof to path: {}",
                         download_path
                     );
-                    // TODO: Implement proof download and storage
+                    let proof = config
+                        .prover_client()
+                        .get_proof(&task_id)
+                        .await
+                        .wrap_err("Prover Client Error".to_string())
+                        .map_err(|e| {
+                            tracing::error!(job_id = %job.internal_id, error = %e, "Failed to get proof from prover client");
+                            JobError::Other(OtherError(e))
+                        })?;
+                    match proof {
+                        Some(proof) => {
+                            config.storage().put_data(Bytes::from(proof), &download_path).await.map_err(|e| {
+                                tracing::error!(job_id = %job.internal_id, error = %e, "Failed to store proof");
+                                ProvingError::ProofStorageFailed(e.to_string())
+                            })?;
+                        }
+                        None => {
+                            tracing::debug!(job_id = %job.internal_id, "Prover client does not hand out proofs, nothing to store")
+                        }
+                    }
                 }
 
                 tracing::info!(
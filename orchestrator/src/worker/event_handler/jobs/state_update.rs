@@ -1,20 +1,24 @@
 use std::sync::Arc;
 
+use crate::cli::service::L1DataAvailabilityMode;
 use crate::core::config::Config;
 use crate::error::job::state_update::StateUpdateError;
 use crate::error::job::JobError;
 use crate::error::other::OtherError;
 use crate::types::jobs::job_item::JobItem;
-use crate::types::jobs::metadata::{JobMetadata, JobSpecificMetadata, StateUpdateMetadata};
+use crate::types::jobs::job_updates::JobItemUpdates;
+use crate::types::jobs::metadata::{AggregatedSettlement, JobMetadata, JobSpecificMetadata, StateUpdateMetadata};
 use crate::types::jobs::status::JobVerificationStatus;
 use crate::types::jobs::types::{JobStatus, JobType};
 use crate::utils::helpers::JobProcessingState;
 use crate::worker::event_handler::jobs::JobHandlerTrait;
 use crate::worker::utils::{fetch_blob_data_for_block, fetch_program_output_for_block, fetch_snos_for_block};
+use alloy::primitives::{keccak256, U256};
 use async_trait::async_trait;
 use cairo_vm::Felt252;
 use chrono::{SubsecRound, Utc};
 use color_eyre::eyre::eyre;
+use orchestrator_da_client_interface::DaVerificationStatus;
 use orchestrator_settlement_client_interface::SettlementVerificationStatus;
 use orchestrator_utils::collections::{has_dup, is_sorted};
 use starknet_os::io::output::StarknetOsOutput;
@@ -86,6 +90,11 @@ impl JobHandlerTrait for StateUpdateJobHandler {
 
         self.validate_block_numbers(config.clone(), &state_metadata.blocks_to_settle).await?;
 
+        if let Some(aggregation) = state_metadata.aggregation.clone() {
+            return self.settle_batch(config, job, state_metadata, aggregation).await;
+        }
+        self.ensure_blobs_published(config.clone(), &state_metadata).await?;
+
         // Filter block numbers if there was a previous failure
         let last_failed_block = state_metadata.last_failed_block_no.unwrap_or(0);
         let filtered_indices: Vec<usize> = state_metadata
@@ -103,6 +112,7 @@ impl JobHandlerTrait for StateUpdateJobHandler {
         let mut nonce = config.settlement_client().get_nonce().await.map_err(|e| JobError::Other(OtherError(e)))?;
 
         let mut sent_tx_hashes: Vec<String> = Vec::with_capacity(filtered_indices.len());
+        state_metadata.calldata_tx_hashes.resize(state_metadata.blocks_to_settle.len(), Vec::new());
 
         for &i in &filtered_indices {
             let block_no = state_metadata.blocks_to_settle[i];
@@ -110,8 +120,27 @@ impl JobHandlerTrait for StateUpdateJobHandler {
             let snos = fetch_snos_for_block(internal_id.clone(), i, config.clone(), &snos_output_paths).await?;
             let program_output = fetch_program_output_for_block(i, config.clone(), &program_output_paths).await?;
             let blob_data = fetch_blob_data_for_block(i, config.clone(), &blob_data_paths).await?;
+            let da_tx_hash = state_metadata.da_tx_hashes.get(i).cloned().flatten();
+            let da_commitment = state_metadata.da_commitments.get(i).cloned().flatten();
+            let blob_versioned_hashes = Self::parse_blob_versioned_hashes(
+                block_no,
+                state_metadata.blob_versioned_hashes.get(i).map(Vec::as_slice).unwrap_or_default(),
+            )?;
             let txn_hash = match self
-                .update_state_for_block(config.clone(), block_no, snos, nonce, program_output, blob_data)
+                .update_state_for_block(
+                    config.clone(),
+                    block_no,
+                    snos,
+                    nonce,
+                    program_output,
+                    blob_data,
+                    da_tx_hash,
+                    da_commitment,
+                    blob_versioned_hashes,
+                    job,
+                    &mut state_metadata,
+                    i,
+                )
                 .await
             {
                 Ok(hash) => hash,
@@ -339,6 +368,66 @@ impl StateUpdateJobHandler {
         Ok(())
     }
 
+    /// Ensure the blobs which were published on their own by the DA layer are still included, as the DA
+    /// transactions could have been reorged out since the DA jobs were verified.
+    async fn ensure_blobs_published(
+        &self,
+        config: Arc<Config>,
+        state_metadata: &StateUpdateMetadata,
+    ) -> Result<(), JobError> {
+        for (block_no, da_tx_hash) in state_metadata.blocks_to_settle.iter().zip(&state_metadata.da_tx_hashes) {
+            let Some(da_tx_hash) = da_tx_hash else { continue };
+            let status =
+                config.da_client().verify_inclusion(da_tx_hash).await.map_err(|e| JobError::Other(OtherError(e)))?;
+            if status != DaVerificationStatus::Verified {
+                Err(StateUpdateError::BlobsNotPublished {
+                    block_no: *block_no,
+                    da_tx_hash: da_tx_hash.clone(),
+                    status: format!("{status:?}"),
+                })?
+            }
+        }
+        Ok(())
+    }
+
+    /// Settle all the blocks of a batch with a single state update, sending the output of the aggregator
+    /// program along with the blobs of the squashed state diff of the batch.
+    async fn settle_batch(
+        &self,
+        config: Arc<Config>,
+        job: &mut JobItem,
+        mut state_metadata: StateUpdateMetadata,
+        aggregation: AggregatedSettlement,
+    ) -> Result<String, JobError> {
+        let batch_num = aggregation.batch_num;
+        let program_output =
+            fetch_program_output_for_block(0, config.clone(), &[aggregation.aggregator_output_path]).await?;
+        let blob_data = fetch_blob_data_for_block(0, config.clone(), &[aggregation.blob_data_path]).await?;
+
+        let settlement_client = config.settlement_client();
+        let nonce = settlement_client.get_nonce().await.map_err(|e| JobError::Other(OtherError(e)))?;
+        let txn_hash =
+            settlement_client.update_state_with_blobs(program_output, blob_data, nonce).await.map_err(|e| {
+                tracing::error!(job_id = %job.internal_id, batch_num = %batch_num, error = %e, "Error updating state for batch");
+                JobError::Other(OtherError(eyre!("Batch #{batch_num} - Error occurred during the state update: {e}")))
+            })?;
+
+        state_metadata.tx_hashes = vec![txn_hash];
+        job.metadata.specific = JobSpecificMetadata::StateUpdate(state_metadata.clone());
+
+        let val = state_metadata.blocks_to_settle.last().ok_or_else(|| StateUpdateError::LastNumberReturnedError)?;
+        tracing::info!(
+            log_type = "completed",
+            category = "state_update",
+            function_type = "process_job",
+            job_id = %job.id,
+            batch_num = %batch_num,
+            last_settled_block = %val,
+            "Batch settled with the aggregated proof."
+        );
+        Ok(val.to_string())
+    }
+
     /// Update the state for the corresponding block using the settlement layer.
     #[allow(clippy::too_many_arguments)]
     async fn update_state_for_block(
@@ -349,18 +438,206 @@ impl StateUpdateJobHandler {
         nonce: u64,
         program_output: Vec<[u8; 32]>,
         blob_data: Vec<Vec<u8>>,
+        da_tx_hash: Option<String>,
+        da_commitment: Option<String>,
+        blob_versioned_hashes: Vec<[u8; 32]>,
+        job: &mut JobItem,
+        state_metadata: &mut StateUpdateMetadata,
+        block_index: usize,
     ) -> Result<String, JobError> {
         let settlement_client = config.settlement_client();
+        let l1_da_mode = config.service_config().l1_da_mode;
         let last_tx_hash_executed = if snos.use_kzg_da == Felt252::ZERO {
-            unimplemented!("update_state_for_block not implemented as of now for calldata DA.")
+            match l1_da_mode {
+                L1DataAvailabilityMode::Calldata => {
+                    self.update_state_with_calldata(
+                        config.clone(),
+                        block_no,
+                        program_output,
+                        blob_data,
+                        job,
+                        state_metadata,
+                        block_index,
+                    )
+                    .await?
+                }
+                L1DataAvailabilityMode::External => {
+                    self.update_state_with_da_commitment(
+                        config.clone(),
+                        block_no,
+                        program_output,
+                        blob_data,
+                        da_commitment,
+                    )
+                    .await?
+                }
+                L1DataAvailabilityMode::Blob | L1DataAvailabilityMode::Validium => {
+                    Err(StateUpdateError::DaModeMismatch { block_no, l1_da_mode })?
+                }
+            }
         } else if snos.use_kzg_da == Felt252::ONE {
-            settlement_client
-                .update_state_with_blobs(program_output, blob_data, nonce)
-                .await
-                .map_err(|e| JobError::Other(OtherError(e)))?
+            match l1_da_mode {
+                // The blobs published by the DA client are referenced by their versioned hashes.
+                L1DataAvailabilityMode::Blob if !blob_versioned_hashes.is_empty() => settlement_client
+                    .update_state_with_published_blobs(program_output, blob_data, blob_versioned_hashes, nonce)
+                    .await
+                    .map_err(|e| JobError::Other(OtherError(e)))?,
+                L1DataAvailabilityMode::Blob => settlement_client
+                    .update_state_with_blobs(program_output, blob_data, nonce)
+                    .await
+                    .map_err(|e| JobError::Other(OtherError(e)))?,
+                L1DataAvailabilityMode::Validium => {
+                    self.update_state_with_dac_attestation(
+                        config.clone(),
+                        block_no,
+                        program_output,
+                        blob_data,
+                        da_tx_hash,
+                        da_commitment,
+                    )
+                    .await?
+                }
+                L1DataAvailabilityMode::Calldata | L1DataAvailabilityMode::External => {
+                    Err(StateUpdateError::DaModeMismatch { block_no, l1_da_mode })?
+                }
+            }
         } else {
             Err(StateUpdateError::UseKZGDaError { block_no })?
         };
         Ok(last_tx_hash_executed)
     }
+
+    /// Publish the state diff as calldata, then update the state referencing the published data by its hash
+    /// and its size in felts. The transaction hash of each chunk is persisted in the job metadata as soon as the
+    /// chunk is included, so the chunks already published by a previous attempt are not sent again.
+    #[allow(clippy::too_many_arguments)]
+    async fn update_state_with_calldata(
+        &self,
+        config: Arc<Config>,
+        block_no: u64,
+        program_output: Vec<[u8; 32]>,
+        state_diff: Vec<Vec<u8>>,
+        job: &mut JobItem,
+        state_metadata: &mut StateUpdateMetadata,
+        block_index: usize,
+    ) -> Result<String, JobError> {
+        let settlement_client = config.settlement_client();
+        let onchain_data_size = Self::state_diff_size(block_no, &state_diff)?;
+        let state_diff = state_diff.concat();
+        let felts: Vec<[u8; 32]> =
+            state_diff.chunks_exact(32).map(|felt| felt.try_into().expect("Chunks are 32 bytes long")).collect();
+
+        while let Some(tx_hash) = settlement_client
+            .publish_state_diff_calldata(felts.clone(), state_metadata.calldata_tx_hashes[block_index].len())
+            .await
+            .map_err(|e| JobError::Other(OtherError(e)))?
+        {
+            state_metadata.calldata_tx_hashes[block_index].push(tx_hash);
+            Self::persist_metadata(config.clone(), job, state_metadata).await?;
+        }
+        tracing::debug!(
+            block_no = %block_no,
+            data_tx_hashes = ?state_metadata.calldata_tx_hashes[block_index],
+            "State diff published as calldata"
+        );
+
+        settlement_client
+            .update_state_calldata(program_output.clone(), program_output, keccak256(&state_diff).0, onchain_data_size)
+            .await
+            .map_err(|e| JobError::Other(OtherError(e)))
+    }
+
+    /// Update the state referencing the state diff published to an external DA layer by its commitment and its
+    /// size in felts, the data itself is not sent to the settlement layer.
+    async fn update_state_with_da_commitment(
+        &self,
+        config: Arc<Config>,
+        block_no: u64,
+        program_output: Vec<[u8; 32]>,
+        state_diff: Vec<Vec<u8>>,
+        da_commitment: Option<String>,
+    ) -> Result<String, JobError> {
+        let onchain_data_hash = Self::parse_da_commitment(block_no, da_commitment)?;
+        let onchain_data_size = Self::state_diff_size(block_no, &state_diff)?;
+
+        config
+            .settlement_client()
+            .update_state_calldata(program_output.clone(), program_output, onchain_data_hash, onchain_data_size)
+            .await
+            .map_err(|e| JobError::Other(OtherError(e)))
+    }
+
+    /// Update the state along with the attestation of the data availability committee for the state diff,
+    /// referencing the state diff by its hash and its size in felts.
+    async fn update_state_with_dac_attestation(
+        &self,
+        config: Arc<Config>,
+        block_no: u64,
+        program_output: Vec<[u8; 32]>,
+        state_diff: Vec<Vec<u8>>,
+        da_tx_hash: Option<String>,
+        da_commitment: Option<String>,
+    ) -> Result<String, JobError> {
+        let onchain_data_hash = Self::parse_da_commitment(block_no, da_commitment)?;
+        let onchain_data_size = Self::state_diff_size(block_no, &state_diff)?;
+
+        let da_tx_hash = da_tx_hash.ok_or(StateUpdateError::MissingDaAttestation { block_no })?;
+        let attestation = config
+            .da_client()
+            .da_attestation(&da_tx_hash)
+            .await
+            .map_err(|e| JobError::Other(OtherError(e)))?
+            .ok_or(StateUpdateError::MissingDaAttestation { block_no })?;
+
+        config
+            .settlement_client()
+            .update_state_with_dac_attestation(program_output, onchain_data_hash, onchain_data_size, attestation)
+            .await
+            .map_err(|e| JobError::Other(OtherError(e)))
+    }
+
+    /// Write the state update metadata to the job and persist it, so that the progress survives a crash of the
+    /// orchestrator before the job is processed.
+    async fn persist_metadata(
+        config: Arc<Config>,
+        job: &mut JobItem,
+        state_metadata: &StateUpdateMetadata,
+    ) -> Result<(), JobError> {
+        job.metadata.specific = JobSpecificMetadata::StateUpdate(state_metadata.clone());
+        *job = config
+            .database()
+            .update_job(job, JobItemUpdates::new().update_metadata(job.metadata.clone()).build())
+            .await?;
+        Ok(())
+    }
+
+    fn parse_blob_versioned_hashes(block_no: u64, blob_versioned_hashes: &[String]) -> Result<Vec<[u8; 32]>, JobError> {
+        blob_versioned_hashes
+            .iter()
+            .map(|hash| {
+                hex::decode(hash.trim_start_matches("0x")).ok().and_then(|hash| hash.try_into().ok()).ok_or_else(|| {
+                    JobError::Other(OtherError(eyre!("Block #{block_no} - Invalid blob versioned hash {hash}")))
+                })
+            })
+            .collect()
+    }
+
+    fn parse_da_commitment(block_no: u64, da_commitment: Option<String>) -> Result<[u8; 32], JobError> {
+        let da_commitment = da_commitment.ok_or(StateUpdateError::MissingDaCommitment { block_no })?;
+        hex::decode(da_commitment.trim_start_matches("0x"))
+            .ok()
+            .and_then(|commitment| commitment.try_into().ok())
+            .ok_or_else(|| {
+                JobError::Other(OtherError(eyre!("Block #{block_no} - Invalid DA commitment {da_commitment}")))
+            })
+    }
+
+    /// Size of the state diff in felts, as expected by the core contract.
+    fn state_diff_size(block_no: u64, state_diff: &[Vec<u8>]) -> Result<[u8; 32], JobError> {
+        let state_diff_len = state_diff.concat().len();
+        if state_diff_len % 32 != 0 {
+            Err(StateUpdateError::InvalidStateDiffLength { block_no, len: state_diff_len })?
+        }
+        Ok(U256::from(state_diff_len / 32).to_be_bytes::<32>())
+    }
 }
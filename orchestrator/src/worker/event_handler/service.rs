@@ -19,6 +19,7 @@ use crate::types::jobs::WorkerTriggerType;
 use crate::utils::metrics::ORCHESTRATOR_METRICS;
 #[double]
 use crate::worker::event_handler::factory::factory;
+use crate::worker::event_handler::triggers::aggregator::AggregatorJobTrigger;
 use crate::worker::event_handler::triggers::batching::BatchingTrigger;
 use crate::worker::event_handler::triggers::data_submission_worker::DataSubmissionJobTrigger;
 use crate::worker::event_handler::triggers::proof_registration::ProofRegistrationJobTrigger;
@@ -528,8 +529,8 @@ impl JobHandlerService {
         .await
     }
 
-    /// Retries a failed job by reprocessing it.
-    /// Only jobs with Failed status can be retried.
+    /// Retries a failed or cancelled job by reprocessing it.
+    /// Only jobs with Failed or Cancelled status can be retried.
     ///
     /// # Arguments
     /// * `id` - UUID of the job to retry
@@ -539,10 +540,11 @@ impl JobHandlerService {
     /// * `Result<(), JobError>` - Success or an error
     ///
     /// # State Transitions
-    /// * `Failed` -> `PendingRetry` -> (normal processing flow)
+    /// * `Failed` | `Cancelled` -> `PendingRetry` -> (normal processing flow)
     ///
     /// # Notes
-    /// * Only jobs in Failed status can be retried
+    /// * Only jobs in Failed or Cancelled status can be retried. The workers never recreate a cancelled job,
+    ///   retrying it is the way to resume its block
     /// * Transitions through PendingRetry status before normal processing
     /// * Uses standard process_job function after status update
     #[tracing::instrument(skip(config), fields(category = "general"), ret, err)]
@@ -557,7 +559,7 @@ impl JobHandlerService {
             block_no = %internal_id,
             "General retry job started for block"
         );
-        if job.status != JobStatus::Failed {
+        if !matches!(job.status, JobStatus::Failed | JobStatus::Cancelled) {
             tracing::error!(
                 job_id = ?id,
                 status = ?job.status,
@@ -620,6 +622,69 @@ impl JobHandlerService {
         Ok(())
     }
 
+    /// Cancels a job so that it is never processed or verified again.
+    /// Completed jobs can't be cancelled.
+    ///
+    /// # Arguments
+    /// * `id` - UUID of the job to cancel
+    /// * `config` - Shared configuration
+    ///
+    /// # Returns
+    /// * `Result<(), JobError>` - Success or an error
+    ///
+    /// # State Transitions
+    /// * Any status except `Completed` and `Cancelled` -> `Cancelled`
+    ///
+    /// # Notes
+    /// * Messages still queued for the job fail on the status check and are dropped
+    /// * The job keeps its internal id, so the workers won't create a new job for it. The jobs depending on it
+    ///   wait until it is retried with [`JobHandlerService::retry_job`]
+    #[tracing::instrument(skip(config), fields(category = "general"), ret, err)]
+    pub async fn cancel_job(id: Uuid, config: Arc<Config>) -> Result<(), JobError> {
+        let job = JobService::get_job(id, config.clone()).await?;
+        let internal_id = job.internal_id.clone();
+
+        tracing::info!(
+            log_type = "starting",
+            category = "general",
+            function_type = "cancel_job",
+            block_no = %internal_id,
+            "General cancel job started for block"
+        );
+        if matches!(job.status, JobStatus::Completed | JobStatus::Cancelled) {
+            tracing::error!(
+                job_id = ?id,
+                status = ?job.status,
+                "Cannot cancel job: invalid status"
+            );
+            return Err(JobError::InvalidStatus { id, job_status: job.status });
+        }
+
+        // The optimistic lock on the version fails the update if a worker changed the job in the meantime
+        config
+            .database()
+            .update_job(&job, JobItemUpdates::new().update_status(JobStatus::Cancelled).build())
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    job_id = ?id,
+                    error = ?e,
+                    "Failed to update job status to Cancelled"
+                );
+                e
+            })?;
+
+        tracing::info!(
+            log_type = "completed",
+            category = "general",
+            function_type = "cancel_job",
+            block_no = %internal_id,
+            "Successfully cancelled job"
+        );
+
+        Ok(())
+    }
+
     fn register_block_gauge(
         job_type: JobType,
         internal_id: &str,
@@ -648,6 +713,7 @@ impl JobHandlerService {
             WorkerTriggerType::ProofRegistration => Box::new(ProofRegistrationJobTrigger),
             WorkerTriggerType::UpdateState => Box::new(UpdateStateJobTrigger),
             WorkerTriggerType::Batching => Box::new(BatchingTrigger),
+            WorkerTriggerType::Aggregator => Box::new(AggregatorJobTrigger),
         }
     }
 }
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::eyre::eyre;
+use opentelemetry::KeyValue;
+
+use crate::core::config::Config;
+use crate::types::batch::Batch;
+use crate::types::constant::{BLOB_DATA_FILE_NAME, PROGRAM_OUTPUT_FILE_NAME, PROOF_FILE_NAME, STORAGE_AGGREGATOR_DIR};
+use crate::types::jobs::metadata::{
+    AggregatorMetadata, CommonMetadata, JobMetadata, JobSpecificMetadata, SnosMetadata,
+};
+use crate::types::jobs::types::{JobStatus, JobType};
+use crate::utils::metrics::ORCHESTRATOR_METRICS;
+use crate::worker::event_handler::service::JobHandlerService;
+use crate::worker::event_handler::triggers::JobTrigger;
+
+pub struct AggregatorJobTrigger;
+
+#[async_trait]
+impl JobTrigger for AggregatorJobTrigger {
+    fn created_job_type(&self) -> Option<JobType> {
+        Some(JobType::Aggregator)
+    }
+
+    /// 1. Find the first batch without an aggregator job
+    /// 2. Create an aggregator job for it once it is ready and all of its blocks are proven
+    /// 3. Repeat with the following batches
+    async fn run_worker(&self, config: Arc<Config>) -> color_eyre::Result<()> {
+        if !config.service_config().proof_aggregation {
+            return Ok(());
+        }
+        tracing::info!(log_type = "starting", category = "AggregatorWorker", "AggregatorWorker started.");
+
+        let latest_job = config.database().get_latest_job_by_type(JobType::Aggregator).await?;
+        // Batch indexes start at 1, see the batching worker
+        let mut batch_index = match latest_job {
+            Some(job) => job.internal_id.parse::<u64>()? + 1,
+            None => 1,
+        };
+
+        while let Some(batch) = config.database().get_batch_by_index(batch_index).await? {
+            if !batch.is_batch_ready {
+                tracing::debug!(batch_index = %batch_index, "Batch is still open, waiting for it to be ready");
+                break;
+            }
+            let Some(aggregator_metadata) = Self::aggregator_metadata(&batch, config.clone()).await? else {
+                tracing::debug!(batch_index = %batch_index, "Blocks of the batch are not all proven yet");
+                break;
+            };
+
+            let metadata = JobMetadata {
+                common: CommonMetadata::default(),
+                specific: JobSpecificMetadata::Aggregator(aggregator_metadata),
+            };
+            match JobHandlerService::create_job(JobType::Aggregator, batch_index.to_string(), metadata, config.clone())
+                .await
+            {
+                Ok(_) => tracing::info!(batch_index = %batch_index, "Successfully created new aggregator job"),
+                Err(e) => {
+                    tracing::warn!(batch_index = %batch_index, error = %e, "Failed to create new aggregator job");
+                    let attributes = [
+                        KeyValue::new("operation_job_type", format!("{:?}", JobType::Aggregator)),
+                        KeyValue::new("operation_type", format!("{:?}", "create_job")),
+                    ];
+                    ORCHESTRATOR_METRICS.failed_job_operations.add(1.0, &attributes);
+                    return Err(e.into());
+                }
+            }
+            batch_index += 1;
+        }
+
+        tracing::trace!(log_type = "completed", category = "AggregatorWorker", "AggregatorWorker completed.");
+        Ok(())
+    }
+}
+
+impl AggregatorJobTrigger {
+    /// Collects the proving task and the program output of each block of the batch. Returns `None` while some
+    /// of the blocks are not proven.
+    async fn aggregator_metadata(batch: &Batch, config: Arc<Config>) -> color_eyre::Result<Option<AggregatorMetadata>> {
+        let mut proving_task_ids = Vec::new();
+        let mut program_output_paths = Vec::new();
+
+        for block_number in batch.start_block..=batch.end_block {
+            let proving_job = config
+                .database()
+                .get_job_by_internal_id_and_type(&block_number.to_string(), &JobType::ProofCreation)
+                .await?;
+            let proving_job = match proving_job {
+                Some(job) if job.status == JobStatus::Completed => job,
+                _ => return Ok(None),
+            };
+            proving_task_ids.push(proving_job.external_id.unwrap_string()?.to_string());
+
+            let snos_job = config
+                .database()
+                .get_job_by_internal_id_and_type(&block_number.to_string(), &JobType::SnosRun)
+                .await?
+                .ok_or_else(|| eyre!("SNOS job not found for block {}", block_number))?;
+            let snos_metadata: SnosMetadata = snos_job.metadata.specific.try_into().map_err(|e| {
+                tracing::error!(job_id = %snos_job.internal_id, error = %e, "Invalid metadata type for SNOS job");
+                e
+            })?;
+            let program_output_path = snos_metadata
+                .program_output_path
+                .ok_or_else(|| eyre!("Program output path not found for block {}", block_number))?;
+            program_output_paths.push(program_output_path);
+        }
+
+        let batch_dir = format!("{}/{}", STORAGE_AGGREGATOR_DIR, batch.index);
+        Ok(Some(AggregatorMetadata {
+            batch_num: batch.index,
+            start_block: batch.start_block,
+            end_block: batch.end_block,
+            proving_task_ids,
+            program_output_paths,
+            squashed_state_update_path: batch.squashed_state_updates_path.clone(),
+            blob_data_path: Some(format!("{}/{}", batch_dir, BLOB_DATA_FILE_NAME)),
+            aggregator_output_path: Some(format!("{}/{}", batch_dir, PROGRAM_OUTPUT_FILE_NAME)),
+            download_proof: Some(format!("{}/{}", batch_dir, PROOF_FILE_NAME)),
+        }))
+    }
+}
@@ -3,6 +3,7 @@ use crate::error::job::JobError;
 use crate::error::other::OtherError;
 use crate::types::batch::{Batch, BatchUpdates};
 use crate::types::constant::{MAX_BATCH_SIZE, STORAGE_STATE_UPDATE_DIR};
+use crate::types::jobs::types::JobType;
 use crate::worker::event_handler::triggers::JobTrigger;
 use bytes::Bytes;
 use color_eyre::eyre::eyre;
@@ -20,6 +21,11 @@ pub struct BatchingTrigger;
 
 #[async_trait::async_trait]
 impl JobTrigger for BatchingTrigger {
+    /// Batches aren't jobs, so pausing a job type never stops batching
+    fn created_job_type(&self) -> Option<JobType> {
+        None
+    }
+
     /// 1. Fetch the latest completed block from Starknet chain
     /// 2. Fetch the last batch and check its `end_block`
     /// 3. Assign batches to all the remaining blocks and store the squashed state update in storage
@@ -13,10 +13,18 @@ pub struct DataSubmissionJobTrigger;
 
 #[async_trait]
 impl JobTrigger for DataSubmissionJobTrigger {
+    fn created_job_type(&self) -> Option<JobType> {
+        Some(JobType::DataSubmission)
+    }
+
     // 0. All ids are assumed to be block numbers.
     // 1. Fetch the latest completed Proving jobs without Data Submission jobs as successor jobs
     // 2. Create jobs.
     async fn run_worker(&self, config: Arc<Config>) -> color_eyre::Result<()> {
+        // The blobs of a batch are sent along with its state update when the proofs are aggregated.
+        if config.service_config().proof_aggregation {
+            return Ok(());
+        }
         tracing::trace!(log_type = "starting", category = "DataSubmissionWorker", "DataSubmissionWorker started.");
 
         let successful_proving_jobs = config
@@ -44,6 +52,8 @@ impl JobTrigger for DataSubmissionJobTrigger {
                     blob_data_path: Some(format!("{}/{BLOB_DATA_FILE_NAME}", proving_metadata.block_number)),
                     // These will be populated during processing
                     tx_hash: None,
+                    da_commitment: None,
+                    blob_versioned_hashes: Vec::new(),
                 }),
             };
 
@@ -1,3 +1,4 @@
+pub(crate) mod aggregator;
 pub(crate) mod batching;
 pub(crate) mod data_submission_worker;
 pub(crate) mod proof_registration;
@@ -6,7 +7,7 @@ pub(crate) mod snos;
 pub(crate) mod update_state;
 
 use crate::core::config::Config;
-use crate::types::jobs::types::JobStatus;
+use crate::types::jobs::types::{JobStatus, JobType};
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -16,11 +17,31 @@ pub trait JobTrigger: Send + Sync {
         if !self.is_worker_enabled(config.clone()).await? {
             return Ok(());
         }
+        if self.is_job_creation_paused(config.clone()).await? {
+            return Ok(());
+        }
         self.run_worker(config).await
     }
 
     async fn run_worker(&self, config: Arc<Config>) -> color_eyre::Result<()>;
 
+    /// The type of the jobs created by this trigger, if it creates any
+    fn created_job_type(&self) -> Option<JobType>;
+
+    /// Checks if an operator paused the creation of the jobs of this trigger
+    async fn is_job_creation_paused(&self, config: Arc<Config>) -> color_eyre::Result<bool> {
+        let Some(job_type) = self.created_job_type() else {
+            return Ok(false);
+        };
+
+        if config.database().get_paused_job_types().await?.contains(&job_type) {
+            tracing::info!(job_type = ?job_type, "Job creation is paused, skipping worker run");
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
     // Assumption
     // If say a job for block X fails, we don't want the worker to respawn another job for the same
     // block we will resolve the existing failed job first.
@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use opentelemetry::KeyValue;
 
 use crate::core::config::Config;
+use crate::types::constant::PROOF_FILE_NAME;
 use crate::types::jobs::metadata::{
     CommonMetadata, JobMetadata, JobSpecificMetadata, ProvingInputType, ProvingMetadata, SnosMetadata,
 };
@@ -16,6 +17,10 @@ pub struct ProvingJobTrigger;
 
 #[async_trait]
 impl JobTrigger for ProvingJobTrigger {
+    fn created_job_type(&self) -> Option<JobType> {
+        Some(JobType::ProofCreation)
+    }
+
     /// 1. Fetch all successful SNOS job runs that don't have a proving job
     /// 2. Create a proving job for each SNOS job run
     async fn run_worker(&self, config: Arc<Config>) -> color_eyre::Result<()> {
@@ -51,8 +56,8 @@ impl JobTrigger for ProvingJobTrigger {
                     block_number: snos_metadata.block_number,
                     // Set input path as CairoPie type
                     input_path: snos_metadata.cairo_pie_path.map(ProvingInputType::CairoPie),
-                    // Set a download path if needed
-                    download_proof: None,
+                    // Store the proof next to the PIE when the prover client hands it out
+                    download_proof: Some(format!("{}/{}", snos_metadata.block_number, PROOF_FILE_NAME)),
                     // Set SNOS fact for on-chain verification
                     ensure_on_chain_registration: Some(snos_fact),
                     n_steps: snos_metadata.snos_n_steps,
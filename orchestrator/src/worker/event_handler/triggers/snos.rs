@@ -55,6 +55,10 @@ struct JobSchedulingContext {
 
 #[async_trait]
 impl JobTrigger for SnosJobTrigger {
+    fn created_job_type(&self) -> Option<JobType> {
+        Some(JobType::SnosRun)
+    }
+
     /// Main entry point for SNOS job creation workflow.
     ///
     /// This method orchestrates the entire job scheduling process:
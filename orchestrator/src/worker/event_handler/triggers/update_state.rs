@@ -6,7 +6,8 @@ use opentelemetry::KeyValue;
 
 use crate::core::config::Config;
 use crate::types::jobs::metadata::{
-    CommonMetadata, DaMetadata, JobMetadata, JobSpecificMetadata, SnosMetadata, StateUpdateMetadata,
+    AggregatedSettlement, AggregatorMetadata, CommonMetadata, DaMetadata, JobMetadata, JobSpecificMetadata,
+    SnosMetadata, StateUpdateMetadata,
 };
 use crate::types::jobs::types::{JobStatus, JobType};
 use crate::utils::metrics::ORCHESTRATOR_METRICS;
@@ -17,9 +18,17 @@ pub struct UpdateStateJobTrigger;
 
 #[async_trait]
 impl JobTrigger for UpdateStateJobTrigger {
+    fn created_job_type(&self) -> Option<JobType> {
+        Some(JobType::StateTransition)
+    }
+
     async fn run_worker(&self, config: Arc<Config>) -> color_eyre::Result<()> {
         tracing::trace!(log_type = "starting", category = "UpdateStateWorker", "UpdateStateWorker started.");
 
+        if config.service_config().proof_aggregation {
+            return self.create_batch_state_update_job(config).await;
+        }
+
         let latest_job = config.database().get_latest_job_by_type(JobType::StateTransition).await?;
         let (completed_da_jobs, last_block_processed_in_last_job) = match latest_job {
             Some(job) => {
@@ -118,10 +127,80 @@ impl JobTrigger for UpdateStateJobTrigger {
             blob_data_paths: Vec::new(),
             last_failed_block_no: None,
             tx_hashes: Vec::new(),
+            da_tx_hashes: Vec::new(),
+            da_commitments: Vec::new(),
+            blob_versioned_hashes: Vec::new(),
+            calldata_tx_hashes: Vec::new(),
+            aggregation: None,
         };
+        Self::add_block_paths(&mut state_metadata, config.clone()).await?;
+        Self::create_state_transition_job(state_metadata, config).await?;
+
+        tracing::trace!(log_type = "completed", category = "UpdateStateWorker", "UpdateStateWorker completed.");
+        Ok(())
+    }
+}
 
+impl UpdateStateJobTrigger {
+    /// With proof aggregation, each batch is settled with a single state update once its aggregator job is
+    /// completed, the batches being settled in order. The blobs of the batch are sent along with the state update,
+    /// there is no DA job for its blocks.
+    async fn create_batch_state_update_job(&self, config: Arc<Config>) -> color_eyre::Result<()> {
+        let latest_job = config.database().get_latest_job_by_type(JobType::StateTransition).await?;
+        // Batch indexes start at 1, see the batching worker
+        let batch_index = match latest_job {
+            Some(job) => {
+                if job.status != JobStatus::Completed {
+                    tracing::warn!("There's already a pending update state job. Returning safely...");
+                    return Ok(());
+                }
+                let state_metadata: StateUpdateMetadata = job.metadata.specific.try_into()?;
+                let aggregation = state_metadata.aggregation.ok_or_else(|| {
+                    eyre!(
+                        "Last state transition job #{} did not settle a batch, proof aggregation must be enabled from \
+                         the first block to process",
+                        job.internal_id
+                    )
+                })?;
+                aggregation.batch_num + 1
+            }
+            None => 1,
+        };
+
+        let aggregator_job =
+            config.database().get_job_by_internal_id_and_type(&batch_index.to_string(), &JobType::Aggregator).await?;
+        let aggregator_job = match aggregator_job {
+            Some(job) if job.status == JobStatus::Completed => job,
+            _ => {
+                tracing::debug!(batch_index = %batch_index, "Aggregator job of the next batch is not completed yet");
+                return Ok(());
+            }
+        };
+        let aggregator_metadata: AggregatorMetadata = aggregator_job.metadata.specific.try_into()?;
+
+        let blocks_to_settle: Vec<u64> = (aggregator_metadata.start_block..=aggregator_metadata.end_block).collect();
+
+        let aggregation = AggregatedSettlement {
+            batch_num: aggregator_metadata.batch_num,
+            aggregator_output_path: aggregator_metadata
+                .aggregator_output_path
+                .ok_or_else(|| eyre!("Aggregator output path not found for batch {}", batch_index))?,
+            blob_data_path: aggregator_metadata
+                .blob_data_path
+                .ok_or_else(|| eyre!("Blob data path not found for batch {}", batch_index))?,
+        };
+        let state_metadata =
+            StateUpdateMetadata { blocks_to_settle, aggregation: Some(aggregation), ..Default::default() };
+        Self::create_state_transition_job(state_metadata, config).await?;
+
+        tracing::trace!(log_type = "completed", category = "UpdateStateWorker", "UpdateStateWorker completed.");
+        Ok(())
+    }
+
+    /// Collects the paths of the SNOS and DA outputs of each block to settle.
+    async fn add_block_paths(state_metadata: &mut StateUpdateMetadata, config: Arc<Config>) -> color_eyre::Result<()> {
         // Collect paths from SNOS and DA jobs
-        for block_number in &blocks_to_process {
+        for block_number in &state_metadata.blocks_to_settle {
             // Get SNOS job paths
             let snos_job = config
                 .database()
@@ -140,7 +219,7 @@ impl JobTrigger for UpdateStateJobTrigger {
                 state_metadata.program_output_paths.push(program_path.clone());
             }
 
-            // Get DA job blob path
+            // Get DA job blob path and the transaction which published the blobs
             let da_job = config
                 .database()
                 .get_job_by_internal_id_and_type(&block_number.to_string(), &JobType::DataSubmission)
@@ -155,7 +234,18 @@ impl JobTrigger for UpdateStateJobTrigger {
             if let Some(blob_path) = &da_metadata.blob_data_path {
                 state_metadata.blob_data_paths.push(blob_path.clone());
             }
+            state_metadata.da_tx_hashes.push(da_metadata.tx_hash);
+            state_metadata.da_commitments.push(da_metadata.da_commitment);
+            state_metadata.blob_versioned_hashes.push(da_metadata.blob_versioned_hashes);
         }
+        Ok(())
+    }
+
+    async fn create_state_transition_job(
+        state_metadata: StateUpdateMetadata,
+        config: Arc<Config>,
+    ) -> color_eyre::Result<()> {
+        let blocks_to_settle = state_metadata.blocks_to_settle.clone();
         // Create job metadata
         let metadata = JobMetadata {
             common: CommonMetadata::default(),
@@ -163,7 +253,7 @@ impl JobTrigger for UpdateStateJobTrigger {
         };
 
         // Create the state transition job
-        let new_job_id = blocks_to_process[0].to_string();
+        let new_job_id = blocks_to_settle[0].to_string();
         match JobHandlerService::create_job(JobType::StateTransition, new_job_id.clone(), metadata, config.clone())
             .await
         {
@@ -179,7 +269,6 @@ impl JobTrigger for UpdateStateJobTrigger {
             }
         }
 
-        tracing::trace!(log_type = "completed", category = "UpdateStateWorker", "UpdateStateWorker completed.");
         Ok(())
     }
 }
@@ -156,7 +156,7 @@ impl JobService {
     /// * `Result<(), JobError>` - Success or an error
     ///
     /// # Notes
-    /// * Skips processing if job is already in Failed status
+    /// * Skips processing if job is already in Failed or Cancelled status
     /// * Records failure reason in job metadata
     /// * Updates metrics for failed jobs
     pub async fn move_job_to_failed(job: &JobItem, config: Arc<Config>, reason: String) -> Result<(), JobError> {
@@ -170,6 +170,11 @@ impl JobService {
             tracing::warn!(job_id = ?job.id, "Job already marked as failed, skipping processing");
             return Ok(());
         }
+        // A cancelled job stays cancelled, any processing still in flight for it is expected to fail
+        else if job.status == JobStatus::Cancelled {
+            tracing::warn!(job_id = ?job.id, "Job was cancelled, skipping processing");
+            return Ok(());
+        }
 
         let mut job_metadata = job.metadata.clone();
         let internal_id = job.internal_id.clone();